@@ -126,6 +126,13 @@ where
             .map_err(Into::into)
     }
 
+    /// Recovers the signer [`Address`] of an arbitrary `message` from its `signature`.
+    fn recover_signer(&self, message: &[u8], signature: &str) -> Result<Address, Error> {
+        self.client
+            .recover_signer(message, signature)
+            .map_err(Into::into)
+    }
+
     /// Creates a `Session` from a provided Token by calling the Contract.
     /// Does **not** cache the (`Token`, [`Session`]) pair.
     async fn session_from_token(&self, token: &str) -> Result<Session, Error> {
@@ -145,4 +152,15 @@ where
             .await
             .map_err(Into::into)
     }
+
+    async fn get_deposit_detailed(
+        &self,
+        channel_context: &ChainOf<Channel>,
+        depositor_address: Address,
+    ) -> Result<DetailedDeposit, Error> {
+        self.client
+            .get_deposit_detailed(channel_context, depositor_address)
+            .await
+            .map_err(Into::into)
+    }
 }
@@ -4,7 +4,7 @@
 //! - [`Unlocked`]
 //! - [`Unlockable`]
 
-use crate::primitives::{Deposit, Session};
+use crate::primitives::{Deposit, DetailedDeposit, Session};
 use async_trait::async_trait;
 use primitives::{Address, ChainId, ChainOf, Channel, ValidatorId};
 
@@ -28,6 +28,11 @@ pub trait Locked: Sync + Send {
         signature: &str,
     ) -> Result<bool, Self::Error>;
 
+    /// Recovers the signer [`Address`] of an arbitrary `message` from its `signature`.
+    ///
+    /// `signature` should be a `0x` prefixed hex string.
+    fn recover_signer(&self, message: &[u8], signature: &str) -> Result<Address, Self::Error>;
+
     /// Creates a `Session` from a provided Token by calling the Contract.
     /// Does **not** cache the (`Token`, `Session`) pair.
     async fn session_from_token(&self, token: &str) -> Result<Session, Self::Error>;
@@ -38,6 +43,20 @@ pub trait Locked: Sync + Send {
         depositor_address: Address,
     ) -> Result<Deposit, Self::Error>;
 
+    /// Same as [`Locked::get_deposit`], alongside the [`DepositBreakdown`](primitives::DepositBreakdown)
+    /// of the deposit if the client supports it.
+    ///
+    /// By default it returns the [`Locked::get_deposit`] without a breakdown.
+    async fn get_deposit_detailed(
+        &self,
+        channel_context: &ChainOf<Channel>,
+        depositor_address: Address,
+    ) -> Result<DetailedDeposit, Self::Error> {
+        self.get_deposit(channel_context, depositor_address)
+            .await
+            .map(DetailedDeposit::from)
+    }
+
     // fn unlock(
     //     &self,
     // ) -> Result<
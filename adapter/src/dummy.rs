@@ -2,8 +2,8 @@
 //!
 use crate::{
     prelude::*,
-    primitives::{Deposit, Session},
-    Error,
+    primitives::{Deposit, Scope, Session},
+    Error, ErrorKind,
 };
 use async_trait::async_trait;
 
@@ -11,7 +11,12 @@ use parse_display::{Display, FromStr};
 use primitives::{
     config::ChainInfo, Address, ChainId, ChainOf, Channel, ToETHChecksum, ValidatorId,
 };
-use std::collections::HashMap;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 #[doc(inline)]
 pub use self::deposit::{Deposits, Key};
@@ -28,6 +33,110 @@ pub struct Options {
     /// The [`ChainInfo`] that will be used for the [`Session`]s and
     /// also for the deposits.
     pub dummy_chains: Vec<ChainInfo>,
+    /// Simulates latency and failures of the adapter calls.
+    pub failure_injection: Option<FailureConfig>,
+}
+
+/// Latency and failures injected in the [`Dummy`] adapter calls.
+///
+/// Used for testing sentry & the validator worker under slow or failing
+/// RPC calls without standing up flaky infrastructure.
+#[derive(Debug, Clone, Default)]
+pub struct FailureConfig {
+    /// The seed for deciding which calls fail.
+    /// When set, the injected failures are reproducible between runs.
+    pub seed: Option<u64>,
+    /// Failures of [`get_deposit()`](Locked::get_deposit)
+    pub get_deposit: Option<MethodFailure>,
+    /// Failures of [`session_from_token()`](Locked::session_from_token)
+    pub session_from_token: Option<MethodFailure>,
+    /// Failures of [`verify()`](Locked::verify) and [`recover_signer()`](Locked::recover_signer)
+    pub verify: Option<MethodFailure>,
+    /// Failures of [`sign()`](Unlocked::sign)
+    pub sign: Option<MethodFailure>,
+    /// Failures of [`get_auth()`](Unlocked::get_auth)
+    pub get_auth: Option<MethodFailure>,
+}
+
+/// The latency and failures injected in a single adapter method.
+#[derive(Debug, Clone, Default)]
+pub struct MethodFailure {
+    /// The probability of a call returning an error, from `0.0` to `1.0`.
+    pub error_probability: f64,
+    /// Fixed latency added to every call.
+    ///
+    /// **NOTE:** For the synchronous methods ([`verify()`](Locked::verify),
+    /// [`recover_signer()`](Locked::recover_signer), [`sign()`](Unlocked::sign)
+    /// and [`get_auth()`](Unlocked::get_auth))
+    /// the latency blocks the current thread.
+    pub latency: Option<Duration>,
+    /// The kind of the returned error,
+    /// by default: a retryable [`ErrorKind::RpcTransport`].
+    pub error_kind: Option<ErrorKind>,
+}
+
+impl MethodFailure {
+    fn to_error(&self, method: &str) -> Error {
+        let message = format!("Injected failure of Dummy adapter `{method}` call");
+
+        match self
+            .error_kind
+            .unwrap_or(ErrorKind::RpcTransport { retryable: true })
+        {
+            ErrorKind::Authentication => Error::authentication(message),
+            ErrorKind::Authorization => Error::authorization(message),
+            ErrorKind::RpcTransport { retryable } => Error::rpc_transport(message, retryable),
+            ErrorKind::Domain => Error::domain(message),
+            ErrorKind::Internal => Error::internal(message),
+        }
+    }
+}
+
+/// The [`FailureConfig`] alongside the random generator
+/// shared between all the clones of the [`Dummy`] client.
+#[derive(Debug, Clone)]
+struct FailureInjection {
+    config: FailureConfig,
+    rng: Arc<Mutex<StdRng>>,
+}
+
+impl FailureInjection {
+    fn new(config: FailureConfig) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        Self {
+            config,
+            rng: Arc::new(Mutex::new(rng)),
+        }
+    }
+
+    /// Returns the latency which should be added to the call
+    /// and the error, if the call should fail.
+    fn outcome(
+        &self,
+        method: &str,
+        failure: Option<&MethodFailure>,
+    ) -> (Option<Duration>, Option<Error>) {
+        let failure = match failure {
+            Some(failure) => failure,
+            None => return (None, None),
+        };
+
+        let probability = failure.error_probability.clamp(0.0, 1.0);
+        let should_fail = probability > 0.0
+            && self
+                .rng
+                .lock()
+                .expect("Failure injection random generator mutex poisoned")
+                .gen_bool(probability);
+
+        let error = should_fail.then(|| failure.to_error(method));
+
+        (failure.latency, error)
+    }
 }
 
 /// Dummy adapter implementation intended for testing.
@@ -39,6 +148,10 @@ pub struct Dummy {
     authorization_tokens: HashMap<Address, String>,
     chains: Vec<ChainInfo>,
     deposits: Deposits,
+    failure_injection: Option<FailureInjection>,
+    /// Whether [`sign()`](Unlocked::sign) & [`get_auth()`](Unlocked::get_auth) are unsupported,
+    /// see [`Dummy::read_only()`]
+    read_only: bool,
 }
 
 impl Dummy {
@@ -48,9 +161,75 @@ impl Dummy {
             authorization_tokens: opts.dummy_auth_tokens,
             chains: opts.dummy_chains,
             deposits: Default::default(),
+            failure_injection: opts.failure_injection.map(FailureInjection::new),
+            read_only: false,
+        }
+    }
+
+    /// Creates a read-only [`Dummy`] client which can't sign.
+    ///
+    /// [`sign()`](Unlocked::sign) & [`get_auth()`](Unlocked::get_auth) return an `Unsupported` error,
+    /// while [`get_deposit()`](Locked::get_deposit), [`session_from_token()`](Locked::session_from_token)
+    /// and the rest of the [`Locked`] methods work as usual.
+    pub fn read_only(opts: Options) -> Self {
+        Self {
+            read_only: true,
+            ..Self::init(opts)
+        }
+    }
+
+    fn ensure_can_sign(&self, method: &str) -> Result<(), Error> {
+        if self.read_only {
+            return Err(Error::internal(format!(
+                "`{method}` is not supported by a read-only Dummy adapter"
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn failure_outcome(
+        &self,
+        method: &str,
+        select: fn(&FailureConfig) -> Option<&MethodFailure>,
+    ) -> (Option<Duration>, Option<Error>) {
+        match &self.failure_injection {
+            Some(injection) => injection.outcome(method, select(&injection.config)),
+            None => (None, None),
         }
     }
 
+    /// Applies the injected latency & failure of a synchronous method.
+    /// The latency blocks the current thread.
+    fn inject_failure(
+        &self,
+        method: &str,
+        select: fn(&FailureConfig) -> Option<&MethodFailure>,
+    ) -> Result<(), Error> {
+        let (latency, error) = self.failure_outcome(method, select);
+
+        if let Some(latency) = latency {
+            std::thread::sleep(latency);
+        }
+
+        error.map_or(Ok(()), Err)
+    }
+
+    /// Applies the injected latency & failure of an asynchronous method.
+    async fn inject_failure_async(
+        &self,
+        method: &str,
+        select: fn(&FailureConfig) -> Option<&MethodFailure>,
+    ) -> Result<(), Error> {
+        let (latency, error) = self.failure_outcome(method, select);
+
+        if let Some(latency) = latency {
+            tokio::time::sleep(latency).await;
+        }
+
+        error.map_or(Ok(()), Err)
+    }
+
     /// Set the deposit that you want the adapter to return every time
     /// when the [`get_deposit()`](Locked::get_deposit) get's called
     /// for the give [`ChannelId`](primitives::ChannelId) and [`Address`].
@@ -98,6 +277,8 @@ impl Locked for Dummy {
         _state_root: &str,
         signature: &str,
     ) -> Result<bool, crate::Error> {
+        self.inject_failure("verify", |config| config.verify.as_ref())?;
+
         // select the `identity` and compare it to the signer
         // for empty string this will return array with 1 element - an empty string `[""]`
         let is_same = match signature.rsplit(' ').take(1).next() {
@@ -108,16 +289,56 @@ impl Locked for Dummy {
         Ok(is_same)
     }
 
+    /// Decodes the signer [`Address`] from the Dummy signature format
+    /// created by [`Dummy::sign()`](Unlocked::sign):
+    ///
+    /// `Dummy adapter signature for {state_root} by {signer}`
+    ///
+    /// As with [`Dummy::verify()`](Locked::verify), the signed `message` is not checked.
+    fn recover_signer(&self, _message: &[u8], signature: &str) -> Result<Address, crate::Error> {
+        self.inject_failure("recover_signer", |config| config.verify.as_ref())?;
+
+        signature
+            .strip_prefix("Dummy adapter signature for ")
+            .and_then(|rest| rest.rsplit_once(" by "))
+            .and_then(|(_state_root, signer)| signer.parse::<Address>().ok())
+            .ok_or_else(|| Error::domain(format!("Invalid Dummy adapter signature: '{signature}'")))
+    }
+
     /// Finds the authorization token from the configured values
     /// and creates a [`Session`] out of it by using the ChainId included in the header:
     ///
     /// `{Auth token}:chain_id:{ChainId}`
     ///
+    /// Optionally, a [`Scope`] can be appended to the header token,
+    /// otherwise the [`Session`] has a [`Scope::Full`]:
+    ///
+    /// `{Auth token}:chain_id:{ChainId}:scope:{Scope}`
+    ///
     /// # Examples
     ///
     /// `AUTH_awesomeLeader:chain_id:1`
     /// `AUTH_awesomeAdvertiser:chain_id:1337`
+    /// `AUTH_awesomeAdvertiser:chain_id:1337:scope:read_only`
     async fn session_from_token(&self, header_token: &str) -> Result<Session, crate::Error> {
+        self.inject_failure_async("session_from_token", |config| {
+            config.session_from_token.as_ref()
+        })
+        .await?;
+
+        let (header_token, scope) = match header_token.split_once(":scope:") {
+            Some((header_token, scope)) => {
+                let scope = scope.parse::<Scope>().map_err(|_parse| {
+                    Error::authentication(format!(
+                        "Unknown Dummy Authentication token scope '{scope}'"
+                    ))
+                })?;
+
+                (header_token, scope)
+            }
+            None => (header_token, Scope::Full),
+        };
+
         let header_token = header_token.parse::<HeaderToken>().map_err(|_parse| {
             Error::authentication(format!("Dummy Authentication token format should be in the format: `{{Auth Token}}:chain_id:{{Chain Id}}` but '{header_token}' was provided"))
         })?;
@@ -147,6 +368,9 @@ impl Locked for Dummy {
             uid: *identity,
             era: 0,
             chain: chain_info.chain.clone(),
+            scope,
+            era_grace: false,
+            valid_until: None,
         })
     }
 
@@ -155,10 +379,13 @@ impl Locked for Dummy {
         channel_context: &ChainOf<Channel>,
         depositor_address: Address,
     ) -> Result<Deposit, crate::Error> {
+        self.inject_failure_async("get_deposit", |config| config.get_deposit.as_ref())
+            .await?;
+
         // validate that the same chain & token are used for the Channel Context
         // as the ones setup in the Dummy adapter.
         if channel_context.token.address != channel_context.context.token {
-            return Err(Error::adapter(
+            return Err(Error::domain(
                 "Token context of channel & channel token addresses are different".to_string(),
             ));
         }
@@ -170,7 +397,7 @@ impl Locked for Dummy {
                 .iter()
                 .find(|chain_info| chain_info.chain == channel_context.chain)
                 .ok_or_else(|| {
-                    Error::adapter(
+                    Error::domain(
                         "Channel Chain not found in Dummy adapter's configuration".to_string(),
                     )
                 })?;
@@ -178,7 +405,7 @@ impl Locked for Dummy {
             let _found_token = found_chain
                 .find_token(channel_context.context.token)
                 .ok_or_else(|| {
-                    Error::adapter(format!(
+                    Error::domain(format!(
                         "Channel Token not found in configured adapter chain: {:?}",
                         found_chain.chain.chain_id
                     ))
@@ -188,7 +415,7 @@ impl Locked for Dummy {
         self.deposits
             .get_deposit(channel_context, depositor_address)
             .ok_or_else(|| {
-                Error::adapter(format!(
+                Error::domain(format!(
                     "No mocked deposit found for {:?} & depositor {:?}",
                     channel_context.context.id(),
                     depositor_address
@@ -201,6 +428,9 @@ impl Locked for Dummy {
 impl Unlocked for Dummy {
     // requires Unlocked
     fn sign(&self, state_root: &str) -> Result<String, Error> {
+        self.ensure_can_sign("sign")?;
+        self.inject_failure("sign", |config| config.sign.as_ref())?;
+
         let signature = format!(
             "Dummy adapter signature for {} by {}",
             state_root,
@@ -213,6 +443,9 @@ impl Unlocked for Dummy {
     // Builds the authentication token as:
     // `{Auth token}:chain_id:{Chain Id}`
     fn get_auth(&self, for_chain: ChainId, _intended_for: ValidatorId) -> Result<String, Error> {
+        self.ensure_can_sign("get_auth")?;
+        self.inject_failure("get_auth", |config| config.get_auth.as_ref())?;
+
         let token = self
             .authorization_tokens
             .get(&self.identity.to_address())
@@ -244,6 +477,9 @@ impl Unlockable for Dummy {
 ///
 /// The format for the header token is:
 /// `{Auth token}:chain_id:{Chain Id}`
+///
+/// A [`Scope`] can additionally be appended to it,
+/// see [`Dummy::session_from_token()`](Locked::session_from_token).
 #[derive(Debug, Clone, Display, FromStr)]
 #[display("{token}:chain_id:{chain_id}")]
 pub struct HeaderToken {
@@ -332,6 +568,7 @@ mod test {
             dummy_identity: IDS[&LEADER],
             dummy_auth_tokens: Default::default(),
             dummy_chains: GANACHE_CONFIG.chains.values().cloned().collect(),
+            failure_injection: None,
         });
 
         let creator = *CREATOR;
@@ -374,6 +611,201 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_session_from_token_scope() {
+        let dummy_client = Dummy::init(Options {
+            dummy_identity: IDS[&LEADER],
+            dummy_auth_tokens: vec![(*CREATOR, "AUTH_Creator".into())]
+                .into_iter()
+                .collect(),
+            dummy_chains: GANACHE_CONFIG.chains.values().cloned().collect(),
+            failure_injection: None,
+        });
+
+        // no scope
+        {
+            let session = dummy_client
+                .session_from_token("AUTH_Creator:chain_id:1337")
+                .await
+                .expect("Should create Session");
+
+            assert_eq!(*CREATOR, session.uid);
+            assert_eq!(GANACHE_1337.clone(), session.chain);
+            assert_eq!(Scope::Full, session.scope);
+        }
+
+        // Full scope
+        {
+            let session = dummy_client
+                .session_from_token("AUTH_Creator:chain_id:1337:scope:full")
+                .await
+                .expect("Should create Session");
+
+            assert_eq!(Scope::Full, session.scope);
+        }
+
+        // ReadOnly scope
+        {
+            let session = dummy_client
+                .session_from_token("AUTH_Creator:chain_id:1337:scope:read_only")
+                .await
+                .expect("Should create Session");
+
+            assert_eq!(*CREATOR, session.uid);
+            assert_eq!(Scope::ReadOnly, session.scope);
+        }
+
+        // Unknown scope
+        {
+            let result = dummy_client
+                .session_from_token("AUTH_Creator:chain_id:1337:scope:admin")
+                .await;
+
+            assert!(result.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_only_cannot_sign() {
+        let channel_context = ChainOf::new(
+            GANACHE_1337.clone(),
+            GANACHE_INFO_1337
+                .find_token(DUMMY_CAMPAIGN.channel.token)
+                .cloned()
+                .unwrap(),
+        )
+        .with_channel(DUMMY_CAMPAIGN.channel);
+
+        let dummy_client = Dummy::read_only(Options {
+            dummy_identity: IDS[&LEADER],
+            dummy_auth_tokens: vec![
+                (*LEADER, "AUTH_Leader".into()),
+                (*CREATOR, "AUTH_Creator".into()),
+            ]
+            .into_iter()
+            .collect(),
+            dummy_chains: GANACHE_CONFIG.chains.values().cloned().collect(),
+            failure_injection: None,
+        });
+
+        let sign_err = dummy_client
+            .sign("state_root")
+            .expect_err("Read-only client should not sign");
+        assert!(sign_err.to_string().starts_with("Internal: "));
+
+        let auth_err = dummy_client
+            .get_auth(GANACHE_1337.chain_id, IDS[&LEADER])
+            .expect_err("Read-only client should not create auth tokens");
+        assert!(auth_err.to_string().starts_with("Internal: "));
+
+        let deposit = Deposit {
+            total: BigNum::from(1_000),
+        };
+        dummy_client.set_deposit(&channel_context, *CREATOR, deposit.clone());
+        let actual = dummy_client
+            .get_deposit(&channel_context, *CREATOR)
+            .await
+            .expect("Should get mocked deposit");
+        assert_eq!(deposit, actual);
+
+        let session = dummy_client
+            .session_from_token("AUTH_Creator:chain_id:1337")
+            .await
+            .expect("Should create Session");
+        assert_eq!(*CREATOR, session.uid);
+    }
+
+    #[tokio::test]
+    async fn test_failure_injection() {
+        let init = |seed: u64| {
+            Dummy::init(Options {
+                dummy_identity: IDS[&LEADER],
+                dummy_auth_tokens: vec![(*CREATOR, "AUTH_Creator".into())]
+                    .into_iter()
+                    .collect(),
+                dummy_chains: GANACHE_CONFIG.chains.values().cloned().collect(),
+                failure_injection: Some(FailureConfig {
+                    seed: Some(seed),
+                    session_from_token: Some(MethodFailure {
+                        error_probability: 1.0,
+                        latency: Some(Duration::from_millis(20)),
+                        error_kind: Some(ErrorKind::Authentication),
+                    }),
+                    sign: Some(MethodFailure {
+                        error_probability: 0.5,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+            })
+        };
+
+        // latency & error kind
+        {
+            let dummy_client = init(1);
+
+            let start = std::time::Instant::now();
+            let err = dummy_client
+                .session_from_token("AUTH_Creator:chain_id:1337")
+                .await
+                .expect_err("Should fail");
+
+            assert!(start.elapsed() >= Duration::from_millis(20));
+            assert!(err.to_string().starts_with("Authentication: "));
+
+            // methods without injected failures are not affected
+            assert!(dummy_client
+                .verify(IDS[&LEADER], "", &IDS[&LEADER].to_checksum())
+                .expect("Should verify"));
+        }
+
+        // the same seed produces the same failures
+        {
+            let sign_results = |dummy_client: Dummy| {
+                (0..20)
+                    .map(|_| dummy_client.sign("state_root").is_ok())
+                    .collect::<Vec<_>>()
+            };
+
+            let first = sign_results(init(42));
+            let second = sign_results(init(42));
+
+            assert_eq!(first, second);
+            assert!(first.contains(&true) && first.contains(&false));
+        }
+    }
+
+    #[test]
+    fn test_recover_signer() {
+        let dummy_client = Dummy::init(Options {
+            dummy_identity: IDS[&LEADER],
+            dummy_auth_tokens: Default::default(),
+            dummy_chains: GANACHE_CONFIG.chains.values().cloned().collect(),
+            failure_injection: None,
+        });
+
+        let signature = format!(
+            "Dummy adapter signature for state_root by {}",
+            IDS[&LEADER].to_checksum()
+        );
+        let signer = dummy_client
+            .recover_signer(b"state_root", &signature)
+            .expect("Should recover signer");
+        assert_eq!(IDS[&LEADER].to_address(), signer);
+
+        // signature created by `Dummy::sign()`
+        let signature = dummy_client.sign("state_root").expect("Should sign");
+        let signer = dummy_client
+            .recover_signer(b"state_root", &signature)
+            .expect("Should recover signer");
+        assert_eq!(dummy_client.whoami().to_address(), signer);
+
+        // not a Dummy adapter signature
+        assert!(dummy_client
+            .recover_signer(b"state_root", "0x9e07f12958ce7c5eb1362eb9461e4745dd9d74a4")
+            .is_err());
+    }
+
     #[test]
     #[should_panic]
     fn test_set_deposit_to_none_should_panic_on_non_mocked_deposits() {
@@ -394,6 +826,7 @@ mod test {
             dummy_identity: IDS[&LEADER],
             dummy_auth_tokens: Default::default(),
             dummy_chains: GANACHE_CONFIG.chains.values().cloned().collect(),
+            failure_injection: None,
         });
 
         // It should panic when no deposit is set and we try to set it to None
@@ -1,11 +1,13 @@
-use parse_display::Display;
 use std::{error::Error as StdError, fmt};
 use thiserror::Error;
 
 pub(crate) type BoxError = Box<dyn StdError + Send + Sync>;
 
 /// The error used by the [`crate::Adapter`] to wrap any custom error from the client
-/// and the kinds of errors that the [`crate::Adapter`] can return.
+/// and the [`Kind`] of error that the [`crate::Adapter`] returns.
+///
+/// Clients should map their errors to the [`Kind`] which describes the failure best,
+/// in order for the callers (e.g. the Sentry REST API) to handle them accordingly.
 #[derive(Debug, Error)]
 #[error("{inner}")]
 pub struct Error {
@@ -25,41 +27,57 @@ impl Error {
         }
     }
 
-    pub fn wallet_unlock<E>(source: E) -> Self
+    /// The authentication token is invalid, expired or not intended for us.
+    pub fn authentication<E>(source: E) -> Self
     where
         E: Into<BoxError>,
     {
-        Self::new(Kind::WalletUnlock, Some(source))
+        Self::new(Kind::Authentication, Some(source))
     }
 
-    pub fn authentication<E>(source: E) -> Self
+    /// The authenticated party does not have the required privileges.
+    pub fn authorization<E>(source: E) -> Self
     where
         E: Into<BoxError>,
     {
-        Self::new(Kind::Authentication, Some(source))
+        Self::new(Kind::Authorization, Some(source))
     }
 
-    pub fn authorization<E>(source: E) -> Self
+    /// The RPC (e.g. the Ethereum node) could not be reached or responded with an error.
+    pub fn rpc_transport<E>(source: E, retryable: bool) -> Self
     where
         E: Into<BoxError>,
     {
-        Self::new(Kind::Authorization, Some(source))
+        Self::new(Kind::RpcTransport { retryable }, Some(source))
     }
 
-    pub fn adapter<A>(source: A) -> Self
+    /// The passed data (e.g. the Channel, token or signature) is invalid.
+    pub fn domain<E>(source: E) -> Self
     where
-        A: Into<BoxError>,
+        E: Into<BoxError>,
     {
-        Self::new(Kind::Adapter, Some(source))
+        Self::new(Kind::Domain, Some(source))
     }
 
-    pub fn verify<A>(source: A) -> Self
+    /// An error of the client itself, e.g. unlocking the wallet or an unsupported operation.
+    pub fn internal<E>(source: E) -> Self
     where
-        A: Into<BoxError>,
+        E: Into<BoxError>,
     {
-        Self::new(Kind::Verify, Some(source))
+        Self::new(Kind::Internal, Some(source))
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.inner.kind
+    }
+
+    /// Whether or not the same call can be retried later,
+    /// i.e. a retryable [`Kind::RpcTransport`] error.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.kind(), Kind::RpcTransport { retryable: true })
     }
 }
+
 #[derive(Debug, Error)]
 struct Inner {
     kind: Kind,
@@ -77,11 +95,27 @@ impl fmt::Display for Inner {
     }
 }
 
-#[derive(Debug, Display)]
-pub(crate) enum Kind {
-    Adapter,
-    WalletUnlock,
-    Verify,
+/// The category of an adapter [`Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
     Authentication,
     Authorization,
+    /// The RPC call failed, `retryable` errors are transient, e.g. the RPC is unreachable.
+    RpcTransport {
+        retryable: bool,
+    },
+    Domain,
+    Internal,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Authentication => write!(f, "Authentication"),
+            Kind::Authorization => write!(f, "Authorization"),
+            Kind::RpcTransport { .. } => write!(f, "RPC transport"),
+            Kind::Domain => write!(f, "Domain"),
+            Kind::Internal => write!(f, "Internal"),
+        }
+    }
 }
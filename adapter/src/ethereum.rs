@@ -3,12 +3,19 @@
 use ethsign::{KeyFile, Protected, SecretKey, Signature};
 
 use once_cell::sync::Lazy;
-use web3::signing::keccak256;
+use primitives::{Address, Channel};
+use web3::{
+    ethabi::{encode, Token},
+    signing::keccak256,
+    types::H160,
+};
 
 use crate::{Adapter, LockedState, UnlockedState};
 
+use self::channel::EthereumChannel;
+
 pub use {
-    client::{ChainTransport, Ethereum, Options},
+    client::{ChainTransport, Ethereum, Options, DEFAULT_TOKEN_VALIDITY},
     error::Error,
 };
 
@@ -21,6 +28,10 @@ mod channel;
 mod client;
 mod error;
 
+/// EIP-712 typed structured data of the authentication tokens
+/// See <https://eips.ethereum.org/EIPS/eip-712>
+pub mod eip712;
+
 /// Ethereum Web Token
 /// See <https://github.com/ethereum/EIPs/issues/1341>
 ///
@@ -45,6 +56,43 @@ pub static SWEEPER_ABI: Lazy<&'static [u8]> =
     Lazy::new(|| include_bytes!("../../lib/protocol-eth/abi/Sweeper.json"));
 pub static IDENTITY_ABI: Lazy<&'static [u8]> =
     Lazy::new(|| include_bytes!("../../lib/protocol-eth/abi/Identity5.2.json"));
+/// The Depositor contract bytecode, deployed by the Sweeper contract for each depositor.
+pub static DEPOSITOR_BYTECODE_DECODED: Lazy<Vec<u8>> = Lazy::new(|| {
+    let bytecode = include_str!("../../lib/protocol-eth/resources/bytecode/Depositor.bin");
+
+    hex::decode(bytecode.trim_end_matches('\n')).expect("Decoded DEPOSITOR_BYTECODE")
+});
+
+/// The counterfactual address of the Depositor contract of the `depositor` in the `channel`,
+/// i.e. the `CREATE2` address (with a zero salt) at which the `sweeper` deploys the Depositor
+/// for sweeping the funds sent to this address into the `outpace` contract.
+pub fn get_counterfactual_address(
+    sweeper: Address,
+    channel: &Channel,
+    outpace: Address,
+    depositor: Address,
+) -> Address {
+    let salt = [0_u8; 32];
+
+    let constructor_params = encode(&[
+        Token::Address(H160(outpace.to_bytes())),
+        channel.tokenize(),
+        Token::Address(H160(depositor.to_bytes())),
+    ]);
+    let mut init_code = DEPOSITOR_BYTECODE_DECODED.clone();
+    init_code.extend(constructor_params);
+
+    // keccak256(0xff ++ sweeper ++ salt ++ keccak256(init_code))[12..]
+    let mut create2 = vec![0xff_u8];
+    create2.extend(sweeper.as_bytes());
+    create2.extend(salt);
+    create2.extend(keccak256(&init_code));
+
+    let mut address = [0_u8; 20];
+    address.copy_from_slice(&keccak256(&create2)[12..]);
+
+    Address::from(address)
+}
 
 /// Hashes the passed message with the format of `Signed Data Standard`
 /// See https://eips.ethereum.org/EIPS/eip-191
@@ -1,19 +1,22 @@
-use std::{fs, str::FromStr};
+use std::{fs, str::FromStr, time::Duration};
 
 use crate::{
     prelude::*,
-    primitives::{Deposit, Session},
+    primitives::{Deposit, DetailedDeposit, Session},
 };
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{TimeZone, Utc};
 use ethsign::{KeyFile, Signature};
-use primitives::{Address, BigNum, Chain, ChainId, ChainOf, Channel, Config, ValidatorId};
+use primitives::{
+    Address, BigNum, Chain, ChainId, ChainOf, Channel, Config, DepositBreakdown, ValidatorId,
+};
 
 use super::{
+    eip712::Domain,
     error::{Error, EwtSigningError, KeystoreError, VerifyError},
     ewt::{self, Payload},
-    to_ethereum_signed, Electrum, LockedWallet, UnlockedWallet, WalletState, IDENTITY_ABI,
-    OUTPACE_ABI,
+    get_counterfactual_address, to_ethereum_signed, Electrum, LockedWallet, UnlockedWallet,
+    WalletState, ERC20_ABI, IDENTITY_ABI, OUTPACE_ABI,
 };
 use web3::{
     contract::{Contract, Options as ContractOptions},
@@ -23,10 +26,19 @@ use web3::{
     Web3,
 };
 
+/// The default [`Options::token_validity`]: 24 hours.
+pub const DEFAULT_TOKEN_VALIDITY: Duration = Duration::from_secs(24 * 60 * 60);
+
 #[derive(Debug, Clone)]
 pub struct Options {
     pub keystore_file: String,
     pub keystore_pwd: String,
+    /// For how long an authentication token is valid after its [`Payload.era`](Payload::era).
+    ///
+    /// The validity is rounded down to whole [`ERA_LENGTH`](ewt::ERA_LENGTH)s.
+    ///
+    /// Default: [`DEFAULT_TOKEN_VALIDITY`]
+    pub token_validity: Option<Duration>,
 }
 
 #[derive(Debug, Clone)]
@@ -34,6 +46,7 @@ pub struct Options {
 pub struct Ethereum<S = LockedWallet> {
     address: ValidatorId,
     config: Config,
+    token_validity: Duration,
     pub(crate) state: S,
 }
 
@@ -66,6 +79,7 @@ impl Ethereum<LockedWallet> {
         Ok(Self {
             address: ValidatorId::from(address),
             config: config.to_owned(),
+            token_validity: opts.token_validity.unwrap_or(DEFAULT_TOKEN_VALIDITY),
             state: LockedWallet::KeyStore {
                 keystore: keystore_json,
                 password: opts.keystore_pwd.into(),
@@ -139,6 +153,7 @@ impl Unlockable for Ethereum<LockedWallet> {
         Ok(Ethereum {
             address: self.address,
             config: self.config.clone(),
+            token_validity: self.token_validity,
             state: unlocked_wallet,
         })
     }
@@ -158,6 +173,14 @@ impl<S: WalletState> Locked for Ethereum<S> {
         state_root: &str,
         signature: &str,
     ) -> Result<bool, Self::Error> {
+        let state_root = hex::decode(state_root).map_err(VerifyError::StateRootDecoding)?;
+
+        Ok(self.recover_signer(&state_root, signature)? == signer.to_address())
+    }
+
+    /// Recovers the signer [`Address`] using `ecrecover` on the
+    /// [Ethereum signed message](to_ethereum_signed) of `message`.
+    fn recover_signer(&self, message: &[u8], signature: &str) -> Result<Address, Self::Error> {
         if !signature.starts_with("0x") {
             return Err(VerifyError::SignatureNotPrefixed.into());
         }
@@ -166,16 +189,15 @@ impl<S: WalletState> Locked for Ethereum<S> {
 
         let signature =
             Signature::from_electrum(&decoded_signature).ok_or(VerifyError::SignatureInvalid)?;
-        let state_root = hex::decode(state_root).map_err(VerifyError::StateRootDecoding)?;
 
-        let message = to_ethereum_signed(&state_root);
+        let message = to_ethereum_signed(message);
 
         // recover the public key using the signature and the eth sign message
         let public_key = signature
             .recover(&message)
             .map_err(|ec_err| VerifyError::PublicKeyRecovery(ec_err.to_string()))?;
 
-        Ok(public_key.address() == signer.as_bytes())
+        Ok(Address::from(public_key.address()))
     }
 
     /// Creates a `Session` from a provided Token by calling the Contract.
@@ -183,9 +205,23 @@ impl<S: WalletState> Locked for Ethereum<S> {
     /// This methods validates that the [`Payload`]'s [`Chain`] is whitelisted in the configuration.
     ///
     /// Does **not** cache the (`Token`, `Session`) pair.
+    ///
+    /// The `Session` has the [`Payload::scope`] of the token,
+    /// [`Scope::Full`](crate::primitives::Scope::Full) if it's not set.
+    ///
+    /// Tokens older than [`Options::token_validity`] are rejected with [`Error::Expired`],
+    /// unless they are within the [`Config.auth_era_grace`](primitives::Config::auth_era_grace)
+    /// after it, in which case the `Session` is marked with [`Session::era_grace`].
+    ///
+    /// Legacy (non EIP-712) tokens are accepted only when
+    /// [`Config.legacy_auth_tokens`](primitives::Config::legacy_auth_tokens) is enabled.
     async fn session_from_token(&self, token: &str) -> Result<Session, Self::Error> {
         let (verified_token, verified) = ewt::Token::verify(token).map_err(Error::VerifyMessage)?;
 
+        if !self.config.legacy_auth_tokens && verified_token.header != *ewt::EIP712_HEADER {
+            return Err(Error::LegacyAuthenticationToken);
+        }
+
         if self.whoami() != verified.payload.id {
             return Err(Error::AuthenticationTokenNotIntendedForUs {
                 payload: verified.payload,
@@ -193,6 +229,25 @@ impl<S: WalletState> Locked for Ethereum<S> {
             });
         }
 
+        let current_era = Payload::era_at(Utc::now());
+        let validity_eras = (self.token_validity.as_millis() / ewt::ERA_LENGTH.as_millis()) as i64;
+        let token_age = current_era.saturating_sub(verified.payload.era);
+        if token_age > validity_eras.saturating_add(self.config.auth_era_grace.into()) {
+            return Err(Error::Expired {
+                era: verified.payload.era,
+                current_era,
+            });
+        }
+        let era_grace = token_age > validity_eras;
+        // the token is valid until the start of the first era after its validity
+        let valid_until = verified
+            .payload
+            .era
+            .saturating_add(validity_eras)
+            .saturating_add(1)
+            .checked_mul(ewt::ERA_LENGTH.as_millis() as i64)
+            .and_then(|millis| Utc.timestamp_millis_opt(millis).single());
+
         // Check if Payload chain is whitelisted
         let whitelisted_chain = self
             .config
@@ -201,6 +256,8 @@ impl<S: WalletState> Locked for Ethereum<S> {
             .chain
             .clone();
 
+        let scope = verified.payload.scope.unwrap_or_default();
+
         let sess = match &verified.payload.identity {
             Some(identity) => {
                 // the Hash for has_privileges should **not** be an Ethereum Signed Message hash
@@ -218,6 +275,9 @@ impl<S: WalletState> Locked for Ethereum<S> {
                         era: verified.payload.era,
                         uid: identity.to_owned(),
                         chain: whitelisted_chain,
+                        scope,
+                        era_grace,
+                        valid_until,
                     }
                 } else {
                     return Err(Error::InsufficientAuthorizationPrivilege);
@@ -227,6 +287,9 @@ impl<S: WalletState> Locked for Ethereum<S> {
                 era: verified.payload.era,
                 uid: verified.from,
                 chain: whitelisted_chain,
+                scope,
+                era_grace,
+                valid_until,
             },
         };
 
@@ -270,6 +333,60 @@ impl<S: WalletState> Locked for Ethereum<S> {
 
         Ok(deposit)
     }
+
+    /// The pending deposit is the token balance of the counterfactual address
+    /// of the depositor, which has not been swept to OUTPACE yet.
+    ///
+    /// Without a [`Chain.sweeper`](Chain::sweeper) the deposit has no breakdown.
+    async fn get_deposit_detailed(
+        &self,
+        channel_context: &ChainOf<Channel>,
+        depositor_address: Address,
+    ) -> Result<DetailedDeposit, Self::Error> {
+        let deposit = self.get_deposit(channel_context, depositor_address).await?;
+
+        let sweeper = match channel_context.chain.sweeper {
+            Some(sweeper) => sweeper,
+            None => return Ok(deposit.into()),
+        };
+
+        let web3 = channel_context.chain.init_web3()?;
+
+        let token_contract = Contract::from_json(
+            web3.eth(),
+            H160(channel_context.token.address.to_bytes()),
+            &ERC20_ABI,
+        )
+        .map_err(Error::ContractInitialization)?;
+
+        let counterfactual_address = get_counterfactual_address(
+            sweeper,
+            &channel_context.context,
+            channel_context.chain.outpace,
+            depositor_address,
+        );
+
+        let pending: U256 = token_contract
+            .query(
+                "balanceOf",
+                H160(counterfactual_address.to_bytes()),
+                None,
+                ContractOptions::default(),
+                None,
+            )
+            .await
+            .map_err(Error::ContractQuerying)?;
+
+        let pending = BigNum::from_str(&pending.to_string()).map_err(Error::BigNumParsing)?;
+
+        Ok(DetailedDeposit {
+            breakdown: Some(DepositBreakdown {
+                outpace: deposit.total.clone(),
+                pending,
+            }),
+            total: deposit.total,
+        })
+    }
 }
 
 #[async_trait]
@@ -288,17 +405,25 @@ impl Unlocked for Ethereum<UnlockedWallet> {
         Ok(format!("0x{}", hex::encode(wallet_sign.to_electrum())))
     }
 
+    /// Signs an EIP-712 typed data token in the [`Domain`] of `for_chain`,
+    /// or a legacy token if [`Config.legacy_auth_tokens`](primitives::Config::legacy_auth_tokens)
+    /// is enabled.
     fn get_auth(&self, for_chain: ChainId, intended_for: ValidatorId) -> Result<String, Error> {
-        let era = Utc::now().timestamp_millis() as f64 / 60000.0;
         let payload = Payload {
             id: intended_for,
-            era: era.floor() as i64,
+            era: Payload::era_at(Utc::now()),
             identity: None,
             address: self.whoami().to_address(),
             chain_id: for_chain,
+            scope: None,
         };
 
-        let token = ewt::Token::sign(&self.state.wallet, payload).map_err(Error::SignMessage)?;
+        let token = if self.config.legacy_auth_tokens {
+            ewt::Token::sign(&self.state.wallet, payload)
+        } else {
+            ewt::Token::sign_typed_data(&self.state.wallet, &Domain::new(for_chain), payload)
+        }
+        .map_err(Error::SignMessage)?;
 
         Ok(token.to_string())
     }
@@ -306,17 +431,22 @@ impl Unlocked for Ethereum<UnlockedWallet> {
 
 #[cfg(test)]
 mod test {
-    use super::{ewt::ETH_SIGN_SUFFIX, Ethereum};
+    use std::time::Duration;
+
+    use super::{ewt::ETH_SIGN_SUFFIX, Ethereum, Options};
     use crate::ethereum::{
         client::ChainTransport,
+        eip712::Domain,
+        error::{Error, EwtVerifyError},
         ewt::{self, Payload},
+        get_counterfactual_address,
         test_util::*,
         to_ethereum_signed, Electrum,
     };
 
     use crate::{
         prelude::*,
-        primitives::{Deposit, Session},
+        primitives::{Deposit, DetailedDeposit, Scope, Session},
     };
     use chrono::Utc;
 
@@ -327,7 +457,7 @@ mod test {
             ADDRESS_3, ADDRESS_4, ADDRESS_5, ADVERTISER, CREATOR, DUMMY_CAMPAIGN, FOLLOWER,
             GUARDIAN, GUARDIAN_2, IDS, LEADER, LEADER_2,
         },
-        BigNum, ChainOf, Channel, ToHex, ValidatorId,
+        Address, BigNum, ChainId, ChainOf, Channel, DepositBreakdown, ToHex, ValidatorId,
     };
     use web3::{
         contract::Options as ContractOptions, ethabi::Token, signing::keccak256, types::H160,
@@ -388,6 +518,39 @@ mod test {
         assert!(verify2, "invalid signature 2 verification");
     }
 
+    #[test]
+    fn should_recover_signer_of_messages() {
+        let eth_adapter =
+            Ethereum::init(KEYSTORE_IDENTITY.1.clone(), &GANACHE_CONFIG).expect("Should init");
+
+        let message =
+            hex::decode("8bc45d8eb27f4c98cab35d17b0baecc2a263d6831ef0800f4c190cbfac6d20a3")
+                .expect("Valid hex");
+        let signature =
+            "0x9e07f12958ce7c5eb1362eb9461e4745dd9d74a42b921391393caea700bfbd6e1ad876a7d8f9202ef1fe6110dbfe87840c5676ca5c4fda9f3330694a1ac2a1fc1b";
+
+        let signer = eth_adapter
+            .recover_signer(&message, signature)
+            .expect("Should recover signer");
+        assert_eq!(
+            "0x2892f6C41E0718eeeDd49D98D648C789668cA67d"
+                .parse::<Address>()
+                .expect("Valid Address"),
+            signer
+        );
+
+        // a different message recovers a different signer
+        let other_signer = eth_adapter
+            .recover_signer(b"another message", signature)
+            .expect("Should recover signer");
+        assert_ne!(signer, other_signer);
+
+        // signature without `0x` prefix
+        assert!(eth_adapter
+            .recover_signer(&message, &signature[2..])
+            .is_err());
+    }
+
     /// Validated using `lib/protocol-eth/js/Bundle.js`
     #[tokio::test]
     async fn test_has_privileges_with_raw_data() {
@@ -542,6 +705,7 @@ mod test {
             address: adapter.whoami().to_address(),
             identity: Some(identity_address),
             chain_id: ganache_chain.chain_id,
+            scope: None,
         };
 
         let auth_token = ewt::Token::sign(&adapter.state.wallet, payload)
@@ -595,6 +759,7 @@ mod test {
             address: signer_adapter.whoami().to_address(),
             // the chain we need to make the token for
             chain_id: ganache_chain.chain_id,
+            scope: None,
         };
 
         let token = ewt::Token::sign(&signer_adapter.state.wallet, payload)
@@ -613,6 +778,235 @@ mod test {
 
         let session: Session = adapter.session_from_token(token.as_str()).await.unwrap();
         assert_eq!(session.uid, identity_address);
+        assert_eq!(Scope::Full, session.scope);
+    }
+
+    #[tokio::test]
+    async fn test_session_from_typed_data_token() {
+        let mut config = GANACHE_CONFIG.clone();
+        config.legacy_auth_tokens = false;
+
+        let adapter = Ethereum::init(KEYSTORES[&LEADER].clone(), &config)
+            .expect("should init Leader ethereum adapter");
+        let signer_adapter = Ethereum::init(KEYSTORES[&ADVERTISER].clone(), &config)
+            .expect("should init Advertiser ethereum adapter")
+            .unlock()
+            .expect("should unlock eth adapter");
+
+        let ganache_chain = GANACHE_1337.clone();
+
+        // round-trip of `get_auth()` & `session_from_token()`
+        {
+            let token = signer_adapter
+                .get_auth(ganache_chain.chain_id, adapter.whoami())
+                .expect("Should get auth token");
+
+            let session = adapter
+                .session_from_token(&token)
+                .await
+                .expect("Should create Session from typed data token");
+
+            assert_eq!(*ADVERTISER, session.uid);
+            assert_eq!(ganache_chain, session.chain);
+            assert_eq!(Scope::Full, session.scope);
+        }
+
+        let payload = Payload {
+            id: adapter.whoami(),
+            era: Payload::era_at(Utc::now()),
+            address: *ADVERTISER,
+            identity: None,
+            chain_id: ganache_chain.chain_id,
+            scope: None,
+        };
+
+        // typed data token signed in the Domain of another chain
+        {
+            let token = ewt::Token::sign_typed_data(
+                &signer_adapter.state.wallet,
+                &Domain::new(ChainId::new(1)),
+                payload.clone(),
+            )
+            .expect("Should sign typed data");
+
+            let result = adapter.session_from_token(token.as_str()).await;
+            assert!(matches!(
+                result,
+                Err(Error::VerifyMessage(EwtVerifyError::SignerMismatch { .. }))
+            ));
+        }
+
+        // read-only token
+        {
+            let read_only = Payload {
+                scope: Some(Scope::ReadOnly),
+                ..payload.clone()
+            };
+            let token = ewt::Token::sign_typed_data(
+                &signer_adapter.state.wallet,
+                &Domain::new(ganache_chain.chain_id),
+                read_only,
+            )
+            .expect("Should sign typed data");
+
+            let session = adapter
+                .session_from_token(token.as_str())
+                .await
+                .expect("Should create Session from read-only token");
+            assert_eq!(*ADVERTISER, session.uid);
+            assert_eq!(Scope::ReadOnly, session.scope);
+
+            // the scope is signed, so it can't be removed from the token
+            let (header, rest) = token
+                .as_str()
+                .split_once('.')
+                .expect("Should have a header");
+            let (_payload, signature) = rest.split_once('.').expect("Should have a signature");
+            let full_payload = base64::encode_config(
+                serde_json::to_string(&payload).expect("Should serialize"),
+                base64::URL_SAFE_NO_PAD,
+            );
+            let tampered = format!("{header}.{full_payload}.{signature}");
+
+            let result = adapter.session_from_token(&tampered).await;
+            assert!(matches!(
+                result,
+                Err(Error::VerifyMessage(EwtVerifyError::SignerMismatch { .. }))
+            ));
+        }
+
+        // legacy tokens are not accepted
+        {
+            let token = ewt::Token::sign(&signer_adapter.state.wallet, payload)
+                .expect("Should sign legacy token");
+
+            let result = adapter.session_from_token(token.as_str()).await;
+            assert!(matches!(result, Err(Error::LegacyAuthenticationToken)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_from_token_expiry() {
+        let adapter = Ethereum::init(
+            Options {
+                token_validity: Some(Duration::from_secs(10 * 60)),
+                ..KEYSTORES[&LEADER].clone()
+            },
+            &GANACHE_CONFIG,
+        )
+        .expect("should init Leader ethereum adapter");
+        let signer_adapter = Ethereum::init(KEYSTORES[&ADVERTISER].clone(), &GANACHE_CONFIG)
+            .expect("should init Advertiser ethereum adapter")
+            .unlock()
+            .expect("should unlock eth adapter");
+
+        let current_era = Payload::era_at(Utc::now());
+        let token_of_era = |era: i64| {
+            let payload = Payload {
+                id: adapter.whoami(),
+                era,
+                address: *ADVERTISER,
+                identity: None,
+                chain_id: GANACHE_1337.chain_id,
+                scope: None,
+            };
+
+            ewt::Token::sign(&signer_adapter.state.wallet, payload)
+                .expect("Should sign the Payload")
+        };
+
+        // within the validity window
+        {
+            let session = adapter
+                .session_from_token(token_of_era(current_era - 5).as_str())
+                .await
+                .expect("Should create Session");
+
+            assert_eq!(current_era - 5, session.era);
+        }
+
+        // past the validity window
+        {
+            let result = adapter
+                .session_from_token(token_of_era(current_era - 11).as_str())
+                .await;
+
+            assert!(
+                matches!(result, Err(Error::Expired { era, .. }) if era == current_era - 11),
+                "Expected an expired token, got: {result:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_from_token_era_grace() {
+        let mut config = GANACHE_CONFIG.clone();
+        config.auth_era_grace = 3;
+
+        let adapter = Ethereum::init(
+            Options {
+                token_validity: Some(Duration::from_secs(10 * 60)),
+                ..KEYSTORES[&LEADER].clone()
+            },
+            &config,
+        )
+        .expect("should init Leader ethereum adapter");
+        let signer_adapter = Ethereum::init(KEYSTORES[&ADVERTISER].clone(), &config)
+            .expect("should init Advertiser ethereum adapter")
+            .unlock()
+            .expect("should unlock eth adapter");
+
+        let current_era = Payload::era_at(Utc::now());
+        let token_of_era = |era: i64| {
+            let payload = Payload {
+                id: adapter.whoami(),
+                era,
+                address: *ADVERTISER,
+                identity: None,
+                chain_id: GANACHE_1337.chain_id,
+                scope: None,
+            };
+
+            ewt::Token::sign(&signer_adapter.state.wallet, payload)
+                .expect("Should sign the Payload")
+        };
+
+        // current era
+        {
+            let session = adapter
+                .session_from_token(token_of_era(current_era).as_str())
+                .await
+                .expect("Should create Session");
+
+            assert_eq!(current_era, session.era);
+            assert!(!session.era_grace);
+            // valid until the end of the 10th era after the current one
+            let valid_until = session.valid_until.expect("Should have a validity");
+            assert_eq!(current_era + 11, Payload::era_at(valid_until));
+        }
+
+        // 1 era past the validity window, within the grace
+        {
+            let session = adapter
+                .session_from_token(token_of_era(current_era - 11).as_str())
+                .await
+                .expect("Should create Session");
+
+            assert_eq!(current_era - 11, session.era);
+            assert!(session.era_grace);
+        }
+
+        // past the grace
+        {
+            let result = adapter
+                .session_from_token(token_of_era(current_era - 14).as_str())
+                .await;
+
+            assert!(
+                matches!(result, Err(Error::Expired { era, .. }) if era == current_era - 14),
+                "Expected an expired token, got: {result:?}"
+            );
+        }
     }
 
     #[tokio::test]
@@ -801,4 +1195,134 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn detailed_deposit_with_counterfactual_balance() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let web3 = GANACHE_1.init_web3().expect("Init web3");
+
+        // deploy contracts
+        let token = Erc20Token::deploy(&web3, 1_000)
+            .await
+            .expect("Correct parameters are passed to the Token constructor.");
+        let outpace = Outpace::deploy(&web3)
+            .await
+            .expect("Correct parameters are passed to the OUTPACE constructor.");
+        let sweeper = Sweeper::deploy(&web3)
+            .await
+            .expect("Correct parameters are passed to the Sweeper constructor.");
+
+        let mut chain = GANACHE_1.clone();
+        chain.outpace = outpace.address;
+        chain.sweeper = Some(sweeper.address);
+
+        let mut config = GANACHE_CONFIG.clone();
+        let ganache_1 = config
+            .chains
+            .get_mut("Ganache #1")
+            .expect("Should have Ganache #1 already in config");
+        ganache_1.chain = chain.clone();
+        ganache_1
+            .tokens
+            .insert("Deployed TOKEN".into(), token.info.clone());
+
+        let chain_of = ChainOf::new(chain, token.info.clone());
+        let channel = Channel {
+            leader: IDS[&LEADER],
+            follower: IDS[&FOLLOWER],
+            guardian: *GUARDIAN,
+            token: token.info.address,
+            nonce: Nonce::from(1_u32),
+        };
+        let channel_context = chain_of.with(channel);
+
+        let eth_adapter = Ethereum::init(KEYSTORES[&LEADER].clone(), &config)
+            .expect("should init ethereum adapter")
+            .unlock()
+            .expect("should unlock eth adapter");
+
+        let spender = *ADVERTISER;
+        let counterfactual_address =
+            get_counterfactual_address(sweeper.address, &channel, outpace.address, spender);
+
+        let one_token = BigNum::with_precision(1, token.info.precision.into());
+        let ten_tokens = BigNum::with_precision(10, token.info.precision.into());
+
+        // Counterfactual balance only
+        {
+            token
+                .set_balance(
+                    LEADER.to_bytes(),
+                    counterfactual_address.to_bytes(),
+                    &one_token,
+                )
+                .await
+                .expect("Failed to set balance");
+
+            let detailed = eth_adapter
+                .get_deposit_detailed(&channel_context, spender)
+                .await
+                .expect("should get detailed deposit");
+
+            assert_eq!(
+                DetailedDeposit {
+                    total: BigNum::from(0),
+                    breakdown: Some(DepositBreakdown {
+                        outpace: BigNum::from(0),
+                        pending: one_token.clone(),
+                    }),
+                },
+                detailed
+            );
+        }
+
+        // Regular OUTPACE deposit alongside the counterfactual balance
+        {
+            token
+                .set_balance(LEADER.to_bytes(), spender.to_bytes(), &ten_tokens)
+                .await
+                .expect("Failed to set balance");
+            outpace
+                .deposit(&channel, spender.to_bytes(), &ten_tokens)
+                .await
+                .expect("Should deposit funds");
+
+            let detailed = eth_adapter
+                .get_deposit_detailed(&channel_context, spender)
+                .await
+                .expect("should get detailed deposit");
+
+            assert_eq!(
+                DetailedDeposit {
+                    total: ten_tokens.clone(),
+                    breakdown: Some(DepositBreakdown {
+                        outpace: ten_tokens.clone(),
+                        pending: one_token.clone(),
+                    }),
+                },
+                detailed
+            );
+        }
+
+        // Without a Sweeper there is no breakdown
+        {
+            let mut no_sweeper = channel_context.clone();
+            no_sweeper.chain.sweeper = None;
+
+            let detailed = eth_adapter
+                .get_deposit_detailed(&no_sweeper, spender)
+                .await
+                .expect("should get detailed deposit");
+
+            assert_eq!(
+                DetailedDeposit {
+                    total: ten_tokens,
+                    breakdown: None,
+                },
+                detailed
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,138 @@
+//! [EIP-712](https://eips.ethereum.org/EIPS/eip-712) typed structured data
+//! used for signing the authentication [`Token`](super::ewt::Token)s.
+//!
+//! Unlike the opaque `keccak256("header.payload")` message of the legacy
+//! Ethereum Web Token, wallets can show the user every field of the typed data:
+//!
+//! ```text
+//! EIP712Domain(string name,string version,uint256 chainId)
+//! AuthToken(address id,int256 era,address address,address identity)
+//! ```
+//!
+//! A token with a [`Scope::ReadOnly`] is signed as a
+//! `ReadOnlyAuthToken` with the same fields instead.
+//!
+//! The [`ChainId`] of the token is part of the [`Domain`],
+//! which means that a token signed for one chain can never be verified for another one.
+
+use primitives::{Address, ChainId};
+use web3::{
+    ethabi::{encode, Token},
+    signing::keccak256,
+    types::{H160, U256},
+};
+
+use crate::primitives::Scope;
+
+use super::ewt::Payload;
+
+/// The [`Domain::name`] of the authentication tokens
+pub const DOMAIN_NAME: &str = "AdEx Validator";
+/// The [`Domain::version`] of the authentication tokens
+pub const DOMAIN_VERSION: &str = "1";
+
+const DOMAIN_TYPE: &str = "EIP712Domain(string name,string version,uint256 chainId)";
+const AUTH_TOKEN_TYPE: &str = "AuthToken(address id,int256 era,address address,address identity)";
+const READ_ONLY_AUTH_TOKEN_TYPE: &str =
+    "ReadOnlyAuthToken(address id,int256 era,address address,address identity)";
+
+/// The EIP-712 domain of the authentication tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Domain {
+    pub name: String,
+    pub version: String,
+    pub chain_id: ChainId,
+}
+
+impl Domain {
+    /// Creates a new [`Domain`] with [`DOMAIN_NAME`] and [`DOMAIN_VERSION`] for the given chain.
+    pub fn new(chain_id: ChainId) -> Self {
+        Self {
+            name: DOMAIN_NAME.to_string(),
+            version: DOMAIN_VERSION.to_string(),
+            chain_id,
+        }
+    }
+
+    /// `keccak256(typeHash || encodeData(domain))`
+    pub fn separator(&self) -> [u8; 32] {
+        keccak256(&encode(&[
+            Token::FixedBytes(keccak256(DOMAIN_TYPE.as_bytes()).to_vec()),
+            Token::FixedBytes(keccak256(self.name.as_bytes()).to_vec()),
+            Token::FixedBytes(keccak256(self.version.as_bytes()).to_vec()),
+            Token::Uint(U256::from(self.chain_id.to_u32())),
+        ]))
+    }
+}
+
+/// The `hashStruct` of the `AuthToken` message of the [`Payload`].
+///
+/// The [`ChainId`] of the [`Payload`] is **not** part of the message,
+/// it's included in the [`Domain`] instead.
+/// A missing [`Payload::identity`] is encoded as the zero address.
+pub fn hash_payload(payload: &Payload) -> [u8; 32] {
+    let address = |address: Address| Token::Address(H160(address.to_bytes()));
+    let token_type = match payload.scope.unwrap_or_default() {
+        Scope::Full => AUTH_TOKEN_TYPE,
+        Scope::ReadOnly => READ_ONLY_AUTH_TOKEN_TYPE,
+    };
+
+    keccak256(&encode(&[
+        Token::FixedBytes(keccak256(token_type.as_bytes()).to_vec()),
+        address(payload.id.to_address()),
+        Token::Int(int256(payload.era)),
+        address(payload.address),
+        address(
+            payload
+                .identity
+                .unwrap_or_else(|| Address::from([0_u8; 20])),
+        ),
+    ]))
+}
+
+/// The final hash which is signed:
+///
+/// `keccak256("\x19\x01" || domainSeparator || hashStruct(message))`
+pub fn typed_data_hash(domain: &Domain, payload: &Payload) -> [u8; 32] {
+    let mut bytes = b"\x19\x01".to_vec();
+    bytes.extend(domain.separator());
+    bytes.extend(hash_payload(payload));
+
+    keccak256(&bytes)
+}
+
+/// Sign-extends the value to a two's complement `int256`.
+fn int256(value: i64) -> U256 {
+    let mut bytes = if value < 0 { [0xff; 32] } else { [0; 32] };
+    bytes[24..].copy_from_slice(&value.to_be_bytes());
+
+    U256::from_big_endian(&bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn domain_type_hash() {
+        // the well-known type hash of `EIP712Domain(string name,string version,uint256 chainId)`
+        assert_eq!(
+            "c2f8787176b8ac6bf7215b4adcc1e069bf4ab82d9ab1df05a57a91d425935b6e",
+            hex::encode(keccak256(DOMAIN_TYPE.as_bytes()))
+        );
+    }
+
+    #[test]
+    fn int256_is_sign_extended() {
+        assert_eq!(U256::from(100_000), int256(100_000));
+        assert_eq!(U256::MAX, int256(-1));
+    }
+
+    #[test]
+    fn separator_depends_on_chain() {
+        assert_ne!(
+            Domain::new(ChainId::new(1)).separator(),
+            Domain::new(ChainId::new(1337)).separator()
+        );
+    }
+}
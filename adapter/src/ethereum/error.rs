@@ -1,6 +1,7 @@
 use crate::Error as AdapterError;
 use primitives::{
-    address::Error as AddressError, big_num::ParseBigIntError, ChainId, ChannelId, ValidatorId,
+    address::Error as AddressError, big_num::ParseBigIntError, Address, ChainId, ChannelId,
+    ValidatorId,
 };
 use thiserror::Error;
 
@@ -9,25 +10,27 @@ use super::ewt::Payload;
 impl From<Error> for AdapterError {
     fn from(error: Error) -> Self {
         match error {
-            err @ Error::Keystore(..) => AdapterError::adapter(err),
-            Error::WalletUnlock(err) => AdapterError::wallet_unlock(err),
-            err @ Error::Web3(..) => AdapterError::adapter(err),
-            err @ Error::InvalidChannelId { .. } => AdapterError::adapter(err),
-            err @ Error::ChannelInactive(..) => AdapterError::adapter(err),
-            err @ Error::ChainNotWhitelisted(..) => AdapterError::adapter(err),
-            err @ Error::InvalidDepositAsset(..) => AdapterError::adapter(err),
-            err @ Error::BigNumParsing(..) => AdapterError::adapter(err),
-            err @ Error::SignMessage(..) => AdapterError::adapter(err),
-            err @ Error::VerifyMessage(..) => AdapterError::adapter(err),
-            err @ Error::ContractInitialization(..) => AdapterError::adapter(err),
-            err @ Error::ContractQuerying(..) => AdapterError::adapter(err),
-            err @ Error::VerifyAddress(..) => AdapterError::adapter(err),
+            err @ Error::Keystore(..) => AdapterError::internal(err),
+            err @ Error::WalletUnlock(..) => AdapterError::internal(err),
+            err @ (Error::Web3(..) | Error::ContractQuerying(..)) => {
+                let retryable = err.is_retryable();
+                AdapterError::rpc_transport(err, retryable)
+            }
+            err @ Error::InvalidChannelId { .. } => AdapterError::domain(err),
+            err @ Error::ChannelInactive(..) => AdapterError::domain(err),
+            err @ Error::ChainNotWhitelisted(..) => AdapterError::domain(err),
+            err @ Error::InvalidDepositAsset(..) => AdapterError::domain(err),
+            err @ Error::BigNumParsing(..) => AdapterError::internal(err),
+            err @ Error::SignMessage(..) => AdapterError::internal(err),
+            err @ Error::VerifyMessage(..) => AdapterError::authentication(err),
+            err @ Error::ContractInitialization(..) => AdapterError::internal(err),
+            err @ Error::VerifyAddress(..) => AdapterError::domain(err),
             err @ Error::AuthenticationTokenNotIntendedForUs { .. } => {
                 AdapterError::authentication(err)
             }
-            err @ Error::InsufficientAuthorizationPrivilege { .. } => {
-                AdapterError::authorization(err)
-            }
+            err @ Error::LegacyAuthenticationToken => AdapterError::authentication(err),
+            err @ Error::Expired { .. } => AdapterError::authentication(err),
+            err @ Error::InsufficientAuthorizationPrivilege => AdapterError::authorization(err),
         }
     }
 }
@@ -74,6 +77,30 @@ pub enum Error {
     },
     #[error("Insufficient privilege")]
     InsufficientAuthorizationPrivilege,
+    /// The authentication token is in the legacy Ethereum Web Token format
+    /// and [`Config.legacy_auth_tokens`](primitives::Config::legacy_auth_tokens) is disabled.
+    #[error("Legacy authentication tokens are not accepted, use an EIP-712 typed data token")]
+    LegacyAuthenticationToken,
+    /// The authentication token is older than the configured validity,
+    /// see [`Options.token_validity`](super::Options::token_validity).
+    #[error("Authentication token of era {era} has expired, current era: {current_era}")]
+    Expired { era: i64, current_era: i64 },
+}
+
+impl Error {
+    /// Whether or not the RPC call can be retried, i.e. the RPC could not be reached
+    /// and not that it has responded with an error.
+    fn is_retryable(&self) -> bool {
+        let web3_error = match self {
+            Error::Web3(err) | Error::ContractQuerying(web3::contract::Error::Api(err)) => err,
+            _ => return false,
+        };
+
+        matches!(
+            web3_error,
+            web3::Error::Unreachable | web3::Error::Transport(..) | web3::Error::Io(..)
+        )
+    }
 }
 
 #[derive(Debug, Error)]
@@ -148,6 +175,10 @@ pub enum EwtVerifyError {
     PayloadDeserialization(#[from] serde_json::Error),
     #[error("Payload is not a valid UTF-8 string: {0}")]
     PayloadUtf8(#[from] std::str::Utf8Error),
+    /// The signer recovered from an EIP-712 typed data token is not the `Payload.address`,
+    /// e.g. when the token was signed in the `Domain` of another chain.
+    #[error("Recovered signer {signer} is not the Payload.address ({address})")]
+    SignerMismatch { signer: Address, address: Address },
 }
 
 #[cfg(test)]
@@ -164,4 +195,11 @@ mod test {
         // Ethereum adapter should be Sync!
         assert_sync::<Error>();
     }
+
+    #[test]
+    fn not_whitelisted_chain_is_a_domain_error() {
+        let error = AdapterError::from(Error::ChainNotWhitelisted(ChainId::new(1)));
+
+        assert_eq!(crate::ErrorKind::Domain, error.kind());
+    }
 }
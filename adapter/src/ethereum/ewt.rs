@@ -1,4 +1,6 @@
-use std::fmt;
+use std::{fmt, time::Duration};
+
+use chrono::{DateTime, Utc};
 
 use ethsign::{SecretKey, Signature};
 use once_cell::sync::Lazy;
@@ -6,13 +8,22 @@ use primitives::{Address, ChainId, ValidatorId};
 use serde::{Deserialize, Serialize};
 use web3::signing::keccak256;
 
+use crate::primitives::Scope;
+
 use super::{
+    eip712::{self, Domain},
     error::{EwtSigningError, EwtVerifyError},
     to_ethereum_signed, Electrum,
 };
 
+/// The length of a single [`Payload::era`]: 1 minute.
+pub const ERA_LENGTH: Duration = Duration::from_secs(60);
+
 pub static ETH_SIGN_SUFFIX: Lazy<Vec<u8>> = Lazy::new(|| hex::decode("01").unwrap());
 
+/// The signature mode suffix of [EIP-712](eip712) typed data signatures.
+pub static EIP712_SIGN_SUFFIX: Lazy<Vec<u8>> = Lazy::new(|| hex::decode("00").unwrap());
+
 pub static ETH_HEADER: Lazy<Header> = Lazy::new(|| Header {
     header_type: "JWT".to_string(),
     alg: "ETH".to_string(),
@@ -21,6 +32,15 @@ pub static ETH_HEADER: Lazy<Header> = Lazy::new(|| Header {
 pub static ETH_HEADER_BASE64: Lazy<String> =
     Lazy::new(|| base64_encode(&*ETH_HEADER).expect("Header should be serializable"));
 
+/// The [`Header`] of tokens signed as [EIP-712](eip712) typed data.
+pub static EIP712_HEADER: Lazy<Header> = Lazy::new(|| Header {
+    header_type: "JWT".to_string(),
+    alg: "EIP712".to_string(),
+});
+
+pub static EIP712_HEADER_BASE64: Lazy<String> =
+    Lazy::new(|| base64_encode(&*EIP712_HEADER).expect("Header should be serializable"));
+
 /// Serializes the value into a JSON and then it encodes the result using `base64`
 /// Base64 encoding is performed using the [`base64::URL_SAFE_NO_PAD`] configuration
 fn base64_encode<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
@@ -50,9 +70,19 @@ pub struct Payload {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub identity: Option<Address>,
     pub chain_id: ChainId,
+    /// The [`Scope`] of the token, a token without a scope has [`Scope::Full`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<Scope>,
 }
 
 impl Payload {
+    /// The era at the given moment, i.e. the number of [`ERA_LENGTH`]s since the Unix epoch.
+    pub fn era_at(datetime: DateTime<Utc>) -> i64 {
+        datetime
+            .timestamp_millis()
+            .div_euclid(ERA_LENGTH.as_millis() as i64)
+    }
+
     /// Decodes the [`Payload`] from a base64 encoded json string
     // TODO: replace with own error?
     pub fn base64_decode(encoded_json: &str) -> Result<Self, EwtVerifyError> {
@@ -80,10 +110,15 @@ pub struct Token {
     pub header: Header,
     pub payload: Payload,
     /// The hashed value of the message:
-    /// `keccak256(header_json_base64.payload_json_base64)`
+    /// - for [`ETH_HEADER`] - `keccak256(header_json_base64.payload_json_base64)`
+    /// - for [`EIP712_HEADER`] - the [typed data hash](eip712::typed_data_hash) of the payload
     pub message_hash: [u8; 32],
-    /// The signature after signing the message `to_ethereum_signed(keccak256("{header_base64}.{payload_base64}"))`
-    /// The signature is in the form of `{r}{s}{v}{mode}` where `mode` is `01` for Ethereum Signature
+    /// The signature after signing the message:
+    /// - for [`ETH_HEADER`] - `to_ethereum_signed(keccak256("{header_base64}.{payload_base64}"))`
+    /// - for [`EIP712_HEADER`] - the signed [typed data hash](eip712::typed_data_hash) of the payload
+    ///
+    /// The signature is in the form of `{r}{s}{v}{mode}` where `mode` is
+    /// `01` for Ethereum Signature and `00` for EIP-712 typed data.
     pub signature: Vec<u8>,
     /// Will result in authentication token string in the format of:
     /// `{header_base64_encoded}.{payload_base64_encoded}.{signature_base64_encoded}`
@@ -132,6 +167,49 @@ impl Token {
         })
     }
 
+    /// Signs a payload as [EIP-712](eip712) typed data in the given [`Domain`].
+    /// For the [`Header`] it uses [`EIP712_HEADER`].
+    ///
+    /// The token is in the same `header.payload.signature` format as the
+    /// Ethereum Web Token, but the signed message is the
+    /// [typed data hash](eip712::typed_data_hash) of the payload.
+    pub fn sign_typed_data(
+        signer: &SecretKey,
+        domain: &Domain,
+        payload: Payload,
+    ) -> Result<Self, EwtSigningError> {
+        let header = EIP712_HEADER.clone();
+        let payload_encoded =
+            base64_encode(&payload).map_err(EwtSigningError::PayloadSerialization)?;
+
+        let message_hash = eip712::typed_data_hash(domain, &payload);
+
+        let mut signature = signer
+            .sign(&message_hash)
+            .map_err(|err| EwtSigningError::SigningMessage(err.to_string()))?
+            .to_electrum()
+            .to_vec();
+        signature.extend(EIP712_SIGN_SUFFIX.as_slice());
+
+        let signature_encoded = base64::encode_config(&signature, base64::URL_SAFE_NO_PAD);
+
+        Ok(Self {
+            header,
+            payload,
+            message_hash,
+            signature,
+            token: format!(
+                "{}.{}.{}",
+                &*EIP712_HEADER_BASE64, payload_encoded, signature_encoded
+            ),
+        })
+    }
+
+    /// Verifies both the legacy Ethereum Web Token ([`ETH_HEADER`])
+    /// and the [EIP-712](eip712) typed data token ([`EIP712_HEADER`]).
+    ///
+    /// Typed data tokens are verified in the [`Domain`] of the [`Payload::chain_id`]
+    /// and the recovered signer must be the [`Payload::address`].
     pub fn verify(token: &str) -> Result<(Token, VerifyPayload), EwtVerifyError> {
         if token.len() < 16 {
             return Err(EwtVerifyError::InvalidTokenLength);
@@ -144,10 +222,12 @@ impl Token {
             .zip(token_parts.get(2))
             .ok_or(EwtVerifyError::InvalidToken)?;
 
-        // if the encoded value of the header matches the expected one
-        // we have a valid EWT header
-        let header = if header_encoded == &*ETH_HEADER_BASE64 {
-            ETH_HEADER.clone()
+        // if the encoded value of the header matches one of the expected ones
+        // we have a valid token header
+        let (header, sign_suffix) = if header_encoded == &*ETH_HEADER_BASE64 {
+            (ETH_HEADER.clone(), ETH_SIGN_SUFFIX.as_slice())
+        } else if header_encoded == &*EIP712_HEADER_BASE64 {
+            (EIP712_HEADER.clone(), EIP712_SIGN_SUFFIX.as_slice())
         } else {
             return Err(EwtVerifyError::InvalidHeader);
         };
@@ -158,8 +238,8 @@ impl Token {
             .map_err(EwtVerifyError::SignatureDecoding)?;
 
         // if it returns the same slice, then there was no suffix
-        // `01` suffix is the Ethereum Signature
-        let stripped_signature = match decoded_signature.strip_suffix(ETH_SIGN_SUFFIX.as_slice()) {
+        // `01` suffix is the Ethereum Signature, `00` is EIP-712
+        let stripped_signature = match decoded_signature.strip_suffix(sign_suffix) {
             // we have a valid signature only if a suffix **was removed**
             Some(stripped_signature) if stripped_signature != decoded_signature => {
                 Ok(stripped_signature)
@@ -170,8 +250,16 @@ impl Token {
         let signature =
             Signature::from_electrum(stripped_signature).ok_or(EwtVerifyError::InvalidSignature)?;
 
-        let message_hash = keccak256(format!("{}.{}", header_encoded, payload_encoded).as_bytes());
-        let recover_message = to_ethereum_signed(&message_hash);
+        let (message_hash, recover_message) = if header == *EIP712_HEADER {
+            let message_hash = eip712::typed_data_hash(&Domain::new(payload.chain_id), &payload);
+
+            (message_hash, message_hash)
+        } else {
+            let message_hash =
+                keccak256(format!("{}.{}", header_encoded, payload_encoded).as_bytes());
+
+            (message_hash, to_ethereum_signed(&message_hash))
+        };
 
         // recover the public key using the signature & the recovery message
         let public_key = signature
@@ -180,6 +268,15 @@ impl Token {
 
         let address = Address::from(*public_key.address());
 
+        // a typed data token signed in a different `Domain` (e.g. for another chain)
+        // recovers a different signer
+        if header == *EIP712_HEADER && address != payload.address {
+            return Err(EwtVerifyError::SignerMismatch {
+                signer: address,
+                address: payload.address,
+            });
+        }
+
         let token = Token {
             header,
             payload: payload.clone(),
@@ -219,7 +316,7 @@ mod test {
     };
 
     use super::*;
-    use crate::ethereum::{test_util::KEYSTORES, Ethereum};
+    use crate::ethereum::{error::EwtVerifyError, test_util::KEYSTORES, Ethereum};
 
     #[test]
     fn should_generate_correct_ewt_sign_and_verify() {
@@ -235,6 +332,7 @@ mod test {
             identity: None,
             // Eth
             chain_id: ChainId::new(1),
+            scope: None,
         };
         let wallet = eth_adapter.state.wallet;
         let token = Token::sign(&wallet, payload).expect("failed to generate ewt signature");
@@ -250,6 +348,7 @@ mod test {
                 identity: None,
                 // Eth
                 chain_id: ChainId::new(1),
+                scope: None,
             },
         };
 
@@ -262,4 +361,49 @@ mod test {
             "generated wrong verification payload"
         );
     }
+
+    #[test]
+    fn should_sign_and_verify_typed_data_token() {
+        let eth_adapter = Ethereum::init(KEYSTORES[&CREATOR].clone(), &GANACHE_CONFIG)
+            .expect("should init ethereum adapter")
+            .unlock()
+            .expect("should unlock eth adapter");
+
+        let payload = Payload {
+            id: ValidatorId::from(*LEADER),
+            era: 100_000,
+            address: *CREATOR,
+            identity: None,
+            chain_id: ChainId::new(1337),
+            scope: None,
+        };
+        let wallet = eth_adapter.state.wallet;
+
+        // round-trip
+        {
+            let token =
+                Token::sign_typed_data(&wallet, &Domain::new(payload.chain_id), payload.clone())
+                    .expect("Should sign typed data");
+            assert!(token.as_str().starts_with(&*EIP712_HEADER_BASE64));
+
+            let (verified_token, verification) =
+                Token::verify(token.as_str()).expect("Should verify typed data token");
+
+            assert_eq!(verified_token, token);
+            assert_eq!(*CREATOR, verification.from);
+            assert_eq!(payload, verification.payload);
+        }
+
+        // signed in the Domain of another chain
+        {
+            let token =
+                Token::sign_typed_data(&wallet, &Domain::new(ChainId::new(1)), payload.clone())
+                    .expect("Should sign typed data");
+
+            assert!(matches!(
+                Token::verify(token.as_str()),
+                Err(EwtVerifyError::SignerMismatch { address, .. }) if address == *CREATOR
+            ));
+        }
+    }
 }
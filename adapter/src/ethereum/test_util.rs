@@ -9,8 +9,8 @@ use web3::{
 };
 
 use primitives::{
-    channel::{Channel, Nonce},
-    config::{ChainInfo, TokenInfo, GANACHE_CONFIG},
+    channel::Channel,
+    config::{ChainInfo, PayoutRounding, TokenInfo, GANACHE_CONFIG},
     test_util::{
         ADVERTISER, ADVERTISER_2, CREATOR, FOLLOWER, GUARDIAN, GUARDIAN_2, LEADER, PUBLISHER,
     },
@@ -145,17 +145,19 @@ fn keystore_options(file_name: &str, password: &str) -> Options {
     Options {
         keystore_file: keystore_file.display().to_string(),
         keystore_pwd: password.to_string(),
+        token_validity: None,
     }
 }
 
 pub fn get_test_channel(token_address: Address) -> Channel {
-    Channel {
-        leader: ValidatorId::from(&LEADER),
-        follower: ValidatorId::from(&FOLLOWER),
-        guardian: *GUARDIAN,
-        token: token_address,
-        nonce: Nonce::from(12345_u32),
-    }
+    Channel::builder()
+        .leader(ValidatorId::from(&LEADER))
+        .follower(ValidatorId::from(&FOLLOWER))
+        .guardian(*GUARDIAN)
+        .token(token_address)
+        .nonce(12345_u32)
+        .build()
+        .expect("Should build a valid Channel")
 }
 
 /// The Sweeper contract
@@ -279,7 +281,12 @@ impl Erc20Token {
             precision: NonZeroU8::new(18).expect("should create NonZeroU8"),
             // 0.000_001
             min_validator_fee: BigNum::from(1_000_000_000_000),
+            allow_zero_fees: false,
+            max_validator_fee: None,
             address: token_address,
+            payout_rounding: PayoutRounding::default(),
+            min_payout: None,
+            event_dedup_window: None,
         };
 
         Ok(Self {
@@ -9,13 +9,15 @@ pub use {
         Adapter,
     },
     dummy::Dummy,
-    error::Error,
+    error::{Error, Kind as ErrorKind},
     ethereum::Ethereum,
 };
 
 /// Primitives used by the [`Adapter`].
 /// Including re-exported types from the `primitives` crate that are being used.
 pub mod primitives {
+    use chrono::{DateTime, Utc};
+    use parse_display::{Display, FromStr};
     use serde::{Deserialize, Serialize};
 
     pub use ::primitives::{
@@ -29,6 +31,10 @@ pub mod primitives {
     /// Returned by [`crate::client::Locked::get_deposit`]
     pub type Deposit = ::primitives::Deposit<primitives::BigNum>;
 
+    /// The [`DetailedDeposit`] struct with [`BigNum`] values.
+    /// Returned by [`crate::client::Locked::get_deposit_detailed`]
+    pub type DetailedDeposit = ::primitives::DetailedDeposit<primitives::BigNum>;
+
     /// A helper type that allows you to use either of them
     /// and dereference the adapter when calling for example an application
     /// with a concrete implementation of the [`crate::Adapter`].
@@ -55,6 +61,39 @@ pub mod primitives {
         pub uid: Address,
         /// Authenticated for [`Chain`].
         pub chain: Chain,
+        /// The [`Scope`] of the authentication token.
+        ///
+        /// Sessions without a scope (e.g. cached before the scope was introduced)
+        /// are treated as [`Scope::Full`].
+        #[serde(default)]
+        pub scope: Scope,
+        /// Whether the authentication token was accepted past its validity,
+        /// within the [`Config.auth_era_grace`](primitives::Config::auth_era_grace).
+        #[serde(default)]
+        pub era_grace: bool,
+        /// Until when the authentication token is valid,
+        /// afterwards it is accepted only within the era grace window, if any.
+        ///
+        /// `None` if the token does not expire.
+        #[serde(default)]
+        pub valid_until: Option<DateTime<Utc>>,
+    }
+
+    /// The scope of an authentication token.
+    ///
+    /// A [`Scope::ReadOnly`] token can be used for reading data (e.g. analytics)
+    /// but it can never be used for creating or modifying any data.
+    #[derive(
+        Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Display, FromStr,
+    )]
+    #[serde(rename_all = "snake_case")]
+    #[display(style = "snake_case")]
+    pub enum Scope {
+        /// Full access
+        #[default]
+        Full,
+        /// Read-only access
+        ReadOnly,
     }
 }
 
@@ -8,7 +8,7 @@ use adapter::{
 use async_trait::async_trait;
 
 use primitives::{
-    config::TokenInfo,
+    config::{PayoutRounding, TokenInfo},
     test_util::{ADDRESS_1, DUMMY_CAMPAIGN},
     Address, BigNum, Chain, ChainId, ChainOf, Channel, ValidatorId,
 };
@@ -36,6 +36,10 @@ impl Locked for Dummy {
         todo!()
     }
 
+    fn recover_signer(&self, _message: &[u8], _signature: &str) -> Result<Address, crate::Error> {
+        todo!()
+    }
+
     /// Creates a `Session` from a provided Token by calling the Contract.
     /// Does **not** cache the (`Token`, `Session`) pair.
     async fn session_from_token(&self, _token: &str) -> Result<Session, crate::Error> {
@@ -84,10 +88,15 @@ async fn main() {
         token: TokenInfo {
             min_campaign_budget: 1_u64.into(),
             min_validator_fee: 1_u64.into(),
+            allow_zero_fees: false,
+            max_validator_fee: None,
             precision: NonZeroU8::new(18).unwrap(),
             address: "0x6B83e7D6B72c098d48968441e0d05658dc17Adb9"
                 .parse()
                 .unwrap(),
+            payout_rounding: PayoutRounding::default(),
+            min_payout: None,
+            event_dedup_window: None,
         },
         chain: Chain {
             chain_id: ChainId::new(1),
@@ -95,6 +104,7 @@ async fn main() {
             outpace: "0x0000000000000000000000000000000000000000"
                 .parse()
                 .unwrap(),
+            sweeper: None,
         },
     };
 
@@ -50,6 +50,7 @@ pub async fn get_preview_ad(Extension(state): Extension<Arc<State>>) -> Html<Str
         publisher_addr,
         // All passed tokens must be of the same price and decimals, so that the amounts can be accurately compared
         whitelisted_tokens,
+        token_conversion_rates: Default::default(),
         size: Some(Size::new(300, 100)),
         // TODO: Check this value
         navigator_language: Some("bg".into()),
@@ -156,6 +157,7 @@ pub async fn get_preview_video(Extension(state): Extension<Arc<State>>) -> Html<
         publisher_addr,
         // All passed tokens must be of the same price and decimals, so that the amounts can be accurately compared
         whitelisted_tokens,
+        token_conversion_rates: Default::default(),
         size: Some(Size::new(728, 90)),
         // TODO: Check this value
         navigator_language: Some("bg".into()),
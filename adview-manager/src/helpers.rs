@@ -148,6 +148,16 @@ pub fn get_unit_html_with_events(
                 ad_unit: ad_unit.ipfs,
                 ad_slot: options.market_slot,
                 referrer: Some("document.referrer".to_string()),
+                click_id: None,
+            },
+            // conversions are reported later on, with the `ClickId` of the attributed click
+            EventType::Conversion => return String::new(),
+            EventType::Custom(event_type) => Event::Custom {
+                event_type,
+                publisher: options.publisher_addr,
+                ad_unit: ad_unit.ipfs,
+                ad_slot: options.market_slot,
+                referrer: Some("document.referrer".to_string()),
             },
         };
         let events_body = InsertEventsRequest {
@@ -370,6 +380,7 @@ mod test {
             publisher_addr: *PUBLISHER,
             // All passed tokens must be of the same price and decimals, so that the amounts can be accurately compared
             whitelisted_tokens,
+            token_conversion_rates: Default::default(),
             size: Some(Size::new(300, 100)),
             navigator_language: Some("bg".into()),
             disabled_video: false,
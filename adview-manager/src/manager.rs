@@ -10,14 +10,14 @@ use adex_primitives::{
 };
 use async_std::{sync::RwLock, task::block_on};
 use chrono::{DateTime, Duration, Utc};
-use log::error;
+use log::{error, warn};
 use once_cell::sync::Lazy;
 use rand::Rng;
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
-    collections::{HashSet, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     sync::Arc,
 };
 use thiserror::Error;
@@ -67,8 +67,19 @@ pub struct Options {
     pub market_url: ApiUrl,
     pub market_slot: IPFS,
     pub publisher_addr: Address,
-    /// All passed tokens must be of the same price and decimals, so that the amounts can be accurately compared
+    /// All passed tokens must be of the same price, so that the amounts can be accurately compared,
+    /// unless [`Options::token_conversion_rates`] are set for them.
     pub whitelisted_tokens: HashSet<Address>,
+    /// The rates of the tokens to a common reference unit (e.g. USD), used for comparing
+    /// the prices of Campaigns in different tokens.
+    /// The rates are in [`UnifiedNum`] precision, e.g. `"25000000"` for a rate of `0.25`.
+    ///
+    /// When the Campaigns are in more than one token, the Campaigns
+    /// of a token without a rate are skipped.
+    ///
+    /// default: no rates
+    #[serde(default)]
+    pub token_conversion_rates: HashMap<Address, UnifiedNum>,
     pub size: Option<Size>,
     pub navigator_language: Option<String>,
     /// Whether or not to disable Video ads.
@@ -314,6 +325,14 @@ impl Manager {
         let random: f64 = rng.gen::<f64>() * (0x80000000_u64 as f64 - 1.0);
         let seed = BigNum::from(random as u64);
 
+        // Prices in different tokens can only be compared after converting them to the same unit
+        let multiple_tokens = ufs_campaigns
+            .iter()
+            .map(|ufs_campaign| ufs_campaign.campaign.channel.token)
+            .collect::<HashSet<_>>()
+            .len()
+            > 1;
+
         // Apply targeting, now with adView.* variables, and sort the resulting ad units
         let mut units_with_price = ufs_campaigns
             .iter()
@@ -325,6 +344,20 @@ impl Manager {
 
                 let campaign_id = ufs_campaign.campaign.id;
 
+                let conversion_rate = if multiple_tokens {
+                    let token = ufs_campaign.campaign.channel.token;
+
+                    match self.options.token_conversion_rates.get(&token) {
+                        Some(rate) => Some(*rate),
+                        None => {
+                            warn!(target: "auction", "Skipping {campaign_id:?}, no conversion rate for its token {token}");
+                            return vec![];
+                        }
+                    }
+                } else {
+                    None
+                };
+
                 let mut unit_input = targeting_input.clone().with_campaign(ufs_campaign.campaign.clone());
 
                 ufs_campaign
@@ -350,22 +383,33 @@ impl Manager {
 
                         output.show
                     })
-                    .map(|uwp| (uwp.clone(), campaign_id))
+                    .map(|uwp| (uwp.clone(), campaign_id, conversion_rate))
                     .collect()
             })
             .filter(|x| !(self.options.disabled_video && is_video(&x.0.unit)))
             .collect::<Vec<_>>();
 
-        units_with_price.sort_by(|b, a| match a.0.price.cmp(&b.0.price) {
-            Ordering::Equal => randomized_sort_pos(&a.0.unit, seed.clone())
-                .cmp(&randomized_sort_pos(&b.0.unit, seed.clone())),
-            ordering => ordering,
+        units_with_price.sort_by(|b, a| {
+            let by_price = match (a.2, b.2) {
+                (Some(a_rate), Some(b_rate)) => {
+                    a.0.price
+                        .checked_mul(&a_rate)
+                        .cmp(&b.0.price.checked_mul(&b_rate))
+                }
+                _ => a.0.price.cmp(&b.0.price),
+            };
+
+            match by_price {
+                Ordering::Equal => randomized_sort_pos(&a.0.unit, seed.clone())
+                    .cmp(&randomized_sort_pos(&b.0.unit, seed.clone())),
+                ordering => ordering,
+            }
         });
 
         // Update history
         let auction_winner = units_with_price.get(0);
 
-        if let Some((unit_with_price, campaign_id)) = auction_winner {
+        if let Some((unit_with_price, campaign_id, _)) = auction_winner {
             let history = self.history.read().await.clone();
 
             let new_entry = HistoryEntry {
@@ -389,7 +433,7 @@ impl Manager {
         }
 
         // Return the results, with a fallback unit if there is one
-        if let Some((unit_with_price, campaign_id)) = auction_winner {
+        if let Some((unit_with_price, campaign_id, _)) = auction_winner {
             let validators = ufs_campaigns
                 .iter()
                 .find_map(|ufs_campaign| {
@@ -467,6 +511,7 @@ mod test {
             publisher_addr: *PUBLISHER,
             // All passed tokens must be of the same price and decimals, so that the amounts can be accurately compared
             whitelisted_tokens,
+            token_conversion_rates: HashMap::new(),
             size: Some(Size::new(300, 100)),
             navigator_language: Some("bg".into()),
             disabled_video: false,
@@ -691,4 +736,160 @@ mod test {
 
         assert!(res.is_none());
     }
+
+    /// Mocks the same `/units-for-slot` response for all the validators of the [`Manager`].
+    async fn mock_units_for_slot(server: &MockServer, campaigns: Vec<Campaign>) {
+        let response = Response {
+            targeting_input_base: Input {
+                ad_view: None,
+                global: input::Global {
+                    ad_slot_id: DUMMY_IPFS[0],
+                    ad_slot_type: "legacy_300x100".to_string(),
+                    publisher_id: *PUBLISHER,
+                    country: None,
+                    event_type: IMPRESSION,
+                    seconds_since_epoch: Utc::now(),
+                    user_agent_os: None,
+                    user_agent_browser_family: None,
+                },
+                ad_unit_id: None,
+                balances: None,
+                campaign: None,
+                ad_slot: None,
+            },
+            accepted_referrers: vec![],
+            fallback_unit: None,
+            campaigns,
+        };
+
+        for validator in ["validator-1", "validator-2", "validator-3"] {
+            Mock::given(method("GET"))
+                .and(path(format!(
+                    "{}/v5/units-for-slot/{}",
+                    validator, DUMMY_IPFS[0]
+                )))
+                .respond_with(ResponseTemplate::new(200).set_body_json(&response))
+                .mount(server)
+                .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn next_ad_unit_normalizes_the_prices_of_different_tokens() {
+        // Chain #1337
+        let campaign_1337 = &CAMPAIGNS[0];
+        // Chain #1
+        let campaign_1 = &CAMPAIGNS[2];
+        assert_ne!(
+            campaign_1337.context.channel.token,
+            campaign_1.context.channel.token
+        );
+
+        let unit_1337 = AdUnit::from(&DUMMY_AD_UNITS[0]);
+        let unit_1 = AdUnit::from(&DUMMY_AD_UNITS[2]);
+        let campaigns = vec![
+            Campaign {
+                campaign: campaign_1337.context.clone(),
+                units_with_price: vec![UnitsWithPrice {
+                    unit: unit_1337.clone(),
+                    price: UnifiedNum::from_whole(1),
+                }],
+            },
+            Campaign {
+                campaign: campaign_1.context.clone(),
+                units_with_price: vec![UnitsWithPrice {
+                    unit: unit_1.clone(),
+                    price: UnifiedNum::from_whole(2),
+                }],
+            },
+        ];
+
+        // the higher price is worth less after the conversion
+        {
+            let server = MockServer::start().await;
+            mock_units_for_slot(&server, campaigns.clone()).await;
+
+            let mut manager = setup_manager(server.uri());
+            manager.options.token_conversion_rates = [
+                (
+                    campaign_1337.context.channel.token,
+                    UnifiedNum::from_whole(1),
+                ),
+                (
+                    campaign_1.context.channel.token,
+                    UnifiedNum::from_whole(0.25),
+                ),
+            ]
+            .into_iter()
+            .collect();
+
+            let next = manager
+                .get_next_ad_unit()
+                .await
+                .expect("Should get the next AdUnit")
+                .expect("Should have an AdUnit");
+
+            assert_eq!(unit_1337, next.unit);
+            assert_eq!(UnifiedNum::from_whole(1), next.price);
+        }
+
+        // the Campaigns of a token without a rate are skipped
+        {
+            let server = MockServer::start().await;
+            mock_units_for_slot(&server, campaigns).await;
+
+            let mut manager = setup_manager(server.uri());
+            manager.options.token_conversion_rates = [(
+                campaign_1.context.channel.token,
+                UnifiedNum::from_whole(0.25),
+            )]
+            .into_iter()
+            .collect();
+
+            let next = manager
+                .get_next_ad_unit()
+                .await
+                .expect("Should get the next AdUnit")
+                .expect("Should have an AdUnit");
+
+            assert_eq!(unit_1, next.unit);
+        }
+    }
+
+    #[tokio::test]
+    async fn next_ad_unit_compares_the_prices_of_a_single_token_as_they_are() {
+        let campaign = &CAMPAIGNS[0];
+        let cheap_unit = AdUnit::from(&DUMMY_AD_UNITS[0]);
+        let expensive_unit = AdUnit::from(&DUMMY_AD_UNITS[1]);
+
+        let server = MockServer::start().await;
+        mock_units_for_slot(
+            &server,
+            vec![Campaign {
+                campaign: campaign.context.clone(),
+                units_with_price: vec![
+                    UnitsWithPrice {
+                        unit: cheap_unit,
+                        price: UnifiedNum::from_whole(1),
+                    },
+                    UnitsWithPrice {
+                        unit: expensive_unit.clone(),
+                        price: UnifiedNum::from_whole(2),
+                    },
+                ],
+            }],
+        )
+        .await;
+
+        // no rate is needed for a single token
+        let manager = setup_manager(server.uri());
+
+        let next = manager
+            .get_next_ad_unit()
+            .await
+            .expect("Should get the next AdUnit")
+            .expect("Should have an AdUnit");
+
+        assert_eq!(expensive_unit, next.unit);
+    }
 }
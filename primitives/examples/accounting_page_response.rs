@@ -0,0 +1,18 @@
+use primitives::sentry::AccountingPageResponse;
+use serde_json::{from_value, json};
+
+fn main() {
+    // The first of 2 pages, the earners and spenders sums are not checked
+    // since the rest of the accounting is on the next page.
+    let json = json!({
+        "earners": {
+            "0x80690751969B234697e9059e04ed72195c3507fa": "10000000000",
+            "0xf3f583AEC5f7C030722Fe992A5688557e1B86ef7": "20000000000",
+        },
+        "spenders": {},
+        "frozen": false,
+        "totalPages": 2,
+        "page": 0
+    });
+    assert!(from_value::<AccountingPageResponse>(json).is_ok());
+}
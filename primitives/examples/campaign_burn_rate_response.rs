@@ -0,0 +1,33 @@
+use primitives::sentry::campaign_burn_rate::BurnRateResponse;
+use serde_json::{from_value, json};
+
+fn main() {
+    // The Campaign has spent 300 in 2 hours and has 750 remaining,
+    // it will be exhausted in 5 hours.
+    {
+        let json = json!({
+          "campaign": "0x936da01f9abd4d9d80c702af85c822a8",
+          "since": 1655719200000_i64,
+          "spent": "300",
+          "remaining": "750",
+          "perHour": "150",
+          "exhaustion": 1655744400000_i64
+        });
+
+        assert!(from_value::<BurnRateResponse>(json).is_ok());
+    }
+
+    // Nothing has been spent in the requested hours, the exhaustion is unknown
+    {
+        let json = json!({
+          "campaign": "0x936da01f9abd4d9d80c702af85c822a8",
+          "since": 1655719200000_i64,
+          "spent": "0",
+          "remaining": "750",
+          "perHour": null
+        });
+
+        let response = from_value::<BurnRateResponse>(json).unwrap();
+        assert!(response.exhaustion.is_none());
+    }
+}
@@ -1,7 +1,7 @@
 use chrono::{TimeZone, Utc};
 use primitives::{
     sentry::campaign_list::{CampaignListQuery, ValidatorParam},
-    test_util::{ADVERTISER, FOLLOWER, IDS, LEADER},
+    test_util::{ADVERTISER, FOLLOWER, GUARDIAN, IDS, LEADER},
 };
 
 fn main() {
@@ -100,6 +100,25 @@ fn main() {
         assert_eq!(with_leader, serde_qs::from_str(with_leader_query).unwrap());
     }
 
+    // Query with `guardian`
+    // You can either have `leader`, `validator` or `guardian` but not more than one!
+    {
+        let with_guardian_query =
+            "activeTo=1624192200&guardian=0xe061E1EB461EaBE512759aa18A201B20Fe90631D";
+
+        let with_guardian = CampaignListQuery {
+            page: 0,
+            active_to_ge: Utc.ymd(2021, 6, 20).and_hms(12, 30, 0),
+            creator: None,
+            validator: Some(ValidatorParam::Guardian(*GUARDIAN)),
+        };
+
+        assert_eq!(
+            with_guardian,
+            serde_qs::from_str(with_guardian_query).unwrap()
+        );
+    }
+
     // Query with all parameters and `validator`
     // You can either have `leader` or `validator` but not both!
     {
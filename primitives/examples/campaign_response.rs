@@ -0,0 +1,81 @@
+use primitives::sentry::campaign_get::CampaignResponse;
+use serde_json::{from_value, json};
+
+fn main() {
+    let json = json!({
+      "campaign": {
+        "id": "0x936da01f9abd4d9d80c702af85c822a8",
+        "channel": {
+          "leader": "0x80690751969B234697e9059e04ed72195c3507fa",
+          "follower": "0xf3f583AEC5f7C030722Fe992A5688557e1B86ef7",
+          "guardian": "0xe061E1EB461EaBE512759aa18A201B20Fe90631D",
+          "token": "0x2BCaf6968aEC8A3b5126FBfAb5Fd419da6E8AD8E",
+          "nonce": "0"
+        },
+        "creator": "0xDd589B43793934EF6Ad266067A0d1D4896b0dff0",
+        "budget": "15000000000",
+        "validators": [
+          {
+            "id": "0x80690751969B234697e9059e04ed72195c3507fa",
+            "fee": "500000000",
+            "url": "http://localhost:8005/"
+          },
+          {
+            "id": "0xf3f583AEC5f7C030722Fe992A5688557e1B86ef7",
+            "fee": "400000000",
+            "url": "http://localhost:8006/"
+          }
+        ],
+        "title": "Dummy Campaign",
+        "pricingBounds": {
+          "CLICK": {
+            "min": "6000",
+            "max": "10000"
+          },
+          "IMPRESSION": {
+            "min": "4000",
+            "max": "5000"
+          }
+        },
+        "eventSubmission": {
+          "allow": []
+        },
+        "adUnits": [
+          {
+            "ipfs": "Qmasg8FrbuSQpjFu3kRnZF9beg8rEBFrqgi1uXDRwCbX5f",
+            "type": "legacy_250x250",
+            "mediaUrl": "ipfs://QmcUVX7fvoLMM93uN2bD3wGTH8MXSxeL8hojYfL2Lhp7mR",
+            "mediaMime": "image/jpeg",
+            "targetUrl": "https://www.adex.network/?stremio-test-banner-1",
+            "owner": "0xE882ebF439207a70dDcCb39E13CA8506c9F45fD9",
+            "created": 1564390800000_u64,
+            "title": "Dummy AdUnit 1",
+            "description": "Dummy AdUnit description 1",
+            "archived": false
+          },
+          {
+            "ipfs": "QmVhRDGXoM3Fg3HZD5xwMuxtb9ZErwC8wHt8CjsfxaiUbZ",
+            "type": "legacy_250x250",
+            "mediaUrl": "ipfs://QmQB7uz7Gxfy7wqAnrnBcZFaVJLos8J9gn8mRcHQU6dAi1",
+            "mediaMime": "image/jpeg",
+            "targetUrl": "https://www.adex.network/?adex-campaign=true&pub=stremio",
+            "owner": "0xE882ebF439207a70dDcCb39E13CA8506c9F45fD9",
+            "created": 1564390800000_u64,
+            "title": "Dummy AdUnit 2",
+            "description": "Dummy AdUnit description 2",
+            "archived": false
+          }
+        ],
+        "targetingRules": [],
+        "created": 1612162800000_u64,
+        "activeTo": 4073414400000_u64
+      },
+      "utilization": {
+        "spent": "3000000000",
+        "remaining": "12000000000",
+        "percent": 20.0
+      }
+    });
+
+    assert!(from_value::<CampaignResponse>(json).is_ok());
+}
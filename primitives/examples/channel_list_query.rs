@@ -1,6 +1,6 @@
 use primitives::{
     sentry::channel_list::ChannelListQuery,
-    test_util::{IDS, LEADER},
+    test_util::{GUARDIAN, IDS, LEADER},
     ChainId,
 };
 
@@ -11,7 +11,9 @@ fn main() {
         let empty_expected = ChannelListQuery {
             page: 0,
             validator: None,
+            guardian: None,
             chains: vec![],
+            limit: None,
         };
 
         assert_eq!(empty_expected, serde_qs::from_str(empty).unwrap());
@@ -23,7 +25,9 @@ fn main() {
         let only_page_expected = ChannelListQuery {
             page: 14,
             validator: None,
+            guardian: None,
             chains: vec![],
+            limit: None,
         };
 
         assert_eq!(only_page_expected, serde_qs::from_str(only_page).unwrap());
@@ -35,7 +39,9 @@ fn main() {
         let only_validator_expected = ChannelListQuery {
             page: 0,
             validator: Some(IDS[&LEADER]),
+            guardian: None,
             chains: vec![],
+            limit: None,
         };
 
         assert_eq!(
@@ -44,13 +50,32 @@ fn main() {
         );
     }
 
+    // Query with `guardian`
+    {
+        let only_guardian = "guardian=0xe061E1EB461EaBE512759aa18A201B20Fe90631D";
+        let only_guardian_expected = ChannelListQuery {
+            page: 0,
+            validator: None,
+            guardian: Some(*GUARDIAN),
+            chains: vec![],
+            limit: None,
+        };
+
+        assert_eq!(
+            only_guardian_expected,
+            serde_qs::from_str(only_guardian).unwrap()
+        );
+    }
+
     // Query with `chains`
     {
         let chains_query = "chains[]=1&chains[]=1337";
         let chains_expected = ChannelListQuery {
             page: 0,
             validator: None,
+            guardian: None,
             chains: vec![ChainId::new(1), ChainId::new(1337)],
+            limit: None,
         };
 
         assert_eq!(chains_expected, serde_qs::from_str(chains_query).unwrap());
@@ -63,7 +88,9 @@ fn main() {
         let all_expected = ChannelListQuery {
             page: 14,
             validator: Some(IDS[&LEADER]),
+            guardian: None,
             chains: vec![ChainId::new(1), ChainId::new(1337)],
+            limit: None,
         };
 
         assert_eq!(all_expected, serde_qs::from_str(all_query).unwrap());
@@ -0,0 +1,38 @@
+use primitives::sentry::ChannelSnapshotResponse;
+use serde_json::{from_value, json};
+
+fn main() {
+    let json = json!({
+      "snapshot": {
+        "channel": {
+          "leader": "0x80690751969B234697e9059e04ed72195c3507fa",
+          "follower": "0xf3f583AEC5f7C030722Fe992A5688557e1B86ef7",
+          "guardian": "0xe061E1EB461EaBE512759aa18A201B20Fe90631D",
+          "token": "0x2BCaf6968aEC8A3b5126FBfAb5Fd419da6E8AD8E",
+          "nonce": "0"
+        },
+        "created": "2022-06-20T10:30:00Z",
+        "balances": {
+          "earners": {
+            "0x80690751969B234697e9059e04ed72195c3507fa": "1000000",
+            "0xE882ebF439207a70dDcCb39E13CA8506c9F45fD9": "9000000"
+          },
+          "spenders": {
+            "0xDd589B43793934EF6Ad266067A0d1D4896b0dff0": "10000000"
+          }
+        },
+        "spendables": {
+          "0xDd589B43793934EF6Ad266067A0d1D4896b0dff0": "200000000000"
+        },
+        "campaignsRemaining": {
+          "0x936da01f9abd4d9d80c702af85c822a8": "14990000000"
+        },
+        "lastApproved": null,
+        "stateRoot": "fa8f11b8ef3d1d6a6be8c1e2e7d7a0f8b8c5f1c0d1f1a3ed0a1e67b8c9e0b1d2"
+      },
+      "signer": "0x80690751969B234697e9059e04ed72195c3507fa",
+      "signature": "0x7d1d8a8e4c5a9ec7b5c1e0c4d9d3a5a9b5f1b2e8a3e7c9d1f0a2b4c6d8e0f1a3b5c7d9e1f3a5b7c9d1e3f5a7b9c1d3e5f7a9b1c3d5e7f9a1b3c5d7e9f1a3b5c71c"
+    });
+
+    assert!(from_value::<ChannelSnapshotResponse>(json).is_ok());
+}
@@ -0,0 +1,43 @@
+use primitives::{
+    sentry::{event_stats::EventStatsQuery, DateHour},
+    test_util::PUBLISHER,
+};
+
+fn main() {
+    // Query with `publisher` only - default time range
+    {
+        let publisher_query = "publisher=0xE882ebF439207a70dDcCb39E13CA8506c9F45fD9";
+        let query: EventStatsQuery = serde_qs::from_str(publisher_query).unwrap();
+
+        assert_eq!(*PUBLISHER, query.publisher);
+        assert!(query.start.is_none());
+        assert!(query.end.is_none());
+
+        // by default the last 24 hours are returned
+        let (start, end) = query.time_range();
+        assert_eq!(end - 23, start);
+    }
+
+    // Query with `publisher`, `start` & `end`
+    {
+        let full_query = "publisher=0xE882ebF439207a70dDcCb39E13CA8506c9F45fD9&start=2022-06-20T10:00:00Z&end=2022-06-21T10:00:00Z";
+        let expected = EventStatsQuery {
+            publisher: *PUBLISHER,
+            start: Some(DateHour::from_ymdh(2022, 6, 20, 10)),
+            end: Some(DateHour::from_ymdh(2022, 6, 21, 10)),
+        };
+
+        assert_eq!(expected, serde_qs::from_str(full_query).unwrap());
+    }
+
+    // Query with `publisher` & `end` only
+    {
+        let end_query =
+            "publisher=0xE882ebF439207a70dDcCb39E13CA8506c9F45fD9&end=2022-06-21T10:00:00Z";
+        let query: EventStatsQuery = serde_qs::from_str(end_query).unwrap();
+
+        let (start, end) = query.time_range();
+        assert_eq!(DateHour::from_ymdh(2022, 6, 20, 11), start);
+        assert_eq!(DateHour::from_ymdh(2022, 6, 21, 10), end);
+    }
+}
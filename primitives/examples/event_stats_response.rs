@@ -0,0 +1,39 @@
+use primitives::sentry::event_stats::EventStatsResponse;
+use serde_json::{from_value, json};
+
+fn main() {
+    let json = json!({
+      "campaign": "0x936da01f9abd4d9d80c702af85c822a8",
+      "publisher": "0xE882ebF439207a70dDcCb39E13CA8506c9F45fD9",
+      "stats": [
+        {
+          "time": "2022-06-20T10:00:00Z",
+          "events": {
+            "IMPRESSION": {
+              "count": 3,
+              "payout": "300000"
+            },
+            "CLICK": {
+              "count": 1,
+              "payout": "2000000"
+            }
+          }
+        },
+        {
+          "time": "2022-06-20T11:00:00Z",
+          "events": {
+            "IMPRESSION": {
+              "count": 0,
+              "payout": "0"
+            },
+            "CLICK": {
+              "count": 0,
+              "payout": "0"
+            }
+          }
+        }
+      ]
+    });
+
+    assert!(from_value::<EventStatsResponse>(json).is_ok());
+}
@@ -0,0 +1,26 @@
+use chrono::{TimeZone, Utc};
+use primitives::{sentry::fees_summary::FeesSummaryQuery, ChannelId};
+
+fn main() {
+    // Empty query - latest summary of each Channel
+    {
+        let query: FeesSummaryQuery = serde_qs::from_str("").unwrap();
+
+        assert_eq!(FeesSummaryQuery::default(), query);
+    }
+
+    // Query with `channel` & `period`
+    {
+        let full_query = "channel=0x061d5e2a67d0a9a10f1c732bca12a676d83f79663a396f7d87b3e30b9b411088&period=2022-12-01T10:00:00Z";
+        let expected = FeesSummaryQuery {
+            channel: Some(
+                "0x061d5e2a67d0a9a10f1c732bca12a676d83f79663a396f7d87b3e30b9b411088"
+                    .parse::<ChannelId>()
+                    .unwrap(),
+            ),
+            period: Some(Utc.with_ymd_and_hms(2022, 12, 1, 10, 0, 0).unwrap()),
+        };
+
+        assert_eq!(expected, serde_qs::from_str(full_query).unwrap());
+    }
+}
@@ -0,0 +1,13 @@
+use primitives::sentry::fees_summary::FeesSummaryRequest;
+use serde_json::{from_value, json};
+
+fn main() {
+    let json = json!({
+      "channel": "0x061d5e2a67d0a9a10f1c732bca12a676d83f79663a396f7d87b3e30b9b411088",
+      "period": "2022-12-01T10:00:00Z",
+      "fees": "1500000",
+      "delta": "500000"
+    });
+
+    assert!(from_value::<FeesSummaryRequest>(json).is_ok());
+}
@@ -0,0 +1,29 @@
+use primitives::sentry::fees_summary::FeesSummaryResponse;
+use serde_json::{from_value, json};
+
+fn main() {
+    let json = json!({
+      "summaries": [
+        {
+          "validator": "0x80690751969B234697e9059e04ed72195c3507fa",
+          "channel": "0x061d5e2a67d0a9a10f1c732bca12a676d83f79663a396f7d87b3e30b9b411088",
+          "period": "2022-12-01T10:00:00Z",
+          "fees": "1500000",
+          "delta": "500000",
+          "created": "2022-12-01T10:00:02.512Z"
+        },
+        {
+          "validator": "0x80690751969B234697e9059e04ed72195c3507fa",
+          "channel": "0xf1a3a5d1ba2fc24b8c6b5e4e8c2d6a79c8a1f3e2d4b5c6a7980f1e2d3c4b5a69",
+          "period": "2022-12-01T10:00:00Z",
+          "fees": "200000",
+          "delta": "200000",
+          "created": "2022-12-01T10:00:02.730Z"
+        }
+      ],
+      "totalFees": "1700000",
+      "totalDelta": "700000"
+    });
+
+    assert!(from_value::<FeesSummaryResponse>(json).is_ok());
+}
@@ -14,6 +14,11 @@ fn main() {
         "0xf3f583AEC5f7C030722Fe992A5688557e1B86ef7",
         "0x6B83e7D6B72c098d48968441e0d05658dc17Adb9"
       ],
+      "legacy_auth_tokens": true,
+      "event_types": [
+        { "type": "IMPRESSION" },
+        { "type": "CLICK" }
+      ],
       "chain": {
         "Ganache #1337": {
           "chain_id": 1337,
@@ -24,7 +29,8 @@ fn main() {
               "min_campaign_budget": "1000000000000000000",
               "min_validator_fee": "1000000000000",
               "precision": 18,
-              "address": "0x2BCaf6968aEC8A3b5126FBfAb5Fd419da6E8AD8E"
+              "address": "0x2BCaf6968aEC8A3b5126FBfAb5Fd419da6E8AD8E",
+              "payout_rounding": "nearest"
             }
           }
         },
@@ -37,7 +43,8 @@ fn main() {
               "min_campaign_budget": "1000000000000000000",
               "min_validator_fee": "1000000000000",
               "precision": 18,
-              "address": "0x12a28f2bfBFfDf5842657235cC058242f40fDEa6"
+              "address": "0x12a28f2bfBFfDf5842657235cC058242f40fDEa6",
+              "payout_rounding": "nearest"
             }
           }
         }
@@ -49,13 +56,19 @@ fn main() {
         },
         "channels_find": 200,
         "campaigns_find": 200,
+        "campaigns_bulk_create": 50,
+        "max_campaigns_per_channel": 1000,
         "spendable_find": 200,
+        "accounting_find": 200,
         "msgs_find": 10,
+        "approved_states_find": 100,
         "analytics_find": 5000,
         "ip_rate_limit": {
           "type": "ip",
           "timeframe": 1200000
-        }
+        },
+        "unit_pricing_override_multiplier": 10,
+        "max_campaign_duration": 3_153_600_000_000_u64
       },
       "sentry": {
         "analytics_maxtime": 20000,
@@ -64,20 +77,37 @@ fn main() {
         ],
         "platform": {
           "url": "https://platform.adex.network/",
-          "keep_alive_interval": 1200000
+          "keep_alive_interval": 1200000,
+          "timeout": 10000,
+          "slot_cache_ttl": 300000
         },
+        "accounting_tolerance": "0",
+        "spender_channel_registration": true,
+        "empty_spendable_max_age": 86400000,
+        "conversion_attribution_window": 604800000,
+        "campaign_awaiting_approval_after": 300000,
+        "campaign_stalled_after": 3600000,
+        "campaign_reservation_window": 30000,
       },
       "worker": {
         "max_channels": 512,
         "wait_time": 500,
         "heartbeat_time": 30000,
+        "clock_skew_threshold": 60000,
         "health_threshold_promilles": 950,
         "health_unsignable_promilles": 750,
+        "fees_summary_interval": 3600000,
         "timeouts": {
           "propagation": 2000,
           "fetch": 5000,
           "all_campaigns": 5000,
           "channel_tick": 8000,
+          "health_check": 1000,
+        },
+        "retry": {
+          "attempts": 3,
+          "backoff": 100,
+          "max_backoff": 10000,
         }
       }
     });
@@ -1,26 +1,71 @@
-use primitives::{sentry::campaign_modify::ModifyCampaign, unified_num::FromWhole, UnifiedNum};
+use primitives::{
+    sentry::campaign_modify::ModifyCampaign, targeting::Rules, unified_num::FromWhole, UnifiedNum,
+};
 use serde_json::json;
 
 fn main() {
-    {
-        let modify_campaign = ModifyCampaign {
-            ad_units: None,
-            budget: Some(UnifiedNum::from_whole(100)),
-            validators: None,
-            title: None,
-            pricing_bounds: None,
-            event_submission: None,
-            targeting_rules: None,
-        };
+    let modify_campaign = ModifyCampaign {
+        version: 0,
+        ad_units: None,
+        budget: Some(UnifiedNum::from_whole(100)),
+        validators: None,
+        title: None,
+        pricing_bounds: None,
+        unit_pricing_overrides: None,
+        event_submission: None,
+        pacing: None,
+        targeting_rules: Some(Rules::new()),
+    };
 
+    {
         let modify_campaign_json = json!({
-            "ad_units": null,
+            "version": 0,
+            "adUnits": null,
             "budget": "10000000000",
             "validators": null,
             "title": null,
-            "pricing_bounds": null,
-            "event_submission": null,
-            "targeting_rules": null,
+            "pricingBounds": null,
+            "unitPricingOverrides": null,
+            "eventSubmission": null,
+            "pacing": null,
+            "targetingRules": [],
+        });
+
+        let modify_campaign_json =
+            serde_json::to_string(&modify_campaign_json).expect("should serialize");
+        let deserialized: ModifyCampaign =
+            serde_json::from_str(&modify_campaign_json).expect("should deserialize");
+
+        // `null` clears the pacing of the Campaign
+        let clear_pacing = ModifyCampaign {
+            pacing: Some(None),
+            ..modify_campaign.clone()
+        };
+        assert_eq!(clear_pacing, deserialized);
+    }
+
+    // no pacing leaves the pacing of the Campaign unchanged
+    {
+        let modify_campaign_json = json!({
+            "version": 0,
+            "budget": "10000000000",
+            "targetingRules": [],
+        });
+
+        let modify_campaign_json =
+            serde_json::to_string(&modify_campaign_json).expect("should serialize");
+        let deserialized: ModifyCampaign =
+            serde_json::from_str(&modify_campaign_json).expect("should deserialize");
+
+        assert_eq!(modify_campaign, deserialized);
+    }
+
+    // snake_case fields are still accepted
+    {
+        let modify_campaign_json = json!({
+            "version": 0,
+            "budget": "10000000000",
+            "targeting_rules": [],
         });
 
         let modify_campaign_json =
@@ -9,4 +9,18 @@ fn main() {
         },
     });
     assert!(from_value::<SpenderResponse>(json).is_ok());
+
+    // With the deposit breakdown, when the adapter supports it
+    let json = json!({
+        "spender": {
+            "totalDeposited": "10000000000",
+            "totalSpent": "100000000",
+        },
+        "depositBreakdown": {
+            "outpace": "10000000000",
+            "pending": "500000000",
+        },
+    });
+    let response = from_value::<SpenderResponse>(json).unwrap();
+    assert!(response.deposit_breakdown.is_some());
 }
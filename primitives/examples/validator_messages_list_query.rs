@@ -1,3 +1,4 @@
+use chrono::{TimeZone, Utc};
 use primitives::sentry::validator_messages::ValidatorMessagesListQuery;
 
 fn main() {
@@ -7,6 +8,7 @@ fn main() {
         let query: ValidatorMessagesListQuery = serde_qs::from_str(empty_query).unwrap();
 
         assert_eq!(None, query.limit);
+        assert!(!query.is_oldest_first());
     }
     // Query with set limit
     {
@@ -15,4 +17,28 @@ fn main() {
 
         assert_eq!(Some(200), query.limit);
     }
+    // Query with messages received since a given time (in milliseconds), oldest-first
+    {
+        let query_str = "since=1660056328160";
+        let query: ValidatorMessagesListQuery = serde_qs::from_str(query_str).unwrap();
+
+        assert_eq!(
+            Some(Utc.ymd(2022, 8, 9).and_hms_milli(14, 45, 28, 160)),
+            query.since
+        );
+        assert!(query.is_oldest_first());
+    }
+    // Query with the cursor of the next page returned from the previous one
+    {
+        let query_str = "limit=10&cursor=0005e5cffbbf4300000000000000002a";
+        let query: ValidatorMessagesListQuery = serde_qs::from_str(query_str).unwrap();
+
+        let cursor = query.cursor.expect("Should have a cursor");
+        assert_eq!(
+            Utc.ymd(2022, 8, 9).and_hms_milli(14, 45, 28, 160),
+            cursor.received
+        );
+        assert_eq!(42, cursor.id);
+        assert!(query.is_oldest_first());
+    }
 }
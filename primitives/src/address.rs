@@ -20,6 +20,17 @@ pub enum Error {
 pub struct Address(#[serde(deserialize_with = "de::from_bytes_insensitive")] [u8; 20]);
 
 impl Address {
+    /// The zero address `0x0000000000000000000000000000000000000000`.
+    pub const ZERO: Address = Address([0; 20]);
+
+    /// Whether or not this is the zero address.
+    ///
+    /// The zero address is parsed as any other address, but it's rejected by the domain
+    /// validation wherever it's semantically invalid, e.g. for a Channel guardian.
+    pub fn is_zero(&self) -> bool {
+        self == &Self::ZERO
+    }
+
     pub fn to_bytes(&self) -> [u8; 20] {
         self.0
     }
@@ -15,6 +15,18 @@ pub struct Balances<S: BalancesState = CheckedState> {
 
 impl Balances<UncheckedState> {
     pub fn check(self) -> Result<Balances<CheckedState>, Error> {
+        self.check_with_tolerance(UnifiedNum::default())
+            .map(|(balances, _residue)| balances)
+    }
+
+    /// Same as [`Balances::check()`] but allows the earners sum and the spenders sum
+    /// to differ by at most `tolerance` (in [`UnifiedNum`] raw units).
+    ///
+    /// Returns the checked [`Balances`] and the absolute difference (residue) between the two sums.
+    pub fn check_with_tolerance(
+        self,
+        tolerance: UnifiedNum,
+    ) -> Result<(Balances<CheckedState>, UnifiedNum), Error> {
         let earned = self
             .earners
             .values()
@@ -26,14 +38,22 @@ impl Balances<UncheckedState> {
             .sum::<Option<UnifiedNum>>()
             .ok_or_else(|| Error::Overflow("spenders overflow".to_string()))?;
 
-        if earned != spent {
+        let residue = if earned > spent {
+            earned - spent
+        } else {
+            spent - earned
+        };
+
+        if residue > tolerance {
             Err(Error::PayoutMismatch { spent, earned })
         } else {
-            Ok(Balances {
+            let balances = Balances {
                 earners: self.earners,
                 spenders: self.spenders,
                 state: PhantomData::<CheckedState>::default(),
-            })
+            };
+
+            Ok((balances, residue))
         }
     }
 }
@@ -83,12 +103,20 @@ impl<S: BalancesState> Balances<S> {
 }
 
 impl Balances<CheckedState> {
+    /// Spends the `amount` from the `spender` to the `earner`.
+    ///
+    /// A zero `amount` is skipped, so no zero entries are added
+    /// for the spender and the earner.
     pub fn spend(
         &mut self,
         spender: Address,
         earner: Address,
         amount: UnifiedNum,
     ) -> Result<(), OverflowError> {
+        if amount == UnifiedNum::ZERO {
+            return Ok(());
+        }
+
         let spent = self.spenders.entry(spender).or_default();
         *spent = spent
             .checked_add(&amount)
@@ -189,3 +217,100 @@ mod de {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::{ADVERTISER, CREATOR, PUBLISHER};
+
+    fn unchecked_balances(earned: u64, spent: u64) -> Balances<UncheckedState> {
+        let mut balances = Balances::<UncheckedState>::new();
+        balances
+            .earners
+            .insert(*PUBLISHER, UnifiedNum::from_u64(earned));
+        balances
+            .spenders
+            .insert(*CREATOR, UnifiedNum::from_u64(spent / 2));
+        balances
+            .spenders
+            .insert(*ADVERTISER, UnifiedNum::from_u64(spent - spent / 2));
+
+        balances
+    }
+
+    #[test]
+    fn check_with_tolerance() {
+        let tolerance = UnifiedNum::from_u64(2);
+
+        // no residue
+        {
+            let (_, residue) = unchecked_balances(1_000, 1_000)
+                .check_with_tolerance(tolerance)
+                .expect("Should pass the check");
+
+            assert_eq!(UnifiedNum::from_u64(0), residue);
+        }
+
+        // exactly the tolerance
+        {
+            let (checked, residue) = unchecked_balances(1_002, 1_000)
+                .check_with_tolerance(tolerance)
+                .expect("Earners residue equal to the tolerance should pass the check");
+            assert_eq!(tolerance, residue);
+            assert_eq!(
+                Some(&UnifiedNum::from_u64(1_002)),
+                checked.earners.get(&PUBLISHER)
+            );
+
+            let (_, residue) = unchecked_balances(1_000, 1_002)
+                .check_with_tolerance(tolerance)
+                .expect("Spenders residue equal to the tolerance should pass the check");
+            assert_eq!(tolerance, residue);
+        }
+
+        // just beyond the tolerance
+        {
+            let error = unchecked_balances(1_003, 1_000)
+                .check_with_tolerance(tolerance)
+                .expect_err("Earners residue beyond the tolerance should fail the check");
+            assert!(matches!(error, Error::PayoutMismatch { .. }));
+
+            let error = unchecked_balances(1_000, 1_003)
+                .check_with_tolerance(tolerance)
+                .expect_err("Spenders residue beyond the tolerance should fail the check");
+            assert!(matches!(error, Error::PayoutMismatch { .. }));
+        }
+
+        // the strict check has no tolerance
+        {
+            assert!(unchecked_balances(1_000, 1_000).check().is_ok());
+            assert!(unchecked_balances(1_001, 1_000).check().is_err());
+        }
+    }
+
+    #[test]
+    fn spend_skips_zero_amounts() {
+        let mut balances = Balances::<CheckedState>::new();
+
+        balances
+            .spend(*CREATOR, *ADVERTISER, UnifiedNum::ZERO)
+            .expect("Should spend");
+        assert_eq!(Balances::<CheckedState>::new(), balances);
+
+        balances
+            .spend(*CREATOR, *PUBLISHER, UnifiedNum::from_u64(100))
+            .expect("Should spend");
+        balances
+            .spend(*CREATOR, *ADVERTISER, UnifiedNum::ZERO)
+            .expect("Should spend");
+
+        assert_eq!(
+            Some(&UnifiedNum::from_u64(100)),
+            balances.spenders.get(&CREATOR)
+        );
+        assert_eq!(
+            vec![(&*PUBLISHER, &UnifiedNum::from_u64(100))],
+            balances.earners.iter().collect::<Vec<_>>()
+        );
+    }
+}
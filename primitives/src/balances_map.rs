@@ -13,6 +13,11 @@ use crate::{Address, BigNum, UnifiedNum};
 pub type UnifiedMap = Map<Address, UnifiedNum>;
 pub type BalancesMap = Map<Address, BigNum>;
 
+/// A map which is always ordered by its keys, regardless of the insertion order.
+///
+/// The [`Address`] keys are ordered by their bytes, i.e. by the lowercase hex address,
+/// and they are serialized in this order. This makes the JSON of the same map byte-identical,
+/// e.g. the [`Balances`](crate::balances::Balances) of the signed `NewState` messages.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(transparent)]
 pub struct Map<K: Ord, V>(BTreeMap<K, V>);
@@ -96,7 +101,7 @@ mod test {
     use serde_json::json;
 
     use super::*;
-    use crate::test_util::{FOLLOWER, LEADER};
+    use crate::test_util::{FOLLOWER, LEADER, PUBLISHER};
 
     #[test]
     fn test_unified_map_de_serialization() {
@@ -121,6 +126,40 @@ mod test {
         assert_eq!(unified_map, balances_map_from_json);
     }
 
+    #[test]
+    fn serialization_does_not_depend_on_the_insertion_order() {
+        let entries = [
+            (*LEADER, UnifiedNum::from(50_u64)),
+            (*FOLLOWER, UnifiedNum::from(100_u64)),
+            (*PUBLISHER, UnifiedNum::from(150_u64)),
+        ];
+
+        let mut in_order = UnifiedMap::default();
+        for (address, amount) in entries {
+            in_order.insert(address, amount);
+        }
+        let mut reversed = UnifiedMap::default();
+        for (address, amount) in entries.into_iter().rev() {
+            reversed.insert(address, amount);
+        }
+
+        let in_order_json = serde_json::to_string(&in_order).expect("Should serialize");
+        let reversed_json = serde_json::to_string(&reversed).expect("Should serialize");
+        assert_eq!(in_order_json, reversed_json);
+
+        // the keys are ordered by the lowercase address
+        let mut expected_order = entries
+            .iter()
+            .map(|(address, _)| hex::encode(address.as_bytes()))
+            .collect::<Vec<_>>();
+        expected_order.sort();
+        let actual_order = in_order
+            .iter()
+            .map(|(address, _)| hex::encode(address.as_bytes()))
+            .collect::<Vec<_>>();
+        assert_eq!(expected_order, actual_order);
+    }
+
     #[test]
     fn test_balances_map_de_serialization() {
         let balances_map: BalancesMap = vec![
@@ -1,6 +1,6 @@
 use crate::{
     sentry::EventType, targeting::Rules, AdUnit, Address, Channel, EventSubmission, UnifiedNum,
-    Validator, ValidatorDesc, ValidatorId,
+    Validator, ValidatorDesc, ValidatorId, IPFS,
 };
 
 use chrono::{
@@ -8,11 +8,14 @@ use chrono::{
     DateTime, Utc,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[doc(inline)]
 pub use {
     campaign_id::CampaignId,
-    pricing::{Pricing, PricingBounds},
+    pricing::{
+        cpm_to_price, price_to_cpm, Pricing, PricingBounds, UnitPricingOverrides, CPM_EVENTS,
+    },
     validators::Validators,
 };
 
@@ -182,9 +185,25 @@ pub struct Campaign {
     /// Events pricing bounds, per 1 event
     #[serde(default, skip_serializing_if = "PricingBounds::is_empty")]
     pub pricing_bounds: PricingBounds,
+    /// Events pricing bounds of specific [`AdUnit`]s, per 1 event.
+    ///
+    /// They override the [`Campaign.pricing_bounds`](Campaign::pricing_bounds)
+    /// for the given event types of the AdUnit, see [`Campaign::unit_pricing()`].
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub unit_pricing_overrides: UnitPricingOverrides,
+    /// The event types which the Campaign pays for.
+    ///
+    /// When `None` all event types are accepted, see [`Campaign::accepts_event()`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accepted_event_types: Option<Vec<EventType>>,
     /// EventSubmission object, applied to event submission
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub event_submission: Option<EventSubmission>,
+    /// Spreads the spending of the budget over the Campaign duration.
+    ///
+    /// When `None` the whole budget can be spent in a single day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pacing: Option<Pacing>,
     /// An array of AdUnit (optional)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub ad_units: Vec<AdUnit>,
@@ -196,6 +215,12 @@ pub struct Campaign {
     /// Used by the AdViewManager & Targeting AIP#31
     #[serde(flatten)]
     pub active: Active,
+    /// The version of the Campaign which is incremented on every modification.
+    ///
+    /// When modifying a Campaign the expected version should be passed,
+    /// see [`ModifyCampaign.version`](crate::sentry::campaign_modify::ModifyCampaign::version).
+    #[serde(default)]
+    pub version: u64,
 }
 
 impl Campaign {
@@ -223,6 +248,52 @@ impl Campaign {
     pub fn pricing(&self, event: EventType) -> Option<&Pricing> {
         self.pricing_bounds.get(&event)
     }
+
+    /// Returns the pricing of a given event for an [`AdUnit`].
+    ///
+    /// The [`Campaign.unit_pricing_overrides`](Campaign::unit_pricing_overrides)
+    /// of the AdUnit are used if they have the event,
+    /// otherwise it falls back to the Campaign's [`pricing()`](Self::pricing).
+    pub fn unit_pricing(&self, event: EventType, ad_unit: &IPFS) -> Option<&Pricing> {
+        self.unit_pricing_overrides
+            .get(ad_unit)
+            .and_then(|overrides| overrides.get(&event))
+            .or_else(|| self.pricing(event))
+    }
+
+    /// Returns the effective CPM (Cost Per Mille) pricing of the Campaign,
+    /// i.e. the [`IMPRESSION`](crate::sentry::IMPRESSION) pricing for 1 000 impressions.
+    ///
+    /// Returns `None` if the Campaign has no impression pricing or the CPM overflows.
+    pub fn cpm(&self) -> Option<Pricing> {
+        self.pricing(crate::sentry::IMPRESSION)
+            .and_then(Pricing::to_cpm)
+    }
+
+    /// Returns the effective CPC (Cost Per Click) pricing of the Campaign,
+    /// i.e. the [`CLICK`](crate::sentry::CLICK) pricing for 1 click.
+    pub fn cpc(&self) -> Option<&Pricing> {
+        self.pricing(crate::sentry::CLICK)
+    }
+
+    /// Whether or not the given event type is in the
+    /// [`Campaign.accepted_event_types`](Campaign::accepted_event_types).
+    ///
+    /// If no accepted event types are set, all event types are accepted.
+    pub fn accepts_event(&self, event: EventType) -> bool {
+        self.accepted_event_types
+            .as_ref()
+            .is_none_or(|accepted| accepted.contains(&event))
+    }
+}
+
+/// The spend pacing of a [`Campaign`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Pacing {
+    /// The maximum amount (incl. the validator fees) which the Campaign can spend
+    /// in a single UTC day.
+    pub daily_cap: UnifiedNum,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -250,7 +321,7 @@ mod pricing {
         ops::{Deref, DerefMut},
     };
 
-    use crate::{sentry::EventType, UnifiedNum};
+    use crate::{sentry::EventType, UnifiedNum, IPFS};
     use serde::{Deserialize, Serialize};
 
     #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
@@ -291,6 +362,128 @@ mod pricing {
             &mut self.0
         }
     }
+
+    /// The [`PricingBounds`] overrides of the Campaign's [`AdUnit`](crate::AdUnit)s
+    /// by the AdUnit's [`IPFS`].
+    pub type UnitPricingOverrides = HashMap<IPFS, PricingBounds>;
+
+    /// The number of events a CPM (Cost Per Mille) price is for.
+    pub const CPM_EVENTS: u64 = 1_000;
+
+    /// Converts the price of a single event to a CPM (Cost Per Mille) price, i.e. `price * 1000`.
+    ///
+    /// Returns `None` on overflow.
+    pub fn price_to_cpm(price: UnifiedNum) -> Option<UnifiedNum> {
+        price
+            .to_u64()
+            .checked_mul(CPM_EVENTS)
+            .map(UnifiedNum::from_u64)
+    }
+
+    /// Converts a CPM (Cost Per Mille) price to the price of a single event, i.e. `cpm / 1000`.
+    ///
+    /// The price is floored to the [`UnifiedNum::PRECISION`].
+    pub fn cpm_to_price(cpm: UnifiedNum) -> UnifiedNum {
+        UnifiedNum::from_u64(cpm.to_u64() / CPM_EVENTS)
+    }
+
+    impl Pricing {
+        /// Converts the per event pricing to CPM (Cost Per Mille) pricing,
+        /// see [`price_to_cpm()`].
+        ///
+        /// Returns `None` on overflow.
+        pub fn to_cpm(&self) -> Option<Pricing> {
+            Some(Pricing {
+                min: price_to_cpm(self.min)?,
+                max: price_to_cpm(self.max)?,
+            })
+        }
+
+        /// Converts CPM (Cost Per Mille) pricing to per event pricing,
+        /// see [`cpm_to_price()`].
+        pub fn from_cpm(cpm: &Pricing) -> Pricing {
+            Pricing {
+                min: cpm_to_price(cpm.min),
+                max: cpm_to_price(cpm.max),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use crate::{
+            sentry::{CLICK, IMPRESSION},
+            test_util::DUMMY_CAMPAIGN,
+        };
+
+        use super::*;
+
+        #[test]
+        fn converts_between_price_and_cpm() {
+            // the Ganache config `global_min_impression_price`
+            // 10 / 10^8 = 0.0000001 per impression = 0.0001 CPM
+            let global_min = UnifiedNum::from_u64(10);
+            assert_eq!(Some(UnifiedNum::from_u64(10_000)), price_to_cpm(global_min));
+            assert_eq!(global_min, cpm_to_price(UnifiedNum::from_u64(10_000)));
+
+            // the example Campaign `IMPRESSION` pricing:
+            // 0.00004 - 0.00005 per impression = 0.04 - 0.05 CPM
+            let impression = Pricing {
+                min: UnifiedNum::from_u64(4_000),
+                max: UnifiedNum::from_u64(5_000),
+            };
+            let impression_cpm = Pricing {
+                min: UnifiedNum::from_u64(4_000_000),
+                max: UnifiedNum::from_u64(5_000_000),
+            };
+            assert_eq!(Some(impression_cpm.clone()), impression.to_cpm());
+            assert_eq!(impression, Pricing::from_cpm(&impression_cpm));
+
+            // a CPM below 1 000 units is floored to 0 per event
+            assert_eq!(UnifiedNum::ZERO, cpm_to_price(UnifiedNum::from_u64(999)));
+            assert_eq!(
+                UnifiedNum::from_u64(1),
+                cpm_to_price(UnifiedNum::from_u64(1_999))
+            );
+
+            // overflow
+            assert_eq!(None, price_to_cpm(UnifiedNum::from_u64(u64::MAX)));
+            let overflowing = Pricing {
+                min: UnifiedNum::from_u64(1),
+                max: UnifiedNum::from_u64(u64::MAX / 2),
+            };
+            assert_eq!(None, overflowing.to_cpm());
+        }
+
+        #[test]
+        fn campaign_cpm_and_cpc() {
+            let mut campaign = DUMMY_CAMPAIGN.clone();
+            campaign.pricing_bounds = PricingBounds::default();
+            assert_eq!(None, campaign.cpm());
+            assert_eq!(None, campaign.cpc());
+
+            let impression = Pricing {
+                min: UnifiedNum::from_u64(4_000),
+                max: UnifiedNum::from_u64(5_000),
+            };
+            let click = Pricing {
+                min: UnifiedNum::from_u64(6_000),
+                max: UnifiedNum::from_u64(10_000),
+            };
+            campaign.pricing_bounds = [(IMPRESSION, impression), (CLICK, click.clone())]
+                .into_iter()
+                .collect();
+
+            assert_eq!(
+                Some(Pricing {
+                    min: UnifiedNum::from_u64(4_000_000),
+                    max: UnifiedNum::from_u64(5_000_000),
+                }),
+                campaign.cpm()
+            );
+            assert_eq!(Some(&click), campaign.cpc());
+        }
+    }
 }
 
 /// Campaign Validators
@@ -406,7 +599,12 @@ mod postgres {
                 validators: row.get("validators"),
                 title: row.get("title"),
                 pricing_bounds: row.get("pricing_bounds"),
+                unit_pricing_overrides: row.get::<_, Json<_>>("unit_pricing_overrides").0,
+                accepted_event_types: row
+                    .get::<_, Option<Json<_>>>("accepted_event_types")
+                    .map(|json| json.0),
                 event_submission: row.get("event_submission"),
+                pacing: row.get::<_, Option<Json<_>>>("pacing").map(|json| json.0),
                 ad_units: row.get::<_, Json<_>>("ad_units").0,
                 targeting_rules: row.get("targeting_rules"),
                 created: row.get("created"),
@@ -414,6 +612,10 @@ mod postgres {
                     from: row.get("active_from"),
                     to: row.get("active_to"),
                 },
+                version: row
+                    .get::<_, i64>("version")
+                    .try_into()
+                    .expect("Campaign version should be positive"),
             }
         }
     }
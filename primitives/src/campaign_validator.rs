@@ -1,8 +1,11 @@
 use crate::{
-    campaign::Validators, config::Config, Address, Campaign, ChainOf, UnifiedNum, ValidatorId,
+    campaign::Validators,
+    config::{Config, EventTypeConfig, TokenInfo},
+    unified_num::FromWhole,
+    Address, Campaign, ChainOf, Channel, UnifiedNum, ValidatorId,
 };
 use chrono::Utc;
-use std::cmp::PartialEq;
+use std::{cmp::PartialEq, collections::HashSet, time::Duration};
 use thiserror::Error;
 
 pub trait Validator {
@@ -24,9 +27,36 @@ pub enum Validation {
     UnlistedValidator,
     UnlistedCreator,
     UnlistedAsset,
+    /// The Campaign budget is lower than the token's
+    /// [`TokenInfo.min_campaign_budget`](crate::config::TokenInfo::min_campaign_budget)
     MinimumDepositNotMet,
+    /// The duration of the Campaign is longer than the
+    /// [`Config.limits.max_campaign_duration`](crate::config::Limits::max_campaign_duration)
+    MaximumCampaignDurationExceeded,
     MinimumValidatorFeeNotMet,
+    /// A validator fee is higher than the token's
+    /// [`TokenInfo.max_validator_fee`](crate::config::TokenInfo::max_validator_fee)
+    MaximumValidatorFeeExceeded,
     FeeConstraintViolated,
+    /// An AdUnit of the [`Campaign.unit_pricing_overrides`](Campaign::unit_pricing_overrides)
+    /// is not one of the [`Campaign.ad_units`](Campaign::ad_units)
+    UnitPricingOverrideForeignUnit,
+    /// An AdUnit pricing override is not within the
+    /// [`Config.limits.unit_pricing_override_multiplier`](crate::config::Limits::unit_pricing_override_multiplier)
+    /// of the Campaign pricing bounds or the Campaign has no pricing bounds for the event type.
+    UnitPricingOverrideOutOfBounds,
+    /// The [`Campaign.accepted_event_types`](Campaign::accepted_event_types)
+    /// are set, but they are empty or contain duplicate event types.
+    InvalidAcceptedEventTypes,
+    /// An event type of the Campaign pricing bounds, AdUnit pricing overrides
+    /// or accepted event types is not one of the [`Config.event_types`](crate::Config::event_types).
+    UnsupportedEventType,
+    /// The [`Pacing.daily_cap`](crate::campaign::Pacing::daily_cap) of the Campaign
+    /// is zero or it exceeds the Campaign budget.
+    InvalidPacing,
+    /// The address of the named field is the zero address,
+    /// see [`validate_addresses()`] and [`validate_channel_addresses()`].
+    ZeroAddress(&'static str),
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Error)]
@@ -49,6 +79,8 @@ impl Validator for Campaign {
         config: &Config,
         validator_identity: ValidatorId,
     ) -> Result<ChainOf<Campaign>, Error> {
+        validate_addresses(&self)?;
+
         // check if the channel validators include our adapter identity
         let whoami_validator = match self.find_validator(&validator_identity) {
             Some(role) => role.into_inner(),
@@ -72,24 +104,27 @@ impl Validator for Campaign {
             .find_chain_of(self.channel.token)
             .ok_or(Validation::UnlistedAsset)?;
 
+        validate_campaign_duration(&self, config.limits.max_campaign_duration)?;
+
         // Check if the campaign budget is above the minimum campaign budget configured
-        if self
-            .budget
-            .to_precision(chain_context.token.precision.get())
-            < chain_context.token.min_campaign_budget
-        {
-            return Err(Validation::MinimumDepositNotMet.into());
-        }
+        validate_campaign_budget(self.budget, &chain_context.token)?;
 
         // Check if the validator fee is greater than the minimum configured fee
-        if whoami_validator
-            .fee
-            .to_precision(chain_context.token.precision.get())
-            < chain_context.token.min_validator_fee
+        // unless it's a zero fee and the token allows zero fees
+        let is_allowed_zero_fee =
+            chain_context.token.allow_zero_fees && whoami_validator.fee == UnifiedNum::ZERO;
+        if !is_allowed_zero_fee
+            && whoami_validator
+                .fee
+                .to_precision(chain_context.token.precision.get())
+                < chain_context.token.min_validator_fee
         {
             return Err(Validation::MinimumValidatorFeeNotMet.into());
         }
 
+        // Check that none of the validator fees exceed the maximum configured fee
+        validate_max_validator_fee(&self.validators, &chain_context.token)?;
+
         let total_validator_fee: UnifiedNum = self
             .validators
             .iter()
@@ -102,10 +137,57 @@ impl Validator for Campaign {
             return Err(Validation::FeeConstraintViolated.into());
         }
 
+        validate_unit_pricing_overrides(&self, config.limits.unit_pricing_override_multiplier)?;
+        validate_accepted_event_types(&self)?;
+        validate_pacing(&self)?;
+        validate_event_types(&self, &config.event_types)?;
+
         Ok(chain_context.with_campaign(self))
     }
 }
 
+/// Checks that the budget is not lower than the token's
+/// [`TokenInfo.min_campaign_budget`](crate::config::TokenInfo::min_campaign_budget).
+pub fn validate_campaign_budget(budget: UnifiedNum, token: &TokenInfo) -> Result<(), Validation> {
+    if budget.to_precision(token.precision.get()) < token.min_campaign_budget {
+        Err(Validation::MinimumDepositNotMet)
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that none of the validator fees exceed the token's
+/// [`TokenInfo.max_validator_fee`](crate::config::TokenInfo::max_validator_fee), if it's set.
+pub fn validate_max_validator_fee(
+    validators: &Validators,
+    token: &TokenInfo,
+) -> Result<(), Validation> {
+    match token.max_validator_fee_rate() {
+        Some(max_fee) if validators.iter().any(|validator| validator.fee > max_fee) => {
+            Err(Validation::MaximumValidatorFeeExceeded)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that the Campaign duration, from [`Active.from`](crate::campaign::Active::from)
+/// (or [`Campaign.created`] if it's not set) until [`Active.to`](crate::campaign::Active::to),
+/// does not exceed the `max_duration`.
+pub fn validate_campaign_duration(
+    campaign: &Campaign,
+    max_duration: Duration,
+) -> Result<(), Validation> {
+    let from = campaign.active.from.unwrap_or(campaign.created);
+    // a negative duration (`active.to` before the start) is not longer than the maximum
+    let duration = (campaign.active.to - from).to_std().unwrap_or_default();
+
+    if duration > max_duration {
+        Err(Validation::MaximumCampaignDurationExceeded)
+    } else {
+        Ok(())
+    }
+}
+
 pub fn all_validators_listed(validators: &Validators, whitelist: &[ValidatorId]) -> bool {
     if whitelist.is_empty() {
         true
@@ -121,6 +203,144 @@ pub fn all_validators_listed(validators: &Validators, whitelist: &[ValidatorId])
     }
 }
 
+/// Validates the [`Campaign.unit_pricing_overrides`](Campaign::unit_pricing_overrides):
+///
+/// - All the overridden AdUnits should be in the [`Campaign.ad_units`](Campaign::ad_units)
+/// - The overrides should be within the `multiplier` of the Campaign pricing bounds for the same event type,
+///   i.e. `base.min / multiplier <= override.min <= override.max <= base.max * multiplier`
+pub fn validate_unit_pricing_overrides(
+    campaign: &Campaign,
+    multiplier: u64,
+) -> Result<(), Validation> {
+    let multiplier = UnifiedNum::from_whole(multiplier);
+
+    for (ad_unit, overrides) in campaign.unit_pricing_overrides.iter() {
+        if !campaign.ad_units.iter().any(|unit| &unit.ipfs == ad_unit) {
+            return Err(Validation::UnitPricingOverrideForeignUnit);
+        }
+
+        for (event_type, pricing) in overrides.iter() {
+            let base = campaign
+                .pricing(*event_type)
+                .ok_or(Validation::UnitPricingOverrideOutOfBounds)?;
+
+            let min_bound = base.min.checked_div(&multiplier).unwrap_or_default();
+            // on overflow there is no upper bound
+            let max_bound = base.max.checked_mul(&multiplier);
+
+            if pricing.min > pricing.max
+                || pricing.min < min_bound
+                || matches!(max_bound, Some(max_bound) if pricing.max > max_bound)
+            {
+                return Err(Validation::UnitPricingOverrideOutOfBounds);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates the [`Campaign.accepted_event_types`](Campaign::accepted_event_types),
+/// when set they should contain at least one event type and no duplicates.
+pub fn validate_accepted_event_types(campaign: &Campaign) -> Result<(), Validation> {
+    match &campaign.accepted_event_types {
+        Some(accepted) => {
+            let unique = accepted.iter().collect::<HashSet<_>>();
+
+            if accepted.is_empty() || unique.len() != accepted.len() {
+                Err(Validation::InvalidAcceptedEventTypes)
+            } else {
+                Ok(())
+            }
+        }
+        None => Ok(()),
+    }
+}
+
+/// Validates that none of the [`Channel`] leader, follower, guardian and token is the zero address.
+pub fn validate_channel_addresses(channel: &Channel) -> Result<(), Validation> {
+    let addresses = [
+        ("channel.leader", channel.leader.as_address()),
+        ("channel.follower", channel.follower.as_address()),
+        ("channel.guardian", &channel.guardian),
+        ("channel.token", &channel.token),
+    ];
+
+    match addresses
+        .into_iter()
+        .find(|(_field, address)| address.is_zero())
+    {
+        Some((field, _address)) => Err(Validation::ZeroAddress(field)),
+        None => Ok(()),
+    }
+}
+
+/// Validates that none of the Campaign's Channel addresses (see [`validate_channel_addresses()`]),
+/// the creator, the validators ids and their fee addresses is the zero address.
+pub fn validate_addresses(campaign: &Campaign) -> Result<(), Validation> {
+    validate_channel_addresses(&campaign.channel)?;
+
+    if campaign.creator.is_zero() {
+        return Err(Validation::ZeroAddress("creator"));
+    }
+
+    for validator in campaign.validators.iter() {
+        if validator.id.is_zero() {
+            return Err(Validation::ZeroAddress("validators.id"));
+        }
+
+        if validator.fee_addr.as_ref().is_some_and(Address::is_zero) {
+            return Err(Validation::ZeroAddress("validators.feeAddr"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates the [`Campaign.pacing`](Campaign::pacing),
+/// when set the daily cap should be positive and not exceed the Campaign budget.
+pub fn validate_pacing(campaign: &Campaign) -> Result<(), Validation> {
+    match &campaign.pacing {
+        Some(pacing)
+            if pacing.daily_cap == UnifiedNum::ZERO || pacing.daily_cap > campaign.budget =>
+        {
+            Err(Validation::InvalidPacing)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Validates that all the event types of the [`Campaign.pricing_bounds`](Campaign::pricing_bounds),
+/// [`Campaign.unit_pricing_overrides`](Campaign::unit_pricing_overrides)
+/// and [`Campaign.accepted_event_types`](Campaign::accepted_event_types) are supported.
+pub fn validate_event_types(
+    campaign: &Campaign,
+    supported: &[EventTypeConfig],
+) -> Result<(), Validation> {
+    let overrides = campaign
+        .unit_pricing_overrides
+        .values()
+        .flat_map(|overrides| overrides.keys());
+    let accepted = campaign.accepted_event_types.iter().flatten();
+
+    let all_supported = campaign
+        .pricing_bounds
+        .keys()
+        .chain(overrides)
+        .chain(accepted)
+        .all(|event_type| {
+            supported
+                .iter()
+                .any(|config| &config.event_type == event_type)
+        });
+
+    if all_supported {
+        Ok(())
+    } else {
+        Err(Validation::UnsupportedEventType)
+    }
+}
+
 pub fn creator_listed(campaign: &Campaign, whitelist: &[Address]) -> bool {
     // if the list is empty, return true, as we don't have a whitelist to restrict us to
     // or if we have a list, check if it includes the `channel.creator`
@@ -134,12 +354,14 @@ pub fn creator_listed(campaign: &Campaign, whitelist: &[Address]) -> bool {
 mod test {
     use super::*;
     use crate::{
+        campaign::{Pacing, Pricing},
         config::{self, GANACHE_CONFIG},
+        sentry::{EventType, CLICK, IMPRESSION},
         test_util::{
-            ADVERTISER, DUMMY_CAMPAIGN, DUMMY_VALIDATOR_FOLLOWER, DUMMY_VALIDATOR_LEADER, FOLLOWER,
-            GUARDIAN, IDS, LEADER, PUBLISHER,
+            ADVERTISER, DUMMY_AD_UNITS, DUMMY_CAMPAIGN, DUMMY_IPFS, DUMMY_VALIDATOR_FOLLOWER,
+            DUMMY_VALIDATOR_LEADER, FOLLOWER, GUARDIAN, IDS, LEADER, PUBLISHER,
         },
-        BigNum,
+        BigNum, ValidatorDesc,
     };
     use chrono::{TimeZone, Utc};
     use std::str::FromStr;
@@ -286,9 +508,8 @@ mod test {
         // token not listed
         {
             let mut campaign = DUMMY_CAMPAIGN.clone();
-            campaign.channel.token = "0x0000000000000000000000000000000000000000"
-                .parse::<Address>()
-                .expect("Should parse");
+            // the zero address is rejected before the token whitelist is checked
+            campaign.channel.token = *PUBLISHER;
 
             let validation_error = campaign
                 .validate(&config, IDS[&LEADER])
@@ -442,4 +663,410 @@ mod test {
                 .expect("Should pass validation");
         }
     }
+
+    #[test]
+    fn zero_validator_fees_validation() {
+        let mut campaign = DUMMY_CAMPAIGN.clone();
+        campaign.validators = {
+            let zero_fee = |validator: &ValidatorDesc| ValidatorDesc {
+                fee: UnifiedNum::ZERO,
+                ..validator.clone()
+            };
+            let validators = campaign.validators.iter().map(zero_fee).collect::<Vec<_>>();
+
+            Validators::new((validators[0].clone(), validators[1].clone()))
+        };
+
+        let mut config = config::GANACHE_CONFIG.clone();
+        assert_eq!(
+            Err(Error::Validation(Validation::MinimumValidatorFeeNotMet)),
+            campaign.clone().validate(&config, IDS[&LEADER]).map(|_| ()),
+            "Zero fees should not be allowed by default"
+        );
+
+        let token_info = config
+            .chains
+            .values_mut()
+            .find_map(|chain_info| {
+                chain_info
+                    .tokens
+                    .values_mut()
+                    .find(|token_info| token_info.address == campaign.channel.token)
+            })
+            .expect("Should find Dummy campaign.channel.token");
+        token_info.allow_zero_fees = true;
+
+        campaign
+            .clone()
+            .validate(&config, IDS[&LEADER])
+            .expect("Should allow zero fees");
+
+        // a non-zero fee should still be at least the minimum fee
+        campaign.validators = {
+            let validators = campaign
+                .validators
+                .iter()
+                .map(|validator| ValidatorDesc {
+                    fee: if validator.id == IDS[&LEADER] {
+                        UnifiedNum::from(1)
+                    } else {
+                        UnifiedNum::ZERO
+                    },
+                    ..validator.clone()
+                })
+                .collect::<Vec<_>>();
+
+            Validators::new((validators[0].clone(), validators[1].clone()))
+        };
+
+        assert_eq!(
+            Err(Error::Validation(Validation::MinimumValidatorFeeNotMet)),
+            campaign.validate(&config, IDS[&LEADER]).map(|_| ()),
+        );
+    }
+
+    #[test]
+    fn max_validator_fee_validation() {
+        let config_with_max_fee = |max_validator_fee: Option<u32>| {
+            let mut config = config::GANACHE_CONFIG.clone();
+            config
+                .chains
+                .values_mut()
+                .find_map(|chain_info| {
+                    chain_info
+                        .tokens
+                        .values_mut()
+                        .find(|token_info| token_info.address == DUMMY_CAMPAIGN.channel.token)
+                })
+                .expect("Should find Dummy campaign.channel.token")
+                .max_validator_fee = max_validator_fee;
+            config
+        };
+
+        let with_follower_fee = |fee: UnifiedNum| {
+            let mut campaign = DUMMY_CAMPAIGN.clone();
+            let follower = ValidatorDesc {
+                fee,
+                ..DUMMY_VALIDATOR_FOLLOWER.clone()
+            };
+            campaign.validators = Validators::new((DUMMY_VALIDATOR_LEADER.clone(), follower));
+            campaign
+        };
+
+        // 1 promille = 0.001
+        let config = config_with_max_fee(Some(1));
+
+        // fee is exactly the maximum fee
+        with_follower_fee(UnifiedNum::from_whole(0.001))
+            .validate(&config, IDS[&LEADER])
+            .expect("Should allow a fee equal to the maximum fee");
+
+        // the fee of the other validator is over the maximum fee
+        assert_eq!(
+            Error::Validation(Validation::MaximumValidatorFeeExceeded),
+            with_follower_fee(UnifiedNum::from_whole(0.5))
+                .validate(&config, IDS[&LEADER])
+                .expect_err("Should reject a fee over the maximum fee"),
+        );
+
+        // without a maximum fee any fee is allowed
+        with_follower_fee(UnifiedNum::from_whole(0.5))
+            .validate(&config_with_max_fee(None), IDS[&LEADER])
+            .expect("Should allow any fee without a maximum fee");
+    }
+
+    #[test]
+    fn campaign_duration_and_budget_validation() {
+        let mut config = config::GANACHE_CONFIG.clone();
+        config.limits.max_campaign_duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+        let from = Utc::now();
+        let max_to = from + chrono::Duration::days(30);
+
+        // duration is exactly the maximum duration
+        {
+            let mut campaign = DUMMY_CAMPAIGN.clone();
+            campaign.active.from = Some(from);
+            campaign.active.to = max_to;
+
+            assert!(campaign.validate(&config, IDS[&LEADER]).is_ok());
+        }
+
+        // duration is 1 second over the maximum duration
+        {
+            let mut campaign = DUMMY_CAMPAIGN.clone();
+            campaign.active.from = Some(from);
+            campaign.active.to = max_to + chrono::Duration::seconds(1);
+
+            assert_eq!(
+                Error::Validation(Validation::MaximumCampaignDurationExceeded),
+                campaign
+                    .validate(&config, IDS[&LEADER])
+                    .expect_err("Should trigger validation error"),
+            );
+        }
+
+        // without `active.from` the duration is counted from the Campaign creation
+        {
+            let mut campaign = DUMMY_CAMPAIGN.clone();
+            campaign.created = from;
+            campaign.active.to = max_to + chrono::Duration::seconds(1);
+
+            assert_eq!(
+                Err(Validation::MaximumCampaignDurationExceeded),
+                validate_campaign_duration(&campaign, config.limits.max_campaign_duration),
+            );
+        }
+
+        // budget is below the per-token minimum campaign budget
+        {
+            let token = &config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Campaign token should be whitelisted")
+                .token;
+            let min_budget = UnifiedNum::from_precision(
+                token.min_campaign_budget.clone(),
+                token.precision.get(),
+            )
+            .expect("Should convert the minimum budget");
+
+            assert_eq!(Ok(()), validate_campaign_budget(min_budget, token));
+            assert_eq!(
+                Err(Validation::MinimumDepositNotMet),
+                validate_campaign_budget(min_budget - UnifiedNum::from_u64(1), token),
+            );
+
+            let mut campaign = DUMMY_CAMPAIGN.clone();
+            campaign.active.from = Some(from);
+            campaign.active.to = max_to;
+            campaign.budget = min_budget - UnifiedNum::from_u64(1);
+
+            assert_eq!(
+                Error::Validation(Validation::MinimumDepositNotMet),
+                campaign
+                    .validate(&config, IDS[&LEADER])
+                    .expect_err("Should trigger validation error"),
+            );
+        }
+    }
+
+    #[test]
+    fn unit_pricing_overrides_validation() {
+        let config = config::GANACHE_CONFIG.clone();
+        let with_override = |ad_unit, min: u64, max: u64| {
+            let mut campaign = DUMMY_CAMPAIGN.clone();
+            campaign.ad_units = DUMMY_AD_UNITS.to_vec();
+            campaign.unit_pricing_overrides = [(
+                ad_unit,
+                [(
+                    IMPRESSION,
+                    Pricing {
+                        min: min.into(),
+                        max: max.into(),
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            )]
+            .into();
+
+            campaign
+        };
+
+        // override of an AdUnit of the Campaign
+        {
+            let _campaign_context = with_override(DUMMY_AD_UNITS[0].ipfs, 2, 20)
+                .validate(&config, IDS[&LEADER])
+                .expect("Should pass validation");
+        }
+
+        // override of an AdUnit which is not in the Campaign
+        {
+            assert!(!DUMMY_AD_UNITS.iter().any(|unit| unit.ipfs == DUMMY_IPFS[0]));
+
+            let validation_error = with_override(DUMMY_IPFS[0], 2, 20)
+                .validate(&config, IDS[&LEADER])
+                .expect_err("Should trigger validation error");
+            assert_eq!(
+                Error::Validation(Validation::UnitPricingOverrideForeignUnit),
+                validation_error,
+            );
+        }
+
+        // override above the Campaign max * multiplier
+        {
+            let max = 10 * config.limits.unit_pricing_override_multiplier + 1;
+            let validation_error = with_override(DUMMY_AD_UNITS[0].ipfs, 2, max)
+                .validate(&config, IDS[&LEADER])
+                .expect_err("Should trigger validation error");
+            assert_eq!(
+                Error::Validation(Validation::UnitPricingOverrideOutOfBounds),
+                validation_error,
+            );
+        }
+
+        // override min > max
+        {
+            let validation_error = with_override(DUMMY_AD_UNITS[0].ipfs, 5, 4)
+                .validate(&config, IDS[&LEADER])
+                .expect_err("Should trigger validation error");
+            assert_eq!(
+                Error::Validation(Validation::UnitPricingOverrideOutOfBounds),
+                validation_error,
+            );
+        }
+    }
+
+    #[test]
+    fn accepted_event_types_validation() {
+        let config = config::GANACHE_CONFIG.clone();
+        let with_accepted = |accepted: Option<Vec<EventType>>| {
+            let mut campaign = DUMMY_CAMPAIGN.clone();
+            campaign.accepted_event_types = accepted;
+
+            campaign.validate(&config, IDS[&LEADER])
+        };
+
+        with_accepted(None).expect("Should accept all event types");
+        with_accepted(Some(vec![IMPRESSION])).expect("Should accept only impressions");
+
+        for invalid in [vec![], vec![IMPRESSION, CLICK, IMPRESSION]] {
+            assert_eq!(
+                Err(Error::Validation(Validation::InvalidAcceptedEventTypes)),
+                with_accepted(Some(invalid)).map(|_| ()),
+            );
+        }
+    }
+
+    /// The validated field and how to set it to the zero address.
+    type ZeroAddressCase = (&'static str, fn(&mut Campaign, Address));
+
+    #[test]
+    fn zero_addresses_validation() {
+        let config = config::GANACHE_CONFIG.clone();
+        let zero = Address::ZERO;
+
+        DUMMY_CAMPAIGN
+            .clone()
+            .validate(&config, IDS[&LEADER])
+            .expect("The dummy Campaign should be valid");
+
+        let cases: [ZeroAddressCase; 7] = [
+            ("channel.leader", |campaign, zero| {
+                campaign.channel.leader = zero.into()
+            }),
+            ("channel.follower", |campaign, zero| {
+                campaign.channel.follower = zero.into()
+            }),
+            ("channel.guardian", |campaign, zero| {
+                campaign.channel.guardian = zero
+            }),
+            ("channel.token", |campaign, zero| {
+                campaign.channel.token = zero
+            }),
+            ("creator", |campaign, zero| campaign.creator = zero),
+            ("validators.id", |campaign, zero| {
+                campaign.validators = Validators::new((
+                    ValidatorDesc {
+                        id: zero.into(),
+                        ..DUMMY_VALIDATOR_LEADER.clone()
+                    },
+                    DUMMY_VALIDATOR_FOLLOWER.clone(),
+                ))
+            }),
+            ("validators.feeAddr", |campaign, zero| {
+                campaign.validators = Validators::new((
+                    DUMMY_VALIDATOR_LEADER.clone(),
+                    ValidatorDesc {
+                        fee_addr: Some(zero),
+                        ..DUMMY_VALIDATOR_FOLLOWER.clone()
+                    },
+                ))
+            }),
+        ];
+
+        for (field, set_zero) in cases {
+            let mut campaign = DUMMY_CAMPAIGN.clone();
+            set_zero(&mut campaign, zero);
+
+            assert_eq!(
+                Err(Validation::ZeroAddress(field)),
+                validate_addresses(&campaign)
+            );
+            assert_eq!(
+                Err(Error::Validation(Validation::ZeroAddress(field))),
+                campaign.validate(&config, IDS[&LEADER]).map(|_| ()),
+            );
+        }
+
+        // a non-zero fee address is valid
+        let mut campaign = DUMMY_CAMPAIGN.clone();
+        campaign.validators = Validators::new((
+            ValidatorDesc {
+                fee_addr: Some(*GUARDIAN),
+                ..DUMMY_VALIDATOR_LEADER.clone()
+            },
+            DUMMY_VALIDATOR_FOLLOWER.clone(),
+        ));
+        assert_eq!(Ok(()), validate_addresses(&campaign));
+    }
+
+    #[test]
+    fn pacing_validation() {
+        let config = config::GANACHE_CONFIG.clone();
+        let with_daily_cap = |daily_cap: Option<UnifiedNum>| {
+            let mut campaign = DUMMY_CAMPAIGN.clone();
+            campaign.pacing = daily_cap.map(|daily_cap| Pacing { daily_cap });
+
+            campaign.validate(&config, IDS[&LEADER])
+        };
+
+        let budget = DUMMY_CAMPAIGN.budget;
+        with_daily_cap(None).expect("Should accept no pacing");
+        with_daily_cap(Some(budget)).expect("Should accept a daily cap equal to the budget");
+
+        let above_budget = budget
+            .checked_add(&UnifiedNum::from_u64(1))
+            .expect("Should not overflow");
+        for invalid in [UnifiedNum::ZERO, above_budget] {
+            assert_eq!(
+                Err(Error::Validation(Validation::InvalidPacing)),
+                with_daily_cap(Some(invalid)).map(|_| ()),
+            );
+        }
+    }
+
+    #[test]
+    fn custom_event_types_validation() {
+        let sign_up = "SIGN_UP".parse::<EventType>().expect("Should parse");
+        let pricing = Pricing {
+            min: 1_000.into(),
+            max: 2_000.into(),
+        };
+
+        let mut campaign = DUMMY_CAMPAIGN.clone();
+        campaign.pricing_bounds = [(IMPRESSION, pricing.clone()), (sign_up, pricing)]
+            .into_iter()
+            .collect();
+
+        let mut config = config::GANACHE_CONFIG.clone();
+        assert_eq!(
+            Err(Error::Validation(Validation::UnsupportedEventType)),
+            campaign.clone().validate(&config, IDS[&LEADER]).map(|_| ()),
+            "SIGN_UP is not a supported event type by default"
+        );
+
+        config.event_types.push(sign_up.into());
+        campaign
+            .clone()
+            .validate(&config, IDS[&LEADER])
+            .expect("Should accept the configured SIGN_UP event type");
+
+        // an accepted event type which is not supported
+        campaign.accepted_event_types = Some(vec![IMPRESSION, "VIEW".parse().unwrap()]);
+        assert_eq!(
+            Err(Error::Validation(Validation::UnsupportedEventType)),
+            campaign.validate(&config, IDS[&LEADER]).map(|_| ()),
+        );
+    }
 }
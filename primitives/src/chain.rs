@@ -52,6 +52,12 @@ pub struct Chain {
     pub rpc: ApiUrl,
     /// The OUTPACE contract address on this Chain
     pub outpace: Address,
+    /// The Sweeper contract address on this Chain, which deploys the counterfactual
+    /// Depositor contracts that sweep the pending deposits to OUTPACE.
+    ///
+    /// Without it the pending deposits can't be looked up.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sweeper: Option<Address>,
 }
 
 /// Context of [`TokenInfo`] & [`Chain`] information for given [`Channel`] or [`Campaign`].
@@ -1,14 +1,28 @@
 use std::{fmt, ops::Deref, str::FromStr};
 
+use chrono::{DateTime, Utc};
 use ethereum_types::U256;
 
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_hex::{SerHex, StrictPfx};
+use thiserror::Error;
 
 use hex::{FromHex, FromHexError};
 
 use crate::{Address, Validator, ValidatorId};
 
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("Channel `{0}` is required")]
+    MissingField(&'static str),
+    #[error("Channel leader and follower should be different validators")]
+    SameLeaderAndFollower,
+    #[error("Channel guardian should not be the zero address")]
+    ZeroGuardian,
+    #[error("Channel token should not be the zero address")]
+    ZeroToken,
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Eq, Copy, Clone, Hash)]
 #[serde(transparent)]
 pub struct ChannelId(
@@ -20,6 +34,22 @@ pub struct ChannelId(
 );
 
 impl ChannelId {
+    /// Derives the [`ChannelId`] of the [`Channel`] the same way the `Outpace` contract does:
+    ///
+    /// `keccak256(abi.encode(leader, follower, guardian, tokenAddr, nonce))`
+    ///
+    /// The ABI encoded value which is hashed can be retrieved with [`Channel::id_preimage()`].
+    pub fn derive(channel: &Channel) -> Self {
+        use tiny_keccak::{Hasher, Keccak};
+
+        let mut channel_id = [0_u8; 32];
+        let mut hasher = Keccak::v256();
+        hasher.update(&channel.id_preimage());
+        hasher.finalize(&mut channel_id);
+
+        Self(channel_id)
+    }
+
     pub fn as_bytes(&self) -> &[u8; 32] {
         &self.0
     }
@@ -101,9 +131,21 @@ pub struct Channel {
 }
 
 impl Channel {
+    /// Creates a [`ChannelBuilder`] for constructing a validated [`Channel`].
+    pub fn builder() -> ChannelBuilder {
+        ChannelBuilder::default()
+    }
+
+    /// See [`ChannelId::derive()`]
     pub fn id(&self) -> ChannelId {
+        ChannelId::derive(self)
+    }
+
+    /// The ABI encoded Channel fields which are hashed for the [`ChannelId`].
+    ///
+    /// Useful for debugging mismatches with the on-chain Channel id.
+    pub fn id_preimage(&self) -> Vec<u8> {
         use ethabi::{encode, Token};
-        use tiny_keccak::{Hasher, Keccak};
 
         let tokens = [
             Token::Address(self.leader.as_bytes().into()),
@@ -113,12 +155,7 @@ impl Channel {
             Token::FixedBytes(self.nonce.to_bytes().to_vec()),
         ];
 
-        let mut channel_id = [0_u8; 32];
-        let mut hasher = Keccak::v256();
-        hasher.update(&encode(&tokens));
-        hasher.finalize(&mut channel_id);
-
-        ChannelId::from(channel_id)
+        encode(&tokens)
     }
 
     pub fn find_validator(&self, validator: ValidatorId) -> Option<Validator<ValidatorId>> {
@@ -130,11 +167,120 @@ impl Channel {
     }
 }
 
+/// Builder for a [`Channel`], see [`Channel::builder()`].
+///
+/// All fields are required and [`ChannelBuilder::build()`] validates that:
+/// - the leader and follower are different validators
+/// - the guardian and token are not the zero address
+#[derive(Debug, Clone, Default)]
+pub struct ChannelBuilder {
+    leader: Option<ValidatorId>,
+    follower: Option<ValidatorId>,
+    guardian: Option<Address>,
+    token: Option<Address>,
+    nonce: Option<Nonce>,
+}
+
+impl ChannelBuilder {
+    pub fn leader(mut self, leader: impl Into<ValidatorId>) -> Self {
+        self.leader = Some(leader.into());
+        self
+    }
+
+    pub fn follower(mut self, follower: impl Into<ValidatorId>) -> Self {
+        self.follower = Some(follower.into());
+        self
+    }
+
+    pub fn guardian(mut self, guardian: Address) -> Self {
+        self.guardian = Some(guardian);
+        self
+    }
+
+    pub fn token(mut self, token: Address) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    pub fn nonce(mut self, nonce: impl Into<Nonce>) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    pub fn build(self) -> Result<Channel, Error> {
+        let leader = self.leader.ok_or(Error::MissingField("leader"))?;
+        let follower = self.follower.ok_or(Error::MissingField("follower"))?;
+        let guardian = self.guardian.ok_or(Error::MissingField("guardian"))?;
+        let token = self.token.ok_or(Error::MissingField("token"))?;
+        let nonce = self.nonce.ok_or(Error::MissingField("nonce"))?;
+
+        if leader == follower {
+            return Err(Error::SameLeaderAndFollower);
+        }
+
+        if guardian.is_zero() {
+            return Err(Error::ZeroGuardian);
+        }
+
+        if token.is_zero() {
+            return Err(Error::ZeroToken);
+        }
+
+        Ok(Channel {
+            leader,
+            follower,
+            guardian,
+            token,
+            nonce,
+        })
+    }
+}
+
 /// The nonce is an Unsigned 256 number
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Nonce(pub U256);
 
 impl Nonce {
+    /// Random 128 bits [`Nonce`].
+    pub fn random() -> Self {
+        Self(U256::from(uuid::Uuid::new_v4().as_u128()))
+    }
+
+    /// [`Nonce`] from the timestamp in milliseconds.
+    ///
+    /// Timestamps before the Unix epoch result in a `0` nonce.
+    pub fn from_timestamp(timestamp: DateTime<Utc>) -> Self {
+        let millis = u64::try_from(timestamp.timestamp_millis()).unwrap_or_default();
+
+        Self(U256::from(millis))
+    }
+
+    /// [`Nonce`] derived from the `creator` of the Channel and the `time` of its creation:
+    ///
+    /// `keccak256(abi.encode(creator, timestampMillis))`
+    ///
+    /// Unlike [`Nonce::from_timestamp()`], creators which open a Channel at the same time
+    /// get different nonces and the same creator gets a new nonce every millisecond.
+    /// Timestamps before the Unix epoch are treated as `0`.
+    #[cfg(test)]
+    pub fn from_creator_and_time(creator: Address, time: DateTime<Utc>) -> Self {
+        use ethabi::{encode, Token};
+        use tiny_keccak::{Hasher, Keccak};
+
+        let millis = u64::try_from(time.timestamp_millis()).unwrap_or_default();
+        let tokens = [
+            Token::Address(creator.as_bytes().into()),
+            Token::Uint(millis.into()),
+        ];
+
+        let mut hash = [0_u8; 32];
+        let mut hasher = Keccak::v256();
+        hasher.update(&encode(&tokens));
+        hasher.finalize(&mut hash);
+
+        Self(U256::from_big_endian(&hash))
+    }
+
     /// In Big-Endian
     pub fn to_bytes(self) -> [u8; 32] {
         // the impl of From<U256> uses BigEndian
@@ -234,6 +380,149 @@ mod test {
         )
     }
 
+    #[test]
+    fn derives_channel_id() {
+        use crate::test_util::{FOLLOWER, GUARDIAN, IDS, LEADER};
+
+        let mocked_token_1337 = "0x2bcaf6968aec8a3b5126fbfab5fd419da6e8ad8e"
+            .parse::<Address>()
+            .unwrap();
+        let mocked_token_1 = "0x12a28f2bfBFfDf5842657235cC058242f40fDEa6"
+            .parse::<Address>()
+            .unwrap();
+
+        let vectors = [
+            (
+                mocked_token_1337,
+                Nonce::from(0_u64),
+                "0xf147fa3f1c5e5e06d359c15aa082442cc3e0380f306306022d1e9047c565a0f9",
+            ),
+            (
+                mocked_token_1337,
+                Nonce::from(987_654_321_u32),
+                "0x6b47e0bde392875b30533160e0ec84f3124efff6892f486b7453d40c67809811",
+            ),
+            (
+                mocked_token_1,
+                Nonce::from(12345_u32),
+                "0x4a7b76181212558d228796dd1e51230aa1952d9bc81814130de25c7a44ffc0b5",
+            ),
+        ];
+
+        for (token, nonce, expected_id) in vectors {
+            let channel = Channel::builder()
+                .leader(IDS[&LEADER])
+                .follower(IDS[&FOLLOWER])
+                .guardian(*GUARDIAN)
+                .token(token)
+                .nonce(nonce)
+                .build()
+                .expect("Should build Channel");
+
+            let expected_id = expected_id.parse::<ChannelId>().unwrap();
+
+            assert_eq!(expected_id, ChannelId::derive(&channel));
+            assert_eq!(expected_id, channel.id());
+
+            let preimage = channel.id_preimage();
+            // 5 ABI encoded tokens of 32 bytes
+            assert_eq!(160, preimage.len());
+            assert_eq!(&channel.leader.as_bytes()[..], &preimage[12..32]);
+            assert_eq!(&nonce.to_bytes()[..], &preimage[128..]);
+        }
+    }
+
+    #[test]
+    fn channel_builder_validation() {
+        use crate::test_util::{ADDRESS_3, FOLLOWER, GUARDIAN, IDS, LEADER};
+
+        let zero_address = Address::from_bytes(&[0_u8; 20]);
+        let builder = Channel::builder()
+            .leader(IDS[&LEADER])
+            .follower(IDS[&FOLLOWER])
+            .guardian(*GUARDIAN)
+            .token(*ADDRESS_3)
+            .nonce(1_u64);
+
+        assert!(builder.clone().build().is_ok());
+
+        assert_eq!(
+            Err(Error::MissingField("nonce")),
+            Channel::builder()
+                .leader(IDS[&LEADER])
+                .follower(IDS[&FOLLOWER])
+                .guardian(*GUARDIAN)
+                .token(*ADDRESS_3)
+                .build()
+        );
+        assert_eq!(
+            Err(Error::SameLeaderAndFollower),
+            builder.clone().follower(IDS[&LEADER]).build()
+        );
+        assert_eq!(
+            Err(Error::ZeroGuardian),
+            builder.clone().guardian(zero_address).build()
+        );
+        assert_eq!(Err(Error::ZeroToken), builder.token(zero_address).build());
+    }
+
+    #[test]
+    fn nonce_helpers() {
+        use chrono::TimeZone;
+
+        let timestamp = Utc.ymd(2022, 11, 21).and_hms_milli(12, 0, 0, 500);
+        assert_eq!(
+            Nonce::from(1_669_032_000_500_u64),
+            Nonce::from_timestamp(timestamp)
+        );
+        assert_eq!(
+            Nonce::from(0_u64),
+            Nonce::from_timestamp(Utc.ymd(1969, 12, 31).and_hms(0, 0, 0))
+        );
+
+        assert_ne!(Nonce::random(), Nonce::random());
+    }
+
+    #[test]
+    fn generated_nonces_produce_distinct_channel_ids() {
+        use crate::test_util::{ADVERTISER, CREATOR, DUMMY_CAMPAIGN};
+        use chrono::TimeZone;
+
+        let time = Utc.ymd(2022, 11, 21).and_hms_milli(12, 0, 0, 500);
+        let nonce = Nonce::from_creator_and_time(*CREATOR, time);
+
+        assert_eq!(
+            nonce,
+            Nonce::from_creator_and_time(*CREATOR, time),
+            "Should be deterministic"
+        );
+
+        let nonces = [
+            nonce,
+            Nonce::from_creator_and_time(*ADVERTISER, time),
+            Nonce::from_creator_and_time(*CREATOR, time + chrono::Duration::milliseconds(1)),
+            Nonce::random(),
+            Nonce::random(),
+        ];
+
+        let channel_ids = nonces
+            .iter()
+            .map(|nonce| {
+                Channel {
+                    nonce: *nonce,
+                    ..DUMMY_CAMPAIGN.channel
+                }
+                .id()
+            })
+            .collect::<std::collections::HashSet<_>>();
+
+        assert_eq!(
+            nonces.len(),
+            channel_ids.len(),
+            "All nonces & channel ids should differ"
+        );
+    }
+
     #[test]
     fn de_serializes_nonce() {
         let nonce_str = "12345";
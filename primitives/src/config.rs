@@ -1,12 +1,21 @@
 use crate::{
+    campaign::Pricing,
     chain::{Chain, ChainId},
     event_submission::RateLimit,
+    sentry::{EventType, CLICK, IMPRESSION},
     util::ApiUrl,
-    Address, BigNum, ChainOf, ValidatorId,
+    Address, BigNum, ChainOf, ChannelId, UnifiedNum, ValidatorId,
 };
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, num::NonZeroU8, time::Duration};
+use serde_with::{serde_as, DurationMilliSeconds};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    num::{NonZeroU64, NonZeroU8, NonZeroUsize},
+    path::PathBuf,
+    time::Duration,
+};
 use thiserror::Error;
 
 pub use toml::de::Error as TomlError;
@@ -65,6 +74,21 @@ pub struct Config {
     ///
     /// If empty, any address will be allowed to be a validator in a [`Campaign`](crate::Campaign).
     pub validators_whitelist: Vec<ValidatorId>,
+    /// Whether the legacy Ethereum Web Token format is still used for authentication tokens.
+    ///
+    /// When enabled, the authentication tokens are signed in the legacy format and
+    /// both legacy and EIP-712 typed data tokens are accepted.
+    /// When disabled, the tokens are signed and accepted only as EIP-712 typed data.
+    ///
+    /// Used for the migration to EIP-712 tokens, default: `true`
+    #[serde(default = "default_legacy_auth_tokens")]
+    pub legacy_auth_tokens: bool,
+    /// The number of eras (1 minute each) after the validity of an authentication token
+    /// in which the token is still accepted, e.g. while the validators are rotated.
+    ///
+    /// The Sessions of such tokens are marked with `era_grace`, default: `0`
+    #[serde(default)]
+    pub auth_era_grace: u32,
     /// The key of this map is a human-readable text of the Chain name
     /// for readability in the configuration file.
     ///
@@ -78,6 +102,14 @@ pub struct Config {
     pub chains: HashMap<String, ChainInfo>,
     /// Any limits applied to Sentry or Validator.
     pub limits: Limits,
+    /// The [`EventType`]s supported by Sentry, events of any other type are rejected.
+    ///
+    /// Custom event types (e.g. `SIGN_UP`) can be introduced without any code changes,
+    /// see [`EventTypeConfig`].
+    ///
+    /// default: [`IMPRESSION`] & [`CLICK`] without a default pricing
+    #[serde(default = "default_event_types")]
+    pub event_types: Vec<EventTypeConfig>,
 }
 
 impl Config {
@@ -88,6 +120,15 @@ impl Config {
         toml::from_str(toml)
     }
 
+    /// Finds the [`EventTypeConfig`] of a supported [`EventType`].
+    ///
+    /// Returns `None` if the event type is not supported.
+    pub fn find_event_type(&self, event_type: &EventType) -> Option<&EventTypeConfig> {
+        self.event_types
+            .iter()
+            .find(|config| &config.event_type == event_type)
+    }
+
     /// Finds a [`Chain`] based on the [`ChainId`].
     pub fn find_chain(&self, chain_id: ChainId) -> Option<&ChainInfo> {
         self.chains
@@ -107,7 +148,59 @@ impl Config {
     }
 }
 
+/// A supported [`EventType`] and how it's priced.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EventTypeConfig {
+    #[serde(rename = "type")]
+    pub event_type: EventType,
+    /// The pricing of a single event used when neither the [`Campaign.pricing_bounds`](crate::Campaign::pricing_bounds)
+    /// nor the [`Campaign.unit_pricing_overrides`](crate::Campaign::unit_pricing_overrides)
+    /// have a pricing for the event type.
+    ///
+    /// default: `None` - the event is not paid for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_pricing: Option<Pricing>,
+}
+
+impl From<EventType> for EventTypeConfig {
+    fn from(event_type: EventType) -> Self {
+        Self {
+            event_type,
+            default_pricing: None,
+        }
+    }
+}
+
+fn default_legacy_auth_tokens() -> bool {
+    true
+}
+
+fn default_event_types() -> Vec<EventTypeConfig> {
+    vec![IMPRESSION.into(), CLICK.into()]
+}
+
+fn default_empty_spendable_max_age() -> Duration {
+    Duration::from_secs(24 * 60 * 60)
+}
+
+fn default_conversion_attribution_window() -> Duration {
+    Duration::from_secs(7 * 24 * 60 * 60)
+}
+
+fn default_campaign_awaiting_approval_after() -> Duration {
+    Duration::from_secs(5 * 60)
+}
+
+fn default_campaign_stalled_after() -> Duration {
+    Duration::from_secs(60 * 60)
+}
+
+fn default_campaign_reservation_window() -> Duration {
+    Duration::from_secs(30)
+}
+
 /// Config values that are used in the sentry only
+#[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SentryConfig {
     /// A timeout to be used when collecting the Analytics for a request:
@@ -121,13 +214,129 @@ pub struct SentryConfig {
     pub analytics_maxtime: Duration,
     pub admins: Vec<Address>,
     pub platform: PlatformConfig,
+    /// The maximum allowed difference between the earners sum and the spenders sum
+    /// of a [`Channel`](crate::Channel)'s accounting, caused by rounding residue
+    /// in fee & precision conversions.
+    ///
+    /// Used for GET `/v5/channel/:id/accounting`.
+    ///
+    /// In [`UnifiedNum`] raw units, default: `0`
+    #[serde(default)]
+    pub accounting_tolerance: UnifiedNum,
+    /// Whether any authenticated address, as a prospective spender, can register
+    /// a [`Channel`](crate::Channel) using POST `/v5/channel`.
+    ///
+    /// Otherwise only the Channel's validators and guardian can register it.
+    ///
+    /// default: `false`
+    #[serde(default)]
+    pub spender_channel_registration: bool,
+    /// The age after which a [`Spendable`](crate::spender::Spendable) with a zero
+    /// total deposit is removed from the database.
+    ///
+    /// Sentry runs the cleanup with this same interval, `0` disables the cleanup.
+    ///
+    /// default: `86 400 000` (1 day)
+    ///
+    /// In milliseconds
+    #[serde(
+        with = "std_duration_millis",
+        default = "default_empty_spendable_max_age"
+    )]
+    pub empty_spendable_max_age: Duration,
+    /// The window after a paid [`CLICK`] in which a [`CONVERSION`](crate::sentry::CONVERSION) attributed to it
+    /// is paid, see [`Event::Conversion`](crate::sentry::Event::Conversion).
+    ///
+    /// default: `604 800 000` (7 days)
+    ///
+    /// In milliseconds
+    #[serde(
+        with = "std_duration_millis",
+        default = "default_conversion_attribution_window"
+    )]
+    pub conversion_attribution_window: Duration,
+    /// The time after the last `ApproveState` of the Channel after which a Campaign with
+    /// pending (not approved) spending is [`AwaitingApproval`](crate::sentry::campaign_health::CampaignHealthStatus::AwaitingApproval).
+    ///
+    /// default: `300 000` (5 minutes)
+    ///
+    /// In milliseconds
+    #[serde(
+        with = "std_duration_millis",
+        default = "default_campaign_awaiting_approval_after"
+    )]
+    pub campaign_awaiting_approval_after: Duration,
+    /// The time after the last `ApproveState` of the Channel after which a Campaign with
+    /// pending (not approved) spending is [`ChannelStalled`](crate::sentry::campaign_health::CampaignHealthStatus::ChannelStalled).
+    ///
+    /// default: `3 600 000` (1 hour)
+    ///
+    /// In milliseconds
+    #[serde(
+        with = "std_duration_millis",
+        default = "default_campaign_stalled_after"
+    )]
+    pub campaign_stalled_after: Duration,
+    /// For how long the budget of a Campaign which is being created is reserved,
+    /// so that concurrent Campaign creations of the same creator in the Channel
+    /// cannot spend the same deposit. The reservation is released once the Campaign
+    /// is created (or has failed to), the window only matters if it's never released.
+    ///
+    /// default: `30 000` (30 seconds)
+    ///
+    /// In milliseconds
+    #[serde(
+        with = "std_duration_millis",
+        default = "default_campaign_reservation_window"
+    )]
+    pub campaign_reservation_window: Duration,
+    /// The allowed Operating Systems of the request's `User-Agent`
+    /// which are kept in the Sentry `Session` for the analytics segmentation,
+    /// any other OS is recorded as `other`.
+    ///
+    /// The names are matched case-insensitively.
+    ///
+    /// default: `None` - the [`OperatingSystem::WHITELISTED`](crate::analytics::OperatingSystem::WHITELISTED)
+    /// and [`OperatingSystem::LINUX_DISTROS`](crate::analytics::OperatingSystem::LINUX_DISTROS)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os_allowlist: Option<Vec<String>>,
+    /// The IP addresses of the trusted proxies (e.g. load balancers) in front of Sentry.
+    ///
+    /// The client IP of the `Session` (used for e.g. the IP rate limits) is taken from the
+    /// `True-Client-IP`, `X-Real-IP` or `X-Forwarded-For` headers only if the request comes
+    /// directly from a trusted proxy, otherwise the IP address of the peer is used.
+    ///
+    /// default: `None` - the headers are used regardless of the peer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trusted_proxies: Option<Vec<IpAddr>>,
+    /// The maximum number of Campaigns for which the remaining budget
+    /// is requested from Redis with a single `MGET`.
+    ///
+    /// default: `None` - chunks of 1 000 Campaigns
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub campaign_remaining_chunk_size: Option<NonZeroUsize>,
+    /// When set, the responses of at least this size (in bytes) are compressed
+    /// with `gzip` for the requests which accept it, see the `Accept-Encoding` header.
+    ///
+    /// default: `None` - the responses are not compressed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_compression_min_size: Option<u16>,
+    /// When set, the Postgres queries which take longer than this
+    /// are logged with their elapsed time.
+    ///
+    /// In milliseconds, default: `None` - the slow queries are not logged
+    #[serde_as(as = "Option<DurationMilliSeconds<u64>>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slow_query_threshold: Option<Duration>,
 }
 
 /// Config values that are used in the validator worker only
+#[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct ValidatorWorkerConfig {
     /// The maximum number of [`Channel`](crate::Channel)s that the worker
-    /// can process for one tick.
+    /// can process for one tick,
+    /// including the monitored guardian Channels, see [`Self::guardian_monitoring`].
     pub max_channels: u32,
     /// The Validator Worker tick time.
     ///
@@ -142,6 +351,15 @@ pub struct ValidatorWorkerConfig {
     /// In milliseconds
     #[serde(with = "std_duration_millis")]
     pub heartbeat_time: Duration,
+    /// The maximum allowed difference between our clock and the clock of the other
+    /// validator of a [`Channel`](crate::Channel), as seen from the `timestamp`
+    /// of its latest [`Heartbeat`](crate::validator::Heartbeat).
+    ///
+    /// default: `60 000` (1 minute)
+    ///
+    /// In milliseconds
+    #[serde(with = "std_duration_millis", default = "default_clock_skew_threshold")]
+    pub clock_skew_threshold: Duration,
     /// The pro miles below which the [`ApproveState`](crate::validator::ApproveState)
     /// becomes **unhealthy** in the [`Channel`](crate::Channel)'s Follower.
     ///
@@ -156,6 +374,86 @@ pub struct ValidatorWorkerConfig {
     /// In pro milles (<= 1000)
     pub health_unsignable_promilles: u32,
     pub timeouts: Timeouts,
+    /// The retry policy for the idempotent GET requests
+    /// which the Validator Worker makes to Sentry.
+    ///
+    /// default: `3` attempts with a backoff of `500` milliseconds,
+    /// see [`RetryPolicy::default()`]
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    /// The page size requested when fetching all the [`Channel`](crate::Channel)s
+    /// or all the spenders of a Channel from Sentry, it's capped by the Sentry limits.
+    ///
+    /// default: `None` - Sentry's [`Limits.channels_find`](Limits::channels_find)
+    /// and [`Limits.spendable_find`](Limits::spendable_find)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<NonZeroU64>,
+    /// The interval (period length) at which the Channel's Leader creates a
+    /// [`ValidatorFeesSummary`](crate::sentry::fees_summary::ValidatorFeesSummary).
+    ///
+    /// default: `86 400 000` (24 hours)
+    ///
+    /// In milliseconds
+    #[serde(
+        with = "std_duration_millis",
+        default = "default_fees_summary_interval"
+    )]
+    pub fees_summary_interval: Duration,
+    /// The [`Channel`](crate::Channel)s which are ticked with debug tracing.
+    ///
+    /// The accounting snapshot, state root, health inputs and the propagated messages
+    /// of these Channels are logged on each tick and dumped to the [`Self::debug_dump_dir`].
+    ///
+    /// default: `[]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub debug_channels: Vec<ChannelId>,
+    /// The directory in which a JSON dump is written for each tick of the [`Self::debug_channels`].
+    ///
+    /// default: `None` - no dumps are written, only the logs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debug_dump_dir: Option<PathBuf>,
+    /// When set, the Sentry of the other validator of a [`Channel`](crate::Channel)
+    /// should pass a `GET /v5/whoami` challenge before messages are propagated to it.
+    /// A verified Sentry is trusted for this amount of time (in milliseconds).
+    ///
+    /// default: `None` - the other validators' Sentries are not verified
+    #[serde_as(as = "Option<DurationMilliSeconds<u64>>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer_verification_ttl: Option<Duration>,
+    /// When set, the worker also monitors (read-only) the [`Channel`](crate::Channel)s
+    /// of which it is the guardian, see [`GuardianMonitoring`].
+    ///
+    /// default: `None` - only the Channels of which the worker is a validator are ticked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guardian_monitoring: Option<GuardianMonitoring>,
+}
+
+fn default_clock_skew_threshold() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_fees_summary_interval() -> Duration {
+    Duration::from_secs(24 * 60 * 60)
+}
+
+/// The thresholds of the read-only monitoring of the guardian [`Channel`](crate::Channel)s.
+///
+/// The monitoring never signs nor propagates validator messages,
+/// it only logs and reports the found issues.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GuardianMonitoring {
+    /// The latest [`Heartbeat`](crate::validator::Heartbeat) of each validator
+    /// should have been received more recently than this.
+    ///
+    /// In milliseconds
+    #[serde(with = "std_duration_millis")]
+    pub heartbeat_stale_after: Duration,
+    /// The last [`ApproveState`](crate::validator::ApproveState) of the Channel
+    /// should have been received more recently than this.
+    ///
+    /// In milliseconds
+    #[serde(with = "std_duration_millis")]
+    pub approve_state_stale_after: Duration,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -163,6 +461,29 @@ pub struct PlatformConfig {
     pub url: ApiUrl,
     #[serde(with = "std_duration_millis")]
     pub keep_alive_interval: Duration,
+    /// The timeout of the requests to the Platform.
+    ///
+    /// default: `10 000` (10 seconds)
+    ///
+    /// In milliseconds
+    #[serde(with = "std_duration_millis", default = "default_platform_timeout")]
+    pub timeout: Duration,
+    /// How long the fetched [`AdSlot.min_per_impression`](crate::AdSlot::min_per_impression)
+    /// is cached for the payout of the events.
+    ///
+    /// default: `300 000` (5 minutes)
+    ///
+    /// In milliseconds
+    #[serde(with = "std_duration_millis", default = "default_slot_cache_ttl")]
+    pub slot_cache_ttl: Duration,
+}
+
+fn default_platform_timeout() -> Duration {
+    Duration::from_secs(10)
+}
+
+fn default_slot_cache_ttl() -> Duration {
+    Duration::from_secs(5 * 60)
 }
 
 /// Configured chain with tokens.
@@ -186,12 +507,80 @@ impl ChainInfo {
 
 /// Configured Token in a specific [`Chain`].
 /// Precision can differ for the same token from one [`Chain`] to another.
+#[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
 pub struct TokenInfo {
+    /// The minimum budget of a [`Campaign`](crate::Campaign) in this token,
+    /// both when creating it and when modifying its budget.
+    ///
+    /// In the token's precision.
     pub min_campaign_budget: BigNum,
     pub min_validator_fee: BigNum,
+    /// Whether a [`Campaign`](crate::Campaign) validator fee of exactly zero is accepted
+    /// regardless of the [`TokenInfo.min_validator_fee`], e.g. for testing Channels.
+    ///
+    /// default: `false`
+    #[serde(default)]
+    pub allow_zero_fees: bool,
+    /// The maximum validator fee of a [`Campaign`](crate::Campaign) in this token,
+    /// in promilles of the event payout, e.g. `100` is a fee of 10% of the payout.
+    ///
+    /// Campaigns with a higher validator fee are rejected and the validator fees
+    /// of the event payouts are capped to it.
+    ///
+    /// default: `None` - no maximum validator fee
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_validator_fee: Option<u32>,
     pub precision: NonZeroU8,
     pub address: Address,
+    /// How the validator fees of the event payouts are rounded.
+    #[serde(default)]
+    pub payout_rounding: PayoutRounding,
+    /// The minimum amount of each earner's payout of a Channel payout
+    /// (POST `/v5/channel/:id/pay`), in the token precision.
+    ///
+    /// default: `None` - no minimum payout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_payout: Option<BigNum>,
+    /// The window (in milliseconds) in which duplicate events of the same
+    /// Campaign, publisher, AdUnit, AdSlot and event type are dropped before spending.
+    /// It can be overridden by the
+    /// [`Campaign.event_submission.dedup_window`](crate::EventSubmission::dedup_window).
+    ///
+    /// default: `None` - events are not deduplicated
+    #[serde_as(as = "Option<DurationMilliSeconds<u64>>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_dedup_window: Option<Duration>,
+}
+
+impl TokenInfo {
+    /// The [`TokenInfo.max_validator_fee`](TokenInfo::max_validator_fee) as a
+    /// [`ValidatorDesc.fee`](crate::ValidatorDesc::fee), i.e. `1000` promilles is a fee of `1.0`
+    pub fn max_validator_fee_rate(&self) -> Option<UnifiedNum> {
+        // 1 promille = 0.001 = 100 000 in UnifiedNum precision
+        self.max_validator_fee
+            .map(|promilles| UnifiedNum::from_u64(u64::from(promilles) * 100_000))
+    }
+}
+
+/// The rounding of the validator fees for each event payout.
+///
+/// The spender is always charged with exactly `payout + leader fee + follower fee`
+/// using the already rounded fees, so the Leader, Follower and Publisher
+/// always sum up to the charged amount.
+/// The sub-unit residue of the fees is never charged, nor paid out:
+///
+/// - [`PayoutRounding::Floor`] - the residue stays in the remaining budget of the Campaign.
+/// - [`PayoutRounding::Nearest`] - the residue is rounded to the nearest unit,
+///   so the validators might receive up to half a unit more or less than the exact fee.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutRounding {
+    /// Floors the validator fees
+    Floor,
+    /// Rounds the validator fees to the nearest unit, halves are rounded up.
+    #[default]
+    Nearest,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -202,11 +591,37 @@ pub struct Limits {
     ///
     /// Also see: [`CampaignListResponse`](crate::sentry::campaign_list::CampaignListResponse)
     pub campaigns_find: u32,
+    /// The maximum number of [`Campaign`](crate::Campaign)s created at once
+    /// by Sentry's POST `/v5/campaign/bulk` route.
+    ///
+    /// default: `50`
+    #[serde(default = "default_campaigns_bulk_create")]
+    pub campaigns_bulk_create: u32,
+    /// The maximum number of active (i.e. not expired) [`Campaign`](crate::Campaign)s
+    /// of a single [`Channel`](crate::Channel).
+    ///
+    /// Sentry's POST `/v5/campaign` and POST `/v5/campaign/bulk` routes
+    /// reject the creation of Campaigns beyond it.
+    ///
+    /// default: `1000`
+    #[serde(default = "default_max_campaigns_per_channel")]
+    pub max_campaigns_per_channel: u32,
     /// The maximum number of [`Spender`](crate::spender::Spender)s per page
-    /// returned by Sentry's GET `/v5/channel/0xXXX.../spender/all` route.
+    /// returned by Sentry's GET `/v5/channel/0xXXX.../spender/all` route
+    /// and the maximum number of spenders requested at once
+    /// by Sentry's POST `/v5/channel/0xXXX.../spender/batch` route.
     ///
     /// Also see: [`AllSpendersResponse`](crate::sentry::AllSpendersResponse)
+    /// and [`SpendersBatchRequest`](crate::sentry::SpendersBatchRequest)
     pub spendable_find: u32,
+    /// The maximum number of earner & spender accounting entries per page
+    /// returned by Sentry's GET `/v5/channel/0xXXX.../accounting/page` route.
+    ///
+    /// Also see: [`AccountingPageResponse`](crate::sentry::AccountingPageResponse)
+    ///
+    /// default: `512`
+    #[serde(default = "default_accounting_find")]
+    pub accounting_find: u32,
     /// The maximum number of [`Channel`](crate::Channel)s per page
     /// returned by Sentry's GET `/v5/channel/list` route.
     ///
@@ -224,6 +639,9 @@ pub struct Limits {
     /// <= `limits.analytics_find` messages in the request.
     ///
     /// Also see: [`AnalyticsQuery`](crate::analytics::AnalyticsQuery)
+    ///
+    /// It's also the maximum number of hourly buckets returned by
+    /// Sentry's GET `/v5/campaign/:id/events/stats` route.
     pub analytics_find: u32,
     /// The maximum allowed limit of [`ValidatorMessage`](crate::sentry::validator_messages::ValidatorMessage)s per page
     /// returned by Sentry's GET `/v5/channel/0xXXX.../validator-messages` route.
@@ -234,9 +652,117 @@ pub struct Limits {
     /// Also see: [`ValidatorMessagesListResponse`](crate::sentry::validator_messages::ValidatorMessagesListResponse),
     /// [`ValidatorMessagesListQuery`](crate::sentry::validator_messages::ValidatorMessagesListQuery)
     pub msgs_find: u32,
-    /// The default IP rate limit that will be imposed if
-    /// [`Campaign.event_submission`](crate::Campaign::event_submission) is [`None`].
+    /// The maximum number of approved states (pairs of [`ApproveState`](crate::validator::ApproveState)
+    /// and [`NewState`](crate::validator::NewState)) returned by Sentry's
+    /// GET `/v5/channel/0xXXX.../last-approved/history` route.
+    ///
+    /// Request query also has a `limit` parameter, which can be used to return
+    /// <= `limits.approved_states_find` approved states in the request.
+    ///
+    /// Also see: [`LastApprovedHistoryQuery`](crate::sentry::LastApprovedHistoryQuery)
+    ///
+    /// default: `100`
+    #[serde(default = "default_approved_states_find")]
+    pub approved_states_find: u32,
+    /// The default IP rate limit that will be imposed on all Campaigns, alongside the rate limits of the rules of
+    /// [`Campaign.event_submission`](crate::Campaign::event_submission), which can only make it stricter.
     pub ip_rate_limit: RateLimit,
+    /// The maximum multiplier between the [`Campaign.unit_pricing_overrides`](crate::Campaign::unit_pricing_overrides)
+    /// and the Campaign's [`Campaign.pricing_bounds`](crate::Campaign::pricing_bounds) of the same event type:
+    ///
+    /// `base.min / multiplier <= override.min <= override.max <= base.max * multiplier`
+    ///
+    /// default: `10`
+    #[serde(default = "default_unit_pricing_override_multiplier")]
+    pub unit_pricing_override_multiplier: u64,
+    /// The maximum duration of a [`Campaign`](crate::Campaign), from its
+    /// [`Active.from`](crate::campaign::Active::from) (or [`Campaign.created`](crate::Campaign::created)
+    /// if it's not set) until its [`Active.to`](crate::campaign::Active::to).
+    ///
+    /// default: `31 536 000 000` (365 days)
+    ///
+    /// In milliseconds
+    #[serde(
+        with = "std_duration_millis",
+        default = "default_max_campaign_duration"
+    )]
+    pub max_campaign_duration: Duration,
+}
+
+fn default_campaigns_bulk_create() -> u32 {
+    50
+}
+
+fn default_max_campaigns_per_channel() -> u32 {
+    1000
+}
+
+fn default_accounting_find() -> u32 {
+    512
+}
+
+fn default_approved_states_find() -> u32 {
+    100
+}
+
+fn default_unit_pricing_override_multiplier() -> u64 {
+    10
+}
+
+fn default_max_campaign_duration() -> Duration {
+    Duration::from_secs(365 * 24 * 60 * 60)
+}
+
+/// Retry policy for the Validator Worker's `SentryApi` idempotent GET requests,
+/// e.g. fetching the latest validator messages, the last approved state and the accounting.
+///
+/// Only failed requests (e.g. timeout or connection errors) and
+/// Server errors (status code `5xx`) are retried.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The total number of attempts, including the first request.
+    ///
+    /// Setting it to `1` disables retrying.
+    pub attempts: NonZeroU8,
+    /// The time to wait before the first retry,
+    /// it is doubled for every following retry.
+    ///
+    /// In milliseconds
+    #[serde(with = "std_duration_millis")]
+    pub backoff: Duration,
+    /// The maximum time to wait before a retry, no matter how many retries were made.
+    ///
+    /// default: `10 000` (10 seconds)
+    ///
+    /// In milliseconds
+    #[serde(with = "std_duration_millis", default = "default_max_backoff")]
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: NonZeroU8::new(3).expect("3 is non-zero"),
+            backoff: Duration::from_millis(500),
+            max_backoff: default_max_backoff(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The backoff before making the given retry, starting from `1`,
+    /// capped at the [`RetryPolicy::max_backoff`].
+    pub fn backoff_for(&self, retry: u8) -> Duration {
+        2_u32
+            .checked_pow(u32::from(retry.saturating_sub(1)))
+            .and_then(|multiplier| self.backoff.checked_mul(multiplier))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+}
+
+fn default_max_backoff() -> Duration {
+    Duration::from_secs(10)
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -270,6 +796,18 @@ pub struct Timeouts {
     /// In milliseconds
     #[serde(with = "std_duration_millis")]
     pub channel_tick: Duration,
+    /// The timeout for checking the health of a foreign validator's Sentry
+    /// before propagating messages to it.
+    ///
+    /// default: `1 000` (1 second)
+    ///
+    /// In milliseconds
+    #[serde(with = "std_duration_millis", default = "default_health_check_timeout")]
+    pub health_check: Duration,
+}
+
+fn default_health_check_timeout() -> Duration {
+    Duration::from_secs(1)
 }
 
 /// Module for [`Config`] (de)serialization of [`std::time::Duration`] from
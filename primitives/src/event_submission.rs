@@ -1,10 +1,20 @@
 use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DurationMilliSeconds};
 use std::time::Duration;
 
+#[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct EventSubmission {
     #[serde(default)]
     pub allow: Vec<Rule>,
+    /// The window (in milliseconds) in which duplicate events of the same
+    /// publisher, AdUnit, AdSlot and event type are dropped before spending.
+    ///
+    /// Overrides the [`TokenInfo.event_dedup_window`](crate::config::TokenInfo::event_dedup_window).
+    #[serde_as(as = "Option<DurationMilliSeconds<u64>>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedup_window: Option<Duration>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
@@ -25,6 +35,17 @@ pub struct RateLimit {
     /// in milliseconds
     #[serde(rename = "timeframe", with = "serde_millis")]
     pub time_frame: Duration,
+    /// The number of event submissions allowed within the `timeframe`.
+    ///
+    /// default: `1`
+    #[serde(default = "RateLimit::default_limit")]
+    pub limit: u32,
+}
+
+impl RateLimit {
+    fn default_limit() -> u32 {
+        1
+    }
 }
 
 #[cfg(feature = "postgres")]
@@ -58,3 +79,28 @@ mod postgres {
         to_sql_checked!();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn rate_limit_defaults_to_a_single_submission() {
+        let rate_limit = serde_json::from_value::<RateLimit>(json!({
+            "type": "ip",
+            "timeframe": 1200000
+        }))
+        .expect("Should deserialize a rate limit without a limit");
+
+        assert_eq!(
+            RateLimit {
+                limit_type: "ip".to_string(),
+                time_frame: Duration::from_millis(1_200_000),
+                limit: 1,
+            },
+            rate_limit
+        );
+    }
+}
@@ -18,7 +18,7 @@ pub use self::{
     chain::{Chain, ChainId, ChainOf},
     channel::{Channel, ChannelId},
     config::Config,
-    deposit::Deposit,
+    deposit::{Deposit, DepositBreakdown, DetailedDeposit},
     event_submission::EventSubmission,
     ipfs::IPFS,
     unified_num::UnifiedNum,
@@ -56,6 +56,9 @@ pub mod test_util;
 pub mod unified_num;
 pub mod validator;
 
+#[cfg(test)]
+mod wire_format_test;
+
 /// This module is available with the `postgres` feature.
 ///
 /// Other places where you'd find `mod postgres` implementations is for many of the structs in the crate
@@ -137,7 +140,7 @@ pub mod postgres {
 }
 
 mod deposit {
-    use crate::{BigNum, UnifiedNum};
+    use crate::{unified_num::FromPrecisionError, BigNum, UnifiedNum};
     use serde::{Deserialize, Serialize};
 
     #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -161,6 +164,40 @@ mod deposit {
 
             total.map(|total| Deposit { total })
         }
+
+        /// Same as [`Deposit::from_precision`] but returns an error
+        /// if the total can't be represented in [`UnifiedNum::PRECISION`] without truncation.
+        pub fn from_precision_exact(
+            deposit: Deposit<BigNum>,
+            precision: u8,
+        ) -> Result<Deposit<UnifiedNum>, FromPrecisionError> {
+            let total = UnifiedNum::from_precision_exact(deposit.total, precision)?;
+
+            Ok(Deposit { total })
+        }
+
+        /// Same as [`Deposit::from_precision`] but also returns the dust of the total,
+        /// see [`UnifiedNum::from_precision_with_dust`].
+        pub fn from_precision_with_dust(
+            deposit: Deposit<BigNum>,
+            precision: u8,
+        ) -> Option<(Deposit<UnifiedNum>, BigNum)> {
+            let (total, dust) = UnifiedNum::from_precision_with_dust(deposit.total, precision)?;
+
+            Some((Deposit { total }, dust))
+        }
+
+        /// Compares the deposit with a deposit in the given token `precision`.
+        ///
+        /// The deposits are compared in the lower of the two precisions,
+        /// i.e. a difference smaller than the unit of the lower precision is ignored.
+        pub fn approx_eq(&self, other: &Deposit<BigNum>, precision: u8) -> bool {
+            if precision < UnifiedNum::PRECISION {
+                self.total.to_precision(precision) == other.total
+            } else {
+                UnifiedNum::from_precision(other.total.clone(), precision) == Some(self.total)
+            }
+        }
     }
 
     impl<N: Default> Default for Deposit<N> {
@@ -170,6 +207,87 @@ mod deposit {
             }
         }
     }
+
+    /// Where the funds of a depositor currently are.
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DepositBreakdown<N> {
+        /// Already deposited in the OUTPACE contract and usable by the depositor.
+        pub outpace: N,
+        /// Sent to the counterfactual address of the depositor, but not yet swept
+        /// to the OUTPACE contract, i.e. not yet part of the [`Deposit.total`](Deposit::total).
+        pub pending: N,
+    }
+
+    impl DepositBreakdown<UnifiedNum> {
+        pub fn from_precision(
+            breakdown: DepositBreakdown<BigNum>,
+            precision: u8,
+        ) -> Option<DepositBreakdown<UnifiedNum>> {
+            Some(DepositBreakdown {
+                outpace: UnifiedNum::from_precision(breakdown.outpace, precision)?,
+                pending: UnifiedNum::from_precision(breakdown.pending, precision)?,
+            })
+        }
+    }
+
+    /// A [`Deposit`] with the [`DepositBreakdown`] of its funds,
+    /// if the adapter supports it.
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DetailedDeposit<N> {
+        pub total: N,
+        pub breakdown: Option<DepositBreakdown<N>>,
+    }
+
+    impl<N> From<Deposit<N>> for DetailedDeposit<N> {
+        /// A [`DetailedDeposit`] without a breakdown.
+        fn from(deposit: Deposit<N>) -> Self {
+            Self {
+                total: deposit.total,
+                breakdown: None,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn approx_eq_aligns_the_precisions() {
+            // 100 DAI
+            let dai = Deposit {
+                total: BigNum::with_precision(100, 18),
+            };
+            let unified = Deposit {
+                total: UnifiedNum::from_u64(100 * 10_u64.pow(UnifiedNum::PRECISION.into())),
+            };
+            assert!(unified.approx_eq(&dai, 18));
+
+            let one_unit_more = Deposit {
+                total: unified.total + UnifiedNum::from_u64(1),
+            };
+            assert!(!one_unit_more.approx_eq(&dai, 18));
+
+            // 1 wei is below the UnifiedNum precision
+            let dai_and_one_wei = Deposit {
+                total: &dai.total + &BigNum::from(1),
+            };
+            assert!(unified.approx_eq(&dai_and_one_wei, 18));
+
+            // 100 USDC
+            let usdc = Deposit {
+                total: BigNum::with_precision(100, 6),
+            };
+            assert!(unified.approx_eq(&usdc, 6));
+
+            let usdc_and_one_unit = Deposit {
+                total: &usdc.total + &BigNum::from(1),
+            };
+            assert!(!unified.approx_eq(&usdc_and_one_unit, 6));
+        }
+    }
 }
 
 pub mod util {
@@ -178,7 +296,11 @@ pub mod util {
 
     pub mod api;
 
+    pub mod canonical_json;
+
     pub mod logging;
+
+    pub mod referrer;
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -11,15 +11,20 @@ use std::{
 };
 use thiserror::Error;
 
-pub use event::{Event, EventType, CLICK, IMPRESSION};
+pub use event::{
+    ClickId, CustomEventType, Event, EventType, EventTypeError, MissingClickId, CLICK, CONVERSION,
+    IMPRESSION,
+};
 
 use self::message::MessageResponse;
 use crate::{
     analytics::{OperatingSystem, Timeframe},
-    balances::BalancesState,
-    spender::Spender,
+    balances::{BalancesState, UncheckedState},
+    spender::{SpendableHistory, Spender},
+    util::canonical_json::to_canonical_json,
     validator::{ApproveState, Heartbeat, NewState},
-    Address, Balances, CampaignId, ChainId, UnifiedMap, UnifiedNum, IPFS,
+    Address, Balances, CampaignId, ChainId, Channel, DepositBreakdown, UnifiedMap, UnifiedNum,
+    ValidatorId, IPFS,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -36,10 +41,123 @@ use crate::{
 pub struct AccountingResponse<S: BalancesState> {
     #[serde(flatten, bound = "S: BalancesState")]
     pub balances: Balances<S>,
+    /// Whether or not the accounting of the Channel is frozen,
+    /// see [`AccountingFreezeRequest`].
+    #[serde(default)]
+    pub frozen: bool,
+}
+
+/// GET `/v5/channel/0xXXX.../accounting/page` query
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AccountingPageQuery {
+    // default is `u64::default()` = `0`
+    #[serde(default)]
+    pub page: u64,
+}
+
+/// GET `/v5/channel/0xXXX.../accounting/page` response
+///
+/// A single page of the [`Channel`](crate::Channel) `Accounting`s,
+/// ordered by side (earners first) and then by address.
+///
+/// Since a page holds only part of the accounting, the earners sum and the
+/// spenders sum are not checked, use [`AccountingResponse`] for the whole accounting.
+///
+/// # Examples
+///
+/// ```
+#[doc = include_str!("../examples/accounting_page_response.rs")]
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountingPageResponse {
+    #[serde(flatten)]
+    pub balances: Balances<UncheckedState>,
+    /// Whether or not the accounting of the Channel is frozen,
+    /// see [`AccountingFreezeRequest`].
+    #[serde(default)]
+    pub frozen: bool,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+/// GET `/v5/channel/0xXXX.../snapshot` response
+///
+/// The [`ChannelSnapshot`] signed by the validator of the Sentry.
+///
+/// The `signature` is of the [`ChannelSnapshot::message()`] and it can be checked
+/// the same way as a state root signature, i.e. with the `verify` of an adapter.
+///
+/// # Examples
+///
+/// ```
+#[doc = include_str!("../examples/channel_snapshot_response.rs")]
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelSnapshotResponse {
+    pub snapshot: ChannelSnapshot,
+    /// The validator which signed the snapshot.
+    pub signer: ValidatorId,
+    pub signature: String,
+}
+
+/// A point-in-time snapshot of the whole state of a [`Channel`], e.g. for off-chain audits.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelSnapshot {
+    pub channel: Channel,
+    /// The time at which the snapshot was taken.
+    pub created: DateTime<Utc>,
+    /// All the earners & spenders of the [`Channel`] accounting.
+    ///
+    /// The balances are not checked, since the snapshot is used for reconciliation.
+    pub balances: Balances<UncheckedState>,
+    /// The total deposit of each spender in the [`Channel`].
+    pub spendables: HashMap<Address, UnifiedNum>,
+    /// The remaining budget of each [`Campaign`](crate::Campaign) in the [`Channel`].
+    pub campaigns_remaining: HashMap<CampaignId, UnifiedNum>,
+    /// The last approved [`NewState`] & [`ApproveState`], `None` if there are none yet.
+    pub last_approved: Option<LastApproved<UncheckedState>>,
+    /// The state root of the [`ChannelSnapshot::balances`].
+    ///
+    /// `None` when the accounting is empty or the earners and spenders sums differ.
+    pub state_root: Option<String>,
+}
+
+impl ChannelSnapshot {
+    /// The [canonical JSON](crate::util::canonical_json) of the snapshot.
+    ///
+    /// When verifying a signed snapshot, the canonical JSON should be produced from the
+    /// `snapshot` object of the response, as it's received, e.g. as a [`serde_json::Value`].
+    pub fn canonical_json(&self) -> Result<String, serde_json::Error> {
+        to_canonical_json(self)
+    }
+
+    /// The message which is signed by the validator for the snapshot.
+    ///
+    /// It's a hex encoded (without `0x` prefix) `keccak256` hash of the
+    /// [`ChannelSnapshot::canonical_json()`], in the same format as a state root.
+    pub fn message(&self) -> Result<String, serde_json::Error> {
+        Ok(canonical_json_message(&self.canonical_json()?))
+    }
+}
+
+/// The hex encoded (without `0x` prefix) `keccak256` hash of the `canonical_json`,
+/// see [`ChannelSnapshot::message()`].
+pub fn canonical_json_message(canonical_json: &str) -> String {
+    use tiny_keccak::{Hasher, Keccak};
+
+    let mut message = [0_u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(canonical_json.as_bytes());
+    hasher.finalize(&mut message);
+
+    hex::encode(message)
 }
 
 /// The last approved [`NewState`] and [`ApproveState`] accordingly to the validator.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct LastApproved<S: BalancesState> {
     /// [`NewState`] can be `None` if the [`Channel`](crate::Channel) is brand new.
@@ -141,41 +259,140 @@ pub mod message {
 }
 
 mod event {
-    use once_cell::sync::Lazy;
-    use parse_display::{Display, FromStr};
     use serde::{Deserialize, Serialize};
     use std::{
         fmt,
         hash::{Hash, Hasher},
+        str::FromStr,
     };
+    use thiserror::Error;
+    use uuid::Uuid;
 
     use crate::{Address, IPFS};
 
     pub static IMPRESSION: EventType = EventType::Impression;
     pub static CLICK: EventType = EventType::Click;
+    pub static CONVERSION: EventType = EventType::Conversion;
+
+    #[derive(Debug, Error, PartialEq, Eq)]
+    pub enum EventTypeError {
+        #[error("Event type should not be empty")]
+        Empty,
+        #[error("Event type should be at most {max} characters long", max = CustomEventType::MAX_LEN)]
+        TooLong,
+        #[error("Event type should be in SCREAMING_SNAKE_CASE, e.g. `SIGN_UP`")]
+        InvalidCharacters,
+    }
+
+    /// A custom event type, e.g. `VIEW` or `SIGN_UP`, introduced through the
+    /// [`Config.event_types`](crate::Config::event_types).
+    ///
+    /// The name is stored inline, so that the [`EventType`] stays [`Copy`].
+    /// It is validated to be in `SCREAMING_SNAKE_CASE` and at most [`CustomEventType::MAX_LEN`] long.
+    #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct CustomEventType {
+        len: u8,
+        name: [u8; CustomEventType::MAX_LEN],
+    }
+
+    impl CustomEventType {
+        pub const MAX_LEN: usize = 32;
+
+        /// Creates a new custom event type.
+        ///
+        /// The built-in `IMPRESSION`, `CLICK` & `CONVERSION` event types should be parsed
+        /// as an [`EventType`] instead.
+        pub fn new(name: &str) -> Result<Self, EventTypeError> {
+            if name.is_empty() {
+                return Err(EventTypeError::Empty);
+            }
+
+            if name.len() > Self::MAX_LEN {
+                return Err(EventTypeError::TooLong);
+            }
 
-    /// We use these statics to create the `as_str()` method for a value with a `'static` lifetime
-    /// the `parse_display::Display` derive macro does not impl such methods
-    static IMPRESSION_STRING: Lazy<String> = Lazy::new(|| EventType::Impression.to_string());
-    static CLICK_STRING: Lazy<String> = Lazy::new(|| EventType::Click.to_string());
+            let valid_chars = name
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+            if !valid_chars || !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+                return Err(EventTypeError::InvalidCharacters);
+            }
+
+            let mut bytes = [0_u8; Self::MAX_LEN];
+            bytes[..name.len()].copy_from_slice(name.as_bytes());
+
+            Ok(Self {
+                len: name.len() as u8,
+                name: bytes,
+            })
+        }
+
+        pub fn as_str(&self) -> &str {
+            std::str::from_utf8(&self.name[..self.len.into()])
+                .expect("Custom event type is validated to be ASCII")
+        }
+    }
+
+    impl fmt::Debug for CustomEventType {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_tuple("CustomEventType")
+                .field(&self.as_str())
+                .finish()
+        }
+    }
 
-    #[derive(Debug, Display, FromStr, Serialize, Deserialize, Ord, Eq, PartialOrd, Clone, Copy)]
-    #[display(style = "SNAKE_CASE")]
-    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    /// The type of an [`Event`].
+    ///
+    /// Apart from the built-in `IMPRESSION`, `CLICK` & `CONVERSION` types, any
+    /// [`CustomEventType`] can be parsed, however only the event types
+    /// supported by the [`Config.event_types`](crate::Config::event_types) are accepted by Sentry.
+    #[derive(Debug, Serialize, Deserialize, Ord, Eq, PartialOrd, Clone, Copy)]
+    #[serde(try_from = "String", into = "String")]
     pub enum EventType {
         Impression,
         Click,
+        Conversion,
+        Custom(CustomEventType),
     }
 
     impl EventType {
         pub fn as_str(&self) -> &str {
             match self {
-                EventType::Impression => IMPRESSION_STRING.as_str(),
-                EventType::Click => CLICK_STRING.as_str(),
+                EventType::Impression => "IMPRESSION",
+                EventType::Click => "CLICK",
+                EventType::Conversion => "CONVERSION",
+                EventType::Custom(custom) => custom.as_str(),
             }
         }
     }
 
+    impl FromStr for EventType {
+        type Err = EventTypeError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "IMPRESSION" => Ok(EventType::Impression),
+                "CLICK" => Ok(EventType::Click),
+                "CONVERSION" => Ok(EventType::Conversion),
+                custom => CustomEventType::new(custom).map(EventType::Custom),
+            }
+        }
+    }
+
+    impl TryFrom<String> for EventType {
+        type Error = EventTypeError;
+
+        fn try_from(value: String) -> Result<Self, Self::Error> {
+            value.parse()
+        }
+    }
+
+    impl fmt::Display for EventType {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.as_str())
+        }
+    }
+
     /// Due to the fact that we want to impl `Borrow<str>`
     /// we must provide custom hash impl using the capitalized
     /// version of the [`EventType`] as a string.
@@ -203,23 +420,115 @@ mod event {
         }
     }
 
+    /// A client-generated identifier of an [`Event::Click`], which allows
+    /// attributing a later [`Event::Conversion`] to the click.
+    ///
+    /// (De)serialized as a hex string of 16 bytes.
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    #[serde(try_from = "String", into = "String")]
+    pub struct ClickId([u8; 16]);
+
+    impl ClickId {
+        /// Generates randomly a `ClickId` using `Uuid::new_v4()`
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn as_bytes(&self) -> &[u8; 16] {
+            &self.0
+        }
+    }
+
+    impl Default for ClickId {
+        fn default() -> Self {
+            Self(*Uuid::new_v4().as_bytes())
+        }
+    }
+
+    impl fmt::Debug for ClickId {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "ClickId({})", self)
+        }
+    }
+
+    impl fmt::Display for ClickId {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&hex::encode(self.0))
+        }
+    }
+
+    impl FromStr for ClickId {
+        type Err = hex::FromHexError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut bytes = [0_u8; 16];
+            hex::decode_to_slice(s, &mut bytes)?;
+
+            Ok(Self(bytes))
+        }
+    }
+
+    impl TryFrom<String> for ClickId {
+        type Error = hex::FromHexError;
+
+        fn try_from(value: String) -> Result<Self, Self::Error> {
+            value.parse()
+        }
+    }
+
+    impl From<ClickId> for String {
+        fn from(click_id: ClickId) -> Self {
+            click_id.to_string()
+        }
+    }
+
+    #[derive(Debug, Error, PartialEq, Eq)]
+    #[error("A CONVERSION event should have the clickId of the attributed CLICK event")]
+    pub struct MissingClickId;
+
     /// All the [`Event`]s available in the validator stack.
+    ///
+    /// All events share the same fields and are (de)serialized with a `type` tag,
+    /// e.g. `{"type": "IMPRESSION", "publisher": "0x...", "adUnit": "Qm...", "adSlot": "Qm...", "referrer": null}`.
+    ///
+    /// Clicks and conversions additionally have a `clickId`, see [`ClickId`].
     #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
-    #[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+    #[serde(try_from = "EventWire", into = "EventWire")]
     pub enum Event {
-        #[serde(rename_all = "camelCase")]
         Impression {
             publisher: Address,
             ad_unit: IPFS,
             ad_slot: IPFS,
             referrer: Option<String>,
         },
-        #[serde(rename_all = "camelCase")]
         Click {
             publisher: Address,
             ad_unit: IPFS,
             ad_slot: IPFS,
             referrer: Option<String>,
+            /// Allows attributing a later [`Event::Conversion`] to this click.
+            click_id: Option<ClickId>,
+        },
+        /// A conversion reported after an [`Event::Click`] of the same
+        /// publisher, AdUnit and AdSlot and attributed to it by its [`ClickId`].
+        ///
+        /// It is paid only within the
+        /// [`SentryConfig.conversion_attribution_window`](crate::config::SentryConfig::conversion_attribution_window)
+        /// after the click and at most once per click.
+        Conversion {
+            publisher: Address,
+            ad_unit: IPFS,
+            ad_slot: IPFS,
+            referrer: Option<String>,
+            click_id: ClickId,
+        },
+        /// An event of a [`CustomEventType`] from the [`Config.event_types`](crate::Config::event_types).
+        Custom {
+            event_type: CustomEventType,
+            publisher: Address,
+            ad_unit: IPFS,
+            ad_slot: IPFS,
+            referrer: Option<String>,
         },
     }
 
@@ -239,6 +548,15 @@ mod event {
         pub fn event_type(&self) -> EventType {
             self.into()
         }
+
+        pub fn publisher(&self) -> Address {
+            match self {
+                Event::Impression { publisher, .. }
+                | Event::Click { publisher, .. }
+                | Event::Conversion { publisher, .. }
+                | Event::Custom { publisher, .. } => *publisher,
+            }
+        }
     }
 
     impl From<&Event> for EventType {
@@ -246,15 +564,19 @@ mod event {
             match event {
                 Event::Impression { .. } => EventType::Impression,
                 Event::Click { .. } => EventType::Click,
+                Event::Conversion { .. } => EventType::Conversion,
+                Event::Custom { event_type, .. } => EventType::Custom(*event_type),
             }
         }
     }
 
     impl AsRef<str> for Event {
-        fn as_ref(&self) -> &'static str {
+        fn as_ref(&self) -> &str {
             match self {
                 Event::Impression { .. } => EventType::Impression.as_str(),
                 Event::Click { .. } => EventType::Click.as_str(),
+                Event::Conversion { .. } => EventType::Conversion.as_str(),
+                Event::Custom { event_type, .. } => event_type.as_str(),
             }
         }
     }
@@ -265,13 +587,133 @@ mod event {
         }
     }
 
+    /// The (de)serialization format of an [`Event`].
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct EventWire {
+        #[serde(rename = "type")]
+        event_type: EventType,
+        publisher: Address,
+        ad_unit: IPFS,
+        ad_slot: IPFS,
+        referrer: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        click_id: Option<ClickId>,
+    }
+
+    impl TryFrom<EventWire> for Event {
+        type Error = MissingClickId;
+
+        fn try_from(wire: EventWire) -> Result<Self, Self::Error> {
+            let EventWire {
+                event_type,
+                publisher,
+                ad_unit,
+                ad_slot,
+                referrer,
+                click_id,
+            } = wire;
+
+            let event = match event_type {
+                EventType::Impression => Event::Impression {
+                    publisher,
+                    ad_unit,
+                    ad_slot,
+                    referrer,
+                },
+                EventType::Click => Event::Click {
+                    publisher,
+                    ad_unit,
+                    ad_slot,
+                    referrer,
+                    click_id,
+                },
+                EventType::Conversion => Event::Conversion {
+                    publisher,
+                    ad_unit,
+                    ad_slot,
+                    referrer,
+                    click_id: click_id.ok_or(MissingClickId)?,
+                },
+                EventType::Custom(event_type) => Event::Custom {
+                    event_type,
+                    publisher,
+                    ad_unit,
+                    ad_slot,
+                    referrer,
+                },
+            };
+
+            Ok(event)
+        }
+    }
+
+    impl From<Event> for EventWire {
+        fn from(event: Event) -> Self {
+            let event_type = event.event_type();
+
+            match event {
+                Event::Impression {
+                    publisher,
+                    ad_unit,
+                    ad_slot,
+                    referrer,
+                }
+                | Event::Custom {
+                    publisher,
+                    ad_unit,
+                    ad_slot,
+                    referrer,
+                    ..
+                } => EventWire {
+                    event_type,
+                    publisher,
+                    ad_unit,
+                    ad_slot,
+                    referrer,
+                    click_id: None,
+                },
+                Event::Click {
+                    publisher,
+                    ad_unit,
+                    ad_slot,
+                    referrer,
+                    click_id,
+                } => EventWire {
+                    event_type,
+                    publisher,
+                    ad_unit,
+                    ad_slot,
+                    referrer,
+                    click_id,
+                },
+                Event::Conversion {
+                    publisher,
+                    ad_unit,
+                    ad_slot,
+                    referrer,
+                    click_id,
+                } => EventWire {
+                    event_type,
+                    publisher,
+                    ad_unit,
+                    ad_slot,
+                    referrer,
+                    click_id: Some(click_id),
+                },
+            }
+        }
+    }
+
     #[cfg(test)]
     mod test {
         use std::borrow::Borrow;
 
-        use crate::sentry::event::{CLICK_STRING, IMPRESSION_STRING};
+        use serde_json::json;
+
+        use crate::test_util::{DUMMY_IPFS, PUBLISHER};
 
-        use super::EventType;
+        use super::{ClickId, CustomEventType, Event, EventType, EventTypeError, MissingClickId};
 
         #[test]
         fn test_event_type_parsing_and_de_serialization() {
@@ -286,8 +728,8 @@ mod event {
                 serde_json::from_value::<EventType>(serde_json::Value::String("CLICK".into()))
                     .expect("Should deserialize");
 
-            assert_eq!(IMPRESSION_STRING.as_str(), "IMPRESSION");
-            assert_eq!(CLICK_STRING.as_str(), "CLICK");
+            assert_eq!(EventType::Impression.as_str(), "IMPRESSION");
+            assert_eq!(EventType::Click.as_str(), "CLICK");
             assert_eq!(EventType::Impression, impression_parse);
             assert_eq!(EventType::Impression, impression_json);
             assert_eq!(EventType::Click, click_parse);
@@ -295,6 +737,117 @@ mod event {
 
             assert_eq!(Borrow::<str>::borrow(&EventType::Impression), "IMPRESSION");
         }
+
+        #[test]
+        fn test_custom_event_type_parsing_and_de_serialization() {
+            let sign_up = "SIGN_UP"
+                .parse::<EventType>()
+                .expect("Should parse SIGN_UP");
+            assert_eq!(
+                EventType::Custom(CustomEventType::new("SIGN_UP").unwrap()),
+                sign_up
+            );
+            assert_eq!("SIGN_UP", sign_up.to_string());
+            assert_eq!(
+                json!("SIGN_UP"),
+                serde_json::to_value(sign_up).expect("Should serialize")
+            );
+
+            assert_eq!(Err(EventTypeError::Empty), "".parse::<EventType>());
+            assert_eq!(
+                Err(EventTypeError::InvalidCharacters),
+                "sign_up".parse::<EventType>()
+            );
+            assert_eq!(
+                Err(EventTypeError::InvalidCharacters),
+                "_SIGN_UP".parse::<EventType>()
+            );
+            assert_eq!(
+                Err(EventTypeError::TooLong),
+                "A".repeat(CustomEventType::MAX_LEN + 1)
+                    .parse::<EventType>()
+            );
+
+            let event_json = json!({
+                "type": "SIGN_UP",
+                "publisher": *PUBLISHER,
+                "adUnit": DUMMY_IPFS[0],
+                "adSlot": DUMMY_IPFS[1],
+                "referrer": null,
+            });
+            let event = serde_json::from_value::<Event>(event_json.clone())
+                .expect("Should deserialize a custom Event");
+
+            assert_eq!(sign_up, event.event_type());
+            assert_eq!("SIGN_UP", event.as_str());
+            assert_eq!(
+                event_json,
+                serde_json::to_value(&event).expect("Should serialize")
+            );
+        }
+
+        #[test]
+        fn test_conversion_event_de_serialization() {
+            let click_id = ClickId::new();
+            assert_eq!(Ok(click_id), click_id.to_string().parse::<ClickId>());
+            assert_eq!(32, click_id.to_string().len());
+            assert!("0xnot-a-click-id".parse::<ClickId>().is_err());
+
+            assert_eq!(
+                EventType::Conversion,
+                "CONVERSION"
+                    .parse::<EventType>()
+                    .expect("Should parse CONVERSION")
+            );
+
+            let conversion_json = json!({
+                "type": "CONVERSION",
+                "publisher": *PUBLISHER,
+                "adUnit": DUMMY_IPFS[0],
+                "adSlot": DUMMY_IPFS[1],
+                "referrer": null,
+                "clickId": click_id,
+            });
+            let conversion = serde_json::from_value::<Event>(conversion_json.clone())
+                .expect("Should deserialize a Conversion");
+
+            assert_eq!(
+                Event::Conversion {
+                    publisher: *PUBLISHER,
+                    ad_unit: DUMMY_IPFS[0],
+                    ad_slot: DUMMY_IPFS[1],
+                    referrer: None,
+                    click_id,
+                },
+                conversion
+            );
+            assert_eq!(
+                conversion_json,
+                serde_json::to_value(&conversion).expect("Should serialize")
+            );
+
+            // a Conversion without the clickId of the attributed Click
+            let mut without_click_id = conversion_json;
+            without_click_id.as_object_mut().unwrap().remove("clickId");
+            let err = serde_json::from_value::<Event>(without_click_id)
+                .expect_err("Should require a clickId");
+            assert_eq!(MissingClickId.to_string(), err.to_string());
+
+            // a Click without a clickId is serialized without the field
+            let click_json = json!({
+                "type": "CLICK",
+                "publisher": *PUBLISHER,
+                "adUnit": DUMMY_IPFS[0],
+                "adSlot": DUMMY_IPFS[1],
+                "referrer": null,
+            });
+            let click = serde_json::from_value::<Event>(click_json.clone())
+                .expect("Should deserialize a Click");
+            assert_eq!(
+                click_json,
+                serde_json::to_value(&click).expect("Should serialize")
+            );
+        }
     }
 }
 
@@ -587,7 +1140,7 @@ impl<'de> Deserialize<'de> for DateHour<Utc> {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct Pagination {
     /// The total amount of pages available for this request
@@ -632,6 +1185,74 @@ pub struct LastApprovedQuery {
     pub with_heartbeat: Option<bool>,
 }
 
+/// An [`ApproveState`] and the [`NewState`] it approves (with the same `stateRoot`).
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovedState<S: BalancesState> {
+    #[serde(bound = "S: BalancesState")]
+    pub new_state: MessageResponse<NewState<S>>,
+    pub approve_state: MessageResponse<ApproveState>,
+}
+
+/// GET `/v5/channel/0xXXX.../last-approved/history` response
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LastApprovedHistoryResponse<S: BalancesState> {
+    /// The last approved states of the [`Channel`](crate::Channel)
+    /// ordered by the [`ApproveState`]'s `received` timestamp, newest first.
+    #[serde(bound = "S: BalancesState")]
+    pub history: Vec<ApprovedState<S>>,
+}
+
+/// GET `/v5/channel/0xXXX.../last-approved/history` query parameters
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LastApprovedHistoryQuery {
+    /// The number of approved states to return.
+    ///
+    /// It's capped (and defaults) to the
+    /// [`Config.limits.approved_states_find`](crate::config::Limits::approved_states_find).
+    #[serde(default)]
+    pub limit: Option<u64>,
+}
+
+/// The `stateRoot` of a validator message and how long ago it was received.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StateAge {
+    pub state_root: String,
+    pub received: DateTime<Utc>,
+    /// The milliseconds elapsed since the message was `received`.
+    pub age_ms: u64,
+}
+
+impl StateAge {
+    pub fn new(state_root: String, received: DateTime<Utc>, now: DateTime<Utc>) -> Self {
+        Self {
+            state_root,
+            received,
+            // a message received after `now` is considered just received
+            age_ms: (now - received).num_milliseconds().max(0) as u64,
+        }
+    }
+}
+
+/// GET `/v5/channel/0xXXX.../lag` response
+///
+/// How far behind the Follower's latest [`ApproveState`] is
+/// from the Leader's latest [`NewState`] of the [`Channel`](crate::Channel).
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelLagResponse {
+    /// The latest [`NewState`] of the Leader, `None` if it's not generated yet.
+    pub new_state: Option<StateAge>,
+    /// The latest [`ApproveState`] of the Follower, `None` if it's not generated yet.
+    pub approve_state: Option<StateAge>,
+    /// Whether the latest [`ApproveState`] approves the latest [`NewState`],
+    /// i.e. the Follower is up-to-date with the Leader.
+    pub corresponds: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct SuccessResponse {
     pub success: bool,
@@ -648,6 +1269,12 @@ pub struct SuccessResponse {
 #[serde(rename_all = "camelCase")]
 pub struct SpenderResponse {
     pub spender: Spender,
+    /// Where the deposit of the spender currently is, i.e. already in OUTPACE
+    /// or still pending to be swept from the counterfactual address.
+    ///
+    /// `None` when the adapter does not support it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_breakdown: Option<DepositBreakdown<UnifiedNum>>,
 }
 
 /// Spender limits for all spenders on a `Channel`.
@@ -670,18 +1297,147 @@ pub struct AllSpendersQuery {
     // default is `u64::default()` = `0`
     #[serde(default)]
     pub page: u64,
+    /// Whether to include the spenders with a zero total deposit.
+    ///
+    /// default: `false`
+    #[serde(default)]
+    pub include_empty: bool,
+    /// The number of spenders per page.
+    ///
+    /// It's capped (and defaults) to the
+    /// [`Config.limits.spendable_find`](crate::config::Limits::spendable_find).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
 }
 
-/// Payouts to be performed for the given [`Channel`](crate::Channel).
-///
-/// # Examples
-///
-/// ```
-#[doc = include_str!("../examples/channel_pay_request.rs")]
-/// ```
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
-pub struct ChannelPayRequest {
-    pub payouts: UnifiedMap,
+/// GET `/v5/channel/0xXXX.../spender/0xXXX.../history` request query
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpendableHistoryQuery {
+    // default is `u64::default()` = `0`
+    #[serde(default)]
+    pub page: u64,
+}
+
+/// The changes of the total deposit of a spender on a `Channel`, oldest first.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendableHistoryResponse {
+    pub history: Vec<SpendableHistory>,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+/// POST `/v5/channel/0xXXX.../spender/batch` request body
+///
+/// The number of spenders is capped at
+/// [`Config.limits.spendable_find`](crate::config::Limits::spendable_find).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendersBatchRequest {
+    pub spenders: Vec<Address>,
+}
+
+/// Spender limits for the requested spenders on a `Channel`.
+///
+/// Spenders without a [`Spendable`](crate::spender::Spendable) for the `Channel`
+/// are not included in the response.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendersBatchResponse {
+    pub spenders: HashMap<Address, Spender>,
+}
+
+/// Payouts to be performed for the given [`Channel`](crate::Channel).
+///
+/// # Examples
+///
+/// ```
+#[doc = include_str!("../examples/channel_pay_request.rs")]
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ChannelPayRequest {
+    /// The earners are compared after parsing the addresses, so the same earner
+    /// passed twice, e.g. checksummed and lowercase, is rejected as a duplicate.
+    #[serde(deserialize_with = "deserialize_payouts")]
+    pub payouts: UnifiedMap,
+}
+
+/// `POST /v5/channel/0xXXX.../pay/preview` response
+///
+/// The result of the same checks which are made for the payout of a [`ChannelPayRequest`],
+/// without applying it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelPayPreviewResponse {
+    /// Whether the payout would be accepted, i.e. there are no `errors`
+    /// and none of the `payouts` has an error.
+    pub valid: bool,
+    /// `total deposited + total earned - total spent` of the spender,
+    /// `None` if the spender has no deposit in the Channel.
+    pub available_for_payout: Option<UnifiedNum>,
+    /// The sum of all payouts, `None` if it overflows.
+    pub total_to_pay: Option<UnifiedNum>,
+    /// The validation result of each payout.
+    pub payouts: Vec<PayoutPreview>,
+    /// The errors which are not specific to a single payout,
+    /// e.g. the Campaigns of the Channel are not closed yet.
+    pub errors: Vec<String>,
+}
+
+/// The validation result of a single payout, see [`ChannelPayPreviewResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutPreview {
+    pub earner: Address,
+    pub amount: UnifiedNum,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Freezes or unfreezes the accounting of the given [`Channel`](crate::Channel).
+///
+/// While frozen, no new spending (events or payouts) is accepted for the Channel
+/// and the Leader does not produce any new [`NewState`]s.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct AccountingFreezeRequest {
+    pub frozen: bool,
+}
+
+fn deserialize_payouts<'de, D>(deserializer: D) -> Result<UnifiedMap, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde::de::{Error, MapAccess, Visitor};
+
+    struct PayoutsVisitor;
+
+    impl<'de> Visitor<'de> for PayoutsVisitor {
+        type Value = UnifiedMap;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a map of earner addresses and payout amounts")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut payouts = UnifiedMap::default();
+
+            while let Some((earner, amount)) = map.next_entry::<Address, UnifiedNum>()? {
+                if payouts.insert(earner, amount).is_some() {
+                    return Err(A::Error::custom(format!(
+                        "Duplicate payout to earner {}",
+                        earner
+                    )));
+                }
+            }
+
+            Ok(payouts)
+        }
+    }
+
+    deserializer.deserialize_map(PayoutsVisitor)
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -713,7 +1469,7 @@ pub struct InsertEventsRequest {
 }
 
 pub mod channel_list {
-    use crate::{ChainId, Channel, ValidatorId};
+    use crate::{Address, ChainId, Channel, ValidatorId};
     use serde::{Deserialize, Serialize};
 
     use super::Pagination;
@@ -740,18 +1496,27 @@ pub mod channel_list {
         pub page: u64,
         /// Returns only the [`Channel`]s containing a specified validator if provided.
         pub validator: Option<ValidatorId>,
+        /// Returns only the [`Channel`]s with the specified [`Channel.guardian`](Channel::guardian) if provided.
+        pub guardian: Option<Address>,
         /// Returns only the Channels from the specified [`ChainId`]s.
         #[serde(default)]
         pub chains: Vec<ChainId>,
+        /// The number of Channels per page.
+        ///
+        /// It's capped (and defaults) to the
+        /// [`Config.limits.channels_find`](crate::config::Limits::channels_find).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<u64>,
     }
 }
 
 pub mod validator_messages {
     use std::{fmt, str::FromStr};
 
-    use chrono::{DateTime, Utc};
+    use chrono::{serde::ts_milliseconds_option, DateTime, TimeZone, Utc};
     use parse_display::ParseError;
     use serde::{Deserialize, Serialize};
+    use thiserror::Error;
 
     use crate::{
         validator::{MessageType, MessageTypes},
@@ -769,13 +1534,147 @@ pub mod validator_messages {
     #[serde(rename_all = "camelCase")]
     pub struct ValidatorMessagesListResponse {
         pub messages: Vec<ValidatorMessage>,
+        /// The cursor of the next page, returned only for the oldest-first listing
+        /// (i.e. when [`ValidatorMessagesListQuery::since`] or [`ValidatorMessagesListQuery::cursor`] is used)
+        /// and when the page is full.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub next_cursor: Option<MessagesCursor>,
     }
 
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, Default)]
     #[serde(rename_all = "camelCase")]
     pub struct ValidatorMessagesListQuery {
         /// Will apply the lower limit of: `query.limit` and `Config::msgs_find_limit`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         pub limit: Option<u64>,
+        /// Returns only the messages received at or after this time,
+        /// ordered oldest-first.
+        ///
+        /// In milliseconds
+        #[serde(
+            default,
+            with = "ts_milliseconds_option",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub since: Option<DateTime<Utc>>,
+        /// Returns the messages after the given cursor, ordered oldest-first,
+        /// see [`ValidatorMessagesListResponse::next_cursor`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub cursor: Option<MessagesCursor>,
+    }
+
+    impl ValidatorMessagesListQuery {
+        /// Whether the messages are listed oldest-first, with a [`MessagesCursor`] for the next page.
+        pub fn is_oldest_first(&self) -> bool {
+            self.since.is_some() || self.cursor.is_some()
+        }
+    }
+
+    /// A validator message which is excluded from the latest `NewState` & `ApproveState`
+    /// of the Channel, e.g. a `NewState` with imbalanced balances and its `ApproveState`.
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct QuarantinedMessage {
+        /// The unique id of the message, used for releasing it from the quarantine.
+        pub id: i64,
+        pub from: ValidatorId,
+        pub received: DateTime<Utc>,
+        pub msg: MessageTypes,
+    }
+
+    /// `GET /v5/channel/0xXXX.../quarantined-messages` response
+    #[derive(Serialize, Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct QuarantinedMessagesResponse {
+        pub messages: Vec<QuarantinedMessage>,
+    }
+
+    /// `POST /v5/channel/0xXXX.../quarantine` request
+    ///
+    /// Quarantines the `NewState` with the given `stateRoot` and its `ApproveState`s,
+    /// if the `NewState` balances don't align.
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct QuarantineStateRequest {
+        pub state_root: String,
+    }
+
+    #[derive(Debug, Error, PartialEq, Eq)]
+    #[error("Invalid validator messages cursor")]
+    pub struct InvalidCursor;
+
+    /// An opaque cursor pointing to a [`ValidatorMessage`] for the cursor-based pagination
+    /// of the validator messages.
+    ///
+    /// It encodes the `received` time and the unique id of the message,
+    /// so that the pages stay stable while new messages arrive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use primitives::sentry::validator_messages::MessagesCursor;
+    ///
+    /// let cursor = MessagesCursor {
+    ///     received: Utc.ymd(2022, 12, 26).and_hms_milli(10, 0, 0, 120),
+    ///     id: 42,
+    /// };
+    ///
+    /// let encoded = cursor.to_string();
+    /// assert_eq!("0005f0b832c73cc0000000000000002a", encoded);
+    /// assert_eq!(Ok(cursor), encoded.parse());
+    /// ```
+    #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+    #[serde(try_from = "String", into = "String")]
+    pub struct MessagesCursor {
+        pub received: DateTime<Utc>,
+        pub id: i64,
+    }
+
+    impl fmt::Display for MessagesCursor {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let mut bytes = [0_u8; 16];
+            bytes[..8].copy_from_slice(&self.received.timestamp_micros().to_be_bytes());
+            bytes[8..].copy_from_slice(&self.id.to_be_bytes());
+
+            f.write_str(&hex::encode(bytes))
+        }
+    }
+
+    impl FromStr for MessagesCursor {
+        type Err = InvalidCursor;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut bytes = [0_u8; 16];
+            hex::decode_to_slice(s, &mut bytes).map_err(|_| InvalidCursor)?;
+
+            let micros = i64::from_be_bytes(bytes[..8].try_into().expect("Should be 8 bytes"));
+            let id = i64::from_be_bytes(bytes[8..].try_into().expect("Should be 8 bytes"));
+
+            let received = Utc
+                .timestamp_opt(
+                    micros.div_euclid(1_000_000),
+                    (micros.rem_euclid(1_000_000) * 1_000) as u32,
+                )
+                .single()
+                .ok_or(InvalidCursor)?;
+
+            Ok(Self { received, id })
+        }
+    }
+
+    impl TryFrom<String> for MessagesCursor {
+        type Error = InvalidCursor;
+
+        fn try_from(value: String) -> Result<Self, Self::Error> {
+            value.parse()
+        }
+    }
+
+    impl From<MessagesCursor> for String {
+        fn from(cursor: MessagesCursor) -> Self {
+            cursor.to_string()
+        }
     }
 
     /// Message type filter (used in path)
@@ -919,6 +1818,8 @@ pub mod campaign_list {
         Leader(ValidatorId),
         /// Results will include all campaigns that have either a leader or follower with the provided address
         Validator(ValidatorId),
+        /// Results will include all campaigns whose [`Channel.guardian`](crate::Channel::guardian) is the provided address
+        Guardian(Address),
     }
 
     #[cfg(test)]
@@ -980,17 +1881,392 @@ pub mod campaign_list {
     }
 }
 
+pub mod campaign_get {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{Campaign, CampaignId, UnifiedNum};
+
+    /// `GET /v5/campaign/:id` response
+    ///
+    /// # Examples
+    ///
+    /// ```
+    #[doc = include_str!("../examples/campaign_response.rs")]
+    /// ```
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CampaignResponse {
+        pub campaign: Campaign,
+        /// `None` when there is no remaining budget set for the Campaign (yet).
+        pub utilization: Option<BudgetUtilization>,
+    }
+
+    /// `POST /v5/campaign/batch` request
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CampaignsBatchRequest {
+        pub campaigns: Vec<CampaignId>,
+    }
+
+    /// `POST /v5/campaign/batch` response
+    ///
+    /// The found Campaigns in the order of the request,
+    /// the unknown [`CampaignId`]s are not included.
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CampaignsBatchResponse {
+        pub campaigns: Vec<CampaignResponse>,
+    }
+
+    /// How much of the [`Campaign.budget`](Campaign::budget) has been spent.
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct BudgetUtilization {
+        /// `spent = budget - remaining`
+        pub spent: UnifiedNum,
+        pub remaining: UnifiedNum,
+        /// The spent amount as a percentage of the budget, from `0` to `100`.
+        pub percent: f64,
+    }
+
+    impl BudgetUtilization {
+        /// The `remaining` is capped to the `budget`.
+        /// For a zero `budget` the `percent` is `0`.
+        pub fn new(budget: UnifiedNum, remaining: UnifiedNum) -> Self {
+            let remaining = remaining.min(budget);
+            let spent = budget - remaining;
+
+            let percent = if budget == UnifiedNum::ZERO {
+                0.0
+            } else {
+                spent.to_u64() as f64 / budget.to_u64() as f64 * 100.0
+            };
+
+            Self {
+                spent,
+                remaining,
+                percent,
+            }
+        }
+    }
+}
+
+pub mod campaign_burn_rate {
+    use chrono::{
+        serde::{ts_milliseconds, ts_milliseconds_option},
+        DateTime, Duration, Utc,
+    };
+    use serde::{Deserialize, Serialize};
+
+    use crate::{CampaignId, UnifiedNum};
+
+    /// `GET /v5/campaign/:id/burn-rate` query
+    #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+    pub struct BurnRateQuery {
+        /// The number of hourly analytics buckets, including the current hour,
+        /// from which the spend rate is calculated.
+        ///
+        /// Default: [`BurnRateQuery::DEFAULT_HOURS`]
+        pub hours: Option<u32>,
+    }
+
+    impl BurnRateQuery {
+        /// The last 24 hours
+        pub const DEFAULT_HOURS: u32 = 24;
+
+        pub fn hours(&self) -> u32 {
+            self.hours.unwrap_or(Self::DEFAULT_HOURS)
+        }
+    }
+
+    /// `GET /v5/campaign/:id/burn-rate` response
+    ///
+    /// # Examples
+    ///
+    /// ```
+    #[doc = include_str!("../examples/campaign_burn_rate_response.rs")]
+    /// ```
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct BurnRateResponse {
+        pub campaign: CampaignId,
+        /// The start of the period in which the `spent` amount has been spent, until now.
+        #[serde(with = "ts_milliseconds")]
+        pub since: DateTime<Utc>,
+        /// The amount spent by the Campaign `since`.
+        pub spent: UnifiedNum,
+        /// `None` when there is no remaining budget set for the Campaign (yet).
+        pub remaining: Option<UnifiedNum>,
+        /// The average amount spent per hour `since`.
+        ///
+        /// `None` when there is insufficient data, i.e. nothing has been spent `since`.
+        pub per_hour: Option<UnifiedNum>,
+        /// The projected time at which the `remaining` budget will be exhausted
+        /// if the Campaign keeps spending at the same rate.
+        ///
+        /// `None` when there is insufficient data, i.e. nothing has been spent `since`
+        /// or the `remaining` budget is unknown.
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "ts_milliseconds_option"
+        )]
+        pub exhaustion: Option<DateTime<Utc>>,
+    }
+
+    impl BurnRateResponse {
+        /// Projects the budget exhaustion of the Campaign based on
+        /// the amount `spent` between `since` and `now`.
+        pub fn project(
+            campaign: CampaignId,
+            since: DateTime<Utc>,
+            now: DateTime<Utc>,
+            spent: UnifiedNum,
+            remaining: Option<UnifiedNum>,
+        ) -> Self {
+            let elapsed = u128::try_from((now - since).num_milliseconds())
+                .ok()
+                .filter(|elapsed| *elapsed > 0 && spent > UnifiedNum::ZERO);
+
+            let per_hour = elapsed.and_then(|elapsed| {
+                let per_hour = u128::from(spent.to_u64()) * 3_600_000 / elapsed;
+
+                u64::try_from(per_hour).ok().map(UnifiedNum::from_u64)
+            });
+
+            let exhaustion = elapsed.zip(remaining).and_then(|(elapsed, remaining)| {
+                // remaining / (spent / elapsed)
+                let millis = u128::from(remaining.to_u64()) * elapsed / u128::from(spent.to_u64());
+
+                let millis = i64::try_from(millis).ok()?;
+
+                now.checked_add_signed(Duration::milliseconds(millis))
+            });
+
+            Self {
+                campaign,
+                since,
+                spent,
+                remaining,
+                per_hour,
+                exhaustion,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use chrono::TimeZone;
+
+        use super::*;
+        use crate::test_util::DUMMY_CAMPAIGN;
+
+        #[test]
+        fn projects_the_budget_exhaustion() {
+            let since = Utc.ymd(2022, 6, 20).and_hms(10, 0, 0);
+            let now = Utc.ymd(2022, 6, 20).and_hms(12, 0, 0);
+
+            // 2 hours spending 300 in total, i.e. 150 per hour
+            let response = BurnRateResponse::project(
+                DUMMY_CAMPAIGN.id,
+                since,
+                now,
+                UnifiedNum::from_u64(300),
+                Some(UnifiedNum::from_u64(750)),
+            );
+
+            assert_eq!(Some(UnifiedNum::from_u64(150)), response.per_hour);
+            // 750 / 150 = 5 hours
+            assert_eq!(
+                Some(Utc.ymd(2022, 6, 20).and_hms(17, 0, 0)),
+                response.exhaustion
+            );
+
+            // nothing remaining, it's exhausted now
+            let exhausted = BurnRateResponse::project(
+                DUMMY_CAMPAIGN.id,
+                since,
+                now,
+                UnifiedNum::from_u64(300),
+                Some(UnifiedNum::ZERO),
+            );
+            assert_eq!(Some(now), exhausted.exhaustion);
+
+            // nothing spent, insufficient data
+            let nothing_spent = BurnRateResponse::project(
+                DUMMY_CAMPAIGN.id,
+                since,
+                now,
+                UnifiedNum::ZERO,
+                Some(UnifiedNum::from_u64(750)),
+            );
+            assert_eq!(None, nothing_spent.per_hour);
+            assert_eq!(None, nothing_spent.exhaustion);
+
+            // unknown remaining
+            let unknown_remaining = BurnRateResponse::project(
+                DUMMY_CAMPAIGN.id,
+                since,
+                now,
+                UnifiedNum::from_u64(300),
+                None,
+            );
+            assert_eq!(Some(UnifiedNum::from_u64(150)), unknown_remaining.per_hour);
+            assert_eq!(None, unknown_remaining.exhaustion);
+        }
+    }
+}
+
+pub mod campaign_health {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        sentry::{campaign_get::BudgetUtilization, StateAge},
+        CampaignId, UnifiedNum,
+    };
+
+    /// `GET /v5/campaign/:id/health` response
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CampaignHealthResponse {
+        pub campaign: CampaignId,
+        pub status: CampaignHealthStatus,
+        /// `None` when there is no remaining budget set for the Campaign (yet).
+        pub utilization: Option<BudgetUtilization>,
+        /// The spending of the [`Campaign.creator`](crate::Campaign::creator)
+        /// in the Channel accounting.
+        ///
+        /// **NOTE:** It includes the spending of all the Campaigns
+        /// of the creator in the same Channel.
+        pub accounting_spent: UnifiedNum,
+        /// The spending of the creator included in the last approved `NewState`.
+        pub approved_spent: UnifiedNum,
+        /// The spending of the creator which is not approved yet,
+        /// i.e. `pending_spent = accounting_spent - approved_spent`
+        pub pending_spent: UnifiedNum,
+        /// The last `ApproveState` of the Channel, `None` if it's not generated yet.
+        pub last_approve_state: Option<StateAge>,
+    }
+
+    /// A coarse status of the Campaign.
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+    pub enum CampaignHealthStatus {
+        /// The Campaign has remaining budget and its spending is being approved.
+        Active,
+        /// The Campaign has no remaining budget.
+        OutOfBudget,
+        /// There is pending spending which hasn't been approved for longer than the
+        /// [`Config.sentry.campaign_awaiting_approval_after`](crate::config::SentryConfig::campaign_awaiting_approval_after).
+        AwaitingApproval,
+        /// There is pending spending which hasn't been approved for longer than the
+        /// [`Config.sentry.campaign_stalled_after`](crate::config::SentryConfig::campaign_stalled_after),
+        /// i.e. the validators are not approving the new states of the Channel.
+        ChannelStalled,
+    }
+
+    impl CampaignHealthStatus {
+        /// The approval statuses take precedence over [`CampaignHealthStatus::OutOfBudget`],
+        /// since the spending of an exhausted Campaign should still be approved.
+        ///
+        /// - `remaining` - `None` when there is no remaining budget set for the Campaign
+        /// - `unapproved_for` - the time since the last `ApproveState`
+        ///   (or the Campaign creation if there is none)
+        pub fn derive(
+            remaining: Option<UnifiedNum>,
+            pending_spent: UnifiedNum,
+            unapproved_for: Duration,
+            awaiting_approval_after: Duration,
+            stalled_after: Duration,
+        ) -> Self {
+            let is_pending = pending_spent > UnifiedNum::ZERO;
+
+            if is_pending && unapproved_for >= stalled_after {
+                Self::ChannelStalled
+            } else if is_pending && unapproved_for >= awaiting_approval_after {
+                Self::AwaitingApproval
+            } else if remaining.unwrap_or(UnifiedNum::ZERO) == UnifiedNum::ZERO {
+                Self::OutOfBudget
+            } else {
+                Self::Active
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn derives_the_campaign_health_status() {
+            let awaiting_approval_after = Duration::from_secs(5 * 60);
+            let stalled_after = Duration::from_secs(60 * 60);
+            let derive = |remaining: Option<u64>, pending: u64, unapproved_for_mins: u64| {
+                CampaignHealthStatus::derive(
+                    remaining.map(UnifiedNum::from_u64),
+                    UnifiedNum::from_u64(pending),
+                    Duration::from_secs(unapproved_for_mins * 60),
+                    awaiting_approval_after,
+                    stalled_after,
+                )
+            };
+
+            // Active
+            assert_eq!(CampaignHealthStatus::Active, derive(Some(100), 0, 120));
+            assert_eq!(
+                CampaignHealthStatus::Active,
+                derive(Some(100), 50, 1),
+                "Pending spending which will be approved soon"
+            );
+
+            // OutOfBudget
+            assert_eq!(CampaignHealthStatus::OutOfBudget, derive(Some(0), 0, 1));
+            assert_eq!(
+                CampaignHealthStatus::OutOfBudget,
+                derive(None, 0, 1),
+                "No remaining budget set"
+            );
+
+            // AwaitingApproval
+            assert_eq!(
+                CampaignHealthStatus::AwaitingApproval,
+                derive(Some(100), 50, 5)
+            );
+            assert_eq!(
+                CampaignHealthStatus::AwaitingApproval,
+                derive(Some(0), 50, 30),
+                "Takes precedence over OutOfBudget"
+            );
+
+            // ChannelStalled
+            assert_eq!(
+                CampaignHealthStatus::ChannelStalled,
+                derive(Some(100), 50, 60)
+            );
+            assert_eq!(
+                CampaignHealthStatus::ChannelStalled,
+                derive(Some(0), 50, 120),
+                "Takes precedence over OutOfBudget"
+            );
+        }
+    }
+}
+
 pub mod campaign_create {
     use chrono::{serde::ts_milliseconds, DateTime, Utc};
     use serde::{Deserialize, Serialize};
 
     use crate::{
-        campaign::{Active, PricingBounds, Validators},
+        campaign::{Active, Pacing, PricingBounds, UnitPricingOverrides, Validators},
+        channel::Nonce,
+        sentry::EventType,
         targeting::Rules,
-        AdUnit, Address, Campaign, CampaignId, Channel, EventSubmission, UnifiedNum,
+        AdUnit, Address, Campaign, CampaignId, Channel, EventSubmission, UnifiedNum, ValidatorId,
     };
 
     /// All fields are present except the `CampaignId` which is randomly created
+    /// and the `channel.nonce` for which a [`Nonce::random()`] is used if it's not set.
     /// This struct defines the Body of the request (in JSON)
     ///
     /// # Examples
@@ -1002,6 +2278,7 @@ pub mod campaign_create {
     #[serde(rename_all = "camelCase")]
     pub struct CreateCampaign {
         pub id: Option<CampaignId>,
+        #[serde(deserialize_with = "deserialize_channel")]
         pub channel: Channel,
         pub creator: Address,
         pub budget: UnifiedNum,
@@ -1011,9 +2288,18 @@ pub mod campaign_create {
         /// Event pricing bounds
         #[serde(default, skip_serializing_if = "PricingBounds::is_empty")]
         pub pricing_bounds: PricingBounds,
+        /// Event pricing bounds overrides of specific AdUnits
+        #[serde(default, skip_serializing_if = "UnitPricingOverrides::is_empty")]
+        pub unit_pricing_overrides: UnitPricingOverrides,
+        /// The accepted event types, all event types are accepted if not set
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub accepted_event_types: Option<Vec<EventType>>,
         /// EventSubmission object, applies to event submission (POST /channel/:id/events)
         #[serde(default, skip_serializing_if = "Option::is_none")]
         pub event_submission: Option<EventSubmission>,
+        /// The spend pacing, the whole budget can be spent in a single day if not set
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub pacing: Option<Pacing>,
         /// An array of AdUnit (optional)
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         pub ad_units: Vec<AdUnit>,
@@ -1040,11 +2326,15 @@ pub mod campaign_create {
                 validators: self.validators,
                 title: self.title,
                 pricing_bounds: self.pricing_bounds,
+                unit_pricing_overrides: self.unit_pricing_overrides,
+                accepted_event_types: self.accepted_event_types,
                 event_submission: self.event_submission,
+                pacing: self.pacing,
                 ad_units: self.ad_units,
                 targeting_rules: self.targeting_rules,
                 created: self.created,
                 active: self.active,
+                version: 0,
             }
         }
 
@@ -1060,7 +2350,10 @@ pub mod campaign_create {
                 validators: campaign.validators,
                 title: campaign.title,
                 pricing_bounds: campaign.pricing_bounds,
+                unit_pricing_overrides: campaign.unit_pricing_overrides,
+                accepted_event_types: campaign.accepted_event_types,
                 event_submission: campaign.event_submission,
+                pacing: campaign.pacing,
                 ad_units: campaign.ad_units,
                 targeting_rules: campaign.targeting_rules,
                 created: campaign.created,
@@ -1074,42 +2367,145 @@ pub mod campaign_create {
             Self::from_campaign_erased(campaign, id)
         }
     }
+
+    /// The [`Channel`] of the request with an optional `nonce`.
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct CreateChannel {
+        leader: ValidatorId,
+        follower: ValidatorId,
+        guardian: Address,
+        token: Address,
+        #[serde(default = "Nonce::random")]
+        nonce: Nonce,
+    }
+
+    fn deserialize_channel<'de, D>(deserializer: D) -> Result<Channel, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let channel = CreateChannel::deserialize(deserializer)?;
+
+        Ok(Channel {
+            leader: channel.leader,
+            follower: channel.follower,
+            guardian: channel.guardian,
+            token: channel.token,
+            nonce: channel.nonce,
+        })
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use crate::test_util::DUMMY_CAMPAIGN;
+
+        #[test]
+        fn uses_a_random_nonce_if_not_set() {
+            let mut json =
+                serde_json::to_value(CreateCampaign::from_campaign(DUMMY_CAMPAIGN.clone()))
+                    .expect("Should serialize");
+
+            let with_nonce: CreateCampaign =
+                serde_json::from_value(json.clone()).expect("Should deserialize");
+            assert_eq!(DUMMY_CAMPAIGN.channel, with_nonce.channel);
+
+            json["channel"]
+                .as_object_mut()
+                .expect("Should be an object")
+                .remove("nonce");
+
+            let first: CreateCampaign =
+                serde_json::from_value(json.clone()).expect("Should deserialize");
+            let second: CreateCampaign = serde_json::from_value(json).expect("Should deserialize");
+
+            assert_ne!(first.channel.nonce, second.channel.nonce);
+            assert_ne!(first.channel.id(), second.channel.id());
+            assert_eq!(DUMMY_CAMPAIGN.channel.leader, first.channel.leader);
+        }
+    }
 }
 
 pub mod campaign_modify {
     use serde::{Deserialize, Serialize};
 
     use crate::{
-        campaign::{PricingBounds, Validators},
+        campaign::{Pacing, PricingBounds, UnitPricingOverrides, Validators},
         targeting::Rules,
         AdUnit, Campaign, EventSubmission, UnifiedNum,
     };
 
     /// All editable fields stored in one place, used for checking when a budget is changed
     ///
+    /// Fields are in camelCase, like the [`Campaign`] fields.
+    /// The snake_case field names are still accepted for backwards compatibility.
+    ///
     /// # Examples:
     /// ```
     #[doc = include_str!("../examples/modify_campaign_request.rs")]
     /// ```
     #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    #[serde(rename_all = "camelCase")]
     pub struct ModifyCampaign {
+        /// The expected current [`Campaign.version`](Campaign::version).
+        ///
+        /// If the Campaign has been modified in the meantime
+        /// the modification will be rejected with a Conflict.
+        pub version: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         pub budget: Option<UnifiedNum>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         pub validators: Option<Validators>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         pub title: Option<String>,
+        #[serde(
+            default,
+            alias = "pricing_bounds",
+            skip_serializing_if = "Option::is_none"
+        )]
         pub pricing_bounds: Option<PricingBounds>,
+        #[serde(
+            default,
+            alias = "unit_pricing_overrides",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub unit_pricing_overrides: Option<UnitPricingOverrides>,
+        #[serde(
+            default,
+            alias = "event_submission",
+            skip_serializing_if = "Option::is_none"
+        )]
         pub event_submission: Option<EventSubmission>,
+        /// Not set leaves the [`Campaign.pacing`](Campaign::pacing) unchanged,
+        /// while `null` clears it.
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub pacing: Option<Option<Pacing>>,
+        #[serde(default, alias = "ad_units", skip_serializing_if = "Option::is_none")]
         pub ad_units: Option<Vec<AdUnit>>,
+        #[serde(
+            default,
+            alias = "targeting_rules",
+            skip_serializing_if = "Option::is_none"
+        )]
         pub targeting_rules: Option<Rules>,
     }
 
     impl ModifyCampaign {
         pub fn from_campaign(campaign: Campaign) -> Self {
             ModifyCampaign {
+                version: campaign.version,
                 budget: Some(campaign.budget),
                 validators: Some(campaign.validators),
                 title: campaign.title,
                 pricing_bounds: Some(campaign.pricing_bounds),
+                unit_pricing_overrides: Some(campaign.unit_pricing_overrides),
                 event_submission: campaign.event_submission,
+                // a Campaign without pacing leaves it unchanged instead of clearing it
+                pacing: campaign.pacing.map(Some),
                 ad_units: Some(campaign.ad_units),
                 targeting_rules: Some(campaign.targeting_rules),
             }
@@ -1133,10 +2529,19 @@ pub mod campaign_modify {
                 campaign.pricing_bounds = new_pricing_bounds;
             }
 
+            if let Some(new_unit_pricing_overrides) = self.unit_pricing_overrides {
+                campaign.unit_pricing_overrides = new_unit_pricing_overrides;
+            }
+
             if let Some(new_event_submission) = self.event_submission {
                 campaign.event_submission = Some(new_event_submission);
             }
 
+            // `Some(None)` clears the pacing
+            if let Some(new_pacing) = self.pacing {
+                campaign.pacing = new_pacing;
+            }
+
             if let Some(new_ad_units) = self.ad_units {
                 campaign.ad_units = new_ad_units;
             }
@@ -1148,6 +2553,307 @@ pub mod campaign_modify {
             campaign
         }
     }
+
+    #[cfg(test)]
+    mod test {
+        use serde_json::json;
+
+        use super::*;
+        use crate::test_util::DUMMY_CAMPAIGN;
+
+        #[test]
+        fn modifies_or_clears_the_pacing() {
+            let mut campaign = DUMMY_CAMPAIGN.clone();
+            campaign.pacing = Some(Pacing {
+                daily_cap: UnifiedNum::from_u64(10_000_000_000),
+            });
+
+            let not_set: ModifyCampaign =
+                serde_json::from_value(json!({ "version": campaign.version }))
+                    .expect("Should deserialize");
+            assert_eq!(None, not_set.pacing);
+            assert_eq!(campaign.pacing, not_set.apply(campaign.clone()).pacing);
+
+            let cleared: ModifyCampaign =
+                serde_json::from_value(json!({ "version": campaign.version, "pacing": null }))
+                    .expect("Should deserialize");
+            assert_eq!(Some(None), cleared.pacing);
+            assert_eq!(None, cleared.clone().apply(campaign.clone()).pacing);
+            assert_eq!(
+                json!({ "version": campaign.version, "pacing": null }),
+                serde_json::to_value(&cleared).expect("Should serialize")
+            );
+
+            let new_pacing = Pacing {
+                daily_cap: UnifiedNum::from_u64(5_000_000_000),
+            };
+            let modified: ModifyCampaign = serde_json::from_value(
+                json!({ "version": campaign.version, "pacing": { "dailyCap": "5000000000" } }),
+            )
+            .expect("Should deserialize");
+            assert_eq!(Some(new_pacing), modified.apply(campaign).pacing);
+        }
+    }
+}
+
+pub mod campaign_budget_history {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Serialize};
+
+    use super::Pagination;
+    use crate::{Address, CampaignId, UnifiedNum};
+
+    /// `GET /v5/campaign/:id/budget/history` query
+    #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+    pub struct BudgetHistoryQuery {
+        // default is `u64::default()` = `0`
+        #[serde(default)]
+        pub page: u64,
+    }
+
+    /// A change of the [`Campaign.budget`](crate::Campaign::budget).
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct BudgetChange {
+        pub old_budget: UnifiedNum,
+        pub new_budget: UnifiedNum,
+        /// Who has changed the budget, e.g. the [`Campaign.creator`](crate::Campaign::creator)
+        /// when modifying or closing the Campaign.
+        pub actor: Address,
+        pub created: DateTime<Utc>,
+    }
+
+    /// `GET /v5/campaign/:id/budget/history` response
+    ///
+    /// The changes of the Campaign budget, oldest first.
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct BudgetHistoryResponse {
+        pub campaign: CampaignId,
+        pub history: Vec<BudgetChange>,
+        #[serde(flatten)]
+        pub pagination: Pagination,
+    }
+}
+
+pub mod event_stats {
+    use std::collections::HashMap;
+
+    use chrono::Utc;
+    use serde::{Deserialize, Serialize};
+
+    use crate::{Address, CampaignId, UnifiedNum};
+
+    use super::{DateHour, EventType, CLICK, IMPRESSION};
+
+    /// `GET /v5/campaign/:id/events/stats` query
+    ///
+    /// # Examples
+    ///
+    /// ```
+    #[doc = include_str!("../examples/event_stats_query.rs")]
+    /// ```
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    pub struct EventStatsQuery {
+        /// The publisher for which to return the event statistics.
+        pub publisher: Address,
+        /// The first hourly bucket (inclusive).
+        ///
+        /// Default: `end` - 23 hours, i.e. the last 24 hourly buckets.
+        pub start: Option<DateHour<Utc>>,
+        /// The last hourly bucket (inclusive).
+        ///
+        /// Default: [`DateHour::now`]
+        pub end: Option<DateHour<Utc>>,
+    }
+
+    impl EventStatsQuery {
+        /// Returns the `(start, end)` [`DateHour`]s of the query with the defaults applied.
+        pub fn time_range(&self) -> (DateHour<Utc>, DateHour<Utc>) {
+            let end = self.end.unwrap_or_else(DateHour::now);
+            let start = self.start.unwrap_or(end - 23);
+
+            (start, end)
+        }
+    }
+
+    /// `GET /v5/campaign/:id/events/stats` response
+    ///
+    /// # Examples
+    ///
+    /// ```
+    #[doc = include_str!("../examples/event_stats_response.rs")]
+    /// ```
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct EventStatsResponse {
+        pub campaign: CampaignId,
+        pub publisher: Address,
+        /// All the hourly buckets of the requested time range, ordered by time.
+        /// Hours without any events are included with zero counts & payouts.
+        pub stats: Vec<HourlyEventStats>,
+    }
+
+    /// The accepted events of a publisher for a single hour.
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct HourlyEventStats {
+        pub time: DateHour<Utc>,
+        pub events: HashMap<EventType, EventStats>,
+    }
+
+    impl HourlyEventStats {
+        /// Creates the bucket with zero [`EventStats`] for both [`IMPRESSION`] and [`CLICK`] events.
+        pub fn empty(time: DateHour<Utc>) -> Self {
+            Self {
+                time,
+                events: [
+                    (IMPRESSION, EventStats::default()),
+                    (CLICK, EventStats::default()),
+                ]
+                .into_iter()
+                .collect(),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct EventStats {
+        /// The number of accepted events.
+        pub count: u32,
+        /// The total payout of the accepted events.
+        pub payout: UnifiedNum,
+    }
+}
+
+pub mod fees_summary {
+    use std::time::Duration;
+
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde::{Deserialize, Serialize};
+
+    use crate::{ChannelId, UnifiedNum, ValidatorId};
+
+    /// A periodic summary of the fees earned by a validator in a single [`Channel`](crate::Channel).
+    ///
+    /// The summaries are keyed by (`validator`, `channel`, `period`).
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ValidatorFeesSummary {
+        pub validator: ValidatorId,
+        pub channel: ChannelId,
+        /// The start of the period for which the summary was created,
+        /// see [`period_start()`].
+        pub period: DateTime<Utc>,
+        /// The total fees earned by the validator in the [`Channel`](crate::Channel)
+        /// according to the latest approved [`NewState`](crate::validator::NewState).
+        pub fees: UnifiedNum,
+        /// The fees earned since the previous summary of the [`Channel`](crate::Channel).
+        pub delta: UnifiedNum,
+        pub created: DateTime<Utc>,
+    }
+
+    /// Returns the start of the period in which `time` is,
+    /// the periods are of length `interval` starting from the Unix epoch.
+    ///
+    /// An `interval` of `0` returns `time` itself.
+    pub fn period_start(time: DateTime<Utc>, interval: Duration) -> DateTime<Utc> {
+        let interval = interval.as_millis() as i64;
+
+        if interval == 0 {
+            return time;
+        }
+
+        let timestamp = time.timestamp_millis();
+
+        Utc.timestamp_millis_opt(timestamp - timestamp.rem_euclid(interval))
+            .single()
+            .expect("The start of the period should be a valid timestamp")
+    }
+
+    /// `POST /v5/validator/:id/fees-summary` request body
+    ///
+    /// # Examples
+    ///
+    /// ```
+    #[doc = include_str!("../examples/fees_summary_request.rs")]
+    /// ```
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct FeesSummaryRequest {
+        pub channel: ChannelId,
+        pub period: DateTime<Utc>,
+        pub fees: UnifiedNum,
+        pub delta: UnifiedNum,
+    }
+
+    /// `GET /v5/validator/:id/fees-summary` query
+    ///
+    /// - Without a `period` the latest summary of each [`Channel`](crate::Channel) is returned.
+    /// - With a `period` only the summaries of this period are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    #[doc = include_str!("../examples/fees_summary_query.rs")]
+    /// ```
+    #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+    pub struct FeesSummaryQuery {
+        /// Filters the summaries only for the given [`Channel`](crate::Channel).
+        pub channel: Option<ChannelId>,
+        pub period: Option<DateTime<Utc>>,
+    }
+
+    /// `GET /v5/validator/:id/fees-summary` response
+    ///
+    /// There is at most one summary per [`Channel`](crate::Channel),
+    /// so the totals are aggregated across all the returned [`Channel`](crate::Channel)s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    #[doc = include_str!("../examples/fees_summary_response.rs")]
+    /// ```
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct FeesSummaryResponse {
+        pub summaries: Vec<ValidatorFeesSummary>,
+        /// The sum of all the summaries' `fees`.
+        pub total_fees: UnifiedNum,
+        /// The sum of all the summaries' `delta`.
+        pub total_delta: UnifiedNum,
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_period_start() {
+            let hour = Duration::from_secs(60 * 60);
+
+            let time = Utc.with_ymd_and_hms(2022, 12, 1, 10, 35, 20).unwrap()
+                + chrono::Duration::milliseconds(500);
+            assert_eq!(
+                Utc.with_ymd_and_hms(2022, 12, 1, 10, 0, 0).unwrap(),
+                period_start(time, hour)
+            );
+
+            // at the exact start of the period
+            let start = Utc.with_ymd_and_hms(2022, 12, 1, 10, 0, 0).unwrap();
+            assert_eq!(start, period_start(start, hour));
+
+            // a day
+            assert_eq!(
+                Utc.with_ymd_and_hms(2022, 12, 1, 0, 0, 0).unwrap(),
+                period_start(time, Duration::from_secs(24 * 60 * 60))
+            );
+
+            // no interval
+            assert_eq!(time, period_start(time, Duration::ZERO));
+        }
+    }
 }
 
 pub mod units_for_slot {
@@ -1217,6 +2923,54 @@ pub mod units_for_slot {
     }
 }
 
+pub mod whoami {
+    use serde::{Deserialize, Serialize};
+    use tiny_keccak::{Hasher, Keccak};
+
+    use crate::ValidatorId;
+
+    /// The maximum length of the [`WhoamiQuery.challenge`](WhoamiQuery::challenge).
+    pub const MAX_CHALLENGE_LENGTH: usize = 128;
+
+    /// Prepended to the challenge before hashing it in [`challenge_message()`].
+    const CHALLENGE_DOMAIN: &[u8] = b"AdEx Sentry whoami challenge:";
+
+    /// `GET /v5/whoami` query
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    pub struct WhoamiQuery {
+        /// A random nonce chosen by the requesting validator.
+        ///
+        /// Should be non-empty and at most [`MAX_CHALLENGE_LENGTH`] long.
+        pub challenge: String,
+    }
+
+    /// `GET /v5/whoami` response
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct WhoamiResponse {
+        /// The validator for which the Sentry speaks.
+        pub validator_id: ValidatorId,
+        /// The signature of the [`challenge_message()`] of the query challenge.
+        pub signature: String,
+    }
+
+    /// The message which is signed by the validator for the given `challenge`.
+    ///
+    /// It's a hex encoded (without `0x` prefix) `keccak256` hash of the challenge
+    /// prefixed with a domain, in the same format as a state root.
+    /// Hashing the challenge makes sure that the Sentry never signs an arbitrary
+    /// state root chosen by the requester.
+    pub fn challenge_message(challenge: &str) -> String {
+        let mut message = [0_u8; 32];
+        let mut hasher = Keccak::v256();
+        hasher.update(CHALLENGE_DOMAIN);
+        hasher.update(challenge.as_bytes());
+        hasher.finalize(&mut message);
+
+        hex::encode(message)
+    }
+}
+
 #[cfg(feature = "postgres")]
 mod postgres {
     use bytes::BytesMut;
@@ -1228,8 +2982,11 @@ mod postgres {
     };
 
     use super::{
-        message::MessageResponse, validator_messages::ValidatorMessage, Analytics, DateHour,
-        EventType, FetchedAnalytics, FetchedMetric,
+        campaign_budget_history::BudgetChange,
+        fees_summary::ValidatorFeesSummary,
+        message::MessageResponse,
+        validator_messages::{QuarantinedMessage, ValidatorMessage},
+        Analytics, DateHour, EventType, FetchedAnalytics, FetchedMetric,
     };
     use crate::{
         analytics::{AnalyticsQuery, Metric},
@@ -1247,6 +3004,41 @@ mod postgres {
         }
     }
 
+    impl From<&Row> for QuarantinedMessage {
+        fn from(row: &Row) -> Self {
+            Self {
+                id: row.get("id"),
+                from: row.get("from"),
+                received: row.get("received"),
+                msg: row.get::<_, Json<MessageTypes>>("msg").0,
+            }
+        }
+    }
+
+    impl From<&Row> for BudgetChange {
+        fn from(row: &Row) -> Self {
+            Self {
+                old_budget: row.get("old_budget"),
+                new_budget: row.get("new_budget"),
+                actor: row.get("actor"),
+                created: row.get("created"),
+            }
+        }
+    }
+
+    impl From<&Row> for ValidatorFeesSummary {
+        fn from(row: &Row) -> Self {
+            Self {
+                validator: row.get("validator_id"),
+                channel: row.get("channel_id"),
+                period: row.get("period"),
+                fees: row.get("fees"),
+                delta: row.get("delta"),
+                created: row.get("created"),
+            }
+        }
+    }
+
     impl<T> TryFrom<&Row> for MessageResponse<T>
     where
         T: Type,
@@ -1445,10 +3237,54 @@ mod postgres {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::test_util::{DUMMY_IPFS, PUBLISHER};
+    use crate::test_util::{DUMMY_IPFS, LEADER, PUBLISHER};
 
     use serde_json::{json, Value};
 
+    #[test]
+    fn test_channel_pay_request_payouts() {
+        // a valid payload
+        {
+            let json = json!({
+                "payouts": {
+                    "0x80690751969B234697e9059e04ed72195c3507fa": "10000000000",
+                    "0xE882ebF439207a70dDcCb39E13CA8506c9F45fD9": "20000000000",
+                }
+            });
+
+            let request =
+                serde_json::from_value::<ChannelPayRequest>(json).expect("Should deserialize");
+
+            assert_eq!(2, request.payouts.len());
+            assert_eq!(
+                Some(&UnifiedNum::from_u64(10_000_000_000)),
+                request.payouts.get(&LEADER)
+            );
+            assert_eq!(
+                Some(&UnifiedNum::from_u64(20_000_000_000)),
+                request.payouts.get(&PUBLISHER)
+            );
+        }
+
+        // the same earner checksummed & lowercase
+        {
+            let json = r#"{
+                "payouts": {
+                    "0x80690751969B234697e9059e04ed72195c3507fa": "10000000000",
+                    "0x80690751969b234697e9059e04ed72195c3507fa": "10000000000"
+                }
+            }"#;
+
+            let err = serde_json::from_str::<ChannelPayRequest>(json)
+                .expect_err("Should reject the duplicate earner");
+
+            assert!(
+                err.to_string().starts_with("Duplicate payout to earner"),
+                "Unexpected error: {err}"
+            );
+        }
+    }
+
     #[test]
     pub fn test_de_serialize_events() {
         let click = Event::Click {
@@ -1456,6 +3292,7 @@ mod test {
             ad_unit: DUMMY_IPFS[0],
             ad_slot: DUMMY_IPFS[1],
             referrer: Some("some_referrer".to_string()),
+            click_id: None,
         };
 
         let click_json = json!({
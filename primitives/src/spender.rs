@@ -1,5 +1,9 @@
-use crate::{Address, Channel, Deposit, UnifiedNum};
+use std::{fmt, str::FromStr};
+
+use crate::{Address, Channel, ChannelId, Deposit, UnifiedNum};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -15,6 +19,75 @@ pub struct Spendable {
     pub deposit: Deposit<UnifiedNum>,
 }
 
+/// Notification that the total deposit of the [`Spendable`] of a spender in a [`Channel`]
+/// has changed, e.g. after a deposit on-chain.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendableChanged {
+    pub channel_id: ChannelId,
+    pub spender: Address,
+}
+
+/// What has changed the total deposit of a [`Spendable`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpendableSource {
+    /// The latest deposit was fetched when creating a Campaign.
+    CampaignCreate,
+    /// The latest deposit was fetched when modifying the budget of a Campaign.
+    CampaignModify,
+    /// The latest deposit was fetched by the spender routes of the Channel.
+    SpenderRoute,
+    /// The latest deposit was fetched outside of a request, e.g. by a worker.
+    WorkerRefresh,
+}
+
+impl SpendableSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpendableSource::CampaignCreate => "campaign-create",
+            SpendableSource::CampaignModify => "campaign-modify",
+            SpendableSource::SpenderRoute => "spender-route",
+            SpendableSource::WorkerRefresh => "worker-refresh",
+        }
+    }
+}
+
+impl fmt::Display for SpendableSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Unknown Spendable source: {0}")]
+pub struct UnknownSpendableSource(pub String);
+
+impl FromStr for SpendableSource {
+    type Err = UnknownSpendableSource;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "campaign-create" => Ok(SpendableSource::CampaignCreate),
+            "campaign-modify" => Ok(SpendableSource::CampaignModify),
+            "spender-route" => Ok(SpendableSource::SpenderRoute),
+            "worker-refresh" => Ok(SpendableSource::WorkerRefresh),
+            unknown => Err(UnknownSpendableSource(unknown.to_string())),
+        }
+    }
+}
+
+/// A change of the total deposit of a [`Spendable`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendableHistory {
+    /// `None` if the [`Spendable`] did not exist before the change.
+    pub old_total: Option<UnifiedNum>,
+    pub new_total: UnifiedNum,
+    pub source: SpendableSource,
+    pub created: DateTime<Utc>,
+}
+
 impl PartialEq<Spendable> for &Spendable {
     fn eq(&self, other: &Spendable) -> bool {
         self.spender == other.spender
@@ -34,7 +107,11 @@ impl PartialEq<&Spendable> for Spendable {
 #[cfg(feature = "postgres")]
 mod postgres {
     use super::*;
-    use tokio_postgres::Row;
+    use bytes::BytesMut;
+    use tokio_postgres::{
+        types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type},
+        Row,
+    };
 
     impl From<&Row> for Spendable {
         fn from(row: &Row) -> Self {
@@ -47,4 +124,41 @@ mod postgres {
             }
         }
     }
+
+    impl From<&Row> for SpendableHistory {
+        fn from(row: &Row) -> Self {
+            Self {
+                old_total: row.get("old_total"),
+                new_total: row.get("new_total"),
+                source: row.get("source"),
+                created: row.get("created"),
+            }
+        }
+    }
+
+    impl<'a> FromSql<'a> for SpendableSource {
+        fn from_sql(
+            ty: &Type,
+            raw: &'a [u8],
+        ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+            let source = <&str as FromSql>::from_sql(ty, raw)?;
+
+            Ok(source.parse()?)
+        }
+
+        accepts!(VARCHAR, TEXT);
+    }
+
+    impl ToSql for SpendableSource {
+        fn to_sql(
+            &self,
+            ty: &Type,
+            w: &mut BytesMut,
+        ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+            self.as_str().to_sql(ty, w)
+        }
+
+        accepts!(VARCHAR, TEXT);
+        to_sql_checked!();
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{campaign::Pricing, sentry::EventType, Campaign, UnifiedNum};
+use crate::{campaign::Pricing, sentry::EventType, Campaign, UnifiedNum, IPFS};
 
 pub use eval::*;
 use serde_json::Number;
@@ -21,6 +21,28 @@ pub fn get_pricing_bounds(campaign: &Campaign, event_type: &EventType) -> Pricin
         })
 }
 
+/// Same as [`get_pricing_bounds()`] but it uses the
+/// [`Campaign.unit_pricing_overrides`](Campaign::unit_pricing_overrides)
+/// of the given AdUnit, see [`Campaign::unit_pricing()`].
+///
+/// If the Campaign has no pricing for the event type, the `default_pricing`
+/// of the event type is used, see [`EventTypeConfig`](crate::config::EventTypeConfig).
+pub fn get_unit_pricing_bounds(
+    campaign: &Campaign,
+    event_type: &EventType,
+    ad_unit: &IPFS,
+    default_pricing: Option<&Pricing>,
+) -> Pricing {
+    campaign
+        .unit_pricing(*event_type, ad_unit)
+        .or(default_pricing)
+        .cloned()
+        .unwrap_or_else(|| Pricing {
+            min: 0.into(),
+            max: 0.into(),
+        })
+}
+
 #[derive(Debug)]
 pub struct Output {
     /// Whether to show the ad
@@ -31,7 +53,7 @@ pub struct Output {
     /// Default: 1.0
     pub boost: f64,
     /// price.{eventType}
-    /// For example: price.IMPRESSION
+    /// For example: price.IMPRESSION, price.CLICK or price.SIGN_UP for a custom event type
     /// The default is the min of the bound of event type:
     /// The price is per one event
     /// Default: pricingBounds.IMPRESSION.min
@@ -1,8 +1,4 @@
-use crate::{
-    sentry::{CLICK, IMPRESSION},
-    unified_num::FromWhole,
-    UnifiedNum,
-};
+use crate::{sentry::EventType, unified_num::FromWhole, UnifiedNum};
 use serde::{Deserialize, Serialize};
 use serde_json::{value::Value as SerdeValue, Number};
 use std::{
@@ -1001,23 +997,20 @@ fn eval(input: &Input, output: &mut Output, rule: &Rule) -> Result<Option<Value>
 
                     output.show = show_value;
                 }
-                "price.IMPRESSION" => {
-                    let price = rule
-                        .eval(input, output)?
-                        .ok_or(Error::TypeError)?
-                        .try_unified()?;
+                // `price.{eventType}`, e.g. `price.IMPRESSION`, `price.CLICK` or `price.SIGN_UP`
+                price_key if price_key.starts_with("price.") => {
+                    let event_type = price_key
+                        .trim_start_matches("price.")
+                        .parse::<EventType>()
+                        .map_err(|_| Error::UnknownVariable)?;
 
-                    // we do not care about any other old value
-                    output.price.insert(IMPRESSION, price);
-                }
-                "price.CLICK" => {
                     let price = rule
                         .eval(input, output)?
                         .ok_or(Error::TypeError)?
                         .try_unified()?;
 
                     // we do not care about any other old value
-                    output.price.insert(CLICK, price);
+                    output.price.insert(event_type, price);
                 }
                 _ => return Err(Error::UnknownVariable),
             }
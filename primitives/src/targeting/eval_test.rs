@@ -335,6 +335,29 @@ mod dsl_test {
         assert_eq!(Ok(None), rule.eval(&input, &mut output));
 
         assert_eq!(Some(&UnifiedNum::from(20)), output.price.get("IMPRESSION"));
+
+        // only the price of the set event type is changed
+        let set_to = Value::UnifiedNum(UnifiedNum::from(3_500));
+        let rule = Rule::Function(Function::new_set("price.CLICK", set_to));
+
+        assert_eq!(Ok(None), rule.eval(&input, &mut output));
+
+        assert_eq!(Some(&UnifiedNum::from(3_500)), output.price.get("CLICK"));
+        assert_eq!(Some(&UnifiedNum::from(20)), output.price.get("IMPRESSION"));
+
+        // custom event types
+        let set_to = Value::UnifiedNum(UnifiedNum::from(5_000));
+        let rule = Rule::Function(Function::new_set("price.SIGN_UP", set_to));
+
+        assert_eq!(Ok(None), rule.eval(&input, &mut output));
+
+        assert_eq!(Some(&UnifiedNum::from(5_000)), output.price.get("SIGN_UP"));
+
+        // not an event type
+        let set_to = Value::UnifiedNum(UnifiedNum::from(5_000));
+        let rule = Rule::Function(Function::new_set("price.sign up", set_to));
+
+        assert_eq!(Err(Error::UnknownVariable), rule.eval(&input, &mut output));
     }
 
     #[test]
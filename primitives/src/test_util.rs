@@ -1,17 +1,16 @@
-use std::{collections::HashMap, ops::Deref};
+use std::{collections::HashMap, ops::Deref, time::Duration};
 
-use chrono::{TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use once_cell::sync::Lazy;
 
 use crate::{
-    campaign::{Active, Pricing, Validators},
-    channel::Nonce,
+    campaign::{Active, Pacing, Pricing, Validators},
     config::GANACHE_CONFIG,
-    sentry::{CLICK, IMPRESSION},
+    sentry::{EventType, CLICK, IMPRESSION},
     targeting::Rules,
     unified_num::FromWhole,
-    AdUnit, Address, Campaign, ChainOf, Channel, EventSubmission, UnifiedNum, ValidatorDesc,
-    ValidatorId, IPFS,
+    AdUnit, Address, Campaign, CampaignId, ChainOf, Channel, EventSubmission, UnifiedNum,
+    ValidatorDesc, ValidatorId, IPFS,
 };
 
 pub use logger::discard_logger;
@@ -160,6 +159,210 @@ pub static DUMMY_VALIDATOR_FOLLOWER: Lazy<ValidatorDesc> = Lazy::new(|| Validato
     fee_addr: None,
 });
 
+/// A builder of deterministic [`Campaign`] fixtures.
+///
+/// All the fields which are not set use the builder defaults,
+/// so adding a new [`Campaign`] field requires changing only [`CampaignBuilder::new`].
+///
+/// ```
+/// use primitives::{
+///     sentry::IMPRESSION,
+///     test_util::{CampaignBuilder, DUMMY_CAMPAIGN, DUMMY_VALIDATOR_FOLLOWER, DUMMY_VALIDATOR_LEADER},
+/// };
+///
+/// let campaign = CampaignBuilder::new(DUMMY_CAMPAIGN.channel)
+///     .budget_whole(150)
+///     .pricing(IMPRESSION, 0.04, 0.05)
+///     .validators(DUMMY_VALIDATOR_LEADER.clone(), DUMMY_VALIDATOR_FOLLOWER.clone())
+///     .build();
+///
+/// assert_eq!(DUMMY_CAMPAIGN.channel, campaign.channel);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CampaignBuilder {
+    campaign: Campaign,
+}
+
+impl CampaignBuilder {
+    /// The defaults are:
+    /// - [`CREATOR`] as the creator with a budget of `1 000`
+    /// - [`DUMMY_VALIDATOR_LEADER`] & [`DUMMY_VALIDATOR_FOLLOWER`] as validators
+    /// - no pricing bounds, ad units nor targeting rules
+    /// - created on `2021-02-01 07:00:00` and active until `2099-01-30`
+    pub fn new(channel: Channel) -> Self {
+        Self {
+            campaign: Campaign {
+                id: "0x936da01f9abd4d9d80c702af85c822a8"
+                    .parse()
+                    .expect("Should parse"),
+                channel,
+                creator: *CREATOR,
+                budget: UnifiedNum::from_whole(1_000),
+                validators: Validators::new((
+                    DUMMY_VALIDATOR_LEADER.clone(),
+                    DUMMY_VALIDATOR_FOLLOWER.clone(),
+                )),
+                title: None,
+                pricing_bounds: Default::default(),
+                unit_pricing_overrides: Default::default(),
+                accepted_event_types: None,
+                event_submission: Some(EventSubmission {
+                    allow: vec![],
+                    dedup_window: None,
+                }),
+                pacing: None,
+                ad_units: vec![],
+                targeting_rules: Rules::new(),
+                created: Utc.with_ymd_and_hms(2021, 2, 1, 7, 0, 0).unwrap(),
+                active: Active {
+                    from: None,
+                    to: Utc.with_ymd_and_hms(2099, 1, 30, 0, 0, 0).unwrap(),
+                },
+                version: 0,
+            },
+        }
+    }
+
+    pub fn id(mut self, id: CampaignId) -> Self {
+        self.campaign.id = id;
+        self
+    }
+
+    pub fn creator(mut self, creator: Address) -> Self {
+        self.campaign.creator = creator;
+        self
+    }
+
+    pub fn budget(mut self, budget: UnifiedNum) -> Self {
+        self.campaign.budget = budget;
+        self
+    }
+
+    /// Sets the budget in whole tokens, e.g. `150` = `150.00000000`
+    pub fn budget_whole(self, budget: u64) -> Self {
+        self.budget(UnifiedNum::from_whole(budget))
+    }
+
+    pub fn validators(mut self, leader: ValidatorDesc, follower: ValidatorDesc) -> Self {
+        self.campaign.validators = Validators::new((leader, follower));
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.campaign.title = Some(title.into());
+        self
+    }
+
+    /// Sets the [`Pricing`] of the event type in whole tokens, e.g. `0.04` = `0.04000000`
+    pub fn pricing(mut self, event_type: EventType, min: f64, max: f64) -> Self {
+        self.campaign.pricing_bounds.insert(
+            event_type,
+            Pricing {
+                min: UnifiedNum::from_whole(min),
+                max: UnifiedNum::from_whole(max),
+            },
+        );
+        self
+    }
+
+    /// Accepts only the given event types, see [`Campaign::accepts_event()`]
+    pub fn accepted_event_types(mut self, event_types: &[EventType]) -> Self {
+        self.campaign.accepted_event_types = Some(event_types.to_vec());
+        self
+    }
+
+    /// Sets the [`EventSubmission.dedup_window`](EventSubmission::dedup_window)
+    pub fn event_dedup_window(mut self, window: Duration) -> Self {
+        self.campaign
+            .event_submission
+            .get_or_insert_with(|| EventSubmission {
+                allow: vec![],
+                dedup_window: None,
+            })
+            .dedup_window = Some(window);
+        self
+    }
+
+    /// Sets the [`Pacing.daily_cap`](Pacing::daily_cap)
+    pub fn daily_cap(mut self, daily_cap: UnifiedNum) -> Self {
+        self.campaign.pacing = Some(Pacing { daily_cap });
+        self
+    }
+
+    pub fn ad_units(mut self, ad_units: &[AdUnit]) -> Self {
+        self.campaign.ad_units = ad_units.to_vec();
+        self
+    }
+
+    pub fn created(mut self, created: DateTime<Utc>) -> Self {
+        self.campaign.created = created;
+        self
+    }
+
+    pub fn active_from(mut self, from: DateTime<Utc>) -> Self {
+        self.campaign.active.from = Some(from);
+        self
+    }
+
+    pub fn build(self) -> Campaign {
+        self.campaign
+    }
+}
+
+/// The standard leader, follower, guardian & token permutations
+/// of the [`Channel`]s used in the tests and the harness.
+#[derive(Debug, Clone, Copy)]
+pub struct ChannelFixtureSet {
+    pub leader: ValidatorId,
+    pub follower: ValidatorId,
+    pub guardian: Address,
+    pub guardian_2: Address,
+}
+
+impl ChannelFixtureSet {
+    /// Uses [`GUARDIAN`] & [`GUARDIAN_2`] as the guardians.
+    pub fn new(leader: impl Into<ValidatorId>, follower: impl Into<ValidatorId>) -> Self {
+        Self {
+            leader: leader.into(),
+            follower: follower.into(),
+            guardian: *GUARDIAN,
+            guardian_2: *GUARDIAN_2,
+        }
+    }
+
+    /// The `leader` & `follower` with the `guardian`
+    pub fn channel(&self, token: Address, nonce: u64) -> Channel {
+        Self::build(self.leader, self.follower, self.guardian, token, nonce)
+    }
+
+    /// The `leader` & `follower` with the `guardian_2`
+    pub fn with_guardian_2(&self, token: Address, nonce: u64) -> Channel {
+        Self::build(self.leader, self.follower, self.guardian_2, token, nonce)
+    }
+
+    /// The `follower` as a leader & the `leader` as a follower with the `guardian_2`
+    pub fn switched(&self, token: Address, nonce: u64) -> Channel {
+        Self::build(self.follower, self.leader, self.guardian_2, token, nonce)
+    }
+
+    fn build(
+        leader: ValidatorId,
+        follower: ValidatorId,
+        guardian: Address,
+        token: Address,
+        nonce: u64,
+    ) -> Channel {
+        Channel::builder()
+            .leader(leader)
+            .follower(follower)
+            .guardian(guardian)
+            .token(token)
+            .nonce(nonce)
+            .build()
+            .expect("Should build a valid Channel")
+    }
+}
+
 /// Dummy Campaign uses Ganache #1337 with the mocked token
 pub static DUMMY_CAMPAIGN: Lazy<Campaign> = Lazy::new(|| {
     let token_info = GANACHE_CONFIG
@@ -170,52 +373,15 @@ pub static DUMMY_CAMPAIGN: Lazy<Campaign> = Lazy::new(|| {
         .get("Mocked TOKEN 1337")
         .unwrap();
 
-    Campaign {
-        id: "0x936da01f9abd4d9d80c702af85c822a8"
-            .parse()
-            .expect("Should parse"),
-        channel: Channel {
-            leader: IDS[&LEADER],
-            follower: IDS[&FOLLOWER],
-            guardian: *GUARDIAN,
-            token: token_info.address,
-            nonce: Nonce::from(987_654_321_u32),
-        },
-        creator: *CREATOR,
-        // 1000.00000000
-        budget: UnifiedNum::from_whole(1_000),
-        validators: Validators::new((
-            DUMMY_VALIDATOR_LEADER.clone(),
-            DUMMY_VALIDATOR_FOLLOWER.clone(),
-        )),
-        title: Some("Dummy Campaign".to_string()),
-        pricing_bounds: vec![
-            (
-                IMPRESSION,
-                Pricing {
-                    min: 1.into(),
-                    max: 10.into(),
-                },
-            ),
-            (
-                CLICK,
-                Pricing {
-                    min: 0.into(),
-                    max: 0.into(),
-                },
-            ),
-        ]
-        .into_iter()
-        .collect(),
-        event_submission: Some(EventSubmission { allow: vec![] }),
-        ad_units: vec![],
-        targeting_rules: Rules::new(),
-        created: Utc.ymd(2021, 2, 1).and_hms(7, 0, 0),
-        active: Active {
-            to: Utc.ymd(2099, 1, 30).and_hms(0, 0, 0),
-            from: None,
-        },
-    }
+    let channel = ChannelFixtureSet::new(IDS[&LEADER], IDS[&FOLLOWER])
+        .channel(token_info.address, 987_654_321);
+
+    CampaignBuilder::new(channel)
+        .title("Dummy Campaign")
+        // 0.00000001 - 0.00000010
+        .pricing(IMPRESSION, 0.000_000_01, 0.000_000_1)
+        .pricing(CLICK, 0.0, 0.0)
+        .build()
 });
 
 pub static DUMMY_AD_UNITS: Lazy<[AdUnit; 4]> = Lazy::new(|| {
@@ -302,18 +468,12 @@ pub static DUMMY_IPFS: Lazy<[IPFS; 5]> = Lazy::new(|| {
 
 /// List of test campaigns
 pub static CAMPAIGNS: Lazy<[ChainOf<Campaign>; 3]> = Lazy::new(|| {
+    let channels = ChannelFixtureSet::new(IDS[&LEADER], IDS[&FOLLOWER]);
+
     let campaign_1337_1 = {
         let ganache_chain_info = GANACHE_CONFIG.chains["Ganache #1337"].clone();
         let token_info = ganache_chain_info.tokens["Mocked TOKEN 1337"].clone();
 
-        let channel = Channel {
-            leader: IDS[&LEADER],
-            follower: IDS[&FOLLOWER],
-            guardian: *GUARDIAN,
-            token: token_info.address,
-            nonce: 0_u64.into(),
-        };
-
         let leader_desc = ValidatorDesc {
             fee: UnifiedNum::from_whole(0.0005),
             ..DUMMY_VALIDATOR_LEADER.clone()
@@ -324,59 +484,25 @@ pub static CAMPAIGNS: Lazy<[ChainOf<Campaign>; 3]> = Lazy::new(|| {
             ..DUMMY_VALIDATOR_FOLLOWER.clone()
         };
 
-        let validators = Validators::new((leader_desc, follower_desc));
-
-        ChainOf::new(ganache_chain_info.chain, token_info).with_campaign(Campaign {
-            id: "0x936da01f9abd4d9d80c702af85c822a8"
-                .parse()
-                .expect("Should parse"),
-            channel,
-            creator: *ADVERTISER,
+        let campaign = CampaignBuilder::new(channels.channel(token_info.address, 0))
+            .creator(*ADVERTISER)
             // 100 000
-            budget: UnifiedNum::from_whole(100_000),
-            validators,
-            title: Some("Dummy Campaign".to_string()),
-            pricing_bounds: vec![
-                (
-                    IMPRESSION,
-                    Pricing {
-                        min: UnifiedNum::from_whole(0.0004),
-                        max: UnifiedNum::from_whole(0.0005),
-                    },
-                ),
-                (
-                    CLICK,
-                    Pricing {
-                        min: UnifiedNum::from_whole(0.0006),
-                        max: UnifiedNum::from_whole(0.001),
-                    },
-                ),
-            ]
-            .into_iter()
-            .collect(),
-            event_submission: Some(EventSubmission { allow: vec![] }),
-            ad_units: vec![DUMMY_AD_UNITS[0].clone(), DUMMY_AD_UNITS[1].clone()],
-            targeting_rules: Rules::new(),
-            created: Utc.ymd(2021, 2, 1).and_hms(7, 0, 0),
-            active: Active {
-                from: Some(Utc.ymd(2022, 6, 27).and_hms(0, 0, 0)),
-                to: Utc.ymd(2099, 1, 30).and_hms(0, 0, 0),
-            },
-        })
+            .budget_whole(100_000)
+            .validators(leader_desc, follower_desc)
+            .title("Dummy Campaign")
+            .pricing(IMPRESSION, 0.0004, 0.0005)
+            .pricing(CLICK, 0.0006, 0.001)
+            .ad_units(&DUMMY_AD_UNITS[..2])
+            .active_from(Utc.with_ymd_and_hms(2022, 6, 27, 0, 0, 0).unwrap())
+            .build();
+
+        ChainOf::new(ganache_chain_info.chain, token_info).with_campaign(campaign)
     };
 
     let campaign_1337_2 = {
         let ganache_chain_info = GANACHE_CONFIG.chains["Ganache #1337"].clone();
         let token_info = ganache_chain_info.tokens["Mocked TOKEN 1337"].clone();
 
-        let channel = Channel {
-            leader: IDS[&FOLLOWER],
-            follower: IDS[&LEADER],
-            guardian: *GUARDIAN_2,
-            token: token_info.address,
-            nonce: 0_u64.into(),
-        };
-
         let leader_desc = ValidatorDesc {
             fee: UnifiedNum::from_whole(0.0001),
             ..DUMMY_VALIDATOR_FOLLOWER.clone()
@@ -386,59 +512,28 @@ pub static CAMPAIGNS: Lazy<[ChainOf<Campaign>; 3]> = Lazy::new(|| {
             fee: UnifiedNum::from_whole(0.00005),
             ..DUMMY_VALIDATOR_LEADER.clone()
         };
-        let validators = Validators::new((leader_desc, follower_desc));
 
-        ChainOf::new(ganache_chain_info.chain, token_info).with_campaign(Campaign {
-            id: "0x127b98248f4e4b73af409d10f62daeaa"
+        let campaign = CampaignBuilder::new(channels.switched(token_info.address, 0))
+            .id("0x127b98248f4e4b73af409d10f62daeaa"
                 .parse()
-                .expect("Should parse"),
-            channel,
-            creator: *ADVERTISER,
+                .expect("Should parse"))
+            .creator(*ADVERTISER)
             // 200 000
-            budget: UnifiedNum::from_whole(200_000.0),
-            validators,
-            title: Some("Dummy Campaign 2 in Chain #1337".to_string()),
-            pricing_bounds: vec![
-                (
-                    IMPRESSION,
-                    Pricing {
-                        min: UnifiedNum::from_whole(0.0004),
-                        max: UnifiedNum::from_whole(0.0005),
-                    },
-                ),
-                (
-                    CLICK,
-                    Pricing {
-                        min: UnifiedNum::from_whole(0.0006),
-                        max: UnifiedNum::from_whole(0.001),
-                    },
-                ),
-            ]
-            .into_iter()
-            .collect(),
-            event_submission: Some(EventSubmission { allow: vec![] }),
-            ad_units: vec![DUMMY_AD_UNITS[0].clone(), DUMMY_AD_UNITS[1].clone()],
-            targeting_rules: Rules::new(),
-            created: Utc.ymd(2021, 2, 1).and_hms(7, 0, 0),
-            active: Active {
-                from: None,
-                to: Utc.ymd(2099, 1, 30).and_hms(0, 0, 0),
-            },
-        })
+            .budget_whole(200_000)
+            .validators(leader_desc, follower_desc)
+            .title("Dummy Campaign 2 in Chain #1337")
+            .pricing(IMPRESSION, 0.0004, 0.0005)
+            .pricing(CLICK, 0.0006, 0.001)
+            .ad_units(&DUMMY_AD_UNITS[..2])
+            .build();
+
+        ChainOf::new(ganache_chain_info.chain, token_info).with_campaign(campaign)
     };
 
     let campaign_1_1 = {
         let ganache_chain_info = GANACHE_CONFIG.chains["Ganache #1"].clone();
         let token_info = ganache_chain_info.tokens["Mocked TOKEN 1"].clone();
 
-        let channel = Channel {
-            leader: IDS[&LEADER],
-            follower: IDS[&FOLLOWER],
-            guardian: *GUARDIAN_2,
-            token: token_info.address,
-            nonce: 1_u64.into(),
-        };
-
         let leader_desc = ValidatorDesc {
             fee: UnifiedNum::from_whole(0.00002),
             ..DUMMY_VALIDATOR_LEADER.clone()
@@ -449,53 +544,25 @@ pub static CAMPAIGNS: Lazy<[ChainOf<Campaign>; 3]> = Lazy::new(|| {
             ..DUMMY_VALIDATOR_FOLLOWER.clone()
         };
 
-        let validators = Validators::new((leader_desc, follower_desc));
-
-        ChainOf::new(ganache_chain_info.chain, token_info).with_campaign(Campaign {
-            id: "0xa78f3492481b41a688488a7aa1ff17df"
+        let campaign = CampaignBuilder::new(channels.with_guardian_2(token_info.address, 1))
+            .id("0xa78f3492481b41a688488a7aa1ff17df"
                 .parse()
-                .expect("Should parse"),
-            channel,
-            creator: *ADVERTISER_2,
+                .expect("Should parse"))
+            .creator(*ADVERTISER_2)
             // 20 000
-            budget: UnifiedNum::from_whole(20_000),
-            validators,
-            title: Some("Dummy Campaign 3 in Chain #1".to_string()),
-            pricing_bounds: vec![
-                (
-                    IMPRESSION,
-                    Pricing {
-                        // 0.01500000
-                        // Per 1000 = 15.00000000
-                        min: UnifiedNum::from_whole(0.015),
-                        // 0.0250000
-                        // Per 1000 = 25.00000000
-                        max: UnifiedNum::from_whole(0.025),
-                    },
-                ),
-                (
-                    CLICK,
-                    Pricing {
-                        // 0.03500000
-                        // Per 1000 = 35.00000000
-                        min: UnifiedNum::from_whole(0.035),
-                        // 0.06500000
-                        // Per 1000 = 65.00000000
-                        max: UnifiedNum::from_whole(0.065),
-                    },
-                ),
-            ]
-            .into_iter()
-            .collect(),
-            event_submission: Some(EventSubmission { allow: vec![] }),
-            ad_units: vec![DUMMY_AD_UNITS[2].clone(), DUMMY_AD_UNITS[3].clone()],
-            targeting_rules: Rules::new(),
-            created: Utc.ymd(2021, 2, 1).and_hms(7, 0, 0),
-            active: Active {
-                from: None,
-                to: Utc.ymd(2099, 1, 30).and_hms(0, 0, 0),
-            },
-        })
+            .budget_whole(20_000)
+            .validators(leader_desc, follower_desc)
+            .title("Dummy Campaign 3 in Chain #1")
+            // 0.01500000 - 0.02500000
+            // Per 1000 = 15.00000000 - 25.00000000
+            .pricing(IMPRESSION, 0.015, 0.025)
+            // 0.03500000 - 0.06500000
+            // Per 1000 = 35.00000000 - 65.00000000
+            .pricing(CLICK, 0.035, 0.065)
+            .ad_units(&DUMMY_AD_UNITS[2..])
+            .build();
+
+        ChainOf::new(ganache_chain_info.chain, token_info).with_campaign(campaign)
     };
 
     [campaign_1337_1, campaign_1337_2, campaign_1_1]
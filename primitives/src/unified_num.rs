@@ -12,9 +12,25 @@ use std::{
     iter::Sum,
     ops::{Add, AddAssign, Div, Mul, Rem, Sub},
 };
+use thiserror::Error;
 
 pub use whole_number::FromWhole;
 
+/// The value can't be represented in the given precision without truncating it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("Converting to precision {precision} would truncate the value")]
+pub struct PrecisionLoss {
+    pub precision: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum FromPrecisionError {
+    #[error(transparent)]
+    PrecisionLoss(#[from] PrecisionLoss),
+    #[error("The value is larger than what UnifiedNum can hold")]
+    Overflow,
+}
+
 mod whole_number {
     use num::ToPrimitive;
 
@@ -234,6 +250,14 @@ impl UnifiedNum {
         CheckedMul::checked_mul(self, rhs)
     }
 
+    /// Same as [`UnifiedNum::checked_mul`] but the result is floored instead of rounded.
+    pub fn checked_mul_floor(&self, rhs: &UnifiedNum) -> Option<Self> {
+        let ratio =
+            Ratio::from_integer(self.0).checked_mul(&Ratio::new(rhs.0, UnifiedNum::MULTIPLIER));
+
+        ratio.map(|ratio| Self(ratio.floor().to_integer()))
+    }
+
     pub fn checked_div(&self, rhs: &UnifiedNum) -> Option<Self> {
         CheckedDiv::checked_div(self, rhs)
     }
@@ -243,6 +267,9 @@ impl UnifiedNum {
     }
 
     /// Transform the UnifiedNum precision 8 to a new precision
+    ///
+    /// For precisions lower than [`UnifiedNum::PRECISION`] the value is floored,
+    /// use [`UnifiedNum::to_precision_lossless`] when truncation is not acceptable.
     pub fn to_precision(self, precision: u8) -> BigNum {
         let inner = BigNum::from(self.0);
 
@@ -253,6 +280,20 @@ impl UnifiedNum {
         }
     }
 
+    /// Same as [`UnifiedNum::to_precision`] but returns an error
+    /// if the value can't be represented in the new precision without truncation.
+    pub fn to_precision_lossless(self, precision: u8) -> Result<BigNum, PrecisionLoss> {
+        if precision < Self::PRECISION {
+            let divisor = 10_u64.pow((Self::PRECISION - precision).into());
+
+            if !self.0.is_multiple_of(divisor) {
+                return Err(PrecisionLoss { precision });
+            }
+        }
+
+        Ok(self.to_precision(precision))
+    }
+
     /// Transform the BigNum of a given precision to UnifiedNum with precision 8
     /// If the resulting value is larger that what UnifiedNum can hold, it will return `None`
     pub fn from_precision(amount: BigNum, precision: u8) -> Option<Self> {
@@ -268,6 +309,30 @@ impl UnifiedNum {
         from_precision.to_u64().map(Self)
     }
 
+    /// Same as [`UnifiedNum::from_precision`] but returns an error
+    /// if the amount can't be represented in [`UnifiedNum::PRECISION`] without truncation.
+    pub fn from_precision_exact(amount: BigNum, precision: u8) -> Result<Self, FromPrecisionError> {
+        if precision > Self::PRECISION {
+            let divisor = BigNum::from(10).pow(precision - Self::PRECISION);
+
+            if !amount.is_multiple_of(&divisor) {
+                return Err(PrecisionLoss { precision }.into());
+            }
+        }
+
+        Self::from_precision(amount, precision).ok_or(FromPrecisionError::Overflow)
+    }
+
+    /// Same as [`UnifiedNum::from_precision`] but also returns the dust,
+    /// i.e. the part of the amount (in the given precision) which was floored
+    /// because it can't be represented in [`UnifiedNum::PRECISION`].
+    pub fn from_precision_with_dust(amount: BigNum, precision: u8) -> Option<(Self, BigNum)> {
+        let unified = Self::from_precision(amount.clone(), precision)?;
+        let dust = &amount - &unified.to_precision(precision);
+
+        Some((unified, dust))
+    }
+
     pub fn to_float_string(self) -> String {
         let mut string_value = self.0.to_string();
         let value_length = string_value.len();
@@ -331,7 +396,7 @@ impl Integer for UnifiedNum {
 
     // TODO: Check math and write tests
     fn is_multiple_of(&self, other: &Self) -> bool {
-        self.0.is_multiple_of(&other.0)
+        Integer::is_multiple_of(&self.0, &other.0)
     }
 
     fn is_even(&self) -> bool {
@@ -772,6 +837,147 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_unified_num_exact_precision_conversions() {
+        let usdc_precision: u8 = 6;
+        let dai_precision: u8 = 18;
+
+        // 321.00000700
+        let exact = UnifiedNum::from(32_100_000_700_u64);
+        assert_eq!(
+            Ok(BigNum::from(321_000_007_u64)),
+            exact.to_precision_lossless(usdc_precision)
+        );
+
+        // 321.00000777 - can't be represented with precision 6
+        let truncated = UnifiedNum::from(32_100_000_777_u64);
+        assert_eq!(
+            Err(PrecisionLoss {
+                precision: usdc_precision
+            }),
+            truncated.to_precision_lossless(usdc_precision)
+        );
+        assert!(truncated.to_precision_lossless(dai_precision).is_ok());
+
+        // 1.000000000000000001 DAI - can't be represented with UnifiedNum precision
+        let dai_dust = BigNum::from(1_000_000_000_000_000_001_u64);
+        assert_eq!(
+            Err(FromPrecisionError::PrecisionLoss(PrecisionLoss {
+                precision: dai_precision
+            })),
+            UnifiedNum::from_precision_exact(dai_dust.clone(), dai_precision)
+        );
+        assert_eq!(
+            Some(UnifiedNum::from(100_000_000_u64)),
+            UnifiedNum::from_precision(dai_dust.clone(), dai_precision),
+            "Lossy conversion should floor the value"
+        );
+        assert_eq!(
+            Some((UnifiedNum::from(100_000_000_u64), BigNum::from(1))),
+            UnifiedNum::from_precision_with_dust(dai_dust, dai_precision),
+        );
+        assert_eq!(
+            Some((UnifiedNum::from(100_000_000_u64), BigNum::from(0))),
+            UnifiedNum::from_precision_with_dust(BigNum::from(1_000_000), usdc_precision),
+        );
+
+        // `u64::MAX + 1` in precision 6
+        let larger_bignum = BigNum::from(u64::MAX) + BigNum::from(1);
+        assert_eq!(
+            Err(FromPrecisionError::Overflow),
+            UnifiedNum::from_precision_exact(larger_bignum, usdc_precision)
+        );
+    }
+
+    mod precision_round_trip {
+        use super::*;
+        use crate::Deposit;
+        use proptest::prelude::*;
+
+        fn precision() -> impl Strategy<Value = u8> {
+            prop_oneof![Just(6_u8), Just(8_u8), Just(12_u8), Just(18_u8)]
+        }
+
+        /// The smallest [`UnifiedNum`] which can be represented in the given precision
+        fn unit(precision: u8) -> u64 {
+            10_u64.pow(UnifiedNum::PRECISION.saturating_sub(precision).into())
+        }
+
+        proptest! {
+            #[test]
+            fn lossless_round_trip(value in any::<u64>(), precision in precision()) {
+                let unified = UnifiedNum::from(value);
+
+                match unified.to_precision_lossless(precision) {
+                    Ok(converted) => {
+                        prop_assert_eq!(0, value % unit(precision));
+                        prop_assert_eq!(Ok(unified), UnifiedNum::from_precision_exact(converted, precision));
+                    }
+                    Err(err) => {
+                        prop_assert_ne!(0, value % unit(precision));
+                        prop_assert_eq!(PrecisionLoss { precision }, err);
+                    }
+                }
+            }
+
+            #[test]
+            fn lossy_round_trip_never_exceeds_value(value in any::<u64>(), precision in precision()) {
+                let unified = UnifiedNum::from(value);
+
+                let round_trip = UnifiedNum::from_precision(unified.to_precision(precision), precision)
+                    .expect("Should never overflow after flooring");
+
+                prop_assert!(round_trip <= unified);
+                prop_assert_eq!(value - value % unit(precision), round_trip.to_u64());
+            }
+
+            #[test]
+            fn exact_from_precision_round_trip(
+                value in any::<u64>(),
+                dust in any::<u64>(),
+                precision in precision(),
+            ) {
+                // for precisions larger than `UnifiedNum::PRECISION` add some dust
+                // which might not be representable in `UnifiedNum`
+                let amount = match precision.checked_sub(UnifiedNum::PRECISION) {
+                    Some(exceeding) if exceeding > 0 => {
+                        let power = BigNum::from(10).pow(exceeding);
+
+                        BigNum::from(value) * power.clone() + BigNum::from(dust) % power
+                    }
+                    _ => BigNum::from(value),
+                };
+
+                match UnifiedNum::from_precision_exact(amount.clone(), precision) {
+                    Ok(unified) => prop_assert_eq!(amount, unified.to_precision(precision)),
+                    Err(FromPrecisionError::PrecisionLoss(_)) => {
+                        prop_assert!(precision > UnifiedNum::PRECISION);
+                        prop_assert_ne!(
+                            Some(amount.clone()),
+                            UnifiedNum::from_precision(amount, precision)
+                                .map(|unified| unified.to_precision(precision))
+                        );
+                    }
+                    Err(FromPrecisionError::Overflow) => {
+                        prop_assert!(UnifiedNum::from_precision(amount, precision).is_none());
+                    }
+                }
+            }
+
+            #[test]
+            fn deposit_round_trip(value in any::<u64>(), precision in precision()) {
+                let deposit = Deposit {
+                    total: UnifiedNum::from(value - value % unit(precision)),
+                };
+
+                prop_assert_eq!(
+                    Ok(deposit.clone()),
+                    Deposit::<UnifiedNum>::from_precision_exact(deposit.to_precision(precision), precision)
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_unified_num_mul_and_div_and_div_floor() {
         // 0.0003
@@ -0,0 +1,94 @@
+//! Canonical JSON serialization, used for hashing & signing JSON documents,
+//! e.g. the [`ChannelSnapshot`](crate::sentry::ChannelSnapshot).
+//!
+//! The canonical form of a value is its compact JSON where:
+//!
+//! - the keys of every object, at any depth, are sorted by their UTF-8 bytes;
+//! - there is no whitespace between the tokens;
+//! - the arrays keep their order;
+//! - strings and numbers are serialized as `serde_json` does.
+//!
+//! Serializing the same value, regardless of the order of its maps (e.g. a `HashMap`),
+//! always produces the same string.
+use serde::Serialize;
+use serde_json::Value;
+
+/// Serializes the `value` to its canonical JSON.
+pub fn to_canonical_json<T: Serialize + ?Sized>(value: &T) -> Result<String, serde_json::Error> {
+    let value = serde_json::to_value(value)?;
+
+    let mut canonical = String::new();
+    write_canonical(&value, &mut canonical);
+
+    Ok(canonical)
+}
+
+fn write_canonical(value: &Value, canonical: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_unstable_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+
+            canonical.push('{');
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    canonical.push(',');
+                }
+                canonical.push_str(&Value::String(key.clone()).to_string());
+                canonical.push(':');
+                write_canonical(value, canonical);
+            }
+            canonical.push('}');
+        }
+        Value::Array(values) => {
+            canonical.push('[');
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    canonical.push(',');
+                }
+                write_canonical(value, canonical);
+            }
+            canonical.push(']');
+        }
+        scalar => canonical.push_str(&scalar.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn sorts_the_keys_at_any_depth_without_whitespace() {
+        let value = json!({
+            "zeta": [3, {"b": "two", "a": null}, 1],
+            "alpha": {"y": true, "x": 1.5, "\"quoted\"": "line\nbreak"},
+            "Beta": "upper case sorts first"
+        });
+
+        assert_eq!(
+            r#"{"Beta":"upper case sorts first","alpha":{"\"quoted\"":"line\nbreak","x":1.5,"y":true},"zeta":[3,{"a":null,"b":"two"},1]}"#,
+            to_canonical_json(&value).expect("Should serialize")
+        );
+    }
+
+    #[test]
+    fn maps_are_serialized_deterministically() {
+        let first = (0..50)
+            .map(|n| (format!("key {n}"), n))
+            .collect::<HashMap<_, _>>();
+        let second = (0..50)
+            .rev()
+            .map(|n| (format!("key {n}"), n))
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(
+            to_canonical_json(&first).expect("Should serialize"),
+            to_canonical_json(&second).expect("Should serialize")
+        );
+    }
+}
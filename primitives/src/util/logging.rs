@@ -1,4 +1,4 @@
-use slog::{o, Drain, Logger, OwnedKVList, Record, KV};
+use slog::{o, Drain, Filter, Level, Logger, OwnedKVList, Record, KV};
 use slog_term::{
     timestamp_local, CompactFormatSerializer, CountingWriter, Decorator, RecordDecorator,
     Serializer, ThreadSafeTimestampFn,
@@ -6,17 +6,172 @@ use slog_term::{
 use std::{
     cell::RefCell,
     io::{Error, Result, Write},
+    str::FromStr,
 };
 
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub mod tracing;
+
 pub use slog_async::Async;
+pub use slog_json::Json;
 pub use slog_term::TermDecorator;
 
+/// The format of the logs, set with the `LOG_FORMAT` environment variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// The human readable [`PrefixedCompactFormat`] in the terminal.
+    #[default]
+    Term,
+    /// A JSON object per line, see [`json_drain`].
+    Json,
+}
+
+impl LogFormat {
+    /// `LOG_FORMAT=json` uses [`LogFormat::Json`],
+    /// any other value or not setting it uses [`LogFormat::Term`].
+    pub fn from_env() -> Self {
+        match std::env::var("LOG_FORMAT") {
+            Ok(format) if format.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Term,
+        }
+    }
+}
+
+/// The global and the per-module log levels, set with the `LOG_LEVEL` environment variable.
+///
+/// The format is a comma separated list of a global level and `module=level` overrides,
+/// e.g. `info,validator_worker::follower=debug`.
+/// The levels are the [`Level`] names, e.g. `trace`, `debug`, `info`, `warn`, `error` or `critical`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelFilters {
+    /// The level of the records whose module has no override.
+    pub global: Level,
+    pub modules: Vec<(String, Level)>,
+}
+
+impl Default for LevelFilters {
+    /// Logs everything, i.e. the [`Level::Trace`] global level without any overrides.
+    fn default() -> Self {
+        Self {
+            global: Level::Trace,
+            modules: vec![],
+        }
+    }
+}
+
+impl LevelFilters {
+    /// Parses the `LOG_LEVEL` environment variable,
+    /// not setting it uses the [`LevelFilters::default()`].
+    ///
+    /// # Panics
+    ///
+    /// When `LOG_LEVEL` is not a valid list of levels.
+    pub fn from_env() -> Self {
+        match std::env::var("LOG_LEVEL") {
+            Ok(filters) => filters
+                .parse()
+                .unwrap_or_else(|error| panic!("Invalid LOG_LEVEL environment variable: {error}")),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// The level of the `module`, from the override of its longest matching parent module, if any.
+    ///
+    /// `validator_worker::follower=debug` applies to both `validator_worker::follower`
+    /// and `validator_worker::follower::tick` but not to `validator_worker::follower_tick`.
+    pub fn level(&self, module: &str) -> Level {
+        self.modules
+            .iter()
+            .filter(|(prefix, _)| {
+                module
+                    .strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.global, |(_, level)| *level)
+    }
+
+    pub fn is_enabled(&self, record: &Record<'_>) -> bool {
+        record.level().is_at_least(self.level(record.module()))
+    }
+}
+
+impl FromStr for LevelFilters {
+    type Err = String;
+
+    fn from_str(filters: &str) -> std::result::Result<Self, Self::Err> {
+        let parse_level = |level: &str| {
+            Level::from_str(level.trim()).map_err(|_| format!("unknown log level `{level}`"))
+        };
+
+        let mut level_filters = Self::default();
+        for filter in filters.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            match filter.split_once('=') {
+                Some((module, level)) => level_filters
+                    .modules
+                    .push((module.trim().to_owned(), parse_level(level)?)),
+                None => level_filters.global = parse_level(filter)?,
+            }
+        }
+
+        Ok(level_filters)
+    }
+}
+
+/// Creates a new [`Logger`] with the [`LogFormat::from_env()`] format
+/// and the [`LevelFilters::from_env()`] levels.
 pub fn new_logger(prefix: &str) -> Logger {
-    let decorator = TermDecorator::new().build();
-    let drain = PrefixedCompactFormat::new(prefix, decorator).fuse();
-    let drain = Async::new(drain).build().fuse();
+    new_logger_with(prefix, LogFormat::from_env(), LevelFilters::from_env())
+}
+
+/// Creates a new [`Logger`] as [`new_logger()`] and installs the global `tracing`
+/// subscriber which emits through it, see [`tracing::init()`].
+///
+/// Keep the returned [`TracingGuard`](tracing::TracingGuard) for the lifetime of the application.
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub fn new_logger_with_tracing(
+    prefix: &str,
+) -> std::result::Result<(Logger, tracing::TracingGuard), tracing::Error> {
+    let logger = new_logger(prefix);
+    let guard = tracing::init(logger.clone())?;
+
+    Ok((logger, guard))
+}
+
+pub fn new_logger_with_format(prefix: &str, format: LogFormat) -> Logger {
+    new_logger_with(prefix, format, LevelFilters::default())
+}
+
+pub fn new_logger_with(prefix: &str, format: LogFormat, filters: LevelFilters) -> Logger {
+    match format {
+        LogFormat::Term => {
+            let decorator = TermDecorator::new().build();
+            let drain = PrefixedCompactFormat::new(prefix, decorator).fuse();
+            let drain = Filter::new(drain, move |record| filters.is_enabled(record)).fuse();
+            let drain = Async::new(drain).build().fuse();
 
-    Logger::root(drain, o!())
+            Logger::root(drain, o!())
+        }
+        LogFormat::Json => {
+            let drain = json_drain(prefix, std::io::stdout()).fuse();
+            let drain = Filter::new(drain, move |record| filters.is_enabled(record)).fuse();
+            let drain = Async::new(drain).build().fuse();
+
+            Logger::root(drain, o!())
+        }
+    }
+}
+
+/// A [`Json`] drain writing a JSON object per line with the `ts`, `level`, `msg`
+/// and `prefix` keys alongside the key/value fields of the logger and the record.
+pub fn json_drain<W: Write>(prefix: &str, writer: W) -> Json<W> {
+    Json::new(writer)
+        .add_default_keys()
+        .add_key_value(o!("prefix" => prefix.to_owned()))
+        .set_newlines(true)
+        .build()
 }
 
 pub struct PrefixedCompactFormat<D>
@@ -120,3 +275,117 @@ pub fn print_msg_header(
     write!(count_rd, "{}", record.msg())?;
     Ok(count_rd.count() != 0)
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::Value;
+    use slog::{debug, error, info, trace};
+
+    use super::*;
+
+    /// Keeps everything written to it, shared between the clones.
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.0.lock().expect("Should lock").write(buf)
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn json_drain_emits_a_json_object_per_line() {
+        let output = Output::default();
+        let drain = Mutex::new(json_drain("sentry", output.clone())).fuse();
+        let logger = Logger::root(drain, o!("worker" => "leader"));
+
+        info!(&logger, "Ticked"; "channel" => "0x061d", "tick" => 5);
+        error!(&logger, "Failed: {}", "timeout"; "retries" => 3);
+
+        let output = String::from_utf8(output.0.lock().expect("Should lock").clone())
+            .expect("Should be UTF-8");
+        let lines = output
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).expect("Should be valid JSON"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(2, lines.len());
+
+        let info = &lines[0];
+        assert!(info["ts"].is_string());
+        assert_eq!("INFO", info["level"]);
+        assert_eq!("Ticked", info["msg"]);
+        assert_eq!("sentry", info["prefix"]);
+        assert_eq!("leader", info["worker"]);
+        assert_eq!("0x061d", info["channel"]);
+        assert_eq!(5, info["tick"]);
+
+        let error = &lines[1];
+        assert_eq!("ERRO", error["level"]);
+        assert_eq!("Failed: timeout", error["msg"]);
+        assert_eq!(3, error["retries"]);
+    }
+
+    #[test]
+    fn parses_the_level_filters() {
+        let filters =
+            LevelFilters::from_str("warn, validator_worker=info,validator_worker::follower=DEBUG")
+                .expect("Should parse");
+
+        assert_eq!(Level::Warning, filters.global);
+        assert_eq!(Level::Warning, filters.level("sentry::routes"));
+        assert_eq!(Level::Info, filters.level("validator_worker::leader"));
+        assert_eq!(Level::Debug, filters.level("validator_worker::follower"));
+        assert_eq!(
+            Level::Debug,
+            filters.level("validator_worker::follower::tick")
+        );
+        assert_eq!(Level::Warning, filters.level("validator_worker_cli"));
+
+        assert_eq!(
+            Ok(LevelFilters::default()),
+            LevelFilters::from_str(""),
+            "No levels should log everything"
+        );
+        assert!(LevelFilters::from_str("info,sentry=verbose").is_err());
+    }
+
+    #[test]
+    fn module_override_logs_below_the_global_level() {
+        let output = Output::default();
+        let filters = LevelFilters::from_str(&format!("info,{}=debug", module_path!()))
+            .expect("Should parse");
+        let drain = Mutex::new(json_drain("worker", output.clone())).fuse();
+        let drain = Filter::new(drain, move |record| filters.is_enabled(record)).fuse();
+        let logger = Logger::root(drain, o!());
+
+        debug!(&logger, "Overridden");
+        trace!(&logger, "Below the override");
+
+        let lines = output.0.lock().expect("Should lock").clone();
+        let lines = String::from_utf8(lines).expect("Should be UTF-8");
+        let messages = lines
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<Value>(line).expect("Should be valid JSON")["msg"].clone()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Value::from("Overridden")], messages);
+
+        // without the override the global level applies
+        let output = Output::default();
+        let filters = LevelFilters::from_str("info").expect("Should parse");
+        let drain = Mutex::new(json_drain("worker", output.clone())).fuse();
+        let drain = Filter::new(drain, move |record| filters.is_enabled(record)).fuse();
+        let logger = Logger::root(drain, o!());
+
+        debug!(&logger, "Filtered");
+        assert!(output.0.lock().expect("Should lock").is_empty());
+    }
+}
@@ -0,0 +1,295 @@
+//! A [`tracing`](::tracing) bridge emitting the `tracing` events through a slog [`Logger`],
+//! so the existing log output (format, prefix & [`LevelFilters`](super::LevelFilters)) is preserved.
+//!
+//! The fields of the spans in which an event happened (e.g. the `route` of a Sentry request
+//! or the `channel` of a worker tick) are added to the key/value fields of the slog record.
+//!
+//! With the `otlp` feature, the spans are also exported with OpenTelemetry
+//! when the `OTEL_EXPORTER_OTLP_ENDPOINT` (or `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`)
+//! environment variable is set, see [`init()`].
+use std::fmt;
+
+use ::tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Level as TracingLevel, Subscriber,
+};
+use slog::{BorrowedKV, Level, Logger, RecordLocation, RecordStatic, Serializer, KV};
+use thiserror::Error;
+use tracing_subscriber::{
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+    Layer, Registry,
+};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Failed to install the tracing subscriber: {0}")]
+    SetGlobalDefault(#[from] ::tracing::subscriber::SetGlobalDefaultError),
+    #[cfg(feature = "otlp")]
+    #[error("Failed to install the OTLP exporter: {0}")]
+    Otlp(#[from] opentelemetry::trace::TraceError),
+}
+
+/// Returned by [`init()`], keep it for as long as the spans should be exported.
+///
+/// With the `otlp` feature, dropping it flushes the remaining spans and shuts down the exporter.
+#[derive(Debug)]
+#[must_use = "dropping the guard shuts down the OTLP exporter"]
+pub struct TracingGuard {
+    #[cfg(feature = "otlp")]
+    otlp: bool,
+}
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otlp")]
+        if self.otlp {
+            opentelemetry::global::shutdown_tracer_provider();
+        }
+    }
+}
+
+/// Creates a [`Subscriber`] which emits the `tracing` events through the [`Logger`].
+pub fn subscriber(logger: Logger) -> impl Subscriber + Send + Sync {
+    Registry::default().with(SlogLayer::new(logger))
+}
+
+/// Installs the global [`subscriber()`] and, with the `otlp` feature, the OTLP exporter
+/// if `OTEL_EXPORTER_OTLP_ENDPOINT` or `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT` is set.
+///
+/// The exporter is configured with the standard `OTEL_*` environment variables,
+/// e.g. `OTEL_SERVICE_NAME` and `OTEL_EXPORTER_OTLP_TIMEOUT`.
+pub fn init(logger: Logger) -> Result<TracingGuard, Error> {
+    #[cfg(feature = "otlp")]
+    {
+        let otlp = otlp_layer()?;
+        let guard = TracingGuard {
+            otlp: otlp.is_some(),
+        };
+        let subscriber = Registry::default().with(SlogLayer::new(logger)).with(otlp);
+        ::tracing::subscriber::set_global_default(subscriber)?;
+
+        Ok(guard)
+    }
+
+    #[cfg(not(feature = "otlp"))]
+    {
+        ::tracing::subscriber::set_global_default(subscriber(logger))?;
+
+        Ok(TracingGuard {})
+    }
+}
+
+#[cfg(feature = "otlp")]
+fn otlp_layer<S>() -> Result<Option<impl Layer<S>>, Error>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let is_configured = [
+        "OTEL_EXPORTER_OTLP_ENDPOINT",
+        "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+    ]
+    .iter()
+    .any(|var| std::env::var_os(var).is_some());
+    if !is_configured {
+        return Ok(None);
+    }
+
+    // the batch processor runs in its own thread,
+    // so the exporter can be installed outside of a Tokio runtime
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().http())
+        .install_batch(opentelemetry::runtime::TokioCurrentThread)?;
+
+    Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+}
+
+/// A [`Layer`] logging the `tracing` events with the slog [`Logger`].
+///
+/// The module of the slog record is the module path of the event,
+/// so the [`LevelFilters`](super::LevelFilters) overrides apply to the `tracing` events as well.
+pub struct SlogLayer {
+    logger: Logger,
+}
+
+impl SlogLayer {
+    pub fn new(logger: Logger) -> Self {
+        Self { logger }
+    }
+}
+
+impl<S> Layer<S> for SlogLayer
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(fields);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(fields) = span.extensions_mut().get_mut::<Fields>() {
+                values.record(fields);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if let Some(span_fields) = span.extensions().get::<Fields>() {
+                    for (name, value) in span_fields.values.iter() {
+                        fields.insert(name, value.clone());
+                    }
+                }
+            }
+        }
+        event.record(&mut fields);
+
+        let metadata = event.metadata();
+        let location = RecordLocation {
+            file: metadata.file().unwrap_or_default(),
+            line: metadata.line().unwrap_or_default(),
+            column: 0,
+            function: "",
+            module: metadata.module_path().unwrap_or_else(|| metadata.target()),
+        };
+        let record_static = RecordStatic {
+            location: &location,
+            tag: "",
+            level: to_slog_level(*metadata.level()),
+        };
+        let message = fields.message.take().unwrap_or_default();
+
+        self.logger.log(&slog::Record::new(
+            &record_static,
+            &format_args!("{message}"),
+            BorrowedKV(&fields),
+        ));
+    }
+}
+
+fn to_slog_level(level: TracingLevel) -> Level {
+    match level {
+        TracingLevel::ERROR => Level::Error,
+        TracingLevel::WARN => Level::Warning,
+        TracingLevel::INFO => Level::Info,
+        TracingLevel::DEBUG => Level::Debug,
+        TracingLevel::TRACE => Level::Trace,
+    }
+}
+
+/// The recorded fields of a span or an event, the `message` of an event is kept separately.
+#[derive(Debug, Default)]
+struct Fields {
+    message: Option<String>,
+    values: Vec<(&'static str, String)>,
+}
+
+impl Fields {
+    /// Replaces the value of an already recorded field.
+    fn insert(&mut self, name: &'static str, value: String) {
+        match self
+            .values
+            .iter_mut()
+            .find(|(existing, _)| *existing == name)
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.values.push((name, value)),
+        }
+    }
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = Some(value.to_owned());
+        } else {
+            self.insert(field.name(), value.to_owned());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{value:?}"));
+        } else {
+            self.insert(field.name(), format!("{value:?}"));
+        }
+    }
+}
+
+impl KV for Fields {
+    fn serialize(
+        &self,
+        _record: &slog::Record<'_>,
+        serializer: &mut dyn Serializer,
+    ) -> slog::Result {
+        for (name, value) in self.values.iter() {
+            serializer.emit_str(name, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::Value;
+    use slog::{o, Drain};
+
+    use super::*;
+    use crate::util::logging::json_drain;
+
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Output {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().expect("Should lock").write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn events_are_logged_with_the_span_fields() {
+        let output = Output::default();
+        let drain = Mutex::new(json_drain("sentry", output.clone())).fuse();
+        let logger = Logger::root(drain, o!());
+
+        ::tracing::subscriber::with_default(subscriber(logger), || {
+            let request = ::tracing::info_span!(
+                "request",
+                route = "/v5/channel/list",
+                channel = ::tracing::field::Empty
+            );
+            let _entered = request.enter();
+            request.record("channel", "0x061d");
+
+            ::tracing::warn!(status = 404, "Channel {} not found", "0x061d");
+        });
+
+        let output = String::from_utf8(output.0.lock().expect("Should lock").clone())
+            .expect("Should be UTF-8");
+        let line: Value = serde_json::from_str(output.trim()).expect("Should be valid JSON");
+
+        assert_eq!("WARN", line["level"]);
+        assert_eq!("Channel 0x061d not found", line["msg"]);
+        assert_eq!("sentry", line["prefix"]);
+        assert_eq!("/v5/channel/list", line["route"]);
+        assert_eq!("0x061d", line["channel"]);
+        assert_eq!("404", line["status"]);
+    }
+}
@@ -38,6 +38,11 @@ impl ValidatorId {
     pub fn inner(&self) -> &[u8; 20] {
         self.0.as_bytes()
     }
+
+    /// Whether or not this is the zero address, see [`Address::is_zero()`].
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
 }
 
 impl ToETHChecksum for ValidatorId {}
@@ -134,6 +139,12 @@ impl ValidatorDesc {
     pub fn try_api_url(&self) -> Result<ApiUrl, ApiUrlError> {
         self.url.parse()
     }
+
+    /// The address which receives the validator fees,
+    /// i.e. the `fee_addr` if it's set, otherwise the validator `id` address.
+    pub fn fee_recipient(&self) -> Address {
+        self.fee_addr.unwrap_or_else(|| self.id.to_address())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -404,6 +415,16 @@ pub mod messages {
         pub fn message_type(&self) -> MessageType {
             self.into()
         }
+
+        /// The state root of the message, all message types have one.
+        pub fn state_root(&self) -> &str {
+            match self {
+                MessageTypes::ApproveState(approve_state) => &approve_state.state_root,
+                MessageTypes::NewState(new_state) => &new_state.state_root,
+                MessageTypes::RejectState(reject_state) => &reject_state.state_root,
+                MessageTypes::Heartbeat(heartbeat) => &heartbeat.state_root,
+            }
+        }
     }
 
     /// All available message type names.
@@ -544,6 +565,7 @@ mod postgres {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::test_util::{DUMMY_VALIDATOR_LEADER, GUARDIAN_2};
 
     #[test]
     fn validator_id_is_checksummed_when_serialized() {
@@ -555,4 +577,17 @@ mod test {
         let expected_json = format!(r#""{}""#, validator_id_checksum_str);
         assert_eq!(expected_json, actual_json);
     }
+
+    #[test]
+    fn test_fee_recipient() {
+        let validator = DUMMY_VALIDATOR_LEADER.clone();
+        assert_eq!(None, validator.fee_addr);
+        assert_eq!(validator.id.to_address(), validator.fee_recipient());
+
+        let with_fee_addr = ValidatorDesc {
+            fee_addr: Some(*GUARDIAN_2),
+            ..validator
+        };
+        assert_eq!(*GUARDIAN_2, with_fee_addr.fee_recipient());
+    }
 }
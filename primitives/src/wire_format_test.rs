@@ -0,0 +1,285 @@
+//! Snapshot tests of the JSON wire format of the API types used by the JS clients.
+//!
+//! Each fixture is serialized and compared to its golden file in `primitives/golden`
+//! and the golden file is deserialized back, so any change of the wire format
+//! fails here until the golden file is updated explicitly with:
+//!
+//! `UPDATE_GOLDEN_FILES=1 cargo test -p primitives wire_format`
+use std::{fs, path::PathBuf};
+
+use chrono::{TimeZone, Utc};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{
+    analytics::{query::Time, AnalyticsQuery, Metric, OperatingSystem, Timeframe},
+    balances::{CheckedState, UncheckedState},
+    sentry::{
+        campaign_create::CreateCampaign,
+        campaign_modify::ModifyCampaign,
+        validator_messages::{ValidatorMessage, ValidatorMessagesListResponse},
+        AccountingResponse, AllSpendersResponse, AnalyticsResponse, ClickId, DateHour, Event,
+        EventType, FetchedAnalytics, FetchedMetric, Pagination,
+    },
+    spender::Spender,
+    test_util::{
+        ADVERTISER, CREATOR, DUMMY_AD_UNITS, DUMMY_CAMPAIGN, DUMMY_IPFS, FOLLOWER, IDS, LEADER,
+        PUBLISHER, PUBLISHER_2,
+    },
+    unified_num::FromWhole,
+    validator::{ApproveState, Heartbeat, MessageTypes, NewState},
+    Balances, Campaign, ChainId, UnifiedNum,
+};
+
+const UPDATE_GOLDEN_FILES: &str = "UPDATE_GOLDEN_FILES";
+
+fn golden_file(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("golden")
+        .join(format!("{name}.json"))
+}
+
+/// Asserts that the fixture serializes to the golden file
+/// and that the golden file deserializes to the same wire format.
+fn assert_wire_format<T: Serialize + DeserializeOwned>(name: &str, fixture: &T) {
+    let path = golden_file(name);
+    let serialized = serde_json::to_value(fixture).expect("Should serialize fixture");
+
+    if std::env::var_os(UPDATE_GOLDEN_FILES).is_some() {
+        let json = serde_json::to_string_pretty(&serialized).expect("Should serialize json");
+        fs::write(&path, json + "\n").expect("Should write golden file");
+    }
+
+    let golden_json = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Should read golden file {}: {err}", path.display()));
+    let golden = serde_json::from_str::<Value>(&golden_json).expect("Should parse golden file");
+
+    pretty_assertions::assert_eq!(
+        golden,
+        serialized,
+        "Serialized {name} differs from the golden file, set {UPDATE_GOLDEN_FILES} if the change is intentional"
+    );
+
+    let deserialized = serde_json::from_value::<T>(golden.clone())
+        .unwrap_or_else(|err| panic!("Should deserialize golden file of {name}: {err}"));
+    pretty_assertions::assert_eq!(
+        golden,
+        serde_json::to_value(&deserialized).expect("Should serialize deserialized golden file"),
+        "Deserialized golden file of {name} is serialized differently"
+    );
+}
+
+fn campaign() -> Campaign {
+    Campaign {
+        ad_units: DUMMY_AD_UNITS.to_vec(),
+        ..DUMMY_CAMPAIGN.clone()
+    }
+}
+
+fn balances() -> Balances<CheckedState> {
+    let mut balances = Balances::<CheckedState>::new();
+    balances
+        .spend(*ADVERTISER, *PUBLISHER, UnifiedNum::from_whole(3))
+        .expect("Should not overflow");
+    balances
+        .spend(*CREATOR, *PUBLISHER_2, UnifiedNum::from_whole(1))
+        .expect("Should not overflow");
+
+    balances
+}
+
+#[test]
+fn campaign_wire_format() {
+    assert_wire_format("campaign", &campaign());
+}
+
+#[test]
+fn channel_wire_format() {
+    assert_wire_format("channel", &DUMMY_CAMPAIGN.channel);
+}
+
+#[test]
+fn create_campaign_wire_format() {
+    assert_wire_format(
+        "create_campaign",
+        &CreateCampaign::from_campaign(campaign()),
+    );
+}
+
+#[test]
+fn modify_campaign_wire_format() {
+    let modify_campaign = ModifyCampaign {
+        budget: Some(UnifiedNum::from_whole(2_000)),
+        title: Some("Modified Campaign".to_string()),
+        event_submission: None,
+        ..ModifyCampaign::from_campaign(campaign())
+    };
+
+    assert_wire_format("modify_campaign", &modify_campaign);
+}
+
+#[test]
+fn event_wire_format() {
+    let click_id = "2d5b4be43bdc4f12a5c6b1c7ac2d0d0e"
+        .parse::<ClickId>()
+        .expect("Should parse ClickId");
+    let events = vec![
+        Event::Impression {
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: Some("https://adex.network".to_string()),
+        },
+        Event::Click {
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: None,
+            click_id: None,
+        },
+        Event::Click {
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: None,
+            click_id: Some(click_id),
+        },
+        Event::Conversion {
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: None,
+            click_id,
+        },
+    ];
+
+    assert_wire_format("events", &events);
+}
+
+#[test]
+fn validator_messages_wire_format() {
+    let received = Utc.ymd(2022, 6, 1).and_hms(12, 0, 0);
+    let state_root = "b1a4fc6c1a1e1ab908a487e504006edcebea297f61b4b8ce6cad3b7e7a31eea8".to_string();
+
+    let response = ValidatorMessagesListResponse {
+        messages: vec![
+            ValidatorMessage {
+                from: IDS[&LEADER],
+                received,
+                msg: MessageTypes::NewState(NewState::<UncheckedState> {
+                    state_root: state_root.clone(),
+                    signature: "0x-leader-signature".to_string(),
+                    balances: balances().into_unchecked(),
+                }),
+            },
+            ValidatorMessage {
+                from: IDS[&FOLLOWER],
+                received,
+                msg: MessageTypes::ApproveState(ApproveState {
+                    state_root: state_root.clone(),
+                    signature: "0x-follower-signature".to_string(),
+                    is_healthy: true,
+                }),
+            },
+            ValidatorMessage {
+                from: IDS[&FOLLOWER],
+                received,
+                msg: MessageTypes::Heartbeat(Heartbeat {
+                    signature: "0x-follower-signature".to_string(),
+                    state_root,
+                    timestamp: received,
+                }),
+            },
+        ],
+        next_cursor: None,
+    };
+
+    assert_wire_format("validator_messages", &response);
+}
+
+#[test]
+fn accounting_response_wire_format() {
+    let response = AccountingResponse::<CheckedState> {
+        balances: balances(),
+        frozen: false,
+    };
+
+    assert_wire_format("accounting_response", &response);
+}
+
+#[test]
+fn all_spenders_response_wire_format() {
+    let response = AllSpendersResponse {
+        spenders: [
+            (
+                *ADVERTISER,
+                Spender {
+                    total_deposited: UnifiedNum::from_whole(10),
+                    total_spent: Some(UnifiedNum::from_whole(3)),
+                },
+            ),
+            (
+                *CREATOR,
+                Spender {
+                    total_deposited: UnifiedNum::from_whole(5),
+                    total_spent: None,
+                },
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        pagination: Pagination {
+            total_pages: 1,
+            page: 0,
+        },
+    };
+
+    assert_wire_format("all_spenders_response", &response);
+}
+
+#[test]
+fn analytics_query_wire_format() {
+    let query = AnalyticsQuery {
+        limit: 200,
+        event_type: EventType::Click,
+        metric: Metric::Paid,
+        segment_by: None,
+        time: Time {
+            timeframe: Timeframe::Week,
+            start: DateHour::from_ymdh(2022, 6, 1, 0),
+            end: Some(DateHour::from_ymdh(2022, 6, 8, 0)),
+        },
+        campaign_id: Some(DUMMY_CAMPAIGN.id),
+        ad_unit: Some(DUMMY_IPFS[0]),
+        ad_slot: None,
+        ad_slot_type: None,
+        advertiser: None,
+        publisher: Some(*PUBLISHER),
+        hostname: None,
+        country: Some("Bulgaria".to_string()),
+        os_name: Some(OperatingSystem::Linux),
+        chains: vec![ChainId::new(1337)],
+    };
+
+    assert_wire_format("analytics_query", &query);
+}
+
+#[test]
+fn analytics_response_wire_format() {
+    let response = AnalyticsResponse {
+        analytics: vec![
+            FetchedAnalytics {
+                time: Utc.ymd(2022, 6, 1).and_hms(0, 0, 0),
+                value: FetchedMetric::Count(42),
+                segment: None,
+            },
+            FetchedAnalytics {
+                time: Utc.ymd(2022, 6, 1).and_hms(1, 0, 0),
+                value: FetchedMetric::Paid(UnifiedNum::from_u64(1_500_000)),
+                segment: Some(DUMMY_CAMPAIGN.id.to_string()),
+            },
+        ],
+    };
+
+    assert_wire_format("analytics_response", &response);
+}
@@ -0,0 +1,2 @@
+-- Optimistic concurrency version of the Campaign, incremented on every update
+ALTER TABLE campaigns ADD COLUMN version bigint DEFAULT 0 NOT NULL;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE validator_fees_summaries DROP CONSTRAINT fk_validator_fees_summaries_channel_id;
+DROP TABLE validator_fees_summaries;
@@ -0,0 +1,11 @@
+CREATE TABLE validator_fees_summaries (
+    validator_id varchar(42) NOT NULL,
+    channel_id varchar(66) NOT NULL,
+    -- The start of the summary period
+    period timestamp(2) with time zone NOT NULL,
+    fees bigint NOT NULL,
+    delta bigint NOT NULL,
+    created timestamp(2) with time zone NOT NULL,
+    PRIMARY KEY (validator_id, channel_id, period),
+    CONSTRAINT fk_validator_fees_summaries_channel_id FOREIGN KEY (channel_id) REFERENCES channels (id) ON DELETE RESTRICT ON UPDATE RESTRICT
+);
@@ -0,0 +1,2 @@
+-- Pricing bounds overrides per AdUnit (IPFS) of the Campaign
+ALTER TABLE campaigns ADD COLUMN unit_pricing_overrides jsonb DEFAULT '{}' NOT NULL;
@@ -0,0 +1,2 @@
+-- The accepted event types of the Campaign, NULL accepts all event types
+ALTER TABLE campaigns ADD COLUMN accepted_event_types jsonb;
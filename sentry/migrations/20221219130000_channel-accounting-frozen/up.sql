@@ -0,0 +1,2 @@
+-- When frozen, no new Accounting spending is accepted for the Channel
+ALTER TABLE channels ADD COLUMN accounting_frozen boolean DEFAULT false NOT NULL;
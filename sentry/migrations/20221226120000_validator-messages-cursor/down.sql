@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX idx_validator_messages_channel_id_received_id;
+
+ALTER TABLE validator_messages DROP COLUMN id;
@@ -0,0 +1,4 @@
+-- A unique id of the message, used along with `received` for the cursor-based pagination
+ALTER TABLE validator_messages ADD COLUMN id bigserial NOT NULL;
+
+CREATE INDEX idx_validator_messages_channel_id_received_id ON validator_messages (channel_id, received, id);
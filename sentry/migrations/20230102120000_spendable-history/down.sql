@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX idx_spendable_history_channel_id_spender;
+
+ALTER TABLE spendable_history DROP CONSTRAINT fk_spendable_history_channel_id;
+DROP TABLE spendable_history;
@@ -0,0 +1,16 @@
+-- An append-only log of the changes of the `spendable` total deposits
+CREATE TABLE spendable_history (
+    id bigserial NOT NULL,
+    spender varchar(42) NOT NULL,
+    channel_id varchar(66) NOT NULL,
+    -- `NULL` when the spendable has been created
+    old_total bigint NULL,
+    new_total bigint NOT NULL,
+    -- What has changed the total, e.g. `campaign-create`
+    source varchar(32) NOT NULL,
+    created timestamp(2) with time zone NOT NULL,
+    PRIMARY KEY (id),
+    CONSTRAINT fk_spendable_history_channel_id FOREIGN KEY (channel_id) REFERENCES channels (id) ON DELETE RESTRICT ON UPDATE RESTRICT
+);
+
+CREATE INDEX idx_spendable_history_channel_id_spender ON spendable_history (channel_id, spender, id);
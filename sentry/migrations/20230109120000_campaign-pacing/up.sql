@@ -0,0 +1,2 @@
+-- The spend pacing of the Campaign, NULL for no daily cap
+ALTER TABLE campaigns ADD COLUMN pacing jsonb;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX idx_campaign_budget_changes_campaign_id;
+
+ALTER TABLE campaign_budget_changes DROP CONSTRAINT fk_campaign_budget_changes_campaign_id;
+DROP TABLE campaign_budget_changes;
@@ -0,0 +1,14 @@
+-- An append-only log of the changes of the Campaigns' budgets
+CREATE TABLE campaign_budget_changes (
+    id bigserial NOT NULL,
+    campaign_id varchar(34) NOT NULL,
+    old_budget bigint NOT NULL,
+    new_budget bigint NOT NULL,
+    -- The address which has changed the budget, e.g. the Campaign creator
+    actor varchar(42) NOT NULL,
+    created timestamp(2) with time zone NOT NULL,
+    PRIMARY KEY (id),
+    CONSTRAINT fk_campaign_budget_changes_campaign_id FOREIGN KEY (campaign_id) REFERENCES campaigns (id) ON DELETE RESTRICT ON UPDATE RESTRICT
+);
+
+CREATE INDEX idx_campaign_budget_changes_campaign_id ON campaign_budget_changes (campaign_id, id);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE validator_messages DROP COLUMN quarantine_released;
+ALTER TABLE validator_messages DROP COLUMN quarantined;
@@ -0,0 +1,5 @@
+-- Quarantined messages (e.g. a NewState with imbalanced balances) are excluded
+-- from the latest NewState & ApproveState queries until an admin releases them
+ALTER TABLE validator_messages ADD COLUMN quarantined boolean DEFAULT false NOT NULL;
+-- Released messages are not quarantined again
+ALTER TABLE validator_messages ADD COLUMN quarantine_released boolean DEFAULT false NOT NULL;
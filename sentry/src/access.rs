@@ -1,14 +1,15 @@
 use chrono::Utc;
 use futures::future::try_join_all;
-use redis::aio::MultiplexedConnection;
+use redis::{aio::MultiplexedConnection, RedisError};
 
-use crate::{Auth, Session};
+use crate::{db::RedisKeys, Auth, Session};
 use primitives::{
     event_submission::{RateLimit, Rule},
     sentry::Event,
-    Campaign,
+    util::referrer::Referrer,
+    Address, Campaign, UnifiedNum,
 };
-use std::cmp::PartialEq;
+use std::{cmp::PartialEq, collections::HashMap, time::Duration};
 use thiserror::Error;
 
 #[derive(Debug, PartialEq, Eq, Error)]
@@ -24,6 +25,7 @@ pub enum Error {
 // @TODO: Make pub(crate)
 pub async fn check_access(
     redis: &MultiplexedConnection,
+    redis_keys: &RedisKeys,
     session: &Session,
     auth: Option<&Auth>,
     rate_limit: &RateLimit,
@@ -75,83 +77,355 @@ pub async fn check_access(
         return Ok(());
     }
 
-    let apply_all_rules = try_join_all(
-        rules
-            .iter()
-            .map(|rule| apply_rule(redis.clone(), rule, events, campaign, &auth_uid, session)),
+    let campaign_limits = rules
+        .iter()
+        .filter_map(|rule| rule.rate_limit.as_ref())
+        .map(|limit| (limit, false));
+
+    // The global rate limit always applies alongside the Campaign's rules,
+    // so they can only make it stricter.
+    // Limits of the same type and timeframe share the same key,
+    // in which case the most restrictive one wins
+    let mut limits = HashMap::<String, &RateLimit>::new();
+    for (limit, is_global) in campaign_limits.chain([(rate_limit, true)]) {
+        let key = match limit_type(limit) {
+            Some(LimitType::Uid) => {
+                redis_keys.rate_limit(campaign.id, "uid", limit.time_frame, &auth_uid)
+            }
+            Some(LimitType::Ip) => {
+                // the IP rules of the Campaign allow a single event per submission
+                if !is_global && events.len() != 1 {
+                    return Err(Error::RulesError(
+                        "rateLimit: only allows 1 event".to_string(),
+                    ));
+                }
+
+                redis_keys.rate_limit(
+                    campaign.id,
+                    "ip",
+                    limit.time_frame,
+                    session.ip.as_deref().unwrap_or_default(),
+                )
+            }
+            // unknown limit types are ignored
+            None => continue,
+        };
+
+        limits
+            .entry(key)
+            .and_modify(|current| {
+                if limit.limit < current.limit {
+                    *current = limit
+                }
+            })
+            .or_insert(limit);
+    }
+
+    let apply_all_limits = try_join_all(
+        limits
+            .into_iter()
+            .map(|(key, limit)| apply_rate_limit(redis.clone(), key, limit)),
     );
 
-    apply_all_rules.await.map_err(Error::RulesError).map(|_| ())
+    apply_all_limits
+        .await
+        .map_err(Error::RulesError)
+        .map(|_| ())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum LimitType {
+    Ip,
+    Uid,
+}
+
+/// The `sid` limit type is kept for backwards compatibility and it's the same as `uid`.
+fn limit_type(rate_limit: &RateLimit) -> Option<LimitType> {
+    match rate_limit.limit_type.as_str() {
+        "ip" => Some(LimitType::Ip),
+        "uid" | "sid" => Some(LimitType::Uid),
+        _ => None,
+    }
 }
 
-async fn apply_rule(
+/// Counts the submission in the `key`, which expires at the end of the `timeframe`
+/// started by the first submission.
+async fn apply_rate_limit(
     mut redis: MultiplexedConnection,
-    rule: &Rule,
-    events: &[Event],
-    campaign: &Campaign,
-    uid: &str,
-    session: &Session,
+    key: String,
+    rate_limit: &RateLimit,
 ) -> Result<(), String> {
-    match &rule.rate_limit {
-        Some(rate_limit) => {
-            let key = if &rate_limit.limit_type == "sid" {
-                Ok(format!(
-                    "adexRateLimit:{}:{}",
-                    hex::encode(campaign.id),
-                    uid
-                ))
-            } else if &rate_limit.limit_type == "ip" {
-                if events.len() != 1 {
-                    Err("rateLimit: only allows 1 event".to_string())
-                } else {
-                    Ok(format!(
-                        "adexRateLimit:{}:{}",
-                        hex::encode(campaign.id),
-                        session.ip.as_ref().unwrap_or(&String::new())
-                    ))
-                }
-            } else {
-                // return for the whole function
-                return Ok(());
-            }?;
+    // the smallest expiration allowed by Redis is 1 millisecond
+    let time_frame_ms = rate_limit.time_frame.as_millis().max(1) as u64;
+
+    let (submissions,) = redis::pipe()
+        .atomic()
+        .cmd("SET")
+        .arg(&key)
+        .arg(0)
+        .arg("PX")
+        .arg(time_frame_ms)
+        .arg("NX")
+        .ignore()
+        .cmd("INCR")
+        .arg(&key)
+        .query_async::<_, (u32,)>(&mut redis)
+        .await
+        .map_err(|error| format!("{}", error))?;
+
+    if submissions > rate_limit.limit {
+        Err("rateLimit: too many requests".to_string())
+    } else {
+        Ok(())
+    }
+}
 
-            if redis::cmd("EXISTS")
-                .arg(&key)
-                .query_async::<_, i8>(&mut redis)
-                .await
-                .map(|exists| exists == 1)
-                .map_err(|error| format!("{}", error))?
-            {
-                return Err("rateLimit: too many requests".to_string());
-            }
+/// Drops the events which have already been submitted within the `window`
+/// for the same Campaign, publisher, AdUnit, AdSlot and event type,
+/// including the duplicates in the passed `events`.
+///
+/// Each unique event is kept in Redis with a TTL of `window`.
+/// The kept events which end up not being spent should be released with
+/// [`release_dedup_events`], so that they can be submitted again.
+pub async fn dedup_events(
+    redis: &MultiplexedConnection,
+    redis_keys: &RedisKeys,
+    window: Duration,
+    campaign: &Campaign,
+    events: Vec<Event>,
+) -> Result<Vec<Event>, RedisError> {
+    // the smallest expiration allowed by Redis is 1 millisecond
+    let window_ms = window.as_millis().max(1) as u64;
+    let mut deduped = Vec::with_capacity(events.len());
+
+    for event in events {
+        let key = event_dedup_key(redis_keys, campaign, &event);
+
+        // `SET NX` sets the key only if it does not exist, otherwise it returns `nil`
+        let is_new = redis::cmd("SET")
+            .arg(&key)
+            .arg("1")
+            .arg("PX")
+            .arg(window_ms)
+            .arg("NX")
+            .query_async::<_, Option<String>>(&mut redis.clone())
+            .await?
+            .is_some();
+
+        if is_new {
+            deduped.push(event);
+        }
+    }
+
+    Ok(deduped)
+}
+
+/// Removes the events kept by [`dedup_events`], e.g. when spending for them has failed.
+pub async fn release_dedup_events(
+    redis: &MultiplexedConnection,
+    redis_keys: &RedisKeys,
+    campaign: &Campaign,
+    events: &[Event],
+) -> Result<(), RedisError> {
+    if events.is_empty() {
+        return Ok(());
+    }
 
-            let seconds = rate_limit.time_frame.as_secs_f32().ceil();
-            redis::cmd("SETEX")
+    let keys = events
+        .iter()
+        .map(|event| event_dedup_key(redis_keys, campaign, event))
+        .collect::<Vec<_>>();
+
+    redis::cmd("DEL")
+        .arg(keys)
+        .query_async::<_, ()>(&mut redis.clone())
+        .await
+}
+
+fn event_dedup_key(redis_keys: &RedisKeys, campaign: &Campaign, event: &Event) -> String {
+    let (publisher, ad_unit, ad_slot) = match event {
+        Event::Impression {
+            publisher,
+            ad_unit,
+            ad_slot,
+            ..
+        }
+        | Event::Click {
+            publisher,
+            ad_unit,
+            ad_slot,
+            ..
+        }
+        | Event::Conversion {
+            publisher,
+            ad_unit,
+            ad_slot,
+            ..
+        }
+        | Event::Custom {
+            publisher,
+            ad_unit,
+            ad_slot,
+            ..
+        } => (publisher, ad_unit, ad_slot),
+    };
+
+    redis_keys.event_dedup(
+        campaign.id,
+        *publisher,
+        ad_unit,
+        ad_slot,
+        event.event_type(),
+    )
+}
+
+/// Keeps the paid [`Event::Click`]s which have a [`ClickId`](primitives::sentry::ClickId)
+/// in Redis with a TTL of the attribution `window`,
+/// so that the [`Event::Conversion`]s submitted later on can be attributed to them.
+///
+/// The window starts with the first paid click of a given click id.
+pub async fn store_attributable_clicks(
+    redis: &MultiplexedConnection,
+    redis_keys: &RedisKeys,
+    window: Duration,
+    campaign: &Campaign,
+    paid_events: &[(Event, Address, UnifiedNum)],
+) -> Result<(), RedisError> {
+    // the smallest expiration allowed by Redis is 1 millisecond
+    let window_ms = window.as_millis().max(1) as u64;
+
+    for (event, _earner, _payout) in paid_events {
+        if let Event::Click {
+            publisher,
+            ad_unit,
+            ad_slot,
+            click_id: Some(click_id),
+            ..
+        } = event
+        {
+            let key =
+                redis_keys.click_attribution(campaign.id, *publisher, ad_unit, ad_slot, click_id);
+
+            redis::cmd("SET")
                 .arg(&key)
-                .arg(seconds as i32)
                 .arg("1")
-                .query_async::<_, ()>(&mut redis)
-                .await
-                .map_err(|error| format!("{}", error))
+                .arg("PX")
+                .arg(window_ms)
+                .arg("NX")
+                .query_async::<_, Option<String>>(&mut redis.clone())
+                .await?;
         }
-        None => Ok(()),
     }
+
+    Ok(())
 }
 
-fn forbidden_referrer(session: &Session) -> bool {
-    match session
-        .referrer_header
-        .as_ref()
-        .and_then(|rf| rf.split('/').nth(2))
-    {
-        Some(hostname) => {
-            hostname == "localhost"
-                || hostname == "127.0.0.1"
-                || hostname.starts_with("localhost:")
-                || hostname.starts_with("127.0.0.1:")
+/// A click kept by [`store_attributable_clicks`] which has been claimed
+/// by an attributed [`Event::Conversion`], see [`attribute_conversions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributedClick {
+    conversion: Event,
+    key: String,
+    /// The remaining attribution window of the click in milliseconds
+    ttl_ms: u64,
+}
+
+/// Drops the [`Event::Conversion`]s which cannot be attributed to a click
+/// kept by [`store_attributable_clicks`], i.e. the click was not paid,
+/// it was of another publisher, AdUnit or AdSlot or the attribution window has passed.
+///
+/// The attributed clicks are claimed by removing them atomically from Redis,
+/// so at most one conversion, even of concurrent requests, is attributed to a click.
+/// The claimed clicks of the conversions which end up not being paid should be restored
+/// with [`restore_attributed_clicks`], so that they can be attributed again.
+/// Any other events are kept as they are.
+pub async fn attribute_conversions(
+    redis: &MultiplexedConnection,
+    redis_keys: &RedisKeys,
+    campaign: &Campaign,
+    events: Vec<Event>,
+) -> Result<(Vec<Event>, Vec<AttributedClick>), RedisError> {
+    let mut attributed = Vec::with_capacity(events.len());
+    let mut attributed_clicks = vec![];
+
+    for event in events {
+        if let Event::Conversion {
+            publisher,
+            ad_unit,
+            ad_slot,
+            click_id,
+            ..
+        } = &event
+        {
+            let key =
+                redis_keys.click_attribution(campaign.id, *publisher, ad_unit, ad_slot, click_id);
+
+            // `DEL` returns `0` for a click that was never paid, has expired
+            // or has already been claimed by another conversion
+            let (ttl_ms, removed) = redis::pipe()
+                .atomic()
+                .cmd("PTTL")
+                .arg(&key)
+                .cmd("DEL")
+                .arg(&key)
+                .query_async::<_, (i64, u8)>(&mut redis.clone())
+                .await?;
+
+            if removed != 1 {
+                continue;
+            }
+
+            attributed_clicks.push(AttributedClick {
+                conversion: event.clone(),
+                key,
+                // the smallest expiration allowed by Redis is 1 millisecond
+                ttl_ms: ttl_ms.max(1) as u64,
+            });
         }
-        None => false,
+
+        attributed.push(event);
     }
+
+    Ok((attributed, attributed_clicks))
+}
+
+/// Restores the clicks claimed by [`attribute_conversions`] whose [`Event::Conversion`]s
+/// are not in the `paid_events`, with their remaining attribution window.
+///
+/// When spending for the events has failed, no events have been paid.
+pub async fn restore_attributed_clicks(
+    redis: &MultiplexedConnection,
+    attributed_clicks: &[AttributedClick],
+    paid_events: &[(Event, Address, UnifiedNum)],
+) -> Result<(), RedisError> {
+    let unpaid_clicks = attributed_clicks.iter().filter(|attributed_click| {
+        !paid_events
+            .iter()
+            .any(|(event, _earner, _payout)| event == &attributed_click.conversion)
+    });
+
+    for attributed_click in unpaid_clicks {
+        redis::cmd("SET")
+            .arg(&attributed_click.key)
+            .arg("1")
+            .arg("PX")
+            .arg(attributed_click.ttl_ms)
+            .arg("NX")
+            .query_async::<_, Option<String>>(&mut redis.clone())
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Invalid referrers are treated as absent, i.e. not forbidden.
+pub(crate) fn forbidden_referrer(session: &Session) -> bool {
+    session
+        .referrer_header
+        .as_deref()
+        .and_then(Referrer::parse)
+        .map(|referrer| referrer.is_loopback())
+        .unwrap_or(false)
 }
 
 fn forbidden_country(session: &Session) -> bool {
@@ -165,13 +439,14 @@ fn forbidden_country(session: &Session) -> bool {
 mod test {
     use std::time::Duration;
 
+    use adapter::primitives::Scope;
     use chrono::TimeZone;
     use primitives::{
         config::GANACHE_CONFIG,
         event_submission::{RateLimit, Rule},
-        sentry::Event,
+        sentry::{ClickId, Event},
         test_util::{DUMMY_CAMPAIGN, DUMMY_IPFS, FOLLOWER, IDS, PUBLISHER_2},
-        Config, EventSubmission,
+        CampaignId, Config, EventSubmission,
     };
 
     use deadpool::managed::Object;
@@ -195,6 +470,7 @@ mod test {
 
         campaign.event_submission = Some(EventSubmission {
             allow: vec![with_rule],
+            dedup_window: None,
         });
 
         campaign
@@ -220,6 +496,7 @@ mod test {
             rate_limit: Some(RateLimit {
                 limit_type: "sid".to_string(),
                 time_frame: Duration::from_millis(20_000),
+                limit: 1,
             }),
         };
         let campaign = get_campaign(rule);
@@ -232,6 +509,7 @@ mod test {
             era: 0,
             uid: IDS[&FOLLOWER],
             chain: chain_context.chain.clone(),
+            scope: Scope::Full,
         };
 
         let session = Session {
@@ -245,6 +523,7 @@ mod test {
 
         let response = check_access(
             &database,
+            &RedisKeys::default(),
             &session,
             Some(&auth),
             &config.limits.ip_rate_limit,
@@ -256,6 +535,7 @@ mod test {
 
         let err_response = check_access(
             &database,
+            &RedisKeys::default(),
             &session,
             Some(&auth),
             &config.limits.ip_rate_limit,
@@ -271,6 +551,145 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn campaign_uid_rate_limit_is_applied_with_the_global_one() {
+        let (config, database) = setup().await;
+
+        let global_limit = RateLimit {
+            limit_type: "ip".to_string(),
+            time_frame: Duration::from_millis(60_000),
+            limit: 10,
+        };
+
+        let chain_context = config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Campaign's Channel.token should be set in config");
+
+        let auth = Auth {
+            era: 0,
+            uid: IDS[&FOLLOWER],
+            chain: chain_context.chain.clone(),
+            scope: Scope::Full,
+        };
+
+        let session = Session {
+            ip: Some("127.0.0.1".to_string()),
+            referrer_header: None,
+            country: None,
+            os: None,
+        };
+
+        // the global limit alone allows both events
+        {
+            let mut campaign = DUMMY_CAMPAIGN.clone();
+            campaign.id = CampaignId::new();
+            campaign.event_submission = None;
+
+            for _ in 0..2 {
+                let response = check_access(
+                    &database,
+                    &RedisKeys::default(),
+                    &session,
+                    Some(&auth),
+                    &global_limit,
+                    &campaign,
+                    &get_impression_events(1),
+                )
+                .await;
+                assert_eq!(Ok(()), response);
+            }
+        }
+
+        // while the stricter uid limit of the Campaign rejects the second one
+        {
+            let rule = Rule {
+                uids: None,
+                rate_limit: Some(RateLimit {
+                    limit_type: "uid".to_string(),
+                    time_frame: Duration::from_millis(60_000),
+                    limit: 1,
+                }),
+            };
+            let campaign = get_campaign(rule);
+
+            let response = check_access(
+                &database,
+                &RedisKeys::default(),
+                &session,
+                Some(&auth),
+                &global_limit,
+                &campaign,
+                &get_impression_events(1),
+            )
+            .await;
+            assert_eq!(Ok(()), response);
+
+            let err_response = check_access(
+                &database,
+                &RedisKeys::default(),
+                &session,
+                Some(&auth),
+                &global_limit,
+                &campaign,
+                &get_impression_events(1),
+            )
+            .await;
+            assert_eq!(
+                Err(Error::RulesError(
+                    "rateLimit: too many requests".to_string()
+                )),
+                err_response
+            );
+        }
+
+        // a looser ip limit of the Campaign can't override the global one
+        {
+            let rule = Rule {
+                uids: None,
+                rate_limit: Some(RateLimit {
+                    limit_type: "ip".to_string(),
+                    time_frame: Duration::from_millis(60_000),
+                    limit: 100,
+                }),
+            };
+            let mut campaign = get_campaign(rule);
+            campaign.id = CampaignId::new();
+            let strict_global_limit = RateLimit {
+                limit: 1,
+                ..global_limit.clone()
+            };
+
+            let response = check_access(
+                &database,
+                &RedisKeys::default(),
+                &session,
+                Some(&auth),
+                &strict_global_limit,
+                &campaign,
+                &get_impression_events(1),
+            )
+            .await;
+            assert_eq!(Ok(()), response);
+
+            let err_response = check_access(
+                &database,
+                &RedisKeys::default(),
+                &session,
+                Some(&auth),
+                &strict_global_limit,
+                &campaign,
+                &get_impression_events(1),
+            )
+            .await;
+            assert_eq!(
+                Err(Error::RulesError(
+                    "rateLimit: too many requests".to_string()
+                )),
+                err_response
+            );
+        }
+    }
+
     #[tokio::test]
     async fn ip_rate_limit() {
         let (config, database) = setup().await;
@@ -280,6 +699,7 @@ mod test {
             rate_limit: Some(RateLimit {
                 limit_type: "ip".to_string(),
                 time_frame: Duration::from_millis(1),
+                limit: 1,
             }),
         };
 
@@ -293,6 +713,7 @@ mod test {
             era: 0,
             uid: IDS[&FOLLOWER],
             chain: chain_context.chain.clone(),
+            scope: Scope::Full,
         };
 
         let session = Session {
@@ -304,6 +725,7 @@ mod test {
 
         let err_response = check_access(
             &database,
+            &RedisKeys::default(),
             &session,
             Some(&auth),
             &config.limits.ip_rate_limit,
@@ -321,6 +743,7 @@ mod test {
 
         let response = check_access(
             &database,
+            &RedisKeys::default(),
             &session,
             Some(&auth),
             &config.limits.ip_rate_limit,
@@ -340,6 +763,7 @@ mod test {
             rate_limit: Some(RateLimit {
                 limit_type: "ip".to_string(),
                 time_frame: Duration::from_millis(1),
+                limit: 1,
             }),
         };
         let mut campaign = get_campaign(rule);
@@ -353,6 +777,7 @@ mod test {
             era: 0,
             uid: IDS[&FOLLOWER],
             chain: chain_context.chain.clone(),
+            scope: Scope::Full,
         };
 
         let session = Session {
@@ -364,6 +789,7 @@ mod test {
 
         let err_response = check_access(
             &database,
+            &RedisKeys::default(),
             &session,
             Some(&auth),
             &config.limits.ip_rate_limit,
@@ -384,6 +810,7 @@ mod test {
             rate_limit: Some(RateLimit {
                 limit_type: "ip".to_string(),
                 time_frame: Duration::from_millis(1),
+                limit: 1,
             }),
         };
         let campaign = get_campaign(rule);
@@ -396,6 +823,7 @@ mod test {
             era: 0,
             uid: IDS[&FOLLOWER],
             chain: chain_context.chain.clone(),
+            scope: Scope::Full,
         };
 
         let session = Session {
@@ -407,6 +835,7 @@ mod test {
 
         let err_response = check_access(
             &database,
+            &RedisKeys::default(),
             &session,
             Some(&auth),
             &config.limits.ip_rate_limit,
@@ -427,6 +856,7 @@ mod test {
             rate_limit: Some(RateLimit {
                 limit_type: "ip".to_string(),
                 time_frame: Duration::from_millis(1),
+                limit: 1,
             }),
         };
         let campaign = get_campaign(rule);
@@ -439,6 +869,7 @@ mod test {
             era: 0,
             uid: IDS[&FOLLOWER],
             chain: chain_context.chain.clone(),
+            scope: Scope::Full,
         };
 
         let session = Session {
@@ -450,6 +881,7 @@ mod test {
 
         let err_response = check_access(
             &database,
+            &RedisKeys::default(),
             &session,
             Some(&auth),
             &config.limits.ip_rate_limit,
@@ -479,6 +911,7 @@ mod test {
             era: 0,
             uid: IDS[&FOLLOWER],
             chain: chain_context.chain.clone(),
+            scope: Scope::Full,
         };
 
         let session = Session {
@@ -490,6 +923,7 @@ mod test {
 
         let ok_response = check_access(
             &database,
+            &RedisKeys::default(),
             &session,
             Some(&auth),
             &config.limits.ip_rate_limit,
@@ -510,6 +944,7 @@ mod test {
             rate_limit: Some(RateLimit {
                 limit_type: "ip".to_string(),
                 time_frame: Duration::from_millis(60_000),
+                limit: 1,
             }),
         };
         let campaign = get_campaign(rule);
@@ -522,6 +957,7 @@ mod test {
             era: 0,
             uid: IDS[&FOLLOWER],
             chain: chain_context.chain.clone(),
+            scope: Scope::Full,
         };
 
         let session = Session {
@@ -533,6 +969,7 @@ mod test {
 
         let ok_response = check_access(
             &database,
+            &RedisKeys::default(),
             &session,
             Some(&auth),
             &config.limits.ip_rate_limit,
@@ -542,7 +979,7 @@ mod test {
         .await;
 
         assert_eq!(Ok(()), ok_response);
-        let key = "adexRateLimit:936da01f9abd4d9d80c702af85c822a8:".to_string();
+        let key = "adexRateLimit:936da01f9abd4d9d80c702af85c822a8:ip:60000:".to_string();
         let value = "1".to_string();
 
         let value_in_redis = redis::cmd("GET")
@@ -553,4 +990,184 @@ mod test {
             .expect("should exist in redis");
         assert_eq!(&value, &value_in_redis);
     }
+
+    #[tokio::test]
+    async fn conversion_attribution() {
+        let (_config, database) = setup().await;
+        let redis_keys = RedisKeys::default();
+        let campaign = DUMMY_CAMPAIGN.clone();
+
+        let click = |click_id| Event::Click {
+            publisher: *PUBLISHER_2,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: None,
+            click_id: Some(click_id),
+        };
+        let conversion = |click_id, ad_slot| Event::Conversion {
+            publisher: *PUBLISHER_2,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot,
+            referrer: None,
+            click_id,
+        };
+        let paid = |event: Event| (event, *PUBLISHER_2, UnifiedNum::from(100));
+
+        // in window
+        {
+            let click_id = ClickId::new();
+            store_attributable_clicks(
+                &database,
+                &redis_keys,
+                Duration::from_secs(60),
+                &campaign,
+                &[paid(click(click_id))],
+            )
+            .await
+            .expect("Should store the click");
+
+            let events = vec![
+                // of another AdSlot
+                conversion(click_id, DUMMY_IPFS[2]),
+                conversion(click_id, DUMMY_IPFS[1]),
+                // a second conversion of the same click
+                conversion(click_id, DUMMY_IPFS[1]),
+                // of a click that was never paid
+                conversion(ClickId::new(), DUMMY_IPFS[1]),
+                // other events are kept
+                get_impression_events(1).remove(0),
+            ];
+
+            let (attributed, attributed_clicks) =
+                attribute_conversions(&database, &redis_keys, &campaign, events)
+                    .await
+                    .expect("Should attribute the conversions");
+
+            assert_eq!(
+                vec![
+                    conversion(click_id, DUMMY_IPFS[1]),
+                    get_impression_events(1).remove(0)
+                ],
+                attributed
+            );
+
+            // the click is claimed by the attributed conversion
+            let (attributed, _) = attribute_conversions(
+                &database,
+                &redis_keys,
+                &campaign,
+                vec![conversion(click_id, DUMMY_IPFS[1])],
+            )
+            .await
+            .expect("Should attribute the conversions");
+            assert!(
+                attributed.is_empty(),
+                "A claimed click should not be attributed again"
+            );
+
+            // the conversion has not been paid
+            restore_attributed_clicks(&database, &attributed_clicks, &[])
+                .await
+                .expect("Should restore the attributed clicks");
+
+            let (attributed, attributed_clicks) = attribute_conversions(
+                &database,
+                &redis_keys,
+                &campaign,
+                vec![conversion(click_id, DUMMY_IPFS[1])],
+            )
+            .await
+            .expect("Should attribute the conversions");
+            assert_eq!(vec![conversion(click_id, DUMMY_IPFS[1])], attributed);
+
+            // the conversion has been paid
+            restore_attributed_clicks(
+                &database,
+                &attributed_clicks,
+                &[paid(conversion(click_id, DUMMY_IPFS[1]))],
+            )
+            .await
+            .expect("Should restore the attributed clicks");
+
+            let (attributed, _) = attribute_conversions(
+                &database,
+                &redis_keys,
+                &campaign,
+                vec![conversion(click_id, DUMMY_IPFS[1])],
+            )
+            .await
+            .expect("Should attribute the conversions");
+            assert!(
+                attributed.is_empty(),
+                "A second paid conversion of the same click should be dropped"
+            );
+        }
+
+        // concurrent conversions of the same click
+        {
+            let click_id = ClickId::new();
+            store_attributable_clicks(
+                &database,
+                &redis_keys,
+                Duration::from_secs(60),
+                &campaign,
+                &[paid(click(click_id))],
+            )
+            .await
+            .expect("Should store the click");
+
+            let (first, second) = tokio::join!(
+                attribute_conversions(
+                    &database,
+                    &redis_keys,
+                    &campaign,
+                    vec![conversion(click_id, DUMMY_IPFS[1])],
+                ),
+                attribute_conversions(
+                    &database,
+                    &redis_keys,
+                    &campaign,
+                    vec![conversion(click_id, DUMMY_IPFS[1])],
+                )
+            );
+            let (first, _) = first.expect("Should attribute the conversions");
+            let (second, _) = second.expect("Should attribute the conversions");
+
+            assert_eq!(
+                1,
+                first.len() + second.len(),
+                "Only one of the conversions should be attributed to the click"
+            );
+        }
+
+        // out of window
+        {
+            let click_id = ClickId::new();
+            store_attributable_clicks(
+                &database,
+                &redis_keys,
+                Duration::from_millis(20),
+                &campaign,
+                &[paid(click(click_id))],
+            )
+            .await
+            .expect("Should store the click");
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let (attributed, _) = attribute_conversions(
+                &database,
+                &redis_keys,
+                &campaign,
+                vec![conversion(click_id, DUMMY_IPFS[1])],
+            )
+            .await
+            .expect("Should attribute the conversions");
+
+            assert!(
+                attributed.is_empty(),
+                "A conversion after the attribution window should be dropped"
+            );
+        }
+    }
 }
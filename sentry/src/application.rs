@@ -1,34 +1,50 @@
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use axum::{
     extract::{FromRequest, RequestParts},
-    http::{Method, StatusCode},
-    middleware,
+    http::{Method, Request, StatusCode},
+    middleware::{self, Next},
+    response::Response,
     routing::get,
     Extension, Router,
 };
 use axum_server::{tls_rustls::RustlsConfig, Handle};
+use chrono::Utc;
 use once_cell::sync::Lazy;
 use redis::{aio::MultiplexedConnection, ConnectionInfo};
 use serde::{Deserialize, Deserializer};
 use slog::{error, info, Logger};
 use tower::ServiceBuilder;
-use tower_http::cors::CorsLayer;
+use tower_http::{
+    compression::{predicate::SizeAbove, CompressionLayer},
+    cors::CorsLayer,
+};
 
-use adapter::{client::Locked, Adapter};
+use adapter::{
+    client::{Locked, Unlocked},
+    primitives::Scope,
+    Adapter,
+};
 use primitives::{config::Environment, ValidatorId};
 
 use crate::{
-    db::{CampaignRemaining, DbPool},
-    middleware::auth::authenticate,
+    db::{spendable::delete_empty_spendables, CampaignRemaining, DbPool, RedisKeys},
+    middleware::{
+        auth::authenticate,
+        geo::{resolve_country, GeoResolver, NoopGeoResolver},
+    },
     platform::PlatformApi,
     routes::{
-        get_cfg,
-        routers::{analytics_router, campaigns_router, channels_router, units_for_slot_router},
+        get_cfg, get_health, get_whoami,
+        routers::{
+            analytics_router, campaigns_router, channels_router, units_for_slot_router,
+            validators_router,
+        },
     },
 };
 
@@ -64,9 +80,21 @@ pub struct EnvConfig {
     /// Defaults to locally running Redis server: [`DEFAULT_REDIS_URL`]
     #[serde(deserialize_with = "redis_url", default = "default_redis_url")]
     pub redis_url: ConnectionInfo,
+    /// The prefix of all the Redis keys, which allows multiple Sentry instances
+    /// to share the same Redis database, e.g. `staging` and `dev`.
+    ///
+    /// Defaults to no prefix.
+    #[serde(default)]
+    pub redis_key_prefix: Option<String>,
     /// Whether or not to seed the database in [`Environment::Development`].
     #[serde(default)]
     pub seed_db: bool,
+    /// The path to a MaxMind GeoIP2 or GeoLite2 Country database (`.mmdb`)
+    /// used for resolving the country of the requests.
+    ///
+    /// Requires the `geoip` feature.
+    #[serde(default)]
+    pub geoip_database: Option<PathBuf>,
 }
 
 impl EnvConfig {
@@ -104,9 +132,35 @@ pub struct Application<C: Locked + 'static> {
     pub config: primitives::Config,
     pub logger: Logger,
     pub redis: MultiplexedConnection,
+    /// All Redis keys should be built with it, in order to apply the configured prefix.
+    ///
+    /// The same [`RedisKeys`] as the [`CampaignRemaining`] ones.
+    pub redis_keys: RedisKeys,
     pub pool: DbPool,
     pub campaign_remaining: CampaignRemaining,
     pub platform_api: PlatformApi,
+    /// The HTTP client shared by the requests to the other validators' Sentries,
+    /// e.g. the closed Campaign notifications, so their connections are pooled.
+    pub http_client: reqwest::Client,
+    /// Resolves the [`Session.country`](Session::country) from the request IP.
+    ///
+    /// default: [`NoopGeoResolver`]
+    pub geo_resolver: Arc<dyn GeoResolver>,
+    /// Signs the challenges of the `GET /v5/whoami` route, it should have the same
+    /// [`Locked::whoami()`] as the [`Application::adapter`].
+    ///
+    /// default: `None` - the route responds with `404 Not Found`
+    pub whoami_signer: Option<Arc<dyn Unlocked<Error = adapter::Error>>>,
+    /// Builds the read-only `/v5` routes of a shadow [`Application`] mounted under `/v5-shadow`,
+    /// see [`Application::with_shadow()`].
+    ///
+    /// default: `None` - no `/v5-shadow` routes
+    pub shadow_routes: Option<Arc<dyn Fn() -> Router + Send + Sync>>,
+    /// Whether this is the shadow [`Application`] of the `/v5-shadow` routes,
+    /// whose routes never write to the DB, see [`Application::with_shadow()`].
+    ///
+    /// default: `false`
+    pub read_only: bool,
 }
 
 impl<C> Application<C>
@@ -127,12 +181,94 @@ where
             config,
             logger,
             redis,
+            redis_keys: campaign_remaining.redis_keys().clone(),
             pool,
             campaign_remaining,
             platform_api,
+            http_client: reqwest::Client::new(),
+            geo_resolver: Arc::new(NoopGeoResolver),
+            whoami_signer: None,
+            shadow_routes: None,
+            read_only: false,
+        }
+    }
+
+    /// Sets the [`GeoResolver`] used for resolving the [`Session.country`](Session::country).
+    pub fn with_geo_resolver(self, geo_resolver: Arc<dyn GeoResolver>) -> Self {
+        Self {
+            geo_resolver,
+            ..self
         }
     }
 
+    /// Sets the [`Unlocked`] signer of the `GET /v5/whoami` challenges,
+    /// usually the unlocked [`Application::adapter`].
+    pub fn with_whoami_signer(
+        self,
+        whoami_signer: Arc<dyn Unlocked<Error = adapter::Error>>,
+    ) -> Self {
+        Self {
+            whoami_signer: Some(whoami_signer),
+            ..self
+        }
+    }
+
+    /// Mounts the `/v5` routes of the `shadow` [`Application`] under `/v5-shadow`.
+    ///
+    /// Used during adapter migrations for comparing the responses of two adapters
+    /// with a single Sentry, the `shadow` should use the same [`DbPool`] & Redis
+    /// and a different adapter, which is used for the authentication, deposits, etc.
+    /// of the `/v5-shadow` routes.
+    ///
+    /// The shadow routes are read-only, any request which is not a `GET` or `HEAD`
+    /// is rejected with `405 Method Not Allowed` and the `GET` routes don't write to the DB,
+    /// e.g. a missing spendable is computed from the shadow adapter without saving it.
+    /// The Redis keys of the shadow (e.g. the cached adapter sessions) are kept under
+    /// an additional `shadow` prefix, the Campaigns' remaining are shared.
+    pub fn with_shadow<S: Locked + 'static>(self, shadow: Application<S>) -> Self {
+        let redis_keys = if shadow.redis_keys.prefix().is_empty() {
+            RedisKeys::new("shadow")
+        } else {
+            RedisKeys::new(format!("{}:shadow", shadow.redis_keys.prefix()))
+        };
+        let shadow = Application {
+            redis_keys,
+            shadow_routes: None,
+            read_only: true,
+            ..shadow
+        };
+
+        let shadow = Arc::new(shadow);
+        let shadow_routes = move || {
+            Application::<S>::v5_routes()
+                .layer(middleware::from_fn(read_only))
+                .layer(Extension(shadow.clone()))
+        };
+
+        Self {
+            shadow_routes: Some(Arc::new(shadow_routes)),
+            ..self
+        }
+    }
+
+    /// The `/v5` routes with the authentication & country resolution middlewares,
+    /// the [`Application`] should be added as an [`Extension`] by the caller.
+    fn v5_routes() -> Router {
+        Router::new()
+            .nest("/channel", channels_router::<C>())
+            .nest("/campaign", campaigns_router::<C>())
+            .nest("/analytics", analytics_router::<C>())
+            .nest("/units-for-slot", units_for_slot_router::<C>())
+            .nest("/validator", validators_router::<C>())
+            .route("/whoami", get(get_whoami::<C>))
+            .layer(
+                // keeps the order from top to bottom!
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(authenticate::<C, _>))
+                    .layer(middleware::from_fn(resolve_country::<C, _>)),
+            )
+    }
+
     pub async fn routing(&self) -> Router {
         let cors = CorsLayer::new()
             // "GET,HEAD,PUT,PATCH,POST,DELETE"
@@ -148,22 +284,35 @@ where
             // "*"
             .allow_origin(tower_http::cors::Any);
 
-        let router = Router::new()
-            .nest("/channel", channels_router::<C>())
-            .nest("/campaign", campaigns_router::<C>())
-            .nest("/analytics", analytics_router::<C>())
-            .nest("/units-for-slot", units_for_slot_router::<C>());
-
-        Router::new()
-            .nest("/v5", router)
+        let mut router = Router::new()
+            .nest("/v5", Self::v5_routes())
             .route("/cfg", get(get_cfg::<C>))
-            .layer(
-                // keeps the order from top to bottom!
-                ServiceBuilder::new()
-                    .layer(cors)
-                    .layer(middleware::from_fn(authenticate::<C, _>)),
-            )
-            .layer(Extension(Arc::new(self.clone())))
+            .route("/health", get(get_health));
+
+        if let Some(shadow_routes) = &self.shadow_routes {
+            router = router.nest("/v5-shadow", shadow_routes());
+        }
+
+        if let Some(min_size) = self.config.sentry.response_compression_min_size {
+            router = router.layer(CompressionLayer::new().compress_when(SizeAbove::new(min_size)));
+        }
+
+        #[cfg(feature = "tracing")]
+        {
+            router = router.layer(middleware::from_fn(crate::middleware::trace::trace_request));
+        }
+
+        router.layer(cors).layer(Extension(Arc::new(self.clone())))
+    }
+}
+
+/// Rejects the requests which can mutate data, i.e. anything other than `GET` & `HEAD`,
+/// with `405 Method Not Allowed`.
+async fn read_only<B>(request: Request<B>, next: Next<B>) -> Result<Response, StatusCode> {
+    if request.method() == Method::GET || request.method() == Method::HEAD {
+        Ok(next.run(request).await)
+    } else {
+        Err(StatusCode::METHOD_NOT_ALLOWED)
     }
 }
 
@@ -183,11 +332,18 @@ impl<C: Locked + 'static> Application<C> {
         // Spawn a task to shutdown server.
         tokio::spawn(shutdown_signal(logger.clone(), handle.clone()));
 
+        // Spawn a task to remove the spendables without a deposit.
+        tokio::spawn(clean_empty_spendables(
+            logger.clone(),
+            self.pool.clone(),
+            self.config.sentry.empty_spendable_max_age,
+        ));
+
         match enable_tls {
             EnableTls::NoTls(socket_addr) => {
                 let server = axum_server::bind(socket_addr)
                     .handle(handle)
-                    .serve(router.into_make_service());
+                    .serve(router.into_make_service_with_connect_info::<SocketAddr>());
 
                 tokio::pin!(server);
 
@@ -203,7 +359,7 @@ impl<C: Locked + 'static> Application<C> {
             } => {
                 let server = axum_server::bind_rustls(socket_addr, config)
                     .handle(handle)
-                    .serve(router.into_make_service());
+                    .serve(router.into_make_service_with_connect_info::<SocketAddr>());
 
                 tokio::pin!(server);
 
@@ -223,9 +379,15 @@ impl<C: Locked> Clone for Application<C> {
             config: self.config.clone(),
             logger: self.logger.clone(),
             redis: self.redis.clone(),
+            redis_keys: self.redis_keys.clone(),
             pool: self.pool.clone(),
             campaign_remaining: self.campaign_remaining.clone(),
             platform_api: self.platform_api.clone(),
+            http_client: self.http_client.clone(),
+            geo_resolver: self.geo_resolver.clone(),
+            whoami_signer: self.whoami_signer.clone(),
+            shadow_routes: self.shadow_routes.clone(),
+            read_only: self.read_only,
         }
     }
 }
@@ -274,6 +436,10 @@ pub struct Auth {
     pub uid: ValidatorId,
     /// The Chain for which this authentication was validated
     pub chain: primitives::Chain,
+    /// The scope of the authentication token.
+    ///
+    /// [`Scope::ReadOnly`] tokens are rejected by all routes creating or modifying data.
+    pub scope: Scope,
 }
 
 /// A query string deserialized using `serde_qs` instead of axum's `serde_urlencoded`
@@ -310,6 +476,40 @@ async fn shutdown_signal(logger: Logger, handle: Handle) {
     info!(&logger, "Received Ctrl+C signal. Shutting down..")
 }
 
+/// Removes the [`Spendable`](primitives::spender::Spendable)s with a zero deposit
+/// older than `max_age`, once every `max_age`.
+///
+/// A zero `max_age` disables the cleanup.
+async fn clean_empty_spendables(logger: Logger, pool: DbPool, max_age: Duration) {
+    if max_age.is_zero() {
+        info!(&logger, "Empty spendables cleanup is disabled"; "main" => "clean_empty_spendables");
+        return;
+    }
+
+    let mut interval = tokio::time::interval(max_age);
+
+    loop {
+        interval.tick().await;
+
+        let older_than = match chrono::Duration::from_std(max_age) {
+            Ok(max_age) => Utc::now() - max_age,
+            Err(err) => {
+                error!(&logger, "Invalid empty spendable max age: {}", err; "main" => "clean_empty_spendables");
+                return;
+            }
+        };
+
+        match delete_empty_spendables(&pool, older_than).await {
+            Ok(deleted) => {
+                info!(&logger, "Removed {} empty spendables", deleted; "main" => "clean_empty_spendables")
+            }
+            Err(err) => {
+                error!(&logger, "Failed to remove empty spendables: {}", err; "main" => "clean_empty_spendables")
+            }
+        }
+    }
+}
+
 pub mod seed {
     use std::sync::Arc;
 
@@ -320,6 +520,7 @@ pub mod seed {
             test_util::{Erc20Token, Outpace},
             ChainTransport,
         },
+        primitives::Scope,
         Dummy, Ethereum,
     };
     use primitives::{
@@ -361,6 +562,7 @@ pub mod seed {
                 era: 0,
                 uid: ValidatorId::from(campaign_to_create.creator),
                 chain: campaign.chain.clone(),
+                scope: Scope::Full,
             };
             create_campaign(
                 Json(campaign_to_create),
@@ -382,6 +584,7 @@ pub mod seed {
                 era: 0,
                 uid: ValidatorId::from(campaign.context.creator),
                 chain: campaign.chain.clone(),
+                scope: Scope::Full,
             };
 
             let request = ChannelDummyDeposit {
@@ -484,6 +687,7 @@ pub mod seed {
                 era: 0,
                 uid: ValidatorId::from(campaign.context.creator),
                 chain: campaign.chain.clone(),
+                scope: Scope::Full,
             };
 
             let spendable = Spendable {
@@ -520,8 +724,34 @@ pub mod seed {
 
 #[cfg(test)]
 mod test {
+    use axum::{
+        body::Body,
+        http::{
+            header::{ACCEPT_ENCODING, AUTHORIZATION, CONTENT_ENCODING},
+            Request,
+        },
+    };
     use serde_json::json;
 
+    use adapter::dummy::{Dummy, HeaderToken, Options};
+    use primitives::{
+        balances::{Balances, CheckedState},
+        sentry::{
+            campaign_list::CampaignListResponse, whoami::WhoamiResponse, AccountingResponse,
+            SpenderResponse,
+        },
+        test_util::{CREATOR, DUMMY_AUTH, DUMMY_CAMPAIGN, FOLLOWER, IDS, LEADER},
+        unified_num::FromWhole,
+        Address, Deposit, UnifiedNum,
+    };
+
+    use crate::{
+        db::{
+            accounting::spend_amount, insert_campaign, insert_channel, spendable::fetch_spendable,
+        },
+        test_util::{body_to, oneshot, setup_dummy_app},
+    };
+
     use super::*;
 
     #[test]
@@ -534,4 +764,223 @@ mod test {
         assert_eq!(Environment::Development, development);
         assert_eq!(Environment::Production, production);
     }
+
+    #[tokio::test]
+    async fn shadow_routes_use_the_shadow_adapter() {
+        let app_guard = setup_dummy_app().await;
+        let leader_signer = app_guard
+            .app
+            .adapter
+            .clone()
+            .unlock()
+            .expect("Should unlock the Dummy adapter");
+        let app = app_guard
+            .app
+            .clone()
+            .with_whoami_signer(Arc::new(leader_signer));
+
+        let shadow_adapter = Adapter::new(Dummy::init(Options {
+            dummy_identity: IDS[&FOLLOWER],
+            dummy_auth_tokens: DUMMY_AUTH.clone(),
+            dummy_chains: app.config.chains.values().cloned().collect(),
+            failure_injection: None,
+        }));
+        let follower_signer = shadow_adapter
+            .clone()
+            .unlock()
+            .expect("Should unlock the Dummy adapter");
+        let shadow = Application {
+            adapter: shadow_adapter,
+            ..app.clone()
+        }
+        .with_whoami_signer(Arc::new(follower_signer));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("Should insert Channel");
+        insert_campaign(&app.pool, &DUMMY_CAMPAIGN)
+            .await
+            .expect("Should insert Campaign");
+
+        // the adapters have different deposits
+        let deposit = UnifiedNum::from_whole(1_000);
+        let shadow_deposit = UnifiedNum::from_whole(500);
+        for (adapter, deposit) in [(&app.adapter, deposit), (&shadow.adapter, shadow_deposit)] {
+            adapter.client.set_deposit(
+                &channel_context,
+                *CREATOR,
+                Deposit {
+                    total: deposit.to_precision(channel_context.token.precision.get()),
+                },
+            );
+        }
+
+        let app = app.with_shadow(shadow);
+
+        let header_token = HeaderToken {
+            token: DUMMY_AUTH[&CREATOR].clone(),
+            chain_id: channel_context.chain.chain_id,
+        };
+        let request = |method: Method, uri: String| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(AUTHORIZATION, format!("Bearer {header_token}"))
+                .body(Body::empty())
+                .expect("Should build Request")
+        };
+
+        // whoami
+        for (prefix, expected) in [("/v5", IDS[&LEADER]), ("/v5-shadow", IDS[&FOLLOWER])] {
+            let response = oneshot(
+                &app,
+                request(Method::GET, format!("{prefix}/whoami?challenge=0x1234")),
+            )
+            .await;
+
+            assert_eq!(StatusCode::OK, response.status(), "{prefix}");
+            let whoami = body_to::<WhoamiResponse>(response)
+                .await
+                .expect("Should deserialize");
+            assert_eq!(expected, whoami.validator_id, "{prefix}");
+        }
+
+        // spender, the shadow route is called first and it should not save the spendable
+        let spender_uri = |prefix: &str| {
+            format!(
+                "{prefix}/channel/{}/spender/{}",
+                DUMMY_CAMPAIGN.channel.id(),
+                *CREATOR
+            )
+        };
+        for (prefix, expected) in [("/v5-shadow", shadow_deposit), ("/v5", deposit)] {
+            let response = oneshot(&app, request(Method::GET, spender_uri(prefix))).await;
+
+            assert_eq!(StatusCode::OK, response.status(), "{prefix}");
+            let spender = body_to::<SpenderResponse>(response)
+                .await
+                .expect("Should deserialize");
+            assert_eq!(expected, spender.spender.total_deposited, "{prefix}");
+        }
+        let spendable = fetch_spendable(app.pool.clone(), &CREATOR, &channel_context.context.id())
+            .await
+            .expect("Should fetch spendable")
+            .expect("Should have saved the spendable of the /v5 route");
+        assert_eq!(deposit, spendable.deposit.total);
+
+        // campaign list
+        {
+            let mut lists = vec![];
+            for prefix in ["/v5", "/v5-shadow"] {
+                let response = oneshot(
+                    &app,
+                    request(Method::GET, format!("{prefix}/campaign/list")),
+                )
+                .await;
+
+                assert_eq!(StatusCode::OK, response.status(), "{prefix}");
+                let list = body_to::<CampaignListResponse>(response)
+                    .await
+                    .expect("Should deserialize");
+                lists.push(list.campaigns);
+            }
+
+            assert_eq!(vec![DUMMY_CAMPAIGN.clone()], lists[0]);
+            assert_eq!(lists[0], lists[1]);
+        }
+
+        // the shadow routes are read-only
+        {
+            let response = oneshot(
+                &app,
+                request(
+                    Method::POST,
+                    format!("/v5-shadow/campaign/{}/close", DUMMY_CAMPAIGN.id),
+                ),
+            )
+            .await;
+
+            assert_eq!(StatusCode::METHOD_NOT_ALLOWED, response.status());
+        }
+    }
+
+    #[tokio::test]
+    async fn large_responses_are_compressed_when_accepted() {
+        let app_guard = setup_dummy_app().await;
+        let mut app = app_guard.app.clone();
+        app.config.sentry.response_compression_min_size = Some(512);
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("Should insert Channel");
+
+        let mut balances = Balances::<CheckedState>::new();
+        for earner in 1..=20_u8 {
+            balances
+                .spend(
+                    *CREATOR,
+                    Address::from([earner; 20]),
+                    UnifiedNum::from_u64(1_000),
+                )
+                .expect("Should spend");
+        }
+        spend_amount(app.pool.clone(), channel_context.context.id(), balances)
+            .await
+            .expect("Should spend the amount");
+
+        let request = |uri: String, accept_encoding: Option<&str>| {
+            let builder = Request::builder().method(Method::GET).uri(uri);
+            let builder = match accept_encoding {
+                Some(accept_encoding) => builder.header(ACCEPT_ENCODING, accept_encoding),
+                None => builder,
+            };
+
+            builder.body(Body::empty()).expect("Should build Request")
+        };
+        let accounting_uri = format!("/v5/channel/{}/accounting", DUMMY_CAMPAIGN.channel.id());
+
+        // large response & gzip is accepted
+        {
+            let response = oneshot(&app, request(accounting_uri.clone(), Some("gzip"))).await;
+
+            assert_eq!(StatusCode::OK, response.status());
+            assert_eq!(
+                Some("gzip"),
+                response
+                    .headers()
+                    .get(CONTENT_ENCODING)
+                    .and_then(|value| value.to_str().ok())
+            );
+        }
+
+        // large response & no Accept-Encoding
+        {
+            let response = oneshot(&app, request(accounting_uri, None)).await;
+
+            assert_eq!(StatusCode::OK, response.status());
+            assert_eq!(None, response.headers().get(CONTENT_ENCODING));
+            let accounting = body_to::<AccountingResponse<CheckedState>>(response)
+                .await
+                .expect("Should deserialize");
+            assert_eq!(20, accounting.balances.earners.len());
+        }
+
+        // small response & gzip is accepted
+        {
+            let response = oneshot(&app, request("/health".to_string(), Some("gzip"))).await;
+
+            assert_eq!(StatusCode::OK, response.status());
+            assert_eq!(None, response.headers().get(CONTENT_ENCODING));
+        }
+    }
 }
@@ -16,12 +16,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         dummy_identity: IDS[&ADVERTISER],
         dummy_auth_tokens: DUMMY_AUTH.clone(),
         dummy_chains: GANACHE_CONFIG.chains.values().cloned().collect(),
+        failure_injection: None,
     }));
 
     let advertiser2_adapter = Adapter::with_unlocked(Dummy::init(Options {
         dummy_identity: IDS[&ADVERTISER_2],
         dummy_auth_tokens: DUMMY_AUTH.clone(),
         dummy_chains: GANACHE_CONFIG.chains.values().cloned().collect(),
+        failure_injection: None,
     }));
 
     let client = reqwest::Client::new();
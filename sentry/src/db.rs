@@ -14,11 +14,15 @@ pub mod accounting;
 pub mod analytics;
 pub mod campaign;
 mod channel;
+pub mod fees_summary;
+mod redis_keys;
+pub mod slow_query;
 pub mod spendable;
 pub mod validator_message;
 
 pub use self::campaign::*;
 pub use self::channel::*;
+pub use self::redis_keys::RedisKeys;
 
 // Re-export the Postgres Config
 pub use tokio_postgres::Config as PostgresConfig;
@@ -98,7 +102,19 @@ pub fn setup_migrations(environment: Environment) {
 
     // NOTE: Make sure to update list of migrations for the tests as well!
     // `tests_postgres::MIGRATIONS`
-    let migrations = vec![make_migration!("20190806011140_initial-tables")];
+    let migrations = vec![
+        make_migration!("20190806011140_initial-tables"),
+        make_migration!("20221121120000_campaign-version"),
+        make_migration!("20221205120000_validator-fees-summaries"),
+        make_migration!("20221212120000_campaign-unit-pricing-overrides"),
+        make_migration!("20221219120000_campaign-accepted-event-types"),
+        make_migration!("20221219130000_channel-accounting-frozen"),
+        make_migration!("20221226120000_validator-messages-cursor"),
+        make_migration!("20230102120000_spendable-history"),
+        make_migration!("20230109120000_campaign-pacing"),
+        make_migration!("20230116120000_campaign-budget-changes"),
+        make_migration!("20230123120000_validator-messages-quarantine"),
+    ];
 
     // Define Migrations
     config
@@ -161,7 +177,19 @@ pub mod tests_postgres {
 
     /// we must have a duplication of the migration because of how migrant is handling migrations
     /// we need to separately setup test migrations
-    pub static MIGRATIONS: &[&str] = &["20190806011140_initial-tables"];
+    pub static MIGRATIONS: &[&str] = &[
+        "20190806011140_initial-tables",
+        "20221121120000_campaign-version",
+        "20221205120000_validator-fees-summaries",
+        "20221212120000_campaign-unit-pricing-overrides",
+        "20221219120000_campaign-accepted-event-types",
+        "20221219130000_channel-accounting-frozen",
+        "20221226120000_validator-messages-cursor",
+        "20230102120000_spendable-history",
+        "20230109120000_campaign-pacing",
+        "20230116120000_campaign-budget-changes",
+        "20230123120000_validator-messages-quarantine",
+    ];
 
     fn create_pool(db_prefix: &str) -> Result<Pool, Error> {
         let manager_config = ManagerConfig {
@@ -539,7 +567,7 @@ pub mod redis_pool {
         /// The maximum databases that Redis has by default is 16, with DB `0` as default.
         const CONNECTIONS: u8 = 16;
         /// The default URL for connecting to the different databases
-        const URL: &'static str = "redis://127.0.0.1:6379/";
+        pub const URL: &'static str = "redis://127.0.0.1:6379/";
 
         pub fn new() -> Self {
             Self {
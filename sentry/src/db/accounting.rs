@@ -1,9 +1,10 @@
 use std::fmt;
 
 use chrono::{DateTime, Utc};
-use futures::future::{join, join_all};
+use deadpool_postgres::Transaction;
 use primitives::{
     balances::{Balances, CheckedState},
+    sentry::Pagination,
     Address, ChannelId, UnifiedNum,
 };
 use tokio_postgres::{
@@ -11,7 +12,7 @@ use tokio_postgres::{
     Row,
 };
 
-use super::{DbPool, PoolError};
+use super::{slow_query::timed, DbPool, PoolError, TotalCount};
 use thiserror::Error;
 
 static UPDATE_ACCOUNTING_STATEMENT: &str = "INSERT INTO accounting(channel_id, side, address, amount, updated, created) VALUES($1, $2, $3, $4, NULL, NOW()) ON CONFLICT ON CONSTRAINT accounting_pkey DO UPDATE SET amount = accounting.amount + EXCLUDED.amount, updated = NOW() WHERE accounting.channel_id = $1 AND accounting.side = $2 AND accounting.address = $3 RETURNING channel_id, side, address, amount, updated, created";
@@ -22,6 +23,8 @@ pub enum Error {
     Balances(#[from] primitives::balances::Error),
     #[error("Fetching Accounting from postgres error: {0}")]
     Postgres(#[from] PoolError),
+    #[error("Accounting of Channel {0} is frozen")]
+    ChannelFrozen(ChannelId),
 }
 
 impl From<tokio_postgres::Error> for Error {
@@ -104,6 +107,57 @@ pub async fn get_all_accountings_for_channel(
     Ok(accountings)
 }
 
+/// Returns a single page of the [`Accounting`]s of the Channel.
+///
+/// The `Accounting`s are ordered by the primary key, i.e. by side (earners first) and address,
+/// so that the pages are stable while paging through the accounting.
+///
+/// ```sql
+/// SELECT channel_id, side, address, amount, updated, created FROM accounting WHERE channel_id = $1 ORDER BY side ASC, address ASC LIMIT {} OFFSET {}
+/// ```
+pub async fn get_accountings_for_channel_page(
+    pool: DbPool,
+    channel_id: ChannelId,
+    skip: u64,
+    limit: u64,
+) -> Result<(Vec<Accounting>, Pagination), PoolError> {
+    // a limit of 0 would divide by zero when calculating the pagination
+    let limit = limit.max(1);
+    let client = pool.get().await?;
+    let query = format!("SELECT channel_id, side, address, amount, updated, created FROM accounting WHERE channel_id = $1 ORDER BY side ASC, address ASC LIMIT {} OFFSET {}", limit, skip);
+    let statement = client.prepare(&query).await?;
+
+    let rows = timed(
+        "get_accountings_for_channel_page",
+        client.query(&statement, &[&channel_id]),
+    )
+    .await?;
+    let accountings = rows.iter().map(Accounting::from).collect();
+
+    let count_statement = client
+        .prepare("SELECT COUNT(accounting)::varchar FROM accounting WHERE channel_id = $1")
+        .await?;
+    let total_count = client
+        .query_one(&count_statement, &[&channel_id])
+        .await?
+        .get::<_, TotalCount>(0)
+        .0;
+
+    // fast ceil for total_pages
+    let total_pages = if total_count == 0 {
+        1
+    } else {
+        1 + ((total_count - 1) / limit)
+    };
+
+    let pagination = Pagination {
+        total_pages,
+        page: skip / limit,
+    };
+
+    Ok((accountings, pagination))
+}
+
 /// Will update current Spender/Earner amount or insert a new Accounting record
 ///
 /// See `UPDATE_ACCOUNTING_STATEMENT` static for full query.
@@ -128,62 +182,80 @@ pub async fn update_accounting(
 /// It will **not** override the whole `Accounting` value
 /// Returns a tuple of `(Vec<Earners Accounting>, Vec<Spenders Accounting>)`
 ///
+/// All the updates are executed in a single transaction, see [`spend_amount_in_tx()`].
+///
 /// # Error
 ///
-/// It will return an error if any of the updates fails but it would have updated the rest of them.
+/// It will return an error if any of the updates fails and none of the updates will be applied.
 ///
-/// This way we ensure that even if a single or multiple Accounting updates fail,
-/// we will still pay out the rest of them.
+/// This way the earners and spenders of the Channel always stay balanced.
+///
+/// If the accounting of the Channel is frozen, see [`set_accounting_frozen()`](super::set_accounting_frozen),
+/// it returns [`Error::ChannelFrozen`] without applying any of the updates.
 pub async fn spend_amount(
     pool: DbPool,
     channel_id: ChannelId,
     delta_balances: Balances<CheckedState>,
-) -> Result<(Vec<Accounting>, Vec<Accounting>), PoolError> {
-    let client = &pool.get().await?;
+) -> Result<(Vec<Accounting>, Vec<Accounting>), Error> {
+    let mut client = pool.get().await?;
+    let transaction = client.transaction().await?;
 
-    let statement = client.prepare_cached(UPDATE_ACCOUNTING_STATEMENT).await?;
+    let accountings = spend_amount_in_tx(&transaction, channel_id, delta_balances).await?;
 
-    // Earners
-    let earners_futures = delta_balances.earners.into_iter().map(|(earner, amount)| {
-        let statement = statement.clone();
+    transaction.commit().await?;
 
-        async move {
-            client
-                .query_one(&statement, &[&channel_id, &Side::Earner, &earner, &amount])
-                .await
-                .map(|row| Accounting::from(&row))
-        }
-    });
-
-    // Spenders
-    let spenders_futures = delta_balances
-        .spenders
-        .into_iter()
-        .map(|(spender, amount)| {
-            let statement = statement.clone();
-
-            async move {
-                client
-                    .query_one(
-                        &statement,
-                        &[&channel_id, &Side::Spender, &spender, &amount],
-                    )
-                    .await
-                    .map(|row| Accounting::from(&row))
-            }
-        });
+    Ok(accountings)
+}
 
-    let earners = join_all(earners_futures);
-    let spenders = join_all(spenders_futures);
+/// Same as [`spend_amount()`] but uses an already started `transaction`.
+///
+/// The caller is responsible for committing the transaction.
+/// If any of the updates fails, the transaction should be rolled back (e.g. dropped).
+pub async fn spend_amount_in_tx(
+    transaction: &Transaction<'_>,
+    channel_id: ChannelId,
+    delta_balances: Balances<CheckedState>,
+) -> Result<(Vec<Accounting>, Vec<Accounting>), Error> {
+    // locks the Channel row, so it cannot be frozen while the spending is applied
+    let frozen = transaction
+        .query_opt(
+            "SELECT accounting_frozen FROM channels WHERE id = $1 FOR SHARE",
+            &[&channel_id],
+        )
+        .await?
+        .map(|row| row.get::<_, bool>("accounting_frozen"))
+        .unwrap_or_default();
+
+    if frozen {
+        return Err(Error::ChannelFrozen(channel_id));
+    }
 
-    // collect all the Accounting updates into Vectors
-    let (earners, spenders) = join(earners, spenders).await;
+    let statement = transaction
+        .prepare_cached(UPDATE_ACCOUNTING_STATEMENT)
+        .await?;
 
-    // Return an error if any of the Accounting updates failed
-    Ok((
-        earners.into_iter().collect::<Result<_, _>>()?,
-        spenders.into_iter().collect::<Result<_, _>>()?,
-    ))
+    let mut earners = Vec::with_capacity(delta_balances.earners.len());
+    for (earner, amount) in delta_balances.earners {
+        let row = transaction
+            .query_one(&statement, &[&channel_id, &Side::Earner, &earner, &amount])
+            .await?;
+
+        earners.push(Accounting::from(&row));
+    }
+
+    let mut spenders = Vec::with_capacity(delta_balances.spenders.len());
+    for (spender, amount) in delta_balances.spenders {
+        let row = transaction
+            .query_one(
+                &statement,
+                &[&channel_id, &Side::Spender, &spender, &amount],
+            )
+            .await?;
+
+        spenders.push(Accounting::from(&row));
+    }
+
+    Ok((earners, spenders))
 }
 
 #[cfg(test)]
@@ -657,4 +729,70 @@ mod test {
             );
         }
     }
+
+    #[tokio::test]
+    async fn spend_amount_is_rolled_back_on_failure() {
+        let database = DATABASE_POOL.get().await.expect("Should get a DB pool");
+
+        setup_test_migrations(database.pool.clone())
+            .await
+            .expect("Migrations should succeed");
+
+        let channel_context = GANACHE_CONFIG
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+        let channel_id = insert_channel(&database.pool, &channel_context)
+            .await
+            .expect("Should insert")
+            .id();
+
+        // the spent amount is just below the maximum `bigint` value of the `amount` column
+        let almost_max = UnifiedNum::from_u64(i64::MAX as u64 - 10);
+        let mut balances = Balances::<CheckedState>::default();
+        balances
+            .spend(*CREATOR, *PUBLISHER_2, almost_max)
+            .expect("Should spend");
+        spend_amount(database.pool.clone(), channel_id, balances)
+            .await
+            .expect("Should spend");
+
+        // The Earner row is updated first and it will succeed,
+        // but the Spender row will fail with `bigint out of range`
+        let mut balances = Balances::<CheckedState>::default();
+        balances
+            .spend(*CREATOR, *PUBLISHER, UnifiedNum::from_u64(100))
+            .expect("Should spend");
+        spend_amount(database.pool.clone(), channel_id, balances)
+            .await
+            .expect_err("Should fail to update the Spender accounting");
+
+        let publisher_earned =
+            get_accounting(database.pool.clone(), channel_id, *PUBLISHER, Side::Earner)
+                .await
+                .expect("Should query for accounting");
+        assert!(
+            publisher_earned.is_none(),
+            "The Earner accounting should have been rolled back"
+        );
+
+        let accountings = get_all_accountings_for_channel(database.pool.clone(), channel_id)
+            .await
+            .expect("Should get accountings");
+        let sum_of = |side: Side| {
+            accountings
+                .iter()
+                .filter(|accounting| accounting.side == side)
+                .map(|accounting| accounting.amount)
+                .sum::<Option<UnifiedNum>>()
+                .expect("Should not overflow")
+        };
+
+        assert_eq!(almost_max, sum_of(Side::Earner));
+        assert_eq!(
+            sum_of(Side::Earner),
+            sum_of(Side::Spender),
+            "Accounting should stay balanced"
+        );
+    }
 }
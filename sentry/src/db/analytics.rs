@@ -1,13 +1,18 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
+use chrono::Utc;
 use futures::TryStreamExt;
 use primitives::{
     analytics::{query::AllowedKey, AnalyticsQuery, AuthenticateAs, Metric, Timeframe},
-    sentry::{Analytics, FetchedAnalytics, UpdateAnalytics},
+    sentry::{
+        event_stats::{EventStats, HourlyEventStats},
+        Analytics, DateHour, FetchedAnalytics, UpdateAnalytics,
+    },
+    Address, CampaignId, UnifiedNum,
 };
 use tokio_postgres::{types::ToSql, Row};
 
-use super::{DbPool, PoolError};
+use super::{slow_query::timed, DbPool, PoolError};
 
 pub async fn fetch_analytics(
     pool: &DbPool,
@@ -64,7 +69,10 @@ pub async fn fetch_analytics(
     // Prepare SQL statement
     let stmt = client.prepare(&sql_query).await?;
     // Execute query
-    let rows: Vec<Row> = client.query_raw(&stmt, params).await?.try_collect().await?;
+    let rows: Vec<Row> = timed("fetch_analytics", async {
+        client.query_raw(&stmt, params).await?.try_collect().await
+    })
+    .await?;
 
     // FetchedAnalytics requires context using the `AnalyticsQuery`
     // this is why we use `impl From<(&AnalyticsQuery, &Row)>`
@@ -116,6 +124,66 @@ fn analytics_query_params(
     (where_clauses, params)
 }
 
+/// Fetches the accepted events (count & payout) of a publisher for the given Campaign,
+/// summed by hour and event type for the `start <= time <= end` range.
+///
+/// Only the hours in which the publisher has any events are returned, ordered by time.
+pub async fn fetch_event_stats(
+    pool: &DbPool,
+    campaign: CampaignId,
+    publisher: Address,
+    start: DateHour<Utc>,
+    end: DateHour<Utc>,
+) -> Result<Vec<HourlyEventStats>, PoolError> {
+    let client = pool.get().await?;
+
+    let query = "SELECT \"time\", event_type, SUM(payout_amount)::bigint AS payout, SUM(payout_count)::integer AS count
+    FROM analytics WHERE campaign_id = $1 AND publisher = $2 AND \"time\" >= $3 AND \"time\" <= $4
+    GROUP BY \"time\", event_type";
+
+    let stmt = client.prepare_cached(query).await?;
+    let rows = client
+        .query(&stmt, &[&campaign, &publisher, &start, &end])
+        .await?;
+
+    let mut hourly_stats = BTreeMap::new();
+    for row in rows.iter() {
+        let time: DateHour<Utc> = row.get("time");
+
+        hourly_stats
+            .entry(time)
+            .or_insert_with(|| HourlyEventStats::empty(time))
+            .events
+            .insert(
+                row.get("event_type"),
+                EventStats {
+                    count: row.get::<_, i32>("count").unsigned_abs(),
+                    payout: row.get("payout"),
+                },
+            );
+    }
+
+    Ok(hourly_stats.into_values().collect())
+}
+
+/// Fetches the total payout of all the accepted events of the Campaign
+/// in the hourly buckets since `start` (inclusive).
+pub async fn fetch_campaign_spent(
+    pool: &DbPool,
+    campaign: CampaignId,
+    start: DateHour<Utc>,
+) -> Result<UnifiedNum, PoolError> {
+    let client = pool.get().await?;
+
+    let query = "SELECT COALESCE(SUM(payout_amount), 0)::bigint AS spent
+    FROM analytics WHERE campaign_id = $1 AND \"time\" >= $2";
+
+    let stmt = client.prepare_cached(query).await?;
+    let row = client.query_one(&stmt, &[&campaign, &start]).await?;
+
+    Ok(row.get("spent"))
+}
+
 /// This will update a record when it's present by incrementing its payout_amount and payout_count fields
 pub async fn update_analytics(
     pool: &DbPool,
@@ -1,30 +1,125 @@
 use std::collections::HashSet;
 
-use crate::db::{DbPool, PoolError, TotalCount};
+use crate::db::{slow_query::timed, DbPool, PoolError, RedisError, RedisKeys, TotalCount};
 use chrono::{DateTime, Utc};
-use futures::TryStreamExt;
+use futures::{Stream, StreamExt, TryStreamExt};
 use primitives::{
     sentry::{
+        campaign_budget_history::BudgetChange,
         campaign_list::{CampaignListResponse, ValidatorParam},
         Pagination,
     },
-    Address, Campaign, CampaignId, ChannelId,
+    Address, Campaign, CampaignId, ChannelId, UnifiedNum,
 };
+use redis::{aio::MultiplexedConnection, IntoConnectionInfo};
 use tokio_postgres::{
     types::{Json, ToSql},
-    Row,
+    GenericClient, Row,
 };
 
-pub use campaign_remaining::CampaignRemaining;
+pub use campaign_remaining::{CampaignRemaining, MissingRemaining, MultipleRemaining};
 
 /// ```text
-/// INSERT INTO campaigns (id, channel_id, creator, budget, validators, title, pricing_bounds, event_submission, ad_units, targeting_rules, created, active_from, active_to)
-/// VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+/// INSERT INTO campaigns (id, channel_id, creator, budget, validators, title, pricing_bounds, unit_pricing_overrides, accepted_event_types, event_submission, pacing, ad_units, targeting_rules, created, active_from, active_to)
+/// VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
 /// ```
 pub async fn insert_campaign(pool: &DbPool, campaign: &Campaign) -> Result<bool, PoolError> {
     let client = pool.get().await?;
+
+    Ok(execute_insert_campaign(&**client, campaign).await?)
+}
+
+/// Inserts all the `campaigns` in a single transaction,
+/// i.e. either all of them are inserted or none of them.
+///
+/// Returns `false` if any of the campaigns was not inserted.
+pub async fn insert_campaigns(pool: &DbPool, campaigns: &[Campaign]) -> Result<bool, PoolError> {
+    let mut client = pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    for campaign in campaigns {
+        if !execute_insert_campaign(&*transaction, campaign).await? {
+            // the transaction is rolled back when dropped
+            return Ok(false);
+        }
+    }
+
+    transaction.commit().await?;
+
+    Ok(true)
+}
+
+/// The outcome of [`insert_campaigns_within_limit()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitedInsert {
+    Inserted,
+    /// Some of the campaigns were not inserted, none of them is inserted.
+    NotInserted,
+    /// The Channel would exceed the maximum number of active campaigns, none of them is inserted.
+    LimitReached,
+}
+
+/// Inserts the `campaigns` of the `channel` in a single transaction, like [`insert_campaigns()`],
+/// unless the Channel would exceed `max_campaigns` active (i.e. not expired) campaigns.
+///
+/// The Channel row is locked until the end of the transaction, so the campaigns of concurrent
+/// requests for the same Channel are counted and inserted one after the other.
+///
+/// ```text
+/// SELECT id FROM channels WHERE id = $1 FOR UPDATE
+///
+/// SELECT COUNT(id)::varchar FROM campaigns WHERE channel_id = $1 AND active_to > NOW()
+/// ```
+pub async fn insert_campaigns_within_limit(
+    pool: &DbPool,
+    channel: ChannelId,
+    campaigns: &[Campaign],
+    max_campaigns: u32,
+) -> Result<LimitedInsert, PoolError> {
+    let mut client = pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    let lock_channel = transaction
+        .prepare("SELECT id FROM channels WHERE id = $1 FOR UPDATE")
+        .await?;
+    transaction.execute(&lock_channel, &[&channel]).await?;
+
+    let count_active = transaction
+        .prepare(
+            "SELECT COUNT(id)::varchar FROM campaigns WHERE channel_id = $1 AND active_to > NOW()",
+        )
+        .await?;
+    let active_campaigns = transaction
+        .query_one(&count_active, &[&channel])
+        .await?
+        .get::<_, TotalCount>(0)
+        .0;
+
+    if active_campaigns.saturating_add(campaigns.len() as u64) > u64::from(max_campaigns) {
+        return Ok(LimitedInsert::LimitReached);
+    }
+
+    for campaign in campaigns {
+        if !execute_insert_campaign(&*transaction, campaign).await? {
+            // the transaction is rolled back when dropped
+            return Ok(LimitedInsert::NotInserted);
+        }
+    }
+
+    transaction.commit().await?;
+
+    Ok(LimitedInsert::Inserted)
+}
+
+async fn execute_insert_campaign(
+    client: &impl GenericClient,
+    campaign: &Campaign,
+) -> Result<bool, tokio_postgres::Error> {
     let ad_units = Json(campaign.ad_units.clone());
-    let stmt = client.prepare("INSERT INTO campaigns (id, channel_id, creator, budget, validators, title, pricing_bounds, event_submission, ad_units, targeting_rules, created, active_from, active_to) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)").await?;
+    let unit_pricing_overrides = Json(&campaign.unit_pricing_overrides);
+    let accepted_event_types = campaign.accepted_event_types.as_ref().map(Json);
+    let pacing = campaign.pacing.as_ref().map(Json);
+    let stmt = client.prepare("INSERT INTO campaigns (id, channel_id, creator, budget, validators, title, pricing_bounds, unit_pricing_overrides, accepted_event_types, event_submission, pacing, ad_units, targeting_rules, created, active_from, active_to) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)").await?;
     let inserted = client
         .execute(
             &stmt,
@@ -36,7 +131,10 @@ pub async fn insert_campaign(pool: &DbPool, campaign: &Campaign) -> Result<bool,
                 &campaign.validators,
                 &campaign.title,
                 &campaign.pricing_bounds,
+                &unit_pricing_overrides,
+                &accepted_event_types,
                 &campaign.event_submission,
+                &pacing,
                 &ad_units,
                 &campaign.targeting_rules,
                 &campaign.created,
@@ -51,7 +149,7 @@ pub async fn insert_campaign(pool: &DbPool, campaign: &Campaign) -> Result<bool,
 }
 
 /// ```text
-/// SELECT campaigns.id, creator, budget, validators, title, pricing_bounds, event_submission, ad_units, targeting_rules, campaigns.created, active_from, active_to,
+/// SELECT campaigns.id, creator, budget, validators, title, pricing_bounds, unit_pricing_overrides, accepted_event_types, event_submission, pacing, ad_units, targeting_rules, campaigns.created, active_from, active_to, campaigns.version,
 /// channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce
 /// FROM campaigns INNER JOIN channels
 /// ON campaigns.channel_id=channels.id
@@ -63,7 +161,7 @@ pub async fn fetch_campaign(
 ) -> Result<Option<Campaign>, PoolError> {
     let client = pool.get().await?;
     // TODO: Check and update
-    let statement = client.prepare("SELECT campaigns.id, creator, budget, validators, title, pricing_bounds, event_submission, ad_units, targeting_rules, campaigns.created, active_from, active_to, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM campaigns INNER JOIN channels
+    let statement = client.prepare("SELECT campaigns.id, creator, budget, validators, title, pricing_bounds, unit_pricing_overrides, accepted_event_types, event_submission, pacing, ad_units, targeting_rules, campaigns.created, active_from, active_to, campaigns.version, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM campaigns INNER JOIN channels
     ON campaigns.channel_id=channels.id WHERE campaigns.id = $1").await?;
 
     let row = client.query_opt(&statement, &[&campaign]).await?;
@@ -71,6 +169,30 @@ pub async fn fetch_campaign(
     Ok(row.as_ref().map(Campaign::from))
 }
 
+/// Fetches the `campaigns` in a single query, unknown Campaigns are skipped.
+///
+/// The Campaigns are **not** returned in the order of the `campaigns`.
+///
+/// ```text
+/// SELECT campaigns.id, creator, budget, validators, title, pricing_bounds, unit_pricing_overrides, accepted_event_types, event_submission, pacing, ad_units, targeting_rules, campaigns.created, active_from, active_to, campaigns.version,
+/// channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce
+/// FROM campaigns INNER JOIN channels
+/// ON campaigns.channel_id=channels.id
+/// WHERE campaigns.id = ANY($1)
+/// ```
+pub async fn fetch_campaigns(
+    pool: &DbPool,
+    campaigns: &[CampaignId],
+) -> Result<Vec<Campaign>, PoolError> {
+    let client = pool.get().await?;
+    let statement = client.prepare("SELECT campaigns.id, creator, budget, validators, title, pricing_bounds, unit_pricing_overrides, accepted_event_types, event_submission, pacing, ad_units, targeting_rules, campaigns.created, active_from, active_to, campaigns.version, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM campaigns INNER JOIN channels
+    ON campaigns.channel_id=channels.id WHERE campaigns.id = ANY($1)").await?;
+
+    let rows = timed("fetch_campaigns", client.query(&statement, &[&campaigns])).await?;
+
+    Ok(rows.iter().map(Campaign::from).collect())
+}
+
 pub async fn list_campaigns(
     pool: &DbPool,
     skip: u64,
@@ -85,9 +207,9 @@ pub async fn list_campaigns(
     let total_count_params = (where_clauses.clone(), params.clone());
 
     // To understand why we use Order by, see Postgres Documentation: https://www.postgresql.org/docs/8.1/queries-limit.html
-    let statement = format!("SELECT campaigns.id, creator, budget, validators, title, pricing_bounds, event_submission, ad_units, targeting_rules, campaigns.created, active_from, active_to, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM campaigns INNER JOIN channels ON campaigns.channel_id=channels.id WHERE {} ORDER BY campaigns.created ASC LIMIT {} OFFSET {}", where_clauses.join(" AND "), limit, skip);
+    let statement = format!("SELECT campaigns.id, creator, budget, validators, title, pricing_bounds, unit_pricing_overrides, accepted_event_types, event_submission, pacing, ad_units, targeting_rules, campaigns.created, active_from, active_to, campaigns.version, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM campaigns INNER JOIN channels ON campaigns.channel_id=channels.id WHERE {} ORDER BY campaigns.created ASC LIMIT {} OFFSET {}", where_clauses.join(" AND "), limit, skip);
     let stmt = client.prepare(&statement).await?;
-    let rows = client.query(&stmt, params.as_slice()).await?;
+    let rows = timed("list_campaigns", client.query(&stmt, params.as_slice())).await?;
     let campaigns = rows.iter().map(Campaign::from).collect();
 
     let total_count =
@@ -137,6 +259,10 @@ fn campaign_list_query_params<'a>(
             ));
             params.push(validator_id);
         }
+        Some(ValidatorParam::Guardian(guardian)) => {
+            where_clauses.push(format!("channels.guardian = ${}", params.len() + 1));
+            params.push(guardian);
+        }
         _ => (),
     }
 
@@ -154,7 +280,11 @@ pub async fn list_campaigns_total_count<'a>(
         where_clauses.join(" AND ")
     );
     let stmt = client.prepare(&statement).await?;
-    let row = client.query_one(&stmt, params.as_slice()).await?;
+    let row = timed(
+        "list_campaigns_total_count",
+        client.query_one(&stmt, params.as_slice()),
+    )
+    .await?;
 
     Ok(row.get::<_, TotalCount>(0).0)
 }
@@ -183,39 +313,162 @@ pub async fn get_campaign_ids_by_channel(
     Ok(campaign_ids)
 }
 
-/// Updates the campaign fields:
-/// budget, validators, title, pricing_bounds, event_submission, ad_units, targeting_rules
+/// All the Campaigns of the `creator` in the Channel.
+///
 /// ```text
-/// UPDATE campaigns SET budget = $1, validators = $2, title = $3, pricing_bounds = $4, event_submission = $5, ad_units = $6, targeting_rules = $7
-/// FROM channels WHERE campaigns.id = $8 AND campaigns.channel_id=channels.id
-/// RETURNING campaigns.id, creator, budget, validators, title, pricing_bounds, event_submission, ad_units, targeting_rules, campaigns.created, active_from, active_to,
+/// SELECT campaigns.id, creator, budget, validators, title, pricing_bounds, unit_pricing_overrides, accepted_event_types, event_submission, pacing, ad_units, targeting_rules, campaigns.created, active_from, active_to, campaigns.version,
 /// channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce
+/// FROM campaigns INNER JOIN channels
+/// ON campaigns.channel_id=channels.id
+/// WHERE campaigns.channel_id = $1 AND campaigns.creator = $2 ORDER BY campaigns.created ASC
 /// ```
-pub async fn update_campaign(pool: &DbPool, campaign: &Campaign) -> Result<Campaign, PoolError> {
+pub async fn get_campaigns_by_channel_and_creator(
+    pool: &DbPool,
+    channel_id: &ChannelId,
+    creator: Address,
+) -> Result<Vec<Campaign>, PoolError> {
     let client = pool.get().await?;
-    let statement = client
-        .prepare("UPDATE campaigns SET budget = $1, validators = $2, title = $3, pricing_bounds = $4, event_submission = $5, ad_units = $6, targeting_rules = $7 FROM channels WHERE campaigns.id = $8 AND campaigns.channel_id=channels.id RETURNING campaigns.id, creator, budget, validators, title, pricing_bounds, event_submission, ad_units, targeting_rules, campaigns.created, active_from, active_to, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce")
+
+    let statement = client.prepare("SELECT campaigns.id, creator, budget, validators, title, pricing_bounds, unit_pricing_overrides, accepted_event_types, event_submission, pacing, ad_units, targeting_rules, campaigns.created, active_from, active_to, campaigns.version, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM campaigns INNER JOIN channels
+    ON campaigns.channel_id=channels.id WHERE campaigns.channel_id = $1 AND campaigns.creator = $2 ORDER BY campaigns.created ASC").await?;
+
+    let rows = client.query(&statement, &[channel_id, &creator]).await?;
+
+    Ok(rows.iter().map(Campaign::from).collect())
+}
+
+/// Updates the campaign fields:
+/// budget, validators, title, pricing_bounds, unit_pricing_overrides, event_submission, ad_units, targeting_rules
+/// and increments the campaign `version`.
+///
+/// The update is only applied if the stored `version` matches the passed [`Campaign.version`](Campaign::version),
+/// otherwise `None` is returned, i.e. the Campaign has been modified in the meantime.
+///
+/// When the budget changes, it is recorded in the `campaign_budget_changes`
+/// with the given `actor` in the same transaction.
+///
+/// ```text
+/// UPDATE campaigns SET budget = $1, validators = $2, title = $3, pricing_bounds = $4, unit_pricing_overrides = $5, event_submission = $6, pacing = $7, ad_units = $8, targeting_rules = $9, version = campaigns.version + 1
+/// FROM channels WHERE campaigns.id = $10 AND campaigns.version = $11 AND campaigns.channel_id=channels.id
+/// RETURNING campaigns.id, creator, budget, validators, title, pricing_bounds, unit_pricing_overrides, accepted_event_types, event_submission, pacing, ad_units, targeting_rules, campaigns.created, active_from, active_to, campaigns.version,
+/// channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce
+/// ```
+pub async fn update_campaign(
+    pool: &DbPool,
+    campaign: &Campaign,
+    actor: Address,
+) -> Result<Option<Campaign>, PoolError> {
+    let mut client = pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    let old_budget = transaction
+        .query_opt(
+            "SELECT budget FROM campaigns WHERE id = $1 FOR UPDATE",
+            &[&campaign.id],
+        )
+        .await?
+        .map(|row| row.get::<_, UnifiedNum>("budget"));
+
+    let statement = transaction
+        .prepare("UPDATE campaigns SET budget = $1, validators = $2, title = $3, pricing_bounds = $4, unit_pricing_overrides = $5, event_submission = $6, pacing = $7, ad_units = $8, targeting_rules = $9, version = campaigns.version + 1 FROM channels WHERE campaigns.id = $10 AND campaigns.version = $11 AND campaigns.channel_id=channels.id RETURNING campaigns.id, creator, budget, validators, title, pricing_bounds, unit_pricing_overrides, accepted_event_types, event_submission, pacing, ad_units, targeting_rules, campaigns.created, active_from, active_to, campaigns.version, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce")
         .await?;
 
     let ad_units = Json(&campaign.ad_units);
+    let unit_pricing_overrides = Json(&campaign.unit_pricing_overrides);
+    let pacing = campaign.pacing.as_ref().map(Json);
+    let version = i64::try_from(campaign.version).expect("Campaign version should fit in i64");
 
-    let updated_row = client
-        .query_one(
+    let updated = transaction
+        .query_opt(
             &statement,
             &[
                 &campaign.budget,
                 &campaign.validators,
                 &campaign.title,
                 &campaign.pricing_bounds,
+                &unit_pricing_overrides,
                 &campaign.event_submission,
+                &pacing,
                 &ad_units,
                 &campaign.targeting_rules,
                 &campaign.id,
+                &version,
             ],
         )
+        .await?
+        .as_ref()
+        .map(Campaign::from);
+
+    if let (Some(updated), Some(old_budget)) = (&updated, old_budget) {
+        if old_budget != updated.budget {
+            transaction
+                .execute(
+                    INSERT_BUDGET_CHANGE_STATEMENT,
+                    &[
+                        &updated.id,
+                        &old_budget,
+                        &updated.budget,
+                        &actor,
+                        &Utc::now(),
+                    ],
+                )
+                .await?;
+        }
+    }
+
+    transaction.commit().await?;
+
+    Ok(updated)
+}
+
+static INSERT_BUDGET_CHANGE_STATEMENT: &str = "INSERT INTO campaign_budget_changes (campaign_id, old_budget, new_budget, actor, created) VALUES ($1, $2, $3, $4, $5)";
+
+/// Gets the changes of the Campaign budget, oldest first.
+///
+/// ```text
+/// SELECT old_budget, new_budget, actor, created FROM campaign_budget_changes WHERE campaign_id = $1 ORDER BY id ASC LIMIT {} OFFSET {}
+/// ```
+pub async fn get_campaign_budget_history(
+    pool: &DbPool,
+    campaign: CampaignId,
+    skip: u64,
+    limit: u64,
+) -> Result<(Vec<BudgetChange>, Pagination), PoolError> {
+    // a limit of 0 would divide by zero when calculating the pagination
+    let limit = limit.max(1);
+    let client = pool.get().await?;
+    let query = format!("SELECT old_budget, new_budget, actor, created FROM campaign_budget_changes WHERE campaign_id = $1 ORDER BY id ASC LIMIT {} OFFSET {}", limit, skip);
+
+    let statement = client.prepare(&query).await?;
+    let rows = timed(
+        "get_campaign_budget_history",
+        client.query(&statement, &[&campaign]),
+    )
+    .await?;
+    let history = rows.iter().map(BudgetChange::from).collect();
+
+    let statement = client
+        .prepare("SELECT COUNT(id)::varchar FROM campaign_budget_changes WHERE campaign_id = $1")
         .await?;
+    let total_count = client
+        .query_one(&statement, &[&campaign])
+        .await?
+        .get::<_, TotalCount>(0)
+        .0;
+
+    // fast ceil for total_pages
+    let total_pages = if total_count == 0 {
+        1
+    } else {
+        1 + ((total_count - 1) / limit)
+    };
+
+    let pagination = Pagination {
+        total_pages,
+        page: skip / limit,
+    };
 
-    Ok(Campaign::from(&updated_row))
+    Ok((history, pagination))
 }
 
 /// Get Campaigns for GET `/v5/units-for-slot` route.
@@ -262,35 +515,141 @@ pub async fn units_for_slot_get_campaigns(
     };
 
     // To understand why we use Order by, see Postgres Documentation: https://www.postgresql.org/docs/8.1/queries-limit.html
-    let statement = format!("SELECT campaigns.id, creator, budget, validators, title, pricing_bounds, event_submission, ad_units, targeting_rules, campaigns.created, active_from, active_to, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM campaigns INNER JOIN channels ON campaigns.channel_id=channels.id WHERE {} ORDER BY campaigns.created ASC", where_clauses.join(" AND "));
+    let statement = format!("SELECT campaigns.id, creator, budget, validators, title, pricing_bounds, unit_pricing_overrides, accepted_event_types, event_submission, pacing, ad_units, targeting_rules, campaigns.created, active_from, active_to, campaigns.version, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM campaigns INNER JOIN channels ON campaigns.channel_id=channels.id WHERE {} ORDER BY campaigns.created ASC", where_clauses.join(" AND "));
     let stmt = client.prepare(&statement).await?;
-    let rows: Vec<Row> = client.query_raw(&stmt, params).await?.try_collect().await?;
+    let rows: Vec<Row> = timed("units_for_slot_get_campaigns", async {
+        client.query_raw(&stmt, params).await?.try_collect().await
+    })
+    .await?;
 
     Ok(rows.iter().map(Campaign::from).collect())
 }
 
+/// Publishes the id of a closed Campaign on the [`RedisKeys::campaign_closed_channel()`],
+/// e.g. for invalidating the cached units-for-slot Campaigns, see [`subscribe_campaign_closed()`].
+pub async fn publish_campaign_closed(
+    redis: &MultiplexedConnection,
+    redis_keys: &RedisKeys,
+    campaign: CampaignId,
+) -> Result<(), RedisError> {
+    let message = serde_json::to_string(&campaign).expect("Should serialize CampaignId");
+
+    redis::cmd("PUBLISH")
+        .arg(redis_keys.campaign_closed_channel())
+        .arg(message)
+        .query_async::<_, ()>(&mut redis.clone())
+        .await
+}
+
+/// Subscribes to the ids of the closed Campaigns published by [`publish_campaign_closed()`].
+///
+/// Sentry itself doesn't cache the units-for-slot Campaigns, so it doesn't subscribe.
+/// It's the hook point for the services which do, e.g. a units-for-slot cache in front of Sentry.
+///
+/// Pub/sub requires a dedicated connection, so a new one is opened to the given Redis url.
+/// Only the notifications of the same [`RedisKeys`] prefix are received.
+/// Messages which cannot be deserialized are skipped.
+pub async fn subscribe_campaign_closed(
+    redis_url: impl IntoConnectionInfo,
+    redis_keys: &RedisKeys,
+) -> Result<impl Stream<Item = CampaignId>, RedisError> {
+    let client = redis::Client::open(redis_url)?;
+    let mut pubsub = client.get_async_connection().await?.into_pubsub();
+    pubsub
+        .subscribe(redis_keys.campaign_closed_channel())
+        .await?;
+
+    Ok(pubsub.into_on_message().filter_map(|message| async move {
+        let payload = message.get_payload::<String>().ok()?;
+
+        serde_json::from_str::<CampaignId>(&payload).ok()
+    }))
+}
+
 /// struct that handles redis calls for the Campaign Remaining Budget
 mod campaign_remaining {
-    use std::collections::HashMap;
+    use std::{
+        collections::{HashMap, HashSet},
+        num::NonZeroUsize,
+        time::Duration,
+    };
 
-    use crate::db::RedisError;
-    use primitives::{CampaignId, UnifiedNum};
+    use crate::db::{RedisError, RedisKeys};
+    use chrono::Utc;
+    use primitives::{Address, CampaignId, ChannelId, UnifiedNum};
     use redis::aio::MultiplexedConnection;
+    use thiserror::Error;
+
+    /// The default maximum number of keys requested with a single `MGET`,
+    /// see [`CampaignRemaining::with_chunk_size`].
+    pub const DEFAULT_CHUNK_SIZE: usize = 1_000;
+
+    const SECONDS_IN_DAY: i64 = 24 * 60 * 60;
 
     #[derive(Clone)]
     pub struct CampaignRemaining {
         redis: MultiplexedConnection,
+        redis_keys: RedisKeys,
+        chunk_size: NonZeroUsize,
     }
 
+    /// The remaining of multiple Campaigns, see [`CampaignRemaining::get_multiple`].
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct MultipleRemaining {
+        /// The remaining of the Campaigns found in Redis, in the requested order.
+        ///
+        /// The values are clamped using `max(0, value)`.
+        pub found: Vec<(CampaignId, UnifiedNum)>,
+        /// The Campaigns without a remaining in Redis, in the requested order.
+        pub missing: Vec<CampaignId>,
+    }
+
+    impl MultipleRemaining {
+        /// Returns an error listing the [`MultipleRemaining::missing`] Campaigns, if any.
+        pub fn ensure_found(self) -> Result<Self, MissingRemaining> {
+            if self.missing.is_empty() {
+                Ok(self)
+            } else {
+                Err(MissingRemaining(self.missing))
+            }
+        }
+
+        /// The sum of the [`MultipleRemaining::found`] remaining,
+        /// returns `None` on overflow.
+        pub fn sum(&self) -> Option<UnifiedNum> {
+            self.found
+                .iter()
+                .map(|(_campaign, remaining)| remaining)
+                .sum()
+        }
+    }
+
+    /// The remaining of the Campaigns is not set in Redis.
+    #[derive(Debug, Clone, PartialEq, Eq, Error)]
+    #[error("Missing remaining budget for Campaigns: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    pub struct MissingRemaining(pub Vec<CampaignId>);
+
     impl CampaignRemaining {
-        pub const CAMPAIGN_REMAINING_KEY: &'static str = "campaignRemaining";
+        pub fn get_key(&self, campaign: CampaignId) -> String {
+            self.redis_keys.campaign_remaining(campaign)
+        }
+
+        pub fn new(redis: MultiplexedConnection, redis_keys: RedisKeys) -> Self {
+            Self {
+                redis,
+                redis_keys,
+                chunk_size: NonZeroUsize::new(DEFAULT_CHUNK_SIZE).expect("Should be non-zero"),
+            }
+        }
 
-        pub fn get_key(campaign: CampaignId) -> String {
-            format!("{}:{}", Self::CAMPAIGN_REMAINING_KEY, campaign)
+        /// Sets the maximum number of keys requested with a single `MGET`,
+        /// by default [`DEFAULT_CHUNK_SIZE`].
+        pub fn with_chunk_size(self, chunk_size: NonZeroUsize) -> Self {
+            Self { chunk_size, ..self }
         }
 
-        pub fn new(redis: MultiplexedConnection) -> Self {
-            Self { redis }
+        pub fn redis_keys(&self) -> &RedisKeys {
+            &self.redis_keys
         }
 
         pub async fn set_initial(
@@ -299,7 +658,7 @@ mod campaign_remaining {
             amount: UnifiedNum,
         ) -> Result<bool, RedisError> {
             redis::cmd("SETNX")
-                .arg(&Self::get_key(campaign))
+                .arg(self.get_key(campaign))
                 .arg(amount.to_u64())
                 .query_async(&mut self.redis.clone())
                 .await
@@ -310,68 +669,84 @@ mod campaign_remaining {
             campaign: CampaignId,
         ) -> Result<Option<i64>, RedisError> {
             redis::cmd("GET")
-                .arg(&Self::get_key(campaign))
+                .arg(self.get_key(campaign))
                 .query_async::<_, Option<i64>>(&mut self.redis.clone())
                 .await
         }
 
-        /// This method uses `max(0, value)` to clamp the value of a campaign, which can be negative and uses `i64`.
-        /// In addition, it defaults the campaign keys that were not found to `0`.
-        pub async fn get_multiple(
+        /// Gets the remaining of the `campaigns` using an `MGET`
+        /// for every [`CampaignRemaining::with_chunk_size`] Campaigns.
+        ///
+        /// `MGET` always returns the values in the same order as the keys.
+        async fn get_multiple_opt(
             &self,
             campaigns: &[CampaignId],
-        ) -> Result<Vec<UnifiedNum>, RedisError> {
-            // `MGET` fails on empty keys
-            if campaigns.is_empty() {
-                return Ok(vec![]);
-            }
+        ) -> Result<Vec<Option<i64>>, RedisError> {
+            self.get_multiple_keys(campaigns, |campaign| self.get_key(campaign))
+                .await
+        }
 
-            let keys: Vec<String> = campaigns
-                .iter()
-                .map(|campaign| Self::get_key(*campaign))
-                .collect();
+        async fn get_multiple_keys(
+            &self,
+            campaigns: &[CampaignId],
+            key: impl Fn(CampaignId) -> String,
+        ) -> Result<Vec<Option<i64>>, RedisError> {
+            let mut campaigns_remaining = Vec::with_capacity(campaigns.len());
 
-            let campaigns_remaining = redis::cmd("MGET")
-                .arg(keys)
-                .query_async::<_, Vec<Option<i64>>>(&mut self.redis.clone())
-                .await?
-                .into_iter()
-                .map(|remaining| match remaining {
-                    Some(remaining) => UnifiedNum::from_u64(remaining.max(0).unsigned_abs()),
-                    None => UnifiedNum::from_u64(0),
-                })
-                .collect();
+            // `MGET` fails on empty keys, but `chunks()` never yields an empty chunk
+            for chunk in campaigns.chunks(self.chunk_size.get()) {
+                let keys: Vec<String> = chunk.iter().map(|campaign| key(*campaign)).collect();
+
+                let chunk_remaining = redis::cmd("MGET")
+                    .arg(keys)
+                    .query_async::<_, Vec<Option<i64>>>(&mut self.redis.clone())
+                    .await?;
+
+                campaigns_remaining.extend(chunk_remaining);
+            }
 
             Ok(campaigns_remaining)
         }
 
+        /// This method uses `max(0, value)` to clamp the value of a campaign, which can be negative and uses `i64`.
+        /// The campaigns which were not found are returned in [`MultipleRemaining::missing`].
+        pub async fn get_multiple(
+            &self,
+            campaigns: &[CampaignId],
+        ) -> Result<MultipleRemaining, RedisError> {
+            let campaigns_remaining = self.get_multiple_opt(campaigns).await?;
+
+            let multiple = campaigns.iter().zip(campaigns_remaining).fold(
+                MultipleRemaining::default(),
+                |mut multiple, (campaign, remaining)| {
+                    match remaining {
+                        Some(remaining) => multiple.found.push((
+                            *campaign,
+                            UnifiedNum::from_u64(remaining.max(0).unsigned_abs()),
+                        )),
+                        None => multiple.missing.push(*campaign),
+                    }
+
+                    multiple
+                },
+            );
+
+            Ok(multiple)
+        }
+
         /// This method will get the remaining of the provided [`Campaign`](primitives::Campaign)s
-        /// and it will also match the returned values to the [`CampaignId`]s
-        /// `MGET` should always return results in the same order!
+        /// and it will also match the returned values to the [`CampaignId`]s.
+        /// It defaults the campaign keys that were not found to `0`.
         pub async fn get_multiple_with_ids(
             &self,
             campaigns: &[CampaignId],
         ) -> Result<HashMap<CampaignId, UnifiedNum>, RedisError> {
-            // `MGET` fails on empty keys
-            if campaigns.is_empty() {
-                return Ok(HashMap::default());
-            }
-
-            let keys: Vec<String> = campaigns
-                .iter()
-                .map(|campaign| Self::get_key(*campaign))
-                .collect();
-
-            let campaigns_remaining = redis::cmd("MGET")
-                .arg(keys)
-                .query_async::<_, Vec<Option<i64>>>(&mut self.redis.clone())
-                .await?
-                .into_iter()
-                .map(|remaining| match remaining {
+            let campaigns_remaining = self.get_multiple_opt(campaigns).await?.into_iter().map(
+                |remaining| match remaining {
                     Some(remaining) => UnifiedNum::from_u64(remaining.max(0).unsigned_abs()),
                     None => UnifiedNum::from_u64(0),
-                })
-                .collect::<Vec<UnifiedNum>>();
+                },
+            );
 
             Ok(campaigns.iter().copied().zip(campaigns_remaining).collect())
         }
@@ -381,7 +756,7 @@ mod campaign_remaining {
             campaign: CampaignId,
             amount: UnifiedNum,
         ) -> Result<i64, RedisError> {
-            let key = Self::get_key(campaign);
+            let key = self.get_key(campaign);
             redis::cmd("INCRBY")
                 .arg(&key)
                 .arg(amount.to_u64())
@@ -394,7 +769,7 @@ mod campaign_remaining {
             campaign: CampaignId,
             amount: UnifiedNum,
         ) -> Result<i64, RedisError> {
-            let key = Self::get_key(campaign);
+            let key = self.get_key(campaign);
             redis::cmd("DECRBY")
                 .arg(&key)
                 .arg(amount.to_u64())
@@ -402,6 +777,204 @@ mod campaign_remaining {
                 .await
         }
 
+        /// Removes the remaining of the `campaigns`,
+        /// e.g. when the creation of the campaigns has failed after setting it.
+        pub async fn remove(&self, campaigns: &[CampaignId]) -> Result<(), RedisError> {
+            // `DEL` fails on empty keys
+            if campaigns.is_empty() {
+                return Ok(());
+            }
+
+            let keys: Vec<String> = campaigns
+                .iter()
+                .map(|campaign| self.get_key(*campaign))
+                .collect();
+
+            redis::cmd("DEL")
+                .arg(keys)
+                .query_async(&mut self.redis.clone())
+                .await
+        }
+
+        /// Reserves the `budgets` of the Campaigns which the `creator` is creating (or modifying)
+        /// in the Channel for the given `window`, so that concurrent requests can take them into account.
+        ///
+        /// Every reservation expires on its own after the `window`, the expired ones are
+        /// removed when reserving.
+        ///
+        /// Returns the sum of the reserved budgets of all the **other** Campaigns
+        /// which are being created at the moment, `None` on overflow.
+        /// The reservations should be released with [`CampaignRemaining::release`]
+        /// once the Campaigns have been created (or have failed to).
+        pub async fn reserve(
+            &self,
+            channel: ChannelId,
+            creator: Address,
+            budgets: &[(CampaignId, UnifiedNum)],
+            window: Duration,
+        ) -> Result<Option<UnifiedNum>, RedisError> {
+            // `ZADD` & `HSET` fail without members
+            if budgets.is_empty() {
+                return Ok(Some(UnifiedNum::from_u64(0)));
+            }
+
+            let deadlines_key = self.redis_keys.campaign_reservations(channel, creator);
+            let budgets_key = self
+                .redis_keys
+                .campaign_reservation_budgets(channel, creator);
+            let window_millis = i64::try_from(window.as_millis()).unwrap_or(i64::MAX);
+            let now = Utc::now().timestamp_millis();
+            let deadline = now.saturating_add(window_millis);
+
+            let mut pipe = redis::pipe();
+            pipe.atomic();
+            pipe.cmd("ZREMRANGEBYSCORE")
+                .arg(&deadlines_key)
+                .arg("-inf")
+                .arg(now)
+                .ignore();
+            let zadd = pipe.cmd("ZADD").arg(&deadlines_key);
+            for (campaign, _budget) in budgets {
+                zadd.arg(deadline).arg(campaign.to_string());
+            }
+            zadd.ignore();
+            let hset = pipe.cmd("HSET").arg(&budgets_key);
+            for (campaign, budget) in budgets {
+                hset.arg(campaign.to_string()).arg(budget.to_u64());
+            }
+            hset.ignore();
+            // the keys live as long as the latest reservation
+            for key in [&deadlines_key, &budgets_key] {
+                pipe.cmd("PEXPIRE").arg(key).arg(window_millis).ignore();
+            }
+            pipe.cmd("ZRANGE").arg(&deadlines_key).arg(0).arg(-1);
+            pipe.cmd("HGETALL").arg(&budgets_key);
+
+            let (active, reserved) = pipe
+                .query_async::<_, (HashSet<String>, HashMap<String, u64>)>(&mut self.redis.clone())
+                .await?;
+
+            // the budgets of the expired reservations
+            let expired = reserved
+                .keys()
+                .filter(|campaign| !active.contains(*campaign))
+                .cloned()
+                .collect::<Vec<_>>();
+            if !expired.is_empty() {
+                redis::cmd("HDEL")
+                    .arg(&budgets_key)
+                    .arg(expired)
+                    .query_async::<_, ()>(&mut self.redis.clone())
+                    .await?;
+            }
+
+            let own = budgets
+                .iter()
+                .map(|(campaign, _budget)| campaign.to_string())
+                .collect::<HashSet<_>>();
+
+            Ok(reserved
+                .into_iter()
+                .filter(|(campaign, _reserved)| {
+                    active.contains(campaign) && !own.contains(campaign)
+                })
+                .map(|(_campaign, reserved)| UnifiedNum::from_u64(reserved))
+                .sum())
+        }
+
+        /// Releases the reservations of the `campaigns` made with [`CampaignRemaining::reserve`].
+        pub async fn release(
+            &self,
+            channel: ChannelId,
+            creator: Address,
+            campaigns: &[CampaignId],
+        ) -> Result<(), RedisError> {
+            // `ZREM` & `HDEL` fail without members
+            if campaigns.is_empty() {
+                return Ok(());
+            }
+
+            let members: Vec<String> = campaigns.iter().map(ToString::to_string).collect();
+
+            redis::pipe()
+                .atomic()
+                .cmd("ZREM")
+                .arg(self.redis_keys.campaign_reservations(channel, creator))
+                .arg(&members)
+                .ignore()
+                .cmd("HDEL")
+                .arg(
+                    self.redis_keys
+                        .campaign_reservation_budgets(channel, creator),
+                )
+                .arg(&members)
+                .ignore()
+                .query_async(&mut self.redis.clone())
+                .await
+        }
+
+        /// Increases the amount spent by the Campaign in the current UTC day by `amount`
+        /// and returns the new amount spent for the day.
+        ///
+        /// The key expires at the end of the UTC day, which resets the daily spending,
+        /// see [`Pacing`](primitives::campaign::Pacing).
+        pub async fn increase_daily_spent(
+            &self,
+            campaign: CampaignId,
+            amount: UnifiedNum,
+        ) -> Result<u64, RedisError> {
+            let key = self.redis_keys.campaign_daily_spent(campaign);
+            let now = Utc::now().timestamp();
+            let end_of_day = now - now.rem_euclid(SECONDS_IN_DAY) + SECONDS_IN_DAY;
+
+            let mut pipe = redis::pipe();
+            pipe.atomic()
+                .cmd("INCRBY")
+                .arg(&key)
+                .arg(amount.to_u64())
+                .cmd("EXPIREAT")
+                .arg(&key)
+                .arg(end_of_day)
+                .ignore();
+
+            let (spent,) = pipe
+                .query_async::<_, (i64,)>(&mut self.redis.clone())
+                .await?;
+
+            Ok(spent.max(0).unsigned_abs())
+        }
+
+        /// Decreases the amount spent by the Campaign in the current UTC day,
+        /// e.g. when the events exceeding the daily cap were not paid.
+        pub async fn decrease_daily_spent(
+            &self,
+            campaign: CampaignId,
+            amount: UnifiedNum,
+        ) -> Result<i64, RedisError> {
+            redis::cmd("DECRBY")
+                .arg(self.redis_keys.campaign_daily_spent(campaign))
+                .arg(amount.to_u64())
+                .query_async(&mut self.redis.clone())
+                .await
+        }
+
+        /// Gets the amount spent by the `campaigns` in the current UTC day,
+        /// the Campaigns without spending for the day default to `0`.
+        pub async fn get_multiple_daily_spent(
+            &self,
+            campaigns: &[CampaignId],
+        ) -> Result<HashMap<CampaignId, UnifiedNum>, RedisError> {
+            let daily_spent = self
+                .get_multiple_keys(campaigns, |campaign| {
+                    self.redis_keys.campaign_daily_spent(campaign)
+                })
+                .await?
+                .into_iter()
+                .map(|spent| UnifiedNum::from_u64(spent.unwrap_or_default().max(0).unsigned_abs()));
+
+            Ok(campaigns.iter().copied().zip(daily_spent).collect())
+        }
+
         /// Atomic `getset` [`redis`] operation
         /// Used to close a [`primitives::Campaign`] `POST /campaign/close`
         pub async fn getset_remaining_to_zero(
@@ -409,7 +982,7 @@ mod campaign_remaining {
             campaign: CampaignId,
         ) -> Result<u64, RedisError> {
             redis::cmd("GETSET")
-                .arg(&Self::get_key(campaign))
+                .arg(self.get_key(campaign))
                 .arg(0)
                 .query_async(&mut self.redis.clone())
                 .await
@@ -418,7 +991,7 @@ mod campaign_remaining {
 
     #[cfg(test)]
     mod test {
-        use primitives::test_util::DUMMY_CAMPAIGN;
+        use primitives::test_util::{ADVERTISER, DUMMY_CAMPAIGN};
 
         use crate::db::redis_pool::TESTS_POOL;
 
@@ -429,7 +1002,8 @@ mod campaign_remaining {
             let redis = TESTS_POOL.get().await.expect("Should return Object");
 
             let campaign = DUMMY_CAMPAIGN.id;
-            let campaign_remaining = CampaignRemaining::new(redis.connection.clone());
+            let campaign_remaining =
+                CampaignRemaining::new(redis.connection.clone(), RedisKeys::default());
 
             // Get remaining on a key which was not set
             {
@@ -527,16 +1101,17 @@ mod campaign_remaining {
         #[tokio::test]
         async fn it_gets_multiple_campaigns_remaining() {
             let redis = TESTS_POOL.get().await.expect("Should return Object");
-            let campaign_remaining = CampaignRemaining::new(redis.connection.clone());
+            let campaign_remaining =
+                CampaignRemaining::new(redis.connection.clone(), RedisKeys::default());
 
             // get multiple with empty campaigns slice
             // `MGET` throws error on an empty keys argument
-            assert!(
+            assert_eq!(
+                MultipleRemaining::default(),
                 campaign_remaining
                     .get_multiple(&[])
                     .await
-                    .expect("Should get multiple")
-                    .is_empty(),
+                    .expect("Should get multiple"),
                 "Should return an empty result"
             );
 
@@ -576,11 +1151,286 @@ mod campaign_remaining {
 
             assert_eq!(
                 vec![
-                    UnifiedNum::from(100),
-                    UnifiedNum::from(0),
-                    UnifiedNum::from(300)
+                    (campaigns.0, UnifiedNum::from(100)),
+                    (campaigns.1, UnifiedNum::from(0)),
+                    (campaigns.2, UnifiedNum::from(300))
                 ],
-                multiple
+                multiple.found
+            );
+            assert!(multiple.missing.is_empty());
+        }
+
+        #[tokio::test]
+        async fn it_gets_multiple_campaigns_remaining_in_chunks() {
+            let redis = TESTS_POOL.get().await.expect("Should return Object");
+            let campaign_remaining =
+                CampaignRemaining::new(redis.connection.clone(), RedisKeys::default());
+
+            let campaigns = (0..5).map(|_| CampaignId::new()).collect::<Vec<_>>();
+            // the 2nd and the last Campaign don't have a remaining set
+            let missing = vec![campaigns[1], campaigns[4]];
+            let found = [
+                (campaigns[0], 100),
+                (campaigns[2], 300),
+                (campaigns[3], 400),
+            ]
+            .into_iter()
+            .map(|(campaign, remaining)| (campaign, UnifiedNum::from(remaining)))
+            .collect::<Vec<_>>();
+
+            for (campaign, remaining) in found.iter() {
+                assert!(campaign_remaining
+                    .set_initial(*campaign, *remaining)
+                    .await
+                    .expect("Should set value in redis"));
+            }
+
+            // chunks smaller, equal and larger than the number of Campaigns
+            for chunk_size in [1, 2, 4, 5, 6] {
+                let multiple = campaign_remaining
+                    .clone()
+                    .with_chunk_size(NonZeroUsize::new(chunk_size).unwrap())
+                    .get_multiple(&campaigns)
+                    .await
+                    .expect("Should get multiple");
+
+                assert_eq!(found, multiple.found, "Chunk size: {chunk_size}");
+                assert_eq!(missing, multiple.missing, "Chunk size: {chunk_size}");
+                assert_eq!(Some(UnifiedNum::from(800)), multiple.sum());
+            }
+
+            let with_ids = campaign_remaining
+                .with_chunk_size(NonZeroUsize::new(2).unwrap())
+                .get_multiple_with_ids(&campaigns)
+                .await
+                .expect("Should get multiple with ids");
+            assert_eq!(campaigns.len(), with_ids.len());
+            assert_eq!(UnifiedNum::from(0), with_ids[&campaigns[4]]);
+            assert_eq!(UnifiedNum::from(400), with_ids[&campaigns[3]]);
+        }
+
+        #[tokio::test]
+        async fn it_reserves_and_releases_budgets() {
+            let redis = TESTS_POOL.get().await.expect("Should return Object");
+            let campaign_remaining =
+                CampaignRemaining::new(redis.connection.clone(), RedisKeys::default());
+
+            let channel = DUMMY_CAMPAIGN.channel.id();
+            let creator = DUMMY_CAMPAIGN.creator;
+            let window = Duration::from_secs(60);
+            let campaigns = (CampaignId::new(), CampaignId::new(), CampaignId::new());
+
+            assert_eq!(
+                Some(UnifiedNum::from(0)),
+                campaign_remaining
+                    .reserve(
+                        channel,
+                        creator,
+                        &[(campaigns.0, UnifiedNum::from(100))],
+                        window
+                    )
+                    .await
+                    .expect("Should reserve"),
+                "There are no other reservations"
+            );
+
+            assert_eq!(
+                Some(UnifiedNum::from(100)),
+                campaign_remaining
+                    .reserve(
+                        channel,
+                        creator,
+                        &[
+                            (campaigns.1, UnifiedNum::from(200)),
+                            (campaigns.2, UnifiedNum::from(300))
+                        ],
+                        window
+                    )
+                    .await
+                    .expect("Should reserve"),
+                "Should include only the reservation of the first Campaign"
+            );
+
+            // reservations of another creator are not included
+            assert_eq!(
+                Some(UnifiedNum::from(0)),
+                campaign_remaining
+                    .reserve(
+                        channel,
+                        *ADVERTISER,
+                        &[(CampaignId::new(), UnifiedNum::from(400))],
+                        window
+                    )
+                    .await
+                    .expect("Should reserve"),
+            );
+
+            campaign_remaining
+                .release(channel, creator, &[campaigns.0, campaigns.1])
+                .await
+                .expect("Should release");
+
+            assert_eq!(
+                Some(UnifiedNum::from(300)),
+                campaign_remaining
+                    .reserve(
+                        channel,
+                        creator,
+                        &[(campaigns.0, UnifiedNum::from(100))],
+                        window
+                    )
+                    .await
+                    .expect("Should reserve"),
+                "Should include only the not released reservation"
+            );
+        }
+
+        #[tokio::test]
+        async fn each_reservation_expires_on_its_own() {
+            let redis = TESTS_POOL.get().await.expect("Should return Object");
+            let campaign_remaining =
+                CampaignRemaining::new(redis.connection.clone(), RedisKeys::default());
+
+            let channel = DUMMY_CAMPAIGN.channel.id();
+            let creator = DUMMY_CAMPAIGN.creator;
+            let campaigns = (CampaignId::new(), CampaignId::new(), CampaignId::new());
+
+            campaign_remaining
+                .reserve(
+                    channel,
+                    creator,
+                    &[(campaigns.0, UnifiedNum::from(100))],
+                    Duration::from_millis(200),
+                )
+                .await
+                .expect("Should reserve");
+
+            // the later reservation should not prolong the first one
+            assert_eq!(
+                Some(UnifiedNum::from(100)),
+                campaign_remaining
+                    .reserve(
+                        channel,
+                        creator,
+                        &[(campaigns.1, UnifiedNum::from(200))],
+                        Duration::from_secs(60)
+                    )
+                    .await
+                    .expect("Should reserve"),
+            );
+
+            tokio::time::sleep(Duration::from_millis(300)).await;
+
+            assert_eq!(
+                Some(UnifiedNum::from(200)),
+                campaign_remaining
+                    .reserve(
+                        channel,
+                        creator,
+                        &[(campaigns.2, UnifiedNum::from(300))],
+                        Duration::from_secs(60)
+                    )
+                    .await
+                    .expect("Should reserve"),
+                "Should include only the reservation which has not expired"
+            );
+        }
+
+        #[test]
+        fn missing_remaining_lists_the_campaigns() {
+            let campaigns = (CampaignId::new(), CampaignId::new());
+            let multiple = MultipleRemaining {
+                found: vec![(DUMMY_CAMPAIGN.id, UnifiedNum::from(100))],
+                missing: vec![campaigns.0, campaigns.1],
+            };
+
+            let error = multiple
+                .ensure_found()
+                .expect_err("Should have missing Campaigns");
+            assert_eq!(MissingRemaining(vec![campaigns.0, campaigns.1]), error);
+            assert_eq!(
+                format!(
+                    "Missing remaining budget for Campaigns: {}, {}",
+                    campaigns.0, campaigns.1
+                ),
+                error.to_string()
+            );
+
+            let all_found = MultipleRemaining {
+                found: vec![(DUMMY_CAMPAIGN.id, UnifiedNum::from(100))],
+                missing: vec![],
+            };
+            assert_eq!(
+                Ok(all_found.clone()),
+                all_found.clone().ensure_found(),
+                "Should not error without missing Campaigns"
+            );
+        }
+
+        #[tokio::test]
+        async fn key_prefixes_isolate_campaigns_remaining() {
+            let redis = TESTS_POOL.get().await.expect("Should return Object");
+
+            let staging =
+                CampaignRemaining::new(redis.connection.clone(), RedisKeys::new("staging"));
+            let dev = CampaignRemaining::new(redis.connection.clone(), RedisKeys::new("dev"));
+
+            let campaign = DUMMY_CAMPAIGN.id;
+            assert_ne!(staging.get_key(campaign), dev.get_key(campaign));
+
+            assert!(staging
+                .set_initial(campaign, UnifiedNum::from(1_000))
+                .await
+                .expect("Should set value in redis"));
+            assert_eq!(
+                None,
+                dev.get_remaining_opt(campaign)
+                    .await
+                    .expect("Should get remaining"),
+                "The remaining of another prefix should not be visible"
+            );
+
+            // each prefix sets its own initial remaining
+            assert!(dev
+                .set_initial(campaign, UnifiedNum::from(300))
+                .await
+                .expect("Should set value in redis"));
+            assert_eq!(
+                700,
+                staging
+                    .decrease_by(campaign, UnifiedNum::from(300))
+                    .await
+                    .expect("Should decrease remaining")
+            );
+
+            assert_eq!(
+                vec![(campaign, UnifiedNum::from(700))],
+                staging
+                    .get_multiple(&[campaign])
+                    .await
+                    .expect("Should get multiple")
+                    .found
+            );
+            assert_eq!(
+                vec![(campaign, UnifiedNum::from(300))],
+                dev.get_multiple(&[campaign])
+                    .await
+                    .expect("Should get multiple")
+                    .found
+            );
+
+            assert_eq!(
+                300,
+                dev.getset_remaining_to_zero(campaign)
+                    .await
+                    .expect("Should getset remaining")
+            );
+            assert_eq!(
+                Some(700),
+                staging
+                    .get_remaining_opt(campaign)
+                    .await
+                    .expect("Should get remaining")
             );
         }
     }
@@ -590,9 +1440,11 @@ mod campaign_remaining {
 mod test {
     use crate::db::{
         insert_channel,
+        redis_pool::{Manager, TESTS_POOL},
         tests_postgres::{setup_test_migrations, DATABASE_POOL},
     };
     use chrono::TimeZone;
+    use futures::future::ready;
     use primitives::{
         campaign,
         campaign::Validators,
@@ -607,10 +1459,35 @@ mod test {
         EventSubmission, UnifiedNum, ValidatorDesc, ValidatorId,
     };
     use std::time::Duration;
+    use tokio::time::timeout;
     use tokio_postgres::error::SqlState;
 
     use super::*;
 
+    #[tokio::test]
+    async fn publish_campaign_closed_notifies_the_subscribers() {
+        let redis = TESTS_POOL.get().await.expect("Should get redis connection");
+
+        let subscriber = subscribe_campaign_closed(Manager::URL, &RedisKeys::default())
+            .await
+            .expect("Should subscribe");
+
+        publish_campaign_closed(&redis.connection, &RedisKeys::default(), DUMMY_CAMPAIGN.id)
+            .await
+            .expect("Should publish");
+
+        // pub/sub is shared between the Redis databases,
+        // so other tests might publish notifications as well
+        let received = timeout(
+            Duration::from_secs(5),
+            Box::pin(subscriber.filter(|campaign| ready(campaign == &DUMMY_CAMPAIGN.id))).next(),
+        )
+        .await
+        .expect("Should receive the notification in time");
+
+        assert_eq!(Some(DUMMY_CAMPAIGN.id), received);
+    }
+
     #[tokio::test]
     async fn it_inserts_fetches_and_updates_a_campaign() {
         let database = DATABASE_POOL.get().await.expect("Should get a DB pool");
@@ -663,10 +1540,12 @@ mod test {
                 rate_limit: Some(RateLimit {
                     limit_type: "sid".to_string(),
                     time_frame: Duration::from_millis(20_000),
+                    limit: 1,
                 }),
             };
             let new_budget = campaign.budget + UnifiedNum::from_u64(1_000_000_000);
             let modified_campaign = ModifyCampaign {
+                version: campaign.version,
                 budget: Some(new_budget),
                 validators: None,
                 title: Some("Modified Campaign".to_string()),
@@ -690,21 +1569,57 @@ mod test {
                     .into_iter()
                     .collect(),
                 ),
-                event_submission: Some(EventSubmission { allow: vec![rule] }),
+                unit_pricing_overrides: Some(
+                    [(
+                        DUMMY_AD_UNITS[0].ipfs,
+                        [(
+                            IMPRESSION,
+                            campaign::Pricing {
+                                min: 2.into(),
+                                max: 20.into(),
+                            },
+                        )]
+                        .into_iter()
+                        .collect(),
+                    )]
+                    .into(),
+                ),
+                event_submission: Some(EventSubmission {
+                    allow: vec![rule],
+                    dedup_window: None,
+                }),
+                pacing: None,
                 ad_units: Some(DUMMY_AD_UNITS.to_vec()),
                 targeting_rules: Some(Rules::new()),
             };
 
             let applied_campaign = modified_campaign.apply(campaign.clone());
 
-            let updated_campaign = update_campaign(&database.pool, &applied_campaign)
-                .await
-                .expect("should update");
+            let updated_campaign =
+                update_campaign(&database.pool, &applied_campaign, campaign.creator)
+                    .await
+                    .expect("should update")
+                    .expect("Campaign version should match");
 
             assert_eq!(
-                applied_campaign, updated_campaign,
-                "Postgres should update all modified fields"
+                Campaign {
+                    version: campaign.version + 1,
+                    ..applied_campaign.clone()
+                },
+                updated_campaign,
+                "Postgres should update all modified fields and increment the version"
             );
+
+            // Updating with the stale version should not update the Campaign
+            let stale_update = update_campaign(&database.pool, &applied_campaign, campaign.creator)
+                .await
+                .expect("Should query successfully");
+            assert_eq!(None, stale_update);
+
+            let fetched_campaign = fetch_campaign(database.pool.clone(), &campaign.id)
+                .await
+                .expect("Should fetch successfully");
+            assert_eq!(Some(updated_campaign), fetched_campaign);
         }
     }
 
@@ -1,8 +1,10 @@
-use primitives::{ChainOf, Channel, ChannelId};
+use chrono::{DateTime, Utc};
+use primitives::{spender::Spendable, CampaignId, ChainOf, Channel, ChannelId};
+use tokio_postgres::IsolationLevel;
 
 pub use list_channels::list_channels;
 
-use super::{DbPool, PoolError};
+use super::{accounting::Accounting, DbPool, PoolError};
 
 pub async fn get_channel_by_id(
     pool: &DbPool,
@@ -62,15 +64,118 @@ pub async fn insert_channel(
     Ok(Channel::from(&row))
 }
 
+/// Freezes or unfreezes the accounting of the [`Channel`].
+///
+/// While frozen, [`spend_amount()`](super::accounting::spend_amount) rejects any spending for the Channel.
+/// Returns `false` if the Channel does not exist.
+///
+/// ```sql
+/// UPDATE channels SET accounting_frozen = $2 WHERE id = $1
+/// ```
+pub async fn set_accounting_frozen(
+    pool: &DbPool,
+    channel_id: ChannelId,
+    frozen: bool,
+) -> Result<bool, PoolError> {
+    let client = pool.get().await?;
+    let statement = client
+        .prepare("UPDATE channels SET accounting_frozen = $2 WHERE id = $1")
+        .await?;
+
+    let updated = client.execute(&statement, &[&channel_id, &frozen]).await?;
+
+    Ok(updated == 1)
+}
+
+/// Whether or not the accounting of the [`Channel`] is frozen,
+/// a non-existing Channel is not frozen.
+///
+/// ```sql
+/// SELECT accounting_frozen FROM channels WHERE id = $1
+/// ```
+pub async fn is_accounting_frozen(pool: &DbPool, channel_id: ChannelId) -> Result<bool, PoolError> {
+    let client = pool.get().await?;
+    let statement = client
+        .prepare("SELECT accounting_frozen FROM channels WHERE id = $1")
+        .await?;
+
+    let row = client.query_opt(&statement, &[&channel_id]).await?;
+
+    Ok(row
+        .map(|row| row.get::<_, bool>("accounting_frozen"))
+        .unwrap_or_default())
+}
+
+/// All the accounting records of a [`Channel`] at a single point in time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelAccountingRecords {
+    /// The transaction time at which the records were fetched.
+    pub created: DateTime<Utc>,
+    pub accountings: Vec<Accounting>,
+    pub spendables: Vec<Spendable>,
+    pub campaigns: Vec<CampaignId>,
+}
+
+/// Fetches all the [`Accounting`]s, [`Spendable`]s and [`CampaignId`]s of the [`Channel`]
+/// in a single read-only transaction with the `REPEATABLE READ` isolation level,
+/// so that the records are consistent with each other.
+pub async fn fetch_channel_accounting_records(
+    pool: &DbPool,
+    channel_id: ChannelId,
+) -> Result<ChannelAccountingRecords, PoolError> {
+    let mut client = pool.get().await?;
+    let transaction = client
+        .build_transaction()
+        .isolation_level(IsolationLevel::RepeatableRead)
+        .read_only(true)
+        .start()
+        .await?;
+
+    let created = transaction.query_one("SELECT NOW()", &[]).await?.get(0);
+
+    let accountings = transaction
+        .query("SELECT channel_id, side, address, amount, updated, created FROM accounting WHERE channel_id = $1", &[&channel_id])
+        .await?
+        .iter()
+        .map(Accounting::from)
+        .collect();
+
+    let spendables = transaction
+        .query("SELECT spender, total, spendable.created, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM spendable INNER JOIN channels ON channels.id = spendable.channel_id WHERE channel_id = $1 ORDER BY spendable.created ASC", &[&channel_id])
+        .await?
+        .iter()
+        .map(Spendable::from)
+        .collect();
+
+    let campaigns = transaction
+        .query(
+            "SELECT id FROM campaigns WHERE channel_id = $1 ORDER BY created ASC",
+            &[&channel_id],
+        )
+        .await?
+        .iter()
+        .map(CampaignId::from)
+        .collect();
+
+    transaction.commit().await?;
+
+    Ok(ChannelAccountingRecords {
+        created,
+        accountings,
+        spendables,
+        campaigns,
+    })
+}
+
 mod list_channels {
     use futures::{pin_mut, TryStreamExt};
     use primitives::{
         sentry::{channel_list::ChannelListResponse, Pagination},
-        ChainId, Channel, ValidatorId,
+        Address, ChainId, Channel, ValidatorId,
     };
     use tokio_postgres::{types::ToSql, Row};
 
-    use crate::db::{DbPool, PoolError, TotalCount};
+    use crate::db::{slow_query::timed, DbPool, PoolError, TotalCount};
 
     /// Lists the `Channel`s in `ASC` order.
     ///
@@ -81,6 +186,7 @@ mod list_channels {
         skip: u64,
         limit: u32,
         validator: Option<ValidatorId>,
+        guardian: Option<Address>,
         chains: &[ChainId],
     ) -> Result<ChannelListResponse, PoolError> {
         let client = pool.get().await?;
@@ -122,6 +228,12 @@ mod list_channels {
             params_total.push(Box::new(validator) as _);
         }
 
+        if let Some(guardian) = guardian {
+            where_clauses.push(format!("guardian = ${}", params.len() + 1));
+            params.push(Box::new(guardian) as _);
+            params_total.push(Box::new(guardian) as _);
+        }
+
         // To understand why we use Order by, see Postgres Documentation: https://www.postgresql.org/docs/8.1/queries-limit.html
         let statement = if !where_clauses.is_empty() {
             format!("SELECT id, leader, follower, guardian, token, nonce, created FROM channels WHERE {} ORDER BY created ASC LIMIT {} OFFSET {}",
@@ -133,7 +245,10 @@ limit, skip)
 
         let stmt = client.prepare(&statement).await?;
 
-        let rows: Vec<Row> = client.query_raw(&stmt, params).await?.try_collect().await?;
+        let rows: Vec<Row> = timed("list_channels", async {
+            client.query_raw(&stmt, params).await?.try_collect().await
+        })
+        .await?;
 
         let channels = rows.iter().map(Channel::from).collect();
 
@@ -249,10 +364,16 @@ mod test {
         // List Channels with Chain #1337
         {
             // Check the response using only that channel's ChainId
-            let response =
-                list_channels(&database.pool, 0, 10, None, &[channel_1337.chain.chain_id])
-                    .await
-                    .expect("Should list Channels");
+            let response = list_channels(
+                &database.pool,
+                0,
+                10,
+                None,
+                None,
+                &[channel_1337.chain.chain_id],
+            )
+            .await
+            .expect("Should list Channels");
 
             assert_eq!(1, response.channels.len());
             assert_eq!(
@@ -268,6 +389,7 @@ mod test {
                 0,
                 10,
                 None,
+                None,
                 &[channel_1337.chain.chain_id, channel_1.chain.chain_id],
             )
             .await
@@ -0,0 +1,191 @@
+use chrono::Utc;
+use tokio_postgres::types::ToSql;
+
+use primitives::{
+    sentry::fees_summary::{FeesSummaryQuery, FeesSummaryRequest, ValidatorFeesSummary},
+    ValidatorId,
+};
+
+use super::{DbPool, PoolError};
+
+/// Inserts a new [`ValidatorFeesSummary`] with `created` at: [`Utc::now()`][Utc].
+///
+/// If a summary for the same (`validator`, `channel`, `period`) already exists,
+/// its `fees` and `delta` are updated instead.
+pub async fn upsert_fees_summary(
+    pool: &DbPool,
+    validator: ValidatorId,
+    request: &FeesSummaryRequest,
+) -> Result<ValidatorFeesSummary, PoolError> {
+    let client = pool.get().await?;
+
+    let stmt = client.prepare("INSERT INTO validator_fees_summaries (validator_id, channel_id, period, fees, delta, created) VALUES ($1, $2, $3, $4, $5, $6)
+    ON CONFLICT ON CONSTRAINT validator_fees_summaries_pkey DO UPDATE SET fees = EXCLUDED.fees, delta = EXCLUDED.delta
+    RETURNING validator_id, channel_id, period, fees, delta, created").await?;
+
+    let row = client
+        .query_one(
+            &stmt,
+            &[
+                &validator,
+                &request.channel,
+                &request.period,
+                &request.fees,
+                &request.delta,
+                &Utc::now(),
+            ],
+        )
+        .await?;
+
+    Ok(ValidatorFeesSummary::from(&row))
+}
+
+/// Fetches the [`ValidatorFeesSummary`]s of a validator, ordered by `channel_id`.
+///
+/// - Without a [`FeesSummaryQuery::period`] the latest summary of each Channel is returned.
+/// - With a [`FeesSummaryQuery::period`] only the summaries of this period are returned.
+///
+/// In both cases, there is at most one summary per Channel.
+pub async fn list_fees_summaries(
+    pool: &DbPool,
+    validator: ValidatorId,
+    query: &FeesSummaryQuery,
+) -> Result<Vec<ValidatorFeesSummary>, PoolError> {
+    let client = pool.get().await?;
+
+    let mut where_clauses = vec!["validator_id = $1".to_string()];
+    let mut params: Vec<&(dyn ToSql + Sync)> = vec![&validator];
+
+    if let Some(channel) = &query.channel {
+        where_clauses.push(format!("channel_id = ${}", params.len() + 1));
+        params.push(channel);
+    }
+
+    let statement = match &query.period {
+        Some(period) => {
+            where_clauses.push(format!("period = ${}", params.len() + 1));
+            params.push(period);
+
+            format!(
+                "SELECT validator_id, channel_id, period, fees, delta, created FROM validator_fees_summaries WHERE {} ORDER BY channel_id ASC",
+                where_clauses.join(" AND ")
+            )
+        }
+        None => format!(
+            "SELECT DISTINCT ON (channel_id) validator_id, channel_id, period, fees, delta, created FROM validator_fees_summaries WHERE {} ORDER BY channel_id ASC, period DESC",
+            where_clauses.join(" AND ")
+        ),
+    };
+
+    let stmt = client.prepare(&statement).await?;
+    let rows = client.query(&stmt, &params).await?;
+
+    Ok(rows.iter().map(ValidatorFeesSummary::from).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+
+    use primitives::{
+        test_util::{DUMMY_CAMPAIGN, IDS, LEADER, LEADER_2},
+        UnifiedNum,
+    };
+
+    use crate::db::{
+        insert_channel,
+        tests_postgres::{setup_test_migrations, DATABASE_POOL},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn insert_update_and_list_fees_summaries() {
+        let database = DATABASE_POOL.get().await.expect("Should get a DB pool");
+
+        setup_test_migrations(database.pool.clone())
+            .await
+            .expect("Migrations should succeed");
+
+        let channel_context = primitives::config::GANACHE_CONFIG
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+        insert_channel(&database.pool, &channel_context)
+            .await
+            .expect("Should insert channel");
+
+        let leader = IDS[&LEADER];
+        let first_period = Utc.with_ymd_and_hms(2022, 12, 1, 10, 0, 0).unwrap();
+        let second_period = Utc.with_ymd_and_hms(2022, 12, 1, 11, 0, 0).unwrap();
+
+        let first = FeesSummaryRequest {
+            channel: DUMMY_CAMPAIGN.channel.id(),
+            period: first_period,
+            fees: UnifiedNum::from_u64(100),
+            delta: UnifiedNum::from_u64(100),
+        };
+        let inserted = upsert_fees_summary(&database.pool, leader, &first)
+            .await
+            .expect("Should insert");
+        assert_eq!(leader, inserted.validator);
+        assert_eq!(first.fees, inserted.fees);
+
+        // the same period is updated
+        let first_updated = FeesSummaryRequest {
+            fees: UnifiedNum::from_u64(150),
+            delta: UnifiedNum::from_u64(150),
+            ..first.clone()
+        };
+        upsert_fees_summary(&database.pool, leader, &first_updated)
+            .await
+            .expect("Should update");
+
+        let second = FeesSummaryRequest {
+            channel: DUMMY_CAMPAIGN.channel.id(),
+            period: second_period,
+            fees: UnifiedNum::from_u64(250),
+            delta: UnifiedNum::from_u64(100),
+        };
+        upsert_fees_summary(&database.pool, leader, &second)
+            .await
+            .expect("Should insert");
+
+        // latest summary
+        {
+            let summaries =
+                list_fees_summaries(&database.pool, leader, &FeesSummaryQuery::default())
+                    .await
+                    .expect("Should list");
+
+            assert_eq!(1, summaries.len());
+            assert_eq!(second_period, summaries[0].period);
+            assert_eq!(second.fees, summaries[0].fees);
+        }
+
+        // summary of a period
+        {
+            let query = FeesSummaryQuery {
+                channel: Some(DUMMY_CAMPAIGN.channel.id()),
+                period: Some(first_period),
+            };
+            let summaries = list_fees_summaries(&database.pool, leader, &query)
+                .await
+                .expect("Should list");
+
+            assert_eq!(1, summaries.len());
+            assert_eq!(first_updated.fees, summaries[0].fees);
+            assert_eq!(first_updated.delta, summaries[0].delta);
+        }
+
+        // no summaries for another validator
+        {
+            let summaries =
+                list_fees_summaries(&database.pool, IDS[&LEADER_2], &FeesSummaryQuery::default())
+                    .await
+                    .expect("Should list");
+
+            assert!(summaries.is_empty());
+        }
+    }
+}
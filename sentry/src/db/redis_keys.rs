@@ -0,0 +1,184 @@
+use std::{fmt, time::Duration};
+
+use primitives::{Address, CampaignId, ChannelId};
+
+/// Builds all the Redis keys (and Pub/Sub channels) used by Sentry.
+///
+/// The keys are namespaced with the configured prefix, i.e. `{prefix}:{key}`,
+/// which allows multiple Sentry instances to share the same Redis database.
+/// With an empty prefix (the default) the keys are left as they are.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RedisKeys {
+    prefix: String,
+}
+
+impl RedisKeys {
+    /// The Pub/Sub channel for the `SpendableChanged` notifications.
+    pub const SPENDABLE_CHANGED_CHANNEL: &'static str = "spendableChanged";
+    /// The Pub/Sub channel for the closed Campaigns notifications.
+    pub const CAMPAIGN_CLOSED_CHANNEL: &'static str = "campaignClosed";
+
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// A pattern matching all the keys of this prefix, e.g. for `SCAN`.
+    ///
+    /// Returns `None` for an empty prefix.
+    pub fn pattern(&self) -> Option<String> {
+        (!self.prefix.is_empty()).then(|| self.key("*"))
+    }
+
+    fn key(&self, key: impl fmt::Display) -> String {
+        if self.prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}:{}", self.prefix, key)
+        }
+    }
+
+    /// The remaining budget of the Campaign.
+    pub fn campaign_remaining(&self, campaign: CampaignId) -> String {
+        self.key(format_args!("campaignRemaining:{}", campaign))
+    }
+
+    /// The amount spent by the Campaign in the current UTC day, see [`Pacing`](primitives::campaign::Pacing).
+    pub fn campaign_daily_spent(&self, campaign: CampaignId) -> String {
+        self.key(format_args!("campaignDailySpent:{}", campaign))
+    }
+
+    /// The deadlines of the budget reservations of the Campaigns which the creator
+    /// is creating in the Channel, a sorted set of the Campaigns scored by the deadline in milliseconds.
+    pub fn campaign_reservations(&self, channel: ChannelId, creator: Address) -> String {
+        self.key(format_args!("campaignReservations:{}:{}", channel, creator))
+    }
+
+    /// The reserved budgets of the Campaigns in [`RedisKeys::campaign_reservations`].
+    pub fn campaign_reservation_budgets(&self, channel: ChannelId, creator: Address) -> String {
+        self.key(format_args!(
+            "campaignReservationBudgets:{}:{}",
+            channel, creator
+        ))
+    }
+
+    /// The rate limit of the Campaign of the given type and timeframe
+    /// for the given `uid` or IP address.
+    ///
+    /// The key holds the number of submissions within the timeframe and expires with it,
+    /// so each type & timeframe is counted separately, e.g. a Campaign `uid` limit and the global `ip` one,
+    /// or two `ip` limits with different timeframes.
+    pub fn rate_limit(
+        &self,
+        campaign: CampaignId,
+        limit_type: &str,
+        time_frame: Duration,
+        uid_or_ip: &str,
+    ) -> String {
+        self.key(format_args!(
+            "adexRateLimit:{}:{}:{}:{}",
+            hex::encode(campaign),
+            limit_type,
+            time_frame.as_millis(),
+            uid_or_ip
+        ))
+    }
+
+    /// The deduplication of an event of the Campaign.
+    pub fn event_dedup(
+        &self,
+        campaign: CampaignId,
+        publisher: Address,
+        ad_unit: impl fmt::Display,
+        ad_slot: impl fmt::Display,
+        event_type: impl fmt::Display,
+    ) -> String {
+        self.key(format_args!(
+            "adexEventDedup:{}:{}:{}:{}:{}",
+            hex::encode(campaign),
+            publisher,
+            ad_unit,
+            ad_slot,
+            event_type
+        ))
+    }
+
+    /// A paid click of the Campaign to which a later conversion can be attributed.
+    pub fn click_attribution(
+        &self,
+        campaign: CampaignId,
+        publisher: Address,
+        ad_unit: impl fmt::Display,
+        ad_slot: impl fmt::Display,
+        click_id: impl fmt::Display,
+    ) -> String {
+        self.key(format_args!(
+            "adexClickAttribution:{}:{}:{}:{}:{}",
+            hex::encode(campaign),
+            publisher,
+            ad_unit,
+            ad_slot,
+            click_id
+        ))
+    }
+
+    /// The cached Adapter session of the authentication token.
+    pub fn session(&self, token: &str) -> String {
+        self.key(token)
+    }
+
+    /// The result of a Channel payout of the spender made with the `Idempotency-Key`.
+    pub fn payout_idempotency(&self, channel: ChannelId, spender: Address, key: &str) -> String {
+        self.key(format_args!(
+            "channelPayout:{}:{}:{}",
+            channel, spender, key
+        ))
+    }
+
+    /// The Pub/Sub channel of the `SpendableChanged` notifications.
+    pub fn spendable_changed_channel(&self) -> String {
+        self.key(Self::SPENDABLE_CHANGED_CHANNEL)
+    }
+
+    /// The Pub/Sub channel of the closed Campaigns notifications.
+    pub fn campaign_closed_channel(&self) -> String {
+        self.key(Self::CAMPAIGN_CLOSED_CHANNEL)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use primitives::test_util::DUMMY_CAMPAIGN;
+
+    use super::*;
+
+    #[test]
+    fn keys_are_prefixed() {
+        let campaign = DUMMY_CAMPAIGN.id;
+
+        let default = RedisKeys::default();
+        assert_eq!(
+            format!("campaignRemaining:{}", campaign),
+            default.campaign_remaining(campaign)
+        );
+        assert_eq!("spendableChanged", default.spendable_changed_channel());
+        assert_eq!("campaignClosed", default.campaign_closed_channel());
+        assert_eq!(None, default.pattern());
+
+        let staging = RedisKeys::new("staging");
+        assert_eq!(
+            format!("staging:campaignRemaining:{}", campaign),
+            staging.campaign_remaining(campaign)
+        );
+        assert_eq!(
+            "staging:spendableChanged",
+            staging.spendable_changed_channel()
+        );
+        assert_eq!(Some("staging:*".to_string()), staging.pattern());
+    }
+}
@@ -0,0 +1,107 @@
+//! Logging of the slow Postgres queries.
+//!
+//! The log is off by default and it's enabled once at startup with [`set_slow_query_log`]
+//! when [`SentryConfig.slow_query_threshold`](primitives::config::SentryConfig::slow_query_threshold) is set.
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::OnceCell;
+use slog::{warn, Logger};
+
+static SLOW_QUERY_LOG: OnceCell<SlowQueryLog> = OnceCell::new();
+
+/// Logs the queries which take longer than the `threshold`.
+#[derive(Debug, Clone)]
+pub struct SlowQueryLog {
+    logger: Logger,
+    threshold: Duration,
+}
+
+impl SlowQueryLog {
+    pub fn new(logger: Logger, threshold: Duration) -> Self {
+        Self { logger, threshold }
+    }
+
+    /// Runs the `query` and logs its `label` and the elapsed time if it exceeds the threshold.
+    pub async fn timed<F: Future>(&self, label: &str, query: F) -> F::Output {
+        let start = Instant::now();
+        let output = query.await;
+        let elapsed = start.elapsed();
+
+        if elapsed > self.threshold {
+            warn!(&self.logger, "Slow query"; "query" => label, "elapsed_ms" => elapsed.as_millis() as u64, "threshold_ms" => self.threshold.as_millis() as u64);
+        }
+
+        output
+    }
+}
+
+/// Enables the slow query log for all the [`timed`] queries.
+///
+/// It can be set only once, otherwise the passed log is returned.
+pub fn set_slow_query_log(log: SlowQueryLog) -> Result<(), SlowQueryLog> {
+    SLOW_QUERY_LOG.set(log)
+}
+
+/// Runs the `query` with the slow query log, if it has been set with [`set_slow_query_log`].
+pub async fn timed<F: Future>(label: &str, query: F) -> F::Output {
+    match SLOW_QUERY_LOG.get() {
+        Some(log) => log.timed(label, query).await,
+        None => query.await,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use slog::{o, Drain, OwnedKVList, Record};
+
+    use super::*;
+    use crate::db::tests_postgres::DATABASE_POOL;
+
+    /// Keeps the messages of the logged records.
+    #[derive(Clone, Default)]
+    struct Records(Arc<Mutex<Vec<String>>>);
+
+    impl Drain for Records {
+        type Ok = ();
+        type Err = slog::Never;
+
+        fn log(&self, record: &Record<'_>, _values: &OwnedKVList) -> Result<(), Self::Err> {
+            self.0
+                .lock()
+                .expect("Should lock")
+                .push(record.msg().to_string());
+
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn logs_the_queries_exceeding_the_threshold() {
+        let database = DATABASE_POOL.get().await.expect("Should get a DB pool");
+        let client = database.pool.get().await.expect("Should get a client");
+
+        let records = Records::default();
+        let log = SlowQueryLog::new(
+            Logger::root(records.clone().fuse(), o!()),
+            Duration::from_millis(50),
+        );
+
+        log.timed("fast", client.simple_query("SELECT 1"))
+            .await
+            .expect("Should run the query");
+        assert!(records.0.lock().expect("Should lock").is_empty());
+
+        log.timed("sleep", client.simple_query("SELECT pg_sleep(0.1)"))
+            .await
+            .expect("Should run the query");
+        assert_eq!(
+            vec!["Slow query".to_string()],
+            *records.0.lock().expect("Should lock")
+        );
+    }
+}
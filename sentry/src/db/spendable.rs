@@ -1,8 +1,14 @@
 use crate::db::TotalCount;
-use chrono::Utc;
-use primitives::{sentry::Pagination, spender::Spendable, Address, ChannelId};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use primitives::{
+    sentry::Pagination,
+    spender::{Spendable, SpendableChanged, SpendableHistory, SpendableSource},
+    Address, ChannelId, UnifiedNum,
+};
+use redis::{aio::MultiplexedConnection, IntoConnectionInfo};
 
-use super::{DbPool, PoolError};
+use super::{slow_query::timed, DbPool, PoolError, RedisError, RedisKeys};
 
 /// ```text
 /// INSERT INTO spendable (spender, channel_id, total, created)
@@ -48,21 +54,43 @@ pub async fn fetch_spendable(
     Ok(row.as_ref().map(Spendable::from))
 }
 
+/// Fetches the [`Spendable`]s of the given spenders for a Channel.
+///
+/// Spenders without a [`Spendable`] are skipped.
+pub async fn fetch_spendables_for_spenders(
+    pool: DbPool,
+    channel_id: &ChannelId,
+    spenders: &[Address],
+) -> Result<Vec<Spendable>, PoolError> {
+    let client = pool.get().await?;
+    let statement = client.prepare("SELECT spender, total, spendable.created, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM spendable INNER JOIN channels ON channels.id = spendable.channel_id WHERE channel_id = $1 AND spender = ANY($2)").await?;
+
+    let rows = client.query(&statement, &[channel_id, &spenders]).await?;
+
+    Ok(rows.iter().map(Spendable::from).collect())
+}
+
+/// Spendables with a zero total deposit are excluded, unless `include_empty` is set.
 pub async fn get_all_spendables_for_channel(
     pool: DbPool,
     channel_id: &ChannelId,
     skip: u64,
     limit: u64,
+    include_empty: bool,
 ) -> Result<(Vec<Spendable>, Pagination), PoolError> {
     let client = pool.get().await?;
-    let query = format!("SELECT spender, total, spendable.created, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM spendable INNER JOIN channels ON channels.id = spendable.channel_id WHERE channel_id = $1 ORDER BY spendable.created ASC LIMIT {} OFFSET {}", limit, skip);
+    let query = format!("SELECT spender, total, spendable.created, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM spendable INNER JOIN channels ON channels.id = spendable.channel_id WHERE channel_id = $1 AND ($2 OR total > 0) ORDER BY spendable.created ASC LIMIT {} OFFSET {}", limit, skip);
 
     let statement = client.prepare(&query).await?;
 
-    let rows = client.query(&statement, &[channel_id]).await?;
+    let rows = timed(
+        "get_all_spendables_for_channel",
+        client.query(&statement, &[channel_id, &include_empty]),
+    )
+    .await?;
     let spendables = rows.iter().map(Spendable::from).collect();
 
-    let total_count = list_spendable_total_count(&pool, channel_id).await?;
+    let total_count = list_spendable_total_count(&pool, channel_id, include_empty).await?;
 
     // fast ceil for total_pages
     let total_pages = if total_count == 0 {
@@ -81,12 +109,31 @@ pub async fn get_all_spendables_for_channel(
 
 static UPDATE_SPENDABLE_STATEMENT: &str = "WITH inserted_spendable AS (INSERT INTO spendable(spender, channel_id, total, created) VALUES($1, $2, $3, $4) ON CONFLICT ON CONSTRAINT spendable_pkey DO UPDATE SET total = $3 WHERE spendable.spender = $1 AND spendable.channel_id = $2 RETURNING *) SELECT inserted_spendable.*, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM inserted_spendable INNER JOIN channels ON inserted_spendable.channel_id = channels.id";
 
-// Updates spendable entry deposit or inserts a new spendable entry if it doesn't exist
-pub async fn update_spendable(pool: DbPool, spendable: &Spendable) -> Result<Spendable, PoolError> {
-    let client = pool.get().await?;
-    let statement = client.prepare(UPDATE_SPENDABLE_STATEMENT).await?;
+// Updates spendable entry deposit or inserts a new spendable entry if it doesn't exist.
+//
+// When the total deposit changes, it is recorded in the `spendable_history`
+// with the given `source` in the same transaction and a `SpendableChanged` notification
+// is published on the `RedisKeys::spendable_changed_channel()`.
+pub async fn update_spendable(
+    pool: DbPool,
+    redis: &MultiplexedConnection,
+    redis_keys: &RedisKeys,
+    spendable: &Spendable,
+    source: SpendableSource,
+) -> Result<Spendable, PoolError> {
+    let mut client = pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    let old_total = transaction
+        .query_opt(
+            "SELECT total FROM spendable WHERE spender = $1 AND channel_id = $2 FOR UPDATE",
+            &[&spendable.spender, &spendable.channel.id()],
+        )
+        .await?
+        .map(|row| row.get::<_, UnifiedNum>("total"));
 
-    let row = client
+    let statement = transaction.prepare(UPDATE_SPENDABLE_STATEMENT).await?;
+    let row = transaction
         .query_one(
             &statement,
             &[
@@ -98,22 +145,238 @@ pub async fn update_spendable(pool: DbPool, spendable: &Spendable) -> Result<Spe
         )
         .await?;
 
-    Ok(Spendable::from(&row))
+    let updated = Spendable::from(&row);
+    let is_changed = old_total != Some(updated.deposit.total);
+
+    if is_changed {
+        transaction
+            .execute(
+                INSERT_SPENDABLE_HISTORY_STATEMENT,
+                &[
+                    &updated.spender,
+                    &updated.channel.id(),
+                    &old_total,
+                    &updated.deposit.total,
+                    &source,
+                    &Utc::now(),
+                ],
+            )
+            .await?;
+    }
+
+    transaction.commit().await?;
+
+    if is_changed {
+        let spendable_changed = SpendableChanged {
+            channel_id: updated.channel.id(),
+            spender: updated.spender,
+        };
+        // publishing is best-effort, the spendable has already been updated
+        let _result = publish_spendable_changed(redis, redis_keys, spendable_changed).await;
+    }
+
+    Ok(updated)
+}
+
+static INSERT_SPENDABLE_IF_MISSING_STATEMENT: &str = "WITH inserted_spendable AS (INSERT INTO spendable(spender, channel_id, total, created) VALUES($1, $2, $3, $4) ON CONFLICT ON CONSTRAINT spendable_pkey DO NOTHING RETURNING *) SELECT inserted_spendable.*, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM inserted_spendable INNER JOIN channels ON inserted_spendable.channel_id = channels.id";
+
+/// Inserts the spendable entry if the spender has none for the Channel yet,
+/// otherwise the existing entry is returned unchanged.
+///
+/// Concurrent inserts of the same spendable converge on a single row: the later inserts
+/// wait for the first one to commit and do nothing on conflict.
+/// Only the inserting call records the total deposit in the `spendable_history`
+/// and publishes a `SpendableChanged` notification.
+pub async fn insert_spendable_if_missing(
+    pool: DbPool,
+    redis: &MultiplexedConnection,
+    redis_keys: &RedisKeys,
+    spendable: &Spendable,
+    source: SpendableSource,
+) -> Result<Spendable, PoolError> {
+    let mut client = pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    let statement = transaction
+        .prepare(INSERT_SPENDABLE_IF_MISSING_STATEMENT)
+        .await?;
+    let inserted = transaction
+        .query_opt(
+            &statement,
+            &[
+                &spendable.spender,
+                &spendable.channel.id(),
+                &spendable.deposit.total,
+                &Utc::now(),
+            ],
+        )
+        .await?
+        .map(|row| Spendable::from(&row));
+
+    let inserted = match inserted {
+        Some(inserted) => inserted,
+        None => {
+            // the conflicting insert has been committed,
+            // so the new statement sees the existing row
+            let existing = transaction.query_one("SELECT spender, total, spendable.created, channels.leader, channels.follower, channels.guardian, channels.token, channels.nonce FROM spendable INNER JOIN channels ON channels.id = spendable.channel_id WHERE spender = $1 AND channel_id = $2", &[&spendable.spender, &spendable.channel.id()]).await?;
+            transaction.commit().await?;
+
+            return Ok(Spendable::from(&existing));
+        }
+    };
+
+    transaction
+        .execute(
+            INSERT_SPENDABLE_HISTORY_STATEMENT,
+            &[
+                &inserted.spender,
+                &inserted.channel.id(),
+                &None::<UnifiedNum>,
+                &inserted.deposit.total,
+                &source,
+                &Utc::now(),
+            ],
+        )
+        .await?;
+
+    transaction.commit().await?;
+
+    let spendable_changed = SpendableChanged {
+        channel_id: inserted.channel.id(),
+        spender: inserted.spender,
+    };
+    // publishing is best-effort, the spendable has already been inserted
+    let _result = publish_spendable_changed(redis, redis_keys, spendable_changed).await;
+
+    Ok(inserted)
+}
+
+static INSERT_SPENDABLE_HISTORY_STATEMENT: &str = "INSERT INTO spendable_history (spender, channel_id, old_total, new_total, source, created) VALUES ($1, $2, $3, $4, $5, $6)";
+
+/// Gets the changes of the total deposit of the spender in a Channel, oldest first.
+///
+/// ```text
+/// SELECT old_total, new_total, source, created FROM spendable_history WHERE channel_id = $1 AND spender = $2 ORDER BY id ASC LIMIT {} OFFSET {}
+/// ```
+pub async fn get_spendable_history(
+    pool: &DbPool,
+    channel_id: &ChannelId,
+    spender: &Address,
+    skip: u64,
+    limit: u64,
+) -> Result<(Vec<SpendableHistory>, Pagination), PoolError> {
+    // a limit of 0 would divide by zero when calculating the pagination
+    let limit = limit.max(1);
+    let client = pool.get().await?;
+    let query = format!("SELECT old_total, new_total, source, created FROM spendable_history WHERE channel_id = $1 AND spender = $2 ORDER BY id ASC LIMIT {} OFFSET {}", limit, skip);
+
+    let statement = client.prepare(&query).await?;
+    let rows = timed(
+        "get_spendable_history",
+        client.query(&statement, &[channel_id, spender]),
+    )
+    .await?;
+    let history = rows.iter().map(SpendableHistory::from).collect();
+
+    let statement = client
+        .prepare("SELECT COUNT(id)::varchar FROM spendable_history WHERE channel_id = $1 AND spender = $2")
+        .await?;
+    let total_count = client
+        .query_one(&statement, &[channel_id, spender])
+        .await?
+        .get::<_, TotalCount>(0)
+        .0;
+
+    // fast ceil for total_pages
+    let total_pages = if total_count == 0 {
+        1
+    } else {
+        1 + ((total_count - 1) / limit)
+    };
+
+    let pagination = Pagination {
+        total_pages,
+        page: skip / limit,
+    };
+
+    Ok((history, pagination))
+}
+
+async fn publish_spendable_changed(
+    redis: &MultiplexedConnection,
+    redis_keys: &RedisKeys,
+    spendable_changed: SpendableChanged,
+) -> Result<(), RedisError> {
+    let message =
+        serde_json::to_string(&spendable_changed).expect("Should serialize SpendableChanged");
+
+    redis::cmd("PUBLISH")
+        .arg(redis_keys.spendable_changed_channel())
+        .arg(message)
+        .query_async::<_, ()>(&mut redis.clone())
+        .await
+}
+
+/// Subscribes to the [`SpendableChanged`] notifications published by [`update_spendable()`],
+/// e.g. for invalidating caches or re-ticking a Channel.
+///
+/// Pub/sub requires a dedicated connection, so a new one is opened to the given Redis url.
+/// Only the notifications of the same [`RedisKeys`] prefix are received.
+/// Messages which cannot be deserialized are skipped.
+pub async fn subscribe_spendable_changed(
+    redis_url: impl IntoConnectionInfo,
+    redis_keys: &RedisKeys,
+) -> Result<impl Stream<Item = SpendableChanged>, RedisError> {
+    let client = redis::Client::open(redis_url)?;
+    let mut pubsub = client.get_async_connection().await?.into_pubsub();
+    pubsub
+        .subscribe(redis_keys.spendable_changed_channel())
+        .await?;
+
+    Ok(pubsub.into_on_message().filter_map(|message| async move {
+        let payload = message.get_payload::<String>().ok()?;
+
+        serde_json::from_str::<SpendableChanged>(&payload).ok()
+    }))
 }
 
 async fn list_spendable_total_count<'a>(
     pool: &DbPool,
     channel_id: &ChannelId,
+    include_empty: bool,
 ) -> Result<u64, PoolError> {
     let client = pool.get().await?;
 
-    let statement = "SELECT COUNT(spendable)::varchar FROM spendable WHERE channel_id = $1";
+    let statement = "SELECT COUNT(spendable)::varchar FROM spendable WHERE channel_id = $1 AND ($2 OR total > 0)";
     let stmt = client.prepare(statement).await?;
-    let row = client.query_one(&stmt, &[&channel_id]).await?;
+    let row = client
+        .query_one(&stmt, &[&channel_id, &include_empty])
+        .await?;
 
     Ok(row.get::<_, TotalCount>(0).0)
 }
 
+/// Removes the [`Spendable`]s with a zero total deposit which were created before `older_than`.
+///
+/// Returns the number of removed [`Spendable`]s.
+///
+/// ```text
+/// DELETE FROM spendable WHERE total = 0 AND created < $1
+/// ```
+pub async fn delete_empty_spendables(
+    pool: &DbPool,
+    older_than: DateTime<Utc>,
+) -> Result<u64, PoolError> {
+    let client = pool.get().await?;
+    let statement = client
+        .prepare("DELETE FROM spendable WHERE total = 0 AND created < $1")
+        .await?;
+
+    let deleted = client.execute(&statement, &[&older_than]).await?;
+
+    Ok(deleted)
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
@@ -128,9 +391,11 @@ mod test {
 
     use crate::db::{
         insert_channel,
+        redis_pool::{Manager, TESTS_POOL},
         tests_postgres::{setup_test_migrations, DATABASE_POOL},
     };
-    use tokio::time::{sleep, Duration};
+    use futures::future::ready;
+    use tokio::time::{sleep, timeout, Duration};
 
     use super::*;
 
@@ -206,7 +471,7 @@ mod test {
 
         // Test for 0 records
         let (spendables, pagination) =
-            get_all_spendables_for_channel(database.clone(), &channel.id(), 0, 2)
+            get_all_spendables_for_channel(database.clone(), &channel.id(), 0, 2, false)
                 .await
                 .expect("should get result");
         assert!(spendables.is_empty());
@@ -221,7 +486,7 @@ mod test {
         sleep(Duration::from_millis(100)).await;
 
         let (spendables, pagination) =
-            get_all_spendables_for_channel(database.clone(), &channel.id(), 0, 2)
+            get_all_spendables_for_channel(database.clone(), &channel.id(), 0, 2, false)
                 .await
                 .expect("should get result");
         let expected_spendables = vec![spendable_user.clone()];
@@ -316,7 +581,7 @@ mod test {
             );
 
             let (spendables, pagination) =
-                get_all_spendables_for_channel(database.clone(), &channel.id(), skip, limit)
+                get_all_spendables_for_channel(database.clone(), &channel.id(), skip, limit, false)
                     .await
                     .unwrap_or_else(|_| panic!("could not fetch spendables {}", debug_msg));
 
@@ -329,4 +594,233 @@ mod test {
             pretty_assertions::assert_eq!(&spendables, expected_spendables);
         }
     }
+
+    #[tokio::test]
+    async fn excludes_and_deletes_empty_spendables() {
+        let database = DATABASE_POOL.get().await.expect("Should get a DB pool");
+
+        setup_test_migrations(database.pool.clone())
+            .await
+            .expect("Migrations should succeed");
+
+        let channel = DUMMY_CAMPAIGN.channel;
+        let channel_context = GANACHE_CONFIG
+            .find_chain_of(channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(channel);
+
+        insert_channel(&database, &channel_context)
+            .await
+            .expect("Should insert");
+
+        let spendable = new_spendable_with(&ADVERTISER);
+        let empty_spendable = Spendable {
+            deposit: Deposit {
+                total: UnifiedNum::ZERO,
+            },
+            ..new_spendable_with(&CREATOR)
+        };
+
+        for spendable in [&spendable, &empty_spendable] {
+            insert_spendable(database.pool.clone(), spendable)
+                .await
+                .expect("should insert spendable");
+            sleep(Duration::from_millis(100)).await;
+        }
+
+        let (spendables, pagination) =
+            get_all_spendables_for_channel(database.clone(), &channel.id(), 0, 1, false)
+                .await
+                .expect("should get result");
+        assert_eq!(vec![spendable.clone()], spendables);
+        assert_eq!(1, pagination.total_pages);
+
+        let (spendables, pagination) =
+            get_all_spendables_for_channel(database.clone(), &channel.id(), 0, 2, true)
+                .await
+                .expect("should get result");
+        assert_eq!(vec![spendable.clone(), empty_spendable.clone()], spendables);
+        assert_eq!(1, pagination.total_pages);
+
+        // not old enough
+        let deleted = delete_empty_spendables(&database, Utc::now() - chrono::Duration::hours(1))
+            .await
+            .expect("Should delete");
+        assert_eq!(0, deleted);
+
+        let deleted = delete_empty_spendables(&database, Utc::now())
+            .await
+            .expect("Should delete");
+        assert_eq!(1, deleted);
+
+        let (spendables, _pagination) =
+            get_all_spendables_for_channel(database.clone(), &channel.id(), 0, 2, true)
+                .await
+                .expect("should get result");
+        assert_eq!(vec![spendable], spendables);
+    }
+
+    #[tokio::test]
+    async fn update_spendable_publishes_spendable_changed() {
+        let database = DATABASE_POOL.get().await.expect("Should get a DB pool");
+        let redis = TESTS_POOL.get().await.expect("Should get redis connection");
+
+        setup_test_migrations(database.pool.clone())
+            .await
+            .expect("Migrations should succeed");
+
+        let channel_context = GANACHE_CONFIG
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+        insert_channel(&database.pool, &channel_context)
+            .await
+            .expect("Should insert Channel before creating spendable");
+
+        let subscriber = subscribe_spendable_changed(Manager::URL, &RedisKeys::default())
+            .await
+            .expect("Should subscribe");
+
+        let expected = SpendableChanged {
+            channel_id: DUMMY_CAMPAIGN.channel.id(),
+            spender: *GUARDIAN_2,
+        };
+        // pub/sub is shared between the Redis databases,
+        // so other tests might publish notifications as well
+        let mut subscriber = Box::pin(subscriber.filter(|message| ready(message == &expected)));
+
+        let spendable = new_spendable_with(&GUARDIAN_2);
+        update_spendable(
+            database.pool.clone(),
+            &redis.connection,
+            &RedisKeys::default(),
+            &spendable,
+            SpendableSource::WorkerRefresh,
+        )
+        .await
+        .expect("Should update spendable");
+
+        let received = timeout(Duration::from_secs(5), subscriber.next())
+            .await
+            .expect("Should receive the notification in time");
+
+        assert_eq!(Some(expected), received);
+
+        // the same deposit again
+        update_spendable(
+            database.pool.clone(),
+            &redis.connection,
+            &RedisKeys::default(),
+            &spendable,
+            SpendableSource::WorkerRefresh,
+        )
+        .await
+        .expect("Should update spendable");
+
+        assert!(
+            timeout(Duration::from_secs(1), subscriber.next())
+                .await
+                .is_err(),
+            "Should not publish a notification when the deposit is unchanged"
+        );
+    }
+
+    #[tokio::test]
+    async fn update_spendable_records_the_changes_of_the_total() {
+        let database = DATABASE_POOL.get().await.expect("Should get a DB pool");
+        let redis = TESTS_POOL.get().await.expect("Should get redis connection");
+
+        setup_test_migrations(database.pool.clone())
+            .await
+            .expect("Migrations should succeed");
+
+        let channel_context = GANACHE_CONFIG
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+        insert_channel(&database.pool, &channel_context)
+            .await
+            .expect("Should insert Channel before creating spendable");
+
+        let mut spendable = new_spendable_with(&ADVERTISER);
+        spendable.deposit.total = UnifiedNum::from_u64(160);
+        update_spendable(
+            database.pool.clone(),
+            &redis.connection,
+            &RedisKeys::default(),
+            &spendable,
+            SpendableSource::CampaignCreate,
+        )
+        .await
+        .expect("Should update spendable");
+
+        // an unchanged total is not recorded
+        update_spendable(
+            database.pool.clone(),
+            &redis.connection,
+            &RedisKeys::default(),
+            &spendable,
+            SpendableSource::WorkerRefresh,
+        )
+        .await
+        .expect("Should update spendable");
+
+        spendable.deposit.total = UnifiedNum::from_u64(150);
+        update_spendable(
+            database.pool.clone(),
+            &redis.connection,
+            &RedisKeys::default(),
+            &spendable,
+            SpendableSource::SpenderRoute,
+        )
+        .await
+        .expect("Should update spendable");
+
+        let (history, pagination) = get_spendable_history(
+            &database.pool,
+            &DUMMY_CAMPAIGN.channel.id(),
+            &ADVERTISER,
+            0,
+            10,
+        )
+        .await
+        .expect("Should get the history");
+
+        assert_eq!(
+            Pagination {
+                page: 0,
+                total_pages: 1
+            },
+            pagination
+        );
+        assert_eq!(2, history.len());
+
+        assert_eq!(None, history[0].old_total);
+        assert_eq!(UnifiedNum::from_u64(160), history[0].new_total);
+        assert_eq!(SpendableSource::CampaignCreate, history[0].source);
+
+        assert_eq!(Some(UnifiedNum::from_u64(160)), history[1].old_total);
+        assert_eq!(UnifiedNum::from_u64(150), history[1].new_total);
+        assert_eq!(SpendableSource::SpenderRoute, history[1].source);
+
+        // a limit of 0 is treated as a limit of 1
+        let (history, pagination) = get_spendable_history(
+            &database.pool,
+            &DUMMY_CAMPAIGN.channel.id(),
+            &ADVERTISER,
+            0,
+            0,
+        )
+        .await
+        .expect("Should get the history");
+
+        assert_eq!(
+            Pagination {
+                page: 0,
+                total_pages: 2
+            },
+            pagination
+        );
+        assert_eq!(1, history.len());
+    }
 }
@@ -1,14 +1,22 @@
-use chrono::Utc;
-use tokio_postgres::types::ToSql;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tokio_postgres::{
+    types::{Json, ToSql},
+    Row,
+};
 
 use primitives::{
     balances::BalancesState,
-    sentry::{message::MessageResponse, validator_messages::ValidatorMessage},
-    validator::{ApproveState, Heartbeat, MessageType, MessageTypes, NewState},
+    sentry::{
+        message::{Message, MessageResponse},
+        validator_messages::{MessagesCursor, QuarantinedMessage, ValidatorMessage},
+        ApprovedState,
+    },
+    validator::{ApproveState, Heartbeat, MessageType, MessageTypes, NewState, Type},
     Channel, ChannelId, ValidatorId,
 };
 
-use super::{DbPool, PoolError};
+use super::{slow_query::timed, DbPool, PoolError};
 
 /// Inserts a new validator [`MessageTypes`] using the `from` [`ValidatorId`] and `received` at: [`Utc::now()`][Utc]
 pub async fn insert_validator_message(
@@ -55,17 +63,87 @@ pub async fn get_validator_messages(
     add_message_types_params(&mut where_clauses, &mut params, message_types);
 
     let statement = format!(
-        r#"SELECT "from", msg, received FROM validator_messages WHERE {} ORDER BY received DESC LIMIT {}"#,
+        r#"SELECT "from", msg, received FROM validator_messages WHERE {} ORDER BY received DESC, id DESC LIMIT {}"#,
         where_clauses.join(" AND "),
         limit
     );
     let select = client.prepare(&statement).await?;
-    let results = client.query(&select, params.as_slice()).await?;
+    let results = timed(
+        "get_validator_messages",
+        client.query(&select, params.as_slice()),
+    )
+    .await?;
     let messages = results.iter().map(ValidatorMessage::from).collect();
 
     Ok(messages)
 }
 
+/// Retrieves [`ValidatorMessage`]s for a given [`Channel`] ordered oldest-first,
+/// filters them by the `message_types` and optionally,
+/// filters them by the provided `from` [`ValidatorId`].
+///
+/// Only the messages received at or after `since` and after the `cursor` are returned.
+/// The ordering is stable (`received ASC, id ASC`), so no message is skipped or duplicated
+/// across the pages while new messages arrive.
+///
+/// Returns the [`MessagesCursor`] of the last message when the page is full.
+pub async fn get_validator_messages_oldest_first(
+    pool: &DbPool,
+    channel_id: &ChannelId,
+    validator_id: &Option<ValidatorId>,
+    message_types: &[MessageType],
+    since: Option<DateTime<Utc>>,
+    cursor: Option<MessagesCursor>,
+    limit: u64,
+) -> Result<(Vec<ValidatorMessage>, Option<MessagesCursor>), PoolError> {
+    let client = pool.get().await?;
+
+    let mut where_clauses: Vec<String> = vec!["channel_id = $1".to_string()];
+    let mut params: Vec<&(dyn ToSql + Sync)> = vec![&channel_id];
+
+    if let Some(validator_id) = validator_id {
+        where_clauses.push(format!(r#""from" = ${}"#, params.len() + 1));
+        params.push(validator_id);
+    }
+
+    add_message_types_params(&mut where_clauses, &mut params, message_types);
+
+    if let Some(since) = since.as_ref() {
+        where_clauses.push(format!("received >= ${}", params.len() + 1));
+        params.push(since);
+    }
+
+    if let Some(cursor) = cursor.as_ref() {
+        where_clauses.push(format!(
+            "(received, id) > (${}, ${})",
+            params.len() + 1,
+            params.len() + 2
+        ));
+        params.push(&cursor.received);
+        params.push(&cursor.id);
+    }
+
+    // uses the `(channel_id, received, id)` index
+    let statement = format!(
+        r#"SELECT id, "from", msg, received FROM validator_messages WHERE {} ORDER BY received ASC, id ASC LIMIT {}"#,
+        where_clauses.join(" AND "),
+        limit
+    );
+    let select = client.prepare(&statement).await?;
+    let rows = client.query(&select, params.as_slice()).await?;
+
+    let next_cursor = match rows.last() {
+        Some(last) if rows.len() as u64 == limit => Some(MessagesCursor {
+            received: last.get("received"),
+            id: last.get("id"),
+        }),
+        _ => None,
+    };
+    let messages = rows.iter().map(ValidatorMessage::from).collect();
+
+    Ok((messages, next_cursor))
+}
+
 fn add_message_types_params<'a>(
     where_clauses: &mut Vec<String>,
     params: &mut Vec<&'a (dyn ToSql + Sync)>,
@@ -88,7 +166,7 @@ pub async fn latest_approve_state(
 ) -> Result<Option<MessageResponse<ApproveState>>, PoolError> {
     let client = pool.get().await?;
 
-    let select = client.prepare("SELECT \"from\", msg, received FROM validator_messages WHERE channel_id = $1 AND \"from\" = $2 AND msg ->> 'type' = 'ApproveState' ORDER BY received DESC LIMIT 1").await?;
+    let select = client.prepare("SELECT \"from\", msg, received FROM validator_messages WHERE channel_id = $1 AND \"from\" = $2 AND msg ->> 'type' = 'ApproveState' AND NOT quarantined ORDER BY received DESC LIMIT 1").await?;
     let rows = client
         .query(&select, &[&channel.id(), &channel.follower])
         .await?;
@@ -109,7 +187,7 @@ pub async fn latest_new_state<S: BalancesState>(
 ) -> Result<Option<MessageResponse<NewState<S>>>, PoolError> {
     let client = pool.get().await?;
 
-    let select = client.prepare("SELECT \"from\", msg, received FROM validator_messages WHERE channel_id = $1 AND \"from\" = $2 AND msg ->> 'type' = 'NewState' AND msg->> 'stateRoot' = $3 ORDER BY received DESC LIMIT 1").await?;
+    let select = client.prepare("SELECT \"from\", msg, received FROM validator_messages WHERE channel_id = $1 AND \"from\" = $2 AND msg ->> 'type' = 'NewState' AND msg->> 'stateRoot' = $3 AND NOT quarantined ORDER BY received DESC LIMIT 1").await?;
     let rows = client
         .query(&select, &[&channel.id(), &channel.leader, &state_root])
         .await?;
@@ -120,6 +198,146 @@ pub async fn latest_new_state<S: BalancesState>(
         .map_err(PoolError::Backend)
 }
 
+/// Quarantines the [`NewState`]s of the [`Channel`] leader with the given `state_root`
+/// and the [`ApproveState`]s of the follower for it.
+///
+/// Quarantined messages are excluded from [`latest_approve_state()`], [`latest_new_state()`]
+/// and [`latest_approved_states()`] until they are released with [`release_quarantined_message()`].
+/// Released messages are not quarantined again.
+///
+/// Returns the number of newly quarantined messages.
+pub async fn quarantine_state(
+    pool: &DbPool,
+    channel: &Channel,
+    state_root: &str,
+) -> Result<u64, PoolError> {
+    let client = pool.get().await?;
+
+    let update = client.prepare(r#"UPDATE validator_messages SET quarantined = true WHERE channel_id = $1 AND msg ->> 'stateRoot' = $4 AND NOT quarantined AND NOT quarantine_released
+        AND (("from" = $2 AND msg ->> 'type' = 'NewState') OR ("from" = $3 AND msg ->> 'type' = 'ApproveState'))"#).await?;
+
+    let updated = client
+        .execute(
+            &update,
+            &[
+                &channel.id(),
+                &channel.leader,
+                &channel.follower,
+                &state_root,
+            ],
+        )
+        .await?;
+
+    Ok(updated)
+}
+
+/// Retrieves the latest `limit` quarantined messages of the [`Channel`].
+///
+/// Ordered by: `received DESC`
+pub async fn get_quarantined_messages(
+    pool: &DbPool,
+    channel_id: &ChannelId,
+    limit: u64,
+) -> Result<Vec<QuarantinedMessage>, PoolError> {
+    let client = pool.get().await?;
+
+    let select = client.prepare(r#"SELECT id, "from", msg, received FROM validator_messages WHERE channel_id = $1 AND quarantined ORDER BY received DESC, id DESC LIMIT $2"#).await?;
+
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    let rows = client.query(&select, &[&channel_id, &limit]).await?;
+
+    Ok(rows.iter().map(QuarantinedMessage::from).collect())
+}
+
+/// Releases a quarantined message of the [`Channel`].
+/// Releasing a [`NewState`] releases the [`ApproveState`]s for it as well.
+///
+/// Released messages are not quarantined again by [`quarantine_state()`].
+///
+/// Returns `false` if the [`Channel`] has no quarantined message with this `id`.
+pub async fn release_quarantined_message(
+    pool: &DbPool,
+    channel_id: &ChannelId,
+    id: i64,
+) -> Result<bool, PoolError> {
+    let client = pool.get().await?;
+
+    let update = client.prepare(r#"UPDATE validator_messages SET quarantined = false, quarantine_released = true WHERE channel_id = $1 AND quarantined
+        AND (id = $2 OR (msg ->> 'type' = 'ApproveState' AND msg ->> 'stateRoot' = (
+            SELECT msg ->> 'stateRoot' FROM validator_messages WHERE channel_id = $1 AND id = $2 AND quarantined AND msg ->> 'type' = 'NewState'
+        )))"#).await?;
+    let updated = client.execute(&update, &[&channel_id, &id]).await?;
+
+    Ok(updated > 0)
+}
+
+/// Returns the latest `limit` [`ApproveState`]s of the [`Channel`] follower
+/// paired with the latest [`NewState`] of the [`Channel`] leader with the same `stateRoot`.
+///
+/// [`ApproveState`]s without a corresponding [`NewState`] are skipped.
+///
+/// Ordered by: [`ApproveState`] `received DESC`
+pub async fn latest_approved_states<S: BalancesState>(
+    pool: &DbPool,
+    channel: &Channel,
+    limit: u64,
+) -> Result<Vec<ApprovedState<S>>, PoolError> {
+    let client = pool.get().await?;
+
+    let select = client
+        .prepare(
+            r#"SELECT approve_state."from" AS approve_state_from, approve_state.msg AS approve_state_msg, approve_state.received AS approve_state_received,
+            new_state."from" AS new_state_from, new_state.msg AS new_state_msg, new_state.received AS new_state_received
+            FROM validator_messages AS approve_state
+            INNER JOIN LATERAL (
+                SELECT "from", msg, received FROM validator_messages
+                WHERE channel_id = approve_state.channel_id AND "from" = $3 AND msg ->> 'type' = 'NewState' AND msg ->> 'stateRoot' = approve_state.msg ->> 'stateRoot' AND NOT quarantined
+                ORDER BY received DESC LIMIT 1
+            ) AS new_state ON true
+            WHERE approve_state.channel_id = $1 AND approve_state."from" = $2 AND approve_state.msg ->> 'type' = 'ApproveState' AND NOT approve_state.quarantined
+            ORDER BY approve_state.received DESC LIMIT $4"#,
+        )
+        .await?;
+
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    let rows = timed(
+        "latest_approved_states",
+        client.query(
+            &select,
+            &[&channel.id(), &channel.follower, &channel.leader, &limit],
+        ),
+    )
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(ApprovedState {
+                new_state: message_response_from_row(row, "new_state")?,
+                approve_state: message_response_from_row(row, "approve_state")?,
+            })
+        })
+        .collect::<Result<_, _>>()
+        .map_err(PoolError::Backend)
+}
+
+fn message_response_from_row<T>(
+    row: &Row,
+    prefix: &str,
+) -> Result<MessageResponse<T>, tokio_postgres::Error>
+where
+    T: Type,
+    for<'de> T: Deserialize<'de>,
+{
+    Ok(MessageResponse {
+        from: row.try_get(format!("{}_from", prefix).as_str())?,
+        received: row.try_get(format!("{}_received", prefix).as_str())?,
+        // guard against mistakes from wrong Queries
+        msg: row
+            .try_get::<_, Json<Message<T>>>(format!("{}_msg", prefix).as_str())?
+            .0,
+    })
+}
+
 /// Returns the latest 2 [`Heartbeat`] messages for this [`Channel`] received `from` the [`ValidatorId`].
 ///
 /// Ordered by: `received DESC`
@@ -17,5 +17,6 @@ pub mod response;
 pub mod routes;
 pub mod spender;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "test-util"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
 pub mod test_util;
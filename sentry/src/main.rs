@@ -1,7 +1,7 @@
 #![deny(clippy::all)]
 #![deny(rust_2018_idioms)]
 
-use std::{env, net::SocketAddr, path::PathBuf};
+use std::{env, net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
 
 use clap::{crate_version, value_parser, Arg, Command};
 
@@ -21,7 +21,12 @@ use sentry::{
         seed::{seed_dummy, seed_ethereum},
         EnableTls, EnvConfig,
     },
-    db::{postgres_connection, redis_connection, setup_migrations, CampaignRemaining, DbPool},
+    db::{
+        postgres_connection, redis_connection, setup_migrations,
+        slow_query::{set_slow_query_log, SlowQueryLog},
+        CampaignRemaining, DbPool, RedisKeys,
+    },
+    middleware::geo::{GeoResolver, NoopGeoResolver},
     platform::PlatformApi,
     Application,
 };
@@ -52,6 +57,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .help("path to the JSON Ethereum Keystore file")
                 .takes_value(true),
         )
+        .arg(
+            Arg::new("tokenValidity")
+                .long("tokenValidity")
+                .help("for how long an authentication token is valid, in milliseconds (ethereum adapter only)")
+                .value_parser(value_parser!(u64))
+                .takes_value(true),
+        )
         .arg(
             Arg::new("dummyIdentity")
                 .long("dummyIdentity")
@@ -92,6 +104,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             let options = adapter::ethereum::Options {
                 keystore_file: keystore_file.to_string(),
                 keystore_pwd,
+                token_validity: cli
+                    .get_one::<u64>("tokenValidity")
+                    .map(|millis| Duration::from_millis(*millis)),
             };
             let ethereum_adapter = Adapter::new(
                 adapter::Ethereum::init(options, &config)
@@ -110,6 +125,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     .expect("failed to parse dummy identity"),
                 dummy_auth_tokens: DUMMY_AUTH.clone(),
                 dummy_chains: config.chains.values().cloned().collect(),
+                failure_injection: None,
             };
 
             let dummy_adapter = Adapter::new(adapter::Dummy::init(options));
@@ -134,20 +150,44 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     let logger = new_logger("sentry");
+    // the guard should live as long as the application
+    #[cfg(feature = "tracing")]
+    let _tracing_guard = primitives::util::logging::tracing::init(logger.clone())
+        .expect("Failed to install the tracing subscriber");
 
-    let (redis, postgres) = setup_databases(&logger, &env_config).await?;
+    if let Some(threshold) = config.sentry.slow_query_threshold {
+        info!(&logger, "Slow query log enabled"; "threshold_ms" => threshold.as_millis() as u64);
+        set_slow_query_log(SlowQueryLog::new(logger.clone(), threshold))
+            .expect("Slow query log should be set only once");
+    }
+
+    let redis_keys = RedisKeys::new(env_config.redis_key_prefix.clone().unwrap_or_default());
+    let (redis, postgres) = setup_databases(&logger, &env_config, &redis_keys).await?;
 
-    let campaign_remaining = CampaignRemaining::new(redis.clone());
+    let campaign_remaining = CampaignRemaining::new(redis.clone(), redis_keys.clone());
+    let campaign_remaining = match config.sentry.campaign_remaining_chunk_size {
+        Some(chunk_size) => campaign_remaining.with_chunk_size(chunk_size),
+        None => campaign_remaining,
+    };
 
     let platform_api = PlatformApi::new(
         config.sentry.platform.url.clone(),
         config.sentry.platform.keep_alive_interval,
+        config.sentry.platform.timeout,
+        config.sentry.platform.slot_cache_ttl,
     )
     .expect("Failed to build PlatformApi");
 
+    let geo_resolver = geo_resolver(&logger, &env_config);
+
     // Like dummy adapter route
     match adapter {
         AdapterTypes::Ethereum(adapter) => {
+            let whoami_signer = adapter
+                .clone()
+                .unlock()
+                .expect("Should unlock the adapter for signing whoami challenges");
+
             let app = Application::new(
                 *adapter,
                 config.clone(),
@@ -156,7 +196,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 postgres,
                 campaign_remaining,
                 platform_api,
-            );
+            )
+            .with_geo_resolver(geo_resolver)
+            .with_whoami_signer(Arc::new(whoami_signer));
 
             if env_config.seed_db && Environment::Development == env_config.env {
                 seed_ethereum(app.clone()).await?;
@@ -165,6 +207,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             app.run(enable_tls).await
         }
         AdapterTypes::Dummy(adapter) => {
+            let whoami_signer = adapter
+                .clone()
+                .unlock()
+                .expect("Should unlock the adapter for signing whoami challenges");
+
             let app = Application::new(
                 *adapter,
                 config.clone(),
@@ -173,7 +220,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 postgres,
                 campaign_remaining,
                 platform_api,
-            );
+            )
+            .with_geo_resolver(geo_resolver)
+            .with_whoami_signer(Arc::new(whoami_signer));
 
             if env_config.seed_db && Environment::Development == env_config.env {
                 seed_dummy(app.clone()).await?;
@@ -186,13 +235,41 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Creates the [`GeoResolver`] from the [`EnvConfig::geoip_database`].
+///
+/// Without a database (or the `geoip` feature) the [`NoopGeoResolver`] is used.
+fn geo_resolver(logger: &slog::Logger, env_config: &EnvConfig) -> Arc<dyn GeoResolver> {
+    match &env_config.geoip_database {
+        #[cfg(feature = "geoip")]
+        Some(database) => {
+            let resolver = sentry::middleware::geo::MaxMindGeoResolver::open(database)
+                .expect("Failed to open the GeoIP database");
+            info!(logger, "GeoIP country resolution enabled"; "database" => database.display().to_string());
+
+            Arc::new(resolver)
+        }
+        #[cfg(not(feature = "geoip"))]
+        Some(_database) => {
+            slog::warn!(
+                logger,
+                "GEOIP_DATABASE is set but Sentry is built without the `geoip` feature"
+            );
+
+            Arc::new(NoopGeoResolver)
+        }
+        None => Arc::new(NoopGeoResolver),
+    }
+}
+
 /// Setup the databases before use in the application:
 ///
 /// 1. Runs migrations on `postgres` but if [`Environment::Development`] then it runs them down first.
-/// 2. Flushes `redis` if [`Environment::Development`].
+/// 2. Flushes `redis` if [`Environment::Development`],
+///    only the keys of the [`RedisKeys`] prefix are removed if one is set.
 async fn setup_databases(
     logger: &slog::Logger,
     env_config: &EnvConfig,
+    redis_keys: &RedisKeys,
 ) -> Result<(MultiplexedConnection, DbPool), Box<dyn std::error::Error>> {
     let redis = redis_connection(env_config.redis_url.clone()).await?;
 
@@ -206,10 +283,29 @@ async fn setup_databases(
 
     // clearing up redis
     if Environment::Development == env_config.env {
-        info!(&logger, "Flushing redis...");
-        redis::cmd("FLUSHDB")
-            .query_async::<_, String>(&mut redis.clone())
-            .await?;
+        match redis_keys.pattern() {
+            Some(pattern) => {
+                info!(&logger, "Flushing redis keys with prefix..."; "prefix" => redis_keys.prefix());
+                let keys = redis::cmd("KEYS")
+                    .arg(pattern)
+                    .query_async::<_, Vec<String>>(&mut redis.clone())
+                    .await?;
+
+                // `DEL` fails on empty keys
+                if !keys.is_empty() {
+                    redis::cmd("DEL")
+                        .arg(keys)
+                        .query_async::<_, ()>(&mut redis.clone())
+                        .await?;
+                }
+            }
+            None => {
+                info!(&logger, "Flushing redis...");
+                redis::cmd("FLUSHDB")
+                    .query_async::<_, String>(&mut redis.clone())
+                    .await?;
+            }
+        }
     }
 
     // use the environmental variables to setup the Postgres connection
@@ -4,3 +4,7 @@
 pub mod auth;
 pub mod campaign;
 pub mod channel;
+pub mod etag;
+pub mod geo;
+#[cfg(feature = "tracing")]
+pub mod trace;
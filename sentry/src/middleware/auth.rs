@@ -1,15 +1,28 @@
-use std::sync::Arc;
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
 
 use axum::{
+    extract::ConnectInfo,
     http::{
-        header::{AUTHORIZATION, REFERER},
+        header::{AUTHORIZATION, REFERER, USER_AGENT},
         Request,
     },
     middleware::Next,
 };
+use chrono::Utc;
+use slog::warn;
+use woothee::{parser::Parser, woothee::VALUE_UNKNOWN};
 
-use adapter::{prelude::*, primitives::Session as AdapterSession};
-use primitives::{analytics::AuthenticateAs, ValidatorId};
+use adapter::{
+    prelude::*,
+    primitives::{Scope, Session as AdapterSession},
+};
+use primitives::{
+    analytics::{AuthenticateAs, OperatingSystem},
+    ValidatorId,
+};
 
 use crate::{response::ResponseError, Application, Auth, Session};
 
@@ -45,21 +58,49 @@ pub async fn authentication_required<C: Locked + 'static, B>(
     }
 }
 
+/// Rejects requests authenticated with a [`Scope::ReadOnly`] token
+/// with [`ResponseError::ReadOnlyScope`].
+///
+/// It does **not** require authentication,
+/// use [`authentication_required`] for routes that require it.
+pub async fn full_scope_required<B>(
+    request: axum::http::Request<B>,
+    next: Next<B>,
+) -> Result<axum::response::Response, ResponseError> {
+    match request.extensions().get::<Auth>() {
+        Some(auth) if auth.scope == Scope::ReadOnly => Err(ResponseError::ReadOnlyScope),
+        _ => Ok(next.run(request).await),
+    }
+}
+
 /// Creates a [`Session`] and additionally [`Auth`] if a Bearer token was provided.
 ///
+/// The [`Session.os`](Session::os) is parsed from the `User-Agent` header
+/// and normalized using the [`SentryConfig.os_allowlist`](primitives::config::SentryConfig::os_allowlist).
+///
 /// Check `Authorization` header for `Bearer` scheme with `Adapter::session_from_token`.
-/// If the `Adapter` fails to create an `AdapterSession`, `ResponseError::BadRequest` will be returned.
+/// The `AdapterSession` is cached in Redis until the token validity ends,
+/// Sessions accepted within the era grace window are not cached.
+/// If the `Adapter` fails to create an `AdapterSession`, a [`ResponseError::Adapter`]
+/// based on the Adapter error kind will be returned, e.g. `401 Unauthorized` for an invalid token.
 pub async fn authenticate<C: Locked + 'static, B>(
     mut request: axum::http::Request<B>,
     next: Next<B>,
 ) -> Result<axum::response::Response, ResponseError> {
-    let (adapter, redis) = {
+    let (adapter, logger, redis, redis_keys, os_allowlist, trusted_proxies) = {
         let app = request
             .extensions()
             .get::<Arc<Application<C>>>()
             .expect("Application should always be present");
 
-        (app.adapter.clone(), app.redis.clone())
+        (
+            app.adapter.clone(),
+            app.logger.clone(),
+            app.redis.clone(),
+            app.redis_keys.clone(),
+            app.config.sentry.os_allowlist.clone(),
+            app.config.sentry.trusted_proxies.clone(),
+        )
     };
 
     let referrer = request
@@ -68,10 +109,10 @@ pub async fn authenticate<C: Locked + 'static, B>(
         .and_then(|hv| hv.to_str().ok().map(ToString::to_string));
 
     let session = Session {
-        ip: get_request_ip(&request),
+        ip: get_request_ip(&request, trusted_proxies.as_deref()),
         country: None,
         referrer_header: referrer,
-        os: None,
+        os: get_request_os(&request).map(|os| normalize_os(&os, os_allowlist.as_deref())),
     };
     request.extensions_mut().insert(session);
 
@@ -88,25 +129,48 @@ pub async fn authenticate<C: Locked + 'static, B>(
         .transpose()?;
 
     if let Some(token) = token {
-        let adapter_session = match redis::cmd("GET")
-            .arg(token)
+        let session_key = redis_keys.session(token);
+        let cached_session = redis::cmd("GET")
+            .arg(&session_key)
             .query_async::<_, Option<String>>(&mut redis.clone())
             .await?
             .and_then(|session_str| serde_json::from_str::<AdapterSession>(&session_str).ok())
-        {
+            // past the token validity, the token is verified again,
+            // e.g. it might be accepted only within the era grace window
+            .filter(|adapter_session| {
+                adapter_session
+                    .valid_until
+                    .is_none_or(|valid_until| Utc::now() < valid_until)
+            });
+
+        let adapter_session = match cached_session {
             Some(adapter_session) => adapter_session,
             None => {
                 // If there was a problem with the Session or the Token, this will error
-                // and a BadRequest response will be returned
+                // and a response based on the Adapter error kind will be returned,
+                // e.g. `401 Unauthorized` for an invalid token
                 let adapter_session = adapter.session_from_token(token).await?;
 
-                // save the Adapter Session to Redis for the next request
-                // if serde errors on deserialization this will override the value inside
-                redis::cmd("SET")
-                    .arg(token)
-                    .arg(serde_json::to_string(&adapter_session)?)
-                    .query_async(&mut redis.clone())
-                    .await?;
+                if adapter_session.era_grace {
+                    warn!(&logger, "Authentication token accepted within the era grace window"; "uid" => %adapter_session.uid, "era" => adapter_session.era);
+                } else {
+                    // save the Adapter Session to Redis for the next request
+                    // if serde errors on deserialization this will override the value inside
+                    //
+                    // Sessions within the era grace window are not saved,
+                    // so their tokens are verified on every request
+                    let mut set_session = redis::cmd("SET");
+                    set_session
+                        .arg(&session_key)
+                        .arg(serde_json::to_string(&adapter_session)?);
+                    if let Some(valid_until) = adapter_session.valid_until {
+                        // expire the saved Session together with the token validity
+                        set_session
+                            .arg("EX")
+                            .arg((valid_until - Utc::now()).num_seconds().max(1));
+                    }
+                    set_session.query_async(&mut redis.clone()).await?;
+                }
 
                 adapter_session
             }
@@ -116,6 +180,7 @@ pub async fn authenticate<C: Locked + 'static, B>(
             era: adapter_session.era,
             uid: ValidatorId::from(adapter_session.uid),
             chain: adapter_session.chain,
+            scope: adapter_session.scope,
         };
 
         request.extensions_mut().insert(auth);
@@ -168,27 +233,128 @@ pub async fn authenticate_as_publisher<B>(
     Ok(next.run(request).await)
 }
 
-/// Get's the Request IP from either `true-client-ip` or `x-forwarded-for`,
-/// splits the IPs separated by `,` (comma) and returns the first one.
-fn get_request_ip<B>(req: &Request<B>) -> Option<String> {
-    req.headers()
-        .get("true-client-ip")
-        .or_else(|| req.headers().get("x-forwarded-for"))
-        .and_then(|hv| {
-            hv.to_str()
-                // filter out empty headers
-                .map(ToString::to_string)
-                .ok()
-                .filter(|ip| !ip.is_empty())
-        })
-        .and_then(|token| {
-            token
+/// The Operating System name parsed from the `User-Agent` header, if known.
+fn get_request_os<B>(req: &Request<B>) -> Option<String> {
+    let user_agent = req.headers().get(USER_AGENT)?.to_str().ok()?;
+
+    Parser::new()
+        .parse(user_agent)
+        .filter(|parsed| parsed.os != VALUE_UNKNOWN)
+        .map(|parsed| parsed.os.to_string())
+}
+
+/// Normalizes the Operating System name to the matching allowlisted name
+/// or to [`OTHER_OS`], in order to limit the cardinality of the analytics segmentation.
+///
+/// When no allowlist is configured, [`OperatingSystem::map_os()`] is used.
+fn normalize_os(os: &str, allowlist: Option<&[String]>) -> String {
+    match allowlist {
+        Some(allowlist) => allowlist
+            .iter()
+            .find(|allowed| allowed.eq_ignore_ascii_case(os))
+            .cloned()
+            .unwrap_or_else(|| OTHER_OS.to_string()),
+        None => match OperatingSystem::map_os(os) {
+            OperatingSystem::Other => OTHER_OS.to_string(),
+            operating_system => operating_system.to_string(),
+        },
+    }
+}
+
+/// The Operating System name of any OS which is not allowlisted.
+const OTHER_OS: &str = "other";
+
+/// Returns the client IP address of the request.
+///
+/// With `trusted_proxies` set, the client IP headers are used only if the peer
+/// ([`ConnectInfo`]) is a trusted proxy, otherwise the peer IP address is returned.
+/// For `X-Forwarded-For` the last address which is not a trusted proxy is the client IP,
+/// since any preceding addresses can be spoofed by the client.
+///
+/// Without `trusted_proxies`, the headers are used regardless of the peer.
+fn get_request_ip<B>(req: &Request<B>, trusted_proxies: Option<&[IpAddr]>) -> Option<String> {
+    let peer_ip = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|connect_info| connect_info.0.ip());
+
+    let trusted_proxies = match trusted_proxies {
+        Some(trusted_proxies) => trusted_proxies,
+        None => return get_header_ip(req, &[]).or_else(|| peer_ip.map(|ip| ip.to_string())),
+    };
+
+    match peer_ip {
+        Some(peer_ip) if trusted_proxies.contains(&peer_ip) => {
+            get_header_ip(req, trusted_proxies).or_else(|| Some(peer_ip.to_string()))
+        }
+        peer_ip => peer_ip.map(|ip| ip.to_string()),
+    }
+}
+
+/// Returns the client IP from the `True-Client-IP`, `X-Real-IP` or `X-Forwarded-For` headers.
+///
+/// Without `trusted_proxies` the `True-Client-IP` & `X-Real-IP` headers take precedence
+/// and the first `X-Forwarded-For` address is the client IP.
+///
+/// With `trusted_proxies` the last `X-Forwarded-For` address which is not a trusted proxy
+/// is the client IP, since the proxies append to it, while the other headers might be
+/// passed through as sent by the client. The `True-Client-IP` & `X-Real-IP` headers
+/// are used only without an `X-Forwarded-For` and only if they are valid IP addresses.
+fn get_header_ip<B>(req: &Request<B>, trusted_proxies: &[IpAddr]) -> Option<String> {
+    let header_value = |name: &str| {
+        req.headers()
+            .get(name)
+            .and_then(|hv| hv.to_str().ok())
+            .map(str::trim)
+            // filter out empty headers
+            .filter(|value| !value.is_empty())
+    };
+
+    let single_ip = |name: &str| {
+        header_value(name)?
+            .split(',')
+            .next()
+            .map(str::trim)
+            // filter out empty IP
+            .filter(|ip| !ip.is_empty())
+    };
+
+    let forwarded_for = header_value("x-forwarded-for")
+        .map(|value| {
+            value
                 .split(',')
-                .next()
-                // filter out empty IP
+                .map(str::trim)
+                // filter out empty IPs
                 .filter(|ip| !ip.is_empty())
-                .map(ToString::to_string)
+                .collect::<Vec<_>>()
         })
+        .unwrap_or_default();
+
+    if trusted_proxies.is_empty() {
+        return single_ip("true-client-ip")
+            .or_else(|| single_ip("x-real-ip"))
+            .or_else(|| forwarded_for.first().copied())
+            .map(ToString::to_string);
+    }
+
+    if !forwarded_for.is_empty() {
+        return forwarded_for
+            .iter()
+            .rev()
+            .find(|ip| {
+                ip.parse::<IpAddr>()
+                    .ok()
+                    .is_none_or(|ip| !trusted_proxies.contains(&ip))
+            })
+            .or_else(|| forwarded_for.first())
+            .map(ToString::to_string);
+    }
+
+    ["true-client-ip", "x-real-ip"]
+        .into_iter()
+        .filter_map(single_ip)
+        .find(|ip| ip.parse::<IpAddr>().is_ok())
+        .map(ToString::to_string)
 }
 
 #[cfg(test)]
@@ -269,6 +435,7 @@ mod test {
                     era: 1,
                     uid: not_admin,
                     chain: GANACHE_1.clone(),
+                    scope: Scope::Full,
                 })
                 .body(Body::empty())
                 .expect("should never fail!");
@@ -288,6 +455,7 @@ mod test {
                     era: 1,
                     uid: admin,
                     chain: GANACHE_1.clone(),
+                    scope: Scope::Full,
                 })
                 .body(Body::empty())
                 .expect("should never fail!");
@@ -362,10 +530,11 @@ mod test {
                 .await
                 .expect("Handling the Request shouldn't have failed");
 
-            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
             let response_body =
                 serde_json::from_str::<HashMap<String, String>>(&body_to_string(response).await)
                     .expect("Should deserialize");
+            assert_eq!("adapter_authentication", response_body["code"]);
             assert_eq!("Authentication: Dummy Authentication token format should be in the format: `{Auth Token}:chain_id:{Chain Id}` but 'wrong-token' was provided", response_body["message"])
         }
     }
@@ -411,6 +580,129 @@ mod test {
         assert_eq!("Ok", body_to_string(response).await);
     }
 
+    #[tokio::test]
+    async fn cached_session_past_the_token_validity_is_verified_again() {
+        let app_guard = setup_dummy_app().await;
+        let app = Arc::new(app_guard.app);
+
+        let header_token = HeaderToken {
+            token: DUMMY_AUTH[&LEADER].clone(),
+            chain_id: GANACHE_1.chain_id,
+        };
+
+        // a stale Session of another uid, cached before the token validity ended
+        let stale_session = AdapterSession {
+            era: 0,
+            uid: *PUBLISHER,
+            chain: GANACHE_1.clone(),
+            scope: Scope::Full,
+            era_grace: false,
+            valid_until: Some(Utc::now() - chrono::Duration::minutes(1)),
+        };
+        redis::cmd("SET")
+            .arg(app.redis_keys.session(&header_token.to_string()))
+            .arg(serde_json::to_string(&stale_session).expect("Should serialize"))
+            .query_async::<_, ()>(&mut app.redis.clone())
+            .await
+            .expect("Should cache the Session");
+
+        async fn handle(Extension(auth): Extension<Auth>) -> String {
+            assert_eq!(*LEADER, auth.uid.to_address());
+
+            "Ok".into()
+        }
+
+        let mut router = Router::new()
+            .route("/", get(handle))
+            .layer(from_fn(authenticate::<Dummy, _>));
+
+        let request = Request::builder()
+            .header(AUTHORIZATION, format!("Bearer {header_token}"))
+            .extension(app.clone())
+            .body(Body::empty())
+            .unwrap();
+
+        let response = router
+            .call(request)
+            .await
+            .expect("Valid requests should succeed");
+
+        assert_eq!("Ok", body_to_string(response).await);
+    }
+
+    #[tokio::test]
+    async fn test_full_scope_required() {
+        let app_guard = setup_dummy_app().await;
+        let app = Arc::new(app_guard.app);
+
+        async fn handle() -> String {
+            "Ok".into()
+        }
+
+        let mut router = Router::new().route("/", get(handle)).layer(
+            ServiceBuilder::new()
+                .layer(from_fn(authenticate::<Dummy, _>))
+                .layer(from_fn(full_scope_required)),
+        );
+
+        let header_token = HeaderToken {
+            token: DUMMY_AUTH[&ADVERTISER].clone(),
+            chain_id: GANACHE_1.chain_id,
+        };
+
+        // No Auth - Ok
+        {
+            let request = Request::builder()
+                .extension(app.clone())
+                .body(Body::empty())
+                .expect("should never fail!");
+
+            let response = router
+                .call(request)
+                .await
+                .expect("Should make request to Router");
+            assert_eq!(StatusCode::OK, response.status());
+        }
+
+        // Full scope - Ok
+        {
+            let request = Request::builder()
+                .header(AUTHORIZATION, format!("Bearer {header_token}:scope:full"))
+                .extension(app.clone())
+                .body(Body::empty())
+                .expect("should never fail!");
+
+            let response = router
+                .call(request)
+                .await
+                .expect("Should make request to Router");
+            assert_eq!(StatusCode::OK, response.status());
+        }
+
+        // ReadOnly scope - Forbidden
+        {
+            let request = Request::builder()
+                .header(
+                    AUTHORIZATION,
+                    format!("Bearer {header_token}:scope:read_only"),
+                )
+                .extension(app.clone())
+                .body(Body::empty())
+                .expect("should never fail!");
+
+            let response = router
+                .call(request)
+                .await
+                .expect("Should make request to Router");
+            assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+            let response_body =
+                serde_json::from_str::<HashMap<String, String>>(&body_to_string(response).await)
+                    .expect("Should deserialize");
+            assert_eq!("read_only_scope", response_body["code"]);
+        }
+    }
+
     #[test]
     fn test_get_request_ip_headers() {
         let build_request = |header: &str, ips: &str| -> Request<Body> {
@@ -423,7 +715,7 @@ mod test {
         // No set headers
         {
             let request = Request::builder().body(Body::empty()).unwrap();
-            let no_headers = get_request_ip(&request);
+            let no_headers = get_request_ip(&request, None);
             assert_eq!(None, no_headers);
         }
 
@@ -432,8 +724,8 @@ mod test {
             let true_client_ip = build_request("true-client-ip", "");
             let x_forwarded_for = build_request("x-forwarded-for", "");
 
-            let actual_true_client = get_request_ip(&true_client_ip);
-            let actual_x_forwarded = get_request_ip(&x_forwarded_for);
+            let actual_true_client = get_request_ip(&true_client_ip, None);
+            let actual_x_forwarded = get_request_ip(&x_forwarded_for, None);
 
             assert_eq!(None, actual_true_client);
             assert_eq!(None, actual_x_forwarded);
@@ -444,8 +736,8 @@ mod test {
             let true_client_ip = build_request("true-client-ip", ",");
             let x_forwarded_for = build_request("x-forwarded-for", ",");
 
-            let actual_true_client = get_request_ip(&true_client_ip);
-            let actual_x_forwarded = get_request_ip(&x_forwarded_for);
+            let actual_true_client = get_request_ip(&true_client_ip, None);
+            let actual_x_forwarded = get_request_ip(&x_forwarded_for, None);
 
             assert_eq!(None, actual_true_client);
             assert_eq!(None, actual_x_forwarded);
@@ -455,7 +747,7 @@ mod test {
         {
             let ips = "120.0.0.1";
             let true_client_ip = build_request("true-client-ip", ips);
-            let actual_ips = get_request_ip(&true_client_ip);
+            let actual_ips = get_request_ip(&true_client_ip, None);
 
             assert_eq!(Some(ips.to_string()), actual_ips);
         }
@@ -464,12 +756,177 @@ mod test {
         {
             let ips = "192.168.0.1,120.0.0.1,10.0.0.10";
             let true_client_ip = build_request("x-forwarded-for", ips);
-            let actual_ips = get_request_ip(&true_client_ip);
+            let actual_ips = get_request_ip(&true_client_ip, None);
 
             assert_eq!(Some("192.168.0.1".to_string()), actual_ips);
         }
     }
 
+    #[test]
+    fn test_get_request_ip_with_trusted_proxies() {
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+        let other_proxy: IpAddr = "10.0.0.2".parse().unwrap();
+        let trusted_proxies = [proxy, other_proxy];
+
+        let request = |peer: Option<IpAddr>, headers: &[(&str, &str)]| {
+            let mut builder = Request::builder();
+            for (name, value) in headers {
+                builder = builder.header(*name, *value);
+            }
+            if let Some(peer) = peer {
+                builder = builder.extension(ConnectInfo(SocketAddr::new(peer, 45_000)));
+            }
+            builder.body(Body::empty()).expect("should never fail!")
+        };
+
+        // Trusted peer - the client IP is taken from the headers
+        {
+            let forwarded_for = request(Some(proxy), &[("x-forwarded-for", "120.0.0.1, 10.0.0.2")]);
+            assert_eq!(
+                Some("120.0.0.1".to_string()),
+                get_request_ip(&forwarded_for, Some(&trusted_proxies))
+            );
+
+            let true_client_ip = request(Some(proxy), &[("true-client-ip", "120.0.0.2")]);
+            assert_eq!(
+                Some("120.0.0.2".to_string()),
+                get_request_ip(&true_client_ip, Some(&trusted_proxies))
+            );
+
+            let real_ip = request(Some(proxy), &[("x-real-ip", "120.0.0.2")]);
+            assert_eq!(
+                Some("120.0.0.2".to_string()),
+                get_request_ip(&real_ip, Some(&trusted_proxies))
+            );
+
+            // the `X-Forwarded-For` takes precedence over the passed through headers
+            let passed_through = request(
+                Some(proxy),
+                &[
+                    ("true-client-ip", "1.1.1.1"),
+                    ("x-real-ip", "1.1.1.1"),
+                    ("x-forwarded-for", "120.0.0.1"),
+                ],
+            );
+            assert_eq!(
+                Some("120.0.0.1".to_string()),
+                get_request_ip(&passed_through, Some(&trusted_proxies))
+            );
+
+            // invalid IP addresses are ignored
+            let invalid_true_client_ip = request(
+                Some(proxy),
+                &[("true-client-ip", "localhost"), ("x-real-ip", "120.0.0.2")],
+            );
+            assert_eq!(
+                Some("120.0.0.2".to_string()),
+                get_request_ip(&invalid_true_client_ip, Some(&trusted_proxies))
+            );
+            let invalid_real_ip = request(Some(proxy), &[("x-real-ip", "localhost")]);
+            assert_eq!(
+                Some(proxy.to_string()),
+                get_request_ip(&invalid_real_ip, Some(&trusted_proxies))
+            );
+
+            // only trusted proxies in the `X-Forwarded-For`
+            let only_proxies = request(Some(proxy), &[("x-forwarded-for", "10.0.0.2, 10.0.0.1")]);
+            assert_eq!(
+                Some("10.0.0.2".to_string()),
+                get_request_ip(&only_proxies, Some(&trusted_proxies))
+            );
+
+            // a spoofed address prepended by the client to the `X-Forwarded-For` is ignored
+            let spoofed_forwarded_for = request(
+                Some(proxy),
+                &[("x-forwarded-for", "1.1.1.1, 120.0.0.1, 10.0.0.2")],
+            );
+            assert_eq!(
+                Some("120.0.0.1".to_string()),
+                get_request_ip(&spoofed_forwarded_for, Some(&trusted_proxies))
+            );
+
+            // no headers - the peer IP is used
+            let no_headers = request(Some(proxy), &[]);
+            assert_eq!(
+                Some(proxy.to_string()),
+                get_request_ip(&no_headers, Some(&trusted_proxies))
+            );
+        }
+
+        // Untrusted peer - the spoofed headers are ignored
+        {
+            let peer: IpAddr = "120.0.0.3".parse().unwrap();
+            let spoofed = request(
+                Some(peer),
+                &[
+                    ("true-client-ip", "1.1.1.1"),
+                    ("x-real-ip", "1.1.1.1"),
+                    ("x-forwarded-for", "1.1.1.1"),
+                ],
+            );
+            assert_eq!(
+                Some(peer.to_string()),
+                get_request_ip(&spoofed, Some(&trusted_proxies))
+            );
+
+            // Unknown peer
+            let unknown_peer = request(None, &[("x-forwarded-for", "1.1.1.1")]);
+            assert_eq!(None, get_request_ip(&unknown_peer, Some(&trusted_proxies)));
+        }
+
+        // No trusted proxies configured - the headers are used regardless of the peer
+        {
+            let peer: IpAddr = "120.0.0.3".parse().unwrap();
+            let forwarded_for = request(Some(peer), &[("x-forwarded-for", "120.0.0.1, 10.0.0.2")]);
+            assert_eq!(
+                Some("120.0.0.1".to_string()),
+                get_request_ip(&forwarded_for, None)
+            );
+
+            let no_headers = request(Some(peer), &[]);
+            assert_eq!(Some(peer.to_string()), get_request_ip(&no_headers, None));
+        }
+    }
+
+    #[test]
+    fn test_get_request_os_and_normalize() {
+        let linux_firefox = Request::builder()
+            .header(
+                USER_AGENT,
+                "Mozilla/5.0 (X11; Linux x86_64; rv:109.0) Gecko/20100101 Firefox/109.0",
+            )
+            .body(Body::empty())
+            .unwrap();
+
+        let os = get_request_os(&linux_firefox).expect("Should parse the OS");
+        assert_eq!("Linux", os);
+
+        // Known OS
+        {
+            assert_eq!("Linux", normalize_os(&os, None));
+            assert_eq!(
+                "Linux",
+                normalize_os("Ubuntu", None),
+                "Should map the distro"
+            );
+
+            let allowlist = vec!["linux".to_string(), "Windows".to_string()];
+            assert_eq!(
+                "linux",
+                normalize_os(&os, Some(&allowlist)),
+                "Should use the allowlisted name"
+            );
+        }
+
+        // Unknown OS
+        {
+            assert_eq!("other", normalize_os("Spoofed OS 3000", None));
+
+            let allowlist = vec!["Windows".to_string()];
+            assert_eq!("other", normalize_os(&os, Some(&allowlist)));
+        }
+    }
+
     #[tokio::test]
     async fn test_authenticate_as_advertiser_and_publisher() {
         let build_request = |auth: Option<Auth>| {
@@ -532,6 +989,7 @@ mod test {
                     era: 1,
                     uid: IDS[&ADVERTISER],
                     chain: GANACHE_1.clone(),
+                    scope: Scope::Full,
                 }));
 
                 let response = router
@@ -580,6 +1038,7 @@ mod test {
                     era: 1,
                     uid: IDS[&PUBLISHER],
                     chain: GANACHE_1.clone(),
+                    scope: Scope::Full,
                 }));
 
                 let response = router
@@ -612,6 +1071,7 @@ mod test {
             era: 1,
             uid: IDS[&ADVERTISER],
             chain: GANACHE_1.clone(),
+            scope: Scope::Full,
         };
 
         let request = Request::builder()
@@ -643,6 +1103,7 @@ mod test {
             era: 1,
             uid: IDS[&PUBLISHER],
             chain: GANACHE_1.clone(),
+            scope: Scope::Full,
         };
 
         let request = Request::builder()
@@ -49,7 +49,9 @@ where
 
     let campaign_context = config
         .find_chain_of(campaign.channel.token)
-        .ok_or_else(|| ResponseError::BadRequest("Channel token not whitelisted".to_string()))?
+        .ok_or(ResponseError::ChannelTokenNotWhitelisted(
+            campaign.channel.token,
+        ))?
         .with_campaign(campaign);
 
     // If this is an authenticated call
@@ -63,6 +65,17 @@ where
             _ => {},
         }
 
+    #[cfg(feature = "tracing")]
+    tracing::Span::current()
+        .record(
+            "campaign",
+            tracing::field::display(campaign_context.context.id),
+        )
+        .record(
+            "channel",
+            tracing::field::display(campaign_context.context.channel.id()),
+        );
+
     request_parts.extensions_mut().insert(campaign_context);
 
     let request = request_parts.try_into_request().expect("Body extracted");
@@ -107,10 +120,10 @@ mod test {
     };
     use tower::Service;
 
-    use adapter::{ethereum::test_util::GANACHE_1, Dummy};
+    use adapter::{ethereum::test_util::GANACHE_1, primitives::Scope, Dummy};
     use primitives::{
-        test_util::{CAMPAIGNS, CREATOR, IDS, PUBLISHER},
-        Campaign, ChainOf,
+        test_util::{ADDRESS_9, CAMPAIGNS, CREATOR, IDS, PUBLISHER},
+        Campaign, ChainOf, Channel,
     };
 
     use crate::{
@@ -199,6 +212,60 @@ mod test {
 
             assert_eq!(response.status(), StatusCode::OK);
         }
+
+        // existing Campaign with a Channel token which is not whitelisted in this validator
+        {
+            let not_whitelisted_token = *ADDRESS_9;
+            assert!(
+                app.config.find_chain_of(not_whitelisted_token).is_none(),
+                "The token should not be whitelisted for this test!"
+            );
+
+            let not_whitelisted_campaign = Campaign {
+                id: CAMPAIGNS[1].context.id,
+                channel: Channel {
+                    token: not_whitelisted_token,
+                    ..campaign_context.context.channel
+                },
+                ..campaign_context.context.clone()
+            };
+            let channel_context = campaign_context
+                .of_channel()
+                .with(not_whitelisted_campaign.channel);
+
+            // insert Channel & Campaign directly in the DB
+            insert_channel(&app.pool, &channel_context)
+                .await
+                .expect("Should insert Channel");
+            assert!(insert_campaign(&app.pool, &not_whitelisted_campaign)
+                .await
+                .expect("Should insert Campaign"));
+
+            let request = Request::builder()
+                .uri(format!("/{id}/test", id = not_whitelisted_campaign.id))
+                .extension(app.clone())
+                .body(Body::empty())
+                .expect("Should build Request");
+
+            let response = router
+                .call(request)
+                .await
+                .expect("Should make request to Router");
+
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+            let body = hyper::body::to_bytes(response.into_body())
+                .await
+                .expect("Should read body");
+            let error_response = serde_json::from_slice::<serde_json::Value>(&body)
+                .expect("Should deserialize error response");
+
+            assert_eq!(error_response["code"], "channel_token_not_whitelisted");
+            assert_eq!(
+                error_response["token"],
+                not_whitelisted_token.to_string().as_str()
+            );
+        }
     }
 
     #[tokio::test]
@@ -240,6 +307,7 @@ mod test {
                 era: 1,
                 uid: IDS[&PUBLISHER],
                 chain: campaign_context.chain.clone(),
+                scope: Scope::Full,
             };
             assert_ne!(
                 not_creator.uid.to_address(),
@@ -262,6 +330,7 @@ mod test {
                 era: 1,
                 uid: IDS[&campaign_context.context.creator],
                 chain: campaign_context.chain.clone(),
+                scope: Scope::Full,
             };
 
             assert_eq!(
@@ -319,6 +388,7 @@ mod test {
             era: 1,
             uid: IDS[&CREATOR],
             chain: GANACHE_1.clone(),
+            scope: Scope::Full,
         };
 
         let request = Request::builder()
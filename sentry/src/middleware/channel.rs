@@ -46,11 +46,7 @@ where
     let channel_context = app
         .config
         .find_chain_of(channel.token)
-        .ok_or_else(|| {
-            ResponseError::FailedValidation(
-                "Channel token is not whitelisted in this validator".into(),
-            )
-        })?
+        .ok_or(ResponseError::ChannelTokenNotWhitelisted(channel.token))?
         .with_channel(channel);
 
     // If this is an authenticated call
@@ -64,6 +60,12 @@ where
             _ => {},
         }
 
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record(
+        "channel",
+        tracing::field::display(channel_context.context.id()),
+    );
+
     request_parts.extensions_mut().insert(channel_context);
 
     let request = request_parts.try_into_request().expect("Body extracted");
@@ -85,9 +87,10 @@ mod test {
     use adapter::{
         dummy::Dummy,
         ethereum::test_util::{GANACHE_1, GANACHE_1337},
+        primitives::Scope,
     };
     use primitives::{
-        test_util::{CAMPAIGNS, CREATOR, IDS},
+        test_util::{ADDRESS_9, CAMPAIGNS, CREATOR, IDS},
         ChainOf, Channel,
     };
 
@@ -175,6 +178,7 @@ mod test {
                 era: 1,
                 uid: IDS[&CREATOR],
                 chain: GANACHE_1.clone(),
+                scope: Scope::Full,
             };
 
             assert_ne!(channel_context.chain, not_same_chain.chain, "The chain of the Channel should be different than the chain of the Auth for this test!");
@@ -195,6 +199,7 @@ mod test {
                 era: 1,
                 uid: IDS[&CREATOR],
                 chain: GANACHE_1337.clone(),
+                scope: Scope::Full,
             };
 
             assert_eq!(
@@ -211,5 +216,48 @@ mod test {
 
             assert_eq!(response.status(), StatusCode::OK);
         }
+
+        // existing Channel with a token which is not whitelisted in this validator
+        {
+            let not_whitelisted_token = *ADDRESS_9;
+            assert!(
+                app.config.find_chain_of(not_whitelisted_token).is_none(),
+                "The token should not be whitelisted for this test!"
+            );
+
+            let not_whitelisted_channel = Channel {
+                token: not_whitelisted_token,
+                ..channel
+            };
+
+            // insert the Channel directly in the DB
+            insert_channel(
+                &app.pool,
+                &channel_context.clone().with(not_whitelisted_channel),
+            )
+            .await
+            .expect("Should insert Channel");
+
+            let request = build_request(not_whitelisted_channel.id(), None);
+
+            let response = router
+                .call(request)
+                .await
+                .expect("Should make request to Router");
+
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+            let body = hyper::body::to_bytes(response.into_body())
+                .await
+                .expect("Should read body");
+            let error_response = serde_json::from_slice::<serde_json::Value>(&body)
+                .expect("Should deserialize error response");
+
+            assert_eq!(error_response["code"], "channel_token_not_whitelisted");
+            assert_eq!(
+                error_response["token"],
+                not_whitelisted_token.to_string().as_str()
+            );
+        }
     }
 }
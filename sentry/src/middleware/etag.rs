@@ -0,0 +1,95 @@
+//! Conditional `GET` requests using an `ETag` of the response body.
+//!
+//! Used for the routes which are polled frequently, even when nothing has changed,
+//! e.g. GET `/v5/channel/:id/accounting` & GET `/v5/channel/:id/last-approved`.
+use axum::{
+    body::{boxed, Full},
+    http::{
+        header::{ETAG, IF_NONE_MATCH},
+        HeaderMap, HeaderValue, Request, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tiny_keccak::{Hasher, Keccak};
+
+/// Sets the `ETag` header of a successful response to the keccak256 hash of its body.
+///
+/// If the request's `If-None-Match` header contains the same `ETag`,
+/// it responds with `304 Not Modified` and an empty body instead.
+pub async fn etag<B>(request: Request<B>, next: Next<B>) -> Response {
+    let if_none_match = request.headers().get(IF_NONE_MATCH).cloned();
+
+    let response = next.run(request).await;
+
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match hyper::body::to_bytes(body).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    let etag = {
+        let mut hash = [0_u8; 32];
+        let mut keccak = Keccak::v256();
+        keccak.update(&bytes);
+        keccak.finalize(&mut hash);
+
+        HeaderValue::from_str(&format!("\"{}\"", hex::encode(hash)))
+            .expect("Hex encoded hash should be a valid header value")
+    };
+
+    if if_none_match
+        .as_ref()
+        .is_some_and(|if_none_match| matches(if_none_match, &etag))
+    {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, etag);
+
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+    }
+
+    parts.headers.insert(ETAG, etag);
+
+    Response::from_parts(parts, boxed(Full::from(bytes)))
+}
+
+/// Whether the `If-None-Match` header value, a list of (possibly weak) `ETag`s or `*`,
+/// contains the `ETag`.
+fn matches(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let (if_none_match, etag) = match (if_none_match.to_str(), etag.to_str()) {
+        (Ok(if_none_match), Ok(etag)) => (if_none_match, etag),
+        _ => return false,
+    };
+
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn if_none_match_matches_the_etag() {
+        let etag = HeaderValue::from_static("\"abc\"");
+
+        for if_none_match in ["\"abc\"", "W/\"abc\"", "\"xyz\", \"abc\"", "*"] {
+            assert!(
+                matches(&HeaderValue::from_static(if_none_match), &etag),
+                "{if_none_match}"
+            );
+        }
+
+        for if_none_match in ["\"xyz\"", "abc", ""] {
+            assert!(
+                !matches(&HeaderValue::from_static(if_none_match), &etag),
+                "{if_none_match}"
+            );
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Resolves the [`Session.country`](crate::Session::country) from the request IP
+//! using the [`Application`]'s [`GeoResolver`].
+//!
+//! By default the [`NoopGeoResolver`] is used, which never resolves a country.
+//! A MaxMind (GeoIP2 / GeoLite2) database backed [`MaxMindGeoResolver`](maxmind::MaxMindGeoResolver)
+//! is available with the `geoip` feature.
+use std::{fmt, net::IpAddr, sync::Arc};
+
+use axum::{http::Request, middleware::Next};
+
+use adapter::client::Locked;
+
+use crate::{Application, Session};
+
+#[cfg(feature = "geoip")]
+#[cfg_attr(docsrs, doc(cfg(feature = "geoip")))]
+pub use maxmind::MaxMindGeoResolver;
+
+/// Resolves the country of an IP address.
+pub trait GeoResolver: fmt::Debug + Send + Sync {
+    /// Returns the ISO 3166-1 alpha-2 country code (e.g. `BG`) of the IP address, if known.
+    ///
+    /// It's called for every request, so it should not block for long.
+    fn country(&self, ip: IpAddr) -> Option<String>;
+}
+
+/// A [`GeoResolver`] which never resolves a country.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopGeoResolver;
+
+impl GeoResolver for NoopGeoResolver {
+    fn country(&self, _ip: IpAddr) -> Option<String> {
+        None
+    }
+}
+
+/// Sets the [`Session.country`](Session::country) using the [`Application`]'s [`GeoResolver`],
+/// if it's not already set and the [`Session.ip`](Session::ip) is a valid IP address.
+///
+/// Should be called after [`authenticate()`](super::auth::authenticate) which creates the [`Session`].
+pub async fn resolve_country<C: Locked + 'static, B>(
+    mut request: Request<B>,
+    next: Next<B>,
+) -> axum::response::Response {
+    let geo_resolver = request
+        .extensions()
+        .get::<Arc<Application<C>>>()
+        .expect("Application should always be present")
+        .geo_resolver
+        .clone();
+
+    if let Some(session) = request.extensions_mut().get_mut::<Session>() {
+        resolve_session_country(session, geo_resolver.as_ref());
+    }
+
+    next.run(request).await
+}
+
+fn resolve_session_country(session: &mut Session, geo_resolver: &dyn GeoResolver) {
+    if session.country.is_some() {
+        return;
+    }
+
+    session.country = session
+        .ip
+        .as_deref()
+        .and_then(|ip| ip.trim().parse::<IpAddr>().ok())
+        .and_then(|ip| geo_resolver.country(ip));
+}
+
+#[cfg(feature = "geoip")]
+mod maxmind {
+    use std::{fmt, net::IpAddr, path::Path};
+
+    use maxminddb::{geoip2, MaxMindDBError, Reader};
+
+    use super::GeoResolver;
+
+    /// A [`GeoResolver`] using a MaxMind GeoIP2 or GeoLite2 Country (or City) database
+    /// which is loaded in memory.
+    pub struct MaxMindGeoResolver {
+        reader: Reader<Vec<u8>>,
+    }
+
+    impl MaxMindGeoResolver {
+        /// Reads the whole `.mmdb` database file.
+        pub fn open(database: impl AsRef<Path>) -> Result<Self, MaxMindDBError> {
+            Ok(Self {
+                reader: Reader::open_readfile(database)?,
+            })
+        }
+    }
+
+    impl fmt::Debug for MaxMindGeoResolver {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("MaxMindGeoResolver")
+                .field("database_type", &self.reader.metadata.database_type)
+                .field("build_epoch", &self.reader.metadata.build_epoch)
+                .finish()
+        }
+    }
+
+    impl GeoResolver for MaxMindGeoResolver {
+        fn country(&self, ip: IpAddr) -> Option<String> {
+            self.reader
+                .lookup::<geoip2::Country<'_>>(ip)
+                .ok()?
+                .country?
+                .iso_code
+                .map(ToString::to_string)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, net::Ipv4Addr};
+
+    use axum::{body::Body, middleware::from_fn, routing::get, Extension, Router};
+    use tower::Service;
+
+    use adapter::dummy::Dummy;
+
+    use crate::{
+        middleware::auth::authenticate,
+        test_util::{body_to_string, setup_dummy_app},
+    };
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockGeoResolver(HashMap<IpAddr, String>);
+
+    impl GeoResolver for MockGeoResolver {
+        fn country(&self, ip: IpAddr) -> Option<String> {
+            self.0.get(&ip).cloned()
+        }
+    }
+
+    fn mock_resolver() -> MockGeoResolver {
+        MockGeoResolver(
+            [(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)), "BG".to_string())]
+                .into_iter()
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_resolve_session_country() {
+        let resolver = mock_resolver();
+        let session = |ip: Option<&str>, country: Option<&str>| Session {
+            ip: ip.map(ToString::to_string),
+            country: country.map(ToString::to_string),
+            referrer_header: None,
+            os: None,
+        };
+
+        // known IP
+        {
+            let mut known = session(Some("1.2.3.4"), None);
+            resolve_session_country(&mut known, &resolver);
+            assert_eq!(Some("BG"), known.country.as_deref());
+        }
+
+        // unknown IP, invalid IP & no IP
+        for ip in [Some("5.6.7.8"), Some("not an ip"), None] {
+            let mut unknown = session(ip, None);
+            resolve_session_country(&mut unknown, &resolver);
+            assert_eq!(None, unknown.country, "IP: {:?}", ip);
+        }
+
+        // already set country is not overridden
+        {
+            let mut already_set = session(Some("1.2.3.4"), Some("US"));
+            resolve_session_country(&mut already_set, &resolver);
+            assert_eq!(Some("US"), already_set.country.as_deref());
+        }
+
+        // the no-op resolver never resolves a country
+        {
+            let mut noop = session(Some("1.2.3.4"), None);
+            resolve_session_country(&mut noop, &NoopGeoResolver);
+            assert_eq!(None, noop.country);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_country_middleware_with_mock_resolver() {
+        let app_guard = setup_dummy_app().await;
+        let app = Arc::new(app_guard.app.with_geo_resolver(Arc::new(mock_resolver())));
+
+        async fn handle(Extension(session): Extension<Session>) -> String {
+            session.country.unwrap_or_default()
+        }
+
+        let mut router = Router::new()
+            .route("/", get(handle))
+            .layer(from_fn(resolve_country::<Dummy, _>))
+            .layer(from_fn(authenticate::<Dummy, _>));
+
+        for (ip, expected_country) in [("1.2.3.4", "BG"), ("5.6.7.8", "")] {
+            let request = Request::builder()
+                .extension(app.clone())
+                .header("x-forwarded-for", ip)
+                .body(Body::empty())
+                .expect("should never fail!");
+
+            let response = router
+                .call(request)
+                .await
+                .expect("Should make request to Router");
+
+            assert_eq!(expected_country, body_to_string(response).await);
+        }
+    }
+}
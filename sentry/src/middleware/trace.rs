@@ -0,0 +1,144 @@
+//! The `tracing` span of every request, enabled with the `tracing` feature.
+//!
+//! The spans are emitted through the Sentry logger or exported with OpenTelemetry (`otlp` feature),
+//! see [`primitives::util::logging::tracing`].
+use axum::{http::Request, middleware::Next, response::Response};
+use tracing::{field::Empty, Instrument};
+
+/// Runs the request in a `request` span with the `method` and the `route` (path) of the request.
+///
+/// The `channel` & `campaign` fields are recorded by the
+/// [`channel_load`](super::channel::channel_load) & [`campaign_load`](super::campaign::campaign_load)
+/// middlewares and the response `status` once the request has been handled.
+pub async fn trace_request<B>(request: Request<B>, next: Next<B>) -> Response {
+    let span = tracing::info_span!(
+        "request",
+        method = %request.method(),
+        route = request.uri().path(),
+        channel = Empty,
+        campaign = Empty,
+        status = Empty,
+    );
+
+    let response = next.run(request).instrument(span.clone()).await;
+    span.record("status", response.status().as_u16());
+
+    response
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashMap,
+        fmt,
+        sync::{Arc, Mutex},
+    };
+
+    use axum::{
+        body::Body,
+        http::{Method, StatusCode},
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Subscriber,
+    };
+    use tracing_subscriber::{
+        layer::{Context, SubscriberExt},
+        Layer, Registry,
+    };
+
+    use primitives::test_util::DUMMY_CAMPAIGN;
+
+    use crate::{
+        db::insert_channel,
+        test_util::{oneshot, setup_dummy_app},
+    };
+
+    use super::*;
+
+    type SpanFields = HashMap<&'static str, String>;
+
+    /// Records the name & fields of every span,
+    /// the span ids can be reused so they point to the latest span with the id.
+    #[derive(Clone, Default)]
+    struct RecordedSpans(Arc<Mutex<(Vec<(&'static str, SpanFields)>, HashMap<u64, usize>)>>);
+
+    struct FieldsVisitor<'a>(&'a mut SpanFields);
+
+    impl Visit for FieldsVisitor<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name(), value.to_owned());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.insert(field.name(), format!("{value:?}"));
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for RecordedSpans {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+            let mut fields = SpanFields::new();
+            attrs.record(&mut FieldsVisitor(&mut fields));
+
+            let (spans, ids) = &mut *self.0.lock().expect("Should lock");
+            ids.insert(id.into_u64(), spans.len());
+            spans.push((attrs.metadata().name(), fields));
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+            let (spans, ids) = &mut *self.0.lock().expect("Should lock");
+            if let Some(index) = ids.get(&id.into_u64()) {
+                values.record(&mut FieldsVisitor(&mut spans[*index].1));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn records_a_span_for_each_request() {
+        let app_guard = setup_dummy_app().await;
+        let app = app_guard.app.clone();
+
+        let channel = DUMMY_CAMPAIGN.channel;
+        let channel_context = app
+            .config
+            .find_chain_of(channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(channel);
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("Should insert Channel");
+
+        let recorded = RecordedSpans::default();
+        let _default = tracing::subscriber::set_default(Registry::default().with(recorded.clone()));
+
+        let route = format!("/v5/channel/{}/last-approved", channel.id());
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(&route)
+            .body(Body::empty())
+            .expect("Should build Request");
+
+        let response = oneshot(&app, request).await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let (spans, _ids) = &*recorded.0.lock().expect("Should lock");
+        let request_spans = spans
+            .iter()
+            .filter(|(name, _fields)| *name == "request")
+            .map(|(_name, fields)| fields)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            1,
+            request_spans.len(),
+            "A single request span should be recorded"
+        );
+        let fields = request_spans[0];
+        assert_eq!(Some(&"GET".to_string()), fields.get("method"));
+        assert_eq!(Some(&route), fields.get("route"));
+        assert_eq!(Some(&channel.id().to_string()), fields.get("channel"));
+        assert_eq!(None, fields.get("campaign"));
+        assert_eq!(Some(&"200".to_string()), fields.get("status"));
+    }
+}
@@ -1,22 +1,38 @@
-use crate::Session;
+use crate::{platform::PlatformApi, Session};
 use chrono::Utc;
+use futures::future::join_all;
 use primitives::{
+    campaign::Pricing,
     sentry::Event,
     targeting::Input,
-    targeting::{eval_with_callback, get_pricing_bounds, input, Error, Output},
-    Address, Campaign, UnifiedNum,
+    targeting::{eval_with_callback, get_unit_pricing_bounds, input, Error, Output},
+    Address, Campaign, UnifiedNum, IPFS,
+};
+use slog::{debug, error, warn, Logger};
+use std::{
+    cmp::{max, min},
+    collections::{HashMap, HashSet},
 };
-use slog::{error, Logger};
-use std::cmp::{max, min};
 
 pub type Result = std::result::Result<Option<(Address, UnifiedNum)>, Error>;
 
 /// If `None` is returned this means that the targeting rules evaluation has set `show = false`
+/// or that the Campaign's maximum price is below the AdSlot minimum price.
+///
+/// `slots_min_per_impression` are the minimum prices of the AdSlots for the Campaign's token,
+/// see [`fetch_slots_min_per_impression()`].
+/// The payout of an `IMPRESSION` event is clamped to at least the minimum price of its AdSlot.
+///
+/// `default_pricing` is the pricing of the event type from the
+/// [`Config.event_types`](primitives::Config::event_types), used when the Campaign
+/// has no pricing for the event type.
 pub fn get_payout(
     logger: &Logger,
     campaign: &Campaign,
     event: &Event,
     session: &Session,
+    slots_min_per_impression: &HashMap<IPFS, UnifiedNum>,
+    default_pricing: Option<&Pricing>,
 ) -> Result {
     let event_type = event.event_type();
 
@@ -32,13 +48,26 @@ pub fn get_payout(
             ad_unit,
             ad_slot,
             ..
+        }
+        | Event::Conversion {
+            publisher,
+            ad_unit,
+            ad_slot,
+            ..
+        }
+        | Event::Custom {
+            publisher,
+            ad_unit,
+            ad_slot,
+            ..
         } => {
             let targeting_rules = campaign.targeting_rules.clone();
 
-            let pricing = get_pricing_bounds(campaign, &event_type);
+            // the AdUnit pricing override takes precedence over the Campaign pricing bounds
+            let pricing = get_unit_pricing_bounds(campaign, &event_type, ad_unit, default_pricing);
 
-            if targeting_rules.is_empty() {
-                Ok(Some((*publisher, pricing.min)))
+            let price = if targeting_rules.is_empty() {
+                Some(pricing.min)
             } else {
                 // Find the event's AdUnit in the Campaign.
                 let ad_unit = campaign.ad_units.iter().find(|u| &u.ipfs == ad_unit);
@@ -62,40 +91,144 @@ pub fn get_payout(
                 }
                 .with_campaign(campaign.clone());
 
-                let mut output = Output {
-                    show: true,
-                    boost: 1.0,
-                    price: [(event_type, pricing.min)].into_iter().collect(),
-                };
+                // the rules can read & set the `price.{eventType}` of any event type,
+                // but only the price of the event's type is used for the payout
+                let mut output = Output::from(campaign);
+                output.price.insert(event_type, pricing.min);
 
                 let on_type_error = |error, rule| error!(logger, "Rule evaluation error for {:?}", campaign.id; "error" => ?error, "rule" => ?rule);
 
                 eval_with_callback(&targeting_rules, &input, &mut output, Some(on_type_error));
 
                 if output.show {
-                    let price = match output.price.get(&event_type) {
-                        Some(output_price) => max(pricing.min, min(pricing.max, *output_price)),
-                        None => max(pricing.min, pricing.max),
-                    };
+                    // clamp the price set by the rules to the pricing bounds of the event type
+                    let price = output
+                        .price
+                        .get(&event_type)
+                        .map(|output_price| max(pricing.min, min(pricing.max, *output_price)))
+                        .unwrap_or(pricing.min);
+
+                    if price != pricing.min {
+                        debug!(logger, "Targeting rules changed the price of the event"; "campaign" => %campaign.id, "event_type" => %event_type, "price" => %price, "pricing" => ?pricing);
+                    }
 
-                    Ok(Some((*publisher, price)))
+                    Some(price)
                 } else {
-                    Ok(None)
+                    None
                 }
-            }
+            };
+
+            let slot_min = if event.is_impression_event() {
+                slots_min_per_impression.get(ad_slot)
+            } else {
+                None
+            };
+
+            let price = price.and_then(|price| match slot_min {
+                // the Campaign cannot pay the minimum price of the AdSlot
+                Some(slot_min) if *slot_min > pricing.max => None,
+                Some(slot_min) => Some(max(price, *slot_min)),
+                None => Some(price),
+            });
+
+            Ok(price.map(|price| (*publisher, price)))
         }
     }
 }
 
+/// Fetches the [`AdSlot.min_per_impression`](primitives::AdSlot::min_per_impression)
+/// for the given `token` of the AdSlots of all `IMPRESSION` events.
+///
+/// AdSlots without a minimum price for the `token` are not included.
+/// If fetching an AdSlot from the Platform fails, the error is logged and
+/// the AdSlot is not included, i.e. the payout falls back to the Campaign pricing bounds.
+pub async fn fetch_slots_min_per_impression(
+    logger: &Logger,
+    platform_api: &PlatformApi,
+    token: Address,
+    events: &[Event],
+) -> HashMap<IPFS, UnifiedNum> {
+    let ad_slots = events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Impression { ad_slot, .. } => Some(*ad_slot),
+            Event::Click { .. } | Event::Conversion { .. } | Event::Custom { .. } => None,
+        })
+        .collect::<HashSet<_>>();
+
+    let results = join_all(ad_slots.into_iter().map(|ad_slot| async move {
+        (
+            ad_slot,
+            platform_api.fetch_slot_min_per_impression(ad_slot).await,
+        )
+    }))
+    .await;
+
+    results
+        .into_iter()
+        .filter_map(|(ad_slot, result)| match result {
+            Ok(mut min_per_impression) => min_per_impression
+                .remove(&token)
+                .map(|slot_min| (ad_slot, slot_min)),
+            Err(err) => {
+                warn!(logger, "Failed to fetch AdSlot minimum price from the Platform, falling back to the Campaign pricing bounds"; "ad_slot" => %ad_slot, "error" => ?err);
+
+                None
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use chrono::TimeZone;
     use primitives::{
-        campaign::Pricing,
-        sentry::{CLICK, IMPRESSION},
-        test_util::{discard_logger, DUMMY_CAMPAIGN, DUMMY_IPFS, LEADER, PUBLISHER},
+        platform::AdSlotResponse,
+        sentry::{ClickId, CustomEventType, EventType, CLICK, CONVERSION, IMPRESSION},
+        targeting::{Function, Rule, Rules, Value},
+        test_util::{discard_logger, DUMMY_CAMPAIGN, DUMMY_IPFS, IDS, LEADER, PUBLISHER},
+        AdSlot,
+    };
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
     };
 
+    fn campaign_with_impression_pricing(min: u64, max: u64) -> Campaign {
+        let mut campaign = DUMMY_CAMPAIGN.clone();
+        campaign.pricing_bounds = vec![(
+            IMPRESSION,
+            Pricing {
+                min: min.into(),
+                max: max.into(),
+            },
+        )]
+        .into_iter()
+        .collect();
+
+        campaign
+    }
+
+    fn impression_event() -> Event {
+        Event::Impression {
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: None,
+        }
+    }
+
+    fn empty_session() -> Session {
+        Session {
+            ip: None,
+            country: None,
+            referrer_header: None,
+            os: None,
+        }
+    }
+
     #[test]
     fn get_event_payouts_pricing_bounds_impression_event() {
         let logger = discard_logger();
@@ -135,7 +268,8 @@ mod test {
             os: None,
         };
 
-        let payout = get_payout(&logger, &campaign, &event, &session).expect("Should be OK");
+        let payout = get_payout(&logger, &campaign, &event, &session, &HashMap::new(), None)
+            .expect("Should be OK");
 
         let expected_option = Some((*LEADER, 8.into()));
         assert_eq!(expected_option, payout, "pricingBounds: impression event");
@@ -170,6 +304,7 @@ mod test {
             ad_unit: DUMMY_IPFS[0],
             ad_slot: DUMMY_IPFS[1],
             referrer: None,
+            click_id: None,
         };
 
         let session = Session {
@@ -179,9 +314,458 @@ mod test {
             os: None,
         };
 
-        let payout = get_payout(&logger, &campaign, &event, &session).expect("Should be OK");
+        let payout = get_payout(&logger, &campaign, &event, &session, &HashMap::new(), None)
+            .expect("Should be OK");
 
         let expected_option = Some((*PUBLISHER, 23.into()));
         assert_eq!(expected_option, payout, "pricingBounds: click event");
     }
+
+    /// A Campaign with IMPRESSION & CLICK pricing bounds
+    /// and a targeting rule setting the `price.{event_type}` to `price`.
+    fn campaign_with_price_rule(event_type: EventType, price: u64) -> Campaign {
+        let mut campaign = campaign_with_impression_pricing(8, 64);
+        campaign.pricing_bounds.insert(
+            CLICK,
+            Pricing {
+                min: 23.into(),
+                max: 100.into(),
+            },
+        );
+        campaign.targeting_rules = Rules(vec![Rule::Function(Function::new_set(
+            &format!("price.{}", event_type),
+            Value::UnifiedNum(price.into()),
+        ))]);
+
+        campaign
+    }
+
+    #[test]
+    fn get_event_payouts_with_click_price_rule() {
+        let logger = discard_logger();
+        let campaign = campaign_with_price_rule(CLICK, 50);
+
+        let click = Event::Click {
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: None,
+            click_id: None,
+        };
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &click,
+            &empty_session(),
+            &HashMap::new(),
+            None,
+        )
+        .expect("Should be OK");
+        assert_eq!(
+            Some((*PUBLISHER, 50.into())),
+            payout,
+            "The rule changes the CLICK price"
+        );
+
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &impression_event(),
+            &empty_session(),
+            &HashMap::new(),
+            None,
+        )
+        .expect("Should be OK");
+        assert_eq!(
+            Some((*PUBLISHER, 8.into())),
+            payout,
+            "The IMPRESSION price stays at the pricing min"
+        );
+    }
+
+    #[test]
+    fn get_event_payouts_price_rule_clamped_to_the_pricing_bounds() {
+        let logger = discard_logger();
+        let click = Event::Click {
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: None,
+            click_id: None,
+        };
+
+        // above the CLICK pricing max
+        let campaign = campaign_with_price_rule(CLICK, 1_000);
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &click,
+            &empty_session(),
+            &HashMap::new(),
+            None,
+        )
+        .expect("Should be OK");
+        assert_eq!(Some((*PUBLISHER, 100.into())), payout);
+
+        // below the CLICK pricing min
+        let campaign = campaign_with_price_rule(CLICK, 1);
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &click,
+            &empty_session(),
+            &HashMap::new(),
+            None,
+        )
+        .expect("Should be OK");
+        assert_eq!(Some((*PUBLISHER, 23.into())), payout);
+
+        // above the IMPRESSION pricing max
+        let campaign = campaign_with_price_rule(IMPRESSION, 1_000);
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &impression_event(),
+            &empty_session(),
+            &HashMap::new(),
+            None,
+        )
+        .expect("Should be OK");
+        assert_eq!(Some((*PUBLISHER, 64.into())), payout);
+    }
+
+    #[test]
+    fn get_event_payouts_with_custom_event_type_price_rule() {
+        let logger = discard_logger();
+        let sign_up = CustomEventType::new("SIGN_UP").expect("Should be valid");
+        let event = Event::Custom {
+            event_type: sign_up,
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: None,
+        };
+        let default_pricing = Pricing {
+            min: 300.into(),
+            max: 500.into(),
+        };
+
+        let campaign = campaign_with_price_rule(EventType::Custom(sign_up), 400);
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &event,
+            &empty_session(),
+            &HashMap::new(),
+            Some(&default_pricing),
+        )
+        .expect("Should be OK");
+        assert_eq!(Some((*PUBLISHER, 400.into())), payout);
+    }
+
+    #[test]
+    fn get_event_payouts_custom_event_type() {
+        let logger = discard_logger();
+        let sign_up = CustomEventType::new("SIGN_UP").expect("Should be valid");
+        let event = Event::Custom {
+            event_type: sign_up,
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: None,
+        };
+        let default_pricing = Pricing {
+            min: 300.into(),
+            max: 500.into(),
+        };
+
+        // the Campaign has no pricing for the event type, the default pricing is used
+        let campaign = campaign_with_impression_pricing(8, 64);
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &event,
+            &empty_session(),
+            &HashMap::new(),
+            Some(&default_pricing),
+        )
+        .expect("Should be OK");
+        assert_eq!(Some((*PUBLISHER, 300.into())), payout);
+
+        // the Campaign pricing takes precedence over the default pricing
+        let mut campaign = campaign;
+        campaign.pricing_bounds.insert(
+            EventType::Custom(sign_up),
+            Pricing {
+                min: 1_000.into(),
+                max: 2_000.into(),
+            },
+        );
+        // the AdSlot minimum price is only for IMPRESSION events
+        let slots_min = [(DUMMY_IPFS[1], UnifiedNum::from(1_500))]
+            .into_iter()
+            .collect();
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &event,
+            &empty_session(),
+            &slots_min,
+            Some(&default_pricing),
+        )
+        .expect("Should be OK");
+        assert_eq!(Some((*PUBLISHER, 1_000.into())), payout);
+    }
+
+    #[test]
+    fn get_event_payouts_conversion_event() {
+        let logger = discard_logger();
+        let event = Event::Conversion {
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: None,
+            click_id: ClickId::new(),
+        };
+
+        let mut campaign = campaign_with_impression_pricing(8, 64);
+        campaign.pricing_bounds.insert(
+            CONVERSION,
+            Pricing {
+                min: 5_000.into(),
+                max: 10_000.into(),
+            },
+        );
+
+        // the AdSlot minimum price is only for IMPRESSION events
+        let slots_min = [(DUMMY_IPFS[1], UnifiedNum::from(7_000))]
+            .into_iter()
+            .collect();
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &event,
+            &empty_session(),
+            &slots_min,
+            None,
+        )
+        .expect("Should be OK");
+        assert_eq!(Some((*PUBLISHER, 5_000.into())), payout);
+    }
+
+    #[test]
+    fn get_event_payouts_with_unit_pricing_override() {
+        let logger = discard_logger();
+        let mut campaign = campaign_with_impression_pricing(8, 64);
+        campaign.unit_pricing_overrides = [(
+            DUMMY_IPFS[0],
+            [(
+                IMPRESSION,
+                Pricing {
+                    min: 16.into(),
+                    max: 128.into(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        )]
+        .into();
+
+        // the overridden AdUnit pays the override min
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &impression_event(),
+            &empty_session(),
+            &HashMap::new(),
+            None,
+        )
+        .expect("Should be OK");
+        assert_eq!(Some((*PUBLISHER, 16.into())), payout);
+
+        // other AdUnits pay the Campaign pricing min
+        let other_unit = Event::Impression {
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[2],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: None,
+        };
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &other_unit,
+            &empty_session(),
+            &HashMap::new(),
+            None,
+        )
+        .expect("Should be OK");
+        assert_eq!(Some((*PUBLISHER, 8.into())), payout);
+    }
+
+    #[test]
+    fn get_event_payouts_clamped_to_the_slot_min_per_impression() {
+        let logger = discard_logger();
+        let campaign = campaign_with_impression_pricing(8, 64);
+        let event = impression_event();
+
+        // within the Campaign pricing bounds
+        let slots_min = [(DUMMY_IPFS[1], UnifiedNum::from(20))]
+            .into_iter()
+            .collect();
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &event,
+            &empty_session(),
+            &slots_min,
+            None,
+        )
+        .expect("Should be OK");
+        assert_eq!(Some((*PUBLISHER, 20.into())), payout);
+
+        // below the Campaign pricing min
+        let slots_min = [(DUMMY_IPFS[1], UnifiedNum::from(2))].into_iter().collect();
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &event,
+            &empty_session(),
+            &slots_min,
+            None,
+        )
+        .expect("Should be OK");
+        assert_eq!(Some((*PUBLISHER, 8.into())), payout);
+
+        // the minimum price is only for IMPRESSION events
+        let click = Event::Click {
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: None,
+            click_id: None,
+        };
+        let mut campaign = campaign;
+        campaign.pricing_bounds.insert(
+            CLICK,
+            Pricing {
+                min: 1.into(),
+                max: 4.into(),
+            },
+        );
+        let slots_min = [(DUMMY_IPFS[1], UnifiedNum::from(20))]
+            .into_iter()
+            .collect();
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &click,
+            &empty_session(),
+            &slots_min,
+            None,
+        )
+        .expect("Should be OK");
+        assert_eq!(Some((*PUBLISHER, 1.into())), payout);
+    }
+
+    #[test]
+    fn get_event_payouts_rejected_below_the_slot_min_per_impression() {
+        let logger = discard_logger();
+        let campaign = campaign_with_impression_pricing(8, 64);
+
+        let slots_min = [(DUMMY_IPFS[1], UnifiedNum::from(65))]
+            .into_iter()
+            .collect();
+        let payout = get_payout(
+            &logger,
+            &campaign,
+            &impression_event(),
+            &empty_session(),
+            &slots_min,
+            None,
+        )
+        .expect("Should be OK");
+
+        assert_eq!(
+            None, payout,
+            "Campaign max is below the AdSlot min per impression"
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_slots_min_per_impression_with_cache_and_fallback() {
+        let logger = discard_logger();
+        let server = MockServer::start().await;
+        let platform_api = PlatformApi::new(
+            server.uri().parse().expect("Should parse"),
+            Duration::from_secs(60),
+            Duration::from_secs(5),
+            Duration::from_secs(60),
+        )
+        .expect("Should build PlatformApi");
+
+        let token = DUMMY_CAMPAIGN.channel.token;
+        let slot_min = UnifiedNum::from(20);
+        let ad_slot = AdSlot {
+            ipfs: DUMMY_IPFS[1],
+            ad_type: "legacy_250x250".to_string(),
+            min_per_impression: Some([(token, slot_min)].into_iter().collect()),
+            rules: Rules::default(),
+            fallback_unit: None,
+            owner: IDS[&PUBLISHER],
+            created: Utc.ymd(2019, 7, 29).and_hms(7, 0, 0),
+            title: None,
+            description: None,
+            website: None,
+            archived: false,
+            modified: None,
+        };
+
+        Mock::given(method("GET"))
+            .and(path(format!("/slot/{}", DUMMY_IPFS[1])))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&AdSlotResponse {
+                slot: ad_slot,
+                fallback: None,
+                website: None,
+            }))
+            // the second fetch should come from the cache
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // the Platform is unavailable for this AdSlot
+        Mock::given(method("GET"))
+            .and(path(format!("/slot/{}", DUMMY_IPFS[2])))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let unavailable_event = Event::Impression {
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[2],
+            referrer: None,
+        };
+        let events = vec![impression_event(), unavailable_event.clone()];
+
+        for _ in 0..2 {
+            let slots_min =
+                fetch_slots_min_per_impression(&logger, &platform_api, token, &events).await;
+
+            assert_eq!(1, slots_min.len());
+            assert_eq!(Some(&slot_min), slots_min.get(&DUMMY_IPFS[1]));
+
+            // falls back to the Campaign pricing bounds
+            let campaign = campaign_with_impression_pricing(8, 64);
+            let payout = get_payout(
+                &logger,
+                &campaign,
+                &unavailable_event,
+                &empty_session(),
+                &slots_min,
+                None,
+            )
+            .expect("Should be OK");
+            assert_eq!(Some((*PUBLISHER, 8.into())), payout);
+        }
+    }
 }
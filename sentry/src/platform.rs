@@ -1,17 +1,42 @@
-use std::time::Duration;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 use reqwest::{Client, Error, StatusCode};
 
 // previously fetched from the market (in the supermarket) it should now be fetched from the Platform!
-use primitives::{platform::AdSlotResponse, util::ApiUrl, IPFS};
+use primitives::{platform::AdSlotResponse, util::ApiUrl, Address, UnifiedNum, IPFS};
+
+/// The [`AdSlot.min_per_impression`](primitives::AdSlot::min_per_impression) of a slot
+pub type SlotMinPerImpression = HashMap<Address, UnifiedNum>;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The maximum number of slots kept in the [`SlotMinPerImpression`] cache.
+///
+/// When the cache is full, the expired slots are removed first
+/// and then the least recently used one.
+pub const SLOT_CACHE_MAX_SIZE: usize = 10_000;
+
+#[derive(Debug, Clone)]
+struct CachedSlot {
+    fetched_at: Instant,
+    last_used: Instant,
+    min_per_impression: SlotMinPerImpression,
+}
+
 #[derive(Debug, Clone)]
 /// The `PlatformApi` is cheap to clone
 pub struct PlatformApi {
     pub(crate) platform_url: ApiUrl,
     client: Client,
+    /// How long a fetched [`SlotMinPerImpression`] is kept in the cache.
+    slot_cache_ttl: Duration,
+    /// Bounded by [`SLOT_CACHE_MAX_SIZE`]
+    slot_cache_max_size: usize,
+    slot_cache: Arc<Mutex<HashMap<IPFS, CachedSlot>>>,
 }
 
 impl PlatformApi {
@@ -22,16 +47,29 @@ impl PlatformApi {
 
     /// Duration specified will be the time to remain idle before sending a TCP keepalive probe.
     /// Sets [`reqwest::Client`]'s [`reqwest::ClientBuilder::tcp_keepalive`](reqwest::ClientBuilder::tcp_keepalive))
-    // @TODO: maybe add timeout too?
-    pub fn new(platform_url: ApiUrl, keep_alive_interval: Duration) -> Result<Self> {
+    ///
+    /// The `timeout` is applied to every request to the Platform,
+    /// see [`reqwest::ClientBuilder::timeout`].
+    ///
+    /// The `slot_cache_ttl` is used for [`PlatformApi::fetch_slot_min_per_impression`].
+    pub fn new(
+        platform_url: ApiUrl,
+        keep_alive_interval: Duration,
+        timeout: Duration,
+        slot_cache_ttl: Duration,
+    ) -> Result<Self> {
         let client = Client::builder()
             .tcp_keepalive(keep_alive_interval)
+            .timeout(timeout)
             .cookie_store(true)
             .build()?;
 
         Ok(Self {
             platform_url,
             client,
+            slot_cache_ttl,
+            slot_cache_max_size: SLOT_CACHE_MAX_SIZE,
+            slot_cache: Default::default(),
         })
     }
 
@@ -55,4 +93,107 @@ impl PlatformApi {
             Err(err) => Err(err),
         }
     }
+
+    /// Fetches the [`AdSlot.min_per_impression`](primitives::AdSlot::min_per_impression)
+    /// of the slot and caches it for the configured TTL.
+    ///
+    /// If the AdSlot is not found or it has no `min_per_impression` set,
+    /// an empty [`SlotMinPerImpression`] is cached.
+    /// Errors are not cached.
+    pub async fn fetch_slot_min_per_impression(&self, ipfs: IPFS) -> Result<SlotMinPerImpression> {
+        {
+            let mut cache = self.slot_cache.lock().expect("Slot cache lock is poisoned");
+
+            if let Some(cached) = cache.get_mut(&ipfs) {
+                if cached.fetched_at.elapsed() < self.slot_cache_ttl {
+                    cached.last_used = Instant::now();
+
+                    return Ok(cached.min_per_impression.clone());
+                }
+            }
+        }
+
+        let min_per_impression = self
+            .fetch_slot(ipfs)
+            .await?
+            .and_then(|response| response.slot.min_per_impression)
+            .unwrap_or_default();
+
+        let mut cache = self.slot_cache.lock().expect("Slot cache lock is poisoned");
+
+        if cache.len() >= self.slot_cache_max_size && !cache.contains_key(&ipfs) {
+            let slot_cache_ttl = self.slot_cache_ttl;
+            cache.retain(|_ipfs, cached| cached.fetched_at.elapsed() < slot_cache_ttl);
+
+            if cache.len() >= self.slot_cache_max_size {
+                let least_recently_used = cache
+                    .iter()
+                    .min_by_key(|(_ipfs, cached)| cached.last_used)
+                    .map(|(ipfs, _cached)| *ipfs);
+
+                if let Some(least_recently_used) = least_recently_used {
+                    cache.remove(&least_recently_used);
+                }
+            }
+        }
+
+        let now = Instant::now();
+        cache.insert(
+            ipfs,
+            CachedSlot {
+                fetched_at: now,
+                last_used: now,
+                min_per_impression: min_per_impression.clone(),
+            },
+        );
+
+        Ok(min_per_impression)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use primitives::test_util::DUMMY_IPFS;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn slot_cache_evicts_the_least_recently_used_slot() {
+        let server = MockServer::start().await;
+        let mut platform_api = PlatformApi::new(
+            server.uri().parse().expect("Should parse"),
+            Duration::from_secs(60),
+            Duration::from_secs(5),
+            Duration::from_secs(60),
+        )
+        .expect("Should build PlatformApi");
+        platform_api.slot_cache_max_size = 2;
+
+        // the not found AdSlots are cached as well
+        for (ipfs, fetches) in [(DUMMY_IPFS[0], 1), (DUMMY_IPFS[1], 2), (DUMMY_IPFS[2], 1)] {
+            Mock::given(method("GET"))
+                .and(path(format!("/slot/{}", ipfs)))
+                .respond_with(ResponseTemplate::new(404))
+                .expect(fetches)
+                .mount(&server)
+                .await;
+        }
+
+        // DUMMY_IPFS[1] is the least recently used when DUMMY_IPFS[2] is cached
+        for ipfs in [0, 1, 0, 2, 0, 1] {
+            let min_per_impression = platform_api
+                .fetch_slot_min_per_impression(DUMMY_IPFS[ipfs])
+                .await
+                .expect("Should fetch");
+
+            assert!(min_per_impression.is_empty());
+        }
+
+        assert_eq!(2, platform_api.slot_cache.lock().unwrap().len());
+        server.verify().await;
+    }
 }
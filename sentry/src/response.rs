@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 
+use adapter::{Error as AdapterError, ErrorKind as AdapterErrorKind};
 use axum::{http::StatusCode, response::IntoResponse, Json};
+use serde_json::json;
 
-use primitives::sentry::ValidationErrorResponse;
+use primitives::{sentry::ValidationErrorResponse, Address};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ResponseError {
@@ -13,6 +15,39 @@ pub enum ResponseError {
     Forbidden(String),
     Conflict(String),
     TooManyRequests(String),
+    /// An [`adapter::Error`] responded based on its [`AdapterErrorKind`]
+    /// with a JSON body containing the error `code` and `message`:
+    ///
+    /// - [`AdapterErrorKind::Authentication`] - `401 Unauthorized` & `adapter_authentication`
+    /// - [`AdapterErrorKind::Authorization`] - `403 Forbidden` & `adapter_authorization`
+    /// - [`AdapterErrorKind::Domain`] - `422 Unprocessable Entity` & `adapter_domain`
+    /// - [`AdapterErrorKind::RpcTransport`] - `503 Service Unavailable` & `adapter_rpc_unavailable`,
+    ///   with an additional `retryable` field
+    /// - [`AdapterErrorKind::Internal`] - `500 Internal Server Error` & `adapter_internal`
+    Adapter {
+        kind: AdapterErrorKind,
+        message: String,
+    },
+    /// The Channel exists, but its token is not whitelisted in this validator.
+    ///
+    /// Responds with `422 Unprocessable Entity` and the error code
+    /// `channel_token_not_whitelisted`, in order to distinguish it from
+    /// an unknown Channel id (`404 Not Found`).
+    ChannelTokenNotWhitelisted(Address),
+    /// The request was authenticated with a read-only scope token,
+    /// but the route creates or modifies data.
+    ///
+    /// Responds with `403 Forbidden` and the error code `read_only_scope`.
+    ReadOnlyScope,
+    /// An entry of a bulk request has failed and none of the entries were applied.
+    ///
+    /// Responds with the status code of the entry's `error` and a JSON body
+    /// with the error code `bulk_entry_failed`, the `index` of the failing entry
+    /// in the request and the error `message`.
+    BulkEntry {
+        index: usize,
+        error: Box<ResponseError>,
+    },
 }
 
 impl IntoResponse for ResponseError {
@@ -41,6 +76,113 @@ impl IntoResponse for ResponseError {
             ResponseError::Forbidden(e) => (StatusCode::FORBIDDEN, e).into_response(),
             ResponseError::Conflict(e) => (StatusCode::CONFLICT, e).into_response(),
             ResponseError::TooManyRequests(e) => (StatusCode::TOO_MANY_REQUESTS, e).into_response(),
+            ResponseError::Adapter { kind, message } => {
+                let (status, code) = match kind {
+                    AdapterErrorKind::Authentication => {
+                        (StatusCode::UNAUTHORIZED, "adapter_authentication")
+                    }
+                    AdapterErrorKind::Authorization => {
+                        (StatusCode::FORBIDDEN, "adapter_authorization")
+                    }
+                    AdapterErrorKind::Domain => {
+                        (StatusCode::UNPROCESSABLE_ENTITY, "adapter_domain")
+                    }
+                    AdapterErrorKind::RpcTransport { .. } => {
+                        (StatusCode::SERVICE_UNAVAILABLE, "adapter_rpc_unavailable")
+                    }
+                    AdapterErrorKind::Internal => {
+                        (StatusCode::INTERNAL_SERVER_ERROR, "adapter_internal")
+                    }
+                };
+
+                let mut error_response = json!({
+                    "code": code,
+                    "message": message,
+                });
+
+                if let AdapterErrorKind::RpcTransport { retryable } = kind {
+                    error_response["retryable"] = retryable.into();
+                }
+
+                (status, Json(error_response)).into_response()
+            }
+            ResponseError::ChannelTokenNotWhitelisted(token) => {
+                let error_response = [
+                    ("code", "channel_token_not_whitelisted".to_string()),
+                    (
+                        "message",
+                        "Channel token is not whitelisted in this validator".to_string(),
+                    ),
+                    ("token", token.to_string()),
+                ]
+                .into_iter()
+                .collect::<HashMap<_, _>>();
+
+                (StatusCode::UNPROCESSABLE_ENTITY, Json(error_response)).into_response()
+            }
+            ResponseError::ReadOnlyScope => {
+                let error_response = [
+                    ("code", "read_only_scope"),
+                    (
+                        "message",
+                        "Authentication token has a read-only scope and cannot modify data",
+                    ),
+                ]
+                .into_iter()
+                .collect::<HashMap<_, _>>();
+
+                (StatusCode::FORBIDDEN, Json(error_response)).into_response()
+            }
+            ResponseError::BulkEntry { index, error } => {
+                let message = error.message();
+                let status = error.into_response().status();
+
+                let error_response = json!({
+                    "code": "bulk_entry_failed",
+                    "index": index,
+                    "message": message,
+                });
+
+                (status, Json(error_response)).into_response()
+            }
+        }
+    }
+}
+
+impl ResponseError {
+    pub fn adapter(error: &AdapterError) -> Self {
+        ResponseError::Adapter {
+            kind: error.kind(),
+            message: error.to_string(),
+        }
+    }
+
+    /// Sets the `index` of the bulk request entry which has failed with this error.
+    pub fn for_bulk_entry(self, index: usize) -> Self {
+        ResponseError::BulkEntry {
+            index,
+            error: Box::new(self),
+        }
+    }
+
+    /// The human readable message of the error.
+    fn message(&self) -> String {
+        match self {
+            ResponseError::NotFound => "Not found".to_string(),
+            ResponseError::Unauthorized => "invalid authorization".to_string(),
+            ResponseError::BadRequest(message)
+            | ResponseError::FailedValidation(message)
+            | ResponseError::Forbidden(message)
+            | ResponseError::Conflict(message)
+            | ResponseError::TooManyRequests(message)
+            | ResponseError::Adapter { message, .. } => message.clone(),
+            ResponseError::ChannelTokenNotWhitelisted(_) => {
+                "Channel token is not whitelisted in this validator".to_string()
+            }
+            ResponseError::ReadOnlyScope => {
+                "Authentication token has a read-only scope and cannot modify data".to_string()
+            }
+            ResponseError::BulkEntry { error, .. } => error.message(),
         }
     }
 }
@@ -49,7 +191,13 @@ impl<T> From<T> for ResponseError
 where
     T: std::error::Error + 'static,
 {
+    /// Any [`adapter::Error`] is mapped using [`ResponseError::adapter`],
+    /// every other error results in a [`ResponseError::BadRequest`].
     fn from(error: T) -> Self {
-        ResponseError::BadRequest(error.to_string())
+        // a separate `From<adapter::Error>` would conflict with this implementation
+        match (&error as &dyn std::any::Any).downcast_ref::<AdapterError>() {
+            Some(adapter_error) => ResponseError::adapter(adapter_error),
+            None => ResponseError::BadRequest(error.to_string()),
+        }
     }
 }
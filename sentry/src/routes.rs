@@ -6,30 +6,64 @@
 //! All routes are listed below. Here is an overview and links to all of them:
 //! - [Channel](#channel) routes
 //!   - [GET `/v5/channel/list`](#get-v5channellist)
+//!   - [POST `/v5/channel`](#post-v5channel-auth-required) (auth required)
 //!   - [GET `/v5/channel/:id/accounting`](#get-v5channelidaccounting)
+//!   - [GET `/v5/channel/:id/accounting/page`](#get-v5channelidaccountingpage)
+//!   - [POST `/v5/channel/:id/accounting-freeze`](#post-v5channelidaccounting-freeze-auth-required) (auth required) guardian or admin only
+//!   - [GET `/v5/channel/:id/snapshot`](#get-v5channelidsnapshot-auth-required) (auth required) Channel validators, guardian or admins only
+//!   - [GET `/v5/channel/:id/quarantined-messages`](#get-v5channelidquarantined-messages-auth-required) (auth required) admins only
+//!   - [POST `/v5/channel/:id/quarantined-messages/:message_id/release`](#post-v5channelidquarantined-messagesmessage_idrelease-auth-required) (auth required) admins only
+//!   - [POST `/v5/channel/:id/quarantine`](#post-v5channelidquarantine-auth-required) (auth required) Channel validators only
 //!   - [GET `/v5/channel/:id/spender/:addr`](#get-v5channelidspenderaddr-auth-required) (auth required)
 //!   - [POST `/v5/channel/:id/spender/:addr`](#post-v5channelidspenderaddr-auth-required) (auth required)
+//!   - [GET `/v5/channel/:id/spender/:addr/history`](#get-v5channelidspenderaddrhistory-auth-required) (auth required)
 //!   - [GET `/v5/channel/:id/spender/all`](#get-v5channelidspenderall-auth-required) (auth required)
+//!   - [POST `/v5/channel/:id/spender/batch`](#post-v5channelidspenderbatch-auth-required) (auth required)
 //!   - [GET `/v5/channel/:id/validator-messages`](#get-v5channelidvalidator-messages)
 //!   - [GET `/v5/channel/:id/validator-messages/:addr`](#get-v5channelidvalidator-messages)
 //!   - [GET `/v5/channel/:id/validator-messages/:addr/:validator_messages`](#get-v5channelidvalidator-messages)
 //!   - [POST `/v5/channel/:id/validator-messages`](#post-v5channelidvalidator-messages-auth-required) (auth required)
 //!   - [GET `/v5/channel/:id/last-approved`](#get-v5channelidlast-approved)
+//!   - [GET `/v5/channel/:id/last-approved/history`](#get-v5channelidlast-approvedhistory)
+//!   - [GET `/v5/channel/:id/lag`](#get-v5channelidlag)
 //!   - [POST `/v5/channel/:id/pay`](#post-v5channelidpay-auth-required) (auth required)
+//!   - [POST `/v5/channel/:id/pay/preview`](#post-v5channelidpaypreview-auth-required) (auth required)
+//!   - [POST `/v5/channel/:id/close-and-pay`](#post-v5channelidclose-and-pay-auth-required) (auth required)
 //!   - [GET `/v5/channel/:id/get-leaf`](#get-v5channelidget-leaf)
 //!   - [POST `/v5/channel/dummy-deposit`](#post-v5channeldummy-deposit-auth-required) (auth required) available only with Dummy adapter
 //! - [Campaign](#campaign) routes
 //!     - [GET `/v5/campaign/list`](#get-v5campaignlist)
 //!     - [POST `/v5/campaign`](#post-v5campaign-auth-required) (auth required)
+//!     - [POST `/v5/campaign/bulk`](#post-v5campaignbulk-auth-required) (auth required)
+//!     - [POST `/v5/campaign/batch`](#post-v5campaignbatch)
+//!     - [GET `/v5/campaign/:id`](#get-v5campaignid)
 //!     - [POST `/v5/campaign/:id`](#post-v5campaignid-auth-required) (auth required)
 //!     - [POST `/v5/campaign/:id/events`](#post-v5campaignidevents) (auth required)
+//!     - [GET `/v5/campaign/:id/events/stats`](#get-v5campaignideventsstats-auth-required) (auth required)
+//!     - [GET `/v5/campaign/:id/burn-rate`](#get-v5campaignidburn-rate)
+//!     - [GET `/v5/campaign/:id/health`](#get-v5campaignidhealth-auth-required) (auth required)
+//!     - [GET `/v5/campaign/:id/budget/history`](#get-v5campaignidbudgethistory-auth-required) (auth required)
 //!     - [POST `/v5/campaign/:id/close`](#post-v5campaignidclose-auth-required) (auth required)
+//!     - [POST `/v5/campaign/:id/closed-notification`](#post-v5campaignidclosed-notification-auth-required) (auth required)
 //! - [Analytics](#analytics) routes
 //!   - [GET `/v5/analytics`](#get-v5analytics)
 //!   - [GET `/v5/analytics/for-publisher`](#get-v5analyticsfor-publisher-auth-required) (auth required)
 //!   - [GET `/v5/analytics/for-advertiser`](#get-v5analyticsfor-advertiser-auth-required) (auth required)
 //!   - [GET `/v5/analytics/for-admin`](#get-v5analyticsfor-admin-auth-required) (auth required)
+//! - [Validator](#validator) routes
+//!   - [GET `/v5/validator/:id/fees-summary`](#get-v5validatoridfees-summary)
+//!   - [POST `/v5/validator/:id/fees-summary`](#post-v5validatoridfees-summary-auth-required) (auth required)
+//! - [GET `/v5/whoami`](#get-v5whoami)
 //! - [GET `/cfg`](#get-cfg)
+//! - [GET `/health`](#get-health)
+//!
+//! ## Authentication scope
+//!
+//! Authentication tokens can have a read-only [`Scope`](adapter::primitives::Scope),
+//! e.g. tokens handed out to reporting tools.
+//! Routes creating or modifying data (Campaign creation, modification & closing, events,
+//! Channel registration & payout and POST `/v5/channel/:id/spender/:addr`) reject such tokens
+//! with `403 Forbidden` and the error code `read_only_scope`.
 //!
 //! ## Channel
 //!
@@ -60,9 +94,35 @@
 #![doc = include_str!("../../primitives/examples/channel_list_query.rs")]
 //! ```
 //!
+//! #### POST `/v5/channel` (auth required)
+//!
+//! Registers a Channel, so the Channel routes (e.g. [GET `/v5/channel/:id/spender/all`](#get-v5channelidspenderall-auth-required))
+//! can be used before any Campaign of the Channel is created.
+//!
+//! The Channel token should be whitelisted in the [`Config`](primitives::Config), otherwise
+//! it responds with `422 Unprocessable Entity` and the error code `channel_token_not_whitelisted`.
+//! The leader, follower, guardian and token cannot be the zero address,
+//! see [`validate_channel_addresses()`](primitives::campaign_validator::validate_channel_addresses).
+//!
+//! Only the Channel's leader, follower or guardian can register it.
+//! If [`SentryConfig::spender_channel_registration`](primitives::config::SentryConfig::spender_channel_registration)
+//! is enabled, any authenticated address can register it as a prospective spender.
+//!
+//! Registering an existing Channel is allowed and responds with the existing Channel.
+//!
+//! The route is handled by [`channel::create_channel()`].
+//!
+//! Request body (json): [`Channel`]
+//!
+//! Response: [`Channel`]
+//!
 //! #### GET `/v5/channel/:id/accounting`
 //!
 //! Gets all of the accounting entries for a channel from the database and checks the balances.
+//! The response also includes whether or not the accounting of the Channel is frozen.
+//!
+//! The response has an `ETag` and if the request's `If-None-Match` header matches it,
+//! `304 Not Modified` is returned without a body, see [`etag()`](crate::middleware::etag::etag).
 //!
 //! The route is handled by [`channel::get_accounting_for_channel()`].
 //!
@@ -76,6 +136,125 @@
 #![doc = include_str!("../../primitives/examples/accounting_response.rs")]
 //! ```
 //!
+//! #### GET `/v5/channel/:id/accounting/page`
+//!
+//! Gets a single page of the accounting entries for a channel, ordered by side (earners first) and address.
+//! Intended for Channels with many earners & spenders, for which the whole accounting is too large.
+//! Since only a part of the accounting is returned, the balances are **not** checked.
+//!
+//! The route is handled by [`channel::get_accounting_page_for_channel()`].
+//!
+//! Request query parameters: [`AccountingPageQuery`][primitives::sentry::AccountingPageQuery]
+//!
+//! Response: [`AccountingPageResponse`][primitives::sentry::AccountingPageResponse]
+//!
+//! ##### Examples
+//!
+//! Query:
+//!
+//! ```text
+//! /v5/channel/0xXXX.../accounting/page?page=1
+//! ```
+//!
+//! Response:
+//!
+//! ```
+#![doc = include_str!("../../primitives/examples/accounting_page_response.rs")]
+//! ```
+//!
+//! #### POST `/v5/channel/:id/accounting-freeze` (auth required)
+//!
+//! Freezes or unfreezes the accounting of the Channel, e.g. once the Channel is considered finished.
+//!
+//! **Authentication is required** and the request must be sent by the Channel guardian
+//! or one of the Sentry [`admins`](primitives::config::SentryConfig::admins).
+//!
+//! While frozen, the Channel payouts (POST `/v5/channel/:id/pay`) and the events of its Campaigns
+//! (POST `/v5/campaign/:id/events`) are rejected with `400 Bad Request`
+//! and the Leader skips producing new `NewState`s, the Heartbeats continue.
+//!
+//! The route is handled by [`channel::channel_accounting_freeze()`].
+//!
+//! Request body (json): [`AccountingFreezeRequest`](primitives::sentry::AccountingFreezeRequest)
+//!
+//! Response: [`SuccessResponse`]
+//!
+//! #### GET `/v5/channel/:id/snapshot` (auth required)
+//!
+//! A point-in-time snapshot of the whole Channel state signed by the validator of the Sentry, e.g. for off-chain audits.
+//! It includes the Channel, all earners & spenders balances (unchecked), the deposits of all spenders (spendables),
+//! the remaining budgets of all Campaigns of the Channel, the last approved `NewState` & `ApproveState`
+//! and the state root of the accounting balances.
+//!
+//! The accounting, spendables & Campaigns are fetched in a single DB transaction.
+//!
+//! The `signature` is the adapter signature of the `keccak256` hash of the canonical JSON of the `snapshot`,
+//! see [`ChannelSnapshot::message()`](primitives::sentry::ChannelSnapshot::message),
+//! and it can be verified by the `signer` address in the same way as a state root signature.
+//! The canonical JSON is the compact JSON of the `snapshot` object, without any whitespace,
+//! with the keys of all objects sorted by their UTF-8 bytes and the arrays kept in order,
+//! see [`canonical_json`](primitives::util::canonical_json).
+//! If Sentry has no signer the route responds with `404 Not Found`.
+//!
+//! **Authentication is required** and the request must be sent by one of the Channel validators,
+//! its guardian or one of the Sentry [`admins`](primitives::config::SentryConfig::admins).
+//!
+//! The route is handled by [`channel::channel_snapshot()`].
+//!
+//! Response: [`ChannelSnapshotResponse`](primitives::sentry::ChannelSnapshotResponse)
+//!
+//! ##### Examples
+//!
+//! Response:
+//!
+//! ```
+#![doc = include_str!("../../primitives/examples/channel_snapshot_response.rs")]
+//! ```
+//!
+//! #### GET `/v5/channel/:id/quarantined-messages` (auth required)
+//!
+//! Lists the latest quarantined validator messages of the Channel.
+//! At most [`Config.limits.msgs_find`](primitives::config::Limits::msgs_find) messages are returned.
+//!
+//! A `NewState` with imbalanced balances (`sum(earners) != sum(spenders)`) and its `ApproveState`s
+//! are quarantined by the validator worker, see
+//! [POST `/v5/channel/:id/quarantine`](#post-v5channelidquarantine-auth-required),
+//! i.e. they are excluded from the latest `NewState` & `ApproveState` of the Channel.
+//!
+//! **Authentication is required** and the request must be sent by one of the Sentry [`admins`](primitives::config::SentryConfig::admins).
+//!
+//! The route is handled by [`channel::list_quarantined_messages()`].
+//!
+//! Response: [`QuarantinedMessagesResponse`](primitives::sentry::validator_messages::QuarantinedMessagesResponse)
+//!
+//! #### POST `/v5/channel/:id/quarantined-messages/:message_id/release` (auth required)
+//!
+//! Releases a quarantined validator message of the Channel, see
+//! [GET `/v5/channel/:id/quarantined-messages`](#get-v5channelidquarantined-messages-auth-required).
+//! Releasing a `NewState` releases its `ApproveState`s as well.
+//! Released messages are not quarantined again.
+//! Responds with `404 Not Found` if the Channel has no quarantined message with this id.
+//!
+//! **Authentication is required** and the request must be sent by one of the Sentry [`admins`](primitives::config::SentryConfig::admins).
+//!
+//! The route is handled by [`channel::release_quarantined()`].
+//!
+//! Response: [`SuccessResponse`]
+//!
+//! #### POST `/v5/channel/:id/quarantine` (auth required)
+//!
+//! Quarantines the leader's `NewState` with the given `stateRoot` and the follower's `ApproveState`s for it,
+//! when the balances of the `NewState` don't align.
+//! Responds with `400 Bad Request` if there is no such `NewState` or its balances align.
+//!
+//! **Authentication is required** and the request must be sent by one of the Channel validators.
+//!
+//! The route is handled by [`channel::quarantine_channel_state()`].
+//!
+//! Request body (json): [`QuarantineStateRequest`](primitives::sentry::validator_messages::QuarantineStateRequest)
+//!
+//! Response: [`SuccessResponse`]
+//!
 //! #### GET `/v5/channel/:id/spender/:addr` (auth required)
 //!
 //! Gets the spender limits for a spender on a [`Channel`]. It does so by fetching the
@@ -83,6 +262,14 @@
 //! the total deposited amount is retrieved, and the latest NewState from which the total spent
 //! amount is retrieved.
 //!
+//! The deposit of the Spendable is refreshed from the [`Adapter.get_deposit()`](adapter::client::Locked::get_deposit).
+//! A missing Spendable is created without overwriting an existing one,
+//! so concurrent first requests for the same spender converge on a single Spendable.
+//!
+//! When the adapter supports it, the response also includes the `depositBreakdown`
+//! of the deposit, i.e. how much is already in OUTPACE and how much is still pending
+//! on the counterfactual address of the spender.
+//!
 //! The route is handled by [`channel::get_spender_limits()`].
 //!
 //! Response: [`SpenderResponse`]
@@ -105,6 +292,20 @@
 //!
 //! Response: [`SuccessResponse`]
 //!
+//! #### GET `/v5/channel/:id/spender/:addr/history` (auth required)
+//!
+//! Gets the changes of the total deposit of the spender on a [`Channel`], oldest first,
+//! with the total before and after the change and what has changed it,
+//! e.g. `campaign-create`, `spender-route` or `worker-refresh`.
+//!
+//! Only the spender and the validators of the [`Channel`] are allowed to get the history.
+//!
+//! The route is handled by [`channel::get_spender_history()`].
+//!
+//! Request query parameters: [`SpendableHistoryQuery`](primitives::sentry::SpendableHistoryQuery)
+//!
+//! Response: [`SpendableHistoryResponse`](primitives::sentry::SpendableHistoryResponse)
+//!
 //! #### GET `/v5/channel/:id/spender/all` (auth required)
 //!
 //! This routes gets total_deposited and total_spent for every spender on a [`Channel`]
@@ -121,6 +322,21 @@
 #![doc = include_str!("../../primitives/examples/all_spenders_response.rs")]
 //! ```
 //!
+//! #### POST `/v5/channel/:id/spender/batch` (auth required)
+//!
+//! Gets total_deposited and total_spent for the requested spenders on a [`Channel`],
+//! computed in the same way as [GET `/v5/channel/:id/spender/all`](#get-v5channelidspenderall-auth-required).
+//!
+//! Up to [`Config.limits.spendable_find`](primitives::config::Limits::spendable_find) spenders
+//! can be requested at once. Spenders without a spendable for the [`Channel`] are not
+//! included in the response.
+//!
+//! The route is handled by [`channel::get_spenders_batch()`].
+//!
+//! Request body (json): [`SpendersBatchRequest`](primitives::sentry::SpendersBatchRequest)
+//!
+//! Response: [`SpendersBatchResponse`](primitives::sentry::SpendersBatchResponse)
+//!
 //! #### GET `/v5/channel/:id/validator-messages`
 //!
 //! Retrieve the latest validator [`MessageTypes`] for a given [`Channel`].
@@ -128,6 +344,13 @@
 //! The query `limit` parameter is constraint to a maximum of [`Config.limits.msgs_find`],
 //! if a large value is passed it will use the [`Config.limits.msgs_find`] instead.
 //!
+//! By default the latest messages are returned (newest-first).
+//! For syncing all the messages in order (e.g. a validator catching up after a downtime),
+//! use the `since` (in milliseconds) and/or the `cursor` query parameters:
+//! the messages are then returned oldest-first and when the page is full,
+//! the response contains a `nextCursor` to be passed as `cursor` for the next page.
+//! The cursor encodes the `received` time and the unique id of the last message,
+//! so no message is skipped or duplicated across the pages while new messages arrive.
 //!
 //! **Sub-routes** with additional filtering:
 //!
@@ -196,6 +419,13 @@
 //!
 //! The same is true of the [`Heartbeat`]s messages if they are requested with the query parameter.
 //!
+//! When the approved [`NewState`] has imbalanced balances, the previous approved pair of states
+//! with valid balances is returned instead. The route doesn't quarantine the [`NewState`], see
+//! [POST `/v5/channel/:id/quarantine`](#post-v5channelidquarantine-auth-required).
+//!
+//! The response has an `ETag` and if the request's `If-None-Match` header matches it,
+//! `304 Not Modified` is returned without a body, see [`etag()`](crate::middleware::etag::etag).
+//!
 //! The route is handled by [`channel::last_approved()`].
 //!
 //! Request query parameters: [`LastApprovedQuery`][primitives::sentry::LastApprovedQuery]
@@ -216,6 +446,33 @@
 #![doc = include_str!("../../primitives/examples/channel_last_approved_response.rs")]
 //! ```
 //!
+//! #### GET `/v5/channel/:id/last-approved/history`
+//!
+//! Retrieves the last approved states of the given [`Channel`], i.e. the [`ApproveState`]s
+//! paired with their corresponding [`NewState`]s (by the `stateRoot`), ordered newest first.
+//! Each validator message includes the timestamp it was `received` at.
+//!
+//! The query `limit` parameter is constraint to a maximum of [`Config.limits.approved_states_find`],
+//! if a large value is passed it will use the [`Config.limits.approved_states_find`] instead.
+//!
+//! The route is handled by [`channel::last_approved_history()`].
+//!
+//! Request query parameters: [`LastApprovedHistoryQuery`][primitives::sentry::LastApprovedHistoryQuery]
+//!
+//! Response: [`LastApprovedHistoryResponse`][primitives::sentry::LastApprovedHistoryResponse]
+//!
+//! #### GET `/v5/channel/:id/lag`
+//!
+//! Diagnostic route for monitoring how far behind the Follower's latest [`ApproveState`]
+//! is from the Leader's latest [`NewState`] of the given [`Channel`].
+//!
+//! Returns the `stateRoot` and the age (since `received`) of both states
+//! and whether they correspond, i.e. the latest [`NewState`] is approved.
+//!
+//! The route is handled by [`channel::channel_lag()`].
+//!
+//! Response: [`ChannelLagResponse`][primitives::sentry::ChannelLagResponse]
+//!
 //! #### POST `/v5/channel/:id/pay` (auth required)
 //!
 //! Channel Payout with authentication of the spender.
@@ -225,6 +482,15 @@
 //! all of the earners and updates their balances accordingly. Used when an advertiser/spender wants
 //! to get their remaining funds back.
 //!
+//! An optional `Idempotency-Key` header makes retrying a request (e.g. after a timeout) safe.
+//! The key is scoped to the Channel and the authenticated spender, repeating the same payout
+//! with it is a no-op which responds with the original result.
+//! Re-using the key for a different payout responds with `409 Conflict`.
+//!
+//! Each earner should be passed only once (addresses are compared case-insensitively)
+//! and it cannot be the spender. Each earner's payout should be non-zero and at least the configured
+//! [`TokenInfo::min_payout`](primitives::config::TokenInfo::min_payout) of the Channel token, if any.
+//!
 //! The route is handled by [`channel::channel_payout()`].
 //!
 //! Request JSON body: [`ChannelPayRequest`]
@@ -239,6 +505,42 @@
 #![doc = include_str!("../../primitives/examples/channel_pay_request.rs")]
 //! ```
 //!
+//! #### POST `/v5/channel/:id/pay/preview` (auth required)
+//!
+//! Previews the payout of the authenticated spender without applying it, e.g. before submitting it with
+//! [POST `/v5/channel/:id/pay`](#post-v5channelidpay-auth-required).
+//!
+//! The same checks are made as for the payout - each earner's payout, the frozen accounting,
+//! the closed Campaigns of the Channel and the available payout of the spender
+//! (`total deposited + total earned - total spent`). Instead of an error response,
+//! the failed checks are returned with the computed available payout and the total payout amount.
+//!
+//! The route is handled by [`channel::channel_payout_preview()`].
+//!
+//! Request JSON body: [`ChannelPayRequest`]
+//!
+//! Response: [`ChannelPayPreviewResponse`](primitives::sentry::ChannelPayPreviewResponse)
+//!
+//! #### POST `/v5/channel/:id/close-and-pay` (auth required)
+//!
+//! Closes all the Campaigns of the authenticated spender in the Channel and performs
+//! the payout in a single request, instead of closing each Campaign and then calling
+//! [POST `/v5/channel/:id/pay`](#post-v5channelidpay-auth-required).
+//!
+//! The payout is validated the same way, taking into account the Campaigns being closed,
+//! before closing any of them, e.g. the Campaigns of other creators in the Channel
+//! should already be closed. The `Idempotency-Key` header is supported as well.
+//! If the payout fails after the Campaigns have been closed, they are re-opened
+//! with their remaining budget, otherwise the other validators of the closed Campaigns
+//! are notified in the same way as for
+//! [POST `/v5/campaign/:id/close`](#post-v5campaignidclose-auth-required).
+//!
+//! The route is handled by [`channel::channel_close_and_pay()`].
+//!
+//! Request JSON body: [`ChannelPayRequest`]
+//!
+//! Response: [`SuccessResponse`](primitives::sentry::SuccessResponse)
+//!
 //! #### GET `/v5/channel/:id/get-leaf
 //!
 //! This route gets the latest approved state ([`NewState`]/[`ApproveState`] pair),
@@ -301,6 +603,20 @@
 //!
 //! - `:id` - [`CampaignId`]
 //!
+//! ### Not Found & Forbidden
+//!
+//! The Campaign of the `/v5/campaign/:id/...` routes is loaded before any access checks,
+//! see [`campaign_load()`](crate::middleware::campaign::campaign_load), so:
+//!
+//! - a missing Campaign is always `404 Not Found`, regardless of who sent the request;
+//! - `403 Forbidden` is returned only for an existing Campaign, e.g. when
+//! [POST `/v5/campaign/:id`](#post-v5campaignid-auth-required) or
+//! [POST `/v5/campaign/:id/close`](#post-v5campaignidclose-auth-required)
+//! is not sent by the [`Campaign.creator`], see [`called_by_creator()`](crate::middleware::campaign::called_by_creator).
+//!
+//! This is the same as for the Channel routes, e.g. a missing spender of
+//! [GET `/v5/channel/:id/spender/:addr`](#get-v5channelidspenderaddr-auth-required).
+//!
 //! ### Routes
 //!
 //! #### GET `/v5/campaign/list`
@@ -320,6 +636,8 @@
 //!   - `validator=[0x...]` or `leader=[0x...]` (optional) - address of the validator to be filtered by. You can either
 //!     - `validator=[0x...]` - it will return all `Campaign`s where this address is **either** `Channel.leader` or `Channel.follower`
 //!     - `leader=[0x...]` - it will return all `Campaign`s where this address is `Channel.leader`
+//!   - `guardian=[0x...]` (optional) - it will return all `Campaign`s where this address is `Channel.guardian`.
+//!     It can't be combined with `validator` or `leader`.
 //!
 //!
 //! Response: [`CampaignListResponse`](primitives::sentry::campaign_list::CampaignListResponse)
@@ -346,6 +664,27 @@
 //!
 //! It will make sure the `Channel` is created if new and it will update
 //! the spendable amount using the [`Adapter.get_deposit()`](adapter::client::Locked::get_deposit).
+//! If the deposit cannot be fetched, it responds based on the Adapter error,
+//! e.g. `503 Service Unavailable` with the `adapter_rpc_unavailable` code
+//! when the RPC is down (see [`ResponseError::Adapter`](crate::response::ResponseError::Adapter)).
+//!
+//! The [`Campaign.unit_pricing_overrides`](primitives::Campaign::unit_pricing_overrides)
+//! should only contain AdUnits of the Campaign and be within the
+//! [`Config.limits.unit_pricing_override_multiplier`](primitives::config::Limits::unit_pricing_override_multiplier)
+//! of the Campaign's pricing bounds, the same applies when modifying the Campaign.
+//!
+//! The [`Campaign.accepted_event_types`](primitives::Campaign::accepted_event_types),
+//! if set, should contain at least one event type and no duplicates.
+//!
+//! The [`Pacing.daily_cap`](primitives::campaign::Pacing::daily_cap) of the
+//! [`Campaign.pacing`](primitives::Campaign::pacing), if set, should be positive
+//! and not exceed the Campaign budget, the same applies when modifying the Campaign.
+//!
+//! None of the Channel addresses, the creator, the validators ids and fee addresses
+//! can be the zero address, see [`validate_addresses()`](primitives::campaign_validator::validate_addresses).
+//!
+//! A Channel can have at most [`Config.limits.max_campaigns_per_channel`](primitives::config::Limits::max_campaigns_per_channel)
+//! active (i.e. not expired) Campaigns, creating more of them responds with `400 Bad Request`.
 //!
 //! The route is handled by [`campaign::create_campaign()`].
 //!
@@ -359,6 +698,66 @@
 #![doc = include_str!("../../primitives/examples/create_campaign_request.rs")]
 //! ```
 //!
+//! #### POST `/v5/campaign/bulk` (auth required)
+//!
+//! Create multiple Campaigns of the same `Channel` at once, either all of them or none.
+//! Request must be sent by the [`Campaign.creator`] of all the Campaigns.
+//!
+//! All the Campaigns are validated as in [POST `/v5/campaign`](#post-v5campaign-auth-required)
+//! before creating any of them and their combined budgets should not exceed
+//! the remaining deposit of the creator, which is updated only once for the whole request.
+//! The Campaigns are inserted in a single database transaction.
+//!
+//! At most [`Config.limits.campaigns_bulk_create`](primitives::config::Limits::campaigns_bulk_create)
+//! Campaigns can be created at once and the Channel should not exceed the
+//! [`Config.limits.max_campaigns_per_channel`](primitives::config::Limits::max_campaigns_per_channel)
+//! active Campaigns.
+//!
+//! The route is handled by [`campaign::create_campaigns_bulk()`].
+//!
+//! Request body (json): an array of [`CreateCampaign`][primitives::sentry::campaign_create::CreateCampaign]s
+//!
+//! Response: an array of the created [`Campaign`]s
+//!
+//! If an entry fails, the response is a [`ResponseError::BulkEntry`](crate::response::ResponseError::BulkEntry)
+//! with the `index` of the first failing entry, e.g.
+//!
+//! ```json
+//! { "code": "bulk_entry_failed", "index": 2, "message": "Not enough deposit left for the new campaign's budget" }
+//! ```
+//!
+//! #### POST `/v5/campaign/batch`
+//!
+//! Gets multiple Campaigns by their [`CampaignId`](primitives::CampaignId)s at once,
+//! each one alongside its budget utilization as in [GET `/v5/campaign/:id`](#get-v5campaignid).
+//!
+//! The Campaigns are returned in the order of the request, unknown Campaigns are skipped.
+//! At most [`Config.limits.campaigns_find`](primitives::config::Limits::campaigns_find)
+//! Campaigns can be requested at once.
+//!
+//! The route is handled by [`campaign::get_campaigns_batch()`].
+//!
+//! Request body (json): [`CampaignsBatchRequest`](primitives::sentry::campaign_get::CampaignsBatchRequest)
+//!
+//! Response: [`CampaignsBatchResponse`](primitives::sentry::campaign_get::CampaignsBatchResponse)
+//!
+//! #### GET `/v5/campaign/:id`
+//!
+//! Gets the [`Campaign`] alongside its budget utilization - the spent & remaining budget
+//! and the spent percentage of the [`Campaign.budget`](primitives::Campaign::budget).
+//!
+//! The route is handled by [`campaign::get_campaign()`].
+//!
+//! Response: [`CampaignResponse`](primitives::sentry::campaign_get::CampaignResponse)
+//!
+//! ##### Examples
+//!
+//! Response:
+//!
+//! ```
+#![doc = include_str!("../../primitives/examples/campaign_response.rs")]
+//! ```
+//!
 //! #### POST `/v5/campaign/:id` (auth required)
 //!
 //! Modify the [`Campaign`]. Request must be sent by the [`Campaign.creator`].
@@ -369,6 +768,9 @@
 //!
 //! Request body (json): [`ModifyCampaign`][primitives::sentry::campaign_modify::ModifyCampaign]
 //!
+//! The request must contain the expected current [`Campaign.version`],
+//! if the [`Campaign`] has been modified in the meantime a `409 Conflict` response is returned.
+//!
 //! Response: [`Campaign`]
 //!
 //! ##### Examples
@@ -379,15 +781,134 @@
 //!
 //! #### POST `/v5/campaign/:id/events`
 //!
-//! Add new [`Event`]s (`IMPRESSION`s & `CLICK`s) to the [`Campaign`].
+//! Add new [`Event`]s (`IMPRESSION`s, `CLICK`s, `CONVERSION`s and any custom event types) to the [`Campaign`].
 //! Applies [`Campaign.event_submission`] rules and additional validation using [`check_access()`].
 //!
+//! Only the event types of the [`Config.event_types`](primitives::Config::event_types) are supported,
+//! if any of the events is of another type, all the events are rejected with `400 Bad Request`.
+//! When the Campaign has no pricing for the event type, the
+//! [`default_pricing`](primitives::config::EventTypeConfig::default_pricing) of the event type is used.
+//!
 //! The route is handled by [`campaign::insert_events::handle_route()`].
 //!
+//! The payout of an `IMPRESSION` is at least the AdSlot's
+//! [`min_per_impression`](primitives::AdSlot::min_per_impression) (fetched from the Platform and cached).
+//! If the Campaign's maximum price is below it, the event is not paid.
+//!
+//! If any of the events is not one of the Campaign's
+//! [`accepted_event_types`](primitives::Campaign::accepted_event_types),
+//! all the events are rejected with `400 Bad Request`.
+//!
+//! If a dedup window is set in the [`EventSubmission.dedup_window`](primitives::EventSubmission::dedup_window)
+//! or the token's [`event_dedup_window`](primitives::config::TokenInfo::event_dedup_window),
+//! duplicate events of the same publisher, AdUnit, AdSlot and event type
+//! within the window are dropped before spending, see [`dedup_events()`](crate::access::dedup_events).
+//!
+//! A `CONVERSION` is attributed to a prior paid `CLICK` by its [`ClickId`](primitives::sentry::ClickId)
+//! and it is paid only if the click was of the same publisher, AdUnit and AdSlot within the
+//! [`conversion_attribution_window`](primitives::config::SentryConfig::conversion_attribution_window).
+//! Otherwise, as well as for any subsequent conversions of the same click, the conversion is dropped
+//! before spending, see [`attribute_conversions()`](crate::access::attribute_conversions).
+//!
+//! If the publisher of any of the events is the zero address, all the events are rejected with `400 Bad Request`.
+//!
+//! If the Campaign has a [`Pacing`](primitives::campaign::Pacing), the events which would exceed
+//! its daily cap for the current UTC day are rejected with `400 Bad Request`
+//! and a message different from the one for a Campaign without remaining budget.
+//!
 //! Request body (json): [`InsertEventsRequest`](primitives::sentry::InsertEventsRequest)
 //!
 //! Response: [`SuccessResponse`]
 //!
+//! #### GET `/v5/campaign/:id/events/stats` (auth required)
+//!
+//! Hourly accepted events (count & payout) of a publisher for the [`Campaign`],
+//! based on the Sentry analytics.
+//! Hours without any events in the requested time range are returned with zero counts & payouts.
+//!
+//! The route is handled by [`campaign::campaign_event_stats()`].
+//!
+//! Request must be sent by the requested publisher, the [`Campaign.creator`] or one of the Campaign validators.
+//!
+//! Request query parameters: [`EventStatsQuery`](primitives::sentry::event_stats::EventStatsQuery)
+//!
+//!   - `publisher=[0x...]` (required) - address of the publisher
+//!   - `start=[DateHour]` (optional) default: `end` - 23 hours
+//!   - `end=[DateHour]` (optional) default: the current hour
+//!
+//! The time range can be at most [`Config.limits.analytics_find`](primitives::config::Limits::analytics_find) hours.
+//!
+//! Response: [`EventStatsResponse`](primitives::sentry::event_stats::EventStatsResponse)
+//!
+//! ##### Examples
+//!
+//! Query:
+//!
+//! ```
+#![doc = include_str!("../../primitives/examples/event_stats_query.rs")]
+//! ```
+//!
+//! Response:
+//!
+//! ```
+#![doc = include_str!("../../primitives/examples/event_stats_response.rs")]
+//! ```
+//!
+//! #### GET `/v5/campaign/:id/burn-rate`
+//!
+//! The spend rate of the [`Campaign`] based on the Sentry analytics of the last hours
+//! and the projected time at which its remaining budget will be exhausted at the same rate.
+//!
+//! The route is handled by [`campaign::campaign_burn_rate()`].
+//!
+//! Request query parameters: [`BurnRateQuery`](primitives::sentry::campaign_burn_rate::BurnRateQuery)
+//!
+//!   - `hours=[integer]` (optional) default: `24` - the number of hourly buckets, including the current hour.
+//!     It can be at most [`Config.limits.analytics_find`](primitives::config::Limits::analytics_find) hours.
+//!
+//! Response: [`BurnRateResponse`](primitives::sentry::campaign_burn_rate::BurnRateResponse)
+//!
+//! The `perHour` and `exhaustion` are `null` when there is insufficient data,
+//! i.e. the Campaign hasn't spent anything in the requested hours
+//! or it has no remaining budget set (yet).
+//!
+//! ##### Examples
+//!
+//! Response:
+//!
+//! ```
+#![doc = include_str!("../../primitives/examples/campaign_burn_rate_response.rs")]
+//! ```
+//!
+//! #### GET `/v5/campaign/:id/health` (auth required)
+//!
+//! Whether the [`Campaign`] is healthy, combining its remaining budget, the Channel accounting
+//! of the [`Campaign.creator`] and the age of the last [`ApproveState`] of the Channel.
+//!
+//! The `status` is derived using the [`Config.sentry.campaign_awaiting_approval_after`](primitives::config::SentryConfig::campaign_awaiting_approval_after)
+//! and [`Config.sentry.campaign_stalled_after`](primitives::config::SentryConfig::campaign_stalled_after) thresholds,
+//! see [`CampaignHealthStatus`](primitives::sentry::campaign_health::CampaignHealthStatus).
+//!
+//! **Can only be called by the [`Campaign.creator`] or the Campaign validators!**
+//!
+//! The route is handled by [`campaign::campaign_health()`].
+//!
+//! Response: [`CampaignHealthResponse`](primitives::sentry::campaign_health::CampaignHealthResponse)
+//!
+//! #### GET `/v5/campaign/:id/budget/history` (auth required)
+//!
+//! Gets the changes of the [`Campaign.budget`](primitives::Campaign::budget), oldest first,
+//! with the budget before and after the change, who has changed it and when.
+//! A change is recorded whenever the budget is modified, including when the [`Campaign`] is closed.
+//!
+//! **Can only be called by the [`Campaign.creator`] or the Campaign validators!**
+//!
+//! The route is handled by [`campaign::campaign_budget_history()`].
+//!
+//! Request query parameters: [`BudgetHistoryQuery`](primitives::sentry::campaign_budget_history::BudgetHistoryQuery)
+//!
+//! Response: [`BudgetHistoryResponse`](primitives::sentry::campaign_budget_history::BudgetHistoryResponse)
+//!
 //! #### POST `/v5/campaign/:id/close` (auth required)
 //!
 //! Close the campaign.
@@ -400,6 +921,24 @@
 //!
 //! Closes the campaign by setting [`Campaign.budget`](primitives::Campaign::budget) so that `remaining budget = 0`.
 //!
+//! The other validator of the Campaign is then notified with
+//! [POST `/v5/campaign/:id/closed-notification`](#post-v5campaignidclosed-notification-auth-required),
+//! if the Sentry has a signer for creating authentication tokens.
+//!
+//! Response: [`SuccessResponse`]
+//!
+//! #### POST `/v5/campaign/:id/closed-notification` (auth required)
+//!
+//! Notification that the campaign has been closed on the other validator of the Campaign.
+//!
+//! The route is handled by [`campaign::campaign_closed_notification()`].
+//!
+//! Request must be sent by the other Campaign validator, i.e. [`Auth.uid`] is
+//! one of the Campaign validators and it's not the validator receiving the request.
+//!
+//! Sets the remaining budget of the campaign to `0` in the same way as
+//! [POST `/v5/campaign/:id/close`](#post-v5campaignidclose-auth-required).
+//!
 //! Response: [`SuccessResponse`]
 //!
 //! ## Analytics
@@ -476,6 +1015,89 @@
 //!
 //! See [GET `/v5/analytics`](#get-v5analytics)
 //!
+//! ## Validator
+//!
+//! All routes are implemented under the module [validator].
+//!
+//! ### Route parameters
+//!
+//! - `:id` - [`ValidatorId`]
+//!
+//! ### Routes
+//!
+//! #### GET `/v5/validator/:id/fees-summary`
+//!
+//! The periodic summaries of the fees earned by the validator,
+//! created by the validator worker once per [`Config.worker.fees_summary_interval`].
+//!
+//! The route is handled by [`validator::get_fees_summary()`].
+//!
+//! Request query parameters: [`FeesSummaryQuery`]
+//!
+//! Response: [`FeesSummaryResponse`]
+//!
+//! ##### Examples
+//!
+//! Query:
+//!
+//! ```
+#![doc = include_str!("../../primitives/examples/fees_summary_query.rs")]
+//! ```
+//!
+//! Response:
+//!
+//! ```
+#![doc = include_str!("../../primitives/examples/fees_summary_response.rs")]
+//! ```
+//!
+//! #### POST `/v5/validator/:id/fees-summary` (auth required)
+//!
+//! Creates or updates the fees summary of a Channel for the given period.
+//! Only the validator itself ([`Auth.uid`] == `:id`) can create its summaries.
+//!
+//! The route is handled by [`validator::create_fees_summary()`].
+//!
+//! Request body (json): [`FeesSummaryRequest`]
+//!
+//! Response: [`ValidatorFeesSummary`]
+//!
+//! ##### Examples
+//!
+//! Request:
+//!
+//! ```
+#![doc = include_str!("../../primitives/examples/fees_summary_request.rs")]
+//! ```
+//!
+//! ## GET `/v5/whoami`
+//!
+//! Proves that the Sentry speaks for the validator it claims to.
+//! The validator signs the [`challenge_message()`] of the given random challenge,
+//! which other validators verify before propagating messages to the Sentry.
+//!
+//! Responds with `404 Not Found` if the Sentry is not set up to sign challenges.
+//!
+//! The route is handled by [`get_whoami()`]
+//!
+//! Request query parameters: [`WhoamiQuery`]
+//!
+//! Response: [`WhoamiResponse`]
+//!
+//! ##### Examples
+//!
+//! Query:
+//!
+//! `GET /v5/whoami?challenge=b1a9a4f0c1d2`
+//!
+//! Response:
+//!
+//! ```json
+//! {
+//!     "validatorId": "0x80690751969B234697e9059e04ed72195c3507fa",
+//!     "signature": "Dummy adapter signature for 7c6c...e2f1 by 0x80690751969B234697e9059e04ed72195c3507fa"
+//! }
+//! ```
+//!
 //! ## GET `/cfg`
 //!
 //! Gets the config that the validator is running on.
@@ -492,6 +1114,23 @@
 #![doc = include_str!("../../primitives/examples/get_cfg_response.rs")]
 //! ```
 //!
+//! ## GET `/health`
+//!
+//! Checks whether the validator's Sentry is up and running.
+//! It is used by other validators before propagating messages.
+//!
+//! The route is handled by [`get_health()`]
+//!
+//! Response: [`SuccessResponse`]
+//!
+//! ##### Examples
+//!
+//! Response:
+//!
+//! ```json
+//! { "success": true }
+//! ```
+//!
 //! [`Adapter`]: adapter::Adapter
 //! [`Address`]: primitives::Address
 //! [`AllowedKey`]: primitives::analytics::query::AllowedKey
@@ -506,6 +1145,7 @@
 //! [`Campaign`]: primitives::Campaign
 //! [`Campaign.creator`]: primitives::Campaign::creator
 //! [`Campaign.event_submission`]: primitives::Campaign::event_submission
+//! [`Campaign.version`]: primitives::Campaign::version
 //! [`CampaignId`]: primitives::CampaignId
 //! [`Channel`]: primitives::Channel
 //! [`Channel.leader`]: primitives::Channel::leader
@@ -514,25 +1154,45 @@
 //! [`ChannelPayRequest`]: primitives::sentry::ChannelPayRequest
 //! [`check_access()`]: crate::access::check_access
 //! [`Config.limits.msgs_find`]: primitives::config::Limits::msgs_find
+//! [`Config.limits.approved_states_find`]: primitives::config::Limits::approved_states_find
+//! [`Config.worker.fees_summary_interval`]: primitives::config::ValidatorWorkerConfig::fees_summary_interval
+//! [`FeesSummaryQuery`]: primitives::sentry::fees_summary::FeesSummaryQuery
+//! [`FeesSummaryRequest`]: primitives::sentry::fees_summary::FeesSummaryRequest
+//! [`FeesSummaryResponse`]: primitives::sentry::fees_summary::FeesSummaryResponse
 //! [`Event`]: primitives::sentry::Event
 //! [`Heartbeat`]: primitives::validator::Heartbeat
 //! [`MessageTypes`]: primitives::validator::MessageTypes
 //! [`NewState`]: primitives::validator::NewState
 //! [`SpenderResponse`]: primitives::sentry::SpenderResponse
 //! [`SuccessResponse`]: primitives::sentry::SuccessResponse
+//! [`ValidatorFeesSummary`]: primitives::sentry::fees_summary::ValidatorFeesSummary
 //! [`ValidatorId`]: primitives::ValidatorId
+//! [`WhoamiQuery`]: primitives::sentry::whoami::WhoamiQuery
+//! [`WhoamiResponse`]: primitives::sentry::whoami::WhoamiResponse
+//! [`challenge_message()`]: primitives::sentry::whoami::challenge_message
 
 pub use analytics::get_analytics;
 
 pub use cfg::get_cfg;
 
+pub use health::get_health;
+
+pub use whoami::get_whoami;
+
 // `analytics` module has single request, so we only export this request
 mod analytics;
 pub mod campaign;
 // `cfg` module has single request, so we only export this request
 mod cfg;
+// `health` module has single request, so we only export this request
 pub mod channel;
+mod health;
 
 pub mod routers;
 
 mod units_for_slot;
+
+pub mod validator;
+
+// `whoami` module has single request, so we only export this request
+mod whoami;
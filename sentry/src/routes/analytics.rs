@@ -117,7 +117,10 @@ mod test {
     };
 
     use super::*;
-    use adapter::ethereum::test_util::{GANACHE_1, GANACHE_1337};
+    use adapter::{
+        ethereum::test_util::{GANACHE_1, GANACHE_1337},
+        primitives::Scope,
+    };
     use chrono::{Datelike, Utc};
     use primitives::{
         analytics::{
@@ -1124,21 +1127,25 @@ mod test {
             era: 0,
             uid: IDS[&PUBLISHER],
             chain: GANACHE_1337.clone(),
+            scope: Scope::Full,
         });
         let advertiser_auth = Extension(Auth {
             era: 0,
             uid: IDS[&ADVERTISER],
             chain: GANACHE_1337.clone(),
+            scope: Scope::Full,
         });
         let admin_auth = Extension(Auth {
             era: 0,
             uid: IDS[&LEADER],
             chain: GANACHE_1337.clone(),
+            scope: Scope::Full,
         });
         let admin_auth_other_chain = Extension(Auth {
             era: 0,
             uid: IDS[&LEADER],
             chain: GANACHE_1.clone(),
+            scope: Scope::Full,
         });
 
         // test for publisher
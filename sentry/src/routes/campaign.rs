@@ -1,40 +1,64 @@
 //! `/v5/campaign` routes
 use std::{
     cmp::{max, Ordering},
+    collections::{HashMap, HashSet},
     sync::Arc,
+    time::Duration,
 };
 
 use axum::{Extension, Json};
+use chrono::{Datelike, Timelike, Utc};
 use deadpool_postgres::PoolError;
-use futures::{future::try_join_all, TryFutureExt};
-use slog::error;
+use futures::{
+    future::{join_all, try_join_all},
+    TryFutureExt,
+};
+use slog::{debug, error, warn, Logger};
 use thiserror::Error;
 use tokio_postgres::error::SqlState;
 
-use adapter::{prelude::*, Adapter, Error as AdaptorError};
+use adapter::{prelude::*, Error as AdaptorError};
 use primitives::{
-    campaign_validator::Validator,
+    balances::UncheckedState,
+    campaign_validator::{
+        validate_addresses, validate_campaign_budget, validate_event_types,
+        validate_max_validator_fee, validate_pacing, validate_unit_pricing_overrides,
+        Error as ValidatorError, Validator,
+    },
     sentry::{
+        campaign_budget_history::{BudgetHistoryQuery, BudgetHistoryResponse},
+        campaign_burn_rate::{BurnRateQuery, BurnRateResponse},
         campaign_create::CreateCampaign,
+        campaign_get::{
+            BudgetUtilization, CampaignResponse, CampaignsBatchRequest, CampaignsBatchResponse,
+        },
+        campaign_health::{CampaignHealthResponse, CampaignHealthStatus},
         campaign_list::{CampaignListQuery, CampaignListResponse},
         campaign_modify::ModifyCampaign,
-        SuccessResponse,
+        event_stats::{EventStatsQuery, EventStatsResponse, HourlyEventStats},
+        DateHour, StateAge, SuccessResponse,
     },
-    spender::Spendable,
-    Address, Campaign, CampaignId, ChainOf, Channel, ChannelId, Deposit, UnifiedNum,
+    spender::{Spendable, SpendableSource},
+    unified_num::FromPrecisionError,
+    util::ApiUrl,
+    Address, BigNum, Campaign, CampaignId, ChainOf, Channel, ChannelId, Config, Deposit,
+    UnifiedNum,
 };
 
 use crate::{
     application::Qs,
     db::{
         accounting::{get_accounting, Side},
+        analytics::{fetch_campaign_spent, fetch_event_stats},
         campaign::{
-            get_campaign_ids_by_channel, list_campaigns, list_campaigns_total_count,
-            update_campaign,
+            fetch_campaigns, get_campaign_budget_history, get_campaign_ids_by_channel,
+            insert_campaigns_within_limit, list_campaigns, list_campaigns_total_count,
+            publish_campaign_closed, update_campaign, LimitedInsert,
         },
-        insert_campaign, insert_channel,
+        insert_channel,
         spendable::update_spendable,
-        CampaignRemaining, DbPool, RedisError,
+        validator_message::{latest_approve_state, latest_new_state},
+        DbPool, MissingRemaining, RedisError,
     },
     response::ResponseError,
     Application, Auth,
@@ -56,10 +80,16 @@ pub enum Error {
     ChannelTokenNotWhitelisted,
     #[error("Campaign was not modified because of spending constraints")]
     CampaignNotModified,
+    #[error("Campaign has been modified in the meantime, the expected version does not match")]
+    VersionConflict,
+    #[error("Campaign validation: {0}")]
+    Validation(#[from] ValidatorError),
     #[error("Error while updating spendable for creator: {0}")]
     LatestSpendable(#[from] LatestSpendableError),
     #[error("Redis error: {0}")]
     Redis(#[from] RedisError),
+    #[error(transparent)]
+    MissingRemaining(#[from] MissingRemaining),
     #[error("DB Pool error: {0}")]
     Pool(#[from] PoolError),
 }
@@ -68,36 +98,62 @@ pub enum Error {
 pub enum LatestSpendableError {
     #[error("Adapter: {0}")]
     Adapter(#[from] AdaptorError),
-    #[error("Overflow occurred while converting native token precision to unified precision")]
-    Overflow,
+    #[error("Converting native token precision to unified precision: {0}")]
+    Precision(#[from] FromPrecisionError),
     #[error("DB Pool error: {0}")]
     Pool(#[from] PoolError),
 }
 
+/// Converts the deposit from the native token precision to [`UnifiedNum::PRECISION`].
+///
+/// The dust of the deposit which can't be represented in [`UnifiedNum::PRECISION`]
+/// is floored and logged, since it can't be spent anyway.
+pub(crate) fn unified_deposit(
+    logger: &Logger,
+    deposit: Deposit<BigNum>,
+    channel_context: &ChainOf<Channel>,
+    spender: Address,
+) -> Result<Deposit<UnifiedNum>, FromPrecisionError> {
+    let (deposit, dust) = Deposit::<UnifiedNum>::from_precision_with_dust(
+        deposit,
+        channel_context.token.precision.get(),
+    )
+    .ok_or(FromPrecisionError::Overflow)?;
+
+    if dust != BigNum::from(0) {
+        debug!(logger, "Flooring the deposit of spender {} in Channel {}", spender, channel_context.context.id(); "dust" => %dust, "module" => "routes::campaign");
+    }
+
+    Ok(deposit)
+}
+
 /// Gets the latest Spendable from the Adapter and updates it in the Database
 /// before returning it
 pub async fn update_latest_spendable<C>(
-    adapter: &Adapter<C>,
-    pool: &DbPool,
+    app: &Application<C>,
     channel_context: &ChainOf<Channel>,
     address: Address,
+    source: SpendableSource,
 ) -> Result<Spendable, LatestSpendableError>
 where
     C: Locked + 'static,
 {
-    let latest_deposit = adapter.get_deposit(channel_context, address).await?;
+    let latest_deposit = app.adapter.get_deposit(channel_context, address).await?;
 
     let spendable = Spendable {
         spender: address,
         channel: channel_context.context,
-        deposit: Deposit::<UnifiedNum>::from_precision(
-            latest_deposit,
-            channel_context.token.precision.get(),
-        )
-        .ok_or(LatestSpendableError::Overflow)?,
+        deposit: unified_deposit(&app.logger, latest_deposit, channel_context, address)?,
     };
 
-    Ok(update_spendable(pool.clone(), &spendable).await?)
+    Ok(update_spendable(
+        app.pool.clone(),
+        &app.redis,
+        &app.redis_keys,
+        &spendable,
+        source,
+    )
+    .await?)
 }
 
 pub async fn fetch_campaign_ids_for_channel(
@@ -152,6 +208,55 @@ pub async fn fetch_campaign_ids_for_channel(
     }
 }
 
+/// The deposit of the `creator` in the Channel which has not been spent yet,
+/// i.e. `total deposited - total spent`.
+///
+/// The deposit is updated from the adapter using [`update_latest_spendable()`].
+async fn get_total_remaining<C: Locked + 'static>(
+    app: &Application<C>,
+    channel_context: &ChainOf<Channel>,
+    creator: Address,
+) -> Result<UnifiedNum, ResponseError> {
+    let accounting_spent = get_accounting(
+        app.pool.clone(),
+        channel_context.context.id(),
+        creator,
+        Side::Spender,
+    )
+    .await?
+    .map(|accounting| accounting.amount)
+    .unwrap_or_default();
+
+    let latest_spendable = update_latest_spendable(
+        app,
+        channel_context,
+        creator,
+        SpendableSource::CampaignCreate,
+    )
+    .await
+    .map_err(|err| match err {
+        // the deposit could not be fetched, e.g. the RPC is unavailable
+        LatestSpendableError::Adapter(err) => ResponseError::adapter(&err),
+        err => ResponseError::BadRequest(err.to_string()),
+    })?;
+    // Gets the latest Spendable for this (spender, channelId) pair
+    let total_deposited = latest_spendable.deposit.total;
+
+    total_deposited
+        .checked_sub(&accounting_spent)
+        .ok_or_else(|| ResponseError::FailedValidation("No more budget remaining".to_string()))
+}
+
+/// The error when the Channel would exceed the
+/// [`Config.limits.max_campaigns_per_channel`](primitives::config::Limits::max_campaigns_per_channel)
+/// active campaigns, see [`insert_campaigns_within_limit()`].
+fn max_campaigns_per_channel_error(config: &Config) -> ResponseError {
+    ResponseError::BadRequest(format!(
+        "A Channel can have at most {} campaigns",
+        config.limits.max_campaigns_per_channel
+    ))
+}
+
 /// POST `/v5/campaign`
 ///
 /// Request body (json): [`CreateCampaign`]
@@ -191,97 +296,343 @@ where
             ResponseError::BadRequest("Failed to fetch/create Channel".to_string())
         })?;
 
-    let total_remaining = {
-        let accounting_spent = get_accounting(
-            app.pool.clone(),
-            campaign.channel.id(),
+    let total_remaining = get_total_remaining(&app, &channel_context, campaign.creator).await?;
+
+    // reserve the budget before reading the remaining of the Channel's campaigns,
+    // so that a concurrent creation by the same creator takes it into account
+    let channel = campaign.channel.id();
+    let other_reservations = app
+        .campaign_remaining
+        .reserve(
+            channel,
             campaign.creator,
-            Side::Spender,
+            &[(campaign.id, campaign.budget)],
+            app.config.sentry.campaign_reservation_window,
         )
         .await?
-        .map(|accounting| accounting.amount)
-        .unwrap_or_default();
+        .ok_or(Error::Calculation)?;
 
-        let latest_spendable =
-            update_latest_spendable(&app.adapter, &app.pool, &channel_context, campaign.creator)
-                .await
-                .map_err(|err| ResponseError::BadRequest(err.to_string()))?;
-        // Gets the latest Spendable for this (spender, channelId) pair
-        let total_deposited = latest_spendable.deposit.total;
+    let created = async {
+        let channel_campaigns =
+            fetch_campaign_ids_for_channel(&app.pool, channel, app.config.limits.campaigns_find)
+                .await?;
 
-        total_deposited
-            .checked_sub(&accounting_spent)
-            .ok_or_else(|| {
-                ResponseError::FailedValidation("No more budget remaining".to_string())
-            })?
-    };
+        let campaigns_remaining_sum = app
+            .campaign_remaining
+            .get_multiple(channel_campaigns.as_slice())
+            .await?
+            .ensure_found()?
+            .sum()
+            .and_then(|sum| sum.checked_add(&other_reservations))
+            // DO NOT FORGET to add the Campaign being created right now!
+            .and_then(|sum| sum.checked_add(&campaign.budget))
+            .ok_or(Error::Calculation)?;
 
-    let channel_campaigns = fetch_campaign_ids_for_channel(
-        &app.pool,
-        campaign.channel.id(),
-        app.config.limits.campaigns_find,
-    )
-    .await?;
+        // `new_campaigns_remaining <= total_remaining` should be upheld
+        // `campaign.budget < total_remaining` should also be upheld!
+        if campaigns_remaining_sum > total_remaining || campaign.budget > total_remaining {
+            return Err(ResponseError::BadRequest(
+                "Not enough deposit left for the new campaign's budget".to_string(),
+            ));
+        }
 
-    let campaigns_remaining_sum = app
-        .campaign_remaining
-        .get_multiple(channel_campaigns.as_slice())
-        .await?
-        .iter()
-        .sum::<Option<UnifiedNum>>()
-        .ok_or(Error::Calculation)?
-        // DO NOT FORGET to add the Campaign being created right now!
-        .checked_add(&campaign.budget)
-        .ok_or(Error::Calculation)?;
+        // If the campaign is being created, the amount spent is 0, therefore remaining = budget
+        let remaining_set = app
+            .campaign_remaining
+            .set_initial(campaign.id, campaign.budget)
+            .await
+            .map_err(|_| {
+                ResponseError::BadRequest("Couldn't set remaining while creating campaign".to_string())
+            })?;
 
-    // `new_campaigns_remaining <= total_remaining` should be upheld
-    // `campaign.budget < total_remaining` should also be upheld!
-    if campaigns_remaining_sum > total_remaining || campaign.budget > total_remaining {
-        return Err(ResponseError::BadRequest(
-            "Not enough deposit left for the new campaign's budget".to_string(),
-        ));
+        // If for some reason the randomly generated `CampaignId` exists in Redis
+        // This should **NOT** happen!
+        if !remaining_set {
+            return Err(ResponseError::Conflict(
+                "The generated CampaignId already exists, please repeat the request".to_string(),
+            ));
+        }
+
+        // Channel insertion can never create a `SqlState::UNIQUE_VIOLATION`
+        // Insert the Campaign too, unless the Channel has reached its maximum active campaigns
+        let inserted = insert_campaigns_within_limit(
+            &app.pool,
+            channel,
+            std::slice::from_ref(&campaign),
+            app.config.limits.max_campaigns_per_channel,
+        )
+        .await;
+
+        match inserted {
+            Err(error) => {
+                error!(&app.logger, "{}", &error; "module" => "create_campaign");
+                match error {
+                    PoolError::Backend(error) if error.code() == Some(&SqlState::UNIQUE_VIOLATION) => {
+                        Err(ResponseError::Conflict(
+                            "Campaign already exists".to_string(),
+                        ))
+                    }
+                    _err => Err(ResponseError::BadRequest(
+                        "Error occurred when inserting Campaign in Database; please try again later"
+                            .to_string(),
+                    )),
+                }
+            }
+            Ok(LimitedInsert::LimitReached) => {
+                remove_campaigns_remaining(&app, &[campaign.id]).await;
+
+                Err(max_campaigns_per_channel_error(&app.config))
+            }
+            Ok(LimitedInsert::NotInserted) => Err(ResponseError::BadRequest(
+                "Encountered error while creating Campaign; please try again".to_string(),
+            )),
+            Ok(LimitedInsert::Inserted) => Ok(()),
+        }
+    }
+    .await;
+
+    // the Campaign is either created and counted in the remaining sum or it has failed,
+    // either way the reservation is no longer needed
+    release_reservations(&app, channel, campaign.creator, &[campaign.id]).await;
+
+    created.map(|_| Json(campaign))
+}
+
+/// POST `/v5/campaign/bulk`
+///
+/// Request body (json): an array of [`CreateCampaign`]s for the same Channel,
+/// at most [`Config.limits.campaigns_bulk_create`](primitives::config::Limits::campaigns_bulk_create).
+///
+/// Response: an array of the created [`Campaign`]s, in the order of the request.
+///
+/// Creates either all of the campaigns or none of them.
+/// All the campaigns are validated before creating any of them and their budgets
+/// combined should not exceed the remaining deposit of the creator in the Channel.
+/// If an entry fails, it responds with [`ResponseError::BulkEntry`] for the first failing entry.
+pub async fn create_campaigns_bulk<C>(
+    Json(create_campaigns): Json<Vec<CreateCampaign>>,
+    Extension(auth): Extension<Auth>,
+    Extension(app): Extension<Arc<Application<C>>>,
+) -> Result<Json<Vec<Campaign>>, ResponseError>
+where
+    C: Locked + 'static,
+{
+    let limit = app.config.limits.campaigns_bulk_create;
+    if create_campaigns.is_empty() || create_campaigns.len() > limit as usize {
+        return Err(ResponseError::BadRequest(format!(
+            "Between 1 and {} campaigns can be created at once",
+            limit
+        )));
+    }
+
+    let mut campaigns: Vec<ChainOf<Campaign>> = Vec::with_capacity(create_campaigns.len());
+    let mut campaign_ids = HashSet::with_capacity(create_campaigns.len());
+    for (index, create_campaign) in create_campaigns.into_iter().enumerate() {
+        let campaign_context = create_campaign
+            .into_campaign()
+            .validate(&app.config, app.adapter.whoami())
+            .map_err(|err| {
+                ResponseError::FailedValidation(err.to_string()).for_bulk_entry(index)
+            })?;
+
+        let campaign = &campaign_context.context;
+        if auth.uid.to_address() != campaign.creator {
+            return Err(ResponseError::Forbidden(
+                "Request not sent by campaign creator".to_string(),
+            )
+            .for_bulk_entry(index));
+        }
+
+        if let Some(first) = campaigns.first() {
+            if campaign.channel != first.context.channel {
+                return Err(ResponseError::BadRequest(
+                    "All campaigns should be for the same Channel".to_string(),
+                )
+                .for_bulk_entry(index));
+            }
+        }
+
+        if !campaign_ids.insert(campaign.id) {
+            return Err(
+                ResponseError::Conflict("Duplicate CampaignId in the request".to_string())
+                    .for_bulk_entry(index),
+            );
+        }
+
+        campaigns.push(campaign_context);
     }
 
-    // If the campaign is being created, the amount spent is 0, therefore remaining = budget
-    let remaining_set = CampaignRemaining::new(app.redis.clone())
-        .set_initial(campaign.id, campaign.budget)
+    let channel_context = campaigns[0].of_channel();
+    let creator = campaigns[0].context.creator;
+
+    // make sure that the Channel is available in the DB
+    insert_channel(&app.pool, &channel_context)
         .await
-        .map_err(|_| {
-            ResponseError::BadRequest("Couldn't set remaining while creating campaign".to_string())
+        .map_err(|error| {
+            error!(&app.logger, "{}", &error; "module" => "create_campaigns_bulk");
+
+            ResponseError::BadRequest("Failed to fetch/create Channel".to_string())
         })?;
 
-    // If for some reason the randomly generated `CampaignId` exists in Redis
-    // This should **NOT** happen!
-    if !remaining_set {
-        return Err(ResponseError::Conflict(
-            "The generated CampaignId already exists, please repeat the request".to_string(),
-        ));
-    }
+    let total_remaining = get_total_remaining(&app, &channel_context, creator).await?;
 
-    // Channel insertion can never create a `SqlState::UNIQUE_VIOLATION`
-    // Insert the Campaign too
-    match insert_campaign(&app.pool, &campaign).await {
-        Err(error) => {
-            error!(&app.logger, "{}", &error; "module" => "create_campaign");
-            match error {
-                PoolError::Backend(error) if error.code() == Some(&SqlState::UNIQUE_VIOLATION) => {
-                    Err(ResponseError::Conflict(
-                        "Campaign already exists".to_string(),
-                    ))
+    // reserve the budgets before reading the remaining of the Channel's campaigns,
+    // so that a concurrent creation by the same creator takes them into account
+    let channel = channel_context.context.id();
+    let reservations: Vec<(CampaignId, UnifiedNum)> = campaigns
+        .iter()
+        .map(|campaign_context| (campaign_context.context.id, campaign_context.context.budget))
+        .collect();
+    let reserved: Vec<CampaignId> = reservations.iter().map(|(id, _budget)| *id).collect();
+    let other_reservations = app
+        .campaign_remaining
+        .reserve(
+            channel,
+            creator,
+            &reservations,
+            app.config.sentry.campaign_reservation_window,
+        )
+        .await?
+        .ok_or(Error::Calculation)?;
+
+    let created = async {
+        let channel_campaigns =
+            fetch_campaign_ids_for_channel(&app.pool, channel, app.config.limits.campaigns_find)
+                .await?;
+
+        let mut campaigns_remaining_sum = app
+            .campaign_remaining
+            .get_multiple(channel_campaigns.as_slice())
+            .await?
+            .ensure_found()?
+            .sum()
+            .and_then(|sum| sum.checked_add(&other_reservations))
+            .ok_or(Error::Calculation)?;
+
+        // the budgets of all the new campaigns are added in order,
+        // so the first campaign exceeding the remaining deposit fails
+        for (index, campaign_context) in campaigns.iter().enumerate() {
+            campaigns_remaining_sum = campaigns_remaining_sum
+                .checked_add(&campaign_context.context.budget)
+                .ok_or(Error::Calculation)?;
+
+            if campaigns_remaining_sum > total_remaining {
+                return Err(ResponseError::BadRequest(
+                    "Not enough deposit left for the new campaign's budget".to_string(),
+                )
+                .for_bulk_entry(index));
+            }
+        }
+
+        let campaigns: Vec<Campaign> = campaigns
+            .into_iter()
+            .map(|campaign_context| campaign_context.context)
+            .collect();
+
+        // the campaigns' remaining which have been set, removed if the creation fails
+        let mut remaining_set = Vec::with_capacity(campaigns.len());
+        for (index, campaign) in campaigns.iter().enumerate() {
+            // If the campaign is being created, the amount spent is 0, therefore remaining = budget
+            let set_result = app
+                .campaign_remaining
+                .set_initial(campaign.id, campaign.budget)
+                .await;
+
+            let error = match set_result {
+                Ok(true) => {
+                    remaining_set.push(campaign.id);
+                    continue;
+                }
+                Ok(false) => ResponseError::Conflict(
+                    "The CampaignId already exists, please repeat the request".to_string(),
+                ),
+                Err(_) => ResponseError::BadRequest(
+                    "Couldn't set remaining while creating campaign".to_string(),
+                ),
+            };
+
+            remove_campaigns_remaining(&app, &remaining_set).await;
+
+            return Err(error.for_bulk_entry(index));
+        }
+
+        let inserted = insert_campaigns_within_limit(
+            &app.pool,
+            channel,
+            &campaigns,
+            app.config.limits.max_campaigns_per_channel,
+        )
+        .await;
+
+        match inserted {
+            Ok(LimitedInsert::Inserted) => Ok(Json(campaigns)),
+            result => {
+                remove_campaigns_remaining(&app, &remaining_set).await;
+
+                match result {
+                    Ok(LimitedInsert::LimitReached) => {
+                        Err(max_campaigns_per_channel_error(&app.config))
+                    }
+                    Err(PoolError::Backend(error))
+                        if error.code() == Some(&SqlState::UNIQUE_VIOLATION) =>
+                    {
+                        error!(&app.logger, "{}", &error; "module" => "create_campaigns_bulk");
+
+                        Err(ResponseError::Conflict(
+                            "Campaign already exists".to_string(),
+                        ))
+                    }
+                    Err(error) => {
+                        error!(&app.logger, "{}", &error; "module" => "create_campaigns_bulk");
+
+                        Err(ResponseError::BadRequest(
+                            "Error occurred when inserting Campaigns in Database; please try again later"
+                                .to_string(),
+                        ))
+                    }
+                    _ => Err(ResponseError::BadRequest(
+                        "Encountered error while creating Campaigns; please try again".to_string(),
+                    )),
                 }
-                _err => Err(ResponseError::BadRequest(
-                    "Error occurred when inserting Campaign in Database; please try again later"
-                        .to_string(),
-                )),
             }
         }
-        Ok(false) => Err(ResponseError::BadRequest(
-            "Encountered error while creating Campaign; please try again".to_string(),
-        )),
-        _ => Ok(()),
-    }?;
+    }
+    .await;
+
+    // the Campaigns are either created and counted in the remaining sum or they have failed,
+    // either way the reservations are no longer needed
+    release_reservations(&app, channel, creator, &reserved).await;
+
+    created
+}
 
-    Ok(Json(campaign))
+/// Removes the remaining of campaigns which have failed to be created,
+/// a failure is only logged as the creation has already failed.
+async fn remove_campaigns_remaining<C: Locked + 'static>(
+    app: &Application<C>,
+    campaigns: &[CampaignId],
+) {
+    if let Err(error) = app.campaign_remaining.remove(campaigns).await {
+        error!(&app.logger, "Failed to remove the remaining of the campaigns: {}", &error; "module" => "create_campaigns_bulk", "campaigns" => ?campaigns);
+    }
+}
+
+/// Releases the budget reservations of campaigns which have been created or have failed to,
+/// a failure is only logged as the reservations expire after
+/// [`Config.sentry.campaign_reservation_window`](primitives::config::SentryConfig::campaign_reservation_window).
+async fn release_reservations<C: Locked + 'static>(
+    app: &Application<C>,
+    channel: ChannelId,
+    creator: Address,
+    campaigns: &[CampaignId],
+) {
+    if let Err(error) = app
+        .campaign_remaining
+        .release(channel, creator, campaigns)
+        .await
+    {
+        error!(&app.logger, "Failed to release the budget reservations of the campaigns: {}", &error; "module" => "create_campaign", "campaigns" => ?campaigns);
+    }
 }
 
 /// GET `/v5/campaign/list`
@@ -307,95 +658,612 @@ pub async fn campaign_list<C: Locked + 'static>(
     Ok(Json(list_response))
 }
 
+/// GET `/v5/campaign/:id`
+///
+/// Returns the Campaign alongside its [`BudgetUtilization`],
+/// calculated from the remaining budget in Redis at the time of the request.
+///
+/// If there is no remaining budget set for the Campaign,
+/// the `utilization` is `None`.
+pub async fn get_campaign<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(campaign_context): Extension<ChainOf<Campaign>>,
+) -> Result<Json<CampaignResponse>, ResponseError> {
+    Ok(Json(
+        campaign_response(&app, campaign_context.context).await?,
+    ))
+}
+
+/// POST `/v5/campaign/batch`
+///
+/// Request body (json): [`CampaignsBatchRequest`]
+///
+/// Response: [`CampaignsBatchResponse`]
+///
+/// Returns the found Campaigns as in [`get_campaign()`], in the order of the request.
+/// Unknown Campaigns and the ones with a token which is not whitelisted are skipped.
+pub async fn get_campaigns_batch<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Json(request): Json<CampaignsBatchRequest>,
+) -> Result<Json<CampaignsBatchResponse>, ResponseError> {
+    let limit = app.config.limits.campaigns_find;
+    if request.campaigns.len() > limit as usize {
+        return Err(ResponseError::FailedValidation(format!(
+            "No more than {} campaigns can be requested at once",
+            limit
+        )));
+    }
+
+    let found = fetch_campaigns(&app.pool, &request.campaigns)
+        .await?
+        .into_iter()
+        .map(|campaign| (campaign.id, campaign))
+        .collect::<HashMap<_, _>>();
+
+    // in the order of the request
+    let whitelisted = request
+        .campaigns
+        .iter()
+        .filter_map(|campaign_id| found.get(campaign_id).cloned())
+        .filter(|campaign| app.config.find_chain_of(campaign.channel.token).is_some());
+
+    let campaigns =
+        try_join_all(whitelisted.map(|campaign| campaign_response(&app, campaign))).await?;
+
+    Ok(Json(CampaignsBatchResponse { campaigns }))
+}
+
+/// The [`Campaign`] alongside its [`BudgetUtilization`], if there is a remaining budget set for it.
+async fn campaign_response<C: Locked + 'static>(
+    app: &Application<C>,
+    campaign: Campaign,
+) -> Result<CampaignResponse, ResponseError> {
+    let utilization = app
+        .campaign_remaining
+        .get_remaining_opt(campaign.id)
+        .await?
+        .map(|remaining| {
+            // the remaining can be negative, see `CampaignRemaining::get_multiple`
+            let remaining = UnifiedNum::from_u64(remaining.max(0).unsigned_abs());
+
+            BudgetUtilization::new(campaign.budget, remaining)
+        });
+
+    Ok(CampaignResponse {
+        campaign,
+        utilization,
+    })
+}
+
 /// POST `/v5/campaign/:id/close` (auth required)
 ///
 /// **Can only be called by the [`Campaign.creator`]!**
 /// To close a campaign, just set it's budget to what it's spent so far (so that remaining == 0)
 /// newBudget = totalSpent, i.e. newBudget = oldBudget - remaining
+///
+/// The closed Campaign is published for invalidating the cached units-for-slot Campaigns,
+/// see [`publish_campaign_closed()`], and the other validators of the Campaign
+/// are notified, see [`notify_campaign_closed()`].
 pub async fn close_campaign<C: Locked + 'static>(
     Extension(app): Extension<Arc<Application<C>>>,
     Extension(auth): Extension<Auth>,
     Extension(campaign_context): Extension<ChainOf<Campaign>>,
 ) -> Result<Json<SuccessResponse>, ResponseError> {
-    let mut campaign = campaign_context.context;
-
-    if auth.uid.to_address() != campaign.creator {
-        Err(ResponseError::Forbidden(
+    if auth.uid.to_address() != campaign_context.context.creator {
+        return Err(ResponseError::Forbidden(
             "Request not sent by campaign creator".to_string(),
-        ))
-    } else {
-        let old_remaining = app
-            .campaign_remaining
-            .getset_remaining_to_zero(campaign.id)
-            .await
-            .map_err(|e| ResponseError::BadRequest(e.to_string()))?;
+        ));
+    }
 
-        campaign.budget = campaign
-            .budget
-            .checked_sub(&UnifiedNum::from(old_remaining))
-            .ok_or_else(|| {
-                ResponseError::BadRequest("Campaign budget overflow/underflow".to_string())
-            })?;
-        update_campaign(&app.pool, &campaign).await?;
+    set_remaining_to_zero(
+        &app,
+        campaign_context.context.clone(),
+        auth.uid.to_address(),
+    )
+    .await?;
 
-        Ok(Json(SuccessResponse { success: true }))
-    }
+    notify_campaign_closed(app, campaign_context);
+
+    Ok(Json(SuccessResponse { success: true }))
 }
 
-pub mod update_campaign {
-    use primitives::Config;
+/// The timeout of the closed Campaign notifications sent to the other validators.
+const CLOSED_NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(5);
 
-    use crate::db::{accounting::Side, CampaignRemaining};
+/// Notifies the other validators of the Campaign that it has been closed,
+/// see [`campaign_closed_notification()`].
+///
+/// The notifications are sent in the background with the [`Application::http_client`],
+/// so the response of the closing request doesn't wait for the other validators.
+/// They are authenticated with the [`Application::whoami_signer`],
+/// without it no notifications are sent.
+/// The notifications are best-effort, since the other validators will notice the closed
+/// Campaign on their next deposit or accounting comparison anyway, failures are only logged.
+pub(crate) fn notify_campaign_closed<C: Locked + 'static>(
+    app: Arc<Application<C>>,
+    campaign_context: ChainOf<Campaign>,
+) {
+    tokio::spawn(async move { send_campaign_closed_notifications(&app, &campaign_context).await });
+}
 
-    use super::*;
+async fn send_campaign_closed_notifications<C: Locked + 'static>(
+    app: &Application<C>,
+    campaign_context: &ChainOf<Campaign>,
+) {
+    let signer = match &app.whoami_signer {
+        Some(signer) => signer,
+        None => return,
+    };
+    let campaign = &campaign_context.context;
+    let whoami = app.adapter.whoami();
+    let client = &app.http_client;
 
-    /// POST `/v5/campaign/:id` (auth required)
-    ///
-    /// Request body (json): [`ModifyCampaign`](`primitives::sentry::campaign_modify::ModifyCampaign`)
-    /// consists of all of the editable fields of the [`Campaign`]
-    ///
-    /// Response[`Campaign`](`primitives::Campaign`)
-    ///
-    /// Ensures that the remaining funds for all campaigns <= total remaining funds (total deposited - total spent)
-    ///
-    pub async fn handle_route<C: Locked + 'static>(
-        Json(modify_campaign_fields): Json<ModifyCampaign>,
-        Extension(campaign_being_mutated): Extension<ChainOf<Campaign>>,
-        Extension(app): Extension<Arc<Application<C>>>,
-    ) -> Result<Json<Campaign>, ResponseError> {
-        // modify Campaign
-        let modified_campaign = modify_campaign(
-            app.adapter.clone(),
-            &app.pool,
-            &app.config,
-            &app.campaign_remaining,
-            &campaign_being_mutated,
-            modify_campaign_fields,
-        )
-        .await
-        .map_err(|err| ResponseError::BadRequest(err.to_string()))?;
+    let other_validators = campaign
+        .validators
+        .iter()
+        .filter(|validator| validator.id != whoami)
+        .collect::<Vec<_>>();
+
+    let results = join_all(other_validators.iter().map(|validator| async move {
+        let token = signer
+            .get_auth(campaign_context.chain.chain_id, validator.id)
+            .map_err(|err| err.to_string())?;
+        let endpoint = ApiUrl::parse(&validator.url)
+            .map_err(|err| err.to_string())?
+            .join(&format!("v5/campaign/{}/closed-notification", campaign.id))
+            .map_err(|err| err.to_string())?;
+
+        client
+            .post(endpoint)
+            .timeout(CLOSED_NOTIFICATION_TIMEOUT)
+            .bearer_auth(token)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map(|_response| ())
+            .map_err(|err| err.to_string())
+    }))
+    .await;
+
+    for (validator, result) in other_validators.iter().zip(results) {
+        if let Err(err) = result {
+            warn!(&app.logger, "Failed to notify validator {} of the closed Campaign {}", validator.id, campaign.id; "error" => err, "module" => "routes::campaign");
+        }
+    }
+}
 
-        Ok(Json(modified_campaign))
+/// POST `/v5/campaign/:id/closed-notification` (auth required)
+///
+/// **Can only be called by the other validator of the Campaign!**
+///
+/// Notifies this validator that the Campaign has been closed on the other validator,
+/// so the remaining budget can be set to `0` without waiting for the next
+/// deposit or accounting comparison, see [`close_campaign()`].
+pub async fn campaign_closed_notification<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(auth): Extension<Auth>,
+    Extension(campaign_context): Extension<ChainOf<Campaign>>,
+) -> Result<Json<SuccessResponse>, ResponseError> {
+    let campaign = campaign_context.context;
+
+    let is_other_validator =
+        auth.uid != app.adapter.whoami() && campaign.find_validator(&auth.uid).is_some();
+
+    if !is_other_validator {
+        return Err(ResponseError::Forbidden(
+            "Request not sent by the other Campaign validator".to_string(),
+        ));
     }
 
-    pub async fn modify_campaign<C: Locked + 'static>(
-        adapter: Adapter<C>,
-        pool: &DbPool,
-        config: &Config,
-        campaign_remaining: &CampaignRemaining,
-        campaign_context: &ChainOf<Campaign>,
-        modify_campaign: ModifyCampaign,
-    ) -> Result<Campaign, Error> {
-        let campaign = &campaign_context.context;
-        // *NOTE*: When updating campaigns make sure sum(campaigns.map(getRemaining)) <= totalDeposited - totalSpent
-        // !WARNING!: totalSpent != sum(campaign.map(c => c.spending)) therefore we must always calculate remaining funds based on total_deposit - lastApprovedNewState.spenders[user]
-        // *NOTE*: To close a campaign set campaignBudget to campaignSpent so that spendable == 0
+    set_remaining_to_zero(&app, campaign, auth.uid.to_address()).await?;
 
-        let delta_budget = if let Some(new_budget) = modify_campaign.budget {
-            get_delta_budget(campaign_remaining, campaign, new_budget).await?
-        } else {
-            None
-        };
+    Ok(Json(SuccessResponse { success: true }))
+}
+
+/// Sets the remaining budget of the Campaign to `0` and
+/// updates the [`Campaign.budget`](Campaign::budget) accordingly,
+/// the budget change is recorded with the `actor`.
+///
+/// The closed Campaign is published with [`publish_campaign_closed()`].
+///
+/// Returns the updated Campaign and its remaining budget before it was set to `0`,
+/// which can be restored with [`restore_remaining()`].
+pub(crate) async fn set_remaining_to_zero<C: Locked + 'static>(
+    app: &Application<C>,
+    mut campaign: Campaign,
+    actor: Address,
+) -> Result<(Campaign, UnifiedNum), ResponseError> {
+    let old_remaining = app
+        .campaign_remaining
+        .getset_remaining_to_zero(campaign.id)
+        .await
+        .map_err(|e| ResponseError::BadRequest(e.to_string()))?;
+
+    campaign.budget = campaign
+        .budget
+        .checked_sub(&UnifiedNum::from(old_remaining))
+        .ok_or_else(|| {
+            ResponseError::BadRequest("Campaign budget overflow/underflow".to_string())
+        })?;
+
+    match update_campaign(&app.pool, &campaign, actor).await? {
+        Some(updated) => {
+            // publishing is best-effort, the Campaign has already been closed
+            let _result = publish_campaign_closed(&app.redis, &app.redis_keys, campaign.id).await;
+
+            Ok((updated, UnifiedNum::from(old_remaining)))
+        }
+        None => {
+            // the Campaign has been modified in the meantime,
+            // restore the remaining budget we've just set to 0
+            app.campaign_remaining
+                .increase_by(campaign.id, UnifiedNum::from(old_remaining))
+                .await
+                .map_err(|e| ResponseError::BadRequest(e.to_string()))?;
+
+            Err(ResponseError::Conflict(Error::VersionConflict.to_string()))
+        }
+    }
+}
+
+/// Reverts [`set_remaining_to_zero()`] by restoring the `remaining` budget
+/// of the returned `campaign`, the budget change is recorded with the `actor`.
+pub(crate) async fn restore_remaining<C: Locked + 'static>(
+    app: &Application<C>,
+    mut campaign: Campaign,
+    remaining: UnifiedNum,
+    actor: Address,
+) -> Result<(), ResponseError> {
+    campaign.budget = campaign.budget.checked_add(&remaining).ok_or_else(|| {
+        ResponseError::BadRequest("Campaign budget overflow/underflow".to_string())
+    })?;
+
+    if update_campaign(&app.pool, &campaign, actor)
+        .await?
+        .is_none()
+    {
+        return Err(ResponseError::Conflict(Error::VersionConflict.to_string()));
+    }
+
+    app.campaign_remaining
+        .increase_by(campaign.id, remaining)
+        .await
+        .map_err(|e| ResponseError::BadRequest(e.to_string()))?;
+
+    Ok(())
+}
+
+/// GET `/v5/campaign/:id/events/stats` (auth required)
+///
+/// Returns the hourly accepted events (count & payout) of the requested publisher
+/// for the Campaign, including the hours without any events.
+///
+/// **Can only be called by the requested publisher, the [`Campaign.creator`] or the Campaign validators!**
+pub async fn campaign_event_stats<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(auth): Extension<Auth>,
+    Extension(campaign_context): Extension<ChainOf<Campaign>>,
+    Qs(query): Qs<EventStatsQuery>,
+) -> Result<Json<EventStatsResponse>, ResponseError> {
+    let campaign = &campaign_context.context;
+
+    let auth_address = auth.uid.to_address();
+    let is_allowed = auth_address == query.publisher
+        || auth_address == campaign.creator
+        || campaign.find_validator(&auth.uid).is_some();
+
+    if !is_allowed {
+        return Err(ResponseError::Forbidden(
+            "Request not sent by the publisher, the campaign creator or a validator".to_string(),
+        ));
+    }
+
+    let (start, end) = query.time_range();
+    if start > end {
+        return Err(ResponseError::BadRequest(
+            "start should be before or equal to end".to_string(),
+        ));
+    }
+
+    let hours = (end.to_datetime() - start.to_datetime()).num_hours() + 1;
+    if hours > i64::from(app.config.limits.analytics_find) {
+        return Err(ResponseError::BadRequest(format!(
+            "Time range cannot be more than {} hours",
+            app.config.limits.analytics_find
+        )));
+    }
+
+    let mut with_events = fetch_event_stats(&app.pool, campaign.id, query.publisher, start, end)
+        .await?
+        .into_iter()
+        .peekable();
+
+    // fill in the hours without any events
+    let stats = (0..hours)
+        .rev()
+        .map(|hours_before_end| {
+            let time = end - hours_before_end;
+
+            with_events
+                .next_if(|hourly| hourly.time == time)
+                .unwrap_or_else(|| HourlyEventStats::empty(time))
+        })
+        .collect();
+
+    Ok(Json(EventStatsResponse {
+        campaign: campaign.id,
+        publisher: query.publisher,
+        stats,
+    }))
+}
+
+/// GET `/v5/campaign/:id/burn-rate`
+///
+/// Returns the spend rate of the Campaign, based on the analytics of the last
+/// [`BurnRateQuery.hours`](BurnRateQuery::hours) hourly buckets, and the projected time
+/// at which the remaining budget in Redis will be exhausted at the same rate.
+pub async fn campaign_burn_rate<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(campaign_context): Extension<ChainOf<Campaign>>,
+    Qs(query): Qs<BurnRateQuery>,
+) -> Result<Json<BurnRateResponse>, ResponseError> {
+    let campaign = &campaign_context.context;
+
+    let hours = query.hours();
+    if hours == 0 || hours > app.config.limits.analytics_find {
+        return Err(ResponseError::BadRequest(format!(
+            "hours should be between 1 and {}",
+            app.config.limits.analytics_find
+        )));
+    }
+
+    let now = Utc::now();
+    let current_hour = DateHour::from_ymdh(now.year(), now.month(), now.day(), now.hour());
+    let start = current_hour - i64::from(hours - 1);
+
+    let spent = fetch_campaign_spent(&app.pool, campaign.id, start).await?;
+
+    let remaining = app
+        .campaign_remaining
+        .get_remaining_opt(campaign.id)
+        .await?
+        // the remaining can be negative, see `CampaignRemaining::get_multiple`
+        .map(|remaining| UnifiedNum::from_u64(remaining.max(0).unsigned_abs()));
+
+    Ok(Json(BurnRateResponse::project(
+        campaign.id,
+        start.to_datetime(),
+        now,
+        spent,
+        remaining,
+    )))
+}
+
+/// GET `/v5/campaign/:id/health` (auth required)
+///
+/// Combines the remaining budget of the Campaign, the Channel accounting of the [`Campaign.creator`]
+/// and the last approved state of the Channel in a single [`CampaignHealthStatus`].
+///
+/// **Can only be called by the [`Campaign.creator`] or the Campaign validators!**
+pub async fn campaign_health<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(auth): Extension<Auth>,
+    Extension(campaign_context): Extension<ChainOf<Campaign>>,
+) -> Result<Json<CampaignHealthResponse>, ResponseError> {
+    let campaign = &campaign_context.context;
+    let channel = campaign.channel;
+
+    let is_allowed =
+        auth.uid.to_address() == campaign.creator || campaign.find_validator(&auth.uid).is_some();
+
+    if !is_allowed {
+        return Err(ResponseError::Forbidden(
+            "Request not sent by the campaign creator or a validator".to_string(),
+        ));
+    }
+
+    let now = Utc::now();
+
+    let remaining = app
+        .campaign_remaining
+        .get_remaining_opt(campaign.id)
+        .await?
+        // the remaining can be negative, see `CampaignRemaining::get_multiple`
+        .map(|remaining| UnifiedNum::from_u64(remaining.max(0).unsigned_abs()));
+
+    let accounting_spent = get_accounting(
+        app.pool.clone(),
+        channel.id(),
+        campaign.creator,
+        Side::Spender,
+    )
+    .await?
+    .map(|accounting| accounting.amount)
+    .unwrap_or_default();
+
+    let approve_state = latest_approve_state(&app.pool, &channel).await?;
+
+    let approved_spent = match &approve_state {
+        Some(approve_state) => {
+            latest_new_state::<UncheckedState>(&app.pool, &channel, &approve_state.msg.state_root)
+                .await?
+                .and_then(|new_state| {
+                    new_state
+                        .msg
+                        .balances
+                        .spenders
+                        .get(&campaign.creator)
+                        .copied()
+                })
+                .unwrap_or_default()
+        }
+        None => UnifiedNum::ZERO,
+    };
+
+    // the accounting can only grow, so the approved spending should never be larger
+    let pending_spent = accounting_spent
+        .checked_sub(&approved_spent)
+        .unwrap_or_default();
+
+    let unapproved_since = approve_state
+        .as_ref()
+        .map(|approve_state| approve_state.received)
+        .unwrap_or(campaign.created);
+
+    let status = CampaignHealthStatus::derive(
+        remaining,
+        pending_spent,
+        (now - unapproved_since).to_std().unwrap_or_default(),
+        app.config.sentry.campaign_awaiting_approval_after,
+        app.config.sentry.campaign_stalled_after,
+    );
+
+    Ok(Json(CampaignHealthResponse {
+        campaign: campaign.id,
+        status,
+        utilization: remaining.map(|remaining| BudgetUtilization::new(campaign.budget, remaining)),
+        accounting_spent,
+        approved_spent,
+        pending_spent,
+        last_approve_state: approve_state.map(|approve_state| {
+            StateAge::new(
+                approve_state.msg.into_inner().state_root,
+                approve_state.received,
+                now,
+            )
+        }),
+    }))
+}
+
+/// GET `/v5/campaign/:id/budget/history` (auth required)
+///
+/// Returns the changes of the Campaign budget, oldest first, with the budget
+/// before and after the change and who has changed it.
+///
+/// **Can only be called by the [`Campaign.creator`] or the Campaign validators!**
+pub async fn campaign_budget_history<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(auth): Extension<Auth>,
+    Extension(campaign_context): Extension<ChainOf<Campaign>>,
+    Qs(query): Qs<BudgetHistoryQuery>,
+) -> Result<Json<BudgetHistoryResponse>, ResponseError> {
+    let campaign = &campaign_context.context;
+
+    let is_allowed =
+        auth.uid.to_address() == campaign.creator || campaign.find_validator(&auth.uid).is_some();
+
+    if !is_allowed {
+        return Err(ResponseError::Forbidden(
+            "Request not sent by the campaign creator or a validator".to_string(),
+        ));
+    }
+
+    let limit = app.config.limits.campaigns_find;
+    let skip = query
+        .page
+        .checked_mul(limit.into())
+        .ok_or_else(|| ResponseError::FailedValidation("Page and/or limit is too large".into()))?;
+
+    let (history, pagination) =
+        get_campaign_budget_history(&app.pool, campaign.id, skip, limit.into()).await?;
+
+    Ok(Json(BudgetHistoryResponse {
+        campaign: campaign.id,
+        history,
+        pagination,
+    }))
+}
+
+pub mod update_campaign {
+    use crate::db::{accounting::Side, CampaignRemaining};
+
+    use super::*;
+
+    /// POST `/v5/campaign/:id` (auth required)
+    ///
+    /// Request body (json): [`ModifyCampaign`](`primitives::sentry::campaign_modify::ModifyCampaign`)
+    /// consists of all of the editable fields of the [`Campaign`]
+    ///
+    /// Response[`Campaign`](`primitives::Campaign`)
+    ///
+    /// Ensures that the remaining funds for all campaigns <= total remaining funds (total deposited - total spent)
+    ///
+    pub async fn handle_route<C: Locked + 'static>(
+        Json(modify_campaign_fields): Json<ModifyCampaign>,
+        Extension(campaign_being_mutated): Extension<ChainOf<Campaign>>,
+        Extension(app): Extension<Arc<Application<C>>>,
+        Extension(auth): Extension<Auth>,
+    ) -> Result<Json<Campaign>, ResponseError> {
+        // modify Campaign
+        let modified_campaign = modify_campaign(
+            &app,
+            &campaign_being_mutated,
+            modify_campaign_fields,
+            auth.uid.to_address(),
+        )
+        .await
+        .map_err(|err| match err {
+            Error::VersionConflict => ResponseError::Conflict(err.to_string()),
+            Error::Validation(_) => ResponseError::FailedValidation(err.to_string()),
+            Error::LatestSpendable(LatestSpendableError::Adapter(err)) => {
+                ResponseError::adapter(&err)
+            }
+            err => ResponseError::BadRequest(err.to_string()),
+        })?;
+
+        Ok(Json(modified_campaign))
+    }
+
+    /// Modifies the Campaign, a change of the budget is recorded with the `actor`,
+    /// see [`update_campaign()`].
+    pub async fn modify_campaign<C: Locked + 'static>(
+        app: &Application<C>,
+        campaign_context: &ChainOf<Campaign>,
+        modify_campaign: ModifyCampaign,
+        actor: Address,
+    ) -> Result<Campaign, Error> {
+        let config = &app.config;
+        let pool = &app.pool;
+        let campaign_remaining = &app.campaign_remaining;
+        let campaign = &campaign_context.context;
+
+        // Reject early if the Campaign has been modified since the client has fetched it
+        if modify_campaign.version != campaign.version {
+            return Err(Error::VersionConflict);
+        }
+
+        let modified_campaign = modify_campaign.clone().apply(campaign.clone());
+
+        // Reject invalid AdUnit pricing overrides before changing the remaining budget
+        validate_unit_pricing_overrides(
+            &modified_campaign,
+            config.limits.unit_pricing_override_multiplier,
+        )
+        .map_err(ValidatorError::from)?;
+        validate_event_types(&modified_campaign, &config.event_types)
+            .map_err(ValidatorError::from)?;
+        validate_pacing(&modified_campaign).map_err(ValidatorError::from)?;
+        validate_addresses(&modified_campaign).map_err(ValidatorError::from)?;
+        validate_max_validator_fee(&modified_campaign.validators, &campaign_context.token)
+            .map_err(ValidatorError::from)?;
+
+        // Lowering the budget should not go below the minimum campaign budget of the token
+        if let Some(new_budget) = modify_campaign.budget {
+            if new_budget < campaign.budget {
+                validate_campaign_budget(new_budget, &campaign_context.token)
+                    .map_err(ValidatorError::from)?;
+            }
+        }
+
+        // *NOTE*: When updating campaigns make sure sum(campaigns.map(getRemaining)) <= totalDeposited - totalSpent
+        // !WARNING!: totalSpent != sum(campaign.map(c => c.spending)) therefore we must always calculate remaining funds based on total_deposit - lastApprovedNewState.spenders[user]
+        // *NOTE*: To close a campaign set campaignBudget to campaignSpent so that spendable == 0
+
+        let delta_budget = if let Some(new_budget) = modify_campaign.budget {
+            get_delta_budget(campaign_remaining, campaign, new_budget).await?
+        } else {
+            None
+        };
 
         // if we are going to update the budget
         // validate the totalDeposit - totalSpent for all campaign
@@ -413,10 +1281,10 @@ pub mod update_campaign {
             .unwrap_or_default();
 
             let latest_spendable = update_latest_spendable(
-                &adapter,
-                pool,
+                app,
                 &campaign_context.of_channel(),
                 campaign.creator,
+                SpendableSource::CampaignModify,
             )
             .await?;
 
@@ -427,67 +1295,118 @@ pub mod update_campaign {
                 .checked_sub(&accounting_spent)
                 .ok_or(Error::Calculation)?;
 
-            let channel_campaigns = fetch_campaign_ids_for_channel(
-                pool,
-                campaign.channel.id(),
-                config.limits.campaigns_find,
-            )
-            .await
-            .map_err(|_| Error::FailedUpdate("couldn't fetch campaigns for channel".to_string()))?;
+            // reserve the budget increase before reading the remaining of the Channel's campaigns,
+            // so that a concurrent creation or modification by the same creator takes it into account
+            let channel = campaign.channel.id();
+            let other_reservations = match delta_budget {
+                DeltaBudget::Increase(increase_by) => campaign_remaining
+                    .reserve(
+                        channel,
+                        campaign.creator,
+                        &[(campaign.id, increase_by)],
+                        config.sentry.campaign_reservation_window,
+                    )
+                    .await?
+                    .ok_or(Error::Calculation)?,
+                DeltaBudget::Decrease(_) => UnifiedNum::from_u64(0),
+            };
 
-            // this will include the Campaign we are currently modifying
-            let campaigns_current_remaining_sum = campaign_remaining
-                .get_multiple(channel_campaigns.as_slice())
-                .await?
-                .iter()
-                .sum::<Option<UnifiedNum>>()
-                .ok_or(Error::Calculation)?;
+            let changed = async {
+                let channel_campaigns =
+                    fetch_campaign_ids_for_channel(pool, channel, config.limits.campaigns_find)
+                        .await
+                        .map_err(|_| {
+                            Error::FailedUpdate("couldn't fetch campaigns for channel".to_string())
+                        })?;
+
+                // this will include the Campaign we are currently modifying
+                let campaigns_current_remaining_sum = campaign_remaining
+                    .get_multiple(channel_campaigns.as_slice())
+                    .await?
+                    .ensure_found()?
+                    .sum()
+                    .ok_or(Error::Calculation)?;
 
-            // apply the delta_budget to the sum
-            let new_campaigns_remaining = match delta_budget {
-                DeltaBudget::Increase(increase_by) => {
-                    campaigns_current_remaining_sum.checked_add(&increase_by)
+                // apply the delta_budget to the sum
+                let new_campaigns_remaining = match delta_budget {
+                    // the budgets reserved by concurrent requests are also taken into account
+                    DeltaBudget::Increase(increase_by) => campaigns_current_remaining_sum
+                        .checked_add(&increase_by)
+                        .and_then(|sum| sum.checked_add(&other_reservations)),
+                    DeltaBudget::Decrease(decrease_by) => {
+                        campaigns_current_remaining_sum.checked_sub(&decrease_by)
+                    }
                 }
-                DeltaBudget::Decrease(decrease_by) => {
-                    campaigns_current_remaining_sum.checked_sub(&decrease_by)
+                .ok_or(Error::Calculation)?;
+
+                // `new_campaigns_remaining <= total_remaining` should be upheld
+                if new_campaigns_remaining > total_remaining {
+                    return Err(Error::NewBudget(
+                        "Not enough deposit left for the campaign's new budget".to_string(),
+                    ));
                 }
-            }
-            .ok_or(Error::Calculation)?;
 
-            // `new_campaigns_remaining <= total_remaining` should be upheld
-            if new_campaigns_remaining > total_remaining {
-                return Err(Error::NewBudget(
-                    "Not enough deposit left for the campaign's new budget".to_string(),
-                ));
+                // there is a chance that the new remaining will be negative even when increasing the budget
+                // We don't currently use this value but can be used to perform additional checks or return messages accordingly
+                let _campaign_remaining = match delta_budget {
+                    DeltaBudget::Increase(increase_by) => {
+                        campaign_remaining
+                            .increase_by(campaign.id, increase_by)
+                            .await?
+                    }
+                    DeltaBudget::Decrease(decrease_by) => {
+                        campaign_remaining
+                            .decrease_by(campaign.id, decrease_by)
+                            .await?
+                    }
+                };
+
+                Ok::<_, Error>(())
             }
+            .await;
 
-            // there is a chance that the new remaining will be negative even when increasing the budget
-            // We don't currently use this value but can be used to perform additional checks or return messages accordingly
-            let _campaign_remaining = match delta_budget {
-                DeltaBudget::Increase(increase_by) => {
-                    campaign_remaining
-                        .increase_by(campaign.id, increase_by)
-                        .await?
-                }
-                DeltaBudget::Decrease(decrease_by) => {
-                    campaign_remaining
-                        .decrease_by(campaign.id, decrease_by)
-                        .await?
+            if let DeltaBudget::Increase(_) = delta_budget {
+                // either way the reservation is no longer needed
+                if let Err(error) = campaign_remaining
+                    .release(channel, campaign.creator, &[campaign.id])
+                    .await
+                {
+                    error!(&app.logger, "Failed to release the budget reservation of the campaign: {}", &error; "module" => "modify_campaign", "campaign" => %campaign.id);
                 }
-            };
+            }
+
+            changed?;
         }
 
-        let modified_campaign = modify_campaign.apply(campaign.clone());
-        update_campaign(pool, &modified_campaign).await?;
+        match update_campaign(pool, &modified_campaign, actor).await? {
+            Some(updated_campaign) => Ok(updated_campaign),
+            // The Campaign has been modified concurrently after we've loaded it,
+            // revert the change of the remaining budget before rejecting the modification
+            None => {
+                match delta_budget {
+                    Some(DeltaBudget::Increase(increase_by)) => {
+                        campaign_remaining
+                            .decrease_by(campaign.id, increase_by)
+                            .await?;
+                    }
+                    Some(DeltaBudget::Decrease(decrease_by)) => {
+                        campaign_remaining
+                            .increase_by(campaign.id, decrease_by)
+                            .await?;
+                    }
+                    None => {}
+                }
 
-        Ok(modified_campaign)
+                Err(Error::VersionConflict)
+            }
+        }
     }
 
     /// Delta Budget describes the difference between the New and Old budget
     /// It is used to decrease or increase the remaining budget instead of setting it up directly
     /// This way if a new event alters the remaining budget in Redis while the modification of campaign hasn't finished
     /// it will correctly update the remaining using an atomic redis operation with `INCRBY` or `DECRBY` instead of using `SET`
-    #[derive(Debug, Clone, PartialEq)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
     pub(super) enum DeltaBudget<T> {
         Increase(T),
         Decrease(T),
@@ -568,15 +1487,22 @@ pub mod insert_events {
     use adapter::prelude::*;
     use primitives::{
         balances::{Balances, CheckedState, OverflowError},
+        config::{PayoutRounding, TokenInfo},
         sentry::{Event, InsertEventsRequest, SuccessResponse},
         Address, Campaign, CampaignId, ChainOf, DomainError, UnifiedNum, ValidatorDesc,
     };
 
     use crate::{
-        access::{self, check_access},
+        access::{
+            self, attribute_conversions, check_access, dedup_events, release_dedup_events,
+            restore_attributed_clicks, store_attributable_clicks,
+        },
         analytics,
-        db::{accounting::spend_amount, CampaignRemaining, DbPool, PoolError, RedisError},
-        payout::get_payout,
+        db::{
+            accounting::{spend_amount, Error as AccountingError},
+            CampaignRemaining, DbPool, PoolError, RedisError,
+        },
+        payout::{fetch_slots_min_per_impression, get_payout},
         response::ResponseError,
         spender::fee::calculate_fee,
         Application, Auth, Session,
@@ -592,6 +1518,8 @@ pub mod insert_events {
         Postgres(#[from] PoolError),
         #[error(transparent)]
         Overflow(#[from] OverflowError),
+        #[error(transparent)]
+        Accounting(#[from] AccountingError),
     }
 
     #[derive(Debug, Error, PartialEq, Eq)]
@@ -606,6 +1534,10 @@ pub mod insert_events {
         CampaignRemainingNotEnoughForPayout,
         #[error("Campaign ran out of remaining budget to spend")]
         CampaignOutOfBudget,
+        #[error(
+            "Campaign has reached its daily spending cap, events are paused until the next UTC day"
+        )]
+        CampaignDailyCapReached,
     }
 
     /// POST `/v5/campaign/:id/events`
@@ -641,9 +1573,39 @@ pub mod insert_events {
     ) -> Result<(), ResponseError> {
         let campaign = &campaign_context.context;
 
+        // reject the whole batch if any of the event types is not supported
+        if let Some(event) = events
+            .iter()
+            .find(|event| app.config.find_event_type(&event.event_type()).is_none())
+        {
+            return Err(ResponseError::BadRequest(format!(
+                "Event type {} is not supported",
+                event.event_type()
+            )));
+        }
+
+        // reject the whole batch if any of the events is for the zero address publisher
+        if events.iter().any(|event| event.publisher().is_zero()) {
+            return Err(ResponseError::BadRequest(
+                "Event publisher cannot be the zero address".to_string(),
+            ));
+        }
+
+        // reject the whole batch if the Campaign does not pay for any of the event types
+        if let Some(event) = events
+            .iter()
+            .find(|event| !campaign.accepts_event(event.event_type()))
+        {
+            return Err(ResponseError::BadRequest(format!(
+                "Campaign does not accept {} events",
+                event.event_type()
+            )));
+        }
+
         // handle events - check access
         check_access(
             &app.redis,
+            &app.redis_keys,
             session,
             auth,
             &app.config.limits.ip_rate_limit,
@@ -668,15 +1630,70 @@ pub mod insert_events {
             (Some(leader), Some(follower)) => (leader, follower),
         };
 
-        let events_success = spend_for_events(
+        // the Campaign's dedup window takes precedence over the token's one
+        let dedup_window = campaign
+            .event_submission
+            .as_ref()
+            .and_then(|event_submission| event_submission.dedup_window)
+            .or(campaign_context.token.event_dedup_window);
+
+        let events = match dedup_window {
+            Some(window) => {
+                dedup_events(&app.redis, &app.redis_keys, window, campaign, events).await?
+            }
+            None => events,
+        };
+        let deduped_events = dedup_window.map(|_window| events.clone());
+
+        // conversions are paid only if attributed to a prior paid click
+        let (events, attributed_clicks) =
+            attribute_conversions(&app.redis, &app.redis_keys, campaign, events).await?;
+
+        let events_success = match spend_for_events(
             app,
             &campaign_context.context,
             events,
             session,
             leader,
             follower,
+            PayoutOptions::from(&campaign_context.token),
+        )
+        .await
+        {
+            Ok(events_success) => events_success,
+            // the events have been spent before the Campaign ran out of budget
+            Err(err @ Error::Event(EventError::CampaignOutOfBudget)) => return Err(err.into()),
+            Err(err) => {
+                // the events have not been spent, so they can be submitted again
+                if let Some(deduped_events) = deduped_events {
+                    if let Err(release_err) =
+                        release_dedup_events(&app.redis, &app.redis_keys, campaign, &deduped_events)
+                            .await
+                    {
+                        error!(&app.logger, "Failed to release the deduplicated events of Campaign {}", campaign.id; "error" => %release_err, "module" => "routes::campaign");
+                    }
+                }
+
+                // and the conversions can be attributed to their clicks again
+                if let Err(restore_err) =
+                    restore_attributed_clicks(&app.redis, &attributed_clicks, &[]).await
+                {
+                    error!(&app.logger, "Failed to restore the attributed clicks of Campaign {}", campaign.id; "error" => %restore_err, "module" => "routes::campaign");
+                }
+
+                return Err(err.into());
+            }
+        };
+
+        store_attributable_clicks(
+            &app.redis,
+            &app.redis_keys,
+            app.config.sentry.conversion_attribution_window,
+            campaign,
+            &events_success,
         )
         .await?;
+        restore_attributed_clicks(&app.redis, &attributed_clicks, &events_success).await?;
 
         // Record successfully paid out events to Analytics
         analytics_record_spawn(
@@ -718,12 +1735,36 @@ pub mod insert_events {
         });
     }
 
+    /// The payout settings of the [`Campaign`]'s Channel token,
+    /// used by [`spend_for_events()`] for the validator fees.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct PayoutOptions {
+        /// The rounding of the validator fees for each event payout.
+        pub payout_rounding: PayoutRounding,
+        /// The maximum validator fee, if any,
+        /// see [`TokenInfo::max_validator_fee_rate()`].
+        pub max_validator_fee: Option<UnifiedNum>,
+    }
+
+    impl From<&TokenInfo> for PayoutOptions {
+        fn from(token: &TokenInfo) -> Self {
+            Self {
+                payout_rounding: token.payout_rounding,
+                max_validator_fee: token.max_validator_fee_rate(),
+            }
+        }
+    }
+
     /// This function calculates the fee for each validator and each `Event`.
     ///
     /// It then spends the given amounts for:
     ///
     /// - [`Event`] `Publisher` - for payout
-    /// - `Leader` and `Follower` - for validator fees
+    /// - `Leader` and `Follower` - for validator fees, credited to their [`ValidatorDesc::fee_recipient`]
+    ///
+    /// The validator fees are rounded using the [`PayoutOptions::payout_rounding`],
+    /// capped to the [`PayoutOptions::max_validator_fee`] (if any)
+    /// and the Campaign is charged with the sum of the payout and the rounded fees.
     pub async fn spend_for_events<C: Locked + 'static>(
         app: &Application<C>,
         campaign: &Campaign,
@@ -731,17 +1772,44 @@ pub mod insert_events {
         session: &Session,
         leader: &ValidatorDesc,
         follower: &ValidatorDesc,
+        payout_options: PayoutOptions,
     ) -> Result<Vec<(Event, Address, UnifiedNum)>, Error> {
+        let PayoutOptions {
+            payout_rounding,
+            max_validator_fee,
+        } = payout_options;
+
+        let slots_min_per_impression = fetch_slots_min_per_impression(
+            &app.logger,
+            &app.platform_api,
+            campaign.channel.token,
+            &events,
+        )
+        .await;
+
         let event_payouts = events
             .into_iter()
             // If payout returns None, then the ad was not shown (`show = false`)
+            // or the Campaign cannot pay the AdSlot minimum price
             .filter_map(|event| {
-                get_payout(&app.logger, campaign, &event, session)
-                    .map_err(|err| {
-                        EventError::FeeCalculation(DomainError::InvalidArgument(err.to_string()))
-                    })
-                    .transpose()
-                    .map(|result| result.map(|earner_amount| (event, earner_amount)))
+                let default_pricing = app
+                    .config
+                    .find_event_type(&event.event_type())
+                    .and_then(|event_type| event_type.default_pricing.as_ref());
+
+                get_payout(
+                    &app.logger,
+                    campaign,
+                    &event,
+                    session,
+                    &slots_min_per_impression,
+                    default_pricing,
+                )
+                .map_err(|err| {
+                    EventError::FeeCalculation(DomainError::InvalidArgument(err.to_string()))
+                })
+                .transpose()
+                .map(|result| result.map(|earner_amount| (event, earner_amount)))
             })
             .collect::<Result<Vec<_>, _>>()?;
 
@@ -749,9 +1817,11 @@ pub mod insert_events {
             .into_iter()
             .map(|(event, earner_amount)| {
                 let leader_fee =
-                    calculate_fee(earner_amount, leader).map_err(EventError::FeeCalculation)?;
+                    calculate_fee(earner_amount, leader, payout_rounding, max_validator_fee)
+                        .map_err(EventError::FeeCalculation)?;
                 let follower_fee =
-                    calculate_fee(earner_amount, follower).map_err(EventError::FeeCalculation)?;
+                    calculate_fee(earner_amount, follower, payout_rounding, max_validator_fee)
+                        .map_err(EventError::FeeCalculation)?;
 
                 Ok((event, earner_amount, leader_fee, follower_fee))
             })
@@ -768,8 +1838,8 @@ pub mod insert_events {
                     .checked_add(&event_spending)
                     .ok_or(EventError::EventPayoutOverflow)?;
 
-                balances.spend(campaign.creator, leader.id.to_address(), *leader_fee)?;
-                balances.spend(campaign.creator, follower.id.to_address(), *follower_fee)?;
+                balances.spend(campaign.creator, leader.fee_recipient(), *leader_fee)?;
+                balances.spend(campaign.creator, follower.fee_recipient(), *follower_fee)?;
                 balances.spend(campaign.creator, earner_amount.0, earner_amount.1)?;
 
                 Ok((spending, balances))
@@ -783,16 +1853,58 @@ pub mod insert_events {
             ));
         }
 
+        // The daily cap of the Campaign pacing should not be exceeded by the events payout
+        if let Some(pacing) = &campaign.pacing {
+            let spent_today = app
+                .campaign_remaining
+                .increase_daily_spent(campaign.id, spending)
+                .await?;
+
+            if spent_today > pacing.daily_cap.to_u64() {
+                app.campaign_remaining
+                    .decrease_daily_spent(campaign.id, spending)
+                    .await?;
+
+                return Err(Error::Event(EventError::CampaignDailyCapReached));
+            }
+        }
+
         // The events payout decreases the remaining budget for the Campaign
-        let remaining = app
+        let remaining = match app
             .campaign_remaining
             .decrease_by(campaign.id, spending)
-            .await?;
+            .await
+        {
+            Ok(remaining) => remaining,
+            Err(error) => {
+                // the events were not paid, so they should not count towards the daily cap
+                if campaign.pacing.is_some() {
+                    app.campaign_remaining
+                        .decrease_daily_spent(campaign.id, spending)
+                        .await?;
+                }
+
+                return Err(error.into());
+            }
+        };
 
         // Update the Accounting records accordingly
         let channel_id = campaign.channel.id();
 
-        spend_amount(app.pool.clone(), channel_id, delta_balances).await?;
+        if let Err(error) = spend_amount(app.pool.clone(), channel_id, delta_balances).await {
+            // the events were not paid, e.g. the Channel accounting is frozen,
+            // so the remaining budget of the Campaign should not be decreased
+            app.campaign_remaining
+                .increase_by(campaign.id, spending)
+                .await?;
+            if campaign.pacing.is_some() {
+                app.campaign_remaining
+                    .decrease_daily_spent(campaign.id, spending)
+                    .await?;
+            }
+
+            return Err(error.into());
+        }
 
         // check if we still have budget to spend, after we've updated both Redis and Postgres
         if remaining.is_negative() {
@@ -824,16 +1936,27 @@ pub mod insert_events {
 
     #[cfg(test)]
     mod test {
+        use std::time::Duration;
+
         use primitives::{
             campaign::Pricing,
-            sentry::IMPRESSION,
-            test_util::{DUMMY_CAMPAIGN, DUMMY_IPFS, PUBLISHER},
+            config::EventTypeConfig,
+            sentry::{ClickId, CustomEventType, EventType, CONVERSION, IMPRESSION},
+            test_util::{
+                CampaignBuilder, DUMMY_CAMPAIGN, DUMMY_IPFS, DUMMY_VALIDATOR_FOLLOWER,
+                DUMMY_VALIDATOR_LEADER, GUARDIAN_2, PUBLISHER,
+            },
             unified_num::FromWhole,
         };
         use redis::aio::MultiplexedConnection;
 
         use crate::{
-            db::{insert_channel, redis_pool::TESTS_POOL},
+            db::{
+                accounting::{get_all_accountings_for_channel, Side},
+                insert_channel,
+                redis_pool::TESTS_POOL,
+                set_accounting_frozen, RedisKeys,
+            },
             test_util::setup_dummy_app,
         };
 
@@ -845,7 +1968,7 @@ pub mod insert_events {
             campaign: CampaignId,
             remaining: i64,
         ) {
-            let key = CampaignRemaining::get_key(campaign);
+            let key = RedisKeys::default().campaign_remaining(campaign);
 
             redis::cmd("SET")
                 .arg(&key)
@@ -858,7 +1981,8 @@ pub mod insert_events {
         #[tokio::test]
         async fn test_has_enough_remaining_budget() {
             let mut redis = TESTS_POOL.get().await.expect("Should get redis connection");
-            let campaign_remaining = CampaignRemaining::new(redis.connection.clone());
+            let campaign_remaining =
+                CampaignRemaining::new(redis.connection.clone(), RedisKeys::default());
             let campaign = DUMMY_CAMPAIGN.id;
             let amount = UnifiedNum::from(10_000);
 
@@ -899,7 +2023,8 @@ pub mod insert_events {
         async fn test_decreasing_remaining_budget() {
             let mut redis = TESTS_POOL.get().await.expect("Should get redis connection");
             let campaign = DUMMY_CAMPAIGN.id;
-            let campaign_remaining = CampaignRemaining::new(redis.connection.clone());
+            let campaign_remaining =
+                CampaignRemaining::new(redis.connection.clone(), RedisKeys::default());
             let amount = UnifiedNum::from(5_000);
 
             set_campaign_remaining(&mut redis, campaign, 9_000).await;
@@ -927,21 +2052,11 @@ pub mod insert_events {
         async fn test_spending_for_events_with_enough_remaining_budget() {
             let mut app = setup_dummy_app().await;
 
-            let campaign = Campaign {
+            let campaign = CampaignBuilder::new(DUMMY_CAMPAIGN.channel)
                 // 1000.00000000
-                budget: UnifiedNum::from_whole(1_000),
-                pricing_bounds: vec![(
-                    IMPRESSION,
-                    Pricing {
-                        // 0.03
-                        min: UnifiedNum::from_whole(0.03),
-                        max: UnifiedNum::from_whole(0.1),
-                    },
-                )]
-                .into_iter()
-                .collect(),
-                ..DUMMY_CAMPAIGN.clone()
-            };
+                .budget_whole(1_000)
+                .pricing(IMPRESSION, 0.03, 0.1)
+                .build();
             let channel_chain = app
                 .config
                 .find_chain_of(DUMMY_CAMPAIGN.channel.token)
@@ -973,9 +2088,16 @@ pub mod insert_events {
 
             // No Campaign Remaining set, should error
             {
-                let spend_event =
-                    spend_for_events(&app, &campaign, events.clone(), &session, leader, follower)
-                        .await;
+                let spend_event = spend_for_events(
+                    &app,
+                    &campaign,
+                    events.clone(),
+                    &session,
+                    leader,
+                    follower,
+                    PayoutOptions::default(),
+                )
+                .await;
 
                 assert!(
                     matches!(
@@ -997,9 +2119,16 @@ pub mod insert_events {
                 )
                 .await;
 
-                let spend_event =
-                    spend_for_events(&app, &campaign, events.clone(), &session, leader, follower)
-                        .await;
+                let spend_event = spend_for_events(
+                    &app,
+                    &campaign,
+                    events.clone(),
+                    &session,
+                    leader,
+                    follower,
+                    PayoutOptions::default(),
+                )
+                .await;
 
                 assert!(
                     spend_event.is_ok(),
@@ -1024,692 +2153,3120 @@ pub mod insert_events {
                 )
             }
         }
-    }
-}
 
-#[cfg(test)]
-mod test {
-    use super::{
-        update_campaign::{get_delta_budget, modify_campaign, DeltaBudget},
-        *,
-    };
-    use crate::{
-        db::{fetch_campaign, redis_pool::TESTS_POOL},
-        test_util::setup_dummy_app,
-    };
-    use adapter::primitives::Deposit;
-    use chrono::{TimeZone, Utc};
-    use primitives::{
-        campaign::validators::Validators,
-        config::GANACHE_CONFIG,
-        sentry::campaign_list::ValidatorParam,
-        test_util::{
-            CREATOR, DUMMY_CAMPAIGN, DUMMY_VALIDATOR_FOLLOWER, DUMMY_VALIDATOR_LEADER, FOLLOWER,
-            GUARDIAN, IDS, LEADER, LEADER_2, PUBLISHER_2,
-        },
-        unified_num::FromWhole,
-        ValidatorDesc, ValidatorId,
-    };
+        #[tokio::test]
+        async fn test_spending_for_events_is_paused_at_the_daily_cap() {
+            let mut app = setup_dummy_app().await;
 
-    /// Test single campaign creation and modification
-    /// &
-    /// Test with multiple campaigns (because of Budget) and modifications
-    #[tokio::test]
-    async fn create_and_modify_with_multiple_campaigns() {
-        let app_guard = setup_dummy_app().await;
-        let app = Extension(Arc::new(app_guard.app.clone()));
+            // Each event spends 0.03 payout + 0.0000009 leader fee + 0.0000006 follower fee
+            let event_spending = UnifiedNum::from_u64(3_000_150);
+            let campaign = CampaignBuilder::new(DUMMY_CAMPAIGN.channel)
+                .budget_whole(1_000)
+                .pricing(IMPRESSION, 0.03, 0.1)
+                // the daily cap is enough for 2 events
+                .daily_cap(UnifiedNum::from_u64(2 * event_spending.to_u64()))
+                .build();
+            let channel_context = app
+                .config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Channel token should be whitelisted in config!")
+                .with_channel(DUMMY_CAMPAIGN.channel);
 
-        // Create a new Campaign with different CampaignId
-        let dummy_channel = DUMMY_CAMPAIGN.channel;
-        let channel_chain = app
-            .config
-            .find_chain_of(dummy_channel.token)
-            .expect("Channel token should be whitelisted in config!");
-        let channel_context = channel_chain.with_channel(dummy_channel);
+            insert_channel(&app.pool, &channel_context)
+                .await
+                .expect("It should insert Channel");
+            set_campaign_remaining(&mut app.redis, campaign.id, campaign.budget.to_u64() as i64)
+                .await;
 
-        // Set the deposit for the CREATOR use for all campaigns in the test
-        assert_eq!(*CREATOR, DUMMY_CAMPAIGN.creator);
-        app.adapter.client.set_deposit(
-            &channel_context,
-            *CREATOR,
-            Deposit {
-                // a deposit 4 times larger than the first Campaign.budget = 500
-                // I.e. 2 000 TOKENS
-                total: UnifiedNum::from_whole(2_000)
-                    .to_precision(channel_context.token.precision.get()),
-            },
-        );
+            let session = Session {
+                ip: None,
+                country: None,
+                referrer_header: None,
+                os: None,
+            };
+            let leader = campaign.leader().unwrap();
+            let follower = campaign.follower().unwrap();
+            let events = vec![Event::Impression {
+                publisher: *PUBLISHER,
+                ad_unit: DUMMY_IPFS[0],
+                ad_slot: DUMMY_IPFS[1],
+                referrer: None,
+            }];
 
-        let auth = Extension(Auth {
-            era: 0,
-            uid: IDS[&CREATOR],
-            chain: channel_context.chain.clone(),
-        });
+            let spend = || {
+                spend_for_events(
+                    &app,
+                    &campaign,
+                    events.clone(),
+                    &session,
+                    leader,
+                    follower,
+                    PayoutOptions::default(),
+                )
+            };
 
-        let campaign_context: ChainOf<Campaign> = {
-            // erases the CampaignId for the CreateCampaign request
-            let mut create = CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None);
-            create.budget = UnifiedNum::from_whole(500);
+            // the events up to the daily cap are paid
+            for _ in 0..2 {
+                spend().await.expect("Should spend for the events");
+            }
 
-            let create_response = create_campaign(Json(create), auth.clone(), app.clone())
+            // the next event exceeds the daily cap
+            let spend_event = spend().await;
+            assert!(
+                matches!(
+                    &spend_event,
+                    Err(Error::Event(EventError::CampaignDailyCapReached))
+                ),
+                "Campaign should have reached its daily cap, result: {spend_event:?}"
+            );
+            assert_eq!(
+                Some(campaign.budget.to_u64() as i64 - 2 * event_spending.to_u64() as i64),
+                app.campaign_remaining
+                    .get_remaining_opt(campaign.id)
+                    .await
+                    .expect("Should have key"),
+                "Only the events within the daily cap should be paid"
+            );
+
+            // the next UTC day the daily spending key has expired
+            redis::cmd("DEL")
+                .arg(app.redis_keys.campaign_daily_spent(campaign.id))
+                .query_async::<_, ()>(&mut app.redis.clone())
                 .await
-                .expect("Should create campaign")
-                .0;
+                .expect("Should delete the daily spent key");
 
-            assert_ne!(DUMMY_CAMPAIGN.id, create_response.id);
+            spend()
+                .await
+                .expect("Should spend for the events on the next day");
+        }
 
-            let campaign_remaining = CampaignRemaining::new(app.redis.clone());
+        #[tokio::test]
+        async fn test_zero_fees_campaign_pays_only_the_publisher() {
+            let mut app = setup_dummy_app().await;
 
-            let remaining = campaign_remaining
-                .get_remaining_opt(create_response.id)
+            let zero_fee = |validator: &ValidatorDesc| ValidatorDesc {
+                fee: UnifiedNum::ZERO,
+                ..validator.clone()
+            };
+            let campaign = CampaignBuilder::new(DUMMY_CAMPAIGN.channel)
+                // 1000.00000000
+                .budget_whole(1_000)
+                .pricing(IMPRESSION, 0.03, 0.1)
+                .validators(
+                    zero_fee(&DUMMY_VALIDATOR_LEADER),
+                    zero_fee(&DUMMY_VALIDATOR_FOLLOWER),
+                )
+                .build();
+            let channel_context = app
+                .config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Channel token should be whitelisted in config!")
+                .with_channel(DUMMY_CAMPAIGN.channel);
+
+            insert_channel(&app.pool, &channel_context)
                 .await
-                .expect("Should get remaining from redis")
-                .expect("There should be value for the Campaign");
+                .expect("It should insert Channel");
 
-            assert_eq!(
-                UnifiedNum::from(50_000_000_000),
-                UnifiedNum::from(remaining.unsigned_abs())
-            );
+            set_campaign_remaining(&mut app.redis, campaign.id, campaign.budget.to_u64() as i64)
+                .await;
 
-            channel_context.clone().with(create_response)
-        };
+            let session = Session {
+                ip: None,
+                country: None,
+                referrer_header: None,
+                os: None,
+            };
 
-        // modify campaign
-        // Deposit = 2 000
-        // old Campaign.budget = 500
-        // new Campaign.budget = 1 000
-        // Deposit left = 1 000
-        let modified = {
-            let new_budget = UnifiedNum::from_whole(1000);
-            let modify = ModifyCampaign {
-                budget: Some(new_budget),
-                validators: None,
-                title: Some("Updated title".to_string()),
-                pricing_bounds: None,
-                event_submission: None,
-                ad_units: None,
-                targeting_rules: None,
+            let impression = Event::Impression {
+                publisher: *PUBLISHER,
+                ad_unit: DUMMY_IPFS[0],
+                ad_slot: DUMMY_IPFS[1],
+                referrer: None,
             };
 
-            let modified_campaign = modify_campaign(
-                app.adapter.clone(),
-                &app.pool,
-                &app.config,
-                &app.campaign_remaining,
-                &campaign_context,
-                modify,
+            let paid = spend_for_events(
+                &app,
+                &campaign,
+                vec![impression.clone(), impression],
+                &session,
+                campaign.leader().unwrap(),
+                campaign.follower().unwrap(),
+                PayoutOptions::default(),
             )
             .await
-            .expect("Should modify campaign");
-
-            assert_eq!(new_budget, modified_campaign.budget);
-            assert_eq!(Some("Updated title".to_string()), modified_campaign.title);
-
-            channel_context.clone().with(modified_campaign)
-        };
-
-        // we have 1000 left from our deposit, so we are using half of it
-        // remaining Deposit = 1 000
-        // new Campaign.budget = 500
-        // Deposit left = 500
-        let _second_campaign = {
-            // erases the CampaignId for the CreateCampaign request
-            let mut create_second =
-                CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None);
-            create_second.budget = UnifiedNum::from_whole(500);
+            .expect("Should spend for the events");
 
-            create_campaign(Json(create_second), auth.clone(), app.clone())
-                .await
-                .expect("Should create campaign")
-                .0
-        };
+            // the publisher is paid exactly the pricing of each event: 0.03
+            for (_event, earner, payout) in paid {
+                assert_eq!(*PUBLISHER, earner);
+                assert_eq!(UnifiedNum::from_whole(0.03), payout);
+            }
 
-        // No budget left for new campaigns
-        // remaining Deposit = 500
-        // new Campaign.budget = 600
-        {
-            // erases the CampaignId for the CreateCampaign request
-            let mut create = CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None);
-            create.budget = UnifiedNum::from_whole(600);
+            // 1000.0 - 2 * 0.03 = 999.94
+            assert_eq!(
+                Some(99_994_000_000),
+                app.campaign_remaining
+                    .get_remaining_opt(campaign.id)
+                    .await
+                    .expect("Should have key")
+            );
 
-            let create_err = create_campaign(Json(create), auth.clone(), app.clone())
-                .await
-                .expect_err("Should return Error response");
+            // no zero amount accounting entries for the validators
+            let accountings =
+                get_all_accountings_for_channel(app.pool.clone(), DUMMY_CAMPAIGN.channel.id())
+                    .await
+                    .expect("Should get the Channel accountings")
+                    .into_iter()
+                    .map(|accounting| (accounting.side, accounting.address, accounting.amount))
+                    .collect::<Vec<_>>();
 
             assert_eq!(
-                ResponseError::BadRequest(
-                    "Not enough deposit left for the new campaign's budget".to_string()
-                ),
-                create_err
+                2,
+                accountings.len(),
+                "Unexpected accountings: {accountings:?}"
             );
+            for expected in [
+                (
+                    Side::Spender,
+                    campaign.creator,
+                    UnifiedNum::from_whole(0.06),
+                ),
+                (Side::Earner, *PUBLISHER, UnifiedNum::from_whole(0.06)),
+            ] {
+                assert!(
+                    accountings.contains(&expected),
+                    "Accounting {expected:?} not found in {accountings:?}"
+                );
+            }
         }
 
-        // modify first campaign, by lowering the budget from 1000 to 900
-        let modified = {
-            let lower_budget = UnifiedNum::from_whole(900);
-            let modify = ModifyCampaign {
-                budget: Some(lower_budget),
-                validators: None,
-                title: None,
-                pricing_bounds: None,
-                event_submission: None,
-                ad_units: None,
-                targeting_rules: None,
-            };
-
-            let modified_campaign = modify_campaign(
-                app.adapter.clone(),
-                &app.pool,
-                &app.config,
-                &app.campaign_remaining,
-                &modified,
-                modify,
-            )
-            .await
-            .expect("Should modify campaign");
+        #[tokio::test]
+        async fn test_validator_fee_is_paid_to_the_fee_addr() {
+            let mut app = setup_dummy_app().await;
 
-            assert_eq!(lower_budget, modified_campaign.budget);
+            let leader = ValidatorDesc {
+                fee_addr: Some(*GUARDIAN_2),
+                ..DUMMY_VALIDATOR_LEADER.clone()
+            };
+            let campaign = CampaignBuilder::new(DUMMY_CAMPAIGN.channel)
+                // 1000.00000000
+                .budget_whole(1_000)
+                .pricing(IMPRESSION, 0.03, 0.1)
+                .validators(leader.clone(), DUMMY_VALIDATOR_FOLLOWER.clone())
+                .build();
+            let channel_context = app
+                .config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Channel token should be whitelisted in config!")
+                .with_channel(DUMMY_CAMPAIGN.channel);
 
-            modified.clone().with(modified_campaign)
-        };
+            insert_channel(&app.pool, &channel_context)
+                .await
+                .expect("It should insert Channel");
 
-        // Just enough budget to create this Campaign
-        // remaining Deposit = 600
-        // new Campaign.budget = 600
-        {
-            // erases the CampaignId for the CreateCampaign request
-            let mut create = CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None);
-            create.budget = UnifiedNum::from_whole(600);
+            set_campaign_remaining(&mut app.redis, campaign.id, campaign.budget.to_u64() as i64)
+                .await;
 
-            let _create_response = create_campaign(Json(create), auth.clone(), app.clone())
-                .await
-                .expect("Should return create campaign");
-        }
+            let session = Session {
+                ip: None,
+                country: None,
+                referrer_header: None,
+                os: None,
+            };
 
-        // Modify a campaign without enough budget
-        // remaining Deposit = 0
-        // new Campaign.budget = 1100
-        // current Campaign.budget = 900
-        {
-            let new_budget = UnifiedNum::from_whole(1_100);
-            let modify = ModifyCampaign {
-                budget: Some(new_budget),
-                validators: None,
-                title: None,
-                pricing_bounds: None,
-                event_submission: None,
-                ad_units: None,
-                targeting_rules: None,
+            let impression = Event::Impression {
+                publisher: *PUBLISHER,
+                ad_unit: DUMMY_IPFS[0],
+                ad_slot: DUMMY_IPFS[1],
+                referrer: None,
             };
 
-            let modify_err = modify_campaign(
-                app.adapter.clone(),
-                &app.pool,
-                &app.config,
-                &app.campaign_remaining,
-                &modified,
-                modify,
+            spend_for_events(
+                &app,
+                &campaign,
+                vec![impression],
+                &session,
+                campaign.leader().unwrap(),
+                campaign.follower().unwrap(),
+                PayoutOptions::default(),
             )
             .await
-            .expect_err("Should return Error response");
+            .expect("Should spend for the event");
 
-            assert!(
-                matches!(&modify_err, Error::NewBudget(string) if string == "Not enough deposit left for the campaign's new budget"),
+            let accountings =
+                get_all_accountings_for_channel(app.pool.clone(), DUMMY_CAMPAIGN.channel.id())
+                    .await
+                    .expect("Should get the Channel accountings")
+                    .into_iter()
+                    .map(|accounting| (accounting.side, accounting.address, accounting.amount))
+                    .collect::<Vec<_>>();
+
+            // 0.03 * 0.00003 = 0.00 000 090
+            // 0.03 * 0.00002 = 0.00 000 060
+            for expected in [
+                (Side::Earner, *PUBLISHER, UnifiedNum::from_whole(0.03)),
+                (Side::Earner, *GUARDIAN_2, UnifiedNum::from(90)),
+                (
+                    Side::Earner,
+                    DUMMY_VALIDATOR_FOLLOWER.id.to_address(),
+                    UnifiedNum::from(60),
+                ),
+            ] {
+                assert!(
+                    accountings.contains(&expected),
+                    "Accounting {expected:?} not found in {accountings:?}"
+                );
+            }
+            assert!(
+                !accountings
+                    .iter()
+                    .any(|(_, address, _)| *address == leader.id.to_address()),
+                "The Leader fee should not be paid to the Leader id: {accountings:?}"
+            );
+        }
+
+        #[tokio::test]
+        async fn test_payout_rounding_sums_to_the_charged_amount() {
+            // 0.03 * 0.00 000 075 = UnifiedNum(2.25)
+            let leader = ValidatorDesc {
+                fee: UnifiedNum::from(75),
+                ..DUMMY_VALIDATOR_LEADER.clone()
+            };
+            // 0.03 * 0.00 000 050 = UnifiedNum(1.5)
+            let follower = ValidatorDesc {
+                fee: UnifiedNum::from(50),
+                ..DUMMY_VALIDATOR_FOLLOWER.clone()
+            };
+
+            let cases = [
+                (PayoutRounding::Floor, 2_u64, 1_u64),
+                (PayoutRounding::Nearest, 2_u64, 2_u64),
+            ];
+
+            for (rounding, leader_fee, follower_fee) in cases {
+                let mut app = setup_dummy_app().await;
+
+                let campaign = CampaignBuilder::new(DUMMY_CAMPAIGN.channel)
+                    // 1000.00000000
+                    .budget_whole(1_000)
+                    .pricing(IMPRESSION, 0.03, 0.1)
+                    .validators(leader.clone(), follower.clone())
+                    .build();
+                let channel_context = app
+                    .config
+                    .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                    .expect("Channel token should be whitelisted in config!")
+                    .with_channel(DUMMY_CAMPAIGN.channel);
+
+                insert_channel(&app.pool, &channel_context)
+                    .await
+                    .expect("It should insert Channel");
+
+                set_campaign_remaining(
+                    &mut app.redis,
+                    campaign.id,
+                    campaign.budget.to_u64() as i64,
+                )
+                .await;
+
+                let session = Session {
+                    ip: None,
+                    country: None,
+                    referrer_header: None,
+                    os: None,
+                };
+
+                let impression = Event::Impression {
+                    publisher: *PUBLISHER,
+                    ad_unit: DUMMY_IPFS[0],
+                    ad_slot: DUMMY_IPFS[1],
+                    referrer: None,
+                };
+
+                spend_for_events(
+                    &app,
+                    &campaign,
+                    vec![impression],
+                    &session,
+                    campaign.leader().unwrap(),
+                    campaign.follower().unwrap(),
+                    PayoutOptions {
+                        payout_rounding: rounding,
+                        max_validator_fee: None,
+                    },
+                )
+                .await
+                .expect("Should spend for the event");
+
+                let accountings =
+                    get_all_accountings_for_channel(app.pool.clone(), DUMMY_CAMPAIGN.channel.id())
+                        .await
+                        .expect("Should get the Channel accountings")
+                        .into_iter()
+                        .map(|accounting| (accounting.side, accounting.address, accounting.amount))
+                        .collect::<Vec<_>>();
+
+                for expected in [
+                    (Side::Earner, *PUBLISHER, UnifiedNum::from_whole(0.03)),
+                    (
+                        Side::Earner,
+                        leader.id.to_address(),
+                        UnifiedNum::from(leader_fee),
+                    ),
+                    (
+                        Side::Earner,
+                        follower.id.to_address(),
+                        UnifiedNum::from(follower_fee),
+                    ),
+                ] {
+                    assert!(
+                        accountings.contains(&expected),
+                        "Accounting {expected:?} not found in {accountings:?} for {rounding:?}"
+                    );
+                }
+
+                let earned = accountings
+                    .iter()
+                    .filter(|(side, _, _)| *side == Side::Earner)
+                    .map(|(_, _, amount)| *amount)
+                    .sum::<Option<UnifiedNum>>()
+                    .expect("Should not overflow");
+                let charged = UnifiedNum::from_whole(0.03)
+                    .checked_add(&UnifiedNum::from(leader_fee + follower_fee))
+                    .expect("Should not overflow");
+
+                // Leader, Follower & Publisher sum to the amount charged to the Campaign
+                assert_eq!(charged, earned, "For {rounding:?}");
+                assert!(
+                    accountings.contains(&(Side::Spender, campaign.creator, charged)),
+                    "The creator should be charged {charged:?}: {accountings:?} for {rounding:?}"
+                );
+                assert_eq!(
+                    Some((campaign.budget - charged).to_u64() as i64),
+                    app.campaign_remaining
+                        .get_remaining_opt(campaign.id)
+                        .await
+                        .expect("Should have key"),
+                    "For {rounding:?}"
+                );
+            }
+        }
+
+        #[tokio::test]
+        async fn test_duplicate_impression_is_paid_once() {
+            let mut app = setup_dummy_app().await;
+
+            let campaign = CampaignBuilder::new(DUMMY_CAMPAIGN.channel)
+                // 1000.00000000
+                .budget_whole(1_000)
+                .pricing(IMPRESSION, 0.03, 0.1)
+                .event_dedup_window(Duration::from_secs(60))
+                .build();
+            let campaign_context = app
+                .config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Channel token should be whitelisted in config!")
+                .with_campaign(campaign.clone());
+
+            // make sure that the Channel is created in Database for the Accounting to work properly
+            insert_channel(&app.pool, &campaign_context.of_channel())
+                .await
+                .expect("It should insert Channel");
+
+            set_campaign_remaining(&mut app.redis, campaign.id, campaign.budget.to_u64() as i64)
+                .await;
+
+            let session = Session {
+                ip: None,
+                country: None,
+                referrer_header: None,
+                os: None,
+            };
+
+            let impression = Event::Impression {
+                publisher: *PUBLISHER,
+                ad_unit: DUMMY_IPFS[0],
+                ad_slot: DUMMY_IPFS[1],
+                referrer: None,
+            };
+
+            // the same impression submitted twice, e.g. a page reload
+            for _ in 0..2 {
+                process_events(
+                    &app,
+                    None,
+                    &session,
+                    &campaign_context,
+                    vec![impression.clone()],
+                )
+                .await
+                .expect("Should process events");
+            }
+
+            // only a single impression payout is spent:
+            // 1000.0 - 0.03 - 0.00 000 090 - 0.00 000 060 = 999.9699985
+            assert_eq!(
+                Some(99_996_999_850),
+                app.campaign_remaining
+                    .get_remaining_opt(campaign.id)
+                    .await
+                    .expect("Should have key")
+            );
+        }
+
+        #[tokio::test]
+        async fn test_duplicate_impression_is_paid_after_a_failed_spend() {
+            let mut app = setup_dummy_app().await;
+
+            let campaign = CampaignBuilder::new(DUMMY_CAMPAIGN.channel)
+                // 1000.00000000
+                .budget_whole(1_000)
+                .pricing(IMPRESSION, 0.03, 0.1)
+                .event_dedup_window(Duration::from_secs(60))
+                .build();
+            let campaign_context = app
+                .config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Channel token should be whitelisted in config!")
+                .with_campaign(campaign.clone());
+
+            insert_channel(&app.pool, &campaign_context.of_channel())
+                .await
+                .expect("It should insert Channel");
+
+            set_campaign_remaining(&mut app.redis, campaign.id, campaign.budget.to_u64() as i64)
+                .await;
+
+            let session = Session {
+                ip: None,
+                country: None,
+                referrer_header: None,
+                os: None,
+            };
+
+            let impression = Event::Impression {
+                publisher: *PUBLISHER,
+                ad_unit: DUMMY_IPFS[0],
+                ad_slot: DUMMY_IPFS[1],
+                referrer: None,
+            };
+
+            // spending fails while the Channel accounting is frozen
+            set_accounting_frozen(&app.pool, campaign.channel.id(), true)
+                .await
+                .expect("Should freeze the accounting");
+            process_events(
+                &app,
+                None,
+                &session,
+                &campaign_context,
+                vec![impression.clone()],
+            )
+            .await
+            .expect_err("Should fail to spend for the events");
+
+            set_accounting_frozen(&app.pool, campaign.channel.id(), false)
+                .await
+                .expect("Should unfreeze the accounting");
+            process_events(&app, None, &session, &campaign_context, vec![impression])
+                .await
+                .expect("Should process events");
+
+            // the impression is paid by the second submission:
+            // 1000.0 - 0.03 - 0.00 000 090 - 0.00 000 060 = 999.9699985
+            assert_eq!(
+                Some(99_996_999_850),
+                app.campaign_remaining
+                    .get_remaining_opt(campaign.id)
+                    .await
+                    .expect("Should have key")
+            );
+        }
+
+        #[tokio::test]
+        async fn test_events_of_the_zero_address_publisher_are_rejected() {
+            let mut app = setup_dummy_app().await;
+
+            let campaign = CampaignBuilder::new(DUMMY_CAMPAIGN.channel)
+                .budget_whole(1_000)
+                .pricing(IMPRESSION, 0.03, 0.1)
+                .build();
+            let campaign_context = app
+                .config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Channel token should be whitelisted in config!")
+                .with_campaign(campaign.clone());
+
+            insert_channel(&app.pool, &campaign_context.of_channel())
+                .await
+                .expect("It should insert Channel");
+
+            let remaining = campaign.budget.to_u64() as i64;
+            set_campaign_remaining(&mut app.redis, campaign.id, remaining).await;
+
+            let session = Session {
+                ip: None,
+                country: None,
+                referrer_header: None,
+                os: None,
+            };
+            let impression = |publisher| Event::Impression {
+                publisher,
+                ad_unit: DUMMY_IPFS[0],
+                ad_slot: DUMMY_IPFS[1],
+                referrer: None,
+            };
+
+            let result = process_events(
+                &app,
+                None,
+                &session,
+                &campaign_context,
+                vec![impression(*PUBLISHER), impression(Address::ZERO)],
+            )
+            .await;
+
+            match result {
+                Err(ResponseError::BadRequest(message)) => {
+                    assert_eq!("Event publisher cannot be the zero address", message)
+                }
+                result => panic!("The whole batch should be rejected, result: {result:?}"),
+            }
+            assert_eq!(
+                Some(remaining),
+                app.campaign_remaining
+                    .get_remaining_opt(campaign.id)
+                    .await
+                    .expect("Should have key"),
+                "None of the events should be paid"
+            );
+        }
+
+        #[tokio::test]
+        async fn test_custom_event_type_is_paid_with_the_default_pricing() {
+            let mut app = setup_dummy_app().await;
+
+            let campaign = CampaignBuilder::new(DUMMY_CAMPAIGN.channel)
+                // 1000.00000000
+                .budget_whole(1_000)
+                .pricing(IMPRESSION, 0.03, 0.1)
+                .build();
+            let campaign_context = app
+                .config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Channel token should be whitelisted in config!")
+                .with_campaign(campaign.clone());
+
+            insert_channel(&app.pool, &campaign_context.of_channel())
+                .await
+                .expect("It should insert Channel");
+
+            set_campaign_remaining(&mut app.redis, campaign.id, campaign.budget.to_u64() as i64)
+                .await;
+
+            let session = Session {
+                ip: None,
+                country: None,
+                referrer_header: None,
+                os: None,
+            };
+
+            let sign_up = CustomEventType::new("SIGN_UP").expect("Should be valid");
+            let event = Event::Custom {
+                event_type: sign_up,
+                publisher: *PUBLISHER,
+                ad_unit: DUMMY_IPFS[0],
+                ad_slot: DUMMY_IPFS[1],
+                referrer: None,
+            };
+
+            // SIGN_UP is not a supported event type by default
+            {
+                let result =
+                    process_events(&app, None, &session, &campaign_context, vec![event.clone()])
+                        .await;
+
+                match result {
+                    Err(ResponseError::BadRequest(message)) => {
+                        assert_eq!("Event type SIGN_UP is not supported", message)
+                    }
+                    result => panic!("SIGN_UP events should be rejected, result: {result:?}"),
+                }
+            }
+
+            app.config.event_types.push(EventTypeConfig {
+                event_type: EventType::Custom(sign_up),
+                // 0.06
+                default_pricing: Some(Pricing {
+                    min: 6_000_000.into(),
+                    max: 10_000_000.into(),
+                }),
+            });
+
+            process_events(&app, None, &session, &campaign_context, vec![event])
+                .await
+                .expect("Should process the SIGN_UP event");
+
+            // The Campaign has no pricing for SIGN_UP, so the default min pricing is paid:
+            // 1000.0 - 0.06 - 0.00 000 180 - 0.00 000 120 = 999.939997
+            assert_eq!(
+                Some(99_993_999_700),
+                app.campaign_remaining
+                    .get_remaining_opt(campaign.id)
+                    .await
+                    .expect("Should have key")
+            );
+        }
+
+        #[tokio::test]
+        async fn test_conversion_is_paid_only_within_the_attribution_window() {
+            let mut app = setup_dummy_app().await;
+            app.config.event_types.push(CONVERSION.into());
+
+            let campaign = CampaignBuilder::new(DUMMY_CAMPAIGN.channel)
+                // 1000.00000000
+                .budget_whole(1_000)
+                .pricing(CONVERSION, 0.06, 0.1)
+                .build();
+            let campaign_context = app
+                .config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Channel token should be whitelisted in config!")
+                .with_campaign(campaign.clone());
+
+            insert_channel(&app.pool, &campaign_context.of_channel())
+                .await
+                .expect("It should insert Channel");
+
+            set_campaign_remaining(&mut app.redis, campaign.id, campaign.budget.to_u64() as i64)
+                .await;
+
+            let session = Session {
+                ip: None,
+                country: None,
+                referrer_header: None,
+                os: None,
+            };
+
+            let click = |click_id| Event::Click {
+                publisher: *PUBLISHER,
+                ad_unit: DUMMY_IPFS[0],
+                ad_slot: DUMMY_IPFS[1],
+                referrer: None,
+                click_id: Some(click_id),
+            };
+            let conversion = |click_id| Event::Conversion {
+                publisher: *PUBLISHER,
+                ad_unit: DUMMY_IPFS[0],
+                ad_slot: DUMMY_IPFS[1],
+                referrer: None,
+                click_id,
+            };
+
+            // The Campaign has no pricing for CLICK, so only the conversion is paid:
+            // 1000.0 - 0.06 - 0.00 000 180 - 0.00 000 120 = 999.939997
+            let remaining_after_conversion = Some(99_993_999_700);
+
+            // in window
+            {
+                app.config.sentry.conversion_attribution_window = Duration::from_secs(60);
+                let click_id = ClickId::new();
+
+                process_events(
+                    &app,
+                    None,
+                    &session,
+                    &campaign_context,
+                    vec![click(click_id)],
+                )
+                .await
+                .expect("Should process the click");
+
+                // the second conversion of the same click is not paid
+                for _ in 0..2 {
+                    process_events(
+                        &app,
+                        None,
+                        &session,
+                        &campaign_context,
+                        vec![conversion(click_id)],
+                    )
+                    .await
+                    .expect("Should process the conversion");
+
+                    assert_eq!(
+                        remaining_after_conversion,
+                        app.campaign_remaining
+                            .get_remaining_opt(campaign.id)
+                            .await
+                            .expect("Should have key")
+                    );
+                }
+            }
+
+            // out of window
+            {
+                app.config.sentry.conversion_attribution_window = Duration::from_millis(20);
+                let click_id = ClickId::new();
+
+                process_events(
+                    &app,
+                    None,
+                    &session,
+                    &campaign_context,
+                    vec![click(click_id)],
+                )
+                .await
+                .expect("Should process the click");
+
+                tokio::time::sleep(Duration::from_millis(100)).await;
+
+                process_events(
+                    &app,
+                    None,
+                    &session,
+                    &campaign_context,
+                    vec![conversion(click_id)],
+                )
+                .await
+                .expect("Should process the conversion");
+
+                assert_eq!(
+                    remaining_after_conversion,
+                    app.campaign_remaining
+                        .get_remaining_opt(campaign.id)
+                        .await
+                        .expect("Should have key"),
+                    "A conversion after the attribution window should not be paid"
+                );
+            }
+        }
+
+        #[tokio::test]
+        async fn test_click_to_impression_only_campaign_is_rejected() {
+            let app = setup_dummy_app().await;
+
+            let campaign = CampaignBuilder::new(DUMMY_CAMPAIGN.channel)
+                .pricing(IMPRESSION, 0.03, 0.1)
+                .accepted_event_types(&[IMPRESSION])
+                .build();
+            let campaign_context = app
+                .config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Channel token should be whitelisted in config!")
+                .with_campaign(campaign);
+
+            let session = Session {
+                ip: None,
+                country: None,
+                referrer_header: None,
+                os: None,
+            };
+
+            let impression = Event::Impression {
+                publisher: *PUBLISHER,
+                ad_unit: DUMMY_IPFS[0],
+                ad_slot: DUMMY_IPFS[1],
+                referrer: None,
+            };
+            let click = Event::Click {
+                publisher: *PUBLISHER,
+                ad_unit: DUMMY_IPFS[0],
+                ad_slot: DUMMY_IPFS[1],
+                referrer: None,
+                click_id: None,
+            };
+
+            let result = process_events(
+                &app,
+                None,
+                &session,
+                &campaign_context,
+                vec![impression, click],
+            )
+            .await;
+
+            match result {
+                Err(ResponseError::BadRequest(message)) => {
+                    assert_eq!("Campaign does not accept CLICK events", message)
+                }
+                result => panic!("Click events should be rejected, result: {result:?}"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        update_campaign::{get_delta_budget, modify_campaign, DeltaBudget},
+        *,
+    };
+    use crate::{
+        db::{
+            accounting::update_accounting, analytics::update_analytics, fetch_campaign,
+            insert_campaign, redis_pool::TESTS_POOL, validator_message::insert_validator_message,
+            CampaignRemaining, RedisKeys,
+        },
+        test_util::{body_to_string, setup_dummy_app},
+    };
+    use adapter::{
+        dummy::{Dummy, FailureConfig, HeaderToken, MethodFailure, Options},
+        primitives::{Deposit, Scope},
+    };
+    use axum::{http::StatusCode, response::IntoResponse};
+    use chrono::{TimeZone, Utc};
+    use primitives::{
+        analytics::OperatingSystem,
+        campaign::validators::Validators,
+        campaign_validator::Validation,
+        config::GANACHE_CONFIG,
+        sentry::{
+            campaign_list::ValidatorParam, event_stats::EventStats, DateHour, EventType,
+            UpdateAnalytics, CLICK, IMPRESSION,
+        },
+        test_util::{
+            CampaignBuilder, CREATOR, DUMMY_AUTH, DUMMY_CAMPAIGN, DUMMY_IPFS,
+            DUMMY_VALIDATOR_FOLLOWER, DUMMY_VALIDATOR_LEADER, FOLLOWER, GUARDIAN, GUARDIAN_2, IDS,
+            LEADER, LEADER_2, PUBLISHER, PUBLISHER_2,
+        },
+        unified_num::FromWhole,
+        validator::{ApproveState, MessageTypes, NewState},
+        Balances, ValidatorDesc, ValidatorId,
+    };
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    /// Test single campaign creation and modification
+    /// &
+    /// Test with multiple campaigns (because of Budget) and modifications
+    #[tokio::test]
+    async fn create_and_modify_with_multiple_campaigns() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        // Create a new Campaign with different CampaignId
+        let dummy_channel = DUMMY_CAMPAIGN.channel;
+        let channel_chain = app
+            .config
+            .find_chain_of(dummy_channel.token)
+            .expect("Channel token should be whitelisted in config!");
+        let channel_context = channel_chain.with_channel(dummy_channel);
+
+        // Set the deposit for the CREATOR use for all campaigns in the test
+        assert_eq!(*CREATOR, DUMMY_CAMPAIGN.creator);
+        app.adapter.client.set_deposit(
+            &channel_context,
+            *CREATOR,
+            Deposit {
+                // a deposit 4 times larger than the first Campaign.budget = 500
+                // I.e. 2 000 TOKENS
+                total: UnifiedNum::from_whole(2_000)
+                    .to_precision(channel_context.token.precision.get()),
+            },
+        );
+
+        let auth = Extension(Auth {
+            era: 0,
+            uid: IDS[&CREATOR],
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        });
+
+        let campaign_context: ChainOf<Campaign> = {
+            // erases the CampaignId for the CreateCampaign request
+            let mut create = CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None);
+            create.budget = UnifiedNum::from_whole(500);
+
+            let create_response = create_campaign(Json(create), auth.clone(), app.clone())
+                .await
+                .expect("Should create campaign")
+                .0;
+
+            assert_ne!(DUMMY_CAMPAIGN.id, create_response.id);
+
+            let campaign_remaining = app.campaign_remaining.clone();
+
+            let remaining = campaign_remaining
+                .get_remaining_opt(create_response.id)
+                .await
+                .expect("Should get remaining from redis")
+                .expect("There should be value for the Campaign");
+
+            assert_eq!(
+                UnifiedNum::from(50_000_000_000),
+                UnifiedNum::from(remaining.unsigned_abs())
+            );
+
+            channel_context.clone().with(create_response)
+        };
+
+        // modify campaign
+        // Deposit = 2 000
+        // old Campaign.budget = 500
+        // new Campaign.budget = 1 000
+        // Deposit left = 1 000
+        let modified = {
+            let new_budget = UnifiedNum::from_whole(1000);
+            let modify = ModifyCampaign {
+                version: campaign_context.context.version,
+                budget: Some(new_budget),
+                validators: None,
+                title: Some("Updated title".to_string()),
+                pricing_bounds: None,
+                unit_pricing_overrides: None,
+                event_submission: None,
+                pacing: None,
+                ad_units: None,
+                targeting_rules: None,
+            };
+
+            let modified_campaign = modify_campaign(
+                &app,
+                &campaign_context,
+                modify,
+                campaign_context.context.creator,
+            )
+            .await
+            .expect("Should modify campaign");
+
+            assert_eq!(new_budget, modified_campaign.budget);
+            assert_eq!(Some("Updated title".to_string()), modified_campaign.title);
+
+            channel_context.clone().with(modified_campaign)
+        };
+
+        // we have 1000 left from our deposit, so we are using half of it
+        // remaining Deposit = 1 000
+        // new Campaign.budget = 500
+        // Deposit left = 500
+        let _second_campaign = {
+            // erases the CampaignId for the CreateCampaign request
+            let mut create_second =
+                CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None);
+            create_second.budget = UnifiedNum::from_whole(500);
+
+            create_campaign(Json(create_second), auth.clone(), app.clone())
+                .await
+                .expect("Should create campaign")
+                .0
+        };
+
+        // No budget left for new campaigns
+        // remaining Deposit = 500
+        // new Campaign.budget = 600
+        {
+            // erases the CampaignId for the CreateCampaign request
+            let mut create = CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None);
+            create.budget = UnifiedNum::from_whole(600);
+
+            let create_err = create_campaign(Json(create), auth.clone(), app.clone())
+                .await
+                .expect_err("Should return Error response");
+
+            assert_eq!(
+                ResponseError::BadRequest(
+                    "Not enough deposit left for the new campaign's budget".to_string()
+                ),
+                create_err
+            );
+        }
+
+        // modify first campaign, by lowering the budget from 1000 to 900
+        let modified = {
+            let lower_budget = UnifiedNum::from_whole(900);
+            let modify = ModifyCampaign {
+                version: modified.context.version,
+                budget: Some(lower_budget),
+                validators: None,
+                title: None,
+                pricing_bounds: None,
+                unit_pricing_overrides: None,
+                event_submission: None,
+                pacing: None,
+                ad_units: None,
+                targeting_rules: None,
+            };
+
+            let modified_campaign =
+                modify_campaign(&app, &modified, modify, modified.context.creator)
+                    .await
+                    .expect("Should modify campaign");
+
+            assert_eq!(lower_budget, modified_campaign.budget);
+            assert_eq!(
+                campaign_context.context.version + 2,
+                modified_campaign.version,
+                "Every modification should increment the version"
+            );
+
+            modified.clone().with(modified_campaign)
+        };
+
+        // modify first campaign with a stale version
+        {
+            let modify = ModifyCampaign {
+                version: campaign_context.context.version,
+                budget: None,
+                validators: None,
+                title: Some("Stale title".to_string()),
+                pricing_bounds: None,
+                unit_pricing_overrides: None,
+                event_submission: None,
+                pacing: None,
+                ad_units: None,
+                targeting_rules: None,
+            };
+
+            let modify_err = modify_campaign(&app, &modified, modify, modified.context.creator)
+                .await
+                .expect_err("Should reject the stale version");
+
+            assert!(
+                matches!(&modify_err, Error::VersionConflict),
+                "Found error: {modify_err}"
+            );
+        }
+
+        // modify first campaign, by lowering the budget below the minimum campaign budget
+        {
+            let modify = ModifyCampaign {
+                version: modified.context.version,
+                budget: Some(UnifiedNum::from_u64(50_000_000)),
+                validators: None,
+                title: None,
+                pricing_bounds: None,
+                unit_pricing_overrides: None,
+                event_submission: None,
+                pacing: None,
+                ad_units: None,
+                targeting_rules: None,
+            };
+
+            let modify_err = modify_campaign(&app, &modified, modify, modified.context.creator)
+                .await
+                .expect_err("Should reject a budget below the minimum campaign budget");
+
+            assert!(
+                matches!(
+                    &modify_err,
+                    Error::Validation(ValidatorError::Validation(Validation::MinimumDepositNotMet))
+                ),
+                "Found error: {modify_err}"
+            );
+        }
+
+        // modify first campaign while another modification has been made
+        // after the Campaign has been loaded, the remaining budget should be reverted
+        {
+            // another client modifies the Campaign in the meantime
+            let concurrently_modified = update_campaign(
+                &app.pool,
+                &Campaign {
+                    title: Some("Concurrent title".to_string()),
+                    ..modified.context.clone()
+                },
+                modified.context.creator,
+            )
+            .await
+            .expect("Should update Campaign")
+            .expect("Campaign version should match");
+
+            let remaining_before = app
+                .campaign_remaining
+                .get_remaining_opt(modified.context.id)
+                .await
+                .expect("Should get remaining from redis");
+
+            let modify = ModifyCampaign {
+                version: modified.context.version,
+                budget: Some(UnifiedNum::from_whole(800)),
+                validators: None,
+                title: None,
+                pricing_bounds: None,
+                unit_pricing_overrides: None,
+                event_submission: None,
+                pacing: None,
+                ad_units: None,
+                targeting_rules: None,
+            };
+
+            let modify_err = modify_campaign(&app, &modified, modify, modified.context.creator)
+                .await
+                .expect_err("Should reject the stale version");
+
+            assert!(
+                matches!(&modify_err, Error::VersionConflict),
+                "Found error: {modify_err}"
+            );
+
+            let remaining_after = app
+                .campaign_remaining
+                .get_remaining_opt(modified.context.id)
+                .await
+                .expect("Should get remaining from redis");
+            assert_eq!(remaining_before, remaining_after);
+
+            let stored_campaign = fetch_campaign(app.pool.clone(), &modified.context.id)
+                .await
+                .expect("Should fetch Campaign")
+                .expect("Campaign should exist");
+            assert_eq!(concurrently_modified, stored_campaign);
+        }
+
+        let modified = modified.clone().with(
+            fetch_campaign(app.pool.clone(), &modified.context.id)
+                .await
+                .expect("Should fetch Campaign")
+                .expect("Campaign should exist"),
+        );
+
+        // Just enough budget to create this Campaign
+        // remaining Deposit = 600
+        // new Campaign.budget = 600
+        {
+            // erases the CampaignId for the CreateCampaign request
+            let mut create = CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None);
+            create.budget = UnifiedNum::from_whole(600);
+
+            let _create_response = create_campaign(Json(create), auth.clone(), app.clone())
+                .await
+                .expect("Should return create campaign");
+        }
+
+        // Modify a campaign without enough budget
+        // remaining Deposit = 0
+        // new Campaign.budget = 1100
+        // current Campaign.budget = 900
+        {
+            let new_budget = UnifiedNum::from_whole(1_100);
+            let modify = ModifyCampaign {
+                version: modified.context.version,
+                budget: Some(new_budget),
+                validators: None,
+                title: None,
+                pricing_bounds: None,
+                unit_pricing_overrides: None,
+                event_submission: None,
+                pacing: None,
+                ad_units: None,
+                targeting_rules: None,
+            };
+
+            let modify_err = modify_campaign(&app, &modified, modify, modified.context.creator)
+                .await
+                .expect_err("Should return Error response");
+
+            assert!(
+                matches!(&modify_err, Error::NewBudget(string) if string == "Not enough deposit left for the campaign's new budget"),
                 "Found error: {modify_err}"
             );
         }
     }
 
     #[tokio::test]
-    async fn delta_budgets_are_calculated_correctly() {
-        let redis = TESTS_POOL.get().await.expect("Should return Object");
-        let campaign_remaining = CampaignRemaining::new(redis.connection.clone());
+    async fn delta_budgets_are_calculated_correctly() {
+        let redis = TESTS_POOL.get().await.expect("Should return Object");
+        let campaign_remaining =
+            CampaignRemaining::new(redis.connection.clone(), RedisKeys::default());
+
+        let campaign = DUMMY_CAMPAIGN.clone();
+
+        // Equal budget
+        {
+            let delta_budget = get_delta_budget(&campaign_remaining, &campaign, campaign.budget)
+                .await
+                .expect("should get delta budget");
+            assert!(delta_budget.is_none());
+        }
+        // Spent cant be higher than the new budget
+        {
+            campaign_remaining
+                .set_initial(campaign.id, UnifiedNum::from_whole(600))
+                .await
+                .expect("should set");
+
+            // campaign_spent > new_budget
+            let new_budget = UnifiedNum::from_whole(300);
+            let delta_budget = get_delta_budget(&campaign_remaining, &campaign, new_budget).await;
+
+            assert!(
+                matches!(&delta_budget, Err(Error::NewBudget(_))),
+                "Got result: {delta_budget:?}"
+            );
+
+            // campaign_spent == new_budget
+            let new_budget = UnifiedNum::from_whole(400);
+            let delta_budget = get_delta_budget(&campaign_remaining, &campaign, new_budget).await;
+
+            assert!(
+                matches!(&delta_budget, Err(Error::NewBudget(_))),
+                "Got result: {delta_budget:?}"
+            );
+        }
+        // Increasing budget
+        {
+            campaign_remaining
+                .set_initial(campaign.id, UnifiedNum::from_whole(900))
+                .await
+                .expect("should set");
+            let new_budget = UnifiedNum::from_whole(1_100);
+            let delta_budget = get_delta_budget(&campaign_remaining, &campaign, new_budget)
+                .await
+                .expect("should get delta budget");
+            assert!(delta_budget.is_some());
+            let increase_by = UnifiedNum::from_whole(100);
+
+            assert_eq!(delta_budget, Some(DeltaBudget::Increase(increase_by)));
+        }
+        // Decreasing budget
+        {
+            campaign_remaining
+                .set_initial(campaign.id, UnifiedNum::from_whole(900))
+                .await
+                .expect("should set");
+            let new_budget = UnifiedNum::from_whole(800);
+            let delta_budget = get_delta_budget(&campaign_remaining, &campaign, new_budget)
+                .await
+                .expect("should get delta budget");
+            assert!(delta_budget.is_some());
+            let decrease_by = UnifiedNum::from_whole(200);
+
+            assert_eq!(delta_budget, Some(DeltaBudget::Decrease(decrease_by)));
+        }
+    }
+
+    #[tokio::test]
+    async fn create_campaign_with_missing_remaining_of_a_channel_campaign() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        app.adapter.client.set_deposit(
+            &channel_context,
+            *CREATOR,
+            Deposit {
+                total: UnifiedNum::from_whole(2_000)
+                    .to_precision(channel_context.token.precision.get()),
+            },
+        );
+
+        // a Campaign of the Channel without a remaining in Redis
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("Should insert dummy channel");
+        insert_campaign(&app.pool, &DUMMY_CAMPAIGN)
+            .await
+            .expect("Should insert dummy campaign");
+
+        let auth = Extension(Auth {
+            era: 0,
+            uid: IDS[&CREATOR],
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        });
+
+        let mut create = CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None);
+        create.budget = UnifiedNum::from_whole(500);
+
+        let error = create_campaign(Json(create), auth, app)
+            .await
+            .expect_err("Should fail with a missing remaining");
+
+        assert_eq!(
+            ResponseError::BadRequest(format!(
+                "Missing remaining budget for Campaigns: {}",
+                DUMMY_CAMPAIGN.id
+            )),
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrent_campaign_creations_cannot_exceed_the_deposit() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        app.adapter.client.set_deposit(
+            &channel_context,
+            *CREATOR,
+            Deposit {
+                total: UnifiedNum::from_whole(2_000)
+                    .to_precision(channel_context.token.precision.get()),
+            },
+        );
+
+        let auth = Extension(Auth {
+            era: 0,
+            uid: IDS[&CREATOR],
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        });
+
+        // each Campaign fits in the deposit, but not both of them
+        let create = |budget| {
+            let mut create = CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None);
+            create.budget = budget;
+
+            Json(create)
+        };
+
+        let (first, second) = tokio::join!(
+            create_campaign(
+                create(UnifiedNum::from_whole(1_200)),
+                auth.clone(),
+                app.clone()
+            ),
+            create_campaign(
+                create(UnifiedNum::from_whole(1_200)),
+                auth.clone(),
+                app.clone()
+            ),
+        );
+
+        let (created, error) = match (first, second) {
+            (Ok(created), Err(error)) | (Err(error), Ok(created)) => (created, error),
+            (first, second) => panic!(
+                "Exactly one of the Campaigns should be created, got: {:?} and {:?}",
+                first.map(|campaign| campaign.0.id),
+                second.map(|campaign| campaign.0.id),
+            ),
+        };
+
+        assert_eq!(
+            ResponseError::BadRequest(
+                "Not enough deposit left for the new campaign's budget".to_string()
+            ),
+            error
+        );
+        assert_eq!(
+            Some(UnifiedNum::from_whole(1_200)),
+            app.campaign_remaining
+                .get_remaining_opt(created.0.id)
+                .await
+                .expect("Should get remaining")
+                .map(|remaining| UnifiedNum::from_u64(remaining.unsigned_abs())),
+            "The remaining of the created Campaign should be set"
+        );
+
+        // the reservations are released, so another Campaign fitting in the deposit can be created
+        create_campaign(create(UnifiedNum::from_whole(800)), auth, app)
+            .await
+            .expect("Should create a Campaign with the rest of the deposit");
+    }
+
+    #[tokio::test]
+    async fn create_campaign_up_to_the_max_campaigns_per_channel() {
+        let mut app_guard = setup_dummy_app().await;
+        app_guard.config.limits.max_campaigns_per_channel = 2;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        app.adapter.client.set_deposit(
+            &channel_context,
+            *CREATOR,
+            Deposit {
+                total: UnifiedNum::from_whole(2_000)
+                    .to_precision(channel_context.token.precision.get()),
+            },
+        );
+
+        let auth = Extension(Auth {
+            era: 0,
+            uid: IDS[&CREATOR],
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        });
+
+        let create = || {
+            let mut create = CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None);
+            create.budget = UnifiedNum::from_whole(100);
+
+            Json(create)
+        };
+
+        // an expired Campaign is not counted
+        {
+            let mut expired = DUMMY_CAMPAIGN.clone();
+            expired.id = CampaignId::new();
+            expired.active.to = Utc::now() - chrono::Duration::days(1);
+
+            insert_channel(&app.pool, &channel_context)
+                .await
+                .expect("Should insert the Channel");
+            assert!(insert_campaign(&app.pool, &expired)
+                .await
+                .expect("Should insert the expired Campaign"));
+        }
+
+        // out of the concurrent creations only the ones up to the limit succeed
+        let results =
+            join_all((0..3).map(|_| create_campaign(create(), auth.clone(), app.clone()))).await;
+        let (created, rejected): (Vec<_>, Vec<_>) = results.into_iter().partition(Result::is_ok);
+        assert_eq!(
+            2,
+            created.len(),
+            "Should create the Campaigns up to the limit"
+        );
+        assert_eq!(
+            vec![ResponseError::BadRequest(
+                "A Channel can have at most 2 campaigns".to_string()
+            )],
+            rejected
+                .into_iter()
+                .filter_map(Result::err)
+                .collect::<Vec<_>>()
+        );
+
+        // a bulk creation is rejected as well
+        let error = create_campaigns_bulk(Json(vec![create().0]), auth, app)
+            .await
+            .expect_err("Should reject the bulk Campaigns beyond the limit");
+
+        assert_eq!(
+            ResponseError::BadRequest("A Channel can have at most 2 campaigns".to_string()),
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn create_campaign_when_get_deposit_fails() {
+        let mut app_guard = setup_dummy_app().await;
+        app_guard.app.adapter = adapter::Adapter::new(Dummy::init(Options {
+            dummy_identity: IDS[&LEADER],
+            dummy_auth_tokens: DUMMY_AUTH.clone(),
+            dummy_chains: app_guard.config.chains.values().cloned().collect(),
+            failure_injection: Some(FailureConfig {
+                seed: Some(0),
+                get_deposit: Some(MethodFailure {
+                    error_probability: 1.0,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+        }));
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        let auth = Extension(Auth {
+            era: 0,
+            uid: IDS[&CREATOR],
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        });
+
+        let create = CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None);
+
+        let result = create_campaign(Json(create), auth, app.clone()).await;
+
+        let response = result
+            .expect_err("Should fail when the deposit cannot be fetched")
+            .into_response();
+
+        assert_eq!(
+            StatusCode::SERVICE_UNAVAILABLE,
+            response.status(),
+            "Should respond with 503 Service Unavailable when the RPC is down"
+        );
+
+        let response_body =
+            serde_json::from_str::<serde_json::Value>(&body_to_string(response).await)
+                .expect("Should deserialize");
+        assert_eq!("adapter_rpc_unavailable", response_body["code"]);
+        assert_eq!(true, response_body["retryable"]);
+    }
+
+    #[tokio::test]
+    async fn campaign_is_closed_properly() {
+        // create a new campaign with a new CampaignId
+        let campaign =
+            CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None).into_campaign();
+
+        let app_guard = setup_dummy_app().await;
+
+        let channel_chain = app_guard
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!");
+
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = channel_chain.with_channel(DUMMY_CAMPAIGN.channel);
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("Should insert dummy channel");
+        insert_campaign(&app.pool, &campaign)
+            .await
+            .expect("Should insert dummy campaign");
+
+        let campaign_context = Extension(
+            app.config
+                .find_chain_of(campaign.channel.token)
+                .expect("Config should have the Dummy campaign.channel.token")
+                .with(campaign.clone()),
+        );
+
+        // Test if remaining is set to 0
+        {
+            app.campaign_remaining
+                .set_initial(campaign.id, campaign.budget)
+                .await
+                .expect("should set");
+
+            let auth = Extension(Auth {
+                era: 0,
+                uid: ValidatorId::from(campaign.creator),
+                chain: campaign_context.chain.clone(),
+                scope: Scope::Full,
+            });
+
+            close_campaign(app.clone(), auth.clone(), campaign_context.clone())
+                .await
+                .expect("Should close campaign");
+
+            let closed_campaign = fetch_campaign(app.pool.clone(), &campaign.id)
+                .await
+                .expect("Should fetch campaign")
+                .expect("Campaign should exist");
+
+            // remaining == campaign_budget therefore old_budget - remaining = 0
+            assert_eq!(closed_campaign.budget, UnifiedNum::from_u64(0));
+
+            let remaining = app
+                .campaign_remaining
+                .get_remaining_opt(campaign.id)
+                .await
+                .expect("Should get remaining from redis")
+                .expect("There should be value for the Campaign");
+
+            assert_eq!(remaining, 0);
+        }
+
+        // Test if an error is returned when request is not sent by creator
+        {
+            let auth = Extension(Auth {
+                era: 0,
+                uid: IDS[&LEADER],
+                chain: campaign_context.chain.clone(),
+                scope: Scope::Full,
+            });
+
+            let res = close_campaign(app.clone(), auth, campaign_context.clone())
+                .await
+                .expect_err("Should return error for Bad Campaign");
+
+            assert_eq!(
+                ResponseError::Forbidden("Request not sent by campaign creator".to_string()),
+                res
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn close_campaign_notifies_the_other_validator() {
+        let server = MockServer::start().await;
+
+        let follower = ValidatorDesc {
+            url: format!("{}/follower", server.uri()),
+            ..DUMMY_VALIDATOR_FOLLOWER.clone()
+        };
+        let mut campaign =
+            CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None).into_campaign();
+        campaign.validators = Validators::new((DUMMY_VALIDATOR_LEADER.clone(), follower));
+
+        let app_guard = setup_dummy_app().await;
+        let signer = app_guard
+            .app
+            .adapter
+            .clone()
+            .unlock()
+            .expect("Should unlock the Dummy adapter");
+        let app = Extension(Arc::new(
+            app_guard.app.clone().with_whoami_signer(Arc::new(signer)),
+        ));
+
+        let campaign_context = app
+            .config
+            .find_chain_of(campaign.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with(campaign.clone());
+
+        insert_channel(&app.pool, &campaign_context.of_channel())
+            .await
+            .expect("Should insert dummy channel");
+        insert_campaign(&app.pool, &campaign)
+            .await
+            .expect("Should insert dummy campaign");
+        app.campaign_remaining
+            .set_initial(campaign.id, campaign.budget)
+            .await
+            .expect("should set");
+
+        // The Dummy adapter of the app is the Leader
+        let token = HeaderToken {
+            token: DUMMY_AUTH[&LEADER].clone(),
+            chain_id: campaign_context.chain.chain_id,
+        };
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "/follower/v5/campaign/{}/closed-notification",
+                campaign.id
+            )))
+            .and(header(
+                "authorization",
+                format!("Bearer {}", token).as_str(),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(&SuccessResponse { success: true }),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let auth = Extension(Auth {
+            era: 0,
+            uid: ValidatorId::from(campaign.creator),
+            chain: campaign_context.chain.clone(),
+            scope: Scope::Full,
+        });
+
+        close_campaign(app.clone(), auth, Extension(campaign_context))
+            .await
+            .expect("Should close campaign");
+
+        // the notification is sent in the background
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while server
+                .received_requests()
+                .await
+                .unwrap_or_default()
+                .is_empty()
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Should notify the Follower in the background");
+
+        // the Follower should have received exactly 1 notification
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn get_campaign_with_budget_utilization() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let campaign = DUMMY_CAMPAIGN.clone();
+        let campaign_context = Extension(
+            app.config
+                .find_chain_of(campaign.channel.token)
+                .expect("Channel token should be whitelisted in config!")
+                .with(campaign.clone()),
+        );
+
+        // no remaining budget set
+        {
+            let response = get_campaign(app.clone(), campaign_context.clone())
+                .await
+                .expect("Should get campaign")
+                .0;
+
+            assert_eq!(campaign, response.campaign);
+            assert_eq!(None, response.utilization);
+        }
+
+        // a quarter of the budget is spent
+        {
+            let remaining = UnifiedNum::from_u64(campaign.budget.to_u64() / 4 * 3);
+            app.campaign_remaining
+                .set_initial(campaign.id, remaining)
+                .await
+                .expect("Should set remaining");
+
+            let utilization = get_campaign(app.clone(), campaign_context.clone())
+                .await
+                .expect("Should get campaign")
+                .0
+                .utilization
+                .expect("Should have utilization");
+
+            assert_eq!(
+                BudgetUtilization {
+                    spent: campaign.budget - remaining,
+                    remaining,
+                    percent: 25.0,
+                },
+                utilization
+            );
+            assert_eq!(campaign.budget, utilization.spent + utilization.remaining);
+        }
+    }
+
+    #[tokio::test]
+    async fn get_campaigns_batch_skips_unknown_campaigns() {
+        let mut app_guard = setup_dummy_app().await;
+        app_guard.config.limits.campaigns_find = 3;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("Should insert Channel");
+
+        let first = DUMMY_CAMPAIGN.clone();
+        let second = Campaign {
+            id: CampaignId::new(),
+            ..DUMMY_CAMPAIGN.clone()
+        };
+        for campaign in [&first, &second] {
+            assert!(insert_campaign(&app.pool, campaign)
+                .await
+                .expect("Should insert Campaign"));
+        }
+
+        let remaining = UnifiedNum::from_u64(second.budget.to_u64() / 2);
+        app.campaign_remaining
+            .set_initial(second.id, remaining)
+            .await
+            .expect("Should set remaining");
+
+        let request = CampaignsBatchRequest {
+            campaigns: vec![first.id, CampaignId::new(), second.id],
+        };
+        let response = get_campaigns_batch(app.clone(), Json(request))
+            .await
+            .expect("Should get the campaigns")
+            .0;
+
+        assert_eq!(
+            vec![
+                CampaignResponse {
+                    campaign: first.clone(),
+                    utilization: None,
+                },
+                CampaignResponse {
+                    campaign: second.clone(),
+                    utilization: Some(BudgetUtilization::new(second.budget, remaining)),
+                },
+            ],
+            response.campaigns
+        );
+
+        // more than `campaigns_find` campaigns
+        let request = CampaignsBatchRequest {
+            campaigns: vec![first.id, second.id, first.id, second.id],
+        };
+        let error = get_campaigns_batch(app.clone(), Json(request))
+            .await
+            .expect_err("Should fail validation");
+
+        assert_eq!(
+            ResponseError::FailedValidation(
+                "No more than 3 campaigns can be requested at once".to_string()
+            ),
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn campaign_closed_notification_sets_remaining_to_zero() {
+        let campaign =
+            CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None).into_campaign();
+
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("Should insert dummy channel");
+        insert_campaign(&app.pool, &campaign)
+            .await
+            .expect("Should insert dummy campaign");
+
+        let campaign_context = Extension(channel_context.clone().with(campaign.clone()));
+
+        app.campaign_remaining
+            .set_initial(campaign.id, campaign.budget)
+            .await
+            .expect("should set");
+
+        let auth = |uid: ValidatorId| {
+            Extension(Auth {
+                era: 0,
+                uid,
+                chain: campaign_context.chain.clone(),
+                scope: Scope::Full,
+            })
+        };
+
+        // Only the other validator of the Campaign can notify us.
+        // The Dummy adapter of the app is the Leader.
+        for uid in [
+            IDS[&LEADER],
+            ValidatorId::from(campaign.creator),
+            IDS[&PUBLISHER],
+        ] {
+            let res =
+                campaign_closed_notification(app.clone(), auth(uid), campaign_context.clone())
+                    .await
+                    .expect_err("Should reject the notification");
+
+            assert!(matches!(res, ResponseError::Forbidden(_)));
+        }
+
+        let remaining = app
+            .campaign_remaining
+            .get_remaining_opt(campaign.id)
+            .await
+            .expect("Should get remaining from redis")
+            .expect("There should be value for the Campaign");
+        assert_eq!(
+            campaign.budget,
+            UnifiedNum::from_u64(remaining.try_into().expect("Should be positive"))
+        );
+
+        campaign_closed_notification(app.clone(), auth(IDS[&FOLLOWER]), campaign_context.clone())
+            .await
+            .expect("Should accept the notification from the Follower");
+
+        let remaining = app
+            .campaign_remaining
+            .get_remaining_opt(campaign.id)
+            .await
+            .expect("Should get remaining from redis")
+            .expect("There should be value for the Campaign");
+        assert_eq!(remaining, 0);
+
+        let closed_campaign = fetch_campaign(app.pool.clone(), &campaign.id)
+            .await
+            .expect("Should fetch campaign")
+            .expect("Campaign should exist");
+        assert_eq!(closed_campaign.budget, UnifiedNum::from_u64(0));
+    }
+
+    #[tokio::test]
+    async fn test_campaign_list() {
+        let mut app_guard = setup_dummy_app().await;
+        app_guard.config.limits.campaigns_find = 2;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        // Setting up new leader and a channel and campaign which use it on Ganache #1337
+        let dummy_leader_2 = ValidatorDesc {
+            id: IDS[&LEADER_2],
+            url: "http://tom.adex.network".to_string(),
+            fee: 200.into(),
+            fee_addr: None,
+        };
+        let channel_new_leader = Channel {
+            leader: IDS[&*LEADER_2],
+            follower: IDS[&*FOLLOWER],
+            guardian: *GUARDIAN,
+            token: DUMMY_CAMPAIGN.channel.token,
+            nonce: DUMMY_CAMPAIGN.channel.nonce,
+        };
+        let mut campaign_new_leader = DUMMY_CAMPAIGN.clone();
+        campaign_new_leader.id = CampaignId::new();
+        campaign_new_leader.channel = channel_new_leader;
+        campaign_new_leader.validators =
+            Validators::new((dummy_leader_2.clone(), DUMMY_VALIDATOR_FOLLOWER.clone()));
+        campaign_new_leader.created = Utc.ymd(2021, 2, 1).and_hms(8, 0, 0);
+
+        let chain_1_token = GANACHE_CONFIG.chains["Ganache #1"].tokens["Mocked TOKEN 1"].address;
+        // Setting up new follower and a channel and campaign which use it on Ganache #1
+        let dummy_follower_2 = ValidatorDesc {
+            id: IDS[&GUARDIAN],
+            url: "http://jerry.adex.network".to_string(),
+            fee: 300.into(),
+            fee_addr: None,
+        };
+        let channel_new_follower = Channel {
+            leader: IDS[&*LEADER],
+            follower: IDS[&*GUARDIAN],
+            guardian: *GUARDIAN,
+            token: chain_1_token,
+            nonce: DUMMY_CAMPAIGN.channel.nonce,
+        };
+        let mut campaign_new_follower = DUMMY_CAMPAIGN.clone();
+        campaign_new_follower.id = CampaignId::new();
+        campaign_new_follower.channel = channel_new_follower;
+        campaign_new_follower.validators =
+            Validators::new((DUMMY_VALIDATOR_LEADER.clone(), dummy_follower_2.clone()));
+        campaign_new_follower.created = Utc.ymd(2021, 2, 1).and_hms(9, 0, 0);
+
+        // Setting up a channel and campaign which use the new leader and follower on Ganache #1
+        let channel_new_leader_and_follower = Channel {
+            leader: IDS[&*LEADER_2],
+            follower: IDS[&*GUARDIAN],
+            guardian: *GUARDIAN,
+            token: chain_1_token,
+            nonce: DUMMY_CAMPAIGN.channel.nonce,
+        };
+        let mut campaign_new_leader_and_follower = DUMMY_CAMPAIGN.clone();
+        campaign_new_leader_and_follower.id = CampaignId::new();
+        campaign_new_leader_and_follower.channel = channel_new_leader_and_follower;
+        campaign_new_leader_and_follower.validators =
+            Validators::new((dummy_leader_2.clone(), dummy_follower_2.clone()));
+        campaign_new_leader_and_follower.created = Utc.ymd(2021, 2, 1).and_hms(10, 0, 0);
+        let channel_chain = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!");
+
+        insert_channel(
+            &app.pool,
+            &channel_chain.clone().with_channel(DUMMY_CAMPAIGN.channel),
+        )
+        .await
+        .expect("Should insert dummy channel");
+        insert_campaign(&app.pool, &DUMMY_CAMPAIGN)
+            .await
+            .expect("Should insert dummy campaign");
+        insert_channel(
+            &app.pool,
+            &channel_chain.clone().with_channel(channel_new_leader),
+        )
+        .await
+        .expect("Should insert dummy channel");
+        insert_campaign(&app.pool, &campaign_new_leader)
+            .await
+            .expect("Should insert dummy campaign");
+        insert_channel(
+            &app.pool,
+            &channel_chain.clone().with_channel(channel_new_follower),
+        )
+        .await
+        .expect("Should insert dummy channel");
+        insert_campaign(&app.pool, &campaign_new_follower)
+            .await
+            .expect("Should insert dummy campaign");
+        insert_channel(
+            &app.pool,
+            &channel_chain.with_channel(channel_new_leader_and_follower),
+        )
+        .await
+        .expect("Should insert dummy channel");
+        insert_campaign(&app.pool, &campaign_new_leader_and_follower)
+            .await
+            .expect("Should insert dummy campaign");
+
+        let mut campaign_other_creator = DUMMY_CAMPAIGN.clone();
+        campaign_other_creator.id = CampaignId::new();
+        campaign_other_creator.creator = *PUBLISHER_2;
+        campaign_other_creator.created = Utc.ymd(2021, 2, 1).and_hms(11, 0, 0);
+
+        insert_campaign(&app.pool, &campaign_other_creator)
+            .await
+            .expect("Should insert dummy campaign");
+
+        let mut campaign_long_active_to = DUMMY_CAMPAIGN.clone();
+        campaign_long_active_to.id = CampaignId::new();
+        campaign_long_active_to.active.to = Utc.ymd(2101, 1, 30).and_hms(0, 0, 0);
+        campaign_long_active_to.created = Utc.ymd(2021, 2, 1).and_hms(12, 0, 0);
+
+        insert_campaign(&app.pool, &campaign_long_active_to)
+            .await
+            .expect("Should insert dummy campaign");
+
+        // Test for dummy leader
+        {
+            let query = CampaignListQuery {
+                page: 0,
+                active_to_ge: Utc::now(),
+                creator: None,
+                validator: Some(ValidatorParam::Leader(DUMMY_VALIDATOR_LEADER.id)),
+            };
+
+            let res = campaign_list(app.clone(), Qs(query))
+                .await
+                .expect("should get campaigns")
+                .0;
+
+            assert_eq!(
+                res.campaigns,
+                vec![DUMMY_CAMPAIGN.clone(), campaign_new_follower.clone()],
+                "First page of campaigns with dummy leader is correct"
+            );
+            assert_eq!(res.pagination.total_pages, 2);
+
+            let query = CampaignListQuery {
+                page: 1,
+                active_to_ge: Utc::now(),
+                creator: None,
+                validator: Some(ValidatorParam::Leader(DUMMY_VALIDATOR_LEADER.id)),
+            };
+            let res = campaign_list(app.clone(), Qs(query))
+                .await
+                .expect("should get campaigns")
+                .0;
+
+            assert_eq!(
+                res.campaigns,
+                vec![
+                    campaign_other_creator.clone(),
+                    campaign_long_active_to.clone()
+                ],
+                "Second page of campaigns with dummy leader is correct"
+            );
+        }
+
+        // Test for dummy follower
+        {
+            let query = CampaignListQuery {
+                page: 0,
+                active_to_ge: Utc::now(),
+                creator: None,
+                validator: Some(ValidatorParam::Validator(DUMMY_VALIDATOR_FOLLOWER.id)),
+            };
+            let res = campaign_list(app.clone(), Qs(query))
+                .await
+                .expect("should get campaigns")
+                .0;
+
+            assert_eq!(
+                res.campaigns,
+                vec![DUMMY_CAMPAIGN.clone(), campaign_new_leader.clone()],
+                "First page of campaigns with dummy follower is correct"
+            );
+            assert_eq!(res.pagination.total_pages, 2);
+
+            let query = CampaignListQuery {
+                page: 1,
+                active_to_ge: Utc::now(),
+                creator: None,
+                validator: Some(ValidatorParam::Validator(DUMMY_VALIDATOR_FOLLOWER.id)),
+            };
+            let res = campaign_list(app.clone(), Qs(query))
+                .await
+                .expect("should get campaigns")
+                .0;
+
+            assert_eq!(
+                res.campaigns,
+                vec![
+                    campaign_other_creator.clone(),
+                    campaign_long_active_to.clone()
+                ],
+                "Second page of campaigns with dummy follower is correct"
+            );
+        }
+
+        // Test for dummy leader 2
+        {
+            let query = CampaignListQuery {
+                page: 0,
+                active_to_ge: Utc::now(),
+                creator: None,
+                validator: Some(ValidatorParam::Leader(dummy_leader_2.id)),
+            };
+            let res = campaign_list(app.clone(), Qs(query))
+                .await
+                .expect("should get campaigns")
+                .0;
+
+            assert_eq!(
+                res.campaigns,
+                vec![
+                    campaign_new_leader.clone(),
+                    campaign_new_leader_and_follower.clone()
+                ],
+                "Campaigns with dummy leader 2 are correct"
+            );
+            assert_eq!(res.pagination.total_pages, 1);
+        }
+
+        // Test for dummy follower 2
+        {
+            let query = CampaignListQuery {
+                page: 0,
+                active_to_ge: Utc::now(),
+                creator: None,
+                validator: Some(ValidatorParam::Validator(dummy_follower_2.id)),
+            };
+            let res = campaign_list(app.clone(), Qs(query))
+                .await
+                .expect("should get campaigns")
+                .0;
+
+            assert_eq!(
+                res.campaigns,
+                vec![
+                    campaign_new_follower.clone(),
+                    campaign_new_leader_and_follower.clone()
+                ],
+                "Campaigns with dummy follower 2 are correct"
+            );
+            assert_eq!(res.pagination.total_pages, 1);
+        }
+
+        // Test for other creator
+        {
+            let query = CampaignListQuery {
+                page: 0,
+                active_to_ge: Utc::now(),
+                creator: Some(*PUBLISHER_2),
+                validator: None,
+            };
+            let res = campaign_list(app.clone(), Qs(query))
+                .await
+                .expect("should get campaigns")
+                .0;
+
+            assert_eq!(
+                res.campaigns,
+                vec![campaign_other_creator.clone()],
+                "The campaign with a different creator is retrieved correctly"
+            );
+            assert_eq!(res.pagination.total_pages, 1);
+        }
+
+        // Test for active_to
+        {
+            let query = CampaignListQuery {
+                page: 0,
+                active_to_ge: Utc.ymd(2101, 1, 1).and_hms(0, 0, 0),
+                creator: None,
+                validator: None,
+            };
+            let res = campaign_list(app.clone(), Qs(query))
+                .await
+                .expect("should get campaigns")
+                .0;
+
+            assert_eq!(
+                res.campaigns,
+                vec![campaign_long_active_to.clone()],
+                "The campaign with a longer active_to is retrieved correctly"
+            );
+            assert_eq!(res.pagination.total_pages, 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_campaign_list_by_guardian() {
+        let mut app_guard = setup_dummy_app().await;
+        app_guard.config.limits.campaigns_find = 1;
+        let app = Extension(Arc::new(app_guard.app.clone()));
 
-        let campaign = DUMMY_CAMPAIGN.clone();
+        let channel_guardian_2 = Channel {
+            guardian: *GUARDIAN_2,
+            nonce: 987_654_321_u32.into(),
+            ..DUMMY_CAMPAIGN.channel
+        };
+        let channel_chain = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!");
 
-        // Equal budget
+        let campaigns = [
+            (DUMMY_CAMPAIGN.channel, Utc.ymd(2021, 2, 1).and_hms(7, 0, 0)),
+            (channel_guardian_2, Utc.ymd(2021, 2, 1).and_hms(8, 0, 0)),
+            (DUMMY_CAMPAIGN.channel, Utc.ymd(2021, 2, 1).and_hms(9, 0, 0)),
+        ]
+        .map(|(channel, created)| {
+            CampaignBuilder::new(channel)
+                .id(CampaignId::new())
+                .created(created)
+                .build()
+        });
+
+        for campaign in campaigns.iter() {
+            insert_channel(
+                &app.pool,
+                &channel_chain.clone().with_channel(campaign.channel),
+            )
+            .await
+            .expect("Should insert channel");
+            insert_campaign(&app.pool, campaign)
+                .await
+                .expect("Should insert campaign");
+        }
+
+        let query = |page: u64, guardian: Address| CampaignListQuery {
+            page,
+            active_to_ge: Utc::now(),
+            creator: None,
+            validator: Some(ValidatorParam::Guardian(guardian)),
+        };
+
+        // Guardian of 2 campaigns - 1 campaign per page
         {
-            let delta_budget = get_delta_budget(&campaign_remaining, &campaign, campaign.budget)
+            let first_page = campaign_list(app.clone(), Qs(query(0, *GUARDIAN)))
                 .await
-                .expect("should get delta budget");
-            assert!(delta_budget.is_none());
+                .expect("should get campaigns")
+                .0;
+            assert_eq!(vec![campaigns[0].clone()], first_page.campaigns);
+            assert_eq!(2, first_page.pagination.total_pages);
+
+            let second_page = campaign_list(app.clone(), Qs(query(1, *GUARDIAN)))
+                .await
+                .expect("should get campaigns")
+                .0;
+            assert_eq!(vec![campaigns[2].clone()], second_page.campaigns);
+            assert_eq!(2, second_page.pagination.total_pages);
         }
-        // Spent cant be higher than the new budget
+
+        // Guardian of a single campaign
         {
-            campaign_remaining
-                .set_initial(campaign.id, UnifiedNum::from_whole(600))
+            let res = campaign_list(app.clone(), Qs(query(0, *GUARDIAN_2)))
                 .await
-                .expect("should set");
+                .expect("should get campaigns")
+                .0;
+            assert_eq!(vec![campaigns[1].clone()], res.campaigns);
+            assert_eq!(1, res.pagination.total_pages);
+        }
+    }
 
-            // campaign_spent > new_budget
-            let new_budget = UnifiedNum::from_whole(300);
-            let delta_budget = get_delta_budget(&campaign_remaining, &campaign, new_budget).await;
+    #[tokio::test]
+    async fn campaign_event_stats_per_publisher() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
 
-            assert!(
-                matches!(&delta_budget, Err(Error::NewBudget(_))),
-                "Got result: {delta_budget:?}"
+        let campaign = DUMMY_CAMPAIGN.clone();
+        let campaign_context = Extension(
+            app.config
+                .find_chain_of(campaign.channel.token)
+                .expect("Config should have the Dummy campaign.channel.token")
+                .with(campaign.clone()),
+        );
+
+        insert_channel(&app.pool, &campaign_context.of_channel())
+            .await
+            .expect("Should insert dummy channel");
+        insert_campaign(&app.pool, &campaign)
+            .await
+            .expect("Should insert dummy campaign");
+
+        let seed = |publisher: Address, time: DateHour<Utc>, event_type: EventType, count: u32| {
+            let pool = app.pool.clone();
+            let chain_id = campaign_context.chain.chain_id;
+
+            async move {
+                update_analytics(
+                    &pool,
+                    UpdateAnalytics {
+                        time,
+                        campaign_id: DUMMY_CAMPAIGN.id,
+                        ad_unit: DUMMY_IPFS[0],
+                        ad_slot: DUMMY_IPFS[1],
+                        ad_slot_type: None,
+                        advertiser: *CREATOR,
+                        publisher,
+                        hostname: None,
+                        country: None,
+                        os_name: OperatingSystem::Linux,
+                        chain_id,
+                        event_type,
+                        amount_to_add: UnifiedNum::from_whole(count as u64),
+                        count_to_add: count.try_into().expect("Should fit"),
+                    },
+                )
+                .await
+                .expect("Should insert analytics")
+            }
+        };
+
+        seed(
+            *PUBLISHER,
+            DateHour::from_ymdh(2022, 6, 20, 10),
+            IMPRESSION,
+            3,
+        )
+        .await;
+        seed(*PUBLISHER, DateHour::from_ymdh(2022, 6, 20, 12), CLICK, 1).await;
+        seed(
+            *PUBLISHER_2,
+            DateHour::from_ymdh(2022, 6, 20, 11),
+            IMPRESSION,
+            5,
+        )
+        .await;
+
+        let auth_as = |address: Address| {
+            Extension(Auth {
+                era: 0,
+                uid: ValidatorId::from(address),
+                chain: campaign_context.chain.clone(),
+                scope: Scope::Full,
+            })
+        };
+        let query_for = |publisher: Address| EventStatsQuery {
+            publisher,
+            start: Some(DateHour::from_ymdh(2022, 6, 20, 10)),
+            end: Some(DateHour::from_ymdh(2022, 6, 20, 13)),
+        };
+        let stat = |count: u32| EventStats {
+            count,
+            payout: UnifiedNum::from_whole(count as u64),
+        };
+
+        // The publisher gets its own events with zero-filled hours
+        {
+            let response = campaign_event_stats(
+                app.clone(),
+                auth_as(*PUBLISHER),
+                campaign_context.clone(),
+                Qs(query_for(*PUBLISHER)),
+            )
+            .await
+            .expect("Should get the event stats")
+            .0;
+
+            assert_eq!(campaign.id, response.campaign);
+            assert_eq!(*PUBLISHER, response.publisher);
+
+            let stats = response
+                .stats
+                .iter()
+                .map(|hourly| {
+                    (
+                        hourly.time,
+                        hourly.events[&IMPRESSION].count,
+                        hourly.events[&CLICK].count,
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                vec![
+                    (DateHour::from_ymdh(2022, 6, 20, 10), 3, 0),
+                    // PUBLISHER_2 events are not included
+                    (DateHour::from_ymdh(2022, 6, 20, 11), 0, 0),
+                    (DateHour::from_ymdh(2022, 6, 20, 12), 0, 1),
+                    (DateHour::from_ymdh(2022, 6, 20, 13), 0, 0),
+                ],
+                stats
+            );
+            assert_eq!(stat(3), response.stats[0].events[&IMPRESSION]);
+            assert_eq!(stat(1), response.stats[2].events[&CLICK]);
+        }
+
+        // Another publisher is not allowed to get the event stats
+        {
+            let err = campaign_event_stats(
+                app.clone(),
+                auth_as(*PUBLISHER_2),
+                campaign_context.clone(),
+                Qs(query_for(*PUBLISHER)),
+            )
+            .await
+            .expect_err("Should not be allowed");
+
+            assert_eq!(
+                ResponseError::Forbidden(
+                    "Request not sent by the publisher, the campaign creator or a validator"
+                        .to_string()
+                ),
+                err
             );
+        }
 
-            // campaign_spent == new_budget
-            let new_budget = UnifiedNum::from_whole(400);
-            let delta_budget = get_delta_budget(&campaign_remaining, &campaign, new_budget).await;
+        // The creator and the validators are allowed to get any publisher's event stats
+        for address in [*CREATOR, *LEADER, *FOLLOWER] {
+            let response = campaign_event_stats(
+                app.clone(),
+                auth_as(address),
+                campaign_context.clone(),
+                Qs(query_for(*PUBLISHER_2)),
+            )
+            .await
+            .expect("Should get the event stats")
+            .0;
 
-            assert!(
-                matches!(&delta_budget, Err(Error::NewBudget(_))),
-                "Got result: {delta_budget:?}"
+            assert_eq!(*PUBLISHER_2, response.publisher);
+            assert_eq!(4, response.stats.len());
+            assert_eq!(stat(5), response.stats[1].events[&IMPRESSION]);
+            assert_eq!(
+                stat(0),
+                response.stats[0].events[&IMPRESSION],
+                "PUBLISHER events are not included"
             );
         }
-        // Increasing budget
-        {
-            campaign_remaining
-                .set_initial(campaign.id, UnifiedNum::from_whole(900))
-                .await
-                .expect("should set");
-            let new_budget = UnifiedNum::from_whole(1_100);
-            let delta_budget = get_delta_budget(&campaign_remaining, &campaign, new_budget)
-                .await
-                .expect("should get delta budget");
-            assert!(delta_budget.is_some());
-            let increase_by = UnifiedNum::from_whole(100);
 
-            assert_eq!(delta_budget, Some(DeltaBudget::Increase(increase_by)));
-        }
-        // Decreasing budget
+        // Start after end
         {
-            campaign_remaining
-                .set_initial(campaign.id, UnifiedNum::from_whole(900))
-                .await
-                .expect("should set");
-            let new_budget = UnifiedNum::from_whole(800);
-            let delta_budget = get_delta_budget(&campaign_remaining, &campaign, new_budget)
-                .await
-                .expect("should get delta budget");
-            assert!(delta_budget.is_some());
-            let decrease_by = UnifiedNum::from_whole(200);
+            let query = EventStatsQuery {
+                publisher: *PUBLISHER,
+                start: Some(DateHour::from_ymdh(2022, 6, 20, 14)),
+                end: Some(DateHour::from_ymdh(2022, 6, 20, 13)),
+            };
 
-            assert_eq!(delta_budget, Some(DeltaBudget::Decrease(decrease_by)));
+            let err = campaign_event_stats(
+                app.clone(),
+                auth_as(*PUBLISHER),
+                campaign_context.clone(),
+                Qs(query),
+            )
+            .await
+            .expect_err("Should be a bad request");
+
+            assert_eq!(
+                ResponseError::BadRequest("start should be before or equal to end".to_string()),
+                err
+            );
         }
     }
 
     #[tokio::test]
-    async fn campaign_is_closed_properly() {
-        // create a new campaign with a new CampaignId
-        let campaign =
-            CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None).into_campaign();
-
+    async fn campaign_burn_rate_projects_the_exhaustion() {
         let app_guard = setup_dummy_app().await;
-
-        let channel_chain = app_guard
-            .config
-            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
-            .expect("Channel token should be whitelisted in config!");
-
         let app = Extension(Arc::new(app_guard.app.clone()));
 
-        let channel_context = channel_chain.with_channel(DUMMY_CAMPAIGN.channel);
+        let campaign = DUMMY_CAMPAIGN.clone();
+        let campaign_context = Extension(
+            app.config
+                .find_chain_of(campaign.channel.token)
+                .expect("Config should have the Dummy campaign.channel.token")
+                .with(campaign.clone()),
+        );
 
-        insert_channel(&app.pool, &channel_context)
+        insert_channel(&app.pool, &campaign_context.of_channel())
             .await
             .expect("Should insert dummy channel");
         insert_campaign(&app.pool, &campaign)
             .await
             .expect("Should insert dummy campaign");
 
-        let campaign_context = Extension(
-            app.config
-                .find_chain_of(campaign.channel.token)
-                .expect("Config should have the Dummy campaign.channel.token")
-                .with(campaign.clone()),
-        );
+        let current_hour = DateHour::now();
+        let seed = |publisher: Address, time: DateHour<Utc>, amount: u64| {
+            let pool = app.pool.clone();
+            let chain_id = campaign_context.chain.chain_id;
+
+            async move {
+                update_analytics(
+                    &pool,
+                    UpdateAnalytics {
+                        time,
+                        campaign_id: DUMMY_CAMPAIGN.id,
+                        ad_unit: DUMMY_IPFS[0],
+                        ad_slot: DUMMY_IPFS[1],
+                        ad_slot_type: None,
+                        advertiser: *CREATOR,
+                        publisher,
+                        hostname: None,
+                        country: None,
+                        os_name: OperatingSystem::Linux,
+                        chain_id,
+                        event_type: IMPRESSION,
+                        amount_to_add: UnifiedNum::from_whole(amount),
+                        count_to_add: 1,
+                    },
+                )
+                .await
+                .expect("Should insert analytics")
+            }
+        };
 
-        // Test if remaining is set to 0
+        // outside of the last 3 hours
+        seed(*PUBLISHER, current_hour - 5, 100).await;
+        // 60 spent in the last 3 hours
+        seed(*PUBLISHER, current_hour - 2, 20).await;
+        seed(*PUBLISHER_2, current_hour - 1, 30).await;
+        seed(*PUBLISHER, current_hour, 10).await;
+
+        // Insufficient data for a Campaign without any spending or remaining budget
         {
-            app.campaign_remaining
-                .set_initial(campaign.id, campaign.budget)
-                .await
-                .expect("should set");
+            let other_campaign = CampaignBuilder::new(campaign.channel).build();
+            let other_context = Extension(campaign_context.0.clone().with(other_campaign));
 
-            let auth = Extension(Auth {
-                era: 0,
-                uid: ValidatorId::from(campaign.creator),
-                chain: campaign_context.chain.clone(),
-            });
+            let response = campaign_burn_rate(
+                app.clone(),
+                other_context,
+                Qs(BurnRateQuery { hours: Some(1) }),
+            )
+            .await
+            .expect("Should get the burn rate")
+            .0;
 
-            close_campaign(app.clone(), auth.clone(), campaign_context.clone())
-                .await
-                .expect("Should close campaign");
+            assert_eq!(UnifiedNum::ZERO, response.spent);
+            assert_eq!(None, response.remaining);
+            assert_eq!(None, response.per_hour);
+            assert_eq!(None, response.exhaustion);
+        }
 
-            let closed_campaign = fetch_campaign(app.pool.clone(), &campaign.id)
-                .await
-                .expect("Should fetch campaign")
-                .expect("Campaign should exist");
+        app.campaign_remaining
+            .set_initial(campaign.id, UnifiedNum::from_whole(120))
+            .await
+            .expect("Should set the remaining budget");
 
-            // remaining == campaign_budget therefore old_budget - remaining = 0
-            assert_eq!(closed_campaign.budget, UnifiedNum::from_u64(0));
+        // The last 3 hours
+        {
+            let response = campaign_burn_rate(
+                app.clone(),
+                campaign_context.clone(),
+                Qs(BurnRateQuery { hours: Some(3) }),
+            )
+            .await
+            .expect("Should get the burn rate")
+            .0;
 
-            let remaining = app
-                .campaign_remaining
-                .get_remaining_opt(campaign.id)
-                .await
-                .expect("Should get remaining from redis")
-                .expect("There should be value for the Campaign");
+            assert_eq!((current_hour - 2).to_datetime(), response.since);
+            assert_eq!(UnifiedNum::from_whole(60), response.spent);
+            assert_eq!(Some(UnifiedNum::from_whole(120)), response.remaining);
 
-            assert_eq!(remaining, 0);
+            let per_hour = response.per_hour.expect("Should have a spend rate");
+            // 60 has been spent in more than 2 and less than 3 hours
+            assert!(
+                per_hour > UnifiedNum::from_whole(20) && per_hour <= UnifiedNum::from_whole(30)
+            );
+
+            // the remaining 120 is twice the amount spent in the elapsed time since the start,
+            // i.e. the exhaustion is in 2 x the elapsed time (between 2 and 3 hours)
+            let exhaustion = response.exhaustion.expect("Should project the exhaustion");
+            let since_start = exhaustion - response.since;
+            assert!(
+                since_start >= chrono::Duration::hours(6)
+                    && since_start <= chrono::Duration::hours(9),
+                "Exhaustion should be in 2 x the elapsed time, found {since_start} since the start"
+            );
         }
 
-        // Test if an error is returned when request is not sent by creator
+        // Out of bounds hours
         {
-            let auth = Extension(Auth {
-                era: 0,
-                uid: IDS[&LEADER],
-                chain: campaign_context.chain.clone(),
-            });
-
-            let res = close_campaign(app.clone(), auth, campaign_context.clone())
-                .await
-                .expect_err("Should return error for Bad Campaign");
+            let err = campaign_burn_rate(
+                app.clone(),
+                campaign_context.clone(),
+                Qs(BurnRateQuery { hours: Some(0) }),
+            )
+            .await
+            .expect_err("Should be a bad request");
 
             assert_eq!(
-                ResponseError::Forbidden("Request not sent by campaign creator".to_string()),
-                res
+                ResponseError::BadRequest(format!(
+                    "hours should be between 1 and {}",
+                    app.config.limits.analytics_find
+                )),
+                err
             );
         }
     }
 
     #[tokio::test]
-    async fn test_campaign_list() {
-        let mut app_guard = setup_dummy_app().await;
-        app_guard.config.limits.campaigns_find = 2;
-        let app = Extension(Arc::new(app_guard.app.clone()));
-
-        // Setting up new leader and a channel and campaign which use it on Ganache #1337
-        let dummy_leader_2 = ValidatorDesc {
-            id: IDS[&LEADER_2],
-            url: "http://tom.adex.network".to_string(),
-            fee: 200.into(),
-            fee_addr: None,
-        };
-        let channel_new_leader = Channel {
-            leader: IDS[&*LEADER_2],
-            follower: IDS[&*FOLLOWER],
-            guardian: *GUARDIAN,
-            token: DUMMY_CAMPAIGN.channel.token,
-            nonce: DUMMY_CAMPAIGN.channel.nonce,
-        };
-        let mut campaign_new_leader = DUMMY_CAMPAIGN.clone();
-        campaign_new_leader.id = CampaignId::new();
-        campaign_new_leader.channel = channel_new_leader;
-        campaign_new_leader.validators =
-            Validators::new((dummy_leader_2.clone(), DUMMY_VALIDATOR_FOLLOWER.clone()));
-        campaign_new_leader.created = Utc.ymd(2021, 2, 1).and_hms(8, 0, 0);
-
-        let chain_1_token = GANACHE_CONFIG.chains["Ganache #1"].tokens["Mocked TOKEN 1"].address;
-        // Setting up new follower and a channel and campaign which use it on Ganache #1
-        let dummy_follower_2 = ValidatorDesc {
-            id: IDS[&GUARDIAN],
-            url: "http://jerry.adex.network".to_string(),
-            fee: 300.into(),
-            fee_addr: None,
-        };
-        let channel_new_follower = Channel {
-            leader: IDS[&*LEADER],
-            follower: IDS[&*GUARDIAN],
-            guardian: *GUARDIAN,
-            token: chain_1_token,
-            nonce: DUMMY_CAMPAIGN.channel.nonce,
-        };
-        let mut campaign_new_follower = DUMMY_CAMPAIGN.clone();
-        campaign_new_follower.id = CampaignId::new();
-        campaign_new_follower.channel = channel_new_follower;
-        campaign_new_follower.validators =
-            Validators::new((DUMMY_VALIDATOR_LEADER.clone(), dummy_follower_2.clone()));
-        campaign_new_follower.created = Utc.ymd(2021, 2, 1).and_hms(9, 0, 0);
+    async fn campaign_health_derives_the_status() {
+        let app_guard = setup_dummy_app().await;
 
-        // Setting up a channel and campaign which use the new leader and follower on Ganache #1
-        let channel_new_leader_and_follower = Channel {
-            leader: IDS[&*LEADER_2],
-            follower: IDS[&*GUARDIAN],
-            guardian: *GUARDIAN,
-            token: chain_1_token,
-            nonce: DUMMY_CAMPAIGN.channel.nonce,
-        };
-        let mut campaign_new_leader_and_follower = DUMMY_CAMPAIGN.clone();
-        campaign_new_leader_and_follower.id = CampaignId::new();
-        campaign_new_leader_and_follower.channel = channel_new_leader_and_follower;
-        campaign_new_leader_and_follower.validators =
-            Validators::new((dummy_leader_2.clone(), dummy_follower_2.clone()));
-        campaign_new_leader_and_follower.created = Utc.ymd(2021, 2, 1).and_hms(10, 0, 0);
-        let channel_chain = app
+        let campaign = DUMMY_CAMPAIGN.clone();
+        let channel = campaign.channel;
+        let campaign_context = app_guard
+            .app
             .config
-            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
-            .expect("Channel token should be whitelisted in config!");
+            .find_chain_of(channel.token)
+            .expect("Config should have the Dummy campaign.channel.token")
+            .with(campaign.clone());
 
-        insert_channel(
-            &app.pool,
-            &channel_chain.clone().with_channel(DUMMY_CAMPAIGN.channel),
-        )
-        .await
-        .expect("Should insert dummy channel");
-        insert_campaign(&app.pool, &DUMMY_CAMPAIGN)
-            .await
-            .expect("Should insert dummy campaign");
-        insert_channel(
-            &app.pool,
-            &channel_chain.clone().with_channel(channel_new_leader),
-        )
-        .await
-        .expect("Should insert dummy channel");
-        insert_campaign(&app.pool, &campaign_new_leader)
+        insert_channel(&app_guard.app.pool, &campaign_context.of_channel())
             .await
-            .expect("Should insert dummy campaign");
-        insert_channel(
-            &app.pool,
-            &channel_chain.clone().with_channel(channel_new_follower),
-        )
-        .await
-        .expect("Should insert dummy channel");
-        insert_campaign(&app.pool, &campaign_new_follower)
+            .expect("Should insert dummy channel");
+        insert_campaign(&app_guard.app.pool, &campaign)
             .await
             .expect("Should insert dummy campaign");
-        insert_channel(
-            &app.pool,
-            &channel_chain.with_channel(channel_new_leader_and_follower),
-        )
-        .await
-        .expect("Should insert dummy channel");
-        insert_campaign(&app.pool, &campaign_new_leader_and_follower)
+
+        // the thresholds: (awaiting approval after, stalled after)
+        let get_health = |thresholds: (Duration, Duration)| {
+            let mut app = app_guard.app.clone();
+            app.config.sentry.campaign_awaiting_approval_after = thresholds.0;
+            app.config.sentry.campaign_stalled_after = thresholds.1;
+
+            campaign_health(
+                Extension(Arc::new(app)),
+                Extension(Auth {
+                    era: 0,
+                    uid: IDS[&CREATOR],
+                    chain: campaign_context.chain.clone(),
+                    scope: Scope::Full,
+                }),
+                Extension(campaign_context.clone()),
+            )
+        };
+        let one_hour = Duration::from_secs(60 * 60);
+        let default_thresholds = (one_hour, 2 * one_hour);
+
+        // only the creator & the validators are allowed
+        {
+            let response = campaign_health(
+                Extension(Arc::new(app_guard.app.clone())),
+                Extension(Auth {
+                    era: 0,
+                    uid: IDS[&PUBLISHER],
+                    chain: campaign_context.chain.clone(),
+                    scope: Scope::Full,
+                }),
+                Extension(campaign_context.clone()),
+            )
             .await
-            .expect("Should insert dummy campaign");
+            .expect_err("Publisher should not be allowed");
 
-        let mut campaign_other_creator = DUMMY_CAMPAIGN.clone();
-        campaign_other_creator.id = CampaignId::new();
-        campaign_other_creator.creator = *PUBLISHER_2;
-        campaign_other_creator.created = Utc.ymd(2021, 2, 1).and_hms(11, 0, 0);
+            assert_eq!(
+                ResponseError::Forbidden(
+                    "Request not sent by the campaign creator or a validator".to_string()
+                ),
+                response
+            );
+        }
 
-        insert_campaign(&app.pool, &campaign_other_creator)
+        app_guard
+            .app
+            .campaign_remaining
+            .set_initial(campaign.id, UnifiedNum::from_whole(100))
             .await
-            .expect("Should insert dummy campaign");
+            .expect("Should set the remaining budget");
 
-        let mut campaign_long_active_to = DUMMY_CAMPAIGN.clone();
-        campaign_long_active_to.id = CampaignId::new();
-        campaign_long_active_to.active.to = Utc.ymd(2101, 1, 30).and_hms(0, 0, 0);
-        campaign_long_active_to.created = Utc.ymd(2021, 2, 1).and_hms(12, 0, 0);
+        // Active - nothing spent yet
+        {
+            let response = get_health(default_thresholds)
+                .await
+                .expect("Should get health")
+                .0;
+
+            assert_eq!(CampaignHealthStatus::Active, response.status);
+            assert_eq!(UnifiedNum::ZERO, response.pending_spent);
+            assert_eq!(None, response.last_approve_state);
+            let utilization = response.utilization.expect("Should have remaining budget");
+            assert_eq!(UnifiedNum::ZERO, utilization.spent);
+        }
 
-        insert_campaign(&app.pool, &campaign_long_active_to)
+        // 10 TOKENs spent and approved
+        let approved_state_root = "1".repeat(64);
+        {
+            update_accounting(
+                app_guard.app.pool.clone(),
+                channel.id(),
+                campaign.creator,
+                Side::Spender,
+                UnifiedNum::from_whole(10),
+            )
             .await
-            .expect("Should insert dummy campaign");
+            .expect("Should update accounting");
+
+            let mut balances = Balances::<UncheckedState>::default();
+            balances
+                .spenders
+                .insert(campaign.creator, UnifiedNum::from_whole(10));
+
+            insert_validator_message(
+                &app_guard.app.pool,
+                &channel,
+                &channel.leader,
+                &MessageTypes::NewState(NewState {
+                    state_root: approved_state_root.clone(),
+                    signature: channel.leader.to_string(),
+                    balances,
+                }),
+            )
+            .await
+            .expect("Should insert NewState");
+            insert_validator_message(
+                &app_guard.app.pool,
+                &channel,
+                &channel.follower,
+                &MessageTypes::ApproveState(ApproveState {
+                    state_root: approved_state_root.clone(),
+                    signature: channel.follower.to_string(),
+                    is_healthy: true,
+                }),
+            )
+            .await
+            .expect("Should insert ApproveState");
 
-        // Test for dummy leader
-        {
-            let query = CampaignListQuery {
-                page: 0,
-                active_to_ge: Utc::now(),
-                creator: None,
-                validator: Some(ValidatorParam::Leader(DUMMY_VALIDATOR_LEADER.id)),
-            };
+            app_guard
+                .app
+                .campaign_remaining
+                .decrease_by(campaign.id, UnifiedNum::from_whole(10))
+                .await
+                .expect("Should decrease the remaining budget");
+        }
 
-            let res = campaign_list(app.clone(), Qs(query))
+        // Active - nothing pending even with zero thresholds
+        {
+            let response = get_health((Duration::ZERO, Duration::ZERO))
                 .await
-                .expect("should get campaigns")
+                .expect("Should get health")
                 .0;
 
+            assert_eq!(CampaignHealthStatus::Active, response.status);
+            assert_eq!(UnifiedNum::from_whole(10), response.accounting_spent);
+            assert_eq!(UnifiedNum::from_whole(10), response.approved_spent);
+            assert_eq!(UnifiedNum::ZERO, response.pending_spent);
             assert_eq!(
-                res.campaigns,
-                vec![DUMMY_CAMPAIGN.clone(), campaign_new_follower.clone()],
-                "First page of campaigns with dummy leader is correct"
+                Some(approved_state_root.as_str()),
+                response
+                    .last_approve_state
+                    .as_ref()
+                    .map(|approve_state| approve_state.state_root.as_str())
             );
-            assert_eq!(res.pagination.total_pages, 2);
+        }
 
-            let query = CampaignListQuery {
-                page: 1,
-                active_to_ge: Utc::now(),
-                creator: None,
-                validator: Some(ValidatorParam::Leader(DUMMY_VALIDATOR_LEADER.id)),
-            };
-            let res = campaign_list(app.clone(), Qs(query))
+        // another 90 TOKENs spent, which exhaust the budget, but are not approved yet
+        update_accounting(
+            app_guard.app.pool.clone(),
+            channel.id(),
+            campaign.creator,
+            Side::Spender,
+            UnifiedNum::from_whole(90),
+        )
+        .await
+        .expect("Should update accounting");
+        app_guard
+            .app
+            .campaign_remaining
+            .decrease_by(campaign.id, UnifiedNum::from_whole(90))
+            .await
+            .expect("Should decrease the remaining budget");
+
+        // OutOfBudget - the pending spending is recent
+        {
+            let response = get_health(default_thresholds)
                 .await
-                .expect("should get campaigns")
+                .expect("Should get health")
                 .0;
 
-            assert_eq!(
-                res.campaigns,
-                vec![
-                    campaign_other_creator.clone(),
-                    campaign_long_active_to.clone()
-                ],
-                "Second page of campaigns with dummy leader is correct"
-            );
+            assert_eq!(CampaignHealthStatus::OutOfBudget, response.status);
+            assert_eq!(UnifiedNum::from_whole(100), response.accounting_spent);
+            assert_eq!(UnifiedNum::from_whole(90), response.pending_spent);
+            let utilization = response.utilization.expect("Should have remaining budget");
+            assert_eq!(UnifiedNum::ZERO, utilization.remaining);
+            assert_eq!(100.0, utilization.percent);
         }
 
-        // Test for dummy follower
+        // AwaitingApproval
         {
-            let query = CampaignListQuery {
-                page: 0,
-                active_to_ge: Utc::now(),
-                creator: None,
-                validator: Some(ValidatorParam::Validator(DUMMY_VALIDATOR_FOLLOWER.id)),
-            };
-            let res = campaign_list(app.clone(), Qs(query))
+            let response = get_health((Duration::ZERO, one_hour))
                 .await
-                .expect("should get campaigns")
+                .expect("Should get health")
                 .0;
 
-            assert_eq!(
-                res.campaigns,
-                vec![DUMMY_CAMPAIGN.clone(), campaign_new_leader.clone()],
-                "First page of campaigns with dummy follower is correct"
-            );
-            assert_eq!(res.pagination.total_pages, 2);
+            assert_eq!(CampaignHealthStatus::AwaitingApproval, response.status);
+        }
 
-            let query = CampaignListQuery {
-                page: 1,
-                active_to_ge: Utc::now(),
-                creator: None,
-                validator: Some(ValidatorParam::Validator(DUMMY_VALIDATOR_FOLLOWER.id)),
-            };
-            let res = campaign_list(app.clone(), Qs(query))
+        // ChannelStalled
+        {
+            let response = get_health((Duration::ZERO, Duration::ZERO))
                 .await
-                .expect("should get campaigns")
+                .expect("Should get health")
                 .0;
 
-            assert_eq!(
-                res.campaigns,
-                vec![
-                    campaign_other_creator.clone(),
-                    campaign_long_active_to.clone()
-                ],
-                "Second page of campaigns with dummy follower is correct"
-            );
+            assert_eq!(CampaignHealthStatus::ChannelStalled, response.status);
         }
+    }
 
-        // Test for dummy leader 2
+    #[tokio::test]
+    async fn create_campaigns_in_bulk() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        app.adapter.client.set_deposit(
+            &channel_context,
+            *CREATOR,
+            Deposit {
+                total: UnifiedNum::from_whole(2_000)
+                    .to_precision(channel_context.token.precision.get()),
+            },
+        );
+
+        let auth = Extension(Auth {
+            era: 0,
+            uid: IDS[&CREATOR],
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        });
+
+        let create_with_budget = |budget: u64| {
+            let mut create = CreateCampaign::from_campaign_erased(
+                DUMMY_CAMPAIGN.clone(),
+                Some(CampaignId::new()),
+            );
+            create.budget = UnifiedNum::from_whole(budget);
+            create
+        };
+
+        let assert_not_persisted = |campaign_ids: Vec<CampaignId>| {
+            let app = app.clone();
+
+            async move {
+                for campaign_id in campaign_ids {
+                    assert!(
+                        fetch_campaign(app.pool.clone(), &campaign_id)
+                            .await
+                            .expect("Should fetch campaign")
+                            .is_none(),
+                        "Campaign should not be inserted"
+                    );
+                    assert!(
+                        app.campaign_remaining
+                            .get_remaining_opt(campaign_id)
+                            .await
+                            .expect("Should get remaining from redis")
+                            .is_none(),
+                        "Campaign remaining should not be set"
+                    );
+                }
+            }
+        };
+
+        // Mid-validation failure, the second campaign is not sent by its creator
         {
-            let query = CampaignListQuery {
-                page: 0,
-                active_to_ge: Utc::now(),
-                creator: None,
-                validator: Some(ValidatorParam::Leader(dummy_leader_2.id)),
-            };
-            let res = campaign_list(app.clone(), Qs(query))
+            let first = create_with_budget(100);
+            let mut second = create_with_budget(100);
+            second.creator = *PUBLISHER;
+            let campaign_ids = vec![first.id.unwrap(), second.id.unwrap()];
+
+            let err = create_campaigns_bulk(Json(vec![first, second]), auth.clone(), app.clone())
                 .await
-                .expect("should get campaigns")
-                .0;
+                .expect_err("Should fail for the second campaign");
 
             assert_eq!(
-                res.campaigns,
-                vec![
-                    campaign_new_leader.clone(),
-                    campaign_new_leader_and_follower.clone()
-                ],
-                "Campaigns with dummy leader 2 are correct"
+                ResponseError::Forbidden("Request not sent by campaign creator".to_string())
+                    .for_bulk_entry(1),
+                err
             );
-            assert_eq!(res.pagination.total_pages, 1);
+            assert_not_persisted(campaign_ids).await;
         }
 
-        // Test for dummy follower 2
+        // The combined budgets exceed the deposit at the third campaign
         {
-            let query = CampaignListQuery {
-                page: 0,
-                active_to_ge: Utc::now(),
-                creator: None,
-                validator: Some(ValidatorParam::Validator(dummy_follower_2.id)),
-            };
-            let res = campaign_list(app.clone(), Qs(query))
+            let creates = vec![
+                create_with_budget(1_000),
+                create_with_budget(900),
+                create_with_budget(200),
+            ];
+            let campaign_ids = creates.iter().map(|create| create.id.unwrap()).collect();
+
+            let err = create_campaigns_bulk(Json(creates), auth.clone(), app.clone())
                 .await
-                .expect("should get campaigns")
-                .0;
+                .expect_err("Should fail for the third campaign");
 
             assert_eq!(
-                res.campaigns,
-                vec![
-                    campaign_new_follower.clone(),
-                    campaign_new_leader_and_follower.clone()
-                ],
-                "Campaigns with dummy follower 2 are correct"
+                ResponseError::BadRequest(
+                    "Not enough deposit left for the new campaign's budget".to_string()
+                )
+                .for_bulk_entry(2),
+                err
             );
-            assert_eq!(res.pagination.total_pages, 1);
+            assert_not_persisted(campaign_ids).await;
         }
 
-        // Test for other creator
+        // Failing to insert the campaigns cleans up the already set remaining
         {
-            let query = CampaignListQuery {
-                page: 0,
-                active_to_ge: Utc::now(),
-                creator: Some(*PUBLISHER_2),
-                validator: None,
-            };
-            let res = campaign_list(app.clone(), Qs(query))
+            let first = create_with_budget(100);
+            let second = create_with_budget(100);
+            let campaign_ids = vec![first.id.unwrap(), second.id.unwrap()];
+
+            // the second Campaign already exists in the database (but not in Redis)
+            // so the transaction fails after setting the remaining of both campaigns
+            let existing = second.clone().into_campaign();
+            insert_channel(&app.pool, &channel_context)
                 .await
-                .expect("should get campaigns")
-                .0;
+                .expect("Should insert channel");
+            assert!(insert_campaign(&app.pool, &existing)
+                .await
+                .expect("Should insert the existing campaign"));
+
+            let err = create_campaigns_bulk(Json(vec![first, second]), auth.clone(), app.clone())
+                .await
+                .expect_err("Should fail to insert the campaigns");
 
             assert_eq!(
-                res.campaigns,
-                vec![campaign_other_creator.clone()],
-                "The campaign with a different creator is retrieved correctly"
+                ResponseError::Conflict("Campaign already exists".to_string()),
+                err
             );
-            assert_eq!(res.pagination.total_pages, 1);
+
+            assert!(
+                fetch_campaign(app.pool.clone(), &campaign_ids[0])
+                    .await
+                    .expect("Should fetch campaign")
+                    .is_none(),
+                "The first Campaign should not be inserted"
+            );
+            for campaign_id in campaign_ids {
+                assert!(
+                    app.campaign_remaining
+                        .get_remaining_opt(campaign_id)
+                        .await
+                        .expect("Should get remaining from redis")
+                        .is_none(),
+                    "The set remaining should be removed"
+                );
+            }
         }
 
-        // Test for active_to
+        // Full success
         {
-            let query = CampaignListQuery {
-                page: 0,
-                active_to_ge: Utc.ymd(2101, 1, 1).and_hms(0, 0, 0),
-                creator: None,
-                validator: None,
-            };
-            let res = campaign_list(app.clone(), Qs(query))
+            let creates = vec![create_with_budget(500), create_with_budget(700)];
+
+            let created = create_campaigns_bulk(Json(creates.clone()), auth.clone(), app.clone())
                 .await
-                .expect("should get campaigns")
+                .expect("Should create campaigns")
                 .0;
 
+            assert_eq!(2, created.len());
+            for (create, campaign) in creates.into_iter().zip(created) {
+                assert_eq!(create.id, Some(campaign.id));
+
+                let fetched = fetch_campaign(app.pool.clone(), &campaign.id)
+                    .await
+                    .expect("Should fetch campaign")
+                    .expect("Campaign should be inserted");
+                assert_eq!(campaign.budget, fetched.budget);
+
+                let remaining = app
+                    .campaign_remaining
+                    .get_remaining_opt(campaign.id)
+                    .await
+                    .expect("Should get remaining from redis")
+                    .expect("Campaign remaining should be set");
+                assert_eq!(campaign.budget.to_u64(), remaining.unsigned_abs());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn budget_history_records_each_modification_in_order() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Channel token should be whitelisted in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        app.adapter.client.set_deposit(
+            &channel_context,
+            *CREATOR,
+            Deposit {
+                total: UnifiedNum::from_whole(2_000)
+                    .to_precision(channel_context.token.precision.get()),
+            },
+        );
+
+        let auth = |uid: ValidatorId| Auth {
+            era: 0,
+            uid,
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        };
+
+        let mut create = CreateCampaign::from_campaign_erased(DUMMY_CAMPAIGN.clone(), None);
+        create.budget = UnifiedNum::from_whole(500);
+        let mut campaign =
+            create_campaign(Json(create), Extension(auth(IDS[&CREATOR])), app.clone())
+                .await
+                .expect("Should create campaign")
+                .0;
+
+        for new_budget in [600, 550] {
+            let modify = ModifyCampaign {
+                version: campaign.version,
+                budget: Some(UnifiedNum::from_whole(new_budget)),
+                validators: None,
+                title: None,
+                pricing_bounds: None,
+                unit_pricing_overrides: None,
+                event_submission: None,
+                pacing: None,
+                ad_units: None,
+                targeting_rules: None,
+            };
+
+            campaign = update_campaign::handle_route(
+                Json(modify),
+                Extension(channel_context.clone().with(campaign)),
+                app.clone(),
+                Extension(auth(IDS[&CREATOR])),
+            )
+            .await
+            .expect("Should modify campaign")
+            .0;
+        }
+
+        let campaign_context = Extension(channel_context.clone().with(campaign.clone()));
+
+        // only the budget changes are recorded, oldest first
+        {
+            let response = campaign_budget_history(
+                app.clone(),
+                Extension(auth(IDS[&LEADER])),
+                campaign_context.clone(),
+                Qs(BudgetHistoryQuery::default()),
+            )
+            .await
+            .expect("Should get the budget history")
+            .0;
+
+            assert_eq!(campaign.id, response.campaign);
             assert_eq!(
-                res.campaigns,
-                vec![campaign_long_active_to.clone()],
-                "The campaign with a longer active_to is retrieved correctly"
+                vec![
+                    (
+                        UnifiedNum::from_whole(500),
+                        UnifiedNum::from_whole(600),
+                        *CREATOR
+                    ),
+                    (
+                        UnifiedNum::from_whole(600),
+                        UnifiedNum::from_whole(550),
+                        *CREATOR
+                    ),
+                ],
+                response
+                    .history
+                    .iter()
+                    .map(|change| (change.old_budget, change.new_budget, change.actor))
+                    .collect::<Vec<_>>()
             );
-            assert_eq!(res.pagination.total_pages, 1);
+            assert!(response.history[0].created <= response.history[1].created);
+        }
+
+        // neither the creator nor a validator
+        {
+            let response = campaign_budget_history(
+                app.clone(),
+                Extension(auth(IDS[&PUBLISHER])),
+                campaign_context,
+                Qs(BudgetHistoryQuery::default()),
+            )
+            .await;
+
+            assert!(matches!(response, Err(ResponseError::Forbidden(_))));
         }
     }
 }
@@ -1,11 +1,12 @@
 //! `/v5/channel` routes
 //!
 
-use axum::{extract::Path, Extension, Json};
+use axum::{extract::Path, http::HeaderMap, Extension, Json};
+use chrono::Utc;
 use futures::future::try_join_all;
 use serde::{Deserialize, Serialize};
-use slog::{error, Logger};
-use std::{any::Any, collections::HashMap, sync::Arc};
+use slog::{error, info, warn, Logger};
+use std::{any::Any, collections::HashMap, sync::Arc, time::Duration};
 
 use adapter::{
     client::Locked,
@@ -13,33 +14,54 @@ use adapter::{
     Adapter, Dummy,
 };
 use primitives::{
-    balances::{Balances, BalancesState, CheckedState, UncheckedState},
+    balances::{Balances, BalancesState, CheckedState, Error as BalancesError, UncheckedState},
+    campaign_validator::{validate_channel_addresses, Error as ValidatorError},
     merkle_tree::MerkleTree,
     sentry::{
         channel_list::{ChannelListQuery, ChannelListResponse},
-        message::MessageResponse,
-        AccountingResponse, AllSpendersQuery, AllSpendersResponse, ChannelPayRequest,
-        GetLeafResponse, LastApproved, LastApprovedQuery, LastApprovedResponse, SpenderResponse,
-        SuccessResponse,
+        message::{Message, MessageResponse},
+        validator_messages::{QuarantineStateRequest, QuarantinedMessagesResponse},
+        AccountingFreezeRequest, AccountingPageQuery, AccountingPageResponse, AccountingResponse,
+        AllSpendersQuery, AllSpendersResponse, ChannelLagResponse, ChannelPayPreviewResponse,
+        ChannelPayRequest, ChannelSnapshot, ChannelSnapshotResponse, GetLeafResponse, LastApproved,
+        LastApprovedHistoryQuery, LastApprovedHistoryResponse, LastApprovedQuery,
+        LastApprovedResponse, PayoutPreview, SpendableHistoryQuery, SpendableHistoryResponse,
+        SpenderResponse, SpendersBatchRequest, SpendersBatchResponse, StateAge, SuccessResponse,
     },
-    spender::{Spendable, Spender},
-    validator::{ApproveState, NewState},
-    Address, ChainOf, Channel, ChannelId, Deposit, UnifiedNum,
+    spender::{Spendable, SpendableSource, Spender},
+    validator::{ApproveState, MessageType, NewState},
+    Address, BigNum, CampaignId, ChainOf, Channel, ChannelId, Deposit, DepositBreakdown,
+    UnifiedNum,
 };
 
 use crate::{
     application::Qs,
     db::{
         accounting::{
-            get_accounting, get_all_accountings_for_channel, spend_amount, update_accounting, Side,
+            get_accounting, get_accountings_for_channel_page, get_all_accountings_for_channel,
+            spend_amount, update_accounting, Error as AccountingError, Side,
+        },
+        fetch_channel_accounting_records, get_campaigns_by_channel_and_creator, insert_channel,
+        is_accounting_frozen, list_channels, set_accounting_frozen,
+        spendable::{
+            fetch_spendable, fetch_spendables_for_spenders, get_all_spendables_for_channel,
+            get_spendable_history, insert_spendable_if_missing, update_spendable,
+        },
+        validator_message::{
+            get_quarantined_messages, get_validator_messages, latest_approve_state,
+            latest_approved_states, latest_heartbeats, latest_new_state, quarantine_state,
+            release_quarantined_message,
         },
-        insert_channel, list_channels,
-        spendable::{fetch_spendable, get_all_spendables_for_channel, update_spendable},
-        validator_message::{latest_approve_state, latest_heartbeats, latest_new_state},
         DbPool,
     },
     response::ResponseError,
-    routes::{campaign::fetch_campaign_ids_for_channel, routers::LeafFor},
+    routes::{
+        campaign::{
+            fetch_campaign_ids_for_channel, notify_campaign_closed, restore_remaining,
+            set_remaining_to_zero, unified_deposit,
+        },
+        routers::LeafFor,
+    },
     Application, Auth,
 };
 
@@ -61,16 +83,25 @@ pub async fn channel_list<C: Locked + 'static>(
     Extension(app): Extension<Arc<Application<C>>>,
     Qs(query): Qs<ChannelListQuery>,
 ) -> Result<Json<ChannelListResponse>, ResponseError> {
+    let config_limit = app.config.limits.channels_find;
+    let limit = query
+        .limit
+        .filter(|n| *n >= 1)
+        .map_or(config_limit, |limit| {
+            u32::try_from(limit).unwrap_or(u32::MAX).min(config_limit)
+        });
+
     let skip = query
         .page
-        .checked_mul(app.config.limits.channels_find.into())
+        .checked_mul(limit.into())
         .ok_or_else(|| ResponseError::BadRequest("Page and/or limit is too large".into()))?;
 
     let list_response = list_channels(
         &app.pool,
         skip,
-        app.config.limits.channels_find,
+        limit,
         query.validator,
+        query.guardian,
         &query.chains,
     )
     .await?;
@@ -78,6 +109,49 @@ pub async fn channel_list<C: Locked + 'static>(
     Ok(Json(list_response))
 }
 
+/// POST `/v5/channel` request
+///
+/// Full details about the route's API and intend can be found in the [`routes`](crate::routes#post-v5channel-auth-required) module
+///
+/// Request body (json): [`Channel`]
+///
+/// Response: [`Channel`]
+pub async fn create_channel<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(auth): Extension<Auth>,
+    Json(channel): Json<Channel>,
+) -> Result<Json<Channel>, ResponseError> {
+    validate_channel_addresses(&channel)
+        .map_err(|err| ResponseError::FailedValidation(ValidatorError::from(err).to_string()))?;
+
+    let channel_context = app
+        .config
+        .find_chain_of(channel.token)
+        .ok_or(ResponseError::ChannelTokenNotWhitelisted(channel.token))?
+        .with_channel(channel);
+
+    let is_channel_party = channel.leader == auth.uid
+        || channel.follower == auth.uid
+        || channel.guardian == auth.uid.to_address();
+
+    if !is_channel_party && !app.config.sentry.spender_channel_registration {
+        return Err(ResponseError::Forbidden(
+            "Channel can be registered only by its validators or guardian".to_string(),
+        ));
+    }
+
+    // returns the existing Channel if it's already registered
+    let channel = insert_channel(&app.pool, &channel_context)
+        .await
+        .map_err(|error| {
+            error!(&app.logger, "{}", &error; "module" => "create_channel");
+
+            ResponseError::BadRequest("Failed to create Channel".to_string())
+        })?;
+
+    Ok(Json(channel))
+}
+
 /// GET `/v5/channel/0xXXX.../last-approved` request
 ///
 /// Full details about the route's API and intend can be found in the [`routes`](crate::routes#get-v5channelidlast-approved) module
@@ -128,37 +202,157 @@ pub async fn last_approved<C: Locked + 'static>(
     }))
 }
 
-/// This will make sure to insert/get the `Channel` from DB before attempting to create the `Spendable`
-async fn create_or_update_spendable_document<A: Locked>(
-    adapter: &Adapter<A>,
-    pool: DbPool,
+/// GET `/v5/channel/0xXXX.../last-approved/history` request
+///
+/// Full details about the route's API and intend can be found in the [`routes`](crate::routes#get-v5channelidlast-approvedhistory) module
+///
+/// Request query parameters: [`LastApprovedHistoryQuery`]
+///
+/// Response: [`LastApprovedHistoryResponse`]
+pub async fn last_approved_history<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(channel_context): Extension<ChainOf<Channel>>,
+    Qs(query): Qs<LastApprovedHistoryQuery>,
+) -> Result<Json<LastApprovedHistoryResponse<UncheckedState>>, ResponseError> {
+    let channel = channel_context.context;
+
+    let config_limit = app.config.limits.approved_states_find as u64;
+    let limit = query
+        .limit
+        .filter(|n| *n >= 1)
+        .unwrap_or(config_limit)
+        .min(config_limit);
+
+    let history = latest_approved_states::<UncheckedState>(&app.pool, &channel, limit).await?;
+
+    Ok(Json(LastApprovedHistoryResponse { history }))
+}
+
+/// GET `/v5/channel/0xXXX.../lag` request
+///
+/// Full details about the route's API and intend can be found in the [`routes`](crate::routes#get-v5channelidlag) module
+///
+/// Response: [`ChannelLagResponse`]
+pub async fn channel_lag<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(channel_context): Extension<ChainOf<Channel>>,
+) -> Result<Json<ChannelLagResponse>, ResponseError> {
+    let channel = channel_context.context;
+    let now = Utc::now();
+
+    let latest_new_state = get_validator_messages(
+        &app.pool,
+        &channel.id(),
+        &Some(channel.leader),
+        &[MessageType::NewState],
+        1,
+    )
+    .await?
+    .into_iter()
+    .next()
+    .map(|message| StateAge::new(message.msg.state_root().to_string(), message.received, now));
+
+    // the NewState of the ApproveState is not required for the lag,
+    // so a missing or invalid NewState doesn't fail the request
+    let approve_state = latest_approve_state(&app.pool, &channel)
+        .await?
+        .map(|approve_state| {
+            StateAge::new(
+                approve_state.msg.state_root.clone(),
+                approve_state.received,
+                now,
+            )
+        });
+
+    let corresponds = match (&latest_new_state, &approve_state) {
+        (Some(new_state), Some(approve_state)) => new_state.state_root == approve_state.state_root,
+        _ => false,
+    };
+
+    Ok(Json(ChannelLagResponse {
+        new_state: latest_new_state,
+        approve_state,
+        corresponds,
+    }))
+}
+
+/// Fetches the `Spendable` of the spender and refreshes its deposit from the
+/// [`Adapter.get_deposit()`](adapter::client::Locked::get_deposit),
+/// creating the `Spendable` if it doesn't exist yet.
+///
+/// See [`refresh_spendable_document()`] for when the `Spendable` is persisted.
+async fn fetch_or_create_spendable_document<C: Locked + 'static>(
+    app: &Application<C>,
     channel_context: &ChainOf<Channel>,
     spender: Address,
 ) -> Result<Spendable, ResponseError> {
-    insert_channel(&pool, channel_context).await?;
+    let deposit = app.adapter.get_deposit(channel_context, spender).await?;
 
-    let deposit = adapter.get_deposit(channel_context, spender).await?;
-    let total = UnifiedNum::from_precision(deposit.total, channel_context.token.precision.get());
+    refresh_spendable_document(app, channel_context, spender, deposit).await
+}
 
-    let total = match total {
-        Some(total) => total,
-        _ => {
-            return Err(ResponseError::BadRequest(
-                "couldn't get total from precision".to_string(),
-            ))
-        }
-    };
+/// Fetches the `Spendable` of the spender and refreshes it with the
+/// already fetched adapter deposit, creating the `Spendable` if it doesn't exist yet.
+///
+/// This will make sure to insert/get the `Channel` from DB before attempting to create the `Spendable`.
+/// Concurrent first requests for the same spender converge on a single `Spendable`,
+/// the first inserted one, see [`insert_spendable_if_missing()`].
+///
+/// An existing `Spendable` is updated whenever its deposit has changed,
+/// while a new `Spendable` without a deposit is not persisted.
+///
+/// With a [`read_only`](Application::read_only) (shadow) [`Application`] nothing is written to the DB,
+/// the `Spendable` is only computed from the deposit.
+async fn refresh_spendable_document<C: Locked + 'static>(
+    app: &Application<C>,
+    channel_context: &ChainOf<Channel>,
+    spender: Address,
+    deposit: Deposit<BigNum>,
+) -> Result<Spendable, ResponseError> {
+    let existing =
+        fetch_spendable(app.pool.clone(), &spender, &channel_context.context.id()).await?;
+
+    let deposit =
+        unified_deposit(&app.logger, deposit, channel_context, spender).map_err(|err| {
+            ResponseError::BadRequest(format!("couldn't get total from precision: {}", err))
+        })?;
 
     let spendable = Spendable {
         channel: channel_context.context,
-        deposit: Deposit { total },
+        deposit,
         spender,
     };
 
-    // Insert latest spendable in DB
-    update_spendable(pool, &spendable).await?;
+    if app.read_only {
+        return Ok(spendable);
+    }
+
+    match existing {
+        Some(existing) if existing.deposit == spendable.deposit => Ok(existing),
+        Some(_existing) => Ok(update_spendable(
+            app.pool.clone(),
+            &app.redis,
+            &app.redis_keys,
+            &spendable,
+            SpendableSource::SpenderRoute,
+        )
+        .await?),
+        // Spenders without a deposit are not persisted,
+        // otherwise every queried address would leave a row behind
+        None if spendable.deposit.total == UnifiedNum::ZERO => Ok(spendable),
+        None => {
+            insert_channel(&app.pool, channel_context).await?;
 
-    Ok(spendable)
+            Ok(insert_spendable_if_missing(
+                app.pool.clone(),
+                &app.redis,
+                &app.redis_keys,
+                &spendable,
+                SpendableSource::SpenderRoute,
+            )
+            .await?)
+        }
+    }
 }
 
 /// GET `/v5/channel/0xXXX.../spender/0xXXX...` request
@@ -173,20 +367,25 @@ pub async fn get_spender_limits<C: Locked + 'static>(
 
     let spender = params.1;
 
-    let latest_spendable = fetch_spendable(app.pool.clone(), &spender, &channel.id()).await?;
+    // a single adapter call for both the deposit and its breakdown
+    let detailed_deposit = app
+        .adapter
+        .get_deposit_detailed(&channel_context, spender)
+        .await?;
 
-    let latest_spendable = match latest_spendable {
-        Some(spendable) => spendable,
-        None => {
-            create_or_update_spendable_document(
-                &app.adapter,
-                app.pool.clone(),
-                &channel_context,
-                spender,
-            )
-            .await?
-        }
-    };
+    let latest_spendable = refresh_spendable_document(
+        &app,
+        &channel_context,
+        spender,
+        Deposit {
+            total: detailed_deposit.total,
+        },
+    )
+    .await?;
+
+    let deposit_breakdown = detailed_deposit.breakdown.and_then(|breakdown| {
+        DepositBreakdown::from_precision(breakdown, channel_context.token.precision.get())
+    });
 
     let (_, new_state) =
         match get_corresponding_states::<CheckedState>(&app.pool, &app.logger, channel).await? {
@@ -197,6 +396,7 @@ pub async fn get_spender_limits<C: Locked + 'static>(
                         total_deposited: latest_spendable.deposit.total,
                         total_spent: None,
                     },
+                    deposit_breakdown,
                 }))
             }
         };
@@ -213,6 +413,7 @@ pub async fn get_spender_limits<C: Locked + 'static>(
             total_deposited: latest_spendable.deposit.total,
             total_spent,
         },
+        deposit_breakdown,
     }))
 }
 
@@ -226,7 +427,13 @@ pub async fn get_all_spender_limits<C: Locked + 'static>(
 ) -> Result<Json<AllSpendersResponse>, ResponseError> {
     let channel = channel_context.context;
 
-    let limit = app.config.limits.spendable_find;
+    let config_limit = app.config.limits.spendable_find;
+    let limit = query
+        .limit
+        .filter(|n| *n >= 1)
+        .map_or(config_limit, |limit| {
+            u32::try_from(limit).unwrap_or(u32::MAX).min(config_limit)
+        });
     let skip = query
         .page
         .checked_mul(limit.into())
@@ -237,33 +444,22 @@ pub async fn get_all_spender_limits<C: Locked + 'static>(
 
     let mut all_spender_limits: HashMap<Address, Spender> = HashMap::new();
 
-    let (all_spendables, pagination) =
-        get_all_spendables_for_channel(app.pool.clone(), &channel.id(), skip, limit.into()).await?;
+    let (all_spendables, pagination) = get_all_spendables_for_channel(
+        app.pool.clone(),
+        &channel.id(),
+        skip,
+        limit.into(),
+        query.include_empty,
+    )
+    .await?;
 
     // Using for loop to avoid async closures
     for spendable in all_spendables {
-        let spender = spendable.spender;
-        let total_spent = corresponding_states.as_ref().and_then(|(_, new_state)| {
-            new_state
-                .msg
-                .balances
-                .spenders
-                .get(&spender)
-                .map(|balance| {
-                    spendable
-                        .deposit
-                        .total
-                        .checked_sub(balance)
-                        .unwrap_or_default()
-                })
-        });
-
-        let spender_info = Spender {
-            total_deposited: spendable.deposit.total,
-            total_spent,
-        };
+        let new_state = corresponding_states
+            .as_ref()
+            .map(|(_, new_state)| new_state);
 
-        all_spender_limits.insert(spender, spender_info);
+        all_spender_limits.insert(spendable.spender, spender_info(&spendable, new_state));
     }
 
     Ok(Json(AllSpendersResponse {
@@ -272,6 +468,105 @@ pub async fn get_all_spender_limits<C: Locked + 'static>(
     }))
 }
 
+/// GET `/v5/channel/0xXXX.../spender/0xXXX.../history` request (auth required).
+///
+/// Only the spender and the validators of the Channel can see the history.
+///
+/// Request query parameters: [`SpendableHistoryQuery`]
+///
+/// Response: [`SpendableHistoryResponse`]
+pub async fn get_spender_history<C: Locked + 'static>(
+    Path(params): Path<(ChannelId, Address)>,
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(auth): Extension<Auth>,
+    Extension(channel_context): Extension<ChainOf<Channel>>,
+    Qs(query): Qs<SpendableHistoryQuery>,
+) -> Result<Json<SpendableHistoryResponse>, ResponseError> {
+    let channel = channel_context.context;
+    let spender = params.1;
+
+    if auth.uid.to_address() != spender && channel.find_validator(auth.uid).is_none() {
+        return Err(ResponseError::Unauthorized);
+    }
+
+    let limit = app.config.limits.spendable_find;
+    let skip = query
+        .page
+        .checked_mul(limit.into())
+        .ok_or_else(|| ResponseError::FailedValidation("Page and/or limit is too large".into()))?;
+
+    let (history, pagination) =
+        get_spendable_history(&app.pool, &channel.id(), &spender, skip, limit.into()).await?;
+
+    Ok(Json(SpendableHistoryResponse {
+        history,
+        pagination,
+    }))
+}
+
+/// POST `/v5/channel/0xXXX.../spender/batch` request.
+///
+/// Request body (json): [`SpendersBatchRequest`]
+///
+/// Response: [`SpendersBatchResponse`]
+pub async fn get_spenders_batch<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(channel_context): Extension<ChainOf<Channel>>,
+    Json(request): Json<SpendersBatchRequest>,
+) -> Result<Json<SpendersBatchResponse>, ResponseError> {
+    let channel = channel_context.context;
+
+    let limit = app.config.limits.spendable_find;
+    if request.spenders.len() > limit as usize {
+        return Err(ResponseError::FailedValidation(format!(
+            "No more than {} spenders can be requested at once",
+            limit
+        )));
+    }
+
+    let corresponding_states =
+        get_corresponding_states::<CheckedState>(&app.pool, &app.logger, &channel).await?;
+    let new_state = corresponding_states
+        .as_ref()
+        .map(|(_, new_state)| new_state);
+
+    let spendables =
+        fetch_spendables_for_spenders(app.pool.clone(), &channel.id(), &request.spenders).await?;
+
+    let spenders = spendables
+        .iter()
+        .map(|spendable| (spendable.spender, spender_info(spendable, new_state)))
+        .collect();
+
+    Ok(Json(SpendersBatchResponse { spenders }))
+}
+
+/// The [`Spender`] info of a [`Spendable`] based on the latest [`NewState`], if any.
+fn spender_info(
+    spendable: &Spendable,
+    new_state: Option<&MessageResponse<NewState<CheckedState>>>,
+) -> Spender {
+    let total_spent = new_state.and_then(|new_state| {
+        new_state
+            .msg
+            .balances
+            .spenders
+            .get(&spendable.spender)
+            .map(|balance| {
+                spendable
+                    .deposit
+                    .total
+                    .checked_sub(balance)
+                    .unwrap_or_default()
+            })
+    });
+
+    Spender {
+        total_deposited: spendable.deposit.total,
+        total_spent,
+    }
+}
+
 /// POST `/v5/channel/0xXXX.../spender/0xXXX...` request
 ///
 /// Internally to make the validator worker add a spender leaf in `NewState` we'll just update `Accounting`
@@ -291,16 +586,7 @@ pub async fn add_spender_leaf<C: Locked + 'static>(
     )
     .await?;
 
-    let latest_spendable =
-        fetch_spendable(app.pool.clone(), &spender, &channel.context.id()).await?;
-
-    let latest_spendable = match latest_spendable {
-        Some(spendable) => spendable,
-        None => {
-            create_or_update_spendable_document(&app.adapter, app.pool.clone(), &channel, spender)
-                .await?
-        }
-    };
+    let latest_spendable = fetch_or_create_spendable_document(&app, &channel, spender).await?;
 
     let (_, new_state) =
         match get_corresponding_states::<CheckedState>(&app.pool, &app.logger, &channel.context)
@@ -313,6 +599,7 @@ pub async fn add_spender_leaf<C: Locked + 'static>(
                         total_deposited: latest_spendable.deposit.total,
                         total_spent: None,
                     },
+                    deposit_breakdown: None,
                 }))
             }
         };
@@ -324,12 +611,22 @@ pub async fn add_spender_leaf<C: Locked + 'static>(
             total_deposited: latest_spendable.deposit.total,
             total_spent,
         },
+        deposit_breakdown: None,
     }))
 }
 
+/// How many previous approved pairs of states are checked by [`get_corresponding_states()`]
+/// when the last approved [`NewState`] has invalid balances.
+const INVALID_STATE_FALLBACK_LIMIT: u64 = 10;
+
 /// Retrieves the [`Channel`]'s latest [`ApproveState`] and the corresponding
 /// approved [`NewState`].
 ///
+/// If the balances of the approved [`NewState`] don't align, i.e. `sum(earner) != sum(spenders)`,
+/// the previous approved pair of states with valid balances is returned instead, if there is one.
+/// Nothing is written to the DB, the invalid [`NewState`] should be quarantined with
+/// [POST `/v5/channel/:id/quarantine`](crate::routes#post-v5channelidquarantine-auth-required).
+///
 /// # Errors
 ///
 /// - Returns [`ResponseError::NotFound`] if there is no [`ApproveState`]
@@ -338,9 +635,6 @@ pub async fn add_spender_leaf<C: Locked + 'static>(
 /// - Returns [`ResponseError::BadRequest`] if the [`ApproveState`]'s
 /// corresponding approve [`NewState`] is not found.
 ///
-/// - Returns [`ResponseError::BadRequest`] if the [`NewState`]'s
-/// [`Balances`] don't align, i.e. `sum(earner) != sum(spenders)`
-///
 /// [`ApproveState`]: primitives::validator::ApproveState
 async fn get_corresponding_states<S: BalancesState>(
     pool: &DbPool,
@@ -352,22 +646,76 @@ async fn get_corresponding_states<S: BalancesState>(
         None => return Ok(None),
     };
 
-    let state_root = approve_state.msg.state_root.clone();
-
-    match latest_new_state::<S>(pool, channel, &state_root).await? {
-        Some(new_state) => Ok(Some((approve_state, new_state))),
+    let new_state = match latest_new_state::<UncheckedState>(
+        pool,
+        channel,
+        &approve_state.msg.state_root,
+    )
+    .await?
+    {
+        Some(new_state) => new_state,
         None => {
             error!(&logger, "{}", "Fatal error! The NewState for the last ApproveState was not found"; "module" => "routes::channel");
 
-            Err(ResponseError::BadRequest(
+            return Err(ResponseError::BadRequest(
                 "Fatal error! The NewState for the last ApproveState was not found".to_string(),
-            ))
+            ));
         }
-    }
+    };
+
+    let err = match check_new_state::<S>(new_state) {
+        Ok(new_state) => return Ok(Some((approve_state, new_state))),
+        Err(err) => err,
+    };
+
+    error!(&logger, "The last approved NewState has invalid balances, falling back to the previous approved state"; "channel" => %channel.id(), "state_root" => &approve_state.msg.state_root, "error" => %err, "module" => "routes::channel");
+
+    let approved_states =
+        latest_approved_states::<UncheckedState>(pool, channel, INVALID_STATE_FALLBACK_LIMIT)
+            .await?;
+
+    Ok(approved_states.into_iter().find_map(|approved| {
+        check_new_state::<S>(approved.new_state)
+            .ok()
+            .map(|new_state| (approved.approve_state, new_state))
+    }))
+}
+
+/// Checks the balances of the [`NewState`], i.e. `sum(earner) == sum(spenders)`.
+fn check_new_state<S: BalancesState>(
+    new_state: MessageResponse<NewState<UncheckedState>>,
+) -> Result<MessageResponse<NewState<S>>, BalancesError> {
+    let MessageResponse {
+        from,
+        received,
+        msg,
+    } = new_state;
+    let NewState {
+        state_root,
+        signature,
+        balances,
+    } = msg.into_inner();
+
+    let balances = balances
+        .check()
+        .and_then(|balances| S::from_unchecked(balances.into_unchecked()))?;
+
+    Ok(MessageResponse {
+        from,
+        received,
+        msg: Message::new(NewState {
+            state_root,
+            signature,
+            balances,
+        }),
+    })
 }
 
 /// GET `/v5/channel/0xXXX.../accounting` request
 ///
+/// The earners sum and the spenders sum are allowed to differ by at most
+/// the configured [`SentryConfig::accounting_tolerance`](primitives::config::SentryConfig::accounting_tolerance).
+///
 /// Response: [`AccountingResponse::<CheckedState>`]
 pub async fn get_accounting_for_channel<C: Locked + 'static>(
     Extension(app): Extension<Arc<Application<C>>>,
@@ -390,8 +738,16 @@ pub async fn get_accounting_for_channel<C: Locked + 'static>(
         };
     }
 
-    let balances = match unchecked_balances.check() {
-        Ok(balances) => balances,
+    let balances = match unchecked_balances
+        .check_with_tolerance(app.config.sentry.accounting_tolerance)
+    {
+        Ok((balances, residue)) => {
+            if residue > UnifiedNum::default() {
+                warn!(&app.logger, "Accounting tolerance applied for channel {:?}, residue: {}", channel.id(), residue; "module" => "channel_accounting");
+            }
+
+            balances
+        }
         Err(error) => {
             error!(&app.logger, "{}", &error; "module" => "channel_accounting");
             return Err(ResponseError::FailedValidation(
@@ -400,1051 +756,3541 @@ pub async fn get_accounting_for_channel<C: Locked + 'static>(
         }
     };
 
-    Ok(Json(AccountingResponse::<CheckedState> { balances }))
+    let frozen = is_accounting_frozen(&app.pool, channel.id()).await?;
+
+    Ok(Json(AccountingResponse::<CheckedState> {
+        balances,
+        frozen,
+    }))
 }
 
-/// POST `/v5/channel/0xXXX.../pay` request
+/// GET `/v5/channel/0xXXX.../accounting/page` request
 ///
-/// Body: [`ChannelPayRequest`]
+/// Returns a single page of the Channel accounting of at most
+/// [`Config.limits.accounting_find`](primitives::config::Limits::accounting_find) earners & spenders.
+/// Since only a part of the accounting is returned, the balances are **not** checked.
+///
+/// Query: [`AccountingPageQuery`]
+///
+/// Response: [`AccountingPageResponse`]
+pub async fn get_accounting_page_for_channel<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(channel_context): Extension<ChainOf<Channel>>,
+    Qs(query): Qs<AccountingPageQuery>,
+) -> Result<Json<AccountingPageResponse>, ResponseError> {
+    let channel = channel_context.context;
+
+    let limit = app.config.limits.accounting_find;
+    let skip = query
+        .page
+        .checked_mul(limit.into())
+        .ok_or_else(|| ResponseError::FailedValidation("Page and/or limit is too large".into()))?;
+
+    let (accountings, pagination) =
+        get_accountings_for_channel_page(app.pool.clone(), channel.id(), skip, limit.into())
+            .await?;
+
+    let mut balances: Balances<UncheckedState> = Balances::default();
+
+    for accounting in accountings {
+        match accounting.side {
+            Side::Earner => balances
+                .earners
+                .insert(accounting.address, accounting.amount),
+            Side::Spender => balances
+                .spenders
+                .insert(accounting.address, accounting.amount),
+        };
+    }
+
+    let frozen = is_accounting_frozen(&app.pool, channel.id()).await?;
+
+    Ok(Json(AccountingPageResponse {
+        balances,
+        frozen,
+        pagination,
+    }))
+}
+
+/// POST `/v5/channel/0xXXX.../accounting-freeze` request
+///
+/// Freezes or unfreezes the accounting of the Channel.
+/// Only the Channel guardian or a sentry admin can change it.
+///
+/// While frozen, any spending for the Channel (events and payouts) is rejected
+/// and the Leader does not produce new `NewState`s.
+///
+/// Request body (json): [`AccountingFreezeRequest`]
 ///
 /// Response: [`SuccessResponse`]
-pub async fn channel_payout<C: Locked + 'static>(
+pub async fn channel_accounting_freeze<C: Locked + 'static>(
     Extension(app): Extension<Arc<Application<C>>>,
     Extension(channel_context): Extension<ChainOf<Channel>>,
     Extension(auth): Extension<Auth>,
-    Json(to_pay): Json<ChannelPayRequest>,
+    Json(request): Json<AccountingFreezeRequest>,
 ) -> Result<Json<SuccessResponse>, ResponseError> {
-    let spender = auth.uid.to_address();
+    let channel = channel_context.context;
 
-    // Handling the case where a request with an empty body comes through
-    if to_pay.payouts.is_empty() {
-        return Err(ResponseError::FailedValidation(
-            "Request has empty payouts".to_string(),
+    let is_guardian = channel.guardian == auth.uid.to_address();
+    if !is_guardian && !app.config.sentry.admins.contains(auth.uid.as_address()) {
+        return Err(ResponseError::Forbidden(
+            "Channel accounting can be frozen only by its guardian or an admin".to_string(),
         ));
     }
 
-    let channel_campaigns = fetch_campaign_ids_for_channel(
-        &app.pool,
-        channel_context.context.id(),
-        app.config.limits.campaigns_find,
-    )
-    .await?;
+    if !set_accounting_frozen(&app.pool, channel.id(), request.frozen).await? {
+        return Err(ResponseError::NotFound);
+    }
 
-    let campaigns_remaining_sum = app
-        .campaign_remaining
-        .get_multiple(&channel_campaigns)
-        .await?
-        .iter()
-        .sum::<Option<UnifiedNum>>()
-        .ok_or_else(|| {
-            ResponseError::BadRequest("Couldn't sum remaining amount for all campaigns".to_string())
-        })?;
+    info!(&app.logger, "Channel accounting freeze changed"; "channel" => %channel.id(), "frozen" => request.frozen, "by" => %auth.uid);
 
-    // A campaign is closed when its remaining == 0
-    // therefore for all campaigns for a channel to be closed their total remaining sum should be 0
-    if campaigns_remaining_sum > UnifiedNum::from_u64(0) {
-        return Err(ResponseError::FailedValidation(
-            "All campaigns should be closed or have no budget left".to_string(),
-        ));
-    }
+    Ok(Json(SuccessResponse { success: true }))
+}
 
-    let accounting_spent = get_accounting(
-        app.pool.clone(),
-        channel_context.context.id(),
-        spender,
-        Side::Spender,
-    )
-    .await?
-    .map(|accounting_spent| accounting_spent.amount)
-    .unwrap_or_default();
-    let accounting_earned = get_accounting(
-        app.pool.clone(),
-        channel_context.context.id(),
-        spender,
-        Side::Earner,
-    )
-    .await?
-    .map(|accounting_spent| accounting_spent.amount)
-    .unwrap_or_default();
-    let latest_spendable =
-        fetch_spendable(app.pool.clone(), &spender, &channel_context.context.id())
-            .await
-            .map_err(|err| ResponseError::BadRequest(err.to_string()))?
-            .ok_or_else(|| {
-                ResponseError::BadRequest(
-                    "There is no spendable amount for the spender in this Channel".to_string(),
-                )
-            })?;
-    let total_deposited = latest_spendable.deposit.total;
+/// GET `/v5/channel/0xXXX.../quarantined-messages` request
+///
+/// Lists the latest quarantined validator messages of the Channel, only sentry admins can request it.
+/// At most [`Config.limits.msgs_find`](primitives::config::Limits::msgs_find) messages are returned.
+///
+/// Response: [`QuarantinedMessagesResponse`]
+pub async fn list_quarantined_messages<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(channel_context): Extension<ChainOf<Channel>>,
+) -> Result<Json<QuarantinedMessagesResponse>, ResponseError> {
+    let channel = channel_context.context;
 
-    let available_for_payout = total_deposited
-        .checked_add(&accounting_earned)
-        .ok_or_else(|| {
-            ResponseError::FailedValidation(
-                "Overflow while calculating available for payout".to_string(),
-            )
-        })?
-        .checked_sub(&accounting_spent)
-        .ok_or_else(|| {
-            ResponseError::FailedValidation(
-                "Underflow while calculating available for payout".to_string(),
-            )
-        })?;
+    let messages =
+        get_quarantined_messages(&app.pool, &channel.id(), app.config.limits.msgs_find.into())
+            .await?;
 
-    let total_to_pay = to_pay
-        .payouts
-        .values()
-        .sum::<Option<UnifiedNum>>()
-        .ok_or_else(|| ResponseError::FailedValidation("Payouts amount overflow".to_string()))?;
+    Ok(Json(QuarantinedMessagesResponse { messages }))
+}
 
-    if total_to_pay > available_for_payout {
-        return Err(ResponseError::FailedValidation(
-            "The total requested payout amount exceeds the available payout".to_string(),
-        ));
-    }
+/// POST `/v5/channel/0xXXX.../quarantined-messages/:message_id/release` request
+///
+/// Releases a quarantined validator message of the Channel, only sentry admins can request it.
+/// Responds with `404 Not Found` if the Channel has no quarantined message with this id.
+///
+/// Response: [`SuccessResponse`]
+pub async fn release_quarantined<C: Locked + 'static>(
+    Path((_channel_id, message_id)): Path<(ChannelId, i64)>,
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(channel_context): Extension<ChainOf<Channel>>,
+    Extension(auth): Extension<Auth>,
+) -> Result<Json<SuccessResponse>, ResponseError> {
+    let channel = channel_context.context;
 
-    let mut balances: Balances<CheckedState> = Balances::new();
-    for (earner, amount) in to_pay.payouts.into_iter() {
-        balances.spend(spender, earner, amount)?;
+    if !release_quarantined_message(&app.pool, &channel.id(), message_id).await? {
+        return Err(ResponseError::NotFound);
     }
 
-    // will return an error if one of the updates fails
-    spend_amount(app.pool.clone(), channel_context.context.id(), balances).await?;
+    info!(&app.logger, "Quarantined validator message released"; "channel" => %channel.id(), "message_id" => message_id, "by" => %auth.uid);
 
     Ok(Json(SuccessResponse { success: true }))
 }
 
-/// GET `/v5/channel/0xXXX.../get-leaf` requests
+/// POST `/v5/channel/0xXXX.../quarantine` request
 ///
-/// # Routes:
+/// Quarantines the leader's [`NewState`] with the given `stateRoot` and the follower's
+/// [`ApproveState`]s for it, see [`quarantine_state()`], only the Channel validators can request it.
 ///
-/// - GET `/v5/channel/:id/get-leaf/spender/:addr`
-/// - GET `/v5/channel/:id/get-leaf/earner/:addr`
+/// Responds with `400 Bad Request` if there is no such [`NewState`] or its balances align.
 ///
-/// Response: [`GetLeafResponse`]
-pub async fn get_leaf<C: Locked + 'static>(
+/// Request body (json): [`QuarantineStateRequest`]
+///
+/// Response: [`SuccessResponse`]
+pub async fn quarantine_channel_state<C: Locked + 'static>(
     Extension(app): Extension<Arc<Application<C>>>,
     Extension(channel_context): Extension<ChainOf<Channel>>,
-    Extension(leaf_for): Extension<LeafFor>,
-    Path(params): Path<(ChannelId, Address)>,
-) -> Result<Json<GetLeafResponse>, ResponseError> {
+    Extension(auth): Extension<Auth>,
+    Json(request): Json<QuarantineStateRequest>,
+) -> Result<Json<SuccessResponse>, ResponseError> {
     let channel = channel_context.context;
 
-    let (_, new_state) = get_corresponding_states::<CheckedState>(&app.pool, &app.logger, &channel)
+    if channel.find_validator(auth.uid).is_none() {
+        return Err(ResponseError::Unauthorized);
+    }
+
+    let new_state = latest_new_state::<UncheckedState>(&app.pool, &channel, &request.state_root)
         .await?
-        .ok_or(ResponseError::NotFound)?;
+        .ok_or_else(|| ResponseError::BadRequest("NewState not found".to_string()))?;
 
-    let addr = params.1;
+    if check_new_state::<UncheckedState>(new_state).is_ok() {
+        return Err(ResponseError::BadRequest(
+            "NewState balances are valid".to_string(),
+        ));
+    }
 
-    let element = match leaf_for {
-        LeafFor::Spender => {
-            let amount = new_state
-                .msg
-                .balances
-                .spenders
-                .get(&addr)
-                .ok_or(ResponseError::NotFound)?;
+    let quarantined = quarantine_state(&app.pool, &channel, &request.state_root).await?;
 
-            get_balance_leaf(
-                true,
-                &addr,
-                &amount.to_precision(channel_context.token.precision.get()),
-            )?
-        }
-        LeafFor::Earner => {
-            let amount = new_state
-                .msg
-                .balances
+    error!(&app.logger, "Quarantined the NewState because of invalid balances"; "channel" => %channel.id(), "state_root" => &request.state_root, "quarantined_messages" => quarantined, "by" => %auth.uid, "module" => "routes::channel");
+
+    Ok(Json(SuccessResponse { success: true }))
+}
+
+/// GET `/v5/channel/0xXXX.../snapshot` request
+///
+/// Only the Channel validators, its guardian or a sentry admin can request it.
+///
+/// The accounting, spendables and Campaign IDs are fetched in a single DB transaction,
+/// the remaining budgets of the Campaigns are then fetched from Redis,
+/// followed by the last approved [`NewState`] & [`ApproveState`].
+///
+/// The [`ChannelSnapshot::message()`] is signed with the [`Application::whoami_signer`],
+/// responds with `404 Not Found` if it's not set.
+///
+/// Response: [`ChannelSnapshotResponse`]
+pub async fn channel_snapshot<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(channel_context): Extension<ChainOf<Channel>>,
+    Extension(auth): Extension<Auth>,
+) -> Result<Json<ChannelSnapshotResponse>, ResponseError> {
+    let channel = channel_context.context;
+
+    let is_validator = channel.leader == auth.uid || channel.follower == auth.uid;
+    let is_guardian = channel.guardian == auth.uid.to_address();
+    if !is_validator && !is_guardian && !app.config.sentry.admins.contains(auth.uid.as_address()) {
+        return Err(ResponseError::Forbidden(
+            "Channel snapshot can be requested only by its validators, guardian or an admin"
+                .to_string(),
+        ));
+    }
+
+    let signer = app.whoami_signer.as_ref().ok_or(ResponseError::NotFound)?;
+
+    let records = fetch_channel_accounting_records(&app.pool, channel.id()).await?;
+
+    let mut balances: Balances<UncheckedState> = Balances::default();
+    for accounting in records.accountings {
+        match accounting.side {
+            Side::Earner => balances
                 .earners
-                .get(&addr)
-                .ok_or(ResponseError::NotFound)?;
+                .insert(accounting.address, accounting.amount),
+            Side::Spender => balances
+                .spenders
+                .insert(accounting.address, accounting.amount),
+        };
+    }
 
-            get_balance_leaf(
-                false,
-                &addr,
-                &amount.to_precision(channel_context.token.precision.get()),
-            )?
-        }
+    let spendables = records
+        .spendables
+        .into_iter()
+        .map(|spendable| (spendable.spender, spendable.deposit.total))
+        .collect();
+
+    let campaigns_remaining = app
+        .campaign_remaining
+        .get_multiple_with_ids(&records.campaigns)
+        .await?;
+
+    let last_approved =
+        get_corresponding_states::<UncheckedState>(&app.pool, &app.logger, &channel)
+            .await?
+            .map(|(approve_state, new_state)| LastApproved {
+                new_state: Some(new_state),
+                approve_state: Some(approve_state),
+            });
+
+    let state_root = match balances
+        .clone()
+        .check_with_tolerance(app.config.sentry.accounting_tolerance)
+    {
+        Ok((checked, _residue)) => balances_state_root(&channel_context, &checked)?,
+        Err(_) => None,
     };
-    let merkle_tree = MerkleTree::new(&[element])?;
 
-    let signable_state_root = get_signable_state_root(channel.id().as_bytes(), &merkle_tree.root());
+    let snapshot = ChannelSnapshot {
+        channel,
+        created: records.created,
+        balances,
+        spendables,
+        campaigns_remaining,
+        last_approved,
+        state_root,
+    };
+    let signature = signer.sign(&snapshot.message()?)?;
 
-    let merkle_proof = hex::encode(signable_state_root);
+    Ok(Json(ChannelSnapshotResponse {
+        snapshot,
+        signer: signer.whoami(),
+        signature,
+    }))
+}
 
-    Ok(Json(GetLeafResponse { merkle_proof }))
+/// The state root of the `balances`, the same as the one of a [`NewState`] with them.
+///
+/// Returns `None` if there are no balances.
+fn balances_state_root(
+    channel_context: &ChainOf<Channel>,
+    balances: &Balances<CheckedState>,
+) -> Result<Option<String>, ResponseError> {
+    let precision = channel_context.token.precision.get();
+
+    let earners = balances
+        .earners
+        .iter()
+        .map(|(address, amount)| get_balance_leaf(false, address, &amount.to_precision(precision)));
+    let spenders = balances
+        .spenders
+        .iter()
+        .map(|(address, amount)| get_balance_leaf(true, address, &amount.to_precision(precision)));
+    let leaves = earners.chain(spenders).collect::<Result<Vec<_>, _>>()?;
+
+    if leaves.is_empty() {
+        return Ok(None);
+    }
+
+    let merkle_tree = MerkleTree::new(&leaves)?;
+    let state_root =
+        get_signable_state_root(channel_context.context.id().as_bytes(), &merkle_tree.root());
+
+    Ok(Some(hex::encode(state_root)))
 }
 
-/// POST `/v5/channel/dummy-deposit` request
+/// The header which makes a POST `/v5/channel/0xXXX.../pay` request idempotent.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// For how long the applied payout of an [`IDEMPOTENCY_KEY_HEADER`] is kept in Redis.
+const PAYOUT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The value of the idempotency key while its payout is being applied.
+const PAYOUT_IDEMPOTENCY_PENDING: &str = "pending";
+
+/// POST `/v5/channel/0xXXX.../pay` request
 ///
-/// Full details about the route's API and intend can be found in the [`routes`](crate::routes#post-v5channeldummy-deposit-auth-required) module
+/// Payouts with a zero amount, below the Token's minimum payout or to the spender
+/// are rejected with a [`ResponseError::FailedValidation`].
 ///
-/// Request body (json): [`ChannelDummyDeposit`]
+/// If an [`IDEMPOTENCY_KEY_HEADER`] is passed, the key is claimed in Redis before the payout
+/// and repeating the same payout request with this key, by the same spender, is a no-op.
+/// Re-using the key for a different payout, or while its payout is still being applied,
+/// results in a [`ResponseError::Conflict`].
+///
+/// Body: [`ChannelPayRequest`]
 ///
 /// Response: [`SuccessResponse`]
-pub async fn channel_dummy_deposit<C: Locked + 'static>(
+pub async fn channel_payout<C: Locked + 'static>(
     Extension(app): Extension<Arc<Application<C>>>,
+    Extension(channel_context): Extension<ChainOf<Channel>>,
     Extension(auth): Extension<Auth>,
-    Json(request): Json<ChannelDummyDeposit>,
+    headers: HeaderMap,
+    Json(to_pay): Json<ChannelPayRequest>,
 ) -> Result<Json<SuccessResponse>, ResponseError> {
-    let depositor = auth.uid.to_address();
+    let spender = auth.uid.to_address();
 
-    // Insert the channel if it does not exist yet
-    let channel_chain = app
-        .config
-        .find_chain_of(request.channel.token)
-        .expect("The Chain of Channel's token was not found in configuration!")
-        .with_channel(request.channel);
+    let balances = validate_payouts(&channel_context, spender, &to_pay)?;
+    let idempotency_key = payout_idempotency_key(&app, &headers, &channel_context, spender)?;
 
-    // if this fails, it will cause Bad Request
-    insert_channel(&app.pool, &channel_chain).await?;
+    // the payout has already been applied
+    if let Some(applied) = claim_payout(&app, idempotency_key.as_deref()).await? {
+        return replayed_payout(&applied, &balances);
+    }
 
-    // Convert the UnifiedNum to BigNum with the precision of the token
-    let deposit = request
-        .deposit
-        .to_precision(channel_chain.token.precision.into());
+    let result = async {
+        let channel_campaigns = fetch_campaign_ids_for_channel(
+            &app.pool,
+            channel_context.context.id(),
+            app.config.limits.campaigns_find,
+        )
+        .await?;
 
-    let dummy_adapter = <dyn Any + Send + Sync>::downcast_ref::<Adapter<Dummy>>(&app.adapter)
-        .expect("Only Dummy adapter is allowed here!");
+        // A campaign is closed when its remaining == 0
+        // therefore for all campaigns for a channel to be closed their total remaining sum should be 0
+        ensure_campaigns_closed(&app, &channel_campaigns).await?;
 
-    // set the deposit in the Dummy adapter's client
-    dummy_adapter
-        .client
-        .set_deposit(&channel_chain, depositor, deposit);
+        ensure_available_for_payout(&app, &channel_context, spender, &to_pay).await?;
 
-    Ok(Json(SuccessResponse { success: true }))
+        apply_payout(&app, &channel_context, balances, idempotency_key.as_deref()).await
+    }
+    .await;
+
+    if result.is_err() {
+        release_payout(&app, idempotency_key.as_deref()).await;
+    }
+
+    result
 }
 
-/// [`Channel`] [validator messages](primitives::validator::MessageTypes) routes
-/// starting with `/v5/channel/0xXXX.../validator-messages`
+/// POST `/v5/channel/0xXXX.../pay/preview` request
 ///
-pub mod validator_message {
-    use std::sync::Arc;
+/// Makes the same checks as [`channel_payout()`] - the payouts validation, the frozen accounting,
+/// the closed Campaigns of the Channel and the available payout of the spender -
+/// without applying the payout.
+///
+/// The failed checks are returned in the response instead of an error response,
+/// only the errors which are not caused by the payout itself (e.g. database errors) are returned as such.
+///
+/// Body: [`ChannelPayRequest`]
+///
+/// Response: [`ChannelPayPreviewResponse`]
+pub async fn channel_payout_preview<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(channel_context): Extension<ChainOf<Channel>>,
+    Extension(auth): Extension<Auth>,
+    Json(to_pay): Json<ChannelPayRequest>,
+) -> Result<Json<ChannelPayPreviewResponse>, ResponseError> {
+    let spender = auth.uid.to_address();
+    let channel_id = channel_context.context.id();
 
-    use axum::{extract::Path, Extension, Json};
-    use futures::future::try_join_all;
-    use serde::Deserialize;
+    let mut errors = vec![];
 
-    use adapter::client::Locked;
-    use primitives::{
-        sentry::{
-            validator_messages::{
-                MessageTypesFilter, ValidatorMessagesCreateRequest, ValidatorMessagesListQuery,
-                ValidatorMessagesListResponse,
-            },
-            SuccessResponse,
-        },
-        ChainOf, Channel, ChannelId, ValidatorId,
+    if to_pay.payouts.is_empty() {
+        errors.push("Request has empty payouts".to_string());
+    }
+
+    let payouts = to_pay
+        .payouts
+        .iter()
+        .map(|(earner, amount)| PayoutPreview {
+            earner: *earner,
+            amount: *amount,
+            error: validate_payout(&channel_context, spender, *earner, *amount).err(),
+        })
+        .collect::<Vec<_>>();
+
+    if is_accounting_frozen(&app.pool, channel_id).await? {
+        errors.push(AccountingError::ChannelFrozen(channel_id).to_string());
+    }
+
+    let channel_campaigns =
+        fetch_campaign_ids_for_channel(&app.pool, channel_id, app.config.limits.campaigns_find)
+            .await?;
+    errors.extend(validation_error(
+        ensure_campaigns_closed(&app, &channel_campaigns).await,
+    )?);
+
+    let total_to_pay = match total_to_pay(&to_pay) {
+        Ok(total) => Some(total),
+        Err(err) => {
+            errors.extend(validation_error(Err(err))?);
+            None
+        }
     };
 
-    use crate::{
-        application::Qs,
-        db::validator_message::{get_validator_messages, insert_validator_message},
-        response::ResponseError,
-        Application, Auth,
+    let available_for_payout = match available_for_payout(&app, &channel_context, spender).await {
+        Ok(Some(available)) => Some(available),
+        Ok(None) => {
+            errors.push("There is no spendable amount for the spender in this Channel".to_string());
+            None
+        }
+        Err(err) => {
+            errors.extend(validation_error(Err(err))?);
+            None
+        }
     };
 
-    #[derive(Debug, Deserialize)]
-    pub struct MessagesListParams {
-        pub id: ChannelId,
-        // Optional filtering by [`ValidatorId`].
-        #[serde(default)]
-        pub address: Option<ValidatorId>,
-        /// Optional filtering by a list of [`MessageType`](primitives::validator::messages::MessageType)s
-        /// separated by `+` (url encoded).
-        ///
-        /// See [`MessageTypesFilter`] for more details.
-        #[serde(default)]
-        pub message_types: MessageTypesFilter,
+    if let (Some(total_to_pay), Some(available_for_payout)) = (total_to_pay, available_for_payout) {
+        errors.extend(validation_error(ensure_within_available(
+            total_to_pay,
+            available_for_payout,
+        ))?);
     }
 
-    /// GET `/v5/channel/0xXXX.../validator-messages`
-    ///
-    /// Full details about the route's API and intend can be found in the [`routes`](crate::routes#get-v5channelidvalidator-messages) module
-    ///
-    /// Request query parameters: [`ValidatorMessagesListQuery`]
-    ///
-    /// Response: [`ValidatorMessagesListResponse`]
-    ///
-    pub async fn list_validator_messages<C: Locked + 'static>(
-        Extension(app): Extension<Arc<Application<C>>>,
-        Extension(channel_context): Extension<ChainOf<Channel>>,
-        Path(params): Path<MessagesListParams>,
-        Qs(query): Qs<ValidatorMessagesListQuery>,
-    ) -> Result<Json<ValidatorMessagesListResponse>, ResponseError> {
-        let channel = channel_context.context;
+    let valid = errors.is_empty() && payouts.iter().all(|payout| payout.error.is_none());
 
-        let config_limit = app.config.limits.msgs_find as u64;
-        let limit = query
-            .limit
-            .filter(|n| *n >= 1)
-            .unwrap_or(config_limit)
-            .min(config_limit);
+    Ok(Json(ChannelPayPreviewResponse {
+        valid,
+        available_for_payout,
+        total_to_pay,
+        payouts,
+        errors,
+    }))
+}
 
-        let validator_messages = get_validator_messages(
-            &app.pool,
-            &channel.id(),
-            &params.address,
-            params.message_types.as_ref(),
-            limit,
-        )
-        .await?;
+/// Returns the message of a [`ResponseError::FailedValidation`],
+/// any other error is not caused by the payout and it's returned as it is.
+fn validation_error(result: Result<(), ResponseError>) -> Result<Option<String>, ResponseError> {
+    match result {
+        Ok(()) => Ok(None),
+        Err(ResponseError::FailedValidation(message)) => Ok(Some(message)),
+        Err(err) => Err(err),
+    }
+}
 
-        Ok(Json(ValidatorMessagesListResponse {
-            messages: validator_messages,
-        }))
+/// POST `/v5/channel/0xXXX.../close-and-pay` request
+///
+/// Closes all the Campaigns of the spender in the Channel, the same way as
+/// [`close_campaign()`](crate::routes::campaign::close_campaign), and performs the payout
+/// in a single request, see [`channel_payout()`].
+///
+/// All the payout checks are made before any Campaign is closed, so if the payout
+/// is invalid (e.g. it exceeds the available payout or the Campaigns of other creators
+/// in the Channel are not closed yet) none of the spender's Campaigns is closed.
+/// If the payout still fails (e.g. the Channel accounting has been frozen in the meantime),
+/// the closed Campaigns are re-opened with their remaining budget.
+///
+/// The [`IDEMPOTENCY_KEY_HEADER`] is supported as well.
+///
+/// Body: [`ChannelPayRequest`]
+///
+/// Response: [`SuccessResponse`]
+pub async fn channel_close_and_pay<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(channel_context): Extension<ChainOf<Channel>>,
+    Extension(auth): Extension<Auth>,
+    headers: HeaderMap,
+    Json(to_pay): Json<ChannelPayRequest>,
+) -> Result<Json<SuccessResponse>, ResponseError> {
+    let spender = auth.uid.to_address();
+
+    let balances = validate_payouts(&channel_context, spender, &to_pay)?;
+    let idempotency_key = payout_idempotency_key(&app, &headers, &channel_context, spender)?;
+
+    // the payout has already been applied
+    if let Some(applied) = claim_payout(&app, idempotency_key.as_deref()).await? {
+        return replayed_payout(&applied, &balances);
     }
 
-    /// POST `/v5/channel/0xXXX.../validator-messages`
-    ///
-    /// Full details about the route's API and intend can be found in the [`routes`](crate::routes#post-v5channelidvalidator-messages-auth-required) module
-    ///
-    /// Request body (json): [`ValidatorMessagesCreateRequest`]
-    ///
-    /// Response: [`SuccessResponse`]
-    ///
-    /// # Examples
-    ///
-    /// Request:
-    ///
-    /// ```
-    #[doc = include_str!("../../../primitives/examples/validator_messages_create_request.rs")]
-    /// ```
-    pub async fn create_validator_messages<C: Locked + 'static>(
-        Extension(app): Extension<Arc<Application<C>>>,
-        Extension(auth): Extension<Auth>,
-        Extension(channel_context): Extension<ChainOf<Channel>>,
-        Json(create_request): Json<ValidatorMessagesCreateRequest>,
-    ) -> Result<Json<SuccessResponse>, ResponseError> {
-        let channel = channel_context.context;
+    let result = close_and_pay(
+        &app,
+        &channel_context,
+        spender,
+        &to_pay,
+        balances,
+        idempotency_key.as_deref(),
+    )
+    .await;
 
-        match channel.find_validator(auth.uid) {
-            None => Err(ResponseError::Unauthorized),
-            _ => {
-                try_join_all(create_request.messages.iter().map(|message| {
-                    insert_validator_message(&app.pool, &channel, &auth.uid, message)
-                }))
-                .await?;
+    if result.is_err() {
+        release_payout(&app, idempotency_key.as_deref()).await;
+    }
 
-                Ok(Json(SuccessResponse { success: true }))
+    result
+}
+
+/// Closes the Campaigns of the `spender` and applies the payout,
+/// see [`channel_close_and_pay()`].
+async fn close_and_pay<C: Locked + 'static>(
+    app: &Arc<Application<C>>,
+    channel_context: &ChainOf<Channel>,
+    spender: Address,
+    to_pay: &ChannelPayRequest,
+    balances: Balances<CheckedState>,
+    idempotency_key: Option<&str>,
+) -> Result<Json<SuccessResponse>, ResponseError> {
+    let spender_campaigns =
+        get_campaigns_by_channel_and_creator(&app.pool, &channel_context.context.id(), spender)
+            .await?;
+    let other_campaigns = fetch_campaign_ids_for_channel(
+        &app.pool,
+        channel_context.context.id(),
+        app.config.limits.campaigns_find,
+    )
+    .await?
+    .into_iter()
+    .filter(|campaign_id| {
+        !spender_campaigns
+            .iter()
+            .any(|campaign| &campaign.id == campaign_id)
+    })
+    .collect::<Vec<_>>();
+
+    // the spender's campaigns are about to be closed,
+    // all the other campaigns of the channel should already be closed
+    ensure_campaigns_closed(app, &other_campaigns).await?;
+
+    ensure_available_for_payout(app, channel_context, spender, to_pay).await?;
+
+    let spender_remaining = app
+        .campaign_remaining
+        .get_multiple(
+            &spender_campaigns
+                .iter()
+                .map(|campaign| campaign.id)
+                .collect::<Vec<_>>(),
+        )
+        .await?
+        .ensure_found()?;
+
+    let mut closed = vec![];
+    let mut close_result = Ok(());
+    for campaign in spender_campaigns {
+        let is_open = spender_remaining
+            .found
+            .iter()
+            .any(|(campaign_id, remaining)| {
+                *campaign_id == campaign.id && *remaining != UnifiedNum::ZERO
+            });
+
+        if is_open {
+            match set_remaining_to_zero(app, campaign, spender).await {
+                Ok(closed_campaign) => closed.push(closed_campaign),
+                Err(err) => {
+                    close_result = Err(err);
+                    break;
+                }
             }
         }
     }
 
-    #[cfg(test)]
-    mod test {
-        use super::*;
-        use crate::{
-            db::{insert_channel, validator_message::get_validator_messages},
-            test_util::setup_dummy_app,
-        };
+    let result = match close_result {
+        Ok(()) => apply_payout(app, channel_context, balances, idempotency_key).await,
+        Err(err) => Err(err),
+    };
 
-        use adapter::prelude::Unlocked;
-        use chrono::Utc;
+    if result.is_ok() {
+        for (campaign, _remaining) in closed {
+            notify_campaign_closed(app.clone(), channel_context.clone().with(campaign));
+        }
 
-        use primitives::{
-            balances::UncheckedState,
-            sentry::validator_messages::ValidatorMessagesCreateRequest,
-            test_util::{ADVERTISER, CAMPAIGNS, IDS, LEADER},
-            validator::{
-                ApproveState, Heartbeat, MessageType, MessageTypes, NewState, RejectState,
-            },
-            Balances,
+        return result;
+    }
+
+    // nothing has been paid, re-open the Campaigns which have been closed
+    for (campaign, remaining) in closed {
+        let campaign_id = campaign.id;
+        if let Err(err) = restore_remaining(app, campaign, remaining, spender).await {
+            error!(&app.logger, "Failed to re-open Campaign {} after a failed close-and-pay", campaign_id; "error" => ?err, "module" => "routes::channel");
+        }
+    }
+
+    result
+}
+
+/// Validates the requested payouts of the spender and
+/// returns the [`Balances`] which should be spent for them.
+///
+/// The duplicate earners are already rejected when deserializing the [`ChannelPayRequest`].
+fn validate_payouts(
+    channel_context: &ChainOf<Channel>,
+    spender: Address,
+    to_pay: &ChannelPayRequest,
+) -> Result<Balances<CheckedState>, ResponseError> {
+    // Handling the case where a request with an empty body comes through
+    if to_pay.payouts.is_empty() {
+        return Err(ResponseError::FailedValidation(
+            "Request has empty payouts".to_string(),
+        ));
+    }
+
+    for (earner, amount) in to_pay.payouts.iter() {
+        validate_payout(channel_context, spender, *earner, *amount)
+            .map_err(ResponseError::FailedValidation)?;
+    }
+
+    let mut balances: Balances<CheckedState> = Balances::new();
+    for (earner, amount) in to_pay.payouts.iter() {
+        balances.spend(spender, *earner, *amount)?;
+    }
+
+    Ok(balances)
+}
+
+/// Validates a single payout of the spender to the `earner`,
+/// returns the reason if the payout is invalid.
+fn validate_payout(
+    channel_context: &ChainOf<Channel>,
+    spender: Address,
+    earner: Address,
+    amount: UnifiedNum,
+) -> Result<(), String> {
+    if amount == UnifiedNum::ZERO {
+        return Err(format!("Payout to earner {} has a zero amount", earner));
+    }
+
+    if earner == spender {
+        return Err("Spender cannot pay out to themselves".to_string());
+    }
+
+    if let Some(min_payout) = &channel_context.token.min_payout {
+        let precision = channel_context.token.precision.get();
+
+        if &amount.to_precision(precision) < min_payout {
+            return Err(format!(
+                "Payout to earner {} is below the minimum payout",
+                earner
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// The Redis key of the payout for the [`IDEMPOTENCY_KEY_HEADER`], if it's passed.
+fn payout_idempotency_key<C: Locked + 'static>(
+    app: &Application<C>,
+    headers: &HeaderMap,
+    channel_context: &ChainOf<Channel>,
+    spender: Address,
+) -> Result<Option<String>, ResponseError> {
+    headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .map(|key| {
+            key.to_str()
+                .map(|key| {
+                    app.redis_keys
+                        .payout_idempotency(channel_context.context.id(), spender, key)
+                })
+                .map_err(|_| {
+                    ResponseError::BadRequest("Invalid Idempotency-Key header".to_string())
+                })
+        })
+        .transpose()
+}
+
+/// Atomically claims the idempotency key, if any, before applying its payout.
+///
+/// Returns the payout which has already been applied with the key, if any.
+/// A key which is claimed by a payout that is still being applied
+/// results in a [`ResponseError::Conflict`].
+async fn claim_payout<C: Locked + 'static>(
+    app: &Application<C>,
+    idempotency_key: Option<&str>,
+) -> Result<Option<String>, ResponseError> {
+    let key = match idempotency_key {
+        Some(key) => key,
+        None => return Ok(None),
+    };
+    let mut redis = app.redis.clone();
+
+    let claimed = redis::cmd("SET")
+        .arg(key)
+        .arg(PAYOUT_IDEMPOTENCY_PENDING)
+        .arg("NX")
+        .arg("EX")
+        .arg(PAYOUT_IDEMPOTENCY_TTL.as_secs())
+        .query_async::<_, Option<String>>(&mut redis)
+        .await?
+        .is_some();
+
+    if claimed {
+        return Ok(None);
+    }
+
+    match redis::cmd("GET")
+        .arg(key)
+        .query_async::<_, Option<String>>(&mut redis)
+        .await?
+    {
+        Some(applied) if applied != PAYOUT_IDEMPOTENCY_PENDING => Ok(Some(applied)),
+        // the payout is still in progress or its claim has just been released
+        _ => Err(ResponseError::Conflict(
+            "A payout with this Idempotency-Key is already in progress".to_string(),
+        )),
+    }
+}
+
+/// Releases the claimed idempotency key, if any, of a payout which has not been applied,
+/// so it can be retried with the same key.
+async fn release_payout<C: Locked + 'static>(app: &Application<C>, idempotency_key: Option<&str>) {
+    let key = match idempotency_key {
+        Some(key) => key,
+        None => return,
+    };
+
+    if let Err(err) = redis::cmd("DEL")
+        .arg(key)
+        .query_async::<_, ()>(&mut app.redis.clone())
+        .await
+    {
+        error!(&app.logger, "Failed to release the Idempotency-Key of a failed payout"; "error" => ?err, "module" => "routes::channel");
+    }
+}
+
+/// Checks that all the given campaigns are closed, i.e. their total remaining sum is 0.
+async fn ensure_campaigns_closed<C: Locked + 'static>(
+    app: &Application<C>,
+    campaigns: &[CampaignId],
+) -> Result<(), ResponseError> {
+    let campaigns_remaining_sum = app
+        .campaign_remaining
+        .get_multiple(campaigns)
+        .await?
+        .ensure_found()?
+        .sum()
+        .ok_or_else(|| {
+            ResponseError::BadRequest("Couldn't sum remaining amount for all campaigns".to_string())
+        })?;
+
+    if campaigns_remaining_sum > UnifiedNum::from_u64(0) {
+        return Err(ResponseError::FailedValidation(
+            "All campaigns should be closed or have no budget left".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that the total requested payout does not exceed the available payout of the spender,
+/// i.e. `total deposited + total earned - total spent`.
+async fn ensure_available_for_payout<C: Locked + 'static>(
+    app: &Application<C>,
+    channel_context: &ChainOf<Channel>,
+    spender: Address,
+    to_pay: &ChannelPayRequest,
+) -> Result<(), ResponseError> {
+    let available_for_payout = available_for_payout(app, channel_context, spender)
+        .await?
+        .ok_or_else(|| {
+            ResponseError::BadRequest(
+                "There is no spendable amount for the spender in this Channel".to_string(),
+            )
+        })?;
+
+    ensure_within_available(total_to_pay(to_pay)?, available_for_payout)
+}
+
+/// The available payout of the spender, i.e. `total deposited + total earned - total spent`.
+///
+/// Returns `None` if the spender has no [`Spendable`] in the Channel.
+async fn available_for_payout<C: Locked + 'static>(
+    app: &Application<C>,
+    channel_context: &ChainOf<Channel>,
+    spender: Address,
+) -> Result<Option<UnifiedNum>, ResponseError> {
+    let accounting_spent = get_accounting(
+        app.pool.clone(),
+        channel_context.context.id(),
+        spender,
+        Side::Spender,
+    )
+    .await?
+    .map(|accounting_spent| accounting_spent.amount)
+    .unwrap_or_default();
+    let accounting_earned = get_accounting(
+        app.pool.clone(),
+        channel_context.context.id(),
+        spender,
+        Side::Earner,
+    )
+    .await?
+    .map(|accounting_spent| accounting_spent.amount)
+    .unwrap_or_default();
+    let latest_spendable =
+        match fetch_spendable(app.pool.clone(), &spender, &channel_context.context.id())
+            .await
+            .map_err(|err| ResponseError::BadRequest(err.to_string()))?
+        {
+            Some(spendable) => spendable,
+            None => return Ok(None),
         };
+    let total_deposited = latest_spendable.deposit.total;
 
-        #[tokio::test]
-        async fn post_validator_messages() {
-            let app_guard = setup_dummy_app().await;
-            let app = Extension(Arc::new(app_guard.app.clone()));
-            let all_message_types: [MessageType; 4] = [
-                MessageType::NewState,
-                MessageType::ApproveState,
-                MessageType::RejectState,
-                MessageType::Heartbeat,
-            ];
+    let available_for_payout = total_deposited
+        .checked_add(&accounting_earned)
+        .ok_or_else(|| {
+            ResponseError::FailedValidation(
+                "Overflow while calculating available for payout".to_string(),
+            )
+        })?
+        .checked_sub(&accounting_spent)
+        .ok_or_else(|| {
+            ResponseError::FailedValidation(
+                "Underflow while calculating available for payout".to_string(),
+            )
+        })?;
 
-            let channel_context = Extension(CAMPAIGNS[0].clone().of_channel());
+    Ok(Some(available_for_payout))
+}
+
+/// The sum of all the requested payouts.
+fn total_to_pay(to_pay: &ChannelPayRequest) -> Result<UnifiedNum, ResponseError> {
+    to_pay
+        .payouts
+        .values()
+        .sum::<Option<UnifiedNum>>()
+        .ok_or_else(|| ResponseError::FailedValidation("Payouts amount overflow".to_string()))
+}
+
+fn ensure_within_available(
+    total_to_pay: UnifiedNum,
+    available_for_payout: UnifiedNum,
+) -> Result<(), ResponseError> {
+    if total_to_pay > available_for_payout {
+        return Err(ResponseError::FailedValidation(
+            "The total requested payout amount exceeds the available payout".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Spends the payout `balances`, storing the applied payout for the claimed idempotency key, if any.
+///
+/// The applied payout replaces the claim of the key only after it has been spent.
+/// If storing it fails, the key stays claimed until it expires,
+/// so the payout cannot be applied twice.
+async fn apply_payout<C: Locked + 'static>(
+    app: &Application<C>,
+    channel_context: &ChainOf<Channel>,
+    balances: Balances<CheckedState>,
+    idempotency_key: Option<&str>,
+) -> Result<Json<SuccessResponse>, ResponseError> {
+    let applied = idempotency_key
+        .map(|key| serde_json::to_string(&balances).map(|applied| (key, applied)))
+        .transpose()?;
+
+    // will return an error if one of the updates fails
+    spend_amount(app.pool.clone(), channel_context.context.id(), balances).await?;
+
+    if let Some((key, applied)) = applied {
+        if let Err(err) = redis::cmd("SET")
+            .arg(key)
+            .arg(applied)
+            .arg("EX")
+            .arg(PAYOUT_IDEMPOTENCY_TTL.as_secs())
+            .query_async::<_, ()>(&mut app.redis.clone())
+            .await
+        {
+            error!(&app.logger, "Failed to store the applied payout of an Idempotency-Key"; "error" => ?err, "module" => "routes::channel");
+        }
+    }
+
+    Ok(Json(SuccessResponse { success: true }))
+}
+
+/// Responds to a repeated payout request with the result of the applied payout,
+/// if the payout is the same as the applied one.
+fn replayed_payout(
+    applied: &str,
+    balances: &Balances<CheckedState>,
+) -> Result<Json<SuccessResponse>, ResponseError> {
+    let applied = serde_json::from_str::<Balances<UncheckedState>>(applied)?;
+
+    if &applied.check()? != balances {
+        return Err(ResponseError::Conflict(
+            "Idempotency-Key has already been used for a different payout".to_string(),
+        ));
+    }
+
+    Ok(Json(SuccessResponse { success: true }))
+}
+
+/// GET `/v5/channel/0xXXX.../get-leaf` requests
+///
+/// # Routes:
+///
+/// - GET `/v5/channel/:id/get-leaf/spender/:addr`
+/// - GET `/v5/channel/:id/get-leaf/earner/:addr`
+///
+/// Response: [`GetLeafResponse`]
+pub async fn get_leaf<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(channel_context): Extension<ChainOf<Channel>>,
+    Extension(leaf_for): Extension<LeafFor>,
+    Path(params): Path<(ChannelId, Address)>,
+) -> Result<Json<GetLeafResponse>, ResponseError> {
+    let channel = channel_context.context;
+
+    let (_, new_state) = get_corresponding_states::<CheckedState>(&app.pool, &app.logger, &channel)
+        .await?
+        .ok_or(ResponseError::NotFound)?;
+
+    let addr = params.1;
+
+    let element = match leaf_for {
+        LeafFor::Spender => {
+            let amount = new_state
+                .msg
+                .balances
+                .spenders
+                .get(&addr)
+                .ok_or(ResponseError::NotFound)?;
+
+            get_balance_leaf(
+                true,
+                &addr,
+                &amount.to_precision(channel_context.token.precision.get()),
+            )?
+        }
+        LeafFor::Earner => {
+            let amount = new_state
+                .msg
+                .balances
+                .earners
+                .get(&addr)
+                .ok_or(ResponseError::NotFound)?;
+
+            get_balance_leaf(
+                false,
+                &addr,
+                &amount.to_precision(channel_context.token.precision.get()),
+            )?
+        }
+    };
+    let merkle_tree = MerkleTree::new(&[element])?;
+
+    let signable_state_root = get_signable_state_root(channel.id().as_bytes(), &merkle_tree.root());
+
+    let merkle_proof = hex::encode(signable_state_root);
+
+    Ok(Json(GetLeafResponse { merkle_proof }))
+}
+
+/// POST `/v5/channel/dummy-deposit` request
+///
+/// Full details about the route's API and intend can be found in the [`routes`](crate::routes#post-v5channeldummy-deposit-auth-required) module
+///
+/// Request body (json): [`ChannelDummyDeposit`]
+///
+/// Response: [`SuccessResponse`]
+pub async fn channel_dummy_deposit<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(auth): Extension<Auth>,
+    Json(request): Json<ChannelDummyDeposit>,
+) -> Result<Json<SuccessResponse>, ResponseError> {
+    let depositor = auth.uid.to_address();
+
+    // Insert the channel if it does not exist yet
+    let channel_chain = app
+        .config
+        .find_chain_of(request.channel.token)
+        .expect("The Chain of Channel's token was not found in configuration!")
+        .with_channel(request.channel);
+
+    // if this fails, it will cause Bad Request
+    insert_channel(&app.pool, &channel_chain).await?;
+
+    // Convert the UnifiedNum to BigNum with the precision of the token
+    let deposit = request
+        .deposit
+        .to_precision(channel_chain.token.precision.into());
+
+    let dummy_adapter = <dyn Any + Send + Sync>::downcast_ref::<Adapter<Dummy>>(&app.adapter)
+        .expect("Only Dummy adapter is allowed here!");
+
+    // set the deposit in the Dummy adapter's client
+    dummy_adapter
+        .client
+        .set_deposit(&channel_chain, depositor, deposit);
+
+    Ok(Json(SuccessResponse { success: true }))
+}
+
+/// [`Channel`] [validator messages](primitives::validator::MessageTypes) routes
+/// starting with `/v5/channel/0xXXX.../validator-messages`
+///
+pub mod validator_message {
+    use std::sync::Arc;
+
+    use axum::{extract::Path, Extension, Json};
+    use futures::future::try_join_all;
+    use serde::Deserialize;
+
+    use adapter::client::Locked;
+    use primitives::{
+        sentry::{
+            validator_messages::{
+                MessageTypesFilter, ValidatorMessagesCreateRequest, ValidatorMessagesListQuery,
+                ValidatorMessagesListResponse,
+            },
+            SuccessResponse,
+        },
+        ChainOf, Channel, ChannelId, ValidatorId,
+    };
+
+    use crate::{
+        application::Qs,
+        db::validator_message::{
+            get_validator_messages, get_validator_messages_oldest_first, insert_validator_message,
+        },
+        response::ResponseError,
+        Application, Auth,
+    };
+
+    #[derive(Debug, Deserialize)]
+    pub struct MessagesListParams {
+        pub id: ChannelId,
+        // Optional filtering by [`ValidatorId`].
+        #[serde(default)]
+        pub address: Option<ValidatorId>,
+        /// Optional filtering by a list of [`MessageType`](primitives::validator::messages::MessageType)s
+        /// separated by `+` (url encoded).
+        ///
+        /// See [`MessageTypesFilter`] for more details.
+        #[serde(default)]
+        pub message_types: MessageTypesFilter,
+    }
+
+    /// GET `/v5/channel/0xXXX.../validator-messages`
+    ///
+    /// Full details about the route's API and intend can be found in the [`routes`](crate::routes#get-v5channelidvalidator-messages) module
+    ///
+    /// Request query parameters: [`ValidatorMessagesListQuery`]
+    ///
+    /// Response: [`ValidatorMessagesListResponse`]
+    ///
+    pub async fn list_validator_messages<C: Locked + 'static>(
+        Extension(app): Extension<Arc<Application<C>>>,
+        Extension(channel_context): Extension<ChainOf<Channel>>,
+        Path(params): Path<MessagesListParams>,
+        Qs(query): Qs<ValidatorMessagesListQuery>,
+    ) -> Result<Json<ValidatorMessagesListResponse>, ResponseError> {
+        let channel = channel_context.context;
+
+        let config_limit = app.config.limits.msgs_find as u64;
+        let limit = query
+            .limit
+            .filter(|n| *n >= 1)
+            .unwrap_or(config_limit)
+            .min(config_limit);
+
+        let (validator_messages, next_cursor) = if query.is_oldest_first() {
+            get_validator_messages_oldest_first(
+                &app.pool,
+                &channel.id(),
+                &params.address,
+                params.message_types.as_ref(),
+                query.since,
+                query.cursor,
+                limit,
+            )
+            .await?
+        } else {
+            let validator_messages = get_validator_messages(
+                &app.pool,
+                &channel.id(),
+                &params.address,
+                params.message_types.as_ref(),
+                limit,
+            )
+            .await?;
+
+            (validator_messages, None)
+        };
+
+        Ok(Json(ValidatorMessagesListResponse {
+            messages: validator_messages,
+            next_cursor,
+        }))
+    }
+
+    /// POST `/v5/channel/0xXXX.../validator-messages`
+    ///
+    /// Full details about the route's API and intend can be found in the [`routes`](crate::routes#post-v5channelidvalidator-messages-auth-required) module
+    ///
+    /// Request body (json): [`ValidatorMessagesCreateRequest`]
+    ///
+    /// Response: [`SuccessResponse`]
+    ///
+    /// # Examples
+    ///
+    /// Request:
+    ///
+    /// ```
+    #[doc = include_str!("../../../primitives/examples/validator_messages_create_request.rs")]
+    /// ```
+    pub async fn create_validator_messages<C: Locked + 'static>(
+        Extension(app): Extension<Arc<Application<C>>>,
+        Extension(auth): Extension<Auth>,
+        Extension(channel_context): Extension<ChainOf<Channel>>,
+        Json(create_request): Json<ValidatorMessagesCreateRequest>,
+    ) -> Result<Json<SuccessResponse>, ResponseError> {
+        let channel = channel_context.context;
+
+        match channel.find_validator(auth.uid) {
+            None => Err(ResponseError::Unauthorized),
+            _ => {
+                try_join_all(create_request.messages.iter().map(|message| {
+                    insert_validator_message(&app.pool, &channel, &auth.uid, message)
+                }))
+                .await?;
+
+                Ok(Json(SuccessResponse { success: true }))
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use crate::{
+            db::{insert_channel, validator_message::get_validator_messages},
+            test_util::setup_dummy_app,
+        };
+
+        use adapter::{prelude::Unlocked, primitives::Scope};
+        use chrono::Utc;
+
+        use primitives::{
+            balances::UncheckedState,
+            sentry::validator_messages::ValidatorMessagesCreateRequest,
+            test_util::{ADVERTISER, CAMPAIGNS, IDS, LEADER},
+            validator::{
+                ApproveState, Heartbeat, MessageType, MessageTypes, NewState, RejectState,
+            },
+            Balances,
+        };
+
+        #[tokio::test]
+        async fn post_validator_messages() {
+            let app_guard = setup_dummy_app().await;
+            let app = Extension(Arc::new(app_guard.app.clone()));
+            let all_message_types: [MessageType; 4] = [
+                MessageType::NewState,
+                MessageType::ApproveState,
+                MessageType::RejectState,
+                MessageType::Heartbeat,
+            ];
+
+            let channel_context = Extension(CAMPAIGNS[0].clone().of_channel());
+
+            insert_channel(&app.pool, &channel_context)
+                .await
+                .expect("should insert channel");
+
+            // Case when the request is not sent by a channel validator
+            {
+                let auth = Auth {
+                    era: 0,
+                    uid: IDS[&ADVERTISER],
+                    chain: channel_context.chain.clone(),
+                    scope: Scope::Full,
+                };
+
+                let req = ValidatorMessagesCreateRequest { messages: vec![] };
+
+                let res = create_validator_messages(
+                    app.clone(),
+                    Extension(auth),
+                    channel_context.clone(),
+                    Json(req),
+                )
+                .await
+                .expect_err("We expect an error to be returned");
+
+                assert_eq!(res, ResponseError::Unauthorized);
+            }
+
+            // Cases where we insert validator messages
+            // Case with 0 messages
+            {
+                let auth = Auth {
+                    era: 0,
+                    uid: IDS[&LEADER],
+                    chain: channel_context.chain.clone(),
+                    scope: Scope::Full,
+                };
+
+                let req = ValidatorMessagesCreateRequest { messages: vec![] };
+
+                create_validator_messages(
+                    app.clone(),
+                    Extension(auth),
+                    channel_context.clone(),
+                    Json(req),
+                )
+                .await
+                .expect("should work");
+
+                let messages = get_validator_messages(
+                    &app.pool,
+                    &channel_context.context.id(),
+                    &Some(IDS[&LEADER]),
+                    &all_message_types,
+                    100,
+                )
+                .await
+                .expect("should get messages");
+
+                assert_eq!(messages.len(), 0);
+            }
+            // Case with 1 message
+            {
+                let auth = Auth {
+                    era: 0,
+                    uid: IDS[&LEADER],
+                    chain: channel_context.chain.clone(),
+                    scope: Scope::Full,
+                };
+
+                let message = MessageTypes::Heartbeat(Heartbeat {
+                    signature: String::new(),
+                    state_root: String::new(),
+                    timestamp: Utc::now(),
+                });
+
+                let req = ValidatorMessagesCreateRequest {
+                    messages: vec![message.clone()],
+                };
+
+                create_validator_messages(
+                    app.clone(),
+                    Extension(auth),
+                    channel_context.clone(),
+                    Json(req),
+                )
+                .await
+                .expect("Should create messages");
+
+                let messages = get_validator_messages(
+                    &app.pool,
+                    &channel_context.context.id(),
+                    &Some(IDS[&LEADER]),
+                    &all_message_types,
+                    100,
+                )
+                .await
+                .expect("should get messages");
+                let inner_messages: Vec<MessageTypes> =
+                    messages.into_iter().map(|m| m.msg.clone()).collect();
+                assert_eq!(inner_messages, vec![message]);
+            }
+            // Case with 4 messages (one of each type, heartbeat is already inserted)
+            {
+                let auth = Auth {
+                    era: 0,
+                    uid: IDS[&LEADER],
+                    chain: channel_context.chain.clone(),
+                    scope: Scope::Full,
+                };
+
+                let state_root =
+                    "b1a4fc6c1a1e1ab908a487e504006edcebea297f61b4b8ce6cad3b29e29454cc".to_string();
+                let signature = app
+                    .adapter
+                    .clone()
+                    .unlock()
+                    .expect("should unlock")
+                    .sign(&state_root.clone())
+                    .expect("should sign");
+                let new_state = MessageTypes::NewState(NewState {
+                    state_root: state_root.clone(),
+                    signature: signature.clone(),
+                    balances: Balances::<UncheckedState>::new(),
+                });
+
+                let approve_state = MessageTypes::ApproveState(ApproveState {
+                    state_root: state_root.clone(),
+                    signature: signature.clone(),
+                    is_healthy: true,
+                });
+
+                let reject_state = MessageTypes::RejectState(RejectState {
+                    state_root,
+                    signature: signature.clone(),
+                    timestamp: Utc::now(),
+                    reason: "rejected".to_string(),
+                    balances: None,
+                });
+
+                let req = ValidatorMessagesCreateRequest {
+                    messages: vec![
+                        new_state.clone(),
+                        approve_state.clone(),
+                        reject_state.clone(),
+                    ],
+                };
+
+                create_validator_messages(
+                    app.clone(),
+                    Extension(auth),
+                    channel_context.clone(),
+                    Json(req),
+                )
+                .await
+                .expect("Should create messages");
+
+                let messages = get_validator_messages(
+                    &app.pool,
+                    &channel_context.context.id(),
+                    &Some(IDS[&LEADER]),
+                    &all_message_types,
+                    100,
+                )
+                .await
+                .expect("should get messages");
+                assert_eq!(messages.len(), 4);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{
+        db::{
+            fetch_campaign, insert_campaign, insert_channel, spendable::insert_spendable,
+            validator_message::insert_validator_message,
+        },
+        test_util::setup_dummy_app,
+    };
+
+    use adapter::{
+        ethereum::test_util::{GANACHE_INFO_1, GANACHE_INFO_1337},
+        prelude::Unlocked,
+        primitives::{Deposit as AdapterDeposit, Scope},
+    };
+    use primitives::{
+        balances::UncheckedState,
+        channel::Nonce,
+        test_util::{
+            ADVERTISER, CREATOR, DUMMY_CAMPAIGN, FOLLOWER, GUARDIAN, GUARDIAN_2, IDS, LEADER,
+            LEADER_2, PUBLISHER, PUBLISHER_2,
+        },
+        util::canonical_json::to_canonical_json,
+        validator::{ApproveState, MessageTypes, NewState},
+        BigNum, ChainId, Deposit, UnifiedMap, ValidatorId,
+    };
+
+    #[tokio::test]
+    async fn create_and_fetch_spendable() {
+        let app = setup_dummy_app().await;
+
+        let (channel_context, channel) = {
+            let channel = DUMMY_CAMPAIGN.channel;
+            let channel_context = app
+                .config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("should retrieve Chain & token");
+
+            (channel_context.with_channel(channel), channel)
+        };
+
+        let precision: u8 = channel_context.token.precision.into();
+        let deposit = AdapterDeposit {
+            total: BigNum::from_str("100000000000000000000").expect("should convert"), // 100 DAI
+        };
+        app.adapter
+            .client
+            .set_deposit(&channel_context, *CREATOR, deposit.clone());
+        // Making sure spendable does not yet exist
+        let spendable = fetch_spendable(app.pool.clone(), &CREATOR, &channel.id())
+            .await
+            .expect("should return None");
+        assert!(spendable.is_none());
+        // Call fetch_or_create_spendable
+        let new_spendable = fetch_or_create_spendable_document(&app, &channel_context, *CREATOR)
+            .await
+            .expect("should create a new spendable");
+        assert_eq!(new_spendable.channel.id(), channel.id());
+
+        let total_as_unified_num =
+            UnifiedNum::from_precision(deposit.total, precision).expect("should convert");
+
+        assert_eq!(new_spendable.deposit.total, total_as_unified_num);
+
+        assert_eq!(new_spendable.spender, *CREATOR);
+
+        // Make sure spendable NOW exists
+        let spendable = fetch_spendable(app.pool.clone(), &CREATOR, &channel.id())
+            .await
+            .expect("should return a spendable");
+        assert!(spendable.is_some());
+
+        let updated_deposit = AdapterDeposit {
+            total: BigNum::from_str("110000000000000000000").expect("should convert"), // 110 DAI
+        };
+
+        app.adapter
+            .client
+            .set_deposit(&channel_context, *CREATOR, updated_deposit.clone());
+
+        let updated_spendable =
+            fetch_or_create_spendable_document(&app, &channel_context, *CREATOR)
+                .await
+                .expect("should update spendable");
+        let total_as_unified_num =
+            UnifiedNum::from_precision(updated_deposit.total, precision).expect("should convert");
+
+        assert_eq!(updated_spendable.deposit.total, total_as_unified_num);
+        assert_eq!(updated_spendable.spender, *CREATOR);
+    }
+
+    #[tokio::test]
+    async fn concurrent_first_spender_requests_create_a_single_spendable() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+        let channel = channel_context.context;
+
+        let deposit = AdapterDeposit {
+            total: BigNum::from_str("100000000000000000000").expect("should convert"), // 100 DAI
+        };
+        app.adapter
+            .client
+            .set_deposit(&channel_context, *CREATOR, deposit.clone());
+
+        let (first, second) = tokio::join!(
+            get_spender_limits(
+                Path((channel.id(), *CREATOR)),
+                app.clone(),
+                Extension(channel_context.clone()),
+            ),
+            get_spender_limits(
+                Path((channel.id(), *CREATOR)),
+                app.clone(),
+                Extension(channel_context.clone()),
+            ),
+        );
+        let first = first.expect("should get spender limits").0;
+        let second = second.expect("should get spender limits").0;
+
+        let precision: u8 = channel_context.token.precision.into();
+        let total = UnifiedNum::from_precision(deposit.total, precision).expect("should convert");
+        assert_eq!(total, first.spender.total_deposited);
+        assert_eq!(total, second.spender.total_deposited);
+
+        let (spendables, _pagination) =
+            get_all_spendables_for_channel(app.pool.clone(), &channel.id(), 0, 10, true)
+                .await
+                .expect("should get spendables");
+        assert_eq!(1, spendables.len(), "A single Spendable should be created");
+        assert_eq!(total, spendables[0].deposit.total);
+
+        let (history, _pagination) =
+            get_spendable_history(&app.pool, &channel.id(), &CREATOR, 0, 10)
+                .await
+                .expect("should get spendable history");
+        assert_eq!(
+            1,
+            history.len(),
+            "The deposit should be recorded only by the inserting request"
+        );
+        assert_eq!(None, history[0].old_total);
+        assert_eq!(total, history[0].new_total);
+    }
+
+    #[tokio::test]
+    async fn spender_without_deposit_is_not_persisted() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        app.adapter.client.set_deposit(
+            &channel_context,
+            *CREATOR,
+            AdapterDeposit {
+                total: BigNum::from(0),
+            },
+        );
+
+        let response = get_spender_limits(
+            Path((channel_context.context.id(), *CREATOR)),
+            app.clone(),
+            Extension(channel_context.clone()),
+        )
+        .await
+        .expect("should get spender limits")
+        .0;
+
+        assert_eq!(UnifiedNum::ZERO, response.spender.total_deposited);
+        assert_eq!(
+            None, response.deposit_breakdown,
+            "The Dummy adapter has no deposit breakdown"
+        );
+
+        let spendable = fetch_spendable(app.pool.clone(), &CREATOR, &channel_context.context.id())
+            .await
+            .expect("should fetch spendable");
+        assert!(
+            spendable.is_none(),
+            "A Spendable without a deposit should not be persisted"
+        );
+    }
+
+    #[tokio::test]
+    async fn stored_spendable_is_updated_when_the_deposit_drops_to_zero() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        for (total, expected) in [
+            // 100 DAI
+            (
+                BigNum::from_str("100000000000000000000").expect("should convert"),
+                UnifiedNum::from_u64(10_000_000_000),
+            ),
+            (BigNum::from(0), UnifiedNum::ZERO),
+        ] {
+            app.adapter
+                .client
+                .set_deposit(&channel_context, *CREATOR, AdapterDeposit { total });
+
+            let response = get_spender_limits(
+                Path((channel_context.context.id(), *CREATOR)),
+                app.clone(),
+                Extension(channel_context.clone()),
+            )
+            .await
+            .expect("should get spender limits")
+            .0;
+            assert_eq!(expected, response.spender.total_deposited);
+
+            let spendable =
+                fetch_spendable(app.pool.clone(), &CREATOR, &channel_context.context.id())
+                    .await
+                    .expect("should fetch spendable")
+                    .expect("The Spendable should be persisted after the first deposit");
+            assert_eq!(expected, spendable.deposit.total);
+        }
+    }
+
+    #[tokio::test]
+    async fn get_spenders_batch_for_channel() {
+        let mut app_guard = setup_dummy_app().await;
+        app_guard.config.limits.spendable_find = 3;
+
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = Extension(
+            app.config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Dummy channel Token should be present in config!")
+                .with_channel(DUMMY_CAMPAIGN.channel),
+        );
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+
+        for (spender, total) in [(*CREATOR, 100_u64), (*ADVERTISER, 200_u64)] {
+            let spendable = Spendable {
+                spender,
+                channel: channel_context.context,
+                deposit: Deposit {
+                    total: UnifiedNum::from_u64(total),
+                },
+            };
+            assert!(insert_spendable(app.pool.clone(), &spendable)
+                .await
+                .expect("should insert spendable"));
+        }
+
+        // PUBLISHER has no spendable
+        {
+            let request = SpendersBatchRequest {
+                spenders: vec![*CREATOR, *ADVERTISER, *PUBLISHER],
+            };
+
+            let response = get_spenders_batch(app.clone(), channel_context.clone(), Json(request))
+                .await
+                .expect("should get spenders")
+                .0;
+
+            let expected = [
+                (
+                    *CREATOR,
+                    Spender {
+                        total_deposited: UnifiedNum::from_u64(100),
+                        total_spent: None,
+                    },
+                ),
+                (
+                    *ADVERTISER,
+                    Spender {
+                        total_deposited: UnifiedNum::from_u64(200),
+                        total_spent: None,
+                    },
+                ),
+            ]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+            assert_eq!(expected, response.spenders);
+        }
+
+        // More spenders than the limit
+        {
+            let request = SpendersBatchRequest {
+                spenders: vec![*CREATOR, *ADVERTISER, *PUBLISHER, *PUBLISHER_2],
+            };
+
+            let result =
+                get_spenders_batch(app.clone(), channel_context.clone(), Json(request)).await;
+
+            assert!(matches!(result, Err(ResponseError::FailedValidation(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn get_accountings_for_channel() {
+        let app_guard = setup_dummy_app().await;
+
+        let app = Extension(Arc::new(app_guard.app.clone()));
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with(DUMMY_CAMPAIGN.channel);
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+
+        // Testing for no accounting yet
+        {
+            let accounting_response =
+                get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
+                    .await
+                    .expect("shoul get accounting");
+
+            assert_eq!(accounting_response.balances.earners.len(), 0);
+            assert_eq!(accounting_response.balances.spenders.len(), 0);
+        }
+
+        // Testing for 2 accountings - first channel
+        {
+            let mut balances = Balances::<CheckedState>::new();
+            balances
+                .spend(*CREATOR, *PUBLISHER, UnifiedNum::from_u64(200))
+                .expect("should not overflow");
+            balances
+                .spend(*CREATOR, *PUBLISHER_2, UnifiedNum::from_u64(100))
+                .expect("Should not overflow");
+            spend_amount(
+                app.pool.clone(),
+                channel_context.context.id(),
+                balances.clone(),
+            )
+            .await
+            .expect("should spend");
+
+            let accounting_response =
+                get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
+                    .await
+                    .expect("should get accounting");
+
+            assert_eq!(balances, accounting_response.balances);
+        }
+
+        // Testing for 2 accountings - second channel (same address is both an earner and a spender)
+        {
+            let mut second_channel = DUMMY_CAMPAIGN.channel;
+            second_channel.leader = IDS[&ADVERTISER]; // channel.id() will be different now
+            let channel_context = app
+                .config
+                .find_chain_of(second_channel.token)
+                .expect("Dummy channel Token should be present in config!")
+                .with(second_channel);
+            insert_channel(&app.pool, &channel_context)
+                .await
+                .expect("should insert channel");
+
+            let mut balances = Balances::<CheckedState>::new();
+            balances
+                .spend(*CREATOR, *PUBLISHER, 300.into())
+                .expect("Should not overflow");
+
+            balances
+                .spend(*PUBLISHER, *ADVERTISER, 300.into())
+                .expect("Should not overflow");
+
+            spend_amount(app.pool.clone(), second_channel.id(), balances.clone())
+                .await
+                .expect("should spend");
+
+            let accounting_response =
+                get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
+                    .await
+                    .expect("shoul get accounting");
+
+            assert_eq!(balances, accounting_response.balances)
+        }
+
+        // Testing for when sums don't match on first channel - Error case
+        {
+            let mut balances = Balances::<CheckedState>::new();
+            balances
+                .earners
+                .insert(*PUBLISHER, UnifiedNum::from_u64(100));
+            balances
+                .spenders
+                .insert(*CREATOR, UnifiedNum::from_u64(200));
+            spend_amount(app.pool.clone(), channel_context.context.id(), balances)
+                .await
+                .expect("should spend");
+
+            let res =
+                get_accounting_for_channel(app.clone(), Extension(channel_context.clone())).await;
+            let expected = ResponseError::FailedValidation(
+                "Earners sum is not equal to spenders sum for channel".to_string(),
+            );
+            assert_eq!(expected, res.expect_err("Should return an error"));
+        }
+    }
+
+    #[tokio::test]
+    async fn get_accountings_for_channel_with_tolerance() {
+        let app_guard = setup_dummy_app().await;
+
+        let mut app = app_guard.app.clone();
+        app.config.sentry.accounting_tolerance = UnifiedNum::from_u64(2);
+        let app = Extension(Arc::new(app));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with(DUMMY_CAMPAIGN.channel);
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+
+        // Residue exactly at the tolerance
+        {
+            let mut balances = Balances::<CheckedState>::new();
+            balances
+                .earners
+                .insert(*PUBLISHER, UnifiedNum::from_u64(1_002));
+            balances
+                .spenders
+                .insert(*CREATOR, UnifiedNum::from_u64(1_000));
+            spend_amount(app.pool.clone(), channel_context.context.id(), balances)
+                .await
+                .expect("should spend");
+
+            let accounting_response =
+                get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
+                    .await
+                    .expect("should get accounting within the tolerance");
+
+            assert_eq!(
+                Some(&UnifiedNum::from_u64(1_002)),
+                accounting_response.balances.earners.get(&PUBLISHER)
+            );
+            assert_eq!(
+                Some(&UnifiedNum::from_u64(1_000)),
+                accounting_response.balances.spenders.get(&CREATOR)
+            );
+        }
+
+        // Residue just beyond the tolerance
+        {
+            let mut balances = Balances::<CheckedState>::new();
+            balances.earners.insert(*PUBLISHER, UnifiedNum::from_u64(1));
+            spend_amount(app.pool.clone(), channel_context.context.id(), balances)
+                .await
+                .expect("should spend");
+
+            let res =
+                get_accounting_for_channel(app.clone(), Extension(channel_context.clone())).await;
+            let expected = ResponseError::FailedValidation(
+                "Earners sum is not equal to spenders sum for channel".to_string(),
+            );
+            assert_eq!(expected, res.expect_err("Should return an error"));
+        }
+    }
+
+    #[tokio::test]
+    async fn get_accounting_page_for_channel_pages_through_all_entries() {
+        let app_guard = setup_dummy_app().await;
+
+        let mut app = app_guard.app.clone();
+        app.config.limits.accounting_find = 4;
+        let app = Extension(Arc::new(app));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with(DUMMY_CAMPAIGN.channel);
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+
+        // No accounting yet
+        {
+            let page = get_accounting_page_for_channel(
+                app.clone(),
+                Extension(channel_context.clone()),
+                Qs(AccountingPageQuery { page: 0 }),
+            )
+            .await
+            .expect("should get accounting page");
+
+            assert_eq!(page.balances, Balances::<UncheckedState>::default());
+            assert_eq!(
+                page.pagination,
+                primitives::sentry::Pagination {
+                    total_pages: 1,
+                    page: 0
+                }
+            );
+        }
+
+        // 10 earners & 1 spender
+        let mut balances = Balances::<CheckedState>::new();
+        for byte in 0..10_u8 {
+            let mut bytes = PUBLISHER.to_bytes();
+            bytes[19] = byte;
+
+            balances
+                .spend(
+                    *CREATOR,
+                    Address::from_bytes(&bytes),
+                    UnifiedNum::from_u64(100),
+                )
+                .expect("Should not overflow");
+        }
+        spend_amount(
+            app.pool.clone(),
+            channel_context.context.id(),
+            balances.clone(),
+        )
+        .await
+        .expect("should spend");
+
+        let mut paged: Balances<UncheckedState> = Balances::default();
+        for page in 0..3 {
+            let response = get_accounting_page_for_channel(
+                app.clone(),
+                Extension(channel_context.clone()),
+                Qs(AccountingPageQuery { page }),
+            )
+            .await
+            .expect("should get accounting page");
+
+            assert_eq!(
+                response.pagination,
+                primitives::sentry::Pagination {
+                    total_pages: 3,
+                    page
+                }
+            );
+            assert!(!response.frozen);
+
+            let Json(AccountingPageResponse { balances, .. }) = response;
+            // earners are ordered before spenders
+            match page {
+                0 | 1 => {
+                    assert_eq!(4, balances.earners.len());
+                    assert!(balances.spenders.is_empty());
+                }
+                _ => {
+                    assert_eq!(2, balances.earners.len());
+                    assert_eq!(1, balances.spenders.len());
+                }
+            }
+
+            for (earner, amount) in balances.earners {
+                assert!(
+                    paged.earners.insert(earner, amount).is_none(),
+                    "Each earner should be returned on a single page"
+                );
+            }
+            for (spender, amount) in balances.spenders {
+                assert!(
+                    paged.spenders.insert(spender, amount).is_none(),
+                    "Each spender should be returned on a single page"
+                );
+            }
+        }
+
+        let whole = get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
+            .await
+            .expect("should get accounting");
+        assert_eq!(balances, whole.balances);
+        assert_eq!(
+            balances,
+            paged.check().expect("Paged balances should be balanced")
+        );
+
+        // A page beyond the last one is empty
+        {
+            let response = get_accounting_page_for_channel(
+                app.clone(),
+                Extension(channel_context.clone()),
+                Qs(AccountingPageQuery { page: 3 }),
+            )
+            .await
+            .expect("should get accounting page");
+
+            assert_eq!(response.balances, Balances::<UncheckedState>::default());
+        }
+    }
+
+    #[tokio::test]
+    async fn channel_snapshot_matches_the_individual_routes() {
+        let app_guard = setup_dummy_app().await;
+        let signer = app_guard
+            .app
+            .adapter
+            .clone()
+            .unlock()
+            .expect("Should unlock the Dummy adapter");
+
+        let app = Extension(Arc::new(
+            app_guard.app.clone().with_whoami_signer(Arc::new(signer)),
+        ));
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with(DUMMY_CAMPAIGN.channel);
+        let channel = channel_context.context;
+        let auth = |uid: ValidatorId| Auth {
+            era: 0,
+            uid,
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        };
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+        insert_campaign(&app.pool, &DUMMY_CAMPAIGN)
+            .await
+            .expect("should insert campaign");
+
+        // Empty Channel accounting
+        {
+            let response = channel_snapshot(
+                app.clone(),
+                Extension(channel_context.clone()),
+                Extension(auth(IDS[&LEADER])),
+            )
+            .await
+            .expect("should get snapshot")
+            .0;
+            let snapshot = response.snapshot;
+
+            assert_eq!(channel, snapshot.channel);
+            assert!(snapshot.balances.earners.is_empty());
+            assert!(snapshot.balances.spenders.is_empty());
+            assert!(snapshot.spendables.is_empty());
+            assert_eq!(
+                Some(&UnifiedNum::from_u64(0)),
+                snapshot.campaigns_remaining.get(&DUMMY_CAMPAIGN.id),
+                "Campaign without remaining in Redis should have 0 remaining"
+            );
+            assert_eq!(None, snapshot.last_approved);
+            assert_eq!(None, snapshot.state_root);
+        }
+
+        for (spender, total) in [(*CREATOR, 1_000_000), (*ADVERTISER, 500_000)] {
+            update_spendable(
+                app.pool.clone(),
+                &app.redis,
+                &app.redis_keys,
+                &Spendable {
+                    spender,
+                    channel,
+                    deposit: Deposit {
+                        total: UnifiedNum::from_u64(total),
+                    },
+                },
+                SpendableSource::WorkerRefresh,
+            )
+            .await
+            .expect("should insert spendable");
+        }
+
+        let mut balances = Balances::<CheckedState>::new();
+        balances
+            .spend(*CREATOR, *PUBLISHER, UnifiedNum::from_u64(200))
+            .expect("should not overflow");
+        balances
+            .spend(*ADVERTISER, *PUBLISHER_2, UnifiedNum::from_u64(100))
+            .expect("Should not overflow");
+        spend_amount(app.pool.clone(), channel.id(), balances.clone())
+            .await
+            .expect("should spend");
+
+        app.campaign_remaining
+            .set_initial(DUMMY_CAMPAIGN.id, UnifiedNum::from_u64(700))
+            .await
+            .expect("should set remaining");
+
+        let state_root = balances_state_root(&channel_context, &balances)
+            .expect("should compute the state root")
+            .expect("should have a state root");
+        insert_validator_message(
+            &app.pool,
+            &channel,
+            &channel.leader,
+            &MessageTypes::NewState(NewState {
+                state_root: state_root.clone(),
+                signature: channel.leader.to_string(),
+                balances: balances.clone().into_unchecked(),
+            }),
+        )
+        .await
+        .expect("Should insert NewState msg");
+        insert_validator_message(
+            &app.pool,
+            &channel,
+            &channel.follower,
+            &MessageTypes::ApproveState(ApproveState {
+                state_root: state_root.clone(),
+                signature: channel.follower.to_string(),
+                is_healthy: true,
+            }),
+        )
+        .await
+        .expect("Should insert ApproveState msg");
+
+        let response = channel_snapshot(
+            app.clone(),
+            Extension(channel_context.clone()),
+            Extension(auth(IDS[&GUARDIAN])),
+        )
+        .await
+        .expect("should get snapshot")
+        .0;
+        let snapshot = &response.snapshot;
+
+        assert_eq!(app.adapter.whoami(), response.signer);
+        let message = snapshot.message().expect("should hash the snapshot");
+        assert!(app
+            .adapter
+            .verify(response.signer, &message, &response.signature)
+            .expect("Should verify"));
+        // the canonical JSON of the received `snapshot` object is the same
+        let received = serde_json::to_value(&response)
+            .expect("should serialize")
+            .get("snapshot")
+            .cloned()
+            .expect("should have a snapshot");
+        assert_eq!(
+            snapshot.canonical_json().expect("should serialize"),
+            to_canonical_json(&received).expect("should serialize")
+        );
+
+        let accounting =
+            get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
+                .await
+                .expect("should get accounting")
+                .0;
+        assert_eq!(accounting.balances.earners, snapshot.balances.earners);
+        assert_eq!(accounting.balances.spenders, snapshot.balances.spenders);
+        assert_eq!(Some(&state_root), snapshot.state_root.as_ref());
+
+        let all_spenders = get_all_spender_limits(
+            app.clone(),
+            Extension(channel_context.clone()),
+            Qs(AllSpendersQuery {
+                page: 0,
+                include_empty: false,
+                limit: None,
+            }),
+        )
+        .await
+        .expect("should get all spenders")
+        .0;
+        let total_deposits = all_spenders
+            .spenders
+            .into_iter()
+            .map(|(spender, limits)| (spender, limits.total_deposited))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(total_deposits, snapshot.spendables);
+
+        assert_eq!(
+            vec![(DUMMY_CAMPAIGN.id, UnifiedNum::from_u64(700))]
+                .into_iter()
+                .collect::<HashMap<_, _>>(),
+            snapshot.campaigns_remaining
+        );
+
+        let last_approved_response = last_approved(
+            app.clone(),
+            Extension(channel_context.clone()),
+            Qs(LastApprovedQuery {
+                with_heartbeat: None,
+            }),
+        )
+        .await
+        .expect("should get last approved")
+        .0;
+        assert_eq!(last_approved_response.last_approved, snapshot.last_approved);
+
+        // neither a validator, nor the guardian or an admin
+        {
+            let response = channel_snapshot(
+                app.clone(),
+                Extension(channel_context.clone()),
+                Extension(auth(IDS[&PUBLISHER])),
+            )
+            .await;
+
+            assert!(matches!(response, Err(ResponseError::Forbidden(_))));
+        }
+
+        // without a signer
+        {
+            let app = Extension(Arc::new(app_guard.app.clone()));
+            let response = channel_snapshot(
+                app,
+                Extension(channel_context.clone()),
+                Extension(auth(IDS[&FOLLOWER])),
+            )
+            .await;
+
+            assert!(matches!(response, Err(ResponseError::NotFound)));
+        }
+    }
+
+    #[tokio::test]
+    async fn adds_and_retrieves_spender_leaf() {
+        let app_guard = setup_dummy_app().await;
+
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with(DUMMY_CAMPAIGN.channel);
+
+        let deposit = AdapterDeposit {
+            total: BigNum::from_str("100000000000000000000").expect("should convert"), // 100 DAI
+        };
+        app.adapter
+            .client
+            .set_deposit(&channel_context, *CREATOR, deposit.clone());
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+
+        // Calling with non existent accounting
+        let _res = add_spender_leaf(
+            app.clone(),
+            Extension(channel_context.clone()),
+            Path((channel_context.context.id(), *CREATOR)),
+        )
+        .await
+        .expect("should get spender leaf");
+
+        let accounting_response =
+            get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
+                .await
+                .expect("should get accounting");
+
+        // Making sure a new entry has been created
+        assert_eq!(
+            accounting_response.balances.spenders.get(&CREATOR),
+            Some(&UnifiedNum::from_u64(0)),
+        );
+
+        let mut balances = Balances::<CheckedState>::new();
+        balances
+            .spend(*CREATOR, *PUBLISHER, UnifiedNum::from_u64(200))
+            .expect("should not overflow");
+        balances
+            .spend(*ADVERTISER, *GUARDIAN, UnifiedNum::from_u64(100))
+            .expect("Should not overflow");
+        spend_amount(
+            app.pool.clone(),
+            channel_context.context.id(),
+            balances.clone(),
+        )
+        .await
+        .expect("should spend");
+
+        let accounting_response =
+            get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
+                .await
+                .expect("should get accounting");
+
+        assert_eq!(balances, accounting_response.balances);
+
+        let res = add_spender_leaf(
+            app.clone(),
+            Extension(channel_context.clone()),
+            Path((channel_context.context.id(), *CREATOR)),
+        )
+        .await;
+        assert!(res.is_ok());
+
+        let accounting_response =
+            get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
+                .await
+                .expect("should get accounting");
+
+        // Balances shouldn't change
+        assert_eq!(
+            accounting_response.balances.spenders.get(&CREATOR),
+            balances.spenders.get(&CREATOR),
+        );
+    }
+
+    #[tokio::test]
+    async fn get_channels_list() {
+        let mut app_guard = setup_dummy_app().await;
+        app_guard.config.limits.channels_find = 2;
+
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel = Channel {
+            leader: IDS[&LEADER],
+            follower: IDS[&FOLLOWER],
+            guardian: *GUARDIAN,
+            token: GANACHE_INFO_1337.tokens["Mocked TOKEN 1337"].address,
+            nonce: Nonce::from(987_654_321_u32),
+        };
+        let channel_context = app
+            .config
+            .find_chain_of(channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with(channel);
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert");
+
+        let channel_other_token = Channel {
+            leader: IDS[&LEADER],
+            follower: IDS[&FOLLOWER],
+            guardian: *GUARDIAN,
+            token: GANACHE_INFO_1.tokens["Mocked TOKEN 1"].address,
+            nonce: Nonce::from(987_654_322_u32),
+        };
+        let channel_context = app
+            .config
+            .find_chain_of(channel_other_token.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with(channel_other_token);
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert");
+
+        let channel_other_leader = Channel {
+            leader: IDS[&LEADER_2],
+            follower: IDS[&FOLLOWER],
+            guardian: *GUARDIAN,
+            token: GANACHE_INFO_1337.tokens["Mocked TOKEN 1337"].address,
+            nonce: Nonce::from(987_654_323_u32),
+        };
+        let channel_context = app
+            .config
+            .find_chain_of(channel_other_leader.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with(channel_other_leader);
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert");
+
+        // Test query limit - capped to the config limit, `0` is the config limit
+        {
+            for (limit, expected_total_pages) in [(1, 3), (5, 2), (0, 2)] {
+                let query = ChannelListQuery {
+                    page: 0,
+                    validator: None,
+                    guardian: None,
+                    chains: vec![],
+                    limit: Some(limit),
+                };
+
+                let channels_list = channel_list(app.clone(), Qs(query))
+                    .await
+                    .expect("should get channels")
+                    .0;
+
+                assert_eq!(
+                    expected_total_pages, channels_list.pagination.total_pages,
+                    "Unexpected total pages for limit {limit}"
+                );
+            }
+        }
+
+        // Test query page - page 0, page 1
+        {
+            let query = ChannelListQuery {
+                page: 0,
+                validator: None,
+                guardian: None,
+                chains: vec![],
+                limit: None,
+            };
+
+            let channels_list = channel_list(app.clone(), Qs(query))
+                .await
+                .expect("should get channels")
+                .0;
+
+            assert_eq!(
+                channels_list.channels,
+                vec![channel, channel_other_token],
+                "The channels should be listed by ascending order of their creation"
+            );
+            assert_eq!(
+                channels_list.pagination.total_pages, 2,
+                "There should be 2 pages in total"
+            );
+
+            let query = ChannelListQuery {
+                page: 1,
+                validator: None,
+                guardian: None,
+                chains: vec![],
+                limit: None,
+            };
+            let channels_list = channel_list(app.clone(), Qs(query))
+                .await
+                .expect("should get channels")
+                .0;
+
+            assert_eq!(
+                channels_list.channels,
+                vec![channel_other_leader],
+                "The channels should be listed by ascending order of their creation"
+            );
+        }
+
+        // Test query validator - query with validator ID
+        {
+            let query = ChannelListQuery {
+                page: 0,
+                validator: Some(IDS[&LEADER_2]),
+                guardian: None,
+                chains: vec![],
+                limit: None,
+            };
+            let channels_list = channel_list(app.clone(), Qs(query))
+                .await
+                .expect("should get channels")
+                .0;
+
+            assert_eq!(
+                channels_list.channels,
+                vec![channel_other_leader],
+                "Response returns the correct channel"
+            );
+            assert_eq!(
+                channels_list.pagination.total_pages, 1,
+                "There should be 1 page in total"
+            );
+        }
+        // Test query with both pagination and validator by querying for the follower validator
+        {
+            let query = ChannelListQuery {
+                page: 0,
+                validator: Some(IDS[&FOLLOWER]),
+                guardian: None,
+                chains: vec![],
+                limit: None,
+            };
+            let channels_list = channel_list(app.clone(), Qs(query))
+                .await
+                .expect("should get channels");
+
+            assert_eq!(
+                channels_list.pagination.total_pages, 2,
+                "There should be 2 pages in total"
+            );
+            assert_eq!(
+                channels_list.channels,
+                vec![channel, channel_other_token],
+                "The channels should be listed by ascending order of their creation"
+            );
+
+            let query = ChannelListQuery {
+                page: 1,
+                validator: Some(IDS[&FOLLOWER]),
+                guardian: None,
+                chains: vec![],
+                limit: None,
+            };
+            let channels_list = channel_list(app.clone(), Qs(query))
+                .await
+                .expect("should get channels");
+
+            assert_eq!(
+                channels_list.channels,
+                vec![channel_other_leader],
+                "The channels should be listed by ascending order of their creation"
+            );
+            assert_eq!(
+                channels_list.pagination.total_pages, 2,
+                "There should be 2 pages in total"
+            );
+        }
+
+        // Test query with different chains
+        {
+            let limit_app = {
+                let mut limit_app = app_guard.app;
+                limit_app.config.limits.channels_find = 10; // no need to test pagination, will ease checking results for this cause
+
+                Extension(Arc::new(limit_app))
+            };
+
+            let query_1 = ChannelListQuery {
+                page: 0,
+                validator: Some(IDS[&FOLLOWER]),
+                guardian: None,
+                chains: vec![ChainId::new(1)],
+                limit: None,
+            };
+
+            let channels_list = channel_list(limit_app.clone(), Qs(query_1))
+                .await
+                .expect("should get channels");
+
+            assert_eq!(
+                channels_list.channels,
+                vec![channel_other_token],
+                "Response returns the correct channel"
+            );
+
+            let query_1337 = ChannelListQuery {
+                page: 0,
+                validator: Some(IDS[&FOLLOWER]),
+                guardian: None,
+                chains: vec![ChainId::new(1337)],
+                limit: None,
+            };
+
+            let channels_list = channel_list(limit_app.clone(), Qs(query_1337))
+                .await
+                .expect("should get channels");
+
+            assert_eq!(
+                channels_list.channels,
+                vec![channel, channel_other_leader],
+                "Response returns the correct channel"
+            );
+
+            let query_both_chains = ChannelListQuery {
+                page: 0,
+                validator: Some(IDS[&FOLLOWER]),
+                guardian: None,
+                chains: vec![ChainId::new(1), ChainId::new(1337)],
+                limit: None,
+            };
+
+            let channels_list = channel_list(limit_app, Qs(query_both_chains))
+                .await
+                .expect("should get channels");
+
+            assert_eq!(
+                channels_list.channels,
+                vec![channel, channel_other_token, channel_other_leader],
+                "Response returns the correct channel"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn get_channels_list_by_guardian() {
+        let mut app_guard = setup_dummy_app().await;
+        app_guard.config.limits.channels_find = 1;
+
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channels = [
+            (*GUARDIAN, 987_654_321_u32),
+            (*GUARDIAN_2, 987_654_322_u32),
+            (*GUARDIAN, 987_654_323_u32),
+        ]
+        .map(|(guardian, nonce)| Channel {
+            leader: IDS[&LEADER],
+            follower: IDS[&FOLLOWER],
+            guardian,
+            token: GANACHE_INFO_1337.tokens["Mocked TOKEN 1337"].address,
+            nonce: Nonce::from(nonce),
+        });
+
+        for channel in channels {
+            let channel_context = app
+                .config
+                .find_chain_of(channel.token)
+                .expect("Dummy channel Token should be present in config!")
+                .with(channel);
+            insert_channel(&app.pool, &channel_context)
+                .await
+                .expect("should insert");
+        }
+
+        let query = |page: u64, guardian: Address| ChannelListQuery {
+            page,
+            validator: None,
+            guardian: Some(guardian),
+            chains: vec![],
+            limit: None,
+        };
 
-            insert_channel(&app.pool, &channel_context)
+        // Guardian of 2 channels - 1 channel per page
+        {
+            let first_page = channel_list(app.clone(), Qs(query(0, *GUARDIAN)))
                 .await
-                .expect("should insert channel");
+                .expect("should get channels")
+                .0;
+            assert_eq!(vec![channels[0]], first_page.channels);
+            assert_eq!(2, first_page.pagination.total_pages);
 
-            // Case when the request is not sent by a channel validator
-            {
-                let auth = Auth {
-                    era: 0,
-                    uid: IDS[&ADVERTISER],
-                    chain: channel_context.chain.clone(),
-                };
+            let second_page = channel_list(app.clone(), Qs(query(1, *GUARDIAN)))
+                .await
+                .expect("should get channels")
+                .0;
+            assert_eq!(vec![channels[2]], second_page.channels);
+            assert_eq!(2, second_page.pagination.total_pages);
+        }
 
-                let req = ValidatorMessagesCreateRequest { messages: vec![] };
+        // Guardian of a single channel
+        {
+            let channels_list = channel_list(app.clone(), Qs(query(0, *GUARDIAN_2)))
+                .await
+                .expect("should get channels")
+                .0;
+            assert_eq!(vec![channels[1]], channels_list.channels);
+            assert_eq!(1, channels_list.pagination.total_pages);
+        }
 
-                let res = create_validator_messages(
-                    app.clone(),
-                    Extension(auth),
-                    channel_context.clone(),
-                    Json(req),
-                )
+        // Not a guardian of any channel
+        {
+            let channels_list = channel_list(app.clone(), Qs(query(0, *PUBLISHER)))
                 .await
-                .expect_err("We expect an error to be returned");
+                .expect("should get channels")
+                .0;
+            assert!(channels_list.channels.is_empty());
+            assert_eq!(1, channels_list.pagination.total_pages);
+        }
+    }
 
-                assert_eq!(res, ResponseError::Unauthorized);
-            }
+    #[tokio::test]
+    async fn create_channel_registers_the_channel() {
+        let mut app_guard = setup_dummy_app().await;
+        app_guard.config.sentry.spender_channel_registration = false;
 
-            // Cases where we insert validator messages
-            // Case with 0 messages
-            {
-                let auth = Auth {
-                    era: 0,
-                    uid: IDS[&LEADER],
-                    chain: channel_context.chain.clone(),
-                };
+        let app = Extension(Arc::new(app_guard.app.clone()));
 
-                let req = ValidatorMessagesCreateRequest { messages: vec![] };
+        let channel = DUMMY_CAMPAIGN.channel;
+        let channel_context = app
+            .config
+            .find_chain_of(channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with_channel(channel);
 
-                create_validator_messages(
-                    app.clone(),
-                    Extension(auth),
-                    channel_context.clone(),
-                    Json(req),
-                )
-                .await
-                .expect("should work");
+        let auth = |uid: ValidatorId| Auth {
+            era: 0,
+            uid,
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        };
 
-                let messages = get_validator_messages(
-                    &app.pool,
-                    &channel_context.context.id(),
-                    &Some(IDS[&LEADER]),
-                    &all_message_types,
-                    100,
-                )
-                .await
-                .expect("should get messages");
+        // Token is not whitelisted
+        {
+            let not_whitelisted = Channel {
+                token: *PUBLISHER,
+                ..channel
+            };
 
-                assert_eq!(messages.len(), 0);
-            }
-            // Case with 1 message
-            {
-                let auth = Auth {
-                    era: 0,
-                    uid: IDS[&LEADER],
-                    chain: channel_context.chain.clone(),
-                };
+            let result = create_channel(
+                app.clone(),
+                Extension(auth(IDS[&LEADER])),
+                Json(not_whitelisted),
+            )
+            .await;
 
-                let message = MessageTypes::Heartbeat(Heartbeat {
-                    signature: String::new(),
-                    state_root: String::new(),
-                    timestamp: Utc::now(),
-                });
+            assert_eq!(
+                ResponseError::ChannelTokenNotWhitelisted(*PUBLISHER),
+                result.expect_err("Should reject non-whitelisted token")
+            );
+        }
 
-                let req = ValidatorMessagesCreateRequest {
-                    messages: vec![message.clone()],
-                };
+        // Zero addresses are rejected
+        {
+            let zero_guardian = Channel {
+                guardian: Address::ZERO,
+                ..channel
+            };
+            let zero_token = Channel {
+                token: Address::ZERO,
+                ..channel
+            };
 
-                create_validator_messages(
+            for (field, zero_address_channel) in [
+                ("channel.guardian", zero_guardian),
+                ("channel.token", zero_token),
+            ] {
+                let result = create_channel(
                     app.clone(),
-                    Extension(auth),
-                    channel_context.clone(),
-                    Json(req),
-                )
-                .await
-                .expect("Should create messages");
-
-                let messages = get_validator_messages(
-                    &app.pool,
-                    &channel_context.context.id(),
-                    &Some(IDS[&LEADER]),
-                    &all_message_types,
-                    100,
+                    Extension(auth(IDS[&LEADER])),
+                    Json(zero_address_channel),
                 )
-                .await
-                .expect("should get messages");
-                let inner_messages: Vec<MessageTypes> =
-                    messages.into_iter().map(|m| m.msg.clone()).collect();
-                assert_eq!(inner_messages, vec![message]);
+                .await;
+
+                assert_eq!(
+                    ResponseError::FailedValidation(format!(
+                        "Validation error: ZeroAddress(\"{field}\")"
+                    )),
+                    result.expect_err("Should reject the zero address")
+                );
             }
-            // Case with 4 messages (one of each type, heartbeat is already inserted)
-            {
-                let auth = Auth {
-                    era: 0,
-                    uid: IDS[&LEADER],
-                    chain: channel_context.chain.clone(),
-                };
+        }
 
-                let state_root =
-                    "b1a4fc6c1a1e1ab908a487e504006edcebea297f61b4b8ce6cad3b29e29454cc".to_string();
-                let signature = app
-                    .adapter
-                    .clone()
-                    .unlock()
-                    .expect("should unlock")
-                    .sign(&state_root.clone())
-                    .expect("should sign");
-                let new_state = MessageTypes::NewState(NewState {
-                    state_root: state_root.clone(),
-                    signature: signature.clone(),
-                    balances: Balances::<UncheckedState>::new(),
-                });
+        // Not a validator or the guardian of the Channel
+        {
+            let result = create_channel(
+                app.clone(),
+                Extension(auth(IDS[&ADVERTISER])),
+                Json(channel),
+            )
+            .await;
 
-                let approve_state = MessageTypes::ApproveState(ApproveState {
-                    state_root: state_root.clone(),
-                    signature: signature.clone(),
-                    is_healthy: true,
-                });
+            assert!(matches!(result, Err(ResponseError::Forbidden(_))));
+        }
 
-                let reject_state = MessageTypes::RejectState(RejectState {
-                    state_root,
-                    signature: signature.clone(),
-                    timestamp: Utc::now(),
-                    reason: "rejected".to_string(),
-                    balances: None,
-                });
+        // Creates the Channel
+        {
+            let created =
+                create_channel(app.clone(), Extension(auth(IDS[&FOLLOWER])), Json(channel))
+                    .await
+                    .expect("Should create Channel")
+                    .0;
 
-                let req = ValidatorMessagesCreateRequest {
-                    messages: vec![
-                        new_state.clone(),
-                        approve_state.clone(),
-                        reject_state.clone(),
-                    ],
-                };
+            assert_eq!(channel, created);
+        }
 
-                create_validator_messages(
-                    app.clone(),
-                    Extension(auth),
-                    channel_context.clone(),
-                    Json(req),
-                )
-                .await
-                .expect("Should create messages");
+        // Re-posting the Channel is idempotent
+        {
+            let existing =
+                create_channel(app.clone(), Extension(auth(IDS[&LEADER])), Json(channel))
+                    .await
+                    .expect("Should return the existing Channel")
+                    .0;
 
-                let messages = get_validator_messages(
-                    &app.pool,
-                    &channel_context.context.id(),
-                    &Some(IDS[&LEADER]),
-                    &all_message_types,
-                    100,
-                )
+            assert_eq!(channel, existing);
+
+            let query = ChannelListQuery {
+                page: 0,
+                validator: None,
+                guardian: None,
+                chains: vec![],
+                limit: None,
+            };
+            let channels_list = channel_list(app.clone(), Qs(query))
                 .await
-                .expect("should get messages");
-                assert_eq!(messages.len(), 4);
-            }
+                .expect("should get channels")
+                .0;
+            assert_eq!(vec![channel], channels_list.channels);
         }
     }
-}
-
-#[cfg(test)]
-mod test {
-    use std::str::FromStr;
-
-    use super::*;
-    use crate::{
-        db::{
-            insert_campaign, insert_channel, validator_message::insert_validator_message,
-            CampaignRemaining,
-        },
-        test_util::setup_dummy_app,
-    };
-
-    use adapter::{
-        ethereum::test_util::{GANACHE_INFO_1, GANACHE_INFO_1337},
-        prelude::Unlocked,
-        primitives::Deposit as AdapterDeposit,
-    };
-    use primitives::{
-        balances::UncheckedState,
-        channel::Nonce,
-        test_util::{
-            ADVERTISER, CREATOR, DUMMY_CAMPAIGN, FOLLOWER, GUARDIAN, IDS, LEADER, LEADER_2,
-            PUBLISHER, PUBLISHER_2,
-        },
-        validator::{ApproveState, MessageTypes, NewState},
-        BigNum, ChainId, Deposit, UnifiedMap, ValidatorId,
-    };
 
     #[tokio::test]
-    async fn create_and_fetch_spendable() {
-        let app = setup_dummy_app().await;
+    async fn create_channel_by_a_prospective_spender() {
+        let mut app_guard = setup_dummy_app().await;
+        app_guard.config.sentry.spender_channel_registration = true;
 
-        let (channel_context, channel) = {
-            let channel = DUMMY_CAMPAIGN.channel;
-            let channel_context = app
-                .config
-                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
-                .expect("should retrieve Chain & token");
+        let app = Extension(Arc::new(app_guard.app.clone()));
 
-            (channel_context.with_channel(channel), channel)
-        };
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with_channel(DUMMY_CAMPAIGN.channel);
 
-        let precision: u8 = channel_context.token.precision.into();
-        let deposit = AdapterDeposit {
-            total: BigNum::from_str("100000000000000000000").expect("should convert"), // 100 DAI
-        };
-        app.adapter
-            .client
-            .set_deposit(&channel_context, *CREATOR, deposit.clone());
-        // Making sure spendable does not yet exist
-        let spendable = fetch_spendable(app.pool.clone(), &CREATOR, &channel.id())
-            .await
-            .expect("should return None");
-        assert!(spendable.is_none());
-        // Call create_or_update_spendable
-        let new_spendable = create_or_update_spendable_document(
-            &app.adapter,
-            app.pool.clone(),
-            &channel_context,
-            *CREATOR,
-        )
-        .await
-        .expect("should create a new spendable");
-        assert_eq!(new_spendable.channel.id(), channel.id());
+        let auth = Auth {
+            era: 0,
+            uid: IDS[&ADVERTISER],
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        };
 
-        let total_as_unified_num =
-            UnifiedNum::from_precision(deposit.total, precision).expect("should convert");
+        let created = create_channel(app.clone(), Extension(auth), Json(DUMMY_CAMPAIGN.channel))
+            .await
+            .expect("Should create Channel")
+            .0;
 
-        assert_eq!(new_spendable.deposit.total, total_as_unified_num);
+        assert_eq!(DUMMY_CAMPAIGN.channel, created);
+    }
 
-        assert_eq!(new_spendable.spender, *CREATOR);
+    #[tokio::test]
+    async fn payouts_for_earners_test() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
 
-        // Make sure spendable NOW exists
-        let spendable = fetch_spendable(app.pool.clone(), &CREATOR, &channel.id())
+        let channel_context = Extension(
+            app.config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Dummy channel Token should be present in config!")
+                .with(DUMMY_CAMPAIGN.channel),
+        );
+
+        insert_channel(&app.pool, &channel_context)
             .await
-            .expect("should return a spendable");
-        assert!(spendable.is_some());
+            .expect("should insert channel");
+        insert_campaign(&app.pool, &DUMMY_CAMPAIGN)
+            .await
+            .expect("should insert the campaign");
 
-        let updated_deposit = AdapterDeposit {
-            total: BigNum::from_str("110000000000000000000").expect("should convert"), // 110 DAI
-        };
+        // Setting the initial remaining to 0
+        let campaign_remaining = app.campaign_remaining.clone();
+        campaign_remaining
+            .set_initial(DUMMY_CAMPAIGN.id, UnifiedNum::from_u64(0))
+            .await
+            .expect("Should set value in redis");
 
-        app.adapter
-            .client
-            .set_deposit(&channel_context, *CREATOR, updated_deposit.clone());
+        let auth = Extension(Auth {
+            era: 0,
+            uid: ValidatorId::from(DUMMY_CAMPAIGN.creator),
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        });
 
-        let updated_spendable = create_or_update_spendable_document(
-            &app.adapter,
-            app.pool.clone(),
-            &channel_context,
-            *CREATOR,
+        let mut payouts = UnifiedMap::default();
+        payouts.insert(*PUBLISHER, UnifiedNum::from_u64(500));
+        let to_pay = Json(ChannelPayRequest { payouts });
+
+        // Testing before Accounting/Spendable are inserted
+        {
+            let err_response = channel_payout(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                HeaderMap::new(),
+                to_pay.clone(),
+            )
+            .await
+            .expect_err("Should return an error when there is no Accounting/Spendable");
+            assert_eq!(
+                err_response,
+                ResponseError::BadRequest(
+                    "There is no spendable amount for the spender in this Channel".to_string()
+                ),
+                "Failed validation because payouts are empty"
+            );
+        }
+
+        // Add accounting for spender = 500
+        update_accounting(
+            app_guard.pool.clone(),
+            channel_context.context.id(),
+            auth.uid.to_address(),
+            Side::Spender,
+            UnifiedNum::from_u64(500),
         )
         .await
-        .expect("should update spendable");
-        let total_as_unified_num =
-            UnifiedNum::from_precision(updated_deposit.total, precision).expect("should convert");
+        .expect("should update");
 
-        assert_eq!(updated_spendable.deposit.total, total_as_unified_num);
-        assert_eq!(updated_spendable.spender, *CREATOR);
-    }
+        // Add spendable for the channel_context where total deposit = 1000
+        let spendable = Spendable {
+            spender: auth.uid.to_address(),
+            channel: channel_context.context,
+            deposit: Deposit {
+                total: UnifiedNum::from_u64(1000),
+            },
+        };
 
-    #[tokio::test]
-    async fn get_accountings_for_channel() {
-        let app_guard = setup_dummy_app().await;
+        // Add accounting for earner = 100
+        // available for return will be = 600
+        update_accounting(
+            app_guard.pool.clone(),
+            channel_context.context.id(),
+            auth.uid.to_address(),
+            Side::Earner,
+            UnifiedNum::from_u64(100),
+        )
+        .await
+        .expect("should update");
 
-        let app = Extension(Arc::new(app_guard.app.clone()));
-        let channel_context = app
-            .config
-            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
-            .expect("Dummy channel Token should be present in config!")
-            .with(DUMMY_CAMPAIGN.channel);
+        // Updating spendable so that we have a value for total_deposited
+        update_spendable(
+            app_guard.pool.clone(),
+            &app_guard.redis,
+            &app_guard.redis_keys,
+            &spendable,
+            SpendableSource::WorkerRefresh,
+        )
+        .await
+        .expect("Should update spendable");
 
-        insert_channel(&app.pool, &channel_context)
+        // Test with empty payouts
+        {
+            let to_pay = Json(ChannelPayRequest {
+                payouts: UnifiedMap::default(),
+            });
+            let err_response = channel_payout(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                HeaderMap::new(),
+                to_pay,
+            )
             .await
-            .expect("should insert channel");
+            .expect_err("Should return an error when payouts are empty");
 
-        // Testing for no accounting yet
+            assert_eq!(
+                err_response,
+                ResponseError::FailedValidation("Request has empty payouts".to_string()),
+                "Failed validation because payouts are empty"
+            );
+        }
+
+        // make a normal request and get accounting to see if its as expected
         {
-            let accounting_response =
-                get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
-                    .await
-                    .expect("shoul get accounting");
+            let success_response = channel_payout(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                HeaderMap::new(),
+                to_pay.clone(),
+            )
+            .await
+            .expect("This request shouldn't result in an error");
 
-            assert_eq!(accounting_response.balances.earners.len(), 0);
-            assert_eq!(accounting_response.balances.spenders.len(), 0);
+            assert_eq!(
+                SuccessResponse { success: true },
+                success_response.0,
+                "Request with JSON body with one address and no errors triggered on purpose"
+            );
         }
 
-        // Testing for 2 accountings - first channel
+        // Checking if Earner/Spender balances have been updated by looking up the Accounting in the database
         {
-            let mut balances = Balances::<CheckedState>::new();
-            balances
-                .spend(*CREATOR, *PUBLISHER, UnifiedNum::from_u64(200))
-                .expect("should not overflow");
-            balances
-                .spend(*CREATOR, *PUBLISHER_2, UnifiedNum::from_u64(100))
-                .expect("Should not overflow");
-            spend_amount(
-                app.pool.clone(),
+            let earner_accounting = get_accounting(
+                app_guard.pool.clone(),
                 channel_context.context.id(),
-                balances.clone(),
+                *PUBLISHER,
+                Side::Earner,
             )
             .await
-            .expect("should spend");
+            .expect("should get accounting")
+            .expect("Should have value, i.e. Some");
+            assert_eq!(
+                earner_accounting.amount,
+                UnifiedNum::from_u64(500),
+                "Accounting is updated to reflect the publisher's earnings"
+            );
 
-            let accounting_response =
-                get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
-                    .await
-                    .expect("should get accounting");
+            let spender_accounting = get_accounting(
+                app_guard.pool.clone(),
+                channel_context.context.id(),
+                auth.uid.to_address(),
+                Side::Spender,
+            )
+            .await
+            .expect("should get accounting")
+            .expect("Should have value, i.e. Some");
 
-            assert_eq!(balances, accounting_response.balances);
+            assert_eq!(
+                spender_accounting.amount,
+                UnifiedNum::from_u64(1000), // 500 initial + 500 for earners
+                "Accounting is updated to reflect the amount deducted from the spender"
+            );
         }
 
-        // Testing for 2 accountings - second channel (same address is both an earner and a spender)
+        // make a request where "total_to_pay" will exceed available
         {
-            let mut second_channel = DUMMY_CAMPAIGN.channel;
-            second_channel.leader = IDS[&ADVERTISER]; // channel.id() will be different now
-            let channel_context = app
-                .config
-                .find_chain_of(second_channel.token)
-                .expect("Dummy channel Token should be present in config!")
-                .with(second_channel);
-            insert_channel(&app.pool, &channel_context)
-                .await
-                .expect("should insert channel");
+            let mut payouts = to_pay.payouts.clone();
+            payouts.insert(*PUBLISHER_2, UnifiedNum::from_u64(1000));
+            let to_pay_exceed = Json(ChannelPayRequest { payouts });
 
-            let mut balances = Balances::<CheckedState>::new();
-            balances
-                .spend(*CREATOR, *PUBLISHER, 300.into())
-                .expect("Should not overflow");
+            let response_error = channel_payout(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                HeaderMap::new(),
+                to_pay_exceed,
+            )
+            .await
+            .expect_err("Should return an error when total_to_pay > available_for_payout");
 
-            balances
-                .spend(*PUBLISHER, *ADVERTISER, 300.into())
-                .expect("Should not overflow");
+            assert_eq!(
+                ResponseError::FailedValidation(
+                    "The total requested payout amount exceeds the available payout".to_string()
+                ),
+                response_error,
+                "Failed validation because total_to_pay > available_for_payout"
+            );
+        }
 
-            spend_amount(app.pool.clone(), second_channel.id(), balances.clone())
-                .await
-                .expect("should spend");
+        // make a request where total - spent + earned will be a negative balance resulting in an error
+        {
+            update_accounting(
+                app_guard.pool.clone(),
+                channel_context.context.id(),
+                auth.uid.to_address(),
+                Side::Spender,
+                UnifiedNum::from_u64(1000),
+            )
+            .await
+            .expect("should update"); // total spent: 500 + 1000
 
-            let accounting_response =
-                get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
-                    .await
-                    .expect("shoul get accounting");
+            let response_error = channel_payout(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                HeaderMap::new(),
+                to_pay.clone(),
+            )
+            .await
+            .expect_err("Should return err when available_for_payout is negative");
 
-            assert_eq!(balances, accounting_response.balances)
+            assert_eq!(
+                ResponseError::FailedValidation(
+                    "Underflow while calculating available for payout".to_string()
+                ),
+                response_error,
+                "Failed validation because available_for_payout is negative"
+            );
         }
 
-        // Testing for when sums don't match on first channel - Error case
+        // make a request where campaigns will have available remaining
         {
-            let mut balances = Balances::<CheckedState>::new();
-            balances
-                .earners
-                .insert(*PUBLISHER, UnifiedNum::from_u64(100));
-            balances
-                .spenders
-                .insert(*CREATOR, UnifiedNum::from_u64(200));
-            spend_amount(app.pool.clone(), channel_context.context.id(), balances)
+            campaign_remaining
+                .increase_by(DUMMY_CAMPAIGN.id, UnifiedNum::from_u64(1000))
                 .await
-                .expect("should spend");
+                .expect("Should set value in redis");
 
-            let res =
-                get_accounting_for_channel(app.clone(), Extension(channel_context.clone())).await;
-            let expected = ResponseError::FailedValidation(
-                "Earners sum is not equal to spenders sum for channel".to_string(),
+            let response_error =
+                channel_payout(app, channel_context, auth, HeaderMap::new(), to_pay)
+                    .await
+                    .expect_err("Should return an error when a campaign has remaining funds");
+
+            assert_eq!(
+                ResponseError::FailedValidation(
+                    "All campaigns should be closed or have no budget left".to_string()
+                ),
+                response_error,
+                "Failed validation because the campaign has remaining funds"
             );
-            assert_eq!(expected, res.expect_err("Should return an error"));
         }
     }
 
     #[tokio::test]
-    async fn adds_and_retrieves_spender_leaf() {
+    async fn payout_with_idempotency_key_is_applied_once() {
         let app_guard = setup_dummy_app().await;
-
         let app = Extension(Arc::new(app_guard.app.clone()));
 
-        let channel_context = app
-            .config
-            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
-            .expect("Dummy channel Token should be present in config!")
-            .with(DUMMY_CAMPAIGN.channel);
-
-        let deposit = AdapterDeposit {
-            total: BigNum::from_str("100000000000000000000").expect("should convert"), // 100 DAI
-        };
-        app.adapter
-            .client
-            .set_deposit(&channel_context, *CREATOR, deposit.clone());
+        let channel_context = Extension(
+            app.config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Dummy channel Token should be present in config!")
+                .with(DUMMY_CAMPAIGN.channel),
+        );
 
         insert_channel(&app.pool, &channel_context)
             .await
             .expect("should insert channel");
+        insert_campaign(&app.pool, &DUMMY_CAMPAIGN)
+            .await
+            .expect("should insert the campaign");
 
-        // Calling with non existent accounting
-        let _res = add_spender_leaf(
-            app.clone(),
-            Extension(channel_context.clone()),
-            Path((channel_context.context.id(), *CREATOR)),
+        app.campaign_remaining
+            .clone()
+            .set_initial(DUMMY_CAMPAIGN.id, UnifiedNum::from_u64(0))
+            .await
+            .expect("Should set value in redis");
+
+        let auth = Extension(Auth {
+            era: 0,
+            uid: ValidatorId::from(DUMMY_CAMPAIGN.creator),
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        });
+
+        let spendable = Spendable {
+            spender: auth.uid.to_address(),
+            channel: channel_context.context,
+            deposit: Deposit {
+                total: UnifiedNum::from_u64(1000),
+            },
+        };
+        update_spendable(
+            app_guard.pool.clone(),
+            &app_guard.redis,
+            &app_guard.redis_keys,
+            &spendable,
+            SpendableSource::WorkerRefresh,
         )
         .await
-        .expect("should get spender leaf");
-
-        let accounting_response =
-            get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
-                .await
-                .expect("should get accounting");
+        .expect("Should update spendable");
 
-        // Making sure a new entry has been created
-        assert_eq!(
-            accounting_response.balances.spenders.get(&CREATOR),
-            Some(&UnifiedNum::from_u64(0)),
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            IDEMPOTENCY_KEY_HEADER,
+            "payout-1".parse().expect("Should parse header value"),
         );
 
-        let mut balances = Balances::<CheckedState>::new();
-        balances
-            .spend(*CREATOR, *PUBLISHER, UnifiedNum::from_u64(200))
-            .expect("should not overflow");
-        balances
-            .spend(*ADVERTISER, *GUARDIAN, UnifiedNum::from_u64(100))
-            .expect("Should not overflow");
-        spend_amount(
-            app.pool.clone(),
+        let mut payouts = UnifiedMap::default();
+        payouts.insert(*PUBLISHER, UnifiedNum::from_u64(300));
+        let to_pay = Json(ChannelPayRequest { payouts });
+
+        // the first request and the replayed one
+        for _ in 0..2 {
+            let response = channel_payout(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                headers.clone(),
+                to_pay.clone(),
+            )
+            .await
+            .expect("Should pay or replay the payout");
+
+            assert_eq!(SuccessResponse { success: true }, response.0);
+        }
+
+        let spender_accounting = get_accounting(
+            app_guard.pool.clone(),
             channel_context.context.id(),
-            balances.clone(),
+            auth.uid.to_address(),
+            Side::Spender,
         )
         .await
-        .expect("should spend");
+        .expect("should get accounting")
+        .expect("Should have value, i.e. Some");
+        assert_eq!(
+            UnifiedNum::from_u64(300),
+            spender_accounting.amount,
+            "The spender should be debited only once"
+        );
 
-        let accounting_response =
-            get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
-                .await
-                .expect("should get accounting");
+        // a different payout with the same key
+        {
+            let mut payouts = UnifiedMap::default();
+            payouts.insert(*PUBLISHER, UnifiedNum::from_u64(400));
 
-        assert_eq!(balances, accounting_response.balances);
+            let response_error = channel_payout(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                headers.clone(),
+                Json(ChannelPayRequest { payouts }),
+            )
+            .await
+            .expect_err("Should not re-use the Idempotency-Key for another payout");
 
-        let res = add_spender_leaf(
-            app.clone(),
-            Extension(channel_context.clone()),
-            Path((channel_context.context.id(), *CREATOR)),
+            assert!(matches!(response_error, ResponseError::Conflict(_)));
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_payouts_with_the_same_idempotency_key_are_applied_once() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = Extension(
+            app.config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Dummy channel Token should be present in config!")
+                .with(DUMMY_CAMPAIGN.channel),
+        );
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+        insert_campaign(&app.pool, &DUMMY_CAMPAIGN)
+            .await
+            .expect("should insert the campaign");
+
+        app.campaign_remaining
+            .clone()
+            .set_initial(DUMMY_CAMPAIGN.id, UnifiedNum::from_u64(0))
+            .await
+            .expect("Should set value in redis");
+
+        let auth = Extension(Auth {
+            era: 0,
+            uid: ValidatorId::from(DUMMY_CAMPAIGN.creator),
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        });
+
+        let spendable = Spendable {
+            spender: auth.uid.to_address(),
+            channel: channel_context.context,
+            deposit: Deposit {
+                total: UnifiedNum::from_u64(1000),
+            },
+        };
+        update_spendable(
+            app_guard.pool.clone(),
+            &app_guard.redis,
+            &app_guard.redis_keys,
+            &spendable,
+            SpendableSource::WorkerRefresh,
         )
-        .await;
-        assert!(res.is_ok());
+        .await
+        .expect("Should update spendable");
 
-        let accounting_response =
-            get_accounting_for_channel(app.clone(), Extension(channel_context.clone()))
-                .await
-                .expect("should get accounting");
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            IDEMPOTENCY_KEY_HEADER,
+            "payout-1".parse().expect("Should parse header value"),
+        );
 
-        // Balances shouldn't change
+        let mut payouts = UnifiedMap::default();
+        payouts.insert(*PUBLISHER, UnifiedNum::from_u64(300));
+        let to_pay = Json(ChannelPayRequest { payouts });
+
+        let (first, second) = tokio::join!(
+            channel_payout(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                headers.clone(),
+                to_pay.clone(),
+            ),
+            channel_payout(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                headers.clone(),
+                to_pay.clone(),
+            )
+        );
+
+        // either the second payout is replayed or it is rejected while the first one is in progress
+        for result in [first, second] {
+            match result {
+                Ok(response) => assert_eq!(SuccessResponse { success: true }, response.0),
+                Err(err) => assert!(
+                    matches!(err, ResponseError::Conflict(_)),
+                    "Should be a Conflict, got: {:?}",
+                    err
+                ),
+            }
+        }
+
+        let spender_accounting = get_accounting(
+            app_guard.pool.clone(),
+            channel_context.context.id(),
+            auth.uid.to_address(),
+            Side::Spender,
+        )
+        .await
+        .expect("should get accounting")
+        .expect("Should have value, i.e. Some");
         assert_eq!(
-            accounting_response.balances.spenders.get(&CREATOR),
-            balances.spenders.get(&CREATOR),
+            UnifiedNum::from_u64(300),
+            spender_accounting.amount,
+            "The spender should be debited only once"
         );
     }
 
     #[tokio::test]
-    async fn get_channels_list() {
-        let mut app_guard = setup_dummy_app().await;
-        app_guard.config.limits.channels_find = 2;
-
+    async fn payout_preview_matches_the_payout() {
+        let app_guard = setup_dummy_app().await;
         let app = Extension(Arc::new(app_guard.app.clone()));
 
-        let channel = Channel {
-            leader: IDS[&LEADER],
-            follower: IDS[&FOLLOWER],
-            guardian: *GUARDIAN,
-            token: GANACHE_INFO_1337.tokens["Mocked TOKEN 1337"].address,
-            nonce: Nonce::from(987_654_321_u32),
-        };
-        let channel_context = app
-            .config
-            .find_chain_of(channel.token)
-            .expect("Dummy channel Token should be present in config!")
-            .with(channel);
-        insert_channel(&app.pool, &channel_context)
-            .await
-            .expect("should insert");
+        let channel_context = Extension(
+            app.config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Dummy channel Token should be present in config!")
+                .with(DUMMY_CAMPAIGN.channel),
+        );
 
-        let channel_other_token = Channel {
-            leader: IDS[&LEADER],
-            follower: IDS[&FOLLOWER],
-            guardian: *GUARDIAN,
-            token: GANACHE_INFO_1.tokens["Mocked TOKEN 1"].address,
-            nonce: Nonce::from(987_654_322_u32),
-        };
-        let channel_context = app
-            .config
-            .find_chain_of(channel_other_token.token)
-            .expect("Dummy channel Token should be present in config!")
-            .with(channel_other_token);
         insert_channel(&app.pool, &channel_context)
             .await
-            .expect("should insert");
+            .expect("should insert channel");
+        insert_campaign(&app.pool, &DUMMY_CAMPAIGN)
+            .await
+            .expect("should insert the campaign");
 
-        let channel_other_leader = Channel {
-            leader: IDS[&LEADER_2],
-            follower: IDS[&FOLLOWER],
-            guardian: *GUARDIAN,
-            token: GANACHE_INFO_1337.tokens["Mocked TOKEN 1337"].address,
-            nonce: Nonce::from(987_654_323_u32),
-        };
-        let channel_context = app
-            .config
-            .find_chain_of(channel_other_leader.token)
-            .expect("Dummy channel Token should be present in config!")
-            .with(channel_other_leader);
-        insert_channel(&app.pool, &channel_context)
+        // the campaign is still open
+        app.campaign_remaining
+            .set_initial(DUMMY_CAMPAIGN.id, UnifiedNum::from_u64(100))
             .await
-            .expect("should insert");
+            .expect("Should set value in redis");
 
-        // Test query page - page 0, page 1
-        {
-            let query = ChannelListQuery {
-                page: 0,
-                validator: None,
-                chains: vec![],
-            };
+        let auth = Extension(Auth {
+            era: 0,
+            uid: ValidatorId::from(DUMMY_CAMPAIGN.creator),
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        });
 
-            let channels_list = channel_list(app.clone(), Qs(query))
+        let spendable = Spendable {
+            spender: auth.uid.to_address(),
+            channel: channel_context.context,
+            deposit: Deposit {
+                total: UnifiedNum::from_u64(1000),
+            },
+        };
+        update_spendable(
+            app_guard.pool.clone(),
+            &app_guard.redis,
+            &app_guard.redis_keys,
+            &spendable,
+            SpendableSource::WorkerRefresh,
+        )
+        .await
+        .expect("Should update spendable");
+
+        let to_pay = |payouts: &[(Address, u64)]| {
+            Json(ChannelPayRequest {
+                payouts: payouts
+                    .iter()
+                    .map(|(earner, amount)| (*earner, UnifiedNum::from_u64(*amount)))
+                    .collect(),
+            })
+        };
+        let preview = |payouts: &[(Address, u64)]| {
+            channel_payout_preview(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                to_pay(payouts),
+            )
+        };
+        let payout = |payouts: &[(Address, u64)]| {
+            channel_payout(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                HeaderMap::new(),
+                to_pay(payouts),
+            )
+        };
+
+        // the campaign is not closed yet
+        {
+            let response = preview(&[(*PUBLISHER, 300)])
                 .await
-                .expect("should get channels")
+                .expect("Should preview the payout")
                 .0;
 
+            let campaigns_open = "All campaigns should be closed or have no budget left";
+            assert!(!response.valid);
+            assert_eq!(vec![campaigns_open.to_string()], response.errors);
             assert_eq!(
-                channels_list.channels,
-                vec![channel, channel_other_token],
-                "The channels should be listed by ascending order of their creation"
+                Some(UnifiedNum::from_u64(1000)),
+                response.available_for_payout
             );
+            assert_eq!(Some(UnifiedNum::from_u64(300)), response.total_to_pay);
+
+            let error = payout(&[(*PUBLISHER, 300)])
+                .await
+                .expect_err("Should reject the payout");
             assert_eq!(
-                channels_list.pagination.total_pages, 2,
-                "There should be 2 pages in total"
+                ResponseError::FailedValidation(campaigns_open.to_string()),
+                error
             );
+        }
 
-            let query = ChannelListQuery {
-                page: 1,
-                validator: None,
-                chains: vec![],
-            };
-            let channels_list = channel_list(app.clone(), Qs(query))
+        app.campaign_remaining
+            .decrease_by(DUMMY_CAMPAIGN.id, UnifiedNum::from_u64(100))
+            .await
+            .expect("Should close the campaign");
+
+        // more than the available payout
+        {
+            let response = preview(&[(*PUBLISHER, 700), (*PUBLISHER_2, 500)])
                 .await
-                .expect("should get channels")
+                .expect("Should preview the payout")
                 .0;
 
-            assert_eq!(
-                channels_list.channels,
-                vec![channel_other_leader],
-                "The channels should be listed by ascending order of their creation"
-            );
+            let exceeds = "The total requested payout amount exceeds the available payout";
+            assert!(!response.valid);
+            assert_eq!(vec![exceeds.to_string()], response.errors);
+            assert_eq!(Some(UnifiedNum::from_u64(1200)), response.total_to_pay);
+            assert!(response.payouts.iter().all(|payout| payout.error.is_none()));
+
+            let error = payout(&[(*PUBLISHER, 700), (*PUBLISHER_2, 500)])
+                .await
+                .expect_err("Should reject the payout");
+            assert_eq!(ResponseError::FailedValidation(exceeds.to_string()), error);
         }
 
-        // Test query validator - query with validator ID
+        // an invalid payout to a single earner
         {
-            let query = ChannelListQuery {
-                page: 0,
-                validator: Some(IDS[&LEADER_2]),
-                chains: vec![],
-            };
-            let channels_list = channel_list(app.clone(), Qs(query))
+            let response = preview(&[(*PUBLISHER, 300), (*PUBLISHER_2, 0)])
                 .await
-                .expect("should get channels")
+                .expect("Should preview the payout")
                 .0;
 
-            assert_eq!(
-                channels_list.channels,
-                vec![channel_other_leader],
-                "Response returns the correct channel"
-            );
-            assert_eq!(
-                channels_list.pagination.total_pages, 1,
-                "There should be 1 page in total"
-            );
+            let zero_amount = format!("Payout to earner {} has a zero amount", *PUBLISHER_2);
+            assert!(!response.valid);
+            assert!(response.errors.is_empty());
+            for expected in [
+                PayoutPreview {
+                    earner: *PUBLISHER,
+                    amount: UnifiedNum::from_u64(300),
+                    error: None,
+                },
+                PayoutPreview {
+                    earner: *PUBLISHER_2,
+                    amount: UnifiedNum::ZERO,
+                    error: Some(zero_amount.clone()),
+                },
+            ] {
+                assert!(response.payouts.contains(&expected), "{expected:?}");
+            }
+
+            let error = payout(&[(*PUBLISHER, 300), (*PUBLISHER_2, 0)])
+                .await
+                .expect_err("Should reject the payout");
+            assert_eq!(ResponseError::FailedValidation(zero_amount), error);
         }
-        // Test query with both pagination and validator by querying for the follower validator
+
+        // a valid payout is accepted and the available payout is updated
         {
-            let query = ChannelListQuery {
-                page: 0,
-                validator: Some(IDS[&FOLLOWER]),
-                chains: vec![],
-            };
-            let channels_list = channel_list(app.clone(), Qs(query))
+            let response = preview(&[(*PUBLISHER, 300)])
                 .await
-                .expect("should get channels");
-
-            assert_eq!(
-                channels_list.pagination.total_pages, 2,
-                "There should be 2 pages in total"
-            );
-            assert_eq!(
-                channels_list.channels,
-                vec![channel, channel_other_token],
-                "The channels should be listed by ascending order of their creation"
-            );
+                .expect("Should preview the payout")
+                .0;
+            assert!(response.valid);
+            assert!(response.errors.is_empty());
 
-            let query = ChannelListQuery {
-                page: 1,
-                validator: Some(IDS[&FOLLOWER]),
-                chains: vec![],
-            };
-            let channels_list = channel_list(app.clone(), Qs(query))
+            payout(&[(*PUBLISHER, 300)])
                 .await
-                .expect("should get channels");
+                .expect("Should accept the payout");
 
+            let response = preview(&[(*PUBLISHER, 300)])
+                .await
+                .expect("Should preview the payout")
+                .0;
+            assert!(response.valid);
             assert_eq!(
-                channels_list.channels,
-                vec![channel_other_leader],
-                "The channels should be listed by ascending order of their creation"
-            );
-            assert_eq!(
-                channels_list.pagination.total_pages, 2,
-                "There should be 2 pages in total"
+                Some(UnifiedNum::from_u64(700)),
+                response.available_for_payout
             );
         }
+    }
 
-        // Test query with different chains
-        {
-            let limit_app = {
-                let mut limit_app = app_guard.app;
-                limit_app.config.limits.channels_find = 10; // no need to test pagination, will ease checking results for this cause
+    #[tokio::test]
+    async fn close_and_pay_closes_the_campaigns_and_pays_in_one_call() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
 
-                Extension(Arc::new(limit_app))
-            };
+        let channel_context = Extension(
+            app.config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Dummy channel Token should be present in config!")
+                .with(DUMMY_CAMPAIGN.channel),
+        );
 
-            let query_1 = ChannelListQuery {
-                page: 0,
-                validator: Some(IDS[&FOLLOWER]),
-                chains: vec![ChainId::new(1)],
-            };
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
 
-            let channels_list = channel_list(limit_app.clone(), Qs(query_1))
-                .await
-                .expect("should get channels");
+        // the open Campaign of the spender
+        insert_campaign(&app.pool, &DUMMY_CAMPAIGN)
+            .await
+            .expect("should insert the campaign");
+        app.campaign_remaining
+            .set_initial(DUMMY_CAMPAIGN.id, DUMMY_CAMPAIGN.budget)
+            .await
+            .expect("Should set value in redis");
 
-            assert_eq!(
-                channels_list.channels,
-                vec![channel_other_token],
-                "Response returns the correct channel"
-            );
+        // an open Campaign of another creator
+        let other_campaign = {
+            let mut campaign = DUMMY_CAMPAIGN.clone();
+            campaign.id = CampaignId::new();
+            campaign.creator = *ADVERTISER;
+            campaign
+        };
+        insert_campaign(&app.pool, &other_campaign)
+            .await
+            .expect("should insert the campaign");
+        app.campaign_remaining
+            .set_initial(other_campaign.id, UnifiedNum::from_u64(100))
+            .await
+            .expect("Should set value in redis");
 
-            let query_1337 = ChannelListQuery {
-                page: 0,
-                validator: Some(IDS[&FOLLOWER]),
-                chains: vec![ChainId::new(1337)],
-            };
+        let auth = Extension(Auth {
+            era: 0,
+            uid: ValidatorId::from(DUMMY_CAMPAIGN.creator),
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        });
 
-            let channels_list = channel_list(limit_app.clone(), Qs(query_1337))
-                .await
-                .expect("should get channels");
+        let spendable = Spendable {
+            spender: auth.uid.to_address(),
+            channel: channel_context.context,
+            deposit: Deposit {
+                total: UnifiedNum::from_u64(1000),
+            },
+        };
+        update_spendable(
+            app_guard.pool.clone(),
+            &app_guard.redis,
+            &app_guard.redis_keys,
+            &spendable,
+            SpendableSource::WorkerRefresh,
+        )
+        .await
+        .expect("Should update spendable");
+
+        let mut payouts = UnifiedMap::default();
+        payouts.insert(*PUBLISHER, UnifiedNum::from_u64(300));
+        let to_pay = Json(ChannelPayRequest { payouts });
+
+        // the Campaign of the other creator is still open
+        {
+            let response_error = channel_close_and_pay(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                HeaderMap::new(),
+                to_pay.clone(),
+            )
+            .await
+            .expect_err("Should not pay with an open Campaign of another creator");
 
             assert_eq!(
-                channels_list.channels,
-                vec![channel, channel_other_leader],
-                "Response returns the correct channel"
+                ResponseError::FailedValidation(
+                    "All campaigns should be closed or have no budget left".to_string()
+                ),
+                response_error
+            );
+            assert_eq!(
+                Some(DUMMY_CAMPAIGN.budget.to_u64() as i64),
+                app.campaign_remaining
+                    .get_remaining_opt(DUMMY_CAMPAIGN.id)
+                    .await
+                    .expect("Should get remaining"),
+                "The spender's Campaign should not be closed"
             );
+        }
 
-            let query_both_chains = ChannelListQuery {
-                page: 0,
-                validator: Some(IDS[&FOLLOWER]),
-                chains: vec![ChainId::new(1), ChainId::new(1337)],
-            };
+        app.campaign_remaining
+            .getset_remaining_to_zero(other_campaign.id)
+            .await
+            .expect("Should close the other Campaign");
 
-            let channels_list = channel_list(limit_app, Qs(query_both_chains))
+        let response = channel_close_and_pay(
+            app.clone(),
+            channel_context.clone(),
+            auth.clone(),
+            HeaderMap::new(),
+            to_pay,
+        )
+        .await
+        .expect("Should close the Campaigns and pay");
+        assert_eq!(SuccessResponse { success: true }, response.0);
+
+        // the Campaign is closed
+        assert_eq!(
+            Some(0),
+            app.campaign_remaining
+                .get_remaining_opt(DUMMY_CAMPAIGN.id)
                 .await
-                .expect("should get channels");
+                .expect("Should get remaining")
+        );
+        let closed = fetch_campaign(app.pool.clone(), &DUMMY_CAMPAIGN.id)
+            .await
+            .expect("Should fetch the Campaign")
+            .expect("Should exist");
+        assert_eq!(UnifiedNum::from_u64(0), closed.budget);
 
-            assert_eq!(
-                channels_list.channels,
-                vec![channel, channel_other_token, channel_other_leader],
-                "Response returns the correct channel"
-            );
-        }
+        // and paid
+        let spender_accounting = get_accounting(
+            app_guard.pool.clone(),
+            channel_context.context.id(),
+            auth.uid.to_address(),
+            Side::Spender,
+        )
+        .await
+        .expect("should get accounting")
+        .expect("Should have value, i.e. Some");
+        assert_eq!(UnifiedNum::from_u64(300), spender_accounting.amount);
+        let earner_accounting = get_accounting(
+            app_guard.pool.clone(),
+            channel_context.context.id(),
+            *PUBLISHER,
+            Side::Earner,
+        )
+        .await
+        .expect("should get accounting")
+        .expect("Should have value, i.e. Some");
+        assert_eq!(UnifiedNum::from_u64(300), earner_accounting.amount);
     }
 
     #[tokio::test]
-    async fn payouts_for_earners_test() {
+    async fn close_and_pay_reopens_the_campaigns_when_the_payout_fails() {
         let app_guard = setup_dummy_app().await;
         let app = Extension(Arc::new(app_guard.app.clone()));
 
@@ -1461,11 +4307,8 @@ mod test {
         insert_campaign(&app.pool, &DUMMY_CAMPAIGN)
             .await
             .expect("should insert the campaign");
-
-        // Setting the initial remaining to 0
-        let campaign_remaining = CampaignRemaining::new(app.redis.clone());
-        campaign_remaining
-            .set_initial(DUMMY_CAMPAIGN.id, UnifiedNum::from_u64(0))
+        app.campaign_remaining
+            .set_initial(DUMMY_CAMPAIGN.id, DUMMY_CAMPAIGN.budget)
             .await
             .expect("Should set value in redis");
 
@@ -1473,209 +4316,428 @@ mod test {
             era: 0,
             uid: ValidatorId::from(DUMMY_CAMPAIGN.creator),
             chain: channel_context.chain.clone(),
+            scope: Scope::Full,
         });
 
+        let spendable = Spendable {
+            spender: auth.uid.to_address(),
+            channel: channel_context.context,
+            deposit: Deposit {
+                total: UnifiedNum::from_u64(1000),
+            },
+        };
+        update_spendable(
+            app_guard.pool.clone(),
+            &app_guard.redis,
+            &app_guard.redis_keys,
+            &spendable,
+            SpendableSource::WorkerRefresh,
+        )
+        .await
+        .expect("Should update spendable");
+
+        // the payout checks pass, but the spending is rejected
+        set_accounting_frozen(&app.pool, channel_context.context.id(), true)
+            .await
+            .expect("Should freeze the accounting");
+
         let mut payouts = UnifiedMap::default();
-        payouts.insert(*PUBLISHER, UnifiedNum::from_u64(500));
-        let to_pay = Json(ChannelPayRequest { payouts });
+        payouts.insert(*PUBLISHER, UnifiedNum::from_u64(300));
 
-        // Testing before Accounting/Spendable are inserted
-        {
-            let err_response = channel_payout(
-                app.clone(),
-                channel_context.clone(),
-                auth.clone(),
-                to_pay.clone(),
-            )
+        channel_close_and_pay(
+            app.clone(),
+            channel_context.clone(),
+            auth.clone(),
+            HeaderMap::new(),
+            Json(ChannelPayRequest { payouts }),
+        )
+        .await
+        .expect_err("Should not pay in a frozen Channel");
+
+        assert_eq!(
+            Some(DUMMY_CAMPAIGN.budget.to_u64() as i64),
+            app.campaign_remaining
+                .get_remaining_opt(DUMMY_CAMPAIGN.id)
+                .await
+                .expect("Should get remaining"),
+            "The remaining budget should be restored"
+        );
+        let campaign = fetch_campaign(app.pool.clone(), &DUMMY_CAMPAIGN.id)
             .await
-            .expect_err("Should return an error when there is no Accounting/Spendable");
-            assert_eq!(
-                err_response,
-                ResponseError::BadRequest(
-                    "There is no spendable amount for the spender in this Channel".to_string()
-                ),
-                "Failed validation because payouts are empty"
-            );
-        }
+            .expect("Should fetch the Campaign")
+            .expect("Should exist");
+        assert_eq!(DUMMY_CAMPAIGN.budget, campaign.budget);
 
-        // Add accounting for spender = 500
-        update_accounting(
+        let spender_accounting = get_accounting(
             app_guard.pool.clone(),
             channel_context.context.id(),
             auth.uid.to_address(),
             Side::Spender,
-            UnifiedNum::from_u64(500),
         )
         .await
-        .expect("should update");
+        .expect("should get accounting");
+        assert!(spender_accounting.is_none(), "Nothing should be paid");
+    }
+
+    #[tokio::test]
+    async fn payout_below_the_minimum_payout() {
+        let mut app_guard = setup_dummy_app().await;
+        // 0.000_01 TOKEN = 1_000 in UnifiedNum
+        app_guard
+            .config
+            .chains
+            .get_mut("Ganache #1337")
+            .expect("Should have Ganache #1337 chain")
+            .tokens
+            .get_mut("Mocked TOKEN 1337")
+            .expect("Should have Mocked TOKEN 1337")
+            .min_payout = Some(BigNum::from(10_000_000_000_000_u64));
+
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = Extension(
+            app.config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Dummy channel Token should be present in config!")
+                .with(DUMMY_CAMPAIGN.channel),
+        );
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+        insert_campaign(&app.pool, &DUMMY_CAMPAIGN)
+            .await
+            .expect("should insert the campaign");
+
+        app.campaign_remaining
+            .clone()
+            .set_initial(DUMMY_CAMPAIGN.id, UnifiedNum::from_u64(0))
+            .await
+            .expect("Should set value in redis");
+
+        let auth = Extension(Auth {
+            era: 0,
+            uid: ValidatorId::from(DUMMY_CAMPAIGN.creator),
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        });
 
-        // Add spendable for the channel_context where total deposit = 1000
         let spendable = Spendable {
             spender: auth.uid.to_address(),
             channel: channel_context.context,
             deposit: Deposit {
-                total: UnifiedNum::from_u64(1000),
+                total: UnifiedNum::from_u64(10_000),
             },
         };
-
-        // Add accounting for earner = 100
-        // available for return will be = 600
-        update_accounting(
+        update_spendable(
             app_guard.pool.clone(),
-            channel_context.context.id(),
-            auth.uid.to_address(),
-            Side::Earner,
-            UnifiedNum::from_u64(100),
+            &app_guard.redis,
+            &app_guard.redis_keys,
+            &spendable,
+            SpendableSource::WorkerRefresh,
         )
         .await
-        .expect("should update");
-
-        // Updating spendable so that we have a value for total_deposited
-        update_spendable(app_guard.pool.clone(), &spendable)
-            .await
-            .expect("Should update spendable");
+        .expect("Should update spendable");
 
-        // Test with empty payouts
+        // below the minimum payout
         {
-            let to_pay = Json(ChannelPayRequest {
-                payouts: UnifiedMap::default(),
-            });
-            let err_response =
-                channel_payout(app.clone(), channel_context.clone(), auth.clone(), to_pay)
-                    .await
-                    .expect_err("Should return an error when payouts are empty");
+            let mut payouts = UnifiedMap::default();
+            payouts.insert(*PUBLISHER, UnifiedNum::from_u64(1_000));
+            payouts.insert(*PUBLISHER_2, UnifiedNum::from_u64(999));
+
+            let response_error = channel_payout(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                HeaderMap::new(),
+                Json(ChannelPayRequest { payouts }),
+            )
+            .await
+            .expect_err("Should reject a payout below the minimum payout");
 
             assert_eq!(
-                err_response,
-                ResponseError::FailedValidation("Request has empty payouts".to_string()),
-                "Failed validation because payouts are empty"
+                ResponseError::FailedValidation(format!(
+                    "Payout to earner {} is below the minimum payout",
+                    *PUBLISHER_2
+                )),
+                response_error
             );
         }
 
-        // make a normal request and get accounting to see if its as expected
+        // exactly the minimum payout
         {
-            let success_response = channel_payout(
+            let mut payouts = UnifiedMap::default();
+            payouts.insert(*PUBLISHER, UnifiedNum::from_u64(1_000));
+
+            let response = channel_payout(
                 app.clone(),
                 channel_context.clone(),
                 auth.clone(),
-                to_pay.clone(),
+                HeaderMap::new(),
+                Json(ChannelPayRequest { payouts }),
             )
             .await
-            .expect("This request shouldn't result in an error");
+            .expect("Should accept a payout equal to the minimum payout");
 
-            assert_eq!(
-                SuccessResponse { success: true },
-                success_response.0,
-                "Request with JSON body with one address and no errors triggered on purpose"
-            );
+            assert_eq!(SuccessResponse { success: true }, response.0);
         }
+    }
 
-        // Checking if Earner/Spender balances have been updated by looking up the Accounting in the database
+    #[tokio::test]
+    async fn payout_with_zero_amount_or_to_the_spender() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = Extension(
+            app.config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Dummy channel Token should be present in config!")
+                .with(DUMMY_CAMPAIGN.channel),
+        );
+
+        let auth = Extension(Auth {
+            era: 0,
+            uid: ValidatorId::from(DUMMY_CAMPAIGN.creator),
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        });
+
+        // zero amount
         {
-            let earner_accounting = get_accounting(
-                app_guard.pool.clone(),
-                channel_context.context.id(),
-                *PUBLISHER,
-                Side::Earner,
-            )
-            .await
-            .expect("should get accounting")
-            .expect("Should have value, i.e. Some");
-            assert_eq!(
-                earner_accounting.amount,
-                UnifiedNum::from_u64(500),
-                "Accounting is updated to reflect the publisher's earnings"
-            );
+            let mut payouts = UnifiedMap::default();
+            payouts.insert(*PUBLISHER, UnifiedNum::from_u64(1_000));
+            payouts.insert(*PUBLISHER_2, UnifiedNum::ZERO);
 
-            let spender_accounting = get_accounting(
-                app_guard.pool.clone(),
-                channel_context.context.id(),
-                auth.uid.to_address(),
-                Side::Spender,
+            let response_error = channel_payout(
+                app.clone(),
+                channel_context.clone(),
+                auth.clone(),
+                HeaderMap::new(),
+                Json(ChannelPayRequest { payouts }),
             )
             .await
-            .expect("should get accounting")
-            .expect("Should have value, i.e. Some");
+            .expect_err("Should reject a zero payout");
 
             assert_eq!(
-                spender_accounting.amount,
-                UnifiedNum::from_u64(1000), // 500 initial + 500 for earners
-                "Accounting is updated to reflect the amount deducted from the spender"
+                ResponseError::FailedValidation(format!(
+                    "Payout to earner {} has a zero amount",
+                    *PUBLISHER_2
+                )),
+                response_error
             );
         }
 
-        // make a request where "total_to_pay" will exceed available
+        // payout to the spender
         {
-            let mut payouts = to_pay.payouts.clone();
-            payouts.insert(*CREATOR, UnifiedNum::from_u64(1000));
-            let to_pay_exceed = Json(ChannelPayRequest { payouts });
+            let mut payouts = UnifiedMap::default();
+            payouts.insert(*PUBLISHER, UnifiedNum::from_u64(1_000));
+            payouts.insert(DUMMY_CAMPAIGN.creator, UnifiedNum::from_u64(1_000));
 
             let response_error = channel_payout(
                 app.clone(),
                 channel_context.clone(),
                 auth.clone(),
-                to_pay_exceed,
+                HeaderMap::new(),
+                Json(ChannelPayRequest { payouts }),
             )
             .await
-            .expect_err("Should return an error when total_to_pay > available_for_payout");
+            .expect_err("Should reject a payout to the spender");
 
             assert_eq!(
-                ResponseError::FailedValidation(
-                    "The total requested payout amount exceeds the available payout".to_string()
-                ),
-                response_error,
-                "Failed validation because total_to_pay > available_for_payout"
+                ResponseError::FailedValidation("Spender cannot pay out to themselves".to_string()),
+                response_error
             );
         }
+    }
 
-        // make a request where total - spent + earned will be a negative balance resulting in an error
-        {
-            update_accounting(
-                app_guard.pool.clone(),
-                channel_context.context.id(),
-                auth.uid.to_address(),
-                Side::Spender,
-                UnifiedNum::from_u64(1000),
-            )
+    #[tokio::test]
+    async fn accounting_freeze_rejects_spending() {
+        use crate::{
+            db::accounting::Error as AccountingError,
+            routes::campaign::insert_events::{
+                spend_for_events, Error as EventsError, PayoutOptions,
+            },
+            Session,
+        };
+        use primitives::{sentry::Event, test_util::DUMMY_IPFS};
+
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = Extension(
+            app.config
+                .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+                .expect("Dummy channel Token should be present in config!")
+                .with(DUMMY_CAMPAIGN.channel),
+        );
+        let channel_id = channel_context.context.id();
+
+        insert_channel(&app.pool, &channel_context)
             .await
-            .expect("should update"); // total spent: 500 + 1000
+            .expect("should insert channel");
+        insert_campaign(&app.pool, &DUMMY_CAMPAIGN)
+            .await
+            .expect("should insert the campaign");
+        app.campaign_remaining
+            .set_initial(DUMMY_CAMPAIGN.id, DUMMY_CAMPAIGN.budget)
+            .await
+            .expect("Should set value in redis");
 
-            let response_error = channel_payout(
+        let auth = |uid: ValidatorId| {
+            Extension(Auth {
+                era: 0,
+                uid,
+                chain: channel_context.chain.clone(),
+                scope: Scope::Full,
+            })
+        };
+        let spender_auth = auth(ValidatorId::from(DUMMY_CAMPAIGN.creator));
+        let guardian_auth = auth(ValidatorId::from(DUMMY_CAMPAIGN.channel.guardian));
+
+        let spendable = Spendable {
+            spender: DUMMY_CAMPAIGN.creator,
+            channel: channel_context.context,
+            deposit: Deposit {
+                total: UnifiedNum::from_u64(100_000),
+            },
+        };
+        update_spendable(
+            app.pool.clone(),
+            &app.redis,
+            &app.redis_keys,
+            &spendable,
+            SpendableSource::WorkerRefresh,
+        )
+        .await
+        .expect("Should update spendable");
+
+        let payout = || {
+            let mut payouts = UnifiedMap::default();
+            payouts.insert(*PUBLISHER, UnifiedNum::from_u64(500));
+
+            channel_payout(
                 app.clone(),
                 channel_context.clone(),
-                auth.clone(),
-                to_pay.clone(),
+                spender_auth.clone(),
+                HeaderMap::new(),
+                Json(ChannelPayRequest { payouts }),
             )
+        };
+
+        let session = Session {
+            ip: None,
+            country: None,
+            referrer_header: None,
+            os: None,
+        };
+        let spend_for_impression = || {
+            spend_for_events(
+                &app,
+                &DUMMY_CAMPAIGN,
+                vec![Event::Impression {
+                    publisher: *PUBLISHER,
+                    ad_unit: DUMMY_IPFS[0],
+                    ad_slot: DUMMY_IPFS[1],
+                    referrer: None,
+                }],
+                &session,
+                DUMMY_CAMPAIGN.leader().expect("Should have a leader"),
+                DUMMY_CAMPAIGN.follower().expect("Should have a follower"),
+                PayoutOptions::default(),
+            )
+        };
+
+        let freeze = |auth, frozen| {
+            channel_accounting_freeze(
+                app.clone(),
+                channel_context.clone(),
+                auth,
+                Json(AccountingFreezeRequest { frozen }),
+            )
+        };
+
+        // Not the guardian of the Channel nor an admin
+        {
+            let response_error = freeze(auth(IDS[&PUBLISHER]), true)
+                .await
+                .expect_err("Should not be allowed to freeze");
+
+            assert!(matches!(response_error, ResponseError::Forbidden(_)));
+        }
+
+        freeze(guardian_auth.clone(), true)
             .await
-            .expect_err("Should return err when available_for_payout is negative");
+            .expect("Guardian should freeze the Channel accounting");
 
-            assert_eq!(
-                ResponseError::FailedValidation(
-                    "Underflow while calculating available for payout".to_string()
+        // Frozen accounting rejects events spending and payouts
+        {
+            let accounting = get_accounting_for_channel(app.clone(), channel_context.clone())
+                .await
+                .expect("Should get accounting")
+                .0;
+            assert!(accounting.frozen);
+
+            let events_error = spend_for_impression()
+                .await
+                .expect_err("Should reject the events spending");
+            assert!(
+                matches!(
+                    events_error,
+                    EventsError::Accounting(AccountingError::ChannelFrozen(frozen)) if frozen == channel_id
                 ),
-                response_error,
-                "Failed validation because available_for_payout is negative"
+                "Unexpected error: {events_error:?}"
+            );
+            assert_eq!(
+                Some(DUMMY_CAMPAIGN.budget.to_u64() as i64),
+                app.campaign_remaining
+                    .get_remaining_opt(DUMMY_CAMPAIGN.id)
+                    .await
+                    .expect("Should get remaining"),
+                "The remaining budget should be restored"
+            );
+
+            // payouts are allowed only when all the Campaigns are closed
+            app.campaign_remaining
+                .getset_remaining_to_zero(DUMMY_CAMPAIGN.id)
+                .await
+                .expect("Should close the Campaign");
+
+            let response_error = payout().await.expect_err("Should reject the payout");
+            assert_eq!(
+                ResponseError::BadRequest(format!("Accounting of Channel {channel_id} is frozen")),
+                response_error
+            );
+
+            assert!(
+                get_all_accountings_for_channel(app.pool.clone(), channel_id)
+                    .await
+                    .expect("Should get accountings")
+                    .is_empty()
             );
         }
 
-        // make a request where campaigns will have available remaining
+        freeze(guardian_auth, false)
+            .await
+            .expect("Guardian should unfreeze the Channel accounting");
+
+        // Unfrozen accounting accepts payouts and events spending again
         {
-            campaign_remaining
-                .increase_by(DUMMY_CAMPAIGN.id, UnifiedNum::from_u64(1000))
-                .await
-                .expect("Should set value in redis");
+            payout().await.expect("Should apply the payout");
 
-            let response_error = channel_payout(app, channel_context, auth, to_pay)
+            app.campaign_remaining
+                .increase_by(DUMMY_CAMPAIGN.id, DUMMY_CAMPAIGN.budget)
+                .await
+                .expect("Should set the remaining budget");
+            spend_for_impression()
                 .await
-                .expect_err("Should return an error when a campaign has remaining funds");
+                .expect("Should spend for the events");
 
-            assert_eq!(
-                ResponseError::FailedValidation(
-                    "All campaigns should be closed or have no budget left".to_string()
-                ),
-                response_error,
-                "Failed validation because the campaign has remaining funds"
-            );
+            let accounting = get_accounting_for_channel(app.clone(), channel_context.clone())
+                .await
+                .expect("Should get accounting")
+                .0;
+            assert!(!accounting.frozen);
+            assert!(accounting.balances.earners.contains_key(&PUBLISHER));
         }
     }
 
@@ -1776,4 +4838,511 @@ mod test {
         assert_eq!(spender_proof, spender_leaf.merkle_proof);
         assert_eq!(earner_proof, earner_leaf.merkle_proof);
     }
+
+    #[tokio::test]
+    async fn last_approved_history_pairs_and_orders_the_states() {
+        let mut app_guard = setup_dummy_app().await;
+        app_guard.app.config.limits.approved_states_find = 2;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with(DUMMY_CAMPAIGN.channel);
+        let channel = channel_context.context;
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+
+        let get_history = |limit: Option<u64>| {
+            last_approved_history(
+                app.clone(),
+                Extension(channel_context.clone()),
+                Qs(LastApprovedHistoryQuery { limit }),
+            )
+        };
+
+        // No approval rounds yet
+        {
+            let response = get_history(None).await.expect("should get history").0;
+            assert!(response.history.is_empty());
+        }
+
+        // 3 approval rounds, each NewState is approved by the follower
+        let mut balances = Balances::<UncheckedState>::default();
+        let state_roots = ["1", "2", "3"].map(|round| round.repeat(64));
+        for (round, state_root) in state_roots.iter().enumerate() {
+            balances
+                .earners
+                .insert(*PUBLISHER, UnifiedNum::from_u64(round as u64 + 1));
+
+            let new_state = NewState::<UncheckedState> {
+                state_root: state_root.clone(),
+                signature: channel.leader.to_string(),
+                balances: balances.clone(),
+            };
+            insert_validator_message(
+                &app.pool,
+                &channel,
+                &channel.leader,
+                &MessageTypes::NewState(new_state),
+            )
+            .await
+            .expect("Should insert NewState msg");
+
+            let approve_state = ApproveState {
+                state_root: state_root.clone(),
+                signature: channel.follower.to_string(),
+                is_healthy: true,
+            };
+            insert_validator_message(
+                &app.pool,
+                &channel,
+                &channel.follower,
+                &MessageTypes::ApproveState(approve_state),
+            )
+            .await
+            .expect("Should insert ApproveState msg");
+        }
+
+        // a newer NewState which is not approved yet
+        insert_validator_message(
+            &app.pool,
+            &channel,
+            &channel.leader,
+            &MessageTypes::NewState(NewState::<UncheckedState> {
+                state_root: "4".repeat(64),
+                signature: channel.leader.to_string(),
+                balances,
+            }),
+        )
+        .await
+        .expect("Should insert NewState msg");
+
+        let all_states = latest_approved_states::<UncheckedState>(&app.pool, &channel, 10)
+            .await
+            .expect("should get approved states");
+        assert_eq!(3, all_states.len());
+
+        let expected_state_roots = state_roots.iter().rev().collect::<Vec<_>>();
+        for (approved, (expected_state_root, expected_earned)) in all_states
+            .iter()
+            .zip(expected_state_roots.iter().zip([3_u64, 2, 1]))
+        {
+            assert_eq!(channel.follower, approved.approve_state.from);
+            assert_eq!(channel.leader, approved.new_state.from);
+            assert_eq!(*expected_state_root, &approved.approve_state.msg.state_root);
+            assert_eq!(*expected_state_root, &approved.new_state.msg.state_root);
+            assert_eq!(
+                Some(&UnifiedNum::from_u64(expected_earned)),
+                approved.new_state.msg.balances.earners.get(&PUBLISHER)
+            );
+        }
+        assert!(
+            all_states
+                .windows(2)
+                .all(|pair| pair[0].approve_state.received >= pair[1].approve_state.received),
+            "Should be ordered newest first"
+        );
+
+        // `limit` query parameter
+        {
+            let response = get_history(Some(1)).await.expect("should get history").0;
+            assert_eq!(1, response.history.len());
+            assert_eq!(all_states[0], response.history[0]);
+        }
+
+        // capped by `Config.limits.approved_states_find`
+        for limit in [None, Some(10)] {
+            let response = get_history(limit).await.expect("should get history").0;
+            assert_eq!(&all_states[..2], response.history.as_slice());
+        }
+    }
+
+    #[tokio::test]
+    async fn channel_lag_of_up_to_date_channel() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with(DUMMY_CAMPAIGN.channel);
+        let channel = channel_context.context;
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+
+        // No states yet
+        {
+            let response = channel_lag(app.clone(), Extension(channel_context.clone()))
+                .await
+                .expect("should get lag")
+                .0;
+            assert_eq!(None, response.new_state);
+            assert_eq!(None, response.approve_state);
+            assert!(!response.corresponds);
+        }
+
+        let state_root = "1".repeat(64);
+        insert_validator_message(
+            &app.pool,
+            &channel,
+            &channel.leader,
+            &MessageTypes::NewState(NewState::<UncheckedState> {
+                state_root: state_root.clone(),
+                signature: channel.leader.to_string(),
+                balances: Balances::default(),
+            }),
+        )
+        .await
+        .expect("Should insert NewState msg");
+        insert_validator_message(
+            &app.pool,
+            &channel,
+            &channel.follower,
+            &MessageTypes::ApproveState(ApproveState {
+                state_root: state_root.clone(),
+                signature: channel.follower.to_string(),
+                is_healthy: true,
+            }),
+        )
+        .await
+        .expect("Should insert ApproveState msg");
+
+        let response = channel_lag(app.clone(), Extension(channel_context.clone()))
+            .await
+            .expect("should get lag")
+            .0;
+
+        let new_state = response.new_state.expect("Should have NewState");
+        let approve_state = response.approve_state.expect("Should have ApproveState");
+        assert_eq!(state_root, new_state.state_root);
+        assert_eq!(state_root, approve_state.state_root);
+        assert!(new_state.received <= approve_state.received);
+        assert!(response.corresponds);
+    }
+
+    #[tokio::test]
+    async fn channel_lag_with_unapproved_new_state() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with(DUMMY_CAMPAIGN.channel);
+        let channel = channel_context.context;
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+
+        let (approved_state_root, unapproved_state_root) = ("1".repeat(64), "2".repeat(64));
+        for (state_root, is_approved) in [
+            (&approved_state_root, true),
+            (&unapproved_state_root, false),
+        ] {
+            insert_validator_message(
+                &app.pool,
+                &channel,
+                &channel.leader,
+                &MessageTypes::NewState(NewState::<UncheckedState> {
+                    state_root: state_root.clone(),
+                    signature: channel.leader.to_string(),
+                    balances: Balances::default(),
+                }),
+            )
+            .await
+            .expect("Should insert NewState msg");
+
+            if is_approved {
+                insert_validator_message(
+                    &app.pool,
+                    &channel,
+                    &channel.follower,
+                    &MessageTypes::ApproveState(ApproveState {
+                        state_root: state_root.clone(),
+                        signature: channel.follower.to_string(),
+                        is_healthy: true,
+                    }),
+                )
+                .await
+                .expect("Should insert ApproveState msg");
+            }
+        }
+
+        let response = channel_lag(app.clone(), Extension(channel_context.clone()))
+            .await
+            .expect("should get lag")
+            .0;
+
+        let new_state = response.new_state.expect("Should have NewState");
+        let approve_state = response.approve_state.expect("Should have ApproveState");
+        assert_eq!(unapproved_state_root, new_state.state_root);
+        assert_eq!(approved_state_root, approve_state.state_root);
+        assert!(
+            approve_state.age_ms >= new_state.age_ms,
+            "The ApproveState is older than the latest NewState"
+        );
+        assert!(!response.corresponds);
+    }
+
+    #[tokio::test]
+    async fn channel_lag_without_new_state() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with(DUMMY_CAMPAIGN.channel);
+        let channel = channel_context.context;
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+
+        // an ApproveState without the Leader's NewState
+        let state_root = "1".repeat(64);
+        insert_validator_message(
+            &app.pool,
+            &channel,
+            &channel.follower,
+            &MessageTypes::ApproveState(ApproveState {
+                state_root: state_root.clone(),
+                signature: channel.follower.to_string(),
+                is_healthy: true,
+            }),
+        )
+        .await
+        .expect("Should insert ApproveState msg");
+
+        let response = channel_lag(app.clone(), Extension(channel_context.clone()))
+            .await
+            .expect("should get lag")
+            .0;
+
+        assert_eq!(None, response.new_state);
+        let approve_state = response.approve_state.expect("Should have ApproveState");
+        assert_eq!(state_root, approve_state.state_root);
+        assert!(!response.corresponds);
+    }
+
+    #[tokio::test]
+    async fn imbalanced_new_state_is_quarantined() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let channel_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with(DUMMY_CAMPAIGN.channel);
+        let channel = channel_context.context;
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+
+        // the first approval round is balanced, the second one is not
+        let good_state_root = "1".repeat(64);
+        let bad_state_root = "2".repeat(64);
+        for (state_root, earned) in [(&good_state_root, 1_u64), (&bad_state_root, 2)] {
+            let mut balances = Balances::<UncheckedState>::default();
+            balances.spenders.insert(*CREATOR, UnifiedNum::from_u64(1));
+            balances
+                .earners
+                .insert(*PUBLISHER, UnifiedNum::from_u64(earned));
+
+            insert_validator_message(
+                &app.pool,
+                &channel,
+                &channel.leader,
+                &MessageTypes::NewState(NewState {
+                    state_root: state_root.clone(),
+                    signature: channel.leader.to_string(),
+                    balances,
+                }),
+            )
+            .await
+            .expect("Should insert NewState msg");
+
+            insert_validator_message(
+                &app.pool,
+                &channel,
+                &channel.follower,
+                &MessageTypes::ApproveState(ApproveState {
+                    state_root: state_root.clone(),
+                    signature: channel.follower.to_string(),
+                    is_healthy: true,
+                }),
+            )
+            .await
+            .expect("Should insert ApproveState msg");
+        }
+
+        let last_approved = last_approved(
+            app.clone(),
+            Extension(channel_context.clone()),
+            Qs(LastApprovedQuery {
+                with_heartbeat: None,
+            }),
+        )
+        .await
+        .expect("Should get last approved")
+        .0
+        .last_approved
+        .expect("Should have the previous approved states");
+
+        let new_state = last_approved.new_state.expect("Should have NewState");
+        let approve_state = last_approved
+            .approve_state
+            .expect("Should have ApproveState");
+        assert_eq!(good_state_root, new_state.msg.state_root);
+        assert_eq!(good_state_root, approve_state.msg.state_root);
+        assert_eq!(
+            Some(&UnifiedNum::from_u64(1)),
+            new_state.msg.balances.earners.get(&PUBLISHER)
+        );
+
+        // reading the last approved states does not quarantine anything
+        let quarantined = get_quarantined_messages(&app.pool, &channel.id(), 10)
+            .await
+            .expect("Should get quarantined messages");
+        assert!(quarantined.is_empty());
+
+        // the NewState is checked as well by the routes using `CheckedState`
+        let (_, checked) =
+            get_corresponding_states::<CheckedState>(&app.pool, &app.logger, &channel)
+                .await
+                .expect("Should get the states")
+                .expect("Should have approved states");
+        assert_eq!(good_state_root, checked.msg.state_root);
+
+        let leader_auth = Auth {
+            era: 0,
+            uid: IDS[&LEADER],
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        };
+
+        // only the Channel validators can quarantine a state
+        {
+            let publisher_auth = Auth {
+                uid: IDS[&PUBLISHER],
+                ..leader_auth.clone()
+            };
+            let error = quarantine_channel_state(
+                app.clone(),
+                Extension(channel_context.clone()),
+                Extension(publisher_auth),
+                Json(QuarantineStateRequest {
+                    state_root: bad_state_root.clone(),
+                }),
+            )
+            .await
+            .expect_err("Should not be a channel validator");
+            assert_eq!(ResponseError::Unauthorized, error);
+        }
+
+        // a balanced state is not quarantined
+        {
+            let error = quarantine_channel_state(
+                app.clone(),
+                Extension(channel_context.clone()),
+                Extension(leader_auth.clone()),
+                Json(QuarantineStateRequest {
+                    state_root: good_state_root.clone(),
+                }),
+            )
+            .await
+            .expect_err("Should not quarantine a valid NewState");
+            assert_eq!(
+                ResponseError::BadRequest("NewState balances are valid".to_string()),
+                error
+            );
+        }
+
+        let quarantine_response = quarantine_channel_state(
+            app.clone(),
+            Extension(channel_context.clone()),
+            Extension(leader_auth.clone()),
+            Json(QuarantineStateRequest {
+                state_root: bad_state_root.clone(),
+            }),
+        )
+        .await
+        .expect("Should quarantine the state");
+        assert!(quarantine_response.0.success);
+
+        let quarantined =
+            list_quarantined_messages(app.clone(), Extension(channel_context.clone()))
+                .await
+                .expect("Should list quarantined messages")
+                .0
+                .messages;
+        assert_eq!(2, quarantined.len());
+        assert!(quarantined
+            .iter()
+            .all(|message| message.msg.state_root() == bad_state_root));
+        let new_state_message = quarantined
+            .iter()
+            .find(|message| matches!(message.msg, MessageTypes::NewState(_)))
+            .expect("The NewState should be quarantined");
+        assert_eq!(channel.leader, new_state_message.from);
+
+        // releasing the NewState releases its ApproveState as well
+        let released = release_quarantined(
+            Path((channel.id(), new_state_message.id)),
+            app.clone(),
+            Extension(channel_context.clone()),
+            Extension(leader_auth.clone()),
+        )
+        .await
+        .expect("Should release the message");
+        assert!(released.0.success);
+
+        let quarantined = get_quarantined_messages(&app.pool, &channel.id(), 10)
+            .await
+            .expect("Should get quarantined messages");
+        assert!(quarantined.is_empty());
+
+        // released messages are not quarantined again
+        {
+            let quarantine_response = quarantine_channel_state(
+                app.clone(),
+                Extension(channel_context.clone()),
+                Extension(leader_auth.clone()),
+                Json(QuarantineStateRequest {
+                    state_root: bad_state_root.clone(),
+                }),
+            )
+            .await
+            .expect("Should succeed");
+            assert!(quarantine_response.0.success);
+
+            let quarantined = get_quarantined_messages(&app.pool, &channel.id(), 10)
+                .await
+                .expect("Should get quarantined messages");
+            assert!(quarantined.is_empty());
+        }
+
+        // the message is no longer quarantined
+        let error = release_quarantined(
+            Path((channel.id(), new_state_message.id)),
+            app.clone(),
+            Extension(channel_context.clone()),
+            Extension(leader_auth),
+        )
+        .await
+        .expect_err("Should not be found");
+        assert_eq!(ResponseError::NotFound, error);
+    }
 }
@@ -0,0 +1,15 @@
+//! `GET /health` request
+
+use axum::Json;
+
+use primitives::sentry::SuccessResponse;
+
+/// GET `/health` request
+///
+/// Used by other validators to check whether this Sentry is up
+/// before propagating messages to it.
+///
+/// Response: [`SuccessResponse`]
+pub async fn get_health() -> Json<SuccessResponse> {
+    Json(SuccessResponse { success: true })
+}
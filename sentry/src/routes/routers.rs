@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 use axum::{
+    handler::Handler,
     http::Request,
     middleware::{self, Next},
     routing::{get, post},
@@ -29,21 +30,26 @@ use crate::{
     middleware::{
         auth::{
             authenticate_as_advertiser, authenticate_as_publisher, authentication_required,
-            is_admin,
+            full_scope_required, is_admin,
         },
         campaign::{called_by_creator, campaign_load},
         channel::channel_load,
+        etag::etag,
     },
     routes::{
         analytics::{get_analytics, GET_ANALYTICS_ALLOWED_KEYS},
         campaign,
         channel::{
-            add_spender_leaf, channel_dummy_deposit, channel_list, channel_payout,
-            get_accounting_for_channel, get_all_spender_limits, get_leaf, get_spender_limits,
-            last_approved,
+            add_spender_leaf, channel_accounting_freeze, channel_close_and_pay,
+            channel_dummy_deposit, channel_lag, channel_list, channel_payout,
+            channel_payout_preview, channel_snapshot, create_channel, get_accounting_for_channel,
+            get_accounting_page_for_channel, get_all_spender_limits, get_leaf, get_spender_history,
+            get_spender_limits, get_spenders_batch, last_approved, last_approved_history,
+            list_quarantined_messages, quarantine_channel_state, release_quarantined,
             validator_message::{create_validator_messages, list_validator_messages},
         },
         units_for_slot::get_units_for_slot,
+        validator::{create_fees_summary, get_fees_summary},
     },
     Application,
 };
@@ -78,9 +84,12 @@ pub fn channels_router<C: Locked + 'static>() -> Router {
     let spender_routes = Router::new()
         .route(
             "/:addr",
-            get(get_spender_limits::<C>).post(add_spender_leaf::<C>),
+            get(get_spender_limits::<C>)
+                .post(add_spender_leaf::<C>.layer(middleware::from_fn(full_scope_required))),
         )
+        .route("/:addr/history", get(get_spender_history::<C>))
         .route("/all", get(get_all_spender_limits::<C>))
+        .route("/batch", post(get_spenders_batch::<C>))
         .layer(
             // keeps the order from top to bottom!
             ServiceBuilder::new().layer(middleware::from_fn(authentication_required::<C, _>)),
@@ -95,11 +104,77 @@ pub fn channels_router<C: Locked + 'static>() -> Router {
     let channel_routes = Router::new()
         .route(
             "/pay",
-            post(channel_payout::<C>)
+            post(channel_payout::<C>).route_layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(authentication_required::<C, _>))
+                    .layer(middleware::from_fn(full_scope_required)),
+            ),
+        )
+        .route(
+            "/pay/preview",
+            post(channel_payout_preview::<C>)
+                .route_layer(middleware::from_fn(authentication_required::<C, _>)),
+        )
+        .route(
+            "/close-and-pay",
+            post(channel_close_and_pay::<C>).route_layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(authentication_required::<C, _>))
+                    .layer(middleware::from_fn(full_scope_required)),
+            ),
+        )
+        .route(
+            "/accounting",
+            get(get_accounting_for_channel::<C>).route_layer(middleware::from_fn(etag)),
+        )
+        .route(
+            "/accounting/page",
+            get(get_accounting_page_for_channel::<C>),
+        )
+        .route(
+            "/accounting-freeze",
+            post(channel_accounting_freeze::<C>).route_layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(authentication_required::<C, _>))
+                    .layer(middleware::from_fn(full_scope_required)),
+            ),
+        )
+        .route(
+            "/quarantined-messages",
+            get(list_quarantined_messages::<C>).route_layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(authentication_required::<C, _>))
+                    .layer(middleware::from_fn(is_admin::<C, _>)),
+            ),
+        )
+        .route(
+            "/quarantined-messages/:message_id/release",
+            post(release_quarantined::<C>).route_layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(authentication_required::<C, _>))
+                    .layer(middleware::from_fn(full_scope_required))
+                    .layer(middleware::from_fn(is_admin::<C, _>)),
+            ),
+        )
+        .route(
+            "/quarantine",
+            post(quarantine_channel_state::<C>).route_layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(authentication_required::<C, _>))
+                    .layer(middleware::from_fn(full_scope_required)),
+            ),
+        )
+        .route(
+            "/snapshot",
+            get(channel_snapshot::<C>)
                 .route_layer(middleware::from_fn(authentication_required::<C, _>)),
         )
-        .route("/accounting", get(get_accounting_for_channel::<C>))
-        .route("/last-approved", get(last_approved::<C>))
+        .route(
+            "/last-approved",
+            get(last_approved::<C>).route_layer(middleware::from_fn(etag)),
+        )
+        .route("/last-approved/history", get(last_approved_history::<C>))
+        .route("/lag", get(channel_lag::<C>))
         .nest("/spender", spender_routes)
         .nest("/get-leaf", get_leaf_routes)
         .route(
@@ -125,6 +200,14 @@ pub fn channels_router<C: Locked + 'static>() -> Router {
         );
 
     Router::new()
+        .route(
+            "/",
+            post(create_channel::<C>).route_layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(authentication_required::<C, _>))
+                    .layer(middleware::from_fn(full_scope_required)),
+            ),
+        )
         .route("/list", get(channel_list::<C>))
         .nest("/:id", channel_routes)
         // Only available if Dummy Adapter is used!
@@ -139,24 +222,55 @@ pub fn channels_router<C: Locked + 'static>() -> Router {
 /// `/v5/campaign` router
 pub fn campaigns_router<C: Locked + 'static>() -> Router {
     let campaign_routes = Router::new()
+        .route("/", get(campaign::get_campaign::<C>))
         .route(
             "/",
             // Campaign update
             post(campaign::update_campaign::handle_route::<C>).route_layer(
                 ServiceBuilder::new()
                     .layer(middleware::from_fn(authentication_required::<C, _>))
+                    .layer(middleware::from_fn(full_scope_required))
                     .layer(middleware::from_fn(called_by_creator::<C, _>)),
             ),
         )
-        .route("/events", post(campaign::insert_events::handle_route::<C>))
+        .route(
+            "/events",
+            post(campaign::insert_events::handle_route::<C>)
+                .route_layer(middleware::from_fn(full_scope_required)),
+        )
+        .route(
+            "/events/stats",
+            get(campaign::campaign_event_stats::<C>)
+                .route_layer(middleware::from_fn(authentication_required::<C, _>)),
+        )
+        .route("/burn-rate", get(campaign::campaign_burn_rate::<C>))
+        .route(
+            "/health",
+            get(campaign::campaign_health::<C>)
+                .route_layer(middleware::from_fn(authentication_required::<C, _>)),
+        )
+        .route(
+            "/budget/history",
+            get(campaign::campaign_budget_history::<C>)
+                .route_layer(middleware::from_fn(authentication_required::<C, _>)),
+        )
         .route(
             "/close",
             post(campaign::close_campaign::<C>).route_layer(
                 ServiceBuilder::new()
                     .layer(middleware::from_fn(authentication_required::<C, _>))
+                    .layer(middleware::from_fn(full_scope_required))
                     .layer(middleware::from_fn(called_by_creator::<C, _>)),
             ),
         )
+        .route(
+            "/closed-notification",
+            post(campaign::campaign_closed_notification::<C>).route_layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(authentication_required::<C, _>))
+                    .layer(middleware::from_fn(full_scope_required)),
+            ),
+        )
         .layer(
             // keeps the order from top to bottom!
             ServiceBuilder::new()
@@ -169,8 +283,16 @@ pub fn campaigns_router<C: Locked + 'static>() -> Router {
         .route(
             "/",
             // For creating campaigns
-            post(campaign::create_campaign::<C>),
+            post(campaign::create_campaign::<C>)
+                .route_layer(middleware::from_fn(full_scope_required)),
+        )
+        .route(
+            "/bulk",
+            // For creating multiple campaigns of the same Channel at once
+            post(campaign::create_campaigns_bulk::<C>)
+                .route_layer(middleware::from_fn(full_scope_required)),
         )
+        .route("/batch", post(campaign::get_campaigns_batch::<C>))
         .nest("/:id", campaign_routes)
 }
 
@@ -179,6 +301,20 @@ pub fn units_for_slot_router<C: Locked + 'static>() -> Router {
     Router::new().route("/", get(get_units_for_slot::<C>))
 }
 
+/// `/v5/validator` router
+pub fn validators_router<C: Locked + 'static>() -> Router {
+    Router::new().route(
+        "/:id/fees-summary",
+        get(get_fees_summary::<C>).post(
+            create_fees_summary::<C>.layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(authentication_required::<C, _>))
+                    .layer(middleware::from_fn(full_scope_required)),
+            ),
+        ),
+    )
+}
+
 /// `/v5/analytics` router
 pub fn analytics_router<C: Locked + 'static>() -> Router {
     let authenticated_analytics = Router::new()
@@ -226,111 +362,89 @@ pub fn analytics_router<C: Locked + 'static>() -> Router {
 mod test {
     use super::*;
     use crate::{
-        db::{insert_channel, validator_message::insert_validator_message},
-        test_util::{body_to, setup_dummy_app},
+        db::{
+            accounting::spend_amount, insert_campaign, insert_channel,
+            validator_message::insert_validator_message,
+        },
+        test_util::{body_to, oneshot, setup_dummy_app},
         Auth,
     };
-    use adapter::ethereum::test_util::GANACHE_1;
+    use adapter::{dummy::HeaderToken, ethereum::test_util::GANACHE_1, primitives::Scope};
     use axum::{
         body::Body,
-        http::{Request, StatusCode},
+        http::{
+            header::{AUTHORIZATION, ETAG, IF_NONE_MATCH},
+            HeaderValue, Request, StatusCode,
+        },
     };
-    use chrono::Utc;
+    use chrono::{Duration, Utc};
     use primitives::{
-        sentry::validator_messages::{MessageTypesFilter, ValidatorMessagesListResponse},
-        test_util::{ADVERTISER, CAMPAIGNS, FOLLOWER, IDS, LEADER, PUBLISHER},
+        balances::{Balances, CheckedState},
+        sentry::{
+            validator_messages::{
+                MessageTypesFilter, MessagesCursor, ValidatorMessagesListQuery,
+                ValidatorMessagesListResponse,
+            },
+            AllSpendersResponse,
+        },
+        test_util::{
+            ADVERTISER, CAMPAIGNS, CREATOR, DUMMY_AUTH, DUMMY_CAMPAIGN, FOLLOWER, IDS, LEADER,
+            PUBLISHER,
+        },
         validator::{Heartbeat, MessageType, MessageTypes, NewState},
+        Address, CampaignId, Channel, UnifiedNum, ValidatorId,
     };
     use tower::Service;
 
     #[tokio::test]
     async fn analytics_router_tests() {
-        let mut router = analytics_router::<Dummy>();
         let app_guard = setup_dummy_app().await;
-        let app = Arc::new(app_guard.app);
+        let app = &app_guard.app;
+
+        let request = |uri: &str, auth_as: Option<Address>| {
+            let builder = Request::builder().uri(format!("/v5/analytics{uri}"));
+            let builder = match auth_as {
+                Some(address) => {
+                    let header_token = HeaderToken {
+                        token: DUMMY_AUTH[&address].clone(),
+                        chain_id: GANACHE_1.chain_id,
+                    };
+
+                    builder.header(AUTHORIZATION, format!("Bearer {header_token}"))
+                }
+                None => builder,
+            };
+
+            builder.body(Body::empty()).expect("Should build Request")
+        };
 
         // Test /for-publisher with no auth
         {
-            let req = Request::builder()
-                .uri("/for-publisher")
-                .extension(app.clone())
-                .body(Body::empty())
-                .expect("Should build Request");
-
-            let response = router
-                .call(req)
-                .await
-                .expect("Should make request to Router");
+            let response = oneshot(app, request("/for-publisher", None)).await;
 
             assert_eq!(StatusCode::UNAUTHORIZED, response.status());
         }
         // Test /for-publisher with auth
         {
-            let req = Request::builder()
-                .uri("/for-publisher")
-                .extension(app.clone())
-                .extension(Auth {
-                    era: 1,
-                    uid: IDS[&PUBLISHER],
-                    chain: GANACHE_1.clone(),
-                })
-                .body(Body::empty())
-                .expect("Should build Request");
-
-            let response = router
-                .call(req)
-                .await
-                .expect("Should make request to Router");
+            let response = oneshot(app, request("/for-publisher", Some(*PUBLISHER))).await;
 
             assert_eq!(StatusCode::OK, response.status());
         }
         // Test /for-advertiser with no auth
         {
-            let req = Request::builder()
-                .uri("/for-advertiser")
-                .extension(app.clone())
-                .body(Body::empty())
-                .expect("Should build Request");
-
-            let response = router
-                .call(req)
-                .await
-                .expect("Should make request to Router");
+            let response = oneshot(app, request("/for-advertiser", None)).await;
 
             assert_eq!(StatusCode::UNAUTHORIZED, response.status());
         }
         // Test /for-advertiser with auth
         {
-            let req = Request::builder()
-                .uri("/for-advertiser")
-                .extension(app.clone())
-                .extension(Auth {
-                    era: 1,
-                    uid: IDS[&ADVERTISER],
-                    chain: GANACHE_1.clone(),
-                })
-                .body(Body::empty())
-                .expect("Should build Request");
-
-            let response = router
-                .call(req)
-                .await
-                .expect("Should make request to Router");
+            let response = oneshot(app, request("/for-advertiser", Some(*ADVERTISER))).await;
 
             assert_eq!(StatusCode::OK, response.status());
         }
         // Test /for-admin with no auth
         {
-            let req = Request::builder()
-                .uri("/for-admin")
-                .extension(app.clone())
-                .body(Body::empty())
-                .expect("Should build Request");
-
-            let response = router
-                .call(req)
-                .await
-                .expect("Should make request to Router");
+            let response = oneshot(app, request("/for-admin", None)).await;
 
             assert_eq!(StatusCode::UNAUTHORIZED, response.status());
         }
@@ -342,23 +456,9 @@ mod test {
                     "Should not contain the Follower as an Admin for this test!"
                 );
 
-                IDS[&FOLLOWER]
+                *FOLLOWER
             };
-            let req = Request::builder()
-                .uri("/for-admin")
-                .extension(app.clone())
-                .extension(Auth {
-                    era: 1,
-                    uid: not_admin,
-                    chain: GANACHE_1.clone(),
-                })
-                .body(Body::empty())
-                .expect("Should build Request");
-
-            let response = router
-                .call(req)
-                .await
-                .expect("Should make request to Router");
+            let response = oneshot(app, request("/for-admin", Some(not_admin))).await;
 
             assert_eq!(StatusCode::UNAUTHORIZED, response.status());
         }
@@ -369,23 +469,9 @@ mod test {
                     app.config.sentry.admins.contains(&LEADER),
                     "Should contain the Leader as an Admin for this test!"
                 );
-                IDS[&LEADER]
+                *LEADER
             };
-            let req = Request::builder()
-                .uri("/for-admin")
-                .extension(app.clone())
-                .extension(Auth {
-                    era: 1,
-                    uid: admin,
-                    chain: GANACHE_1.clone(),
-                })
-                .body(Body::empty())
-                .expect("Should build Request");
-
-            let response = router
-                .call(req)
-                .await
-                .expect("Should make request to Router");
+            let response = oneshot(app, request("/for-admin", Some(admin))).await;
 
             assert_eq!(StatusCode::OK, response.status());
         }
@@ -393,10 +479,8 @@ mod test {
 
     #[tokio::test]
     async fn test_validator_messages_routes() -> Result<(), Box<dyn std::error::Error>> {
-        let mut router = channels_router::<Dummy>();
-
         let app_guard = setup_dummy_app().await;
-        let app = Arc::new(app_guard.app);
+        let app = &app_guard.app;
 
         let channel_context = Extension(CAMPAIGNS[0].clone().of_channel());
 
@@ -420,6 +504,7 @@ mod test {
             era: 1,
             uid: IDS[&LEADER],
             chain: channel_context.chain.clone(),
+            scope: Scope::Full,
         };
         let follower_messages = vec![
             MessageTypes::NewState(NewState {
@@ -437,6 +522,7 @@ mod test {
             era: 0,
             uid: IDS[&FOLLOWER],
             chain: channel_context.chain.clone(),
+            scope: Scope::Full,
         };
 
         let all_messages = {
@@ -480,14 +566,13 @@ mod test {
         {
             let request = Request::builder()
                 .uri(format!(
-                    "/{id}/validator-messages",
+                    "/v5/channel/{id}/validator-messages",
                     id = channel_context.context.id()
                 ))
-                .extension(app.clone())
                 .body(Body::empty())
                 .unwrap();
 
-            let response = router.call(request).await?;
+            let response = oneshot(app, request).await;
             let status = response.status();
 
             assert_eq!(StatusCode::OK, status);
@@ -508,16 +593,15 @@ mod test {
         {
             let request = Request::builder()
                 .uri(format!(
-                    "/{id}/validator-messages/{leader}",
+                    "/v5/channel/{id}/validator-messages/{leader}",
                     id = channel_context.context.id(),
                     // Address & ValidatorId are displayed in the exact same way
                     leader = leader_auth.uid.to_address()
                 ))
-                .extension(app.clone())
                 .body(Body::empty())
                 .unwrap();
 
-            let response = router.call(request).await?;
+            let response = oneshot(app, request).await;
             let status = response.status();
 
             let response = body_to::<ValidatorMessagesListResponse>(response).await?;
@@ -539,17 +623,16 @@ mod test {
         {
             let request = Request::builder()
                 .uri(format!(
-                    "/{id}/validator-messages/{follower}/{types}",
+                    "/v5/channel/{id}/validator-messages/{follower}/{types}",
                     id = channel_context.context.id(),
                     // Address & ValidatorId are displayed in the exact same way
                     follower = follower_auth.uid.to_address(),
                     types = MessageTypesFilter(vec![MessageType::NewState])
                 ))
-                .extension(app.clone())
                 .body(Body::empty())
                 .unwrap();
 
-            let response = router.call(request).await?;
+            let response = oneshot(app, request).await;
             let status = response.status();
 
             let response = body_to::<ValidatorMessagesListResponse>(response).await?;
@@ -576,7 +659,7 @@ mod test {
         {
             let request = Request::builder()
                 .uri(format!(
-                    "/{id}/validator-messages/{leader}/{types}",
+                    "/v5/channel/{id}/validator-messages/{leader}/{types}",
                     id = channel_context.context.id(),
                     // Address & ValidatorId are displayed in the exact same way
                     leader = leader_auth.uid.to_address(),
@@ -586,11 +669,10 @@ mod test {
                         MessageType::RejectState
                     ])
                 ))
-                .extension(app.clone())
                 .body(Body::empty())
                 .unwrap();
 
-            let response = router.call(request).await?;
+            let response = oneshot(app, request).await;
             let status = response.status();
 
             let response = body_to::<ValidatorMessagesListResponse>(response).await?;
@@ -609,4 +691,336 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_validator_messages_oldest_first_pagination(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut router = channels_router::<Dummy>();
+
+        let app_guard = setup_dummy_app().await;
+        let app = Arc::new(app_guard.app);
+
+        let channel_context = Extension(CAMPAIGNS[0].clone().of_channel());
+
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+
+        let since = Utc::now() - Duration::seconds(1);
+        let heartbeat = |n: usize| {
+            MessageTypes::Heartbeat(Heartbeat {
+                signature: format!("heartbeat {n}"),
+                state_root: String::new(),
+                timestamp: Utc::now(),
+            })
+        };
+        let insert_heartbeats = |range: std::ops::Range<usize>| {
+            let app = app.clone();
+            let channel = channel_context.context;
+
+            async move {
+                for n in range {
+                    let inserted =
+                        insert_validator_message(&app.pool, &channel, &IDS[&LEADER], &heartbeat(n))
+                            .await
+                            .expect("Should insert message");
+                    assert!(inserted, "Failed to insert heartbeat {n}");
+                }
+            }
+        };
+
+        let mut fetch_page = |cursor: Option<MessagesCursor>| {
+            let query = ValidatorMessagesListQuery {
+                limit: Some(2),
+                since: Some(since),
+                cursor,
+            };
+            let request = Request::builder()
+                .uri(format!(
+                    "/{id}/validator-messages/{leader}/Heartbeat?{query}",
+                    id = channel_context.context.id(),
+                    leader = IDS[&LEADER].to_address(),
+                    query = serde_qs::to_string(&query).expect("Should serialize query"),
+                ))
+                .extension(app.clone())
+                .body(Body::empty())
+                .unwrap();
+
+            let response_fut = router.call(request);
+
+            async move {
+                let response = response_fut.await.expect("Should call router");
+                assert_eq!(StatusCode::OK, response.status());
+
+                body_to::<ValidatorMessagesListResponse>(response)
+                    .await
+                    .expect("Should deserialize response")
+            }
+        };
+
+        let signatures = |response: &ValidatorMessagesListResponse| {
+            response
+                .messages
+                .iter()
+                .map(|validator_message| match &validator_message.msg {
+                    MessageTypes::Heartbeat(heartbeat) => heartbeat.signature.clone(),
+                    msg => panic!("Only Heartbeat messages are expected, got: {msg:?}"),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        insert_heartbeats(0..3).await;
+
+        let first_page = fetch_page(None).await;
+        assert_eq!(vec!["heartbeat 0", "heartbeat 1"], signatures(&first_page));
+        assert!(first_page.next_cursor.is_some());
+
+        // new messages received in between the pages should neither be skipped
+        // nor should they shift the messages of the next page
+        insert_heartbeats(3..5).await;
+
+        let second_page = fetch_page(first_page.next_cursor).await;
+        assert_eq!(vec!["heartbeat 2", "heartbeat 3"], signatures(&second_page));
+        assert!(second_page.next_cursor.is_some());
+
+        let last_page = fetch_page(second_page.next_cursor).await;
+        assert_eq!(vec!["heartbeat 4"], signatures(&last_page));
+        assert_eq!(
+            None, last_page.next_cursor,
+            "The last, non-full page should not have a cursor"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_spender_all_after_create_channel() -> Result<(), Box<dyn std::error::Error>> {
+        let mut router = channels_router::<Dummy>();
+
+        let app_guard = setup_dummy_app().await;
+        let app = Arc::new(app_guard.app);
+
+        let channel_context = CAMPAIGNS[0].clone().of_channel();
+        let channel = channel_context.context;
+        let auth = Auth {
+            era: 1,
+            uid: IDS[&LEADER],
+            chain: channel_context.chain.clone(),
+            scope: Scope::Full,
+        };
+
+        let spender_all_request = || {
+            Request::builder()
+                .uri(format!("/{id}/spender/all", id = channel.id()))
+                .extension(app.clone())
+                .extension(auth.clone())
+                .body(Body::empty())
+        };
+
+        // GET /v5/channel/:id/spender/all
+        // The Channel does not exist yet
+        {
+            let response = router.call(spender_all_request()?).await?;
+
+            assert_eq!(StatusCode::NOT_FOUND, response.status());
+        }
+
+        // POST /v5/channel
+        {
+            let request = Request::builder()
+                .method("POST")
+                .uri("/")
+                .header("content-type", "application/json")
+                .extension(app.clone())
+                .extension(auth.clone())
+                .body(Body::from(serde_json::to_vec(&channel)?))?;
+
+            let response = router.call(request).await?;
+            assert_eq!(StatusCode::OK, response.status());
+
+            let created = body_to::<Channel>(response).await?;
+            assert_eq!(channel, created);
+        }
+
+        // GET /v5/channel/:id/spender/all
+        // The Channel is registered
+        {
+            let response = router.call(spender_all_request()?).await?;
+            assert_eq!(StatusCode::OK, response.status());
+
+            let response = body_to::<AllSpendersResponse>(response).await?;
+            assert!(response.spenders.is_empty());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn accounting_and_last_approved_respond_not_modified_if_unchanged(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut router = channels_router::<Dummy>();
+
+        let app_guard = setup_dummy_app().await;
+        let app = Arc::new(app_guard.app);
+
+        let channel_context = CAMPAIGNS[0].clone().of_channel();
+        let channel = channel_context.context;
+        insert_channel(&app.pool, &channel_context)
+            .await
+            .expect("should insert channel");
+
+        let request = |uri: &str, if_none_match: Option<&HeaderValue>| {
+            let builder = Request::builder().uri(uri).extension(app.clone());
+            let builder = match if_none_match {
+                Some(etag) => builder.header(IF_NONE_MATCH, etag),
+                None => builder,
+            };
+
+            builder.body(Body::empty())
+        };
+
+        let accounting_uri = format!("/{id}/accounting", id = channel.id());
+        let last_approved_uri =
+            format!("/{id}/last-approved?withHeartbeat=true", id = channel.id());
+
+        for uri in [&accounting_uri, &last_approved_uri] {
+            let response = router.call(request(uri, None)?).await?;
+            assert_eq!(StatusCode::OK, response.status(), "{uri}");
+            let etag = response
+                .headers()
+                .get(ETAG)
+                .cloned()
+                .expect("Should have an ETag");
+
+            let response = router.call(request(uri, Some(&etag))?).await?;
+            assert_eq!(StatusCode::NOT_MODIFIED, response.status(), "{uri}");
+            assert_eq!(Some(&etag), response.headers().get(ETAG), "{uri}");
+            assert!(
+                hyper::body::to_bytes(response.into_body())
+                    .await?
+                    .is_empty(),
+                "{uri}"
+            );
+        }
+
+        // the accounting has changed
+        {
+            let response = router.call(request(&accounting_uri, None)?).await?;
+            let etag = response
+                .headers()
+                .get(ETAG)
+                .cloned()
+                .expect("Should have an ETag");
+
+            let mut balances = Balances::<CheckedState>::new();
+            balances.spend(*ADVERTISER, *PUBLISHER, UnifiedNum::from_u64(1_000))?;
+            spend_amount(app.pool.clone(), channel.id(), balances).await?;
+
+            let response = router.call(request(&accounting_uri, Some(&etag))?).await?;
+            assert_eq!(StatusCode::OK, response.status());
+            assert_ne!(Some(&etag), response.headers().get(ETAG));
+        }
+
+        // a new Heartbeat has been received
+        {
+            let response = router.call(request(&last_approved_uri, None)?).await?;
+            let etag = response
+                .headers()
+                .get(ETAG)
+                .cloned()
+                .expect("Should have an ETag");
+
+            let heartbeat = MessageTypes::Heartbeat(Heartbeat {
+                signature: "of leader".into(),
+                state_root: String::new(),
+                timestamp: Utc::now(),
+            });
+            insert_validator_message(&app.pool, &channel, &IDS[&LEADER], &heartbeat).await?;
+
+            let response = router
+                .call(request(&last_approved_uri, Some(&etag))?)
+                .await?;
+            assert_eq!(StatusCode::OK, response.status());
+            assert_ne!(Some(&etag), response.headers().get(ETAG));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn campaign_routes_respond_not_found_before_forbidden() {
+        let app_guard = setup_dummy_app().await;
+        let app = Arc::new(app_guard.app);
+        let mut router = campaigns_router::<Dummy>();
+
+        let campaign_context = app
+            .config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Dummy channel Token should be present in config!")
+            .with_campaign(DUMMY_CAMPAIGN.clone());
+        insert_channel(&app.pool, &campaign_context.of_channel())
+            .await
+            .expect("Should insert Channel");
+        insert_campaign(&app.pool, &campaign_context.context)
+            .await
+            .expect("Should insert Campaign");
+
+        let auth_as = |address| Auth {
+            era: 1,
+            uid: ValidatorId::from(address),
+            chain: campaign_context.chain.clone(),
+            scope: Scope::Full,
+        };
+        let not_creator = *ADVERTISER;
+        assert_ne!(not_creator, campaign_context.context.creator);
+
+        let missing_campaign = CampaignId::new();
+        let cases = [
+            // update
+            (missing_campaign, "", *CREATOR, StatusCode::NOT_FOUND),
+            (missing_campaign, "", not_creator, StatusCode::NOT_FOUND),
+            (
+                campaign_context.context.id,
+                "",
+                not_creator,
+                StatusCode::FORBIDDEN,
+            ),
+            // close
+            (missing_campaign, "/close", *CREATOR, StatusCode::NOT_FOUND),
+            (
+                missing_campaign,
+                "/close",
+                not_creator,
+                StatusCode::NOT_FOUND,
+            ),
+            (
+                campaign_context.context.id,
+                "/close",
+                not_creator,
+                StatusCode::FORBIDDEN,
+            ),
+        ];
+
+        for (campaign_id, route, caller, expected) in cases {
+            let request = Request::builder()
+                .method("POST")
+                .uri(format!("/{campaign_id}{route}"))
+                .header("Content-Type", "application/json")
+                .extension(app.clone())
+                .extension(auth_as(caller))
+                .body(Body::from("{}"))
+                .expect("Should build Request");
+
+            let response = router
+                .call(request)
+                .await
+                .expect("Should make request to Router");
+
+            assert_eq!(
+                expected,
+                response.status(),
+                "POST /v5/campaign/{campaign_id}{route} by {caller}"
+            );
+        }
+    }
 }
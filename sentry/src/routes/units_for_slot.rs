@@ -9,7 +9,6 @@ use axum::{
 use chrono::Utc;
 use futures::future::try_join_all;
 use once_cell::sync::Lazy;
-use reqwest::Url;
 use slog::{debug, error, Logger};
 use thiserror::Error;
 use woothee::{parser::Parser, woothee::VALUE_UNKNOWN};
@@ -24,6 +23,7 @@ use primitives::{
         IMPRESSION,
     },
     targeting::{eval_with_callback, get_pricing_bounds, input, input::Input, Output},
+    util::referrer::Referrer,
     AdSlot, Address, Campaign, Config, UnifiedNum, ValidatorId, IPFS,
 };
 
@@ -105,9 +105,11 @@ where
         .get(CLOUDFLARE_IPCOUNTRY_HEADER.clone())
         .and_then(|h| h.to_str().map(ToString::to_string).ok());
 
-    let hostname = Url::parse(&slot.website.clone().unwrap_or_default())
-        .ok()
-        .and_then(|url| url.host().map(|h| h.to_string()))
+    let hostname = slot
+        .website
+        .as_deref()
+        .and_then(Referrer::parse)
+        .map(|website| website.host().to_string())
         .unwrap_or_default();
 
     let publisher_id = slot.owner;
@@ -231,6 +233,28 @@ async fn get_campaigns(
         })
         .collect::<Vec<_>>();
 
+    // 2.1. Skip the Campaigns which have reached the daily cap of their pacing (in redis)
+    let paced_campaigns = campaigns_with_remaining
+        .iter()
+        .filter(|campaign| campaign.pacing.is_some())
+        .map(|campaign| campaign.id)
+        .collect::<Vec<_>>();
+    let daily_spent = campaign_remaining
+        .get_multiple_daily_spent(&paced_campaigns)
+        .await?;
+
+    let campaigns_with_remaining = campaigns_with_remaining
+        .into_iter()
+        .filter(|campaign| match (&campaign.pacing, daily_spent.get(&campaign.id)) {
+            (Some(pacing), Some(spent)) if *spent >= pacing.daily_cap => {
+                debug!(logger, "Skip {:?} because it has reached its daily cap", campaign.id; "daily_cap" => %pacing.daily_cap, "spent" => %spent);
+
+                false
+            }
+            _ => true,
+        })
+        .collect::<Vec<_>>();
+
     let channels = campaigns_with_remaining
         .iter()
         .map(|campaign| campaign.channel.id())
@@ -2,7 +2,7 @@ use std::iter::Iterator;
 
 use adapter::{
     ethereum::test_util::{GANACHE_INFO_1, GANACHE_INFO_1337},
-    primitives::Deposit,
+    primitives::{Deposit, Scope},
 };
 use axum::http::HeaderValue;
 use chrono::{TimeZone, Utc};
@@ -170,6 +170,7 @@ async fn test_targeting_input() {
                     era: 0,
                     uid: campaigns[0].context.creator.into(),
                     chain: campaigns[0].chain.clone(),
+                    scope: Scope::Full,
                 }),
                 Extension(app.clone()),
             )
@@ -186,6 +187,7 @@ async fn test_targeting_input() {
                     era: 0,
                     uid: campaigns[1].context.creator.into(),
                     chain: campaigns[1].chain.clone(),
+                    scope: Scope::Full,
                 }),
                 Extension(app.clone()),
             )
@@ -202,6 +204,7 @@ async fn test_targeting_input() {
                     era: 0,
                     uid: campaigns[2].context.creator.into(),
                     chain: campaigns[2].chain.clone(),
+                    scope: Scope::Full,
                 }),
                 Extension(app.clone()),
             )
@@ -369,6 +372,7 @@ async fn test_inactive_campaign() {
                 era: 0,
                 uid: campaign.context.creator.into(),
                 chain: campaign.chain.clone(),
+                scope: Scope::Full,
             }),
             Extension(app.clone()),
         )
@@ -469,6 +473,7 @@ async fn test_creator_is_not_the_provided_publisher() {
                         era: 0,
                         uid: campaign.context.creator.into(),
                         chain: campaign.chain.clone(),
+                        scope: Scope::Full,
                     }),
                     Extension(app.clone()),
                 )
@@ -509,6 +514,7 @@ async fn test_creator_is_not_the_provided_publisher() {
                         era: 0,
                         uid: campaign.context.creator.into(),
                         chain: campaign.chain.clone(),
+                        scope: Scope::Full,
                     }),
                     Extension(app.clone()),
                 )
@@ -656,6 +662,7 @@ async fn test_non_matching_ad_units() {
                         era: 0,
                         uid: campaign.context.creator.into(),
                         chain: campaign.chain.clone(),
+                        scope: Scope::Full,
                     }),
                     Extension(app.clone()),
                 )
@@ -698,6 +705,7 @@ async fn test_non_matching_ad_units() {
                     era: 0,
                     uid: campaign.context.creator.into(),
                     chain: campaign.chain.clone(),
+                    scope: Scope::Full,
                 }),
                 Extension(app.clone()),
             )
@@ -825,6 +833,7 @@ async fn test_price_is_less_than_min_per_impression() {
                         era: 0,
                         uid: campaign.context.creator.into(),
                         chain: campaign.chain.clone(),
+                        scope: Scope::Full,
                     }),
                     Extension(app.clone()),
                 )
@@ -883,6 +892,7 @@ async fn test_price_is_less_than_min_per_impression() {
                     era: 0,
                     uid: campaign.context.creator.into(),
                     chain: campaign.chain.clone(),
+                    scope: Scope::Full,
                 }),
                 Extension(app.clone()),
             )
@@ -1010,6 +1020,7 @@ async fn test_non_matching_deposit_asset() {
                         era: 0,
                         uid: campaign.context.creator.into(),
                         chain: campaign.chain.clone(),
+                        scope: Scope::Full,
                     }),
                     Extension(app.clone()),
                 )
@@ -1049,6 +1060,7 @@ async fn test_non_matching_deposit_asset() {
                     era: 0,
                     uid: campaign.context.creator.into(),
                     chain: campaign.chain.clone(),
+                    scope: Scope::Full,
                 }),
                 Extension(app.clone()),
             )
@@ -0,0 +1,193 @@
+//! `/v5/validator` routes
+//!
+
+use std::sync::Arc;
+
+use axum::{extract::Path, Extension, Json};
+
+use adapter::client::Locked;
+use primitives::{
+    sentry::fees_summary::{
+        FeesSummaryQuery, FeesSummaryRequest, FeesSummaryResponse, ValidatorFeesSummary,
+    },
+    UnifiedNum, ValidatorId,
+};
+
+use crate::{
+    application::Qs,
+    db::fees_summary::{list_fees_summaries, upsert_fees_summary},
+    response::ResponseError,
+    Application, Auth,
+};
+
+/// GET `/v5/validator/0xXXX.../fees-summary` request
+///
+/// Query: [`FeesSummaryQuery`]
+///
+/// Response: [`FeesSummaryResponse`]
+pub async fn get_fees_summary<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Path(validator): Path<ValidatorId>,
+    Qs(query): Qs<FeesSummaryQuery>,
+) -> Result<Json<FeesSummaryResponse>, ResponseError> {
+    let summaries = list_fees_summaries(&app.pool, validator, &query).await?;
+
+    let total_fees = summaries
+        .iter()
+        .map(|summary| summary.fees)
+        .sum::<Option<UnifiedNum>>()
+        .ok_or_else(|| ResponseError::BadRequest("Total fees overflow".to_string()))?;
+    let total_delta = summaries
+        .iter()
+        .map(|summary| summary.delta)
+        .sum::<Option<UnifiedNum>>()
+        .ok_or_else(|| ResponseError::BadRequest("Total delta overflow".to_string()))?;
+
+    Ok(Json(FeesSummaryResponse {
+        summaries,
+        total_fees,
+        total_delta,
+    }))
+}
+
+/// POST `/v5/validator/0xXXX.../fees-summary` request
+///
+/// Only the validator itself can create its fees summaries.
+///
+/// Request body (json): [`FeesSummaryRequest`]
+///
+/// Response: [`ValidatorFeesSummary`]
+pub async fn create_fees_summary<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Extension(auth): Extension<Auth>,
+    Path(validator): Path<ValidatorId>,
+    Json(request): Json<FeesSummaryRequest>,
+) -> Result<Json<ValidatorFeesSummary>, ResponseError> {
+    if auth.uid != validator {
+        return Err(ResponseError::Forbidden(
+            "Fees summary can be created only by the validator itself".to_string(),
+        ));
+    }
+
+    let summary = upsert_fees_summary(&app.pool, validator, &request).await?;
+
+    Ok(Json(summary))
+}
+
+#[cfg(test)]
+mod test {
+    use adapter::{ethereum::test_util::GANACHE_1337, primitives::Scope};
+    use chrono::{TimeZone, Utc};
+    use primitives::{
+        channel::Nonce,
+        test_util::{DUMMY_CAMPAIGN, FOLLOWER, IDS, LEADER},
+    };
+
+    use super::*;
+    use crate::{db::insert_channel, test_util::setup_dummy_app};
+
+    #[tokio::test]
+    async fn create_and_get_fees_summaries() {
+        let app_guard = setup_dummy_app().await;
+        let app = Extension(Arc::new(app_guard.app.clone()));
+
+        let leader = IDS[&LEADER];
+        let auth = |uid: ValidatorId| Auth {
+            era: 0,
+            uid,
+            chain: GANACHE_1337.clone(),
+            scope: Scope::Full,
+        };
+
+        let first_channel = DUMMY_CAMPAIGN.channel;
+        let second_channel = {
+            let mut channel = DUMMY_CAMPAIGN.channel;
+            channel.nonce = Nonce::from(2_u32);
+            channel
+        };
+        for channel in [first_channel, second_channel] {
+            let channel_context = app
+                .config
+                .find_chain_of(channel.token)
+                .expect("Dummy channel Token should be present in config!")
+                .with(channel);
+            insert_channel(&app.pool, &channel_context)
+                .await
+                .expect("should insert channel");
+        }
+
+        let period = Utc.with_ymd_and_hms(2022, 12, 1, 10, 0, 0).unwrap();
+        let requests = [
+            FeesSummaryRequest {
+                channel: first_channel.id(),
+                period,
+                fees: UnifiedNum::from_u64(1_500),
+                delta: UnifiedNum::from_u64(500),
+            },
+            FeesSummaryRequest {
+                channel: second_channel.id(),
+                period,
+                fees: UnifiedNum::from_u64(200),
+                delta: UnifiedNum::from_u64(200),
+            },
+        ];
+
+        // Not the validator itself
+        {
+            let result = create_fees_summary(
+                app.clone(),
+                Extension(auth(IDS[&FOLLOWER])),
+                Path(leader),
+                Json(requests[0].clone()),
+            )
+            .await;
+
+            assert!(matches!(result, Err(ResponseError::Forbidden(_))));
+        }
+
+        for request in requests.iter() {
+            let summary = create_fees_summary(
+                app.clone(),
+                Extension(auth(leader)),
+                Path(leader),
+                Json(request.clone()),
+            )
+            .await
+            .expect("Should create fees summary")
+            .0;
+
+            assert_eq!(leader, summary.validator);
+            assert_eq!(request.channel, summary.channel);
+            assert_eq!(request.fees, summary.fees);
+        }
+
+        // aggregated across channels
+        {
+            let response =
+                get_fees_summary(app.clone(), Path(leader), Qs(FeesSummaryQuery::default()))
+                    .await
+                    .expect("Should get fees summary")
+                    .0;
+
+            assert_eq!(2, response.summaries.len());
+            assert_eq!(UnifiedNum::from_u64(1_700), response.total_fees);
+            assert_eq!(UnifiedNum::from_u64(700), response.total_delta);
+        }
+
+        // filtered by channel
+        {
+            let query = FeesSummaryQuery {
+                channel: Some(second_channel.id()),
+                period: Some(period),
+            };
+            let response = get_fees_summary(app.clone(), Path(leader), Qs(query))
+                .await
+                .expect("Should get fees summary")
+                .0;
+
+            assert_eq!(1, response.summaries.len());
+            assert_eq!(UnifiedNum::from_u64(200), response.total_fees);
+            assert_eq!(UnifiedNum::from_u64(200), response.total_delta);
+        }
+    }
+}
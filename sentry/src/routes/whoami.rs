@@ -0,0 +1,114 @@
+//! `GET /v5/whoami` request
+
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+
+use adapter::client::Locked;
+use primitives::sentry::whoami::{
+    challenge_message, WhoamiQuery, WhoamiResponse, MAX_CHALLENGE_LENGTH,
+};
+
+use crate::{application::Qs, response::ResponseError, Application};
+
+/// GET `/v5/whoami` request
+///
+/// Used by other validators to make sure that this Sentry speaks for the validator
+/// it claims to, by signing the [`challenge_message()`] of the given challenge
+/// with the [`Application::whoami_signer`].
+///
+/// Responds with `404 Not Found` if the [`Application::whoami_signer`] is not set.
+///
+/// Request query parameters: [`WhoamiQuery`]
+///
+/// Response: [`WhoamiResponse`]
+pub async fn get_whoami<C: Locked + 'static>(
+    Extension(app): Extension<Arc<Application<C>>>,
+    Qs(query): Qs<WhoamiQuery>,
+) -> Result<Json<WhoamiResponse>, ResponseError> {
+    if query.challenge.is_empty() || query.challenge.len() > MAX_CHALLENGE_LENGTH {
+        return Err(ResponseError::BadRequest(format!(
+            "Challenge should be between 1 and {} characters long",
+            MAX_CHALLENGE_LENGTH
+        )));
+    }
+
+    let signer = app.whoami_signer.as_ref().ok_or(ResponseError::NotFound)?;
+    let signature = signer.sign(&challenge_message(&query.challenge))?;
+
+    Ok(Json(WhoamiResponse {
+        validator_id: signer.whoami(),
+        signature,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::setup_dummy_app;
+
+    #[tokio::test]
+    async fn test_whoami_signs_the_challenge() {
+        let app_guard = setup_dummy_app().await;
+        let signer = app_guard
+            .app
+            .adapter
+            .clone()
+            .unlock()
+            .expect("Should unlock the Dummy adapter");
+        let app = Arc::new(app_guard.app.clone().with_whoami_signer(Arc::new(signer)));
+
+        let query = WhoamiQuery {
+            challenge: "0x1234".to_string(),
+        };
+        let response = get_whoami(Extension(app.clone()), Qs(query))
+            .await
+            .expect("Should sign the challenge")
+            .0;
+
+        assert_eq!(app.adapter.whoami(), response.validator_id);
+        assert!(app
+            .adapter
+            .verify(
+                app.adapter.whoami(),
+                &challenge_message("0x1234"),
+                &response.signature
+            )
+            .expect("Should verify"));
+
+        // empty challenge
+        {
+            let query = WhoamiQuery {
+                challenge: String::new(),
+            };
+            let response = get_whoami(Extension(app.clone()), Qs(query)).await;
+
+            assert!(matches!(response, Err(ResponseError::BadRequest(_))));
+        }
+
+        // too long challenge
+        {
+            let query = WhoamiQuery {
+                challenge: "a".repeat(MAX_CHALLENGE_LENGTH + 1),
+            };
+            let response = get_whoami(Extension(app), Qs(query)).await;
+
+            assert!(matches!(response, Err(ResponseError::BadRequest(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_whoami_without_a_signer() {
+        let app_guard = setup_dummy_app().await;
+        let app = Arc::new(app_guard.app.clone());
+
+        let query = WhoamiQuery {
+            challenge: "0x1234".to_string(),
+        };
+        let response = get_whoami(Extension(app), Qs(query))
+            .await
+            .expect_err("Should not sign without a signer");
+
+        assert_eq!(ResponseError::NotFound, response);
+    }
+}
@@ -1,26 +1,50 @@
 pub mod fee {
 
-    use primitives::{Address, DomainError, UnifiedNum, ValidatorDesc};
+    use primitives::{config::PayoutRounding, Address, DomainError, UnifiedNum, ValidatorDesc};
 
     /// Calculates the fee for a given payout of the specified validator
     /// This function will return None if the provided validator is not part of the Campaign / Channel
     /// In the case of overflow when calculating the payout, an error will be returned
+    ///
+    /// The fee is rounded based on the Token's [`PayoutRounding`].
+    ///
+    /// The validator fee is capped to the Token's maximum validator fee, if any,
+    /// see [`TokenInfo::max_validator_fee_rate`](primitives::config::TokenInfo::max_validator_fee_rate).
     pub fn calculate_fee(
         (_earner, payout): (Address, UnifiedNum),
         validator: &ValidatorDesc,
+        rounding: PayoutRounding,
+        max_fee: Option<UnifiedNum>,
     ) -> Result<UnifiedNum, DomainError> {
+        // e.g. a zero fee validator of a Channel with `allow_zero_fees` tokens
+        if validator.fee == UnifiedNum::ZERO {
+            return Ok(UnifiedNum::ZERO);
+        }
+
+        // Campaigns over the maximum fee are rejected on creation,
+        // but we guard against them when calculating the fee as well
+        let validator_fee = match max_fee {
+            Some(max_fee) => validator.fee.min(max_fee),
+            None => validator.fee,
+        };
+
+        let fee = match rounding {
+            PayoutRounding::Floor => payout.checked_mul_floor(&validator_fee),
+            PayoutRounding::Nearest => payout.checked_mul(&validator_fee),
+        };
+
         // should never overflow, but we guard against overflow
-        payout
-            .checked_mul(&validator.fee)
-            .ok_or_else(|| DomainError::InvalidArgument("payout calculation overflow".to_string()))
+        fee.ok_or_else(|| DomainError::InvalidArgument("payout calculation overflow".to_string()))
     }
 
     #[cfg(test)]
     mod test {
         use primitives::{
-            test_util::{DUMMY_VALIDATOR_LEADER, PUBLISHER},
+            balances::CheckedState,
+            config::PayoutRounding,
+            test_util::{CREATOR, DUMMY_VALIDATOR_FOLLOWER, DUMMY_VALIDATOR_LEADER, PUBLISHER},
             unified_num::FromWhole,
-            UnifiedNum, ValidatorDesc,
+            Balances, UnifiedNum, ValidatorDesc,
         };
 
         use crate::spender::fee::calculate_fee;
@@ -39,7 +63,8 @@ pub mod fee {
                 let payout = (*PUBLISHER, UnifiedNum::from_whole(0.0003));
 
                 let validator_fee =
-                    calculate_fee(payout, &dummy_leader).expect("Should not overflow");
+                    calculate_fee(payout, &dummy_leader, PayoutRounding::Nearest, None)
+                        .expect("Should not overflow");
 
                 assert_eq!(
                     UnifiedNum::from_whole(0.00003),
@@ -57,7 +82,8 @@ pub mod fee {
                 // u64::MAX * u64::MAX / 100 000 000 000
                 let payout = (*PUBLISHER, UnifiedNum::from(u64::MAX));
 
-                calculate_fee(payout, &very_high_fee).expect_err("Should overflow");
+                calculate_fee(payout, &very_high_fee, PayoutRounding::Nearest, None)
+                    .expect_err("Should overflow");
             }
 
             // whole number payout
@@ -67,11 +93,95 @@ pub mod fee {
 
                 // 300 000 000 × 10 000 000 / 100 000 000 = 30 000 000
                 let validator_fee =
-                    calculate_fee(payout, &dummy_leader).expect("Should not overflow");
+                    calculate_fee(payout, &dummy_leader, PayoutRounding::Nearest, None)
+                        .expect("Should not overflow");
 
                 // 0.3
                 assert_eq!(UnifiedNum::from_whole(0.3), validator_fee);
             }
         }
+
+        #[test]
+        fn test_fee_is_capped_to_the_max_fee() {
+            let mut leader = DUMMY_VALIDATOR_LEADER.clone();
+            leader.fee = UnifiedNum::from_whole(0.5);
+            // 3 TOKENs
+            let payout = (*PUBLISHER, UnifiedNum::from_whole(3));
+
+            let max_fee = Some(UnifiedNum::from_whole(0.1));
+            for rounding in [PayoutRounding::Floor, PayoutRounding::Nearest] {
+                let capped_fee =
+                    calculate_fee(payout, &leader, rounding, max_fee).expect("Should not overflow");
+                assert_eq!(UnifiedNum::from_whole(0.3), capped_fee);
+
+                // a fee below the maximum fee is not changed
+                let fee = calculate_fee(payout, &leader, rounding, Some(UnifiedNum::from_whole(1)))
+                    .expect("Should not overflow");
+                assert_eq!(UnifiedNum::from_whole(1.5), fee);
+            }
+        }
+
+        #[test]
+        fn test_zero_fee_is_not_spent() {
+            let mut leader = DUMMY_VALIDATOR_LEADER.clone();
+            leader.fee = UnifiedNum::ZERO;
+            let payout = (*PUBLISHER, UnifiedNum::from_whole(0.0003));
+
+            for rounding in [PayoutRounding::Floor, PayoutRounding::Nearest] {
+                let leader_fee =
+                    calculate_fee(payout, &leader, rounding, None).expect("Should not overflow");
+                assert_eq!(UnifiedNum::ZERO, leader_fee);
+
+                let mut balances = Balances::<CheckedState>::new();
+                balances
+                    .spend(*CREATOR, leader.id.to_address(), leader_fee)
+                    .expect("Should spend");
+                balances
+                    .spend(*CREATOR, payout.0, payout.1)
+                    .expect("Should spend");
+
+                assert_eq!(
+                    None,
+                    balances.earners.get(&leader.id.to_address()),
+                    "No zero fee should be earned by the Leader"
+                );
+                assert_eq!(Some(&payout.1), balances.spenders.get(&*CREATOR));
+            }
+        }
+
+        #[test]
+        fn test_fee_rounding() {
+            let mut leader = DUMMY_VALIDATOR_LEADER.clone();
+            leader.fee = UnifiedNum::from_whole(0.15);
+            let mut follower = DUMMY_VALIDATOR_FOLLOWER.clone();
+            follower.fee = UnifiedNum::from_whole(0.1);
+
+            // 0.00 000 015 * 0.15 = UnifiedNum(2.25)
+            // 0.00 000 015 * 0.10 = UnifiedNum(1.5)
+            let payout = (*PUBLISHER, UnifiedNum::from(15_u64));
+
+            let cases = [
+                (PayoutRounding::Floor, 2_u64, 1_u64),
+                (PayoutRounding::Nearest, 2_u64, 2_u64),
+            ];
+
+            for (rounding, expected_leader_fee, expected_follower_fee) in cases {
+                let leader_fee =
+                    calculate_fee(payout, &leader, rounding, None).expect("Should not overflow");
+                let follower_fee =
+                    calculate_fee(payout, &follower, rounding, None).expect("Should not overflow");
+
+                assert_eq!(
+                    UnifiedNum::from(expected_leader_fee),
+                    leader_fee,
+                    "Unexpected Leader fee for {rounding:?}"
+                );
+                assert_eq!(
+                    UnifiedNum::from(expected_follower_fee),
+                    follower_fee,
+                    "Unexpected Follower fee for {rounding:?}"
+                );
+            }
+        }
     }
 }
@@ -1,11 +1,20 @@
 //! Testing utilities for the Sentry Application
+//!
+//! Available for downstream crates with the `test-util` feature.
 
 use std::ops;
 
-use axum::{body::BoxBody, response::Response};
+use axum::{
+    body::{Body, BoxBody},
+    http::Request,
+    response::Response,
+    Router,
+};
 use serde::de::DeserializeOwned;
+use tower::ServiceExt;
 
 use adapter::{
+    client::Locked,
     dummy::{Dummy, Options},
     Adapter,
 };
@@ -15,15 +24,13 @@ use primitives::{
 };
 
 use crate::{
-    db::{
-        redis_pool::TESTS_POOL,
-        tests_postgres::{setup_test_migrations, DATABASE_POOL},
-        CampaignRemaining,
-    },
+    db::{tests_postgres::setup_test_migrations, CampaignRemaining, RedisKeys},
     platform::PlatformApi,
     Application,
 };
 
+pub use crate::db::{redis_pool::TESTS_POOL, tests_postgres::DATABASE_POOL};
+
 /// This guard holds the Redis and Postgres pools taken from their respective Pool of pools.
 ///
 /// This ensures that they will not be dropped which will cause tests to fail randomly.
@@ -58,6 +65,7 @@ pub async fn setup_dummy_app() -> ApplicationGuard {
         dummy_identity: IDS[&LEADER],
         dummy_auth_tokens: DUMMY_AUTH.clone(),
         dummy_chains: config.chains.values().cloned().collect(),
+        failure_injection: None,
     }));
 
     let redis = TESTS_POOL.get().await.expect("Should return Object");
@@ -69,11 +77,16 @@ pub async fn setup_dummy_app() -> ApplicationGuard {
 
     let logger = discard_logger();
 
-    let campaign_remaining = CampaignRemaining::new(redis.connection.clone());
+    let campaign_remaining = CampaignRemaining::new(redis.connection.clone(), RedisKeys::default());
 
     let platform_url = "http://change-me.tm".parse().expect("Bad ApiUrl!");
-    let platform_api = PlatformApi::new(platform_url, config.sentry.platform.keep_alive_interval)
-        .expect("should build test PlatformApi");
+    let platform_api = PlatformApi::new(
+        platform_url,
+        config.sentry.platform.keep_alive_interval,
+        config.sentry.platform.timeout,
+        config.sentry.platform.slot_cache_ttl,
+    )
+    .expect("should build test PlatformApi");
 
     let app = Application::new(
         adapter,
@@ -92,6 +105,26 @@ pub async fn setup_dummy_app() -> ApplicationGuard {
     }
 }
 
+/// Builds the [`Router`] of the `app` with all of its routes and layers.
+///
+/// It can be called in-memory without binding a socket,
+/// e.g. with [`tower::ServiceExt::oneshot`] or using [`oneshot`].
+pub async fn app_router<C: Locked + 'static>(app: &Application<C>) -> Router {
+    app.routing().await
+}
+
+/// Makes a single in-memory request to the [`app_router`] of the `app`.
+pub async fn oneshot<C: Locked + 'static>(
+    app: &Application<C>,
+    request: Request<Body>,
+) -> Response<BoxBody> {
+    app_router(app)
+        .await
+        .oneshot(request)
+        .await
+        .expect("Router should never fail")
+}
+
 /// Extracts the body as a String from the Response.
 ///
 /// Used when you want to check the response body or debug a response.
@@ -0,0 +1,180 @@
+//! Harness tests which run the Sentries with the [`Dummy`] adapter and
+//! the leader & follower validator identities in a single Validator worker.
+//!
+//! These tests do not require any Ganache chains running,
+//! only Redis & Postgres with the [`DUMMY_VALIDATORS`] databases.
+use adapter::{
+    dummy::{Dummy, Options},
+    prelude::*,
+    primitives::Deposit,
+    Adapter,
+};
+use primitives::{
+    config::GANACHE_CONFIG,
+    sentry::{campaign_create::CreateCampaign, Event, InsertEventsRequest, SuccessResponse},
+    test_util::{
+        CREATOR, DUMMY_AUTH, DUMMY_CAMPAIGN, DUMMY_IPFS, DUMMY_VALIDATOR_FOLLOWER,
+        DUMMY_VALIDATOR_LEADER, FOLLOWER, IDS, LEADER, PUBLISHER,
+    },
+    unified_num::FromWhole,
+    util::logging::new_logger,
+    Campaign, UnifiedNum, ValidatorDesc, ValidatorId,
+};
+use reqwest::StatusCode;
+use validator_worker::{sentry_interface::build_client, SentryApi, Worker};
+
+use crate::{run::run_sentry_app, TestValidator, DUMMY_VALIDATORS};
+
+fn dummy_options(identity: ValidatorId) -> Options {
+    Options {
+        dummy_identity: identity,
+        dummy_auth_tokens: DUMMY_AUTH.clone(),
+        dummy_chains: GANACHE_CONFIG.chains.values().cloned().collect(),
+        failure_injection: None,
+    }
+}
+
+/// Runs the Sentry of the [`TestValidator`] with the [`Dummy`] adapter
+/// and sets the given deposit of the `Campaign.creator` for the Campaign's Channel.
+async fn setup_dummy_sentry(validator: &TestValidator, campaign: &Campaign, deposit: &Deposit) {
+    let adapter = Adapter::new(Dummy::init(dummy_options(IDS[&validator.address])));
+
+    let channel_context = validator
+        .config
+        .find_chain_of(campaign.channel.token)
+        .expect("Campaign token should be whitelisted in config")
+        .with_channel(campaign.channel);
+    adapter
+        .client
+        .set_deposit(&channel_context, campaign.creator, deposit.clone());
+
+    run_sentry_app(adapter, validator)
+        .await
+        .expect("To run Sentry API server");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn leader_and_follower_in_a_single_worker() {
+    let leader = DUMMY_VALIDATORS[&LEADER].clone();
+    let follower = DUMMY_VALIDATORS[&FOLLOWER].clone();
+
+    // point the Campaign validators to the dummy Sentries
+    let campaign = {
+        let mut campaign = DUMMY_CAMPAIGN.clone();
+        campaign.validators = (
+            ValidatorDesc {
+                url: leader.sentry_url.to_string(),
+                ..DUMMY_VALIDATOR_LEADER.clone()
+            },
+            ValidatorDesc {
+                url: follower.sentry_url.to_string(),
+                ..DUMMY_VALIDATOR_FOLLOWER.clone()
+            },
+        )
+            .into();
+
+        campaign
+    };
+    let token_chain = GANACHE_CONFIG
+        .find_chain_of(campaign.channel.token)
+        .expect("Campaign token should be whitelisted in config");
+
+    let deposit = Deposit {
+        total: UnifiedNum::from_whole(2_000).to_precision(token_chain.token.precision.get()),
+    };
+
+    setup_dummy_sentry(&leader, &campaign, &deposit).await;
+    setup_dummy_sentry(&follower, &campaign, &deposit).await;
+
+    // the workers share the same HTTP client
+    let client = build_client(&GANACHE_CONFIG).expect("Should build HTTP client");
+    let workers = [&leader, &follower]
+        .into_iter()
+        .map(|validator| {
+            let adapter =
+                Adapter::with_unlocked(Dummy::init(dummy_options(IDS[&validator.address])));
+
+            SentryApi::with_client(
+                adapter,
+                new_logger(&validator.worker_logger_prefix),
+                validator.config.clone(),
+                validator.sentry_url.clone(),
+                client.clone(),
+            )
+            .map(Worker::from_sentry)
+            .expect("Should create the SentryApi")
+        })
+        .collect::<Vec<_>>();
+
+    let creator_adapter = Adapter::with_unlocked(Dummy::init(dummy_options(IDS[&CREATOR])));
+    let create_campaign = CreateCampaign::from_campaign(campaign.clone());
+    let events = vec![
+        Event::Impression {
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[1],
+            referrer: Some("https://adex.network".into()),
+        };
+        3
+    ];
+
+    // Create the Campaign and submit the same events to both Sentries
+    for validator in [&leader, &follower] {
+        let token = creator_adapter
+            .get_auth(token_chain.chain.chain_id, IDS[&validator.address])
+            .expect("Get authentication");
+
+        let create_response = client
+            .post(validator.sentry_url.join("v5/campaign").unwrap())
+            .json(&create_campaign)
+            .bearer_auth(&token)
+            .send()
+            .await
+            .expect("Should create Campaign");
+        assert_eq!(StatusCode::OK, create_response.status());
+
+        let events_response = client
+            .post(
+                validator
+                    .sentry_url
+                    .join(&format!("v5/campaign/{}/events", campaign.id))
+                    .unwrap(),
+            )
+            .json(&InsertEventsRequest {
+                events: events.clone(),
+            })
+            .send()
+            .await
+            .expect("Should post events")
+            .json::<SuccessResponse>()
+            .await
+            .expect("Should deserialize response");
+        assert_eq!(SuccessResponse { success: true }, events_response);
+    }
+
+    // The first tick produces the leader's NewState,
+    // the second one - the follower's ApproveState
+    Worker::run_concurrently(&workers, true).await;
+    Worker::run_concurrently(&workers, true).await;
+
+    let last_approved = workers[0]
+        .sentry
+        .get_last_approved(campaign.channel.id())
+        .await
+        .expect("Should fetch last approved from the leader Sentry")
+        .last_approved
+        .expect("Should have last approved messages");
+
+    let new_state = last_approved
+        .new_state
+        .expect("Leader should have last approved NewState");
+    let approve_state = last_approved
+        .approve_state
+        .expect("Leader should have last approved ApproveState");
+
+    assert_eq!(new_state.from, IDS[&LEADER]);
+    assert_eq!(approve_state.from, IDS[&FOLLOWER]);
+
+    assert_eq!(new_state.msg.state_root, approve_state.msg.state_root);
+    assert!(approve_state.msg.is_healthy);
+}
@@ -19,6 +19,8 @@ use slog::{debug, Logger};
 use web3::{transports::Http, Web3};
 
 pub mod deposits;
+#[cfg(test)]
+mod dummy;
 
 /// ganache-cli setup with deployed contracts using the snapshot directory
 /// NOTE: Current the snapshot and test setup use a single Chain.
@@ -112,6 +114,8 @@ pub static VALIDATORS: Lazy<HashMap<Address, TestValidator>> = Lazy::new(|| {
                     ip_addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
                     redis_url: "redis://127.0.0.1:6379/1".parse().unwrap(),
                     seed_db: false,
+                    redis_key_prefix: None,
+                    geoip_database: None,
                 },
                 config: GANACHE_CONFIG.clone(),
                 sentry_url: "http://localhost:8005".parse().expect("Valid Sentry URL"),
@@ -131,6 +135,8 @@ pub static VALIDATORS: Lazy<HashMap<Address, TestValidator>> = Lazy::new(|| {
                     ip_addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
                     redis_url: "redis://127.0.0.1:6379/2".parse().unwrap(),
                     seed_db: false,
+                    redis_key_prefix: None,
+                    geoip_database: None,
                 },
                 config: GANACHE_CONFIG.clone(),
                 sentry_url: "http://localhost:8006".parse().expect("Valid Sentry URL"),
@@ -144,6 +150,63 @@ pub static VALIDATORS: Lazy<HashMap<Address, TestValidator>> = Lazy::new(|| {
     .collect()
 });
 
+/// Test validators used for running the leader & follower Sentries with the [`adapter::Dummy`] adapter
+/// and both validator identities in a single Validator worker process.
+///
+/// They use different ports, Redis & Postgres databases from the [`VALIDATORS`],
+/// the `keystore` is not used by the Dummy adapter.
+pub static DUMMY_VALIDATORS: Lazy<HashMap<Address, TestValidator>> = Lazy::new(|| {
+    use adapter::ethereum::test_util::KEYSTORES;
+    use primitives::config::Environment;
+
+    vec![
+        (
+            *LEADER,
+            TestValidator {
+                address: *LEADER,
+                keystore: KEYSTORES[&LEADER].clone(),
+                sentry_config: sentry::application::EnvConfig {
+                    env: Environment::Development,
+                    port: 8015,
+                    ip_addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+                    redis_url: "redis://127.0.0.1:6379/3".parse().unwrap(),
+                    seed_db: false,
+                    redis_key_prefix: None,
+                    geoip_database: None,
+                },
+                config: GANACHE_CONFIG.clone(),
+                sentry_url: "http://localhost:8015".parse().expect("Valid Sentry URL"),
+                sentry_logger_prefix: "sentry-dummy-leader".into(),
+                worker_logger_prefix: "worker-dummy-leader".into(),
+                db_name: "harness_dummy_leader".into(),
+            },
+        ),
+        (
+            *FOLLOWER,
+            TestValidator {
+                address: *FOLLOWER,
+                keystore: KEYSTORES[&FOLLOWER].clone(),
+                sentry_config: sentry::application::EnvConfig {
+                    env: Environment::Development,
+                    port: 8016,
+                    ip_addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+                    redis_url: "redis://127.0.0.1:6379/4".parse().unwrap(),
+                    seed_db: false,
+                    redis_key_prefix: None,
+                    geoip_database: None,
+                },
+                config: GANACHE_CONFIG.clone(),
+                sentry_url: "http://localhost:8016".parse().expect("Valid Sentry URL"),
+                sentry_logger_prefix: "sentry-dummy-follower".into(),
+                worker_logger_prefix: "worker-dummy-follower".into(),
+                db_name: "harness_dummy_follower".into(),
+            },
+        ),
+    ]
+    .into_iter()
+    .collect()
+});
+
 pub struct Setup {
     pub chain: Chain,
     pub logger: Logger,
@@ -240,13 +303,13 @@ mod tests {
         },
         spender::Spender,
         test_util::{
-            ADVERTISER, ADVERTISER_2, DUMMY_AD_UNITS, DUMMY_IPFS, GUARDIAN, GUARDIAN_2, IDS,
-            PUBLISHER, PUBLISHER_2,
+            CampaignBuilder, ChannelFixtureSet, ADVERTISER, ADVERTISER_2, DUMMY_AD_UNITS,
+            DUMMY_IPFS, IDS, PUBLISHER, PUBLISHER_2,
         },
         unified_num::FromWhole,
         util::{logging::new_logger, ApiUrl},
-        validator::{ApproveState, Heartbeat, MessageType, NewState, RejectState},
-        Balances, BigNum, Campaign, CampaignId, ChainId, Channel, ChannelId, UnifiedNum,
+        validator::{ApproveState, Heartbeat, MessageType, NewState, RejectState, ValidatorDesc},
+        Address, Balances, BigNum, Campaign, CampaignId, ChainId, ChannelId, UnifiedNum,
     };
     use reqwest::{Client, StatusCode};
     use slog::info;
@@ -278,79 +341,41 @@ mod tests {
         }
     }
 
-    static CAMPAIGN_1: Lazy<Campaign> = Lazy::new(|| {
-        use chrono::TimeZone;
-        use primitives::{
-            campaign::{Active, Pricing, Validators},
-            targeting::Rules,
-            validator::ValidatorDesc,
-            EventSubmission,
-        };
-
-        let channel = Channel {
-            leader: VALIDATORS[&LEADER].address.into(),
-            follower: VALIDATORS[&FOLLOWER].address.into(),
-            guardian: *GUARDIAN,
-            token: SNAPSHOT_CONTRACTS_1337.token.info.address,
-            nonce: 0_u64.into(),
-        };
-
-        let leader_desc = ValidatorDesc {
-            id: VALIDATORS[&LEADER].address.into(),
-            url: VALIDATORS[&LEADER].sentry_url.to_string(),
-            // min_validator_fee for token: 0.000_010
-            // fee per 1 payout: payout * fee  = payout * 0.00500000
-            fee: UnifiedNum::from_whole(0.005),
-            fee_addr: None,
-        };
+    /// The [`Channel`]s of the harness [`VALIDATORS`]
+    static CHANNELS: Lazy<ChannelFixtureSet> = Lazy::new(|| {
+        ChannelFixtureSet::new(VALIDATORS[&LEADER].address, VALIDATORS[&FOLLOWER].address)
+    });
 
-        let follower_desc = ValidatorDesc {
-            id: VALIDATORS[&FOLLOWER].address.into(),
-            url: VALIDATORS[&FOLLOWER].sentry_url.to_string(),
-            // min_validator_fee for token: 0.000_010
-            // fee per 1 payout: payout * fee = payout * 0.00400000
-            fee: UnifiedNum::from_whole(0.004),
+    /// The [`ValidatorDesc`] of the harness [`VALIDATORS`] with the given fee
+    fn validator_desc(validator: &Address, fee: f64) -> ValidatorDesc {
+        ValidatorDesc {
+            id: VALIDATORS[validator].address.into(),
+            url: VALIDATORS[validator].sentry_url.to_string(),
+            fee: UnifiedNum::from_whole(fee),
             fee_addr: None,
-        };
+        }
+    }
 
-        let validators = Validators::new((leader_desc, follower_desc));
+    static CAMPAIGN_1: Lazy<Campaign> = Lazy::new(|| {
+        use chrono::TimeZone;
 
-        Campaign {
-            id: "0x936da01f9abd4d9d80c702af85c822a8"
-                .parse()
-                .expect("Should parse"),
-            channel,
-            creator: *ADVERTISER,
-            budget: UnifiedNum::from_whole(150),
-            validators,
-            title: Some("Dummy Campaign".to_string()),
-            pricing_bounds: vec![
-                (
-                    IMPRESSION,
-                    Pricing {
-                        min: UnifiedNum::from_whole(0.04),
-                        max: UnifiedNum::from_whole(0.05),
-                    },
-                ),
-                (
-                    CLICK,
-                    Pricing {
-                        min: UnifiedNum::from_whole(0.06),
-                        max: UnifiedNum::from_whole(0.1),
-                    },
-                ),
-            ]
-            .into_iter()
-            .collect(),
-            event_submission: Some(EventSubmission { allow: vec![] }),
-            ad_units: vec![DUMMY_AD_UNITS[0].clone(), DUMMY_AD_UNITS[1].clone()],
-            targeting_rules: Rules::new(),
-            created: Utc.ymd(2021, 2, 1).and_hms(7, 0, 0),
-            active: Active {
-                from: Some(Utc.ymd(2022, 6, 27).and_hms(0, 0, 0)),
-                to: Utc.ymd(2099, 1, 30).and_hms(0, 0, 0),
-            },
-        }
+        // min_validator_fee for token: 0.000_010
+        // fee per 1 payout: payout * fee  = payout * 0.00500000
+        let leader_desc = validator_desc(&LEADER, 0.005);
+        // min_validator_fee for token: 0.000_010
+        // fee per 1 payout: payout * fee = payout * 0.00400000
+        let follower_desc = validator_desc(&FOLLOWER, 0.004);
+
+        CampaignBuilder::new(CHANNELS.channel(SNAPSHOT_CONTRACTS_1337.token.info.address, 0))
+            .creator(*ADVERTISER)
+            .budget_whole(150)
+            .validators(leader_desc, follower_desc)
+            .title("Dummy Campaign")
+            .pricing(IMPRESSION, 0.04, 0.05)
+            .pricing(CLICK, 0.06, 0.1)
+            .ad_units(&DUMMY_AD_UNITS[..2])
+            .active_from(Utc.ymd(2022, 6, 27).and_hms(0, 0, 0))
+            .build()
     });
 
     /// This Campaign's Channel has switched leader & follower compared to [`CAMPAIGN_1`]
@@ -359,43 +384,14 @@ mod tests {
     /// `Channel.follower = VALIDATOR["leader"].address`
     /// See [`VALIDATORS`] for more details.
     static CAMPAIGN_2: Lazy<Campaign> = Lazy::new(|| {
-        use chrono::TimeZone;
-        use primitives::{
-            campaign::{Active, Pricing, Validators},
-            targeting::Rules,
-            validator::ValidatorDesc,
-            EventSubmission,
-        };
-
-        let channel = Channel {
-            leader: VALIDATORS[&FOLLOWER].address.into(),
-            follower: VALIDATORS[&LEADER].address.into(),
-            guardian: *GUARDIAN_2,
-            token: SNAPSHOT_CONTRACTS_1337.token.info.address,
-            nonce: 0_u64.into(),
-        };
-
         // Uses the VALIDATORS[&FOLLOWER] as the Leader for this Channel
         // switches the URL as well
-        let leader_desc = ValidatorDesc {
-            id: VALIDATORS[&FOLLOWER].address.into(),
-            url: VALIDATORS[&FOLLOWER].sentry_url.to_string(),
-            // fee per 1 = 0.00010000
-            fee: UnifiedNum::from_whole(0.0001),
-            fee_addr: None,
-        };
-
+        // fee per 1 = 0.00010000
+        let leader_desc = validator_desc(&FOLLOWER, 0.0001);
         // Uses the VALIDATORS[&LEADER] as the Follower for this Channel
         // switches the URL as well
-        let follower_desc = ValidatorDesc {
-            id: VALIDATORS[&LEADER].address.into(),
-            url: VALIDATORS[&LEADER].sentry_url.to_string(),
-            // fee per 1 = 0.00005000
-            fee: UnifiedNum::from_whole(0.00005),
-            fee_addr: None,
-        };
-
-        let validators = Validators::new((leader_desc, follower_desc));
+        // fee per 1 = 0.00005000
+        let follower_desc = validator_desc(&LEADER, 0.00005);
 
         // CAMPAIGN_2 budget 20 TOKENs (2_000_000_000)
         // leader fee (pro mile) 10_000_000 = 0.10000000 TOKENs
@@ -403,132 +399,49 @@ mod tests {
         // IMPRESSION pricing (min) - 1 TOKEN
         // CLICK pricing (min) - 3 TOKENs
         //
-        Campaign {
-            id: "0x127b98248f4e4b73af409d10f62daeaa"
+        CampaignBuilder::new(CHANNELS.switched(SNAPSHOT_CONTRACTS_1337.token.info.address, 0))
+            .id("0x127b98248f4e4b73af409d10f62daeaa"
                 .parse()
-                .expect("Should parse"),
-            channel,
-            creator: *ADVERTISER,
+                .expect("Should parse"))
+            .creator(*ADVERTISER)
             // 20.00000000
-            budget: UnifiedNum::from_whole(20),
-            validators,
-            title: Some("Dummy Campaign 2 in Chain #1337".to_string()),
-            pricing_bounds: vec![
-                (
-                    IMPRESSION,
-                    Pricing {
-                        // 1 TOKEN
-                        min: UnifiedNum::from_whole(1),
-                        // 2 TOKENs
-                        max: UnifiedNum::from_whole(2),
-                    },
-                ),
-                (
-                    CLICK,
-                    Pricing {
-                        // 3 TOKENs
-                        min: UnifiedNum::from_whole(3),
-                        // 5 TOKENs
-                        max: UnifiedNum::from_whole(5),
-                    },
-                ),
-            ]
-            .into_iter()
-            .collect(),
-            event_submission: Some(EventSubmission { allow: vec![] }),
-            ad_units: vec![DUMMY_AD_UNITS[0].clone(), DUMMY_AD_UNITS[1].clone()],
-            targeting_rules: Rules::new(),
-            created: Utc.ymd(2021, 2, 1).and_hms(7, 0, 0),
-            active: Active {
-                from: None,
-                to: Utc.ymd(2099, 1, 30).and_hms(0, 0, 0),
-            },
-        }
+            .budget_whole(20)
+            .validators(leader_desc, follower_desc)
+            .title("Dummy Campaign 2 in Chain #1337")
+            // 1 - 2 TOKENs
+            .pricing(IMPRESSION, 1.0, 2.0)
+            // 3 - 5 TOKENs
+            .pricing(CLICK, 3.0, 5.0)
+            .ad_units(&DUMMY_AD_UNITS[..2])
+            .build()
     });
 
     /// This Campaign has a token from the GANACHE_1 chain instead of the GANACHE_1337 one like the others
     static CAMPAIGN_3: Lazy<Campaign> = Lazy::new(|| {
-        use chrono::TimeZone;
-        use primitives::{
-            campaign::{Active, Pricing, Validators},
-            targeting::Rules,
-            validator::ValidatorDesc,
-            EventSubmission,
-        };
-
-        let channel = Channel {
-            leader: VALIDATORS[&LEADER].address.into(),
-            follower: VALIDATORS[&FOLLOWER].address.into(),
-            guardian: *GUARDIAN_2,
-            token: SNAPSHOT_CONTRACTS_1.token.info.address,
-            nonce: 1_u64.into(),
-        };
-
-        let leader_desc = ValidatorDesc {
-            id: VALIDATORS[&LEADER].address.into(),
-            url: VALIDATORS[&LEADER].sentry_url.to_string(),
-            // min_validator_fee for token: 0.000_010
-            // fee per 1 payout: payout * fee / 1000 = payout * 0.00200000
-            fee: UnifiedNum::from_whole(0.002),
-            fee_addr: None,
-        };
-
-        let follower_desc = ValidatorDesc {
-            id: VALIDATORS[&FOLLOWER].address.into(),
-            url: VALIDATORS[&FOLLOWER].sentry_url.to_string(),
-            // min_validator_fee for token: 0.000_010
-            // fee per 1 payout: payout * fee = payout * 0.00175000
-            fee: UnifiedNum::from_whole(0.00175),
-            fee_addr: None,
-        };
-
-        let validators = Validators::new((leader_desc, follower_desc));
-
-        Campaign {
-            id: "0xa78f3492481b41a688488a7aa1ff17df"
+        // min_validator_fee for token: 0.000_010
+        // fee per 1 payout: payout * fee / 1000 = payout * 0.00200000
+        let leader_desc = validator_desc(&LEADER, 0.002);
+        // min_validator_fee for token: 0.000_010
+        // fee per 1 payout: payout * fee = payout * 0.00175000
+        let follower_desc = validator_desc(&FOLLOWER, 0.00175);
+
+        CampaignBuilder::new(CHANNELS.with_guardian_2(SNAPSHOT_CONTRACTS_1.token.info.address, 1))
+            .id("0xa78f3492481b41a688488a7aa1ff17df"
                 .parse()
-                .expect("Should parse"),
-            channel,
-            creator: *ADVERTISER_2,
+                .expect("Should parse"))
+            .creator(*ADVERTISER_2)
             // 20.00000000
-            budget: UnifiedNum::from_whole(20),
-            validators,
-            title: Some("Dummy Campaign 3 in Chain #1".to_string()),
-            pricing_bounds: vec![
-                (
-                    IMPRESSION,
-                    Pricing {
-                        // 0.01500000
-                        // Per 1000 = 15.00000000
-                        min: UnifiedNum::from_whole(0.015),
-                        // 0.0250000
-                        // Per 1000 = 25.00000000
-                        max: UnifiedNum::from_whole(0.025),
-                    },
-                ),
-                (
-                    CLICK,
-                    Pricing {
-                        // 0.03500000
-                        // Per 1000 = 35.00000000
-                        min: UnifiedNum::from_whole(0.035),
-                        // 0.06500000
-                        // Per 1000 = 65.00000000
-                        max: UnifiedNum::from_whole(0.065),
-                    },
-                ),
-            ]
-            .into_iter()
-            .collect(),
-            event_submission: Some(EventSubmission { allow: vec![] }),
-            ad_units: vec![DUMMY_AD_UNITS[2].clone(), DUMMY_AD_UNITS[3].clone()],
-            targeting_rules: Rules::new(),
-            created: Utc.ymd(2021, 2, 1).and_hms(7, 0, 0),
-            active: Active {
-                from: None,
-                to: Utc.ymd(2099, 1, 30).and_hms(0, 0, 0),
-            },
-        }
+            .budget_whole(20)
+            .validators(leader_desc, follower_desc)
+            .title("Dummy Campaign 3 in Chain #1")
+            // 0.01500000 - 0.02500000
+            // Per 1000 = 15.00000000 - 25.00000000
+            .pricing(IMPRESSION, 0.015, 0.025)
+            // 0.03500000 - 0.06500000
+            // Per 1000 = 35.00000000 - 65.00000000
+            .pricing(CLICK, 0.035, 0.065)
+            .ad_units(&DUMMY_AD_UNITS[2..])
+            .build()
     });
 
     /// These `CAMPAIGN_2` events are used to test the `ApproveState` with `is_healthy: false`
@@ -596,6 +509,7 @@ mod tests {
                     .ipfs,
                 ad_slot: DUMMY_IPFS[3],
                 referrer: Some("https://ambire.com".into()),
+                click_id: None,
             },
             Event::Click {
                 publisher: *PUBLISHER_2,
@@ -606,6 +520,7 @@ mod tests {
                     .ipfs,
                 ad_slot: DUMMY_IPFS[3],
                 referrer: Some("https://ambire.com".into()),
+                click_id: None,
             },
             Event::Click {
                 publisher: *PUBLISHER_2,
@@ -616,6 +531,7 @@ mod tests {
                     .ipfs,
                 ad_slot: DUMMY_IPFS[3],
                 referrer: Some("https://ambire.com".into()),
+                click_id: None,
             },
         ]
     });
@@ -648,6 +564,32 @@ mod tests {
         }
     }
 
+    /// Calls the Sentry routes in-memory, without spawning the Sentry server.
+    #[tokio::test]
+    async fn sentry_routes_without_a_server() {
+        use axum::{
+            body::Body,
+            http::{Method, Request},
+        };
+        use primitives::Config;
+        use sentry::test_util::{body_to, oneshot, setup_dummy_app};
+
+        let app = setup_dummy_app().await;
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/cfg")
+            .body(Body::empty())
+            .expect("Should build Request");
+        let response = oneshot(&app, request).await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        let config = body_to::<Config>(response)
+            .await
+            .expect("Should deserialize");
+        assert_eq!(app.config, config);
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
     async fn run_full_test() {
         let chain = GANACHE_1337.clone();
@@ -1070,7 +1012,11 @@ mod tests {
             }
         }
 
-        let leader_worker = Worker::from_sentry(leader_sentry.clone());
+        // the tick reports of the Leader worker are used to assert in which tick
+        // the messages were produced
+        let tick_reports_dir = tempfile::tempdir().expect("Should create tick reports dir");
+        let leader_worker = Worker::from_sentry(leader_sentry.clone())
+            .with_tick_report_path(tick_reports_dir.path());
         let follower_worker = Worker::from_sentry(follower_sentry.clone());
 
         // Add new events for `CAMPAIGN_2` to sentry
@@ -1185,6 +1131,7 @@ mod tests {
 
                         balances
                     },
+                    frozen: false,
                 };
                 // Channel Leader (FOLLOWER)
                 let actual_accounting = follower_sentry
@@ -1206,6 +1153,7 @@ mod tests {
         {
             let expected_accounting = AccountingResponse {
                 balances: Balances::<CheckedState>::new(),
+                frozen: false,
             };
             let actual_accounting = leader_sentry
                 .get_accounting(&token_chain_1337.clone().with_channel(CAMPAIGN_1.channel))
@@ -1338,6 +1286,7 @@ mod tests {
                         .ipfs,
                     ad_slot: DUMMY_IPFS[2],
                     referrer: Some("https://ambire.com".into()),
+                    click_id: None,
                 },
             ];
 
@@ -1402,6 +1351,7 @@ mod tests {
                         .ipfs,
                     ad_slot: DUMMY_IPFS[3],
                     referrer: Some("https://ambire.com".into()),
+                    click_id: None,
                 },
                 Event::Click {
                     publisher: *PUBLISHER_2,
@@ -1412,6 +1362,7 @@ mod tests {
                         .ipfs,
                     ad_slot: DUMMY_IPFS[3],
                     referrer: Some("https://ambire.com".into()),
+                    click_id: None,
                 },
             ];
 
@@ -1476,14 +1427,20 @@ mod tests {
             expected_balances
                 .spend(
                     CAMPAIGN_1.creator,
-                    CAMPAIGN_1.channel.leader.to_address(),
+                    CAMPAIGN_1
+                        .leader()
+                        .expect("Should have a Leader")
+                        .fee_recipient(),
                     UnifiedNum::from_whole(0.0005),
                 )
                 .expect("Should spend for Leader");
             expected_balances
                 .spend(
                     CAMPAIGN_1.creator,
-                    CAMPAIGN_1.channel.follower.to_address(),
+                    CAMPAIGN_1
+                        .follower()
+                        .expect("Should have a Follower")
+                        .fee_recipient(),
                     UnifiedNum::from_whole(0.0004),
                 )
                 .expect("Should spend for Follower");
@@ -1493,6 +1450,7 @@ mod tests {
 
             let expected_accounting = AccountingResponse {
                 balances: expected_balances,
+                frozen: false,
             };
 
             let actual_accounting = leader_sentry
@@ -1537,14 +1495,20 @@ mod tests {
             expected_balances
                 .spend(
                     CAMPAIGN_3.creator,
-                    CAMPAIGN_3.channel.leader.to_address(),
+                    CAMPAIGN_3
+                        .leader()
+                        .expect("Should have a Leader")
+                        .fee_recipient(),
                     UnifiedNum::from_whole(0.00020),
                 )
                 .expect("Should spend for Leader");
             expected_balances
                 .spend(
                     CAMPAIGN_3.creator,
-                    CAMPAIGN_3.channel.follower.to_address(),
+                    CAMPAIGN_3
+                        .follower()
+                        .expect("Should have a Follower")
+                        .fee_recipient(),
                     UnifiedNum::from_whole(0.000175),
                 )
                 .expect("Should spend for Follower");
@@ -1558,6 +1522,7 @@ mod tests {
 
             let expected_accounting = AccountingResponse {
                 balances: expected_balances,
+                frozen: false,
             };
 
             let actual_accounting = leader_sentry
@@ -1599,14 +1564,20 @@ mod tests {
                 expected_balances
                     .spend(
                         CAMPAIGN_1.creator,
-                        CAMPAIGN_1.channel.leader.to_address(),
+                        CAMPAIGN_1
+                            .leader()
+                            .expect("Should have a Leader")
+                            .fee_recipient(),
                         UnifiedNum::from_u64(50_000),
                     )
                     .expect("Should spend");
                 expected_balances
                     .spend(
                         CAMPAIGN_1.creator,
-                        CAMPAIGN_1.channel.follower.to_address(),
+                        CAMPAIGN_1
+                            .follower()
+                            .expect("Should have a Follower")
+                            .fee_recipient(),
                         UnifiedNum::from_u64(40_000),
                     )
                     .expect("Should spend");
@@ -1778,7 +1749,38 @@ mod tests {
                     .expect("Should not overflow summing balances")
                     // does not really matter if we're checking earners or spenders for CheckedState
                     .0
-            )
+            );
+
+            // The RejectState should be reported in the second tick of the Channel's Follower (LEADER)
+            let tick_reporter = leader_worker
+                .tick_report
+                .as_ref()
+                .expect("Leader worker should have a tick reporter");
+            let has_reject_state = |tick: u64| {
+                let report = tick_reporter
+                    .read(leader_worker.adapter.whoami(), tick)
+                    .expect("Should read the tick report of the Leader worker");
+                assert!(report.error.is_none(), "Tick should not have failed");
+
+                let channel_report = report
+                    .channel(CAMPAIGN_2.channel.id())
+                    .expect("Should have processed the Campaign 2 channel");
+                assert!(channel_report.error.is_none());
+
+                channel_report.messages.iter().any(|message| {
+                    message.message_type == MessageType::RejectState
+                        && message.state_root == latest_reject_state_follower.state_root
+                })
+            };
+
+            assert!(
+                !has_reject_state(1),
+                "RejectState should not be produced in the first tick"
+            );
+            assert!(
+                has_reject_state(2),
+                "RejectState should be produced in the second tick"
+            );
         }
 
         // For CAMPAIGN_3
@@ -1806,14 +1808,20 @@ mod tests {
                 expected_balances
                     .spend(
                         CAMPAIGN_3.creator,
-                        CAMPAIGN_3.channel.leader.to_address(),
+                        CAMPAIGN_3
+                            .leader()
+                            .expect("Should have a Leader")
+                            .fee_recipient(),
                         UnifiedNum::from_whole(0.0002),
                     )
                     .expect("Should spend");
                 expected_balances
                     .spend(
                         CAMPAIGN_3.creator,
-                        CAMPAIGN_3.channel.follower.to_address(),
+                        CAMPAIGN_3
+                            .follower()
+                            .expect("Should have a Follower")
+                            .fee_recipient(),
                         UnifiedNum::from_whole(0.000175),
                     )
                     .expect("Should spend");
@@ -2748,6 +2756,7 @@ mod tests {
 pub mod run {
     use std::{env::current_dir, net::SocketAddr, path::PathBuf};
 
+    use adapter::{client::Locked, Adapter};
     use primitives::{
         postgres::{POSTGRES_HOST, POSTGRES_PASSWORD, POSTGRES_PORT, POSTGRES_USER},
         util::logging::new_logger,
@@ -2757,7 +2766,7 @@ pub mod run {
         application::EnableTls,
         db::{
             postgres_connection, redis_connection, redis_pool::Manager,
-            tests_postgres::setup_test_migrations, CampaignRemaining,
+            tests_postgres::setup_test_migrations, CampaignRemaining, RedisKeys,
         },
         platform::PlatformApi,
         Application,
@@ -2767,8 +2776,8 @@ pub mod run {
 
     use crate::TestValidator;
 
-    pub async fn run_sentry_app(
-        adapter: adapter::ethereum::LockedAdapter,
+    pub async fn run_sentry_app<C: Locked + 'static>(
+        adapter: Adapter<C>,
         validator: &TestValidator,
     ) -> anyhow::Result<()> {
         let socket_addr = SocketAddr::new(
@@ -2797,11 +2806,20 @@ pub mod run {
             .expect("Should flush redis database");
 
         let logger = new_logger(&validator.sentry_logger_prefix);
-        let campaign_remaining = CampaignRemaining::new(redis.clone());
+        let redis_keys = RedisKeys::new(
+            validator
+                .sentry_config
+                .redis_key_prefix
+                .clone()
+                .unwrap_or_default(),
+        );
+        let campaign_remaining = CampaignRemaining::new(redis.clone(), redis_keys);
 
         let platform_api = PlatformApi::new(
             validator.config.sentry.platform.url.clone(),
             validator.config.sentry.platform.keep_alive_interval,
+            validator.config.sentry.platform.timeout,
+            validator.config.sentry.platform.slot_cache_ttl,
         )
         .expect("Failed to build PlatformApi");
 
@@ -1,4 +1,5 @@
 use crate::{
+    debug::{self, TickDebug},
     error::{Error, TickError},
     follower, leader, SentryApi,
 };
@@ -7,11 +8,45 @@ use adapter::prelude::*;
 use primitives::{config::Config, ChainOf, Channel, ChannelId};
 use slog::info;
 use tokio::time::timeout;
+use tracing::{info_span, Instrument};
 
+/// Ticks the [`Channel`] as a Leader or a Follower.
+///
+/// Each stage of the tick is wrapped in a [`tracing`] span and all of them
+/// are inside a `channel_tick` span with the `channel` id as a field.
+///
+/// The Channels in the
+/// [`Config.worker.debug_channels`](primitives::config::ValidatorWorkerConfig::debug_channels)
+/// are ticked inside a [`TickDebug`] scope, see the [`debug`] module.
 pub async fn channel_tick<C: Unlocked + 'static>(
     sentry: &SentryApi<C>,
     config: &Config,
     channel_context: ChainOf<Channel>,
+) -> Result<(ChannelId, Box<dyn std::fmt::Debug>), Error> {
+    let channel_id = channel_context.context.id();
+    let span = info_span!("channel_tick", channel = %channel_id);
+
+    if config.worker.debug_channels.contains(&channel_id) {
+        let tick_debug = TickDebug::new(
+            sentry.logger.clone(),
+            config.worker.debug_dump_dir.clone(),
+            channel_id,
+            sentry.adapter.whoami(),
+        );
+
+        tick_debug
+            .scope(tick(sentry, config, channel_context))
+            .instrument(span)
+            .await
+    } else {
+        tick(sentry, config, channel_context).instrument(span).await
+    }
+}
+
+async fn tick<C: Unlocked + 'static>(
+    sentry: &SentryApi<C>,
+    config: &Config,
+    channel_context: ChainOf<Channel>,
 ) -> Result<(ChannelId, Box<dyn std::fmt::Debug>), Error> {
     let logger = sentry.logger.clone();
     let channel = channel_context.context;
@@ -22,13 +57,21 @@ pub async fn channel_tick<C: Unlocked + 'static>(
         .find_validator(adapter.whoami())
         .ok_or(Error::ChannelNotIntendedForUs)?;
 
-    // 1. `GET /channel/:id/spender/all`
-    let all_spenders = sentry.get_all_spenders(&channel_context).await?;
+    let (all_spenders, accounting) = async {
+        // 1. `GET /channel/:id/spender/all`
+        let all_spenders = sentry.get_all_spenders(&channel_context).await?;
 
-    // 2. `GET /channel/:id/accounting`
-    // Validation #1:
-    // sum(Accounting.spenders) == sum(Accounting.earners)
-    let accounting = sentry.get_accounting(&channel_context).await?;
+        // 2. `GET /channel/:id/accounting`
+        // Validation #1:
+        // sum(Accounting.spenders) == sum(Accounting.earners)
+        let accounting = sentry.get_accounting(&channel_context).await?;
+
+        Ok::<_, Error>((all_spenders, accounting))
+    }
+    .instrument(info_span!("fetch_accounting", channel = %channel.id()))
+    .await?;
+
+    debug::accounting(&accounting);
 
     // Validation #2:
     // spender.total_deposit >= accounting.balances.spenders[spender.address]
@@ -47,7 +90,7 @@ pub async fn channel_tick<C: Unlocked + 'static>(
     match tick {
         primitives::Validator::Leader(_v) => match timeout(
             config.worker.timeouts.channel_tick,
-            leader::tick(sentry, &channel_context, accounting.balances),
+            leader::tick(sentry, &channel_context, accounting),
         )
         .await
         {
@@ -84,3 +127,473 @@ pub async fn channel_tick<C: Unlocked + 'static>(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashMap,
+        fmt,
+        str::FromStr,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Event, Metadata, Subscriber,
+    };
+    use tracing_core::span::Current;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use adapter::dummy::{Adapter, Dummy, FailureConfig, MethodFailure, Options};
+    use primitives::{
+        balances::CheckedState,
+        config::GANACHE_CONFIG,
+        sentry::{
+            validator_messages::ValidatorMessagesListResponse, AccountingResponse,
+            AllSpendersResponse, Pagination, SuccessResponse,
+        },
+        spender::Spender,
+        test_util::{
+            discard_logger, ADVERTISER, DUMMY_AUTH, DUMMY_CAMPAIGN, DUMMY_VALIDATOR_FOLLOWER,
+            DUMMY_VALIDATOR_LEADER, FOLLOWER, IDS, LEADER, PUBLISHER,
+        },
+        util::ApiUrl,
+        Balances, ChainId, UnifiedNum,
+    };
+
+    use super::*;
+    use crate::{
+        sentry_interface::{ChainsValidators, Validator},
+        GetStateRoot,
+    };
+
+    #[derive(Debug, Clone)]
+    struct RecordedSpan {
+        name: &'static str,
+        metadata: &'static Metadata<'static>,
+        parent: Option<&'static str>,
+        fields: HashMap<&'static str, String>,
+    }
+
+    impl Visit for RecordedSpan {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.fields.insert(field.name(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.fields.insert(field.name(), format!("{:?}", value));
+        }
+    }
+
+    /// A [`Subscriber`] which records all the created spans and their fields.
+    /// Works only for a single threaded runtime.
+    #[derive(Debug, Clone, Default)]
+    struct SpansRecorder {
+        spans: Arc<Mutex<Vec<RecordedSpan>>>,
+        entered: Arc<Mutex<Vec<Id>>>,
+    }
+
+    impl SpansRecorder {
+        fn find(&self, name: &str) -> Option<RecordedSpan> {
+            self.spans
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|span| span.name == name)
+                .cloned()
+        }
+    }
+
+    impl Subscriber for SpansRecorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+            let mut spans = self.spans.lock().unwrap();
+            let parent = self
+                .entered
+                .lock()
+                .unwrap()
+                .last()
+                .map(|id| spans[id.into_u64() as usize - 1].name);
+
+            let mut span = RecordedSpan {
+                name: attributes.metadata().name(),
+                metadata: attributes.metadata(),
+                parent,
+                fields: HashMap::new(),
+            };
+            attributes.record(&mut span);
+            spans.push(span);
+
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, id: &Id, values: &Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            values.record(&mut spans[id.into_u64() as usize - 1]);
+        }
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, _event: &Event<'_>) {}
+
+        fn enter(&self, id: &Id) {
+            self.entered.lock().unwrap().push(id.clone());
+        }
+
+        fn exit(&self, _id: &Id) {
+            self.entered.lock().unwrap().pop();
+        }
+
+        fn current_span(&self) -> Current {
+            match self.entered.lock().unwrap().last() {
+                Some(id) => Current::new(
+                    id.clone(),
+                    self.spans.lock().unwrap()[id.into_u64() as usize - 1].metadata,
+                ),
+                None => Current::none(),
+            }
+        }
+    }
+
+    /// Sets up the Leader's [`SentryApi`] with both validators
+    /// on the `/leader` and `/follower` paths of the mock server.
+    fn setup_leader_sentry(
+        server: &MockServer,
+        config: &Config,
+        failure_injection: Option<FailureConfig>,
+    ) -> SentryApi<Dummy> {
+        let adapter = Adapter::with_unlocked(Dummy::init(Options {
+            dummy_identity: IDS[&LEADER],
+            dummy_auth_tokens: DUMMY_AUTH.clone(),
+            dummy_chains: config.chains.values().cloned().collect(),
+            failure_injection,
+        }));
+        let validators: HashMap<_, _> = [
+            (DUMMY_VALIDATOR_LEADER.id, "leader", &*LEADER),
+            (DUMMY_VALIDATOR_FOLLOWER.id, "follower", &*FOLLOWER),
+        ]
+        .into_iter()
+        .map(|(id, url_path, address)| {
+            let validator = Validator {
+                url: ApiUrl::from_str(&format!("{}/{}", server.uri(), url_path))
+                    .expect("should be valid"),
+                token: DUMMY_AUTH
+                    .get(address)
+                    .expect("should be valid")
+                    .to_string(),
+            };
+
+            (id, validator)
+        })
+        .collect();
+        let propagate_to: ChainsValidators = [(ChainId::from(1337), validators)].into();
+        let sentry_url =
+            ApiUrl::from_str(&format!("{}/leader", server.uri())).expect("Should parse");
+        SentryApi::new(adapter, discard_logger(), config.clone(), sentry_url)
+            .expect("Should create instance")
+            .with_propagate(propagate_to)
+            .expect("Should propagate")
+    }
+
+    fn accounting_balances() -> Balances<CheckedState> {
+        let mut balances = Balances::<CheckedState>::new();
+        balances
+            .spend(*ADVERTISER, *PUBLISHER, UnifiedNum::from_u64(1_000))
+            .expect("Should spend");
+
+        balances
+    }
+
+    /// Mocks the requests made to the Leader's & Follower's Sentry during a Leader tick
+    /// for a Channel with the given `balances` and no previous validator messages.
+    async fn mock_leader_tick(server: &MockServer, balances: &Balances<CheckedState>) {
+        let channel = DUMMY_CAMPAIGN.channel;
+
+        let all_spenders = AllSpendersResponse {
+            spenders: [(
+                *ADVERTISER,
+                Spender {
+                    total_deposited: UnifiedNum::from_u64(10_000),
+                    total_spent: Some(UnifiedNum::from_u64(1_000)),
+                },
+            )]
+            .into(),
+            pagination: Pagination {
+                total_pages: 1,
+                page: 0,
+            },
+        };
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/leader/v5/channel/{}/spender/all",
+                channel.id()
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&all_spenders))
+            .mount(server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/leader/v5/channel/{}/accounting",
+                channel.id()
+            )))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(&AccountingResponse {
+                    balances: balances.clone(),
+                    frozen: false,
+                }),
+            )
+            .mount(server)
+            .await;
+
+        // no previous NewState or Heartbeat messages
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/leader/v5/channel/{}/validator-messages/{}/NewState",
+                channel.id(),
+                channel.leader
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                &ValidatorMessagesListResponse {
+                    messages: vec![],
+                    next_cursor: None,
+                },
+            ))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/leader/v5/channel/{}/validator-messages/{}/Heartbeat",
+                channel.id(),
+                channel.leader
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                &ValidatorMessagesListResponse {
+                    messages: vec![],
+                    next_cursor: None,
+                },
+            ))
+            .mount(server)
+            .await;
+
+        for validator in ["leader", "follower"] {
+            Mock::given(method("POST"))
+                .and(path(format!(
+                    "/{}/v5/channel/{}/validator-messages",
+                    validator,
+                    channel.id()
+                )))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(&SuccessResponse { success: true }),
+                )
+                .mount(server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path(format!("/{validator}/health")))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(server)
+                .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_channel_tick_emits_spans() {
+        let recorder = SpansRecorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+
+        let server = MockServer::start().await;
+        let config = GANACHE_CONFIG.clone();
+        let channel = DUMMY_CAMPAIGN.channel;
+
+        let sentry = setup_leader_sentry(&server, &config, None);
+        let balances = accounting_balances();
+        mock_leader_tick(&server, &balances).await;
+
+        let channel_context = config
+            .find_chain_of(channel.token)
+            .expect("Should find Dummy campaign token in config")
+            .with_channel(channel);
+
+        channel_tick(&sentry, &config, channel_context.clone())
+            .await
+            .expect("Should tick");
+
+        let state_root = balances
+            .encode(channel.id(), channel_context.token.precision.get())
+            .expect("Should encode");
+        let channel_id = channel.id().to_string();
+
+        let channel_tick = recorder
+            .find("channel_tick")
+            .expect("Should have a channel_tick span");
+        assert_eq!(None, channel_tick.parent);
+        assert_eq!(Some(&channel_id), channel_tick.fields.get("channel"));
+
+        let fetch_accounting = recorder
+            .find("fetch_accounting")
+            .expect("Should have a fetch_accounting span");
+        assert_eq!(Some("channel_tick"), fetch_accounting.parent);
+        assert_eq!(Some(&channel_id), fetch_accounting.fields.get("channel"));
+
+        let generate_new_state = recorder
+            .find("generate_new_state")
+            .expect("Should have a generate_new_state span");
+        assert_eq!(Some("channel_tick"), generate_new_state.parent);
+        assert_eq!(Some(&channel_id), generate_new_state.fields.get("channel"));
+        assert_eq!(
+            Some(&state_root),
+            generate_new_state.fields.get("state_root")
+        );
+
+        let propagate = recorder
+            .find("propagate")
+            .expect("Should have a propagate span");
+        assert_eq!(Some("generate_new_state"), propagate.parent);
+        assert_eq!(Some(&channel_id), propagate.fields.get("channel"));
+        assert_eq!(Some(&state_root), propagate.fields.get("state_root"));
+
+        assert!(
+            recorder.find("follower_validate").is_none(),
+            "The Leader should not validate a NewState"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_debug_channel_tick_writes_dump() {
+        let server = MockServer::start().await;
+        let dump_dir = tempfile::tempdir().expect("Should create temp dir");
+        let channel = DUMMY_CAMPAIGN.channel;
+
+        let mut config = GANACHE_CONFIG.clone();
+        config.worker.debug_dump_dir = Some(dump_dir.path().to_path_buf());
+
+        let sentry = setup_leader_sentry(&server, &config, None);
+        let balances = accounting_balances();
+        mock_leader_tick(&server, &balances).await;
+
+        let channel_context = config
+            .find_chain_of(channel.token)
+            .expect("Should find Dummy campaign token in config")
+            .with_channel(channel);
+
+        let dumps = || {
+            std::fs::read_dir(dump_dir.path())
+                .expect("Should read dump dir")
+                .map(|entry| entry.expect("Should read entry").path())
+                .collect::<Vec<_>>()
+        };
+
+        // not a debug channel
+        {
+            channel_tick(&sentry, &config, channel_context.clone())
+                .await
+                .expect("Should tick");
+
+            assert!(dumps().is_empty(), "Should not dump a non-debug channel");
+        }
+
+        // debug channel
+        {
+            config.worker.debug_channels = vec![channel.id()];
+
+            channel_tick(&sentry, &config, channel_context.clone())
+                .await
+                .expect("Should tick");
+
+            let dumps = dumps();
+            assert_eq!(1, dumps.len(), "Should write a single dump for the tick");
+
+            let dump: serde_json::Value =
+                serde_json::from_slice(&std::fs::read(&dumps[0]).expect("Should read the dump"))
+                    .expect("Should be a valid JSON");
+
+            let state_root = balances
+                .encode(channel.id(), channel_context.token.precision.get())
+                .expect("Should encode");
+
+            assert_eq!(channel.id().to_string(), dump["channel"]);
+            assert_eq!(IDS[&LEADER].to_string(), dump["validator"]);
+            assert_eq!(
+                serde_json::to_value(&balances.earners).unwrap(),
+                dump["accounting"]["earners"]
+            );
+            assert_eq!(false, dump["accounting"]["frozen"]);
+            assert_eq!(state_root, dump["stateRoot"]);
+            // the Leader has no health inputs
+            assert!(dump["healthEarners"].is_null());
+            assert!(dump["error"].is_null());
+
+            let propagations = dump["propagations"]
+                .as_array()
+                .expect("Should have propagations");
+            // the NewState and the Heartbeat
+            assert_eq!(2, propagations.len());
+
+            let new_state = propagations
+                .iter()
+                .map(|propagation| &propagation["messages"][0])
+                .find(|message| message["type"] == "NewState")
+                .expect("Should have propagated a NewState");
+            assert_eq!(state_root, new_state["stateRoot"]);
+            assert_eq!("<redacted>", new_state["signature"]);
+            assert_eq!(1, new_state["balances"]["earners"]);
+            assert_eq!(1, new_state["balances"]["spenders"]);
+            assert_eq!("1000", new_state["balances"]["earnersSum"]);
+
+            for propagation in propagations {
+                let results = propagation["results"]
+                    .as_array()
+                    .expect("Should have results");
+                assert_eq!(2, results.len(), "Should propagate to both validators");
+                assert!(results.iter().all(|result| result["error"].is_null()));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_leader_channel_tick_with_high_sign_latency() {
+        let server = MockServer::start().await;
+        let config = GANACHE_CONFIG.clone();
+        let sign_latency = Duration::from_millis(200);
+
+        let sentry = setup_leader_sentry(
+            &server,
+            &config,
+            Some(FailureConfig {
+                seed: Some(0),
+                sign: Some(MethodFailure {
+                    latency: Some(sign_latency),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+        );
+        let balances = accounting_balances();
+        mock_leader_tick(&server, &balances).await;
+
+        let channel_context = config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Should find Dummy campaign token in config")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        let start = Instant::now();
+        // the fees summary requests are not mocked,
+        // this error is logged but it does not fail the tick
+        let (channel_id, _tick_status) = channel_tick(&sentry, &config, channel_context)
+            .await
+            .expect("Should complete the tick");
+
+        assert_eq!(DUMMY_CAMPAIGN.channel.id(), channel_id);
+        // both the Heartbeat and the NewState are signed
+        assert!(start.elapsed() >= sign_latency * 2);
+    }
+}
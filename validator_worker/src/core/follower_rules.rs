@@ -1,4 +1,5 @@
 use primitives::{UnifiedMap, UnifiedNum};
+use serde::Serialize;
 
 static MAX_HEALTH: u64 = 1_000;
 
@@ -19,11 +20,17 @@ pub fn is_valid_transition(
     Some(sum_next >= sum_prev && sum_next <= all_spenders_sum && prev_checks)
 }
 
-pub fn get_health(
-    all_spenders_sum: UnifiedNum,
-    our: &UnifiedMap,
-    approved: &UnifiedMap,
-) -> Option<u64> {
+/// The sums from which the health is calculated, see [`get_health()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthInputs {
+    /// The sum of our (accounting) balances.
+    pub sum_our: UnifiedNum,
+    /// The sum of the minimum of our and the approved balance for each address.
+    pub sum_approved_mins: UnifiedNum,
+}
+
+pub fn get_health_inputs(our: &UnifiedMap, approved: &UnifiedMap) -> Option<HealthInputs> {
     let sum_our: UnifiedNum = our.values().sum::<Option<_>>()?;
 
     let sum_approved_mins = our
@@ -31,6 +38,22 @@ pub fn get_health(
         .map(|(acc, val)| val.min(approved.get(acc).unwrap_or(&UnifiedNum::ZERO)))
         .sum::<Option<_>>()?;
 
+    Some(HealthInputs {
+        sum_our,
+        sum_approved_mins,
+    })
+}
+
+pub fn get_health(
+    all_spenders_sum: UnifiedNum,
+    our: &UnifiedMap,
+    approved: &UnifiedMap,
+) -> Option<u64> {
+    let HealthInputs {
+        sum_our,
+        sum_approved_mins,
+    } = get_health_inputs(our, approved)?;
+
     if sum_approved_mins >= sum_our {
         return Some(MAX_HEALTH);
     }
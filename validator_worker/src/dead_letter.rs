@@ -0,0 +1,149 @@
+//! Dead-letter log of validator messages which could not be delivered
+//! to a validator when propagating them.
+//!
+//! Each [`DeadLetter`] is appended as a single JSON line to the log file,
+//! this allows operators to investigate delivery failures
+//! using [`DeadLetterLog::recent()`] or any JSON lines tool.
+use std::{
+    fs::OpenOptions,
+    io::{BufRead, BufReader, ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use primitives::{
+    validator::{MessageType, MessageTypes},
+    ChannelId, ValidatorId,
+};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Dead-letter log file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Dead-letter (de)serialization: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A validator message which could not be propagated to a validator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadLetter {
+    pub channel: ChannelId,
+    pub message_type: MessageType,
+    pub state_root: String,
+    /// The validator to which the message was not delivered.
+    pub validator: ValidatorId,
+    /// The last propagation error.
+    pub error: String,
+    pub created: DateTime<Utc>,
+}
+
+impl DeadLetter {
+    pub fn new(
+        channel: ChannelId,
+        message: &MessageTypes,
+        validator: ValidatorId,
+        error: String,
+    ) -> Self {
+        Self {
+            channel,
+            message_type: message.message_type(),
+            state_root: message.state_root().to_string(),
+            validator,
+            error,
+            created: Utc::now(),
+        }
+    }
+}
+
+/// Persistent dead-letter log in the JSON lines format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadLetterLog {
+    path: PathBuf,
+}
+
+impl DeadLetterLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends the [`DeadLetter`] to the log file, creating the file if it doesn't exist.
+    pub fn record(&self, dead_letter: &DeadLetter) -> Result<(), Error> {
+        let mut line = serde_json::to_vec(dead_letter)?;
+        line.push(b'\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        // write the whole line at once, so concurrent writers do not mix lines
+        file.write_all(&line)?;
+
+        Ok(())
+    }
+
+    /// Lists up to `limit` of the most recent [`DeadLetter`]s, the newest being first.
+    ///
+    /// If the log file does not exist, there are no dead-letters.
+    pub fn recent(&self, limit: usize) -> Result<Vec<DeadLetter>, Error> {
+        let file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut dead_letters = BufReader::new(file)
+            .lines()
+            .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+            .map(|line| Ok(serde_json::from_str::<DeadLetter>(&line?)?))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        dead_letters.reverse();
+        dead_letters.truncate(limit);
+
+        Ok(dead_letters)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use primitives::{
+        test_util::{DUMMY_CAMPAIGN, FOLLOWER, IDS, LEADER},
+        validator::Heartbeat,
+    };
+
+    #[test]
+    fn records_and_lists_recent_dead_letters() {
+        let dir = tempfile::tempdir().expect("Should create temp dir");
+        let log = DeadLetterLog::new(dir.path().join("dead-letters.jsonl"));
+
+        assert!(
+            log.recent(10).expect("Should list").is_empty(),
+            "Non-existent log file has no dead-letters"
+        );
+
+        let heartbeat = MessageTypes::Heartbeat(Heartbeat::new(
+            "signature".to_string(),
+            "state root".to_string(),
+        ));
+        let channel = DUMMY_CAMPAIGN.channel.id();
+
+        let first = DeadLetter::new(channel, &heartbeat, IDS[&LEADER], "error 1".into());
+        let second = DeadLetter::new(channel, &heartbeat, IDS[&FOLLOWER], "error 2".into());
+
+        log.record(&first).expect("Should record");
+        log.record(&second).expect("Should record");
+
+        assert_eq!(vec![second.clone(), first], log.recent(10).unwrap());
+        assert_eq!(vec![second], log.recent(1).unwrap());
+    }
+}
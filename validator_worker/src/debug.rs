@@ -0,0 +1,286 @@
+//! Debug tracing of the ticks of specific Channels.
+//!
+//! The Channels set in the
+//! [`Config.worker.debug_channels`](primitives::config::ValidatorWorkerConfig::debug_channels)
+//! are ticked inside a [`TickDebug`] scope.
+//! While in this scope, the leader, follower and heartbeat code paths log the intermediates
+//! of the tick and collect them in a [`TickDump`], which is written as a JSON file to the
+//! [`Config.worker.debug_dump_dir`](primitives::config::ValidatorWorkerConfig::debug_dump_dir)
+//! at the end of the tick.
+//!
+//! Outside of the scope (i.e. for all other Channels) recording is a no-op.
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use slog::{error, info, Logger};
+use thiserror::Error;
+
+use primitives::{
+    balances::{BalancesState, CheckedState},
+    sentry::AccountingResponse,
+    validator::{MessageType, MessageTypes},
+    Balances, ChannelId, UnifiedNum, ValidatorId,
+};
+
+use crate::{core::follower_rules::HealthInputs, sentry_interface::PropagationResult};
+
+tokio::task_local! {
+    static TICK_DEBUG: TickDebug;
+}
+
+/// The value which replaces the signatures of the dumped messages.
+const REDACTED: &str = "<redacted>";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Debug dump file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Debug dump serialization: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// The intermediates of a single Channel tick.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TickDump {
+    pub channel: ChannelId,
+    /// The validator which ticked the Channel.
+    pub validator: ValidatorId,
+    pub started: DateTime<Utc>,
+    /// The fetched accounting of the Channel.
+    pub accounting: Option<AccountingResponse<CheckedState>>,
+    /// The state root generated by the Leader or proposed to the Follower.
+    pub state_root: Option<String>,
+    /// The Follower's health inputs of the earners.
+    pub health_earners: Option<HealthInputs>,
+    /// The Follower's health inputs of the spenders.
+    pub health_spenders: Option<HealthInputs>,
+    pub propagations: Vec<PropagationDump>,
+    /// The error of the tick, if it failed.
+    pub error: Option<String>,
+}
+
+/// The propagated messages and to which validators they were delivered.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropagationDump {
+    pub messages: Vec<MessageDump>,
+    pub results: Vec<PropagationResultDump>,
+}
+
+/// A propagated message with its signature redacted and balances summarized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageDump {
+    #[serde(rename = "type")]
+    pub message_type: MessageType,
+    pub state_root: String,
+    pub signature: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balances: Option<BalancesSummary>,
+}
+
+/// The number of earners & spenders and their sums.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalancesSummary {
+    pub earners: usize,
+    pub spenders: usize,
+    /// `None` if the sum overflows.
+    pub earners_sum: Option<UnifiedNum>,
+    /// `None` if the sum overflows.
+    pub spenders_sum: Option<UnifiedNum>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropagationResultDump {
+    pub validator: ValidatorId,
+    pub error: Option<String>,
+}
+
+impl<S: BalancesState> From<&Balances<S>> for BalancesSummary {
+    fn from(balances: &Balances<S>) -> Self {
+        let sums = balances.sum();
+
+        Self {
+            earners: balances.earners.len(),
+            spenders: balances.spenders.len(),
+            earners_sum: sums.map(|(earners, _spenders)| earners),
+            spenders_sum: sums.map(|(_earners, spenders)| spenders),
+        }
+    }
+}
+
+impl From<&MessageTypes> for MessageDump {
+    fn from(message: &MessageTypes) -> Self {
+        let balances = match message {
+            MessageTypes::NewState(new_state) => Some(BalancesSummary::from(&new_state.balances)),
+            MessageTypes::RejectState(reject_state) => {
+                reject_state.balances.as_ref().map(BalancesSummary::from)
+            }
+            MessageTypes::ApproveState(_) | MessageTypes::Heartbeat(_) => None,
+        };
+
+        Self {
+            message_type: message.message_type(),
+            state_root: message.state_root().to_string(),
+            signature: REDACTED.to_string(),
+            balances,
+        }
+    }
+}
+
+/// The debug scope of a single Channel tick.
+#[derive(Debug)]
+pub struct TickDebug {
+    logger: Logger,
+    dump_dir: Option<PathBuf>,
+    dump: Mutex<TickDump>,
+}
+
+impl TickDebug {
+    pub fn new(
+        logger: Logger,
+        dump_dir: Option<PathBuf>,
+        channel: ChannelId,
+        validator: ValidatorId,
+    ) -> Self {
+        Self {
+            logger,
+            dump_dir,
+            dump: Mutex::new(TickDump {
+                channel,
+                validator,
+                started: Utc::now(),
+                accounting: None,
+                state_root: None,
+                health_earners: None,
+                health_spenders: None,
+                propagations: vec![],
+                error: None,
+            }),
+        }
+    }
+
+    /// Runs the tick inside this debug scope and writes the [`TickDump`]
+    /// to the `dump_dir` (if set) once the tick has finished.
+    ///
+    /// Failing to write the dump is logged and does not fail the tick.
+    pub async fn scope<T, E, F>(self, tick: F) -> Result<T, E>
+    where
+        E: std::fmt::Display,
+        F: Future<Output = Result<T, E>>,
+    {
+        TICK_DEBUG
+            .scope(self, async {
+                let result = tick.await;
+
+                TICK_DEBUG.with(|debug| {
+                    let mut dump = debug.dump.lock().expect("Should lock the debug dump");
+                    dump.error = result.as_ref().err().map(ToString::to_string);
+
+                    if let Some(dump_dir) = &debug.dump_dir {
+                        match write_dump(dump_dir, &dump) {
+                            Ok(path) => {
+                                info!(&debug.logger, "Debug tick dump written"; "channel" => %dump.channel, "path" => %path.display())
+                            }
+                            Err(err) => {
+                                error!(&debug.logger, "Failed to write debug tick dump: {err}"; "channel" => %dump.channel)
+                            }
+                        }
+                    }
+                });
+
+                result
+            })
+            .await
+    }
+}
+
+/// Writes the [`TickDump`] to `{channel}-{validator}-{started in milliseconds}.json`
+/// in the given directory and returns the path of the file.
+pub fn write_dump(dump_dir: &Path, dump: &TickDump) -> Result<PathBuf, Error> {
+    std::fs::create_dir_all(dump_dir)?;
+
+    let path = dump_dir.join(format!(
+        "{}-{}-{}.json",
+        dump.channel,
+        dump.validator,
+        dump.started.timestamp_millis()
+    ));
+    std::fs::write(&path, serde_json::to_vec_pretty(dump)?)?;
+
+    Ok(path)
+}
+
+/// Records in the [`TickDump`] of the current debug scope, if any.
+fn record(record: impl FnOnce(&Logger, &mut TickDump)) {
+    let _ = TICK_DEBUG.try_with(|debug| {
+        let mut dump = debug.dump.lock().expect("Should lock the debug dump");
+
+        record(&debug.logger, &mut dump)
+    });
+}
+
+pub fn accounting(accounting: &AccountingResponse<CheckedState>) {
+    record(|logger, dump| {
+        info!(logger, "Debug tick: fetched accounting"; "channel" => %dump.channel, "accounting" => ?accounting);
+
+        dump.accounting = Some(accounting.clone());
+    })
+}
+
+pub fn state_root(state_root: &str) {
+    record(|logger, dump| {
+        info!(logger, "Debug tick: state root"; "channel" => %dump.channel, "state_root" => state_root);
+
+        dump.state_root = Some(state_root.to_string());
+    })
+}
+
+pub fn health_earners(inputs: Option<HealthInputs>) {
+    record(|logger, dump| {
+        info!(logger, "Debug tick: earners health inputs"; "channel" => %dump.channel, "inputs" => ?inputs);
+
+        dump.health_earners = inputs;
+    })
+}
+
+pub fn health_spenders(inputs: Option<HealthInputs>) {
+    record(|logger, dump| {
+        info!(logger, "Debug tick: spenders health inputs"; "channel" => %dump.channel, "inputs" => ?inputs);
+
+        dump.health_spenders = inputs;
+    })
+}
+
+pub fn propagation(messages: &[MessageTypes], results: &[PropagationResult]) {
+    record(|logger, dump| {
+        let propagation = PropagationDump {
+            messages: messages.iter().map(MessageDump::from).collect(),
+            results: results
+                .iter()
+                .map(|result| match result {
+                    Ok(validator) => PropagationResultDump {
+                        validator: *validator,
+                        error: None,
+                    },
+                    Err((validator, err)) => PropagationResultDump {
+                        validator: *validator,
+                        error: Some(err.to_string()),
+                    },
+                })
+                .collect(),
+        };
+
+        info!(logger, "Debug tick: propagation"; "channel" => %dump.channel, "propagation" => ?propagation);
+
+        dump.propagations.push(propagation);
+    })
+}
@@ -0,0 +1,302 @@
+//! Periodic summary of the fees earned by the _Who am I_ validator in a [`Channel`].
+
+use chrono::{DateTime, Utc};
+
+use adapter::prelude::*;
+use primitives::{
+    sentry::fees_summary::{
+        period_start, FeesSummaryQuery, FeesSummaryRequest, ValidatorFeesSummary,
+    },
+    ChainOf, Channel, ChannelId,
+};
+
+use crate::sentry_interface::{Error, SentryApi};
+
+/// Creates a [`ValidatorFeesSummary`] once per
+/// [`Config.worker.fees_summary_interval`](primitives::config::ValidatorWorkerConfig::fees_summary_interval).
+///
+/// The fees are taken from the latest approved [`NewState`](primitives::validator::NewState)
+/// and the `delta` is calculated against the previous summary of the [`Channel`].
+///
+/// Returns `None` if a summary for the current period already exists.
+/// Once it exists, the Sentry is not queried again until the next period,
+/// see [`SentryApi::fees_summary_periods`].
+pub async fn tick<C: Unlocked + 'static, P>(
+    sentry: &SentryApi<C, P>,
+    channel_context: &ChainOf<Channel>,
+) -> Result<Option<ValidatorFeesSummary>, Error> {
+    let channel = channel_context.context.id();
+    let period = period_start(Utc::now(), sentry.config.worker.fees_summary_interval);
+
+    let is_summarized = sentry
+        .fees_summary_periods
+        .lock()
+        .expect("Fees summary periods mutex poisoned")
+        .get(&channel)
+        .is_some_and(|summarized| *summarized >= period);
+
+    if is_summarized {
+        return Ok(None);
+    }
+
+    let previous = sentry
+        .get_fees_summary(&FeesSummaryQuery {
+            channel: Some(channel),
+            period: None,
+        })
+        .await?
+        .summaries
+        .into_iter()
+        .next();
+
+    if let Some(previous) = previous
+        .as_ref()
+        .filter(|previous| previous.period >= period)
+    {
+        record_period(sentry, channel, previous.period);
+
+        return Ok(None);
+    }
+
+    let whoami = sentry.adapter.whoami().to_address();
+    let fees = sentry
+        .get_last_approved(channel)
+        .await?
+        .last_approved
+        .and_then(|last_approved| last_approved.new_state)
+        .and_then(|new_state| new_state.msg.balances.earners.get(&whoami).copied())
+        .unwrap_or_default();
+
+    // the fees of the latest approved `NewState` should never decrease
+    let delta = match &previous {
+        Some(previous) => fees.checked_sub(&previous.fees).unwrap_or_default(),
+        None => fees,
+    };
+
+    let summary = sentry
+        .create_fees_summary(
+            channel_context,
+            &FeesSummaryRequest {
+                channel,
+                period,
+                fees,
+                delta,
+            },
+        )
+        .await?;
+
+    record_period(sentry, channel, summary.period);
+
+    Ok(Some(summary))
+}
+
+fn record_period<C: Unlocked + 'static, P>(
+    sentry: &SentryApi<C, P>,
+    channel: ChannelId,
+    period: DateTime<Utc>,
+) {
+    sentry
+        .fees_summary_periods
+        .lock()
+        .expect("Fees summary periods mutex poisoned")
+        .insert(channel, period);
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use chrono::Duration;
+    use wiremock::{
+        matchers::{body_json, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use adapter::dummy::{Adapter, Dummy, Options};
+    use primitives::{
+        balances::{CheckedState, UncheckedState},
+        config::GANACHE_CONFIG,
+        sentry::{
+            fees_summary::FeesSummaryResponse,
+            message::{Message, MessageResponse},
+            LastApproved, LastApprovedResponse,
+        },
+        test_util::{
+            discard_logger, ADVERTISER, DUMMY_AUTH, DUMMY_CAMPAIGN, IDS, LEADER, PUBLISHER,
+        },
+        util::ApiUrl,
+        validator::NewState,
+        Balances, Config, UnifiedNum,
+    };
+
+    use super::*;
+
+    fn setup_sentry(server: &MockServer, config: &Config) -> SentryApi<Dummy, ()> {
+        let adapter = Adapter::with_unlocked(Dummy::init(Options {
+            dummy_identity: IDS[&LEADER],
+            dummy_auth_tokens: DUMMY_AUTH.clone(),
+            dummy_chains: config.chains.values().cloned().collect(),
+            failure_injection: None,
+        }));
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+
+        SentryApi::new(adapter, discard_logger(), config.clone(), sentry_url)
+            .expect("Should build sentry")
+    }
+
+    fn summary(
+        period: chrono::DateTime<Utc>,
+        fees: UnifiedNum,
+        delta: UnifiedNum,
+    ) -> ValidatorFeesSummary {
+        ValidatorFeesSummary {
+            validator: IDS[&LEADER],
+            channel: DUMMY_CAMPAIGN.channel.id(),
+            period,
+            fees,
+            delta,
+            created: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fees_summary_matches_balances_delta() {
+        let server = MockServer::start().await;
+        let config = GANACHE_CONFIG.clone();
+        let sentry = setup_sentry(&server, &config);
+
+        let channel_context = config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Should find Dummy campaign token in config")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        let period = period_start(Utc::now(), config.worker.fees_summary_interval);
+        let previous_period = period
+            - Duration::from_std(config.worker.fees_summary_interval)
+                .expect("Should convert interval");
+
+        let previous_fees = UnifiedNum::from_u64(1_000);
+        let fees = UnifiedNum::from_u64(2_500);
+
+        let mut balances = Balances::<CheckedState>::new();
+        balances
+            .spend(*ADVERTISER, *PUBLISHER, UnifiedNum::from_u64(10_000))
+            .expect("Should spend");
+        balances
+            .spend(*ADVERTISER, IDS[&LEADER].to_address(), fees)
+            .expect("Should spend");
+
+        let last_approved = LastApprovedResponse::<UncheckedState> {
+            last_approved: Some(LastApproved {
+                new_state: Some(MessageResponse {
+                    from: IDS[&LEADER],
+                    received: Utc::now(),
+                    msg: Message::new(NewState {
+                        state_root: String::new(),
+                        signature: String::new(),
+                        balances: balances.into_unchecked(),
+                    }),
+                }),
+                approve_state: None,
+            }),
+            heartbeats: None,
+        };
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/v5/channel/{}/last-approved",
+                DUMMY_CAMPAIGN.channel.id()
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&last_approved))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(format!("/v5/validator/{}/fees-summary", IDS[&LEADER])))
+            .and(query_param(
+                "channel",
+                DUMMY_CAMPAIGN.channel.id().to_string(),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(&FeesSummaryResponse {
+                    summaries: vec![summary(previous_period, previous_fees, previous_fees)],
+                    total_fees: previous_fees,
+                    total_delta: previous_fees,
+                }),
+            )
+            .mount(&server)
+            .await;
+
+        let expected_delta = UnifiedNum::from_u64(1_500);
+        let expected_request = FeesSummaryRequest {
+            channel: DUMMY_CAMPAIGN.channel.id(),
+            period,
+            fees,
+            delta: expected_delta,
+        };
+        Mock::given(method("POST"))
+            .and(path(format!("/v5/validator/{}/fees-summary", IDS[&LEADER])))
+            .and(body_json(&expected_request))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&summary(
+                period,
+                fees,
+                expected_delta,
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let created = tick(&sentry, &channel_context)
+            .await
+            .expect("Should create fees summary")
+            .expect("Should have a summary for the new period");
+
+        assert_eq!(period, created.period);
+        assert_eq!(fees, created.fees);
+        assert_eq!(expected_delta, created.delta);
+    }
+
+    #[tokio::test]
+    async fn test_fees_summary_already_exists_for_period() {
+        let server = MockServer::start().await;
+        let config = GANACHE_CONFIG.clone();
+        let sentry = setup_sentry(&server, &config);
+
+        let channel_context = config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Should find Dummy campaign token in config")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        let period = period_start(Utc::now(), config.worker.fees_summary_interval);
+        let fees = UnifiedNum::from_u64(1_000);
+
+        // the Sentry is queried only on the first tick of the period
+        Mock::given(method("GET"))
+            .and(path(format!("/v5/validator/{}/fees-summary", IDS[&LEADER])))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(&FeesSummaryResponse {
+                    summaries: vec![summary(period, fees, fees)],
+                    total_fees: fees,
+                    total_delta: fees,
+                }),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path(format!("/v5/validator/{}/fees-summary", IDS[&LEADER])))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        for _ in 0..2 {
+            let result = tick(&sentry, &channel_context)
+                .await
+                .expect("Should not error");
+
+            assert!(result.is_none());
+        }
+    }
+}
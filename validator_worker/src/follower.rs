@@ -10,13 +10,16 @@ use primitives::{
 };
 
 use crate::{
-    core::follower_rules::{get_health, is_valid_transition},
-    heartbeat::{heartbeat, HeartbeatStatus},
+    core::follower_rules::{get_health, get_health_inputs, is_valid_transition},
+    debug,
+    heartbeat::{check_clock_skew, heartbeat, ClockSkew, HeartbeatStatus},
     sentry_interface::{Error as SentryApiError, PropagationResult, SentryApi},
     GetStateRoot, GetStateRootError,
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use slog::{error, info};
 use thiserror::Error;
+use tracing::{info_span, Instrument};
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -80,6 +83,11 @@ pub enum ApproveStateResult {
 pub struct TickStatus {
     pub heartbeat: HeartbeatStatus,
     pub approve_state: ApproveStateResult,
+    /// When `Ok(None)` the Leader has not sent a Heartbeat yet
+    /// or we have not sent a Heartbeat in this tick, see [`check_clock_skew()`].
+    ///
+    /// Failing to check the clock skew does not fail the whole tick.
+    pub clock_skew: Result<Option<ClockSkew>, SentryApiError>,
 }
 
 pub async fn tick<C: Unlocked + 'static>(
@@ -107,15 +115,16 @@ pub async fn tick<C: Unlocked + 'static>(
         .expect("Should always return a NewState message");
 
     let our_latest_msg_response = sentry
-        .get_our_latest_msg(
+        .get_latest_validator_msg(
             channel_id,
+            sentry.adapter.whoami(),
             &[MessageType::ApproveState, MessageType::RejectState],
         )
         .await?;
 
-    let our_latest_msg_state_root = match our_latest_msg_response {
-        Some(MessageTypes::ApproveState(approve_state)) => Some(approve_state.state_root),
-        Some(MessageTypes::RejectState(reject_state)) => Some(reject_state.state_root),
+    let our_latest_msg_state_root = match our_latest_msg_response.as_ref().map(|msg| &msg.msg) {
+        Some(MessageTypes::ApproveState(approve_state)) => Some(approve_state.state_root.clone()),
+        Some(MessageTypes::RejectState(reject_state)) => Some(reject_state.state_root.clone()),
         _ => None,
     };
 
@@ -125,6 +134,28 @@ pub async fn tick<C: Unlocked + 'static>(
     };
 
     let approve_state_result = if let (Some(new_state), false) = (new_msg, latest_is_responded_to) {
+        let span = info_span!(
+            "follower_validate",
+            channel = %channel_id,
+            state_root = %new_state.state_root
+        );
+
+        // catching up with the leader, e.g. after the follower was down,
+        // only the latest NewState is validated as it supersedes the previous ones
+        if let Some(our_latest_msg) = &our_latest_msg_response {
+            let superseded = superseded_new_states(
+                sentry,
+                channel_context,
+                our_latest_msg.received,
+                &new_state.state_root,
+            )
+            .await?;
+
+            if superseded > 0 {
+                info!(&sentry.logger, "Skipping {superseded} superseded NewState(s) of the leader"; "channel" => %channel_id, "state_root" => &new_state.state_root);
+            }
+        }
+
         on_new_state(
             sentry,
             channel_context,
@@ -132,17 +163,52 @@ pub async fn tick<C: Unlocked + 'static>(
             new_state,
             all_spenders_sum,
         )
+        .instrument(span)
         .await?
     } else {
         ApproveStateResult::Sent(None)
     };
 
+    let heartbeat = heartbeat(sentry, channel_context).await?;
+    // the Leader sends its Heartbeats as often as we do,
+    // so its clock is checked only when we've sent ours
+    let clock_skew = match heartbeat {
+        Some(_) => check_clock_skew(sentry, channel_context).await,
+        None => Ok(None),
+    };
+
     Ok(TickStatus {
-        heartbeat: heartbeat(sentry, channel_context).await?,
+        heartbeat,
         approve_state: approve_state_result,
+        clock_skew,
     })
 }
 
+/// Counts the leader's `NewState`s received since our last response (`ApproveState` or `RejectState`),
+/// other than the `latest_state_root` one, by fetching all of them with [`SentryApi::get_msgs_since()`].
+async fn superseded_new_states<C: Unlocked + 'static>(
+    sentry: &SentryApi<C>,
+    channel_context: &ChainOf<Channel>,
+    since: DateTime<Utc>,
+    latest_state_root: &str,
+) -> Result<usize, Error> {
+    let channel = channel_context.context;
+
+    let new_states = sentry
+        .get_msgs_since(
+            channel.id(),
+            channel.leader,
+            &[MessageType::NewState],
+            since,
+        )
+        .await?;
+
+    Ok(new_states
+        .iter()
+        .filter(|message| message.msg.state_root() != latest_state_root)
+        .count())
+}
+
 async fn on_new_state<'a, C: Unlocked + 'static>(
     sentry: &'a SentryApi<C>,
     channel_context: &'a ChainOf<Channel>,
@@ -156,6 +222,15 @@ async fn on_new_state<'a, C: Unlocked + 'static>(
         Ok(balances) => balances,
         // TODO: Should we show the Payout Mismatch between Spent & Earned?
         Err(balances::Error::PayoutMismatch { .. }) => {
+            // quarantine the imbalanced state in our own Sentry, so that it is no longer
+            // returned as the last approved state (best-effort)
+            if let Err(err) = sentry
+                .quarantine_state(channel_context, &new_state.state_root)
+                .await
+            {
+                error!(&sentry.logger, "Failed to quarantine the imbalanced NewState"; "channel" => %channel.id(), "state_root" => &new_state.state_root, "error" => ?err);
+            }
+
             return on_error(
                 sentry,
                 channel_context,
@@ -169,6 +244,7 @@ async fn on_new_state<'a, C: Unlocked + 'static>(
     };
 
     let proposed_state_root = new_state.state_root.clone();
+    debug::state_root(&proposed_state_root);
 
     if proposed_state_root
         != proposed_balances.encode(channel.id(), channel_context.token.precision.get())?
@@ -260,6 +336,10 @@ async fn on_new_state<'a, C: Unlocked + 'static>(
         .await;
     }
 
+    debug::health_earners(get_health_inputs(
+        &accounting_balances.earners,
+        &proposed_balances.earners,
+    ));
     let health_earners = get_health(
         all_spenders_sum,
         &accounting_balances.earners,
@@ -276,6 +356,10 @@ async fn on_new_state<'a, C: Unlocked + 'static>(
         .await;
     }
 
+    debug::health_spenders(get_health_inputs(
+        &accounting_balances.spenders,
+        &proposed_balances.spenders,
+    ));
     let health_spenders = get_health(
         all_spenders_sum,
         &accounting_balances.spenders,
@@ -296,6 +380,11 @@ async fn on_new_state<'a, C: Unlocked + 'static>(
     let health_threshold = u64::from(sentry.config.worker.health_threshold_promilles);
     let is_healthy = health_earners >= health_threshold && health_spenders >= health_threshold;
 
+    let span = info_span!(
+        "propagate",
+        channel = %channel.id(),
+        state_root = %proposed_state_root
+    );
     let propagation_result = sentry
         .propagate(
             channel_context,
@@ -305,6 +394,7 @@ async fn on_new_state<'a, C: Unlocked + 'static>(
                 is_healthy,
             })],
         )
+        .instrument(span)
         .await?;
 
     Ok(ApproveStateResult::Sent(Some(propagation_result)))
@@ -316,6 +406,11 @@ async fn on_error<'a, C: Unlocked + 'static>(
     new_state: NewState<UncheckedState>,
     status: InvalidNewState,
 ) -> Result<ApproveStateResult, Error> {
+    let span = info_span!(
+        "propagate",
+        channel = %channel_context.context.id(),
+        state_root = %new_state.state_root
+    );
     let propagation = sentry
         .propagate(
             channel_context,
@@ -328,6 +423,7 @@ async fn on_error<'a, C: Unlocked + 'static>(
                 timestamp: Utc::now(),
             })],
         )
+        .instrument(span)
         .await?;
 
     Ok(ApproveStateResult::RejectedState {
@@ -376,6 +472,7 @@ mod test {
             dummy_identity: IDS[&LEADER],
             dummy_auth_tokens: DUMMY_AUTH.clone(),
             dummy_chains: config.chains.values().cloned().collect(),
+            failure_injection: None,
         }));
         let logger = discard_logger();
 
@@ -452,8 +549,12 @@ mod test {
                     received: Utc::now(),
                     msg: MessageTypes::NewState(msg),
                 }],
+                next_cursor: None,
+            },
+            None => ValidatorMessagesListResponse {
+                messages: vec![],
+                next_cursor: None,
             },
-            None => ValidatorMessagesListResponse { messages: vec![] },
         };
 
         Mock::given(method("GET"))
@@ -483,8 +584,12 @@ mod test {
                     received: Utc::now(),
                     msg: MessageTypes::ApproveState(msg),
                 }],
+                next_cursor: None,
+            },
+            None => ValidatorMessagesListResponse {
+                messages: vec![],
+                next_cursor: None,
             },
-            None => ValidatorMessagesListResponse { messages: vec![] },
         };
 
         Mock::given(method("GET"))
@@ -514,8 +619,12 @@ mod test {
                     received: Utc::now(),
                     msg: MessageTypes::RejectState(msg),
                 }],
+                next_cursor: None,
+            },
+            None => ValidatorMessagesListResponse {
+                messages: vec![],
+                next_cursor: None,
             },
-            None => ValidatorMessagesListResponse { messages: vec![] },
         };
 
         Mock::given(method("GET"))
@@ -572,6 +681,15 @@ mod test {
             .mount(&server)
             .await;
 
+        // both validators' Sentries are healthy
+        for validator in ["leader", "follower"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/{validator}/health")))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(&server)
+                .await;
+        }
+
         let heartbeat = Heartbeat {
             signature: String::new(),
             state_root: String::new(),
@@ -583,6 +701,7 @@ mod test {
                 received: Utc::now(),
                 msg: MessageTypes::Heartbeat(heartbeat),
             }],
+            next_cursor: None,
         };
         Mock::given(method("GET"))
             .and(path(format!(
@@ -1060,4 +1179,58 @@ mod test {
             );
         }
     }
+
+    #[tokio::test]
+    async fn test_superseded_new_states_since_our_last_response() {
+        let server = MockServer::start().await;
+        let config = GANACHE_CONFIG.clone();
+        let sentry = setup_sentry(&server, &config).await;
+
+        let channel_context = config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Should find Dummy campaign token in config")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        let responded_at = Utc::now() - chrono::Duration::hours(1);
+        let new_state_msg = |state_root: &str, minutes| ValidatorMessage {
+            from: DUMMY_CAMPAIGN.channel.leader,
+            received: responded_at + chrono::Duration::minutes(minutes),
+            msg: MessageTypes::NewState(NewState::<UncheckedState> {
+                state_root: state_root.to_string(),
+                signature: IDS[&*LEADER].to_checksum(),
+                balances: Balances::default(),
+            }),
+        };
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/follower/v5/channel/{}/validator-messages/{}/{}",
+                DUMMY_CAMPAIGN.channel.id(),
+                DUMMY_CAMPAIGN.channel.leader,
+                "NewState",
+            )))
+            .and(query_param(
+                "since",
+                responded_at.timestamp_millis().to_string(),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                &ValidatorMessagesListResponse {
+                    messages: vec![
+                        new_state_msg("missed-1", 10),
+                        new_state_msg("missed-2", 20),
+                        new_state_msg("latest", 30),
+                    ],
+                    next_cursor: None,
+                },
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let superseded = superseded_new_states(&sentry, &channel_context, responded_at, "latest")
+            .await
+            .expect("Should get the NewStates since our last response");
+
+        assert_eq!(2, superseded);
+    }
 }
@@ -0,0 +1,320 @@
+//! Read-only monitoring of the [`Channel`]s of which the worker is the guardian,
+//! see [`Config.worker.guardian_monitoring`](primitives::config::ValidatorWorkerConfig::guardian_monitoring).
+//!
+//! On each tick the monitored Channel is checked for:
+//!
+//! - the freshness of the latest [`Heartbeat`](primitives::validator::Heartbeat) of both validators;
+//! - the age of the last [`ApproveState`](primitives::validator::ApproveState);
+//! - the last approved balances, which can never exceed the current accounting.
+//!
+//! The found issues are logged and reported as [`Warning`]s,
+//! validator messages are never signed nor propagated.
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use slog::warn;
+
+use adapter::prelude::*;
+use primitives::{
+    config::GuardianMonitoring, sentry::message::MessageResponse, validator::Heartbeat, ChainOf,
+    Channel, ChannelId, ValidatorId,
+};
+
+use crate::{
+    sentry_interface::{Error as SentryApiError, SentryApi},
+    verify::{balance_discrepancies, Discrepancy},
+};
+
+/// An issue found by the guardian monitoring of a [`Channel`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// The validator has not sent a Heartbeat yet.
+    NoHeartbeat { validator: ValidatorId },
+    /// The latest Heartbeat of the validator is older than
+    /// [`GuardianMonitoring::heartbeat_stale_after`].
+    StaleHeartbeat {
+        validator: ValidatorId,
+        received: DateTime<Utc>,
+    },
+    /// The Channel has an accounting but no `ApproveState` yet.
+    NoApproveState,
+    /// The last `ApproveState` is older than [`GuardianMonitoring::approve_state_stale_after`].
+    StaleApproveState { received: DateTime<Utc> },
+    /// The last approved balances exceed the accounting.
+    Discrepancy(Discrepancy),
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::NoHeartbeat { validator } => {
+                write!(f, "Validator {validator} has not sent a Heartbeat")
+            }
+            Warning::StaleHeartbeat {
+                validator,
+                received,
+            } => write!(
+                f,
+                "The latest Heartbeat of validator {validator} is stale, received at {received}"
+            ),
+            Warning::NoApproveState => write!(f, "The Channel has no ApproveState"),
+            Warning::StaleApproveState { received } => {
+                write!(f, "The last ApproveState is stale, received at {received}")
+            }
+            Warning::Discrepancy(discrepancy) => discrepancy.fmt(f),
+        }
+    }
+}
+
+/// The monitored state of a guardian [`Channel`].
+#[derive(Debug, Clone)]
+pub struct GuardianStatus {
+    pub channel: ChannelId,
+    pub warnings: Vec<Warning>,
+}
+
+/// Checks the [`Channel`] and logs a warning for each found issue.
+pub async fn tick<C: Unlocked + 'static, P>(
+    sentry: &SentryApi<C, P>,
+    monitoring: &GuardianMonitoring,
+    channel_context: &ChainOf<Channel>,
+) -> Result<GuardianStatus, SentryApiError> {
+    let channel = channel_context.context;
+    let now = Utc::now();
+    let is_stale = |received: DateTime<Utc>, threshold| {
+        chrono::Duration::from_std(threshold).is_ok_and(|threshold| now - received > threshold)
+    };
+
+    let last_approved_response = sentry.get_last_approved(channel.id()).await?;
+    let accounting = sentry.get_accounting(channel_context).await?;
+
+    let mut warnings = vec![];
+
+    let heartbeats = last_approved_response.heartbeats.unwrap_or_default();
+    for validator in [channel.leader, channel.follower] {
+        match latest_heartbeat(&heartbeats, validator) {
+            None => warnings.push(Warning::NoHeartbeat { validator }),
+            Some(received) if is_stale(received, monitoring.heartbeat_stale_after) => warnings
+                .push(Warning::StaleHeartbeat {
+                    validator,
+                    received,
+                }),
+            Some(_) => {}
+        }
+    }
+
+    let last_approved = last_approved_response.last_approved;
+    let approve_state = last_approved
+        .as_ref()
+        .and_then(|last_approved| last_approved.approve_state.as_ref());
+    match approve_state {
+        None if !accounting.balances.spenders.is_empty() => warnings.push(Warning::NoApproveState),
+        Some(approve_state)
+            if is_stale(approve_state.received, monitoring.approve_state_stale_after) =>
+        {
+            warnings.push(Warning::StaleApproveState {
+                received: approve_state.received,
+            })
+        }
+        _ => {}
+    }
+
+    if let Some(new_state) = last_approved.and_then(|last_approved| last_approved.new_state) {
+        match new_state.msg.balances.clone().check() {
+            Ok(approved) => warnings.extend(
+                balance_discrepancies(&approved, &accounting.balances)
+                    .into_iter()
+                    .map(Warning::Discrepancy),
+            ),
+            Err(error) => warnings.push(Warning::Discrepancy(Discrepancy::InvalidBalances(
+                error.to_string(),
+            ))),
+        }
+    }
+
+    for warning in warnings.iter() {
+        warn!(&sentry.logger, "Guardian monitoring: {warning}"; "channel" => %channel.id());
+    }
+
+    Ok(GuardianStatus {
+        channel: channel.id(),
+        warnings,
+    })
+}
+
+fn latest_heartbeat(
+    heartbeats: &[MessageResponse<Heartbeat>],
+    validator: ValidatorId,
+) -> Option<DateTime<Utc>> {
+    heartbeats
+        .iter()
+        .filter(|heartbeat| heartbeat.from == validator)
+        .map(|heartbeat| heartbeat.received)
+        .max()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{str::FromStr, time::Duration};
+
+    use chrono::Duration as ChronoDuration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use adapter::dummy::{Adapter, Dummy, Options};
+    use primitives::{
+        balances::{CheckedState, UncheckedState},
+        config::GANACHE_CONFIG,
+        sentry::{message::Message, AccountingResponse, LastApproved, LastApprovedResponse},
+        test_util::{
+            discard_logger, CREATOR, DUMMY_AUTH, DUMMY_CAMPAIGN, FOLLOWER, GUARDIAN, IDS, LEADER,
+            PUBLISHER,
+        },
+        util::ApiUrl,
+        validator::{ApproveState, NewState},
+        Balances, UnifiedNum,
+    };
+
+    use crate::tick_report;
+
+    use super::*;
+
+    fn heartbeat(from: ValidatorId, received: DateTime<Utc>) -> MessageResponse<Heartbeat> {
+        MessageResponse {
+            from,
+            received,
+            msg: Message::new(Heartbeat {
+                signature: "signature".to_string(),
+                state_root: "state root".to_string(),
+                timestamp: received,
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn stalled_channel_produces_warnings_without_messages() {
+        let server = MockServer::start().await;
+        let channel_context = GANACHE_CONFIG
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Should find the token")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+        let channel = channel_context.context;
+        assert_eq!(IDS[&GUARDIAN].to_address(), channel.guardian);
+
+        let stalled_at = Utc::now() - ChronoDuration::hours(2);
+        let mut balances = Balances::<CheckedState>::new();
+        balances
+            .spend(*CREATOR, *PUBLISHER, UnifiedNum::from_u64(100))
+            .expect("Should spend");
+
+        let last_approved = LastApprovedResponse::<UncheckedState> {
+            last_approved: Some(LastApproved {
+                new_state: Some(MessageResponse {
+                    from: IDS[&LEADER],
+                    received: stalled_at,
+                    msg: Message::new(NewState {
+                        state_root: "state root".to_string(),
+                        signature: "signature".to_string(),
+                        balances: balances.clone().into_unchecked(),
+                    }),
+                }),
+                approve_state: Some(MessageResponse {
+                    from: IDS[&FOLLOWER],
+                    received: stalled_at,
+                    msg: Message::new(ApproveState {
+                        state_root: "state root".to_string(),
+                        signature: "signature".to_string(),
+                        is_healthy: true,
+                    }),
+                }),
+            }),
+            // the Follower has never sent a Heartbeat
+            heartbeats: Some(vec![heartbeat(IDS[&LEADER], stalled_at)]),
+        };
+        Mock::given(method("GET"))
+            .and(path(format!("/v5/channel/{}/last-approved", channel.id())))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&last_approved))
+            .mount(&server)
+            .await;
+
+        let accounting = AccountingResponse {
+            balances,
+            frozen: false,
+        };
+        Mock::given(method("GET"))
+            .and(path(format!("/v5/channel/{}/accounting", channel.id())))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&accounting))
+            .mount(&server)
+            .await;
+
+        // the guardian should never propagate messages
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "/v5/channel/{}/validator-messages",
+                channel.id()
+            )))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let adapter = Adapter::with_unlocked(Dummy::init(Options {
+            dummy_identity: IDS[&GUARDIAN],
+            dummy_auth_tokens: DUMMY_AUTH.clone(),
+            dummy_chains: GANACHE_CONFIG.chains.values().cloned().collect(),
+            failure_injection: None,
+        }));
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = SentryApi::new(
+            adapter,
+            discard_logger(),
+            GANACHE_CONFIG.clone(),
+            sentry_url,
+        )
+        .expect("Should build sentry");
+
+        let monitoring = GuardianMonitoring {
+            heartbeat_stale_after: Duration::from_secs(5 * 60),
+            approve_state_stale_after: Duration::from_secs(60 * 60),
+        };
+
+        let (status, messages) =
+            tick_report::scope(tick(&sentry, &monitoring, &channel_context)).await;
+        let status = status.expect("Should monitor the Channel");
+
+        assert_eq!(
+            vec![
+                Warning::StaleHeartbeat {
+                    validator: IDS[&LEADER],
+                    received: stalled_at,
+                },
+                Warning::NoHeartbeat {
+                    validator: IDS[&FOLLOWER],
+                },
+                Warning::StaleApproveState {
+                    received: stalled_at,
+                },
+            ],
+            status.warnings
+        );
+        assert!(messages.is_empty(), "No messages should be created");
+
+        // within the thresholds only the missing Heartbeat is reported
+        let monitoring = GuardianMonitoring {
+            heartbeat_stale_after: Duration::from_secs(3 * 60 * 60),
+            approve_state_stale_after: Duration::from_secs(3 * 60 * 60),
+        };
+        let status = tick(&sentry, &monitoring, &channel_context)
+            .await
+            .expect("Should monitor the Channel");
+
+        assert_eq!(
+            vec![Warning::NoHeartbeat {
+                validator: IDS[&FOLLOWER],
+            }],
+            status.warnings
+        );
+    }
+}
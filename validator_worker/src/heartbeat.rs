@@ -5,8 +5,9 @@ use byteorder::{BigEndian, ByteOrder};
 use primitives::{
     merkle_tree::MerkleTree,
     validator::{Heartbeat, MessageType, MessageTypes},
-    ChainOf, Channel,
+    ChainOf, Channel, ValidatorId,
 };
+use slog::warn;
 use thiserror::Error;
 
 use crate::sentry_interface::{Error as SentryApiError, PropagationResult, SentryApi};
@@ -50,6 +51,79 @@ pub async fn heartbeat<C: Unlocked + 'static>(
     }
 }
 
+/// The clock skew of the other validator of a [`Channel`],
+/// based on the `timestamp` of its latest [`Heartbeat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockSkew {
+    /// The other validator of the [`Channel`].
+    pub validator: ValidatorId,
+    /// Positive when the validator's clock is ahead of ours and negative when it's behind.
+    pub skew: chrono::Duration,
+    /// Whether the skew exceeds the
+    /// [`Config.worker.clock_skew_threshold`](primitives::config::ValidatorWorkerConfig::clock_skew_threshold).
+    pub exceeds_threshold: bool,
+}
+
+/// Compares the `timestamp` of the other validator's latest [`Heartbeat`] with our clock.
+///
+/// The `timestamp` is taken from the signed message itself and not from the time
+/// the message was received by the Sentry.
+/// Heartbeats are sent once every
+/// [`Config.worker.heartbeat_time`](primitives::config::ValidatorWorkerConfig::heartbeat_time),
+/// so a Heartbeat from the past exceeds the threshold only when it's older
+/// than the `heartbeat_time` and the `clock_skew_threshold` combined.
+///
+/// Logs a warning when the skew exceeds the threshold.
+///
+/// It fetches the other validator's latest [`Heartbeat`], so the leader & follower ticks
+/// call it only when they've sent a Heartbeat, i.e. once every `heartbeat_time`.
+///
+/// Returns `None` if the other validator has not sent a [`Heartbeat`] yet.
+pub async fn check_clock_skew<C: Unlocked + 'static>(
+    iface: &SentryApi<C>,
+    channel_context: &ChainOf<Channel>,
+) -> Result<Option<ClockSkew>, SentryApiError> {
+    let channel = channel_context.context;
+    let validator = if channel.leader == iface.adapter.whoami() {
+        channel.follower
+    } else {
+        channel.leader
+    };
+
+    let heartbeat = match iface
+        .get_latest_msg(channel.id(), validator, &[MessageType::Heartbeat])
+        .await?
+    {
+        Some(MessageTypes::Heartbeat(heartbeat)) => heartbeat,
+        _ => return Ok(None),
+    };
+
+    let to_chrono =
+        |duration| chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::MAX);
+    let threshold = to_chrono(iface.config.worker.clock_skew_threshold);
+    let heartbeat_time = to_chrono(iface.config.worker.heartbeat_time);
+
+    let skew = heartbeat.timestamp - Utc::now();
+    let exceeds_threshold = skew > threshold || -skew > threshold + heartbeat_time;
+
+    if exceeds_threshold {
+        warn!(
+            &iface.logger,
+            "Clock skew with the other validator exceeds the threshold";
+            "channel" => %channel.id(),
+            "validator" => %validator,
+            "skew_ms" => skew.num_milliseconds(),
+            "threshold_ms" => threshold.num_milliseconds(),
+        );
+    }
+
+    Ok(Some(ClockSkew {
+        validator,
+        skew,
+        exceeds_threshold,
+    }))
+}
+
 async fn send_heartbeat<C: Unlocked + 'static>(
     iface: &SentryApi<C>,
     channel_context: &ChainOf<Channel>,
@@ -125,6 +199,15 @@ mod test {
             .mount(&server)
             .await;
 
+        // both validators' Sentries are healthy
+        for validator in ["leader", "follower"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/{validator}/health")))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(&server)
+                .await;
+        }
+
         server
     }
 
@@ -135,6 +218,7 @@ mod test {
             dummy_identity: IDS[&LEADER],
             dummy_auth_tokens: DUMMY_AUTH.clone(),
             dummy_chains: config.chains.values().cloned().collect(),
+            failure_injection: None,
         }));
         let logger = discard_logger();
 
@@ -170,6 +254,7 @@ mod test {
                 received: Utc::now(),
                 msg: MessageTypes::Heartbeat(heartbeat),
             }],
+            next_cursor: None,
         };
         Mock::given(method("GET"))
             .and(path(format!(
@@ -251,4 +336,89 @@ mod test {
             );
         }
     }
+
+    #[tokio::test]
+    async fn test_clock_skew() {
+        let config = GANACHE_CONFIG.clone();
+        let server = setup_mock_server().await;
+        // the Leader checks the Follower's Heartbeats
+        let sentry = setup_sentry(&server, &config).await;
+
+        let channel_context = config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Should find Dummy campaign token in config")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        let follower_heartbeat_res = |timestamp| {
+            // a `received` time close to ours should not matter
+            let heartbeat_res = ValidatorMessagesListResponse {
+                messages: vec![ValidatorMessage {
+                    from: DUMMY_CAMPAIGN.channel.follower,
+                    received: Utc::now(),
+                    msg: MessageTypes::Heartbeat(Heartbeat {
+                        signature: String::new(),
+                        state_root: String::new(),
+                        timestamp,
+                    }),
+                }],
+                next_cursor: None,
+            };
+
+            Mock::given(method("GET"))
+                .and(path(format!(
+                    "/v5/channel/{}/validator-messages/{}/{}",
+                    DUMMY_CAMPAIGN.channel.id(),
+                    DUMMY_CAMPAIGN.channel.follower,
+                    "Heartbeat",
+                )))
+                .and(query_param("limit", "1"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(&heartbeat_res))
+                .expect(1)
+        };
+
+        // Recent heartbeat
+        {
+            let _mock_guard = follower_heartbeat_res(Utc::now() - Duration::seconds(10))
+                .mount_as_scoped(&server)
+                .await;
+
+            let clock_skew = check_clock_skew(&sentry, &channel_context)
+                .await
+                .expect("Should check the clock skew")
+                .expect("Should have a Follower heartbeat");
+
+            assert_eq!(IDS[&FOLLOWER], clock_skew.validator);
+            assert!(!clock_skew.exceeds_threshold);
+        }
+
+        // Follower's clock is 3 minutes ahead
+        {
+            let _mock_guard = follower_heartbeat_res(Utc::now() + Duration::minutes(3))
+                .mount_as_scoped(&server)
+                .await;
+
+            let clock_skew = check_clock_skew(&sentry, &channel_context)
+                .await
+                .expect("Should check the clock skew")
+                .expect("Should have a Follower heartbeat");
+
+            assert!(clock_skew.exceeds_threshold);
+            assert!(clock_skew.skew > Duration::minutes(2));
+        }
+
+        // Follower's clock is 3 minutes behind
+        {
+            let _mock_guard = follower_heartbeat_res(Utc::now() - Duration::minutes(3))
+                .mount_as_scoped(&server)
+                .await;
+
+            let clock_skew = check_clock_skew(&sentry, &channel_context)
+                .await
+                .expect("Should check the clock skew")
+                .expect("Should have a Follower heartbeat");
+
+            assert!(clock_skew.exceeds_threshold);
+            assert!(clock_skew.skew < Duration::minutes(-2));
+        }
+    }
 }
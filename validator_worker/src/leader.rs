@@ -1,14 +1,18 @@
+use slog::info;
 use thiserror::Error;
+use tracing::{field, info_span, Instrument, Span};
 
 use adapter::{prelude::*, Error as AdapterError};
 use primitives::{
     balances::CheckedState,
+    sentry::{fees_summary::ValidatorFeesSummary, AccountingResponse},
     validator::{MessageError, MessageType, MessageTypes, NewState},
     Balances, ChainOf, Channel,
 };
 
 use crate::{
-    heartbeat::{heartbeat, Error as HeartbeatError, HeartbeatStatus},
+    debug, fees_summary,
+    heartbeat::{check_clock_skew, heartbeat, ClockSkew, Error as HeartbeatError, HeartbeatStatus},
     sentry_interface::{Error as SentryApiError, PropagationResult, SentryApi},
     GetStateRoot, GetStateRootError,
 };
@@ -18,6 +22,15 @@ pub struct TickStatus {
     pub heartbeat: HeartbeatStatus,
     /// When `None` the conditions for creating a `NewState` haven't been met
     pub new_state: Option<Vec<PropagationResult>>,
+    /// When `Ok(None)` a summary for the current period already exists.
+    ///
+    /// Failing to create the summary does not fail the whole tick.
+    pub fees_summary: Result<Option<ValidatorFeesSummary>, SentryApiError>,
+    /// When `Ok(None)` the Follower has not sent a Heartbeat yet
+    /// or we have not sent a Heartbeat in this tick, see [`check_clock_skew()`].
+    ///
+    /// Failing to check the clock skew does not fail the whole tick.
+    pub clock_skew: Result<Option<ClockSkew>, SentryApiError>,
 }
 
 #[derive(Debug, Error)]
@@ -36,69 +49,100 @@ pub enum Error {
     Overflow,
 }
 
+/// No `NewState` is produced while the accounting of the Channel is
+/// [`frozen`](AccountingResponse::frozen), but the Heartbeats continue.
 pub async fn tick<C: Unlocked + 'static>(
     sentry: &SentryApi<C>,
     channel_context: &ChainOf<Channel>,
-    accounting_balances: Balances<CheckedState>,
+    accounting: AccountingResponse<CheckedState>,
 ) -> Result<TickStatus, Error> {
     let channel = channel_context.context;
+    let accounting_balances = accounting.balances;
 
     // Check if Accounting != than latest NewState (Accounting.balances != NewState.balances)
-    let should_generate_new_state =
+    let should_generate_new_state = {
+        if accounting.frozen {
+            info!(&sentry.logger, "Channel accounting is frozen, skipping NewState"; "channel" => %channel.id());
+
+            false
+        } else if accounting_balances.earners.is_empty() || accounting_balances.spenders.is_empty()
         {
             // If the accounting is empty, then we don't need to create a NewState
-            if accounting_balances.earners.is_empty() || accounting_balances.spenders.is_empty() {
-                false
-            } else {
-                let latest_new_state = sentry
-                    .get_our_latest_msg(channel.id(), &[MessageType::NewState])
-                    .await?
-                    .map(NewState::<CheckedState>::try_from)
-                    .transpose()?;
-
-                match latest_new_state {
-                    Some(new_state) => {
-                        let check_spenders = accounting_balances.spenders.iter().any(
-                            |(spender, accounting_balance)| {
+            false
+        } else {
+            let latest_new_state = sentry
+                .get_our_latest_msg(channel.id(), &[MessageType::NewState])
+                .await?
+                .map(NewState::<CheckedState>::try_from)
+                .transpose()?;
+
+            match latest_new_state {
+                Some(new_state) => {
+                    let check_spenders =
+                        accounting_balances
+                            .spenders
+                            .iter()
+                            .any(|(spender, accounting_balance)| {
                                 match new_state.balances.spenders.get(spender) {
                                     Some(prev_balance) => accounting_balance > prev_balance,
                                     // if there is no previous balance for this Spender then it should generate a `NewState`
                                     // this includes adding an empty Spender to be included in the MerkleTree
                                     None => true,
                                 }
-                            },
-                        );
+                            });
 
-                        let check_earners = accounting_balances.earners.iter().any(
-                            |(earner, accounting_balance)| {
+                    let check_earners =
+                        accounting_balances
+                            .earners
+                            .iter()
+                            .any(|(earner, accounting_balance)| {
                                 match new_state.balances.earners.get(earner) {
                                     Some(prev_balance) => accounting_balance > prev_balance,
                                     // if there is no previous balance for this Earner then it should generate a `NewState`
                                     // this includes adding an empty Earner to be included in the MerkleTree
                                     None => true,
                                 }
-                            },
-                        );
-
-                        check_spenders || check_earners
-                    }
-                    // if no previous `NewState` (i.e. `Channel` is new) - it should generate a `NewState`
-                    // this is only valid if the Accounting balances are not empty!
-                    None => true,
+                            });
+
+                    check_spenders || check_earners
                 }
+                // if no previous `NewState` (i.e. `Channel` is new) - it should generate a `NewState`
+                // this is only valid if the Accounting balances are not empty!
+                None => true,
             }
-        };
+        }
+    };
 
     // Create a `NewState` if balances have changed
     let new_state = if should_generate_new_state {
-        Some(on_new_accounting(sentry, channel_context, accounting_balances).await?)
+        let span = info_span!(
+            "generate_new_state",
+            channel = %channel.id(),
+            state_root = field::Empty
+        );
+
+        Some(
+            on_new_accounting(sentry, channel_context, accounting_balances)
+                .instrument(span)
+                .await?,
+        )
     } else {
         None
     };
 
+    let heartbeat = heartbeat(sentry, channel_context).await?;
+    // the Follower sends its Heartbeats as often as we do,
+    // so its clock is checked only when we've sent ours
+    let clock_skew = match heartbeat {
+        Some(_) => check_clock_skew(sentry, channel_context).await,
+        None => Ok(None),
+    };
+
     Ok(TickStatus {
-        heartbeat: heartbeat(sentry, channel_context).await?,
+        heartbeat,
         new_state,
+        fees_summary: fees_summary::tick(sentry, channel_context).await,
+        clock_skew,
     })
 }
 
@@ -112,8 +156,16 @@ async fn on_new_accounting<C: Unlocked + 'static>(
         channel_context.token.precision.get(),
     )?;
 
+    Span::current().record("state_root", state_root.as_str());
+    debug::state_root(&state_root);
+
     let signature = sentry.adapter.sign(&state_root)?;
 
+    let span = info_span!(
+        "propagate",
+        channel = %channel_context.context.id(),
+        state_root = %state_root
+    );
     let propagation_results = sentry
         .propagate(
             channel_context,
@@ -123,6 +175,7 @@ async fn on_new_accounting<C: Unlocked + 'static>(
                 balances: accounting_balances.into_unchecked(),
             })],
         )
+        .instrument(span)
         .await?;
 
     Ok(propagation_results)
@@ -165,6 +218,7 @@ mod test {
             dummy_identity: IDS[&LEADER],
             dummy_auth_tokens: DUMMY_AUTH.clone(),
             dummy_chains: config.chains.values().cloned().collect(),
+            failure_injection: None,
         }));
         let logger = discard_logger();
 
@@ -193,6 +247,13 @@ mod test {
             .expect("Should propagate")
     }
 
+    fn accounting(
+        balances: Balances<CheckedState>,
+        frozen: bool,
+    ) -> AccountingResponse<CheckedState> {
+        AccountingResponse { balances, frozen }
+    }
+
     async fn setup_new_state_response(
         server: &MockServer,
         new_state_msg: Option<NewState<UncheckedState>>,
@@ -204,8 +265,12 @@ mod test {
                     received: Utc::now(),
                     msg: MessageTypes::NewState(msg),
                 }],
+                next_cursor: None,
+            },
+            None => ValidatorMessagesListResponse {
+                messages: vec![],
+                next_cursor: None,
             },
-            None => ValidatorMessagesListResponse { messages: vec![] },
         };
 
         Mock::given(method("GET"))
@@ -263,6 +328,15 @@ mod test {
             .mount(&server)
             .await;
 
+        // both validators' Sentries are healthy
+        for validator in ["leader", "follower"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/{validator}/health")))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(&server)
+                .await;
+        }
+
         let heartbeat = Heartbeat {
             signature: String::new(),
             state_root: String::new(),
@@ -274,6 +348,7 @@ mod test {
                 received: Utc::now(),
                 msg: MessageTypes::Heartbeat(heartbeat),
             }],
+            next_cursor: None,
         };
         Mock::given(method("GET"))
             .and(path(format!(
@@ -313,7 +388,7 @@ mod test {
         // Test case for empty balances
         {
             let balances: Balances<CheckedState> = Balances::new();
-            let tick_result = tick(&sentry, &channel_context, balances)
+            let tick_result = tick(&sentry, &channel_context, accounting(balances, false))
                 .await
                 .expect("Shouldn't return an error");
             assert!(
@@ -325,9 +400,13 @@ mod test {
         {
             let _mock_guard = setup_new_state_response(&server, None).await;
 
-            let tick_result = tick(&sentry, &channel_context, get_initial_balances())
-                .await
-                .expect("Shouldn't return an error");
+            let tick_result = tick(
+                &sentry,
+                &channel_context,
+                accounting(get_initial_balances(), false),
+            )
+            .await
+            .expect("Shouldn't return an error");
             assert!(
                 tick_result.new_state.is_some(),
                 "A NewState message should be generated when there isn't one"
@@ -370,9 +449,13 @@ mod test {
             };
             let _mock_guard = setup_new_state_response(&server, Some(new_state)).await;
 
-            let tick_result = tick(&sentry, &channel_context, get_initial_balances())
-                .await
-                .expect("Shouldn't return an error");
+            let tick_result = tick(
+                &sentry,
+                &channel_context,
+                accounting(get_initial_balances(), false),
+            )
+            .await
+            .expect("Shouldn't return an error");
             assert!(tick_result.new_state.is_none(), "Shouldn't generate a NewState when the balances in the previous NewState are equal to the ones in accounting_balances");
         }
 
@@ -397,9 +480,13 @@ mod test {
             expected_balances
                 .spend(*ADVERTISER, *PUBLISHER, UnifiedNum::from_u64(1000))
                 .expect("should spend");
-            let tick_result = tick(&sentry, &channel_context, expected_balances)
-                .await
-                .expect("Shouldn't return an error");
+            let tick_result = tick(
+                &sentry,
+                &channel_context,
+                accounting(expected_balances, false),
+            )
+            .await
+            .expect("Shouldn't return an error");
             assert!(
                 tick_result.new_state.is_some(),
                 "NewState message should be generated accounting_balances have been changed"
@@ -424,5 +511,37 @@ mod test {
                 "NewState message isn't propagated to any other validator"
             );
         }
+
+        // Frozen Channel accounting, no NewState is generated even though there isn't one
+        {
+            let tick_result = tick(
+                &sentry,
+                &channel_context,
+                accounting(get_initial_balances(), true),
+            )
+            .await
+            .expect("Shouldn't return an error");
+            assert!(
+                tick_result.new_state.is_none(),
+                "Shouldn't generate a NewState when the Channel accounting is frozen"
+            );
+        }
+
+        // Unfrozen Channel accounting, NewState generation resumes
+        {
+            let _mock_guard = setup_new_state_response(&server, None).await;
+
+            let tick_result = tick(
+                &sentry,
+                &channel_context,
+                accounting(get_initial_balances(), false),
+            )
+            .await
+            .expect("Shouldn't return an error");
+            assert!(
+                tick_result.new_state.is_some(),
+                "A NewState message should be generated after unfreezing the Channel accounting"
+            );
+        }
     }
 }
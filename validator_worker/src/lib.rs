@@ -17,11 +17,18 @@ pub use self::sentry_interface::SentryApi;
 pub use worker::Worker;
 
 pub mod channel;
+pub mod dead_letter;
+pub mod debug;
 pub mod error;
+pub mod fees_summary;
 pub mod follower;
+pub mod guardian;
 pub mod heartbeat;
 pub mod leader;
+pub mod recompute;
 pub mod sentry_interface;
+pub mod tick_report;
+pub mod verify;
 pub mod worker;
 
 pub mod core {
@@ -78,10 +85,20 @@ fn get_state_root_hash(
 mod test {
     use super::*;
 
+    use adapter::{
+        dummy::{Dummy, Options},
+        prelude::*,
+        Adapter,
+    };
     use primitives::{
         channel::Nonce,
-        test_util::{CREATOR, FOLLOWER, GUARDIAN, IDS, LEADER, PUBLISHER},
-        Channel,
+        config::GANACHE_CONFIG,
+        test_util::{
+            ADVERTISER, CREATOR, DUMMY_AUTH, DUMMY_CAMPAIGN, FOLLOWER, GUARDIAN, IDS, LEADER,
+            PUBLISHER, PUBLISHER_2,
+        },
+        validator::{MessageTypes, NewState},
+        Address, Channel, UnifiedNum,
     };
 
     #[test]
@@ -135,4 +152,62 @@ mod test {
             hex::encode(actual_hash)
         );
     }
+
+    #[test]
+    fn new_state_is_identical_regardless_of_the_insertion_order() {
+        let channel = DUMMY_CAMPAIGN.channel;
+        let spends = [
+            (*CREATOR, *PUBLISHER, 300_u64),
+            (*ADVERTISER, *PUBLISHER_2, 200),
+            (*CREATOR, *PUBLISHER_2, 100),
+            (*ADVERTISER, *PUBLISHER, 50),
+        ];
+
+        let balances = |spends: &mut dyn Iterator<Item = &(Address, Address, u64)>| {
+            let mut balances = Balances::<CheckedState>::default();
+            for (spender, earner, amount) in spends {
+                balances
+                    .spend(*spender, *earner, UnifiedNum::from_u64(*amount))
+                    .expect("Should spend");
+            }
+            balances
+        };
+
+        let adapter = Adapter::with_unlocked(Dummy::init(Options {
+            dummy_identity: IDS[&LEADER],
+            dummy_auth_tokens: DUMMY_AUTH.clone(),
+            dummy_chains: GANACHE_CONFIG.chains.values().cloned().collect(),
+            failure_injection: None,
+        }));
+
+        let new_state = |balances: Balances<CheckedState>| {
+            let state_root = balances
+                .encode(channel.id(), 18)
+                .expect("Should encode the state root");
+            let signature = adapter.sign(&state_root).expect("Should sign");
+
+            MessageTypes::NewState(NewState {
+                state_root,
+                signature,
+                balances: balances.into_unchecked(),
+            })
+        };
+
+        let in_order = new_state(balances(&mut spends.iter()));
+        let reversed = new_state(balances(&mut spends.iter().rev()));
+
+        assert_eq!(
+            serde_json::to_string(&in_order).expect("Should serialize"),
+            serde_json::to_string(&reversed).expect("Should serialize"),
+            "The NewState JSON should be byte-identical"
+        );
+
+        match (in_order, reversed) {
+            (MessageTypes::NewState(in_order), MessageTypes::NewState(reversed)) => {
+                assert_eq!(in_order.state_root, reversed.state_root);
+                assert_eq!(in_order.signature, reversed.signature);
+            }
+            _ => unreachable!("Both messages are NewStates"),
+        }
+    }
 }
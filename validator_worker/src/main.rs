@@ -5,14 +5,21 @@ use std::{env::VarError, error::Error};
 
 use clap::{crate_version, Arg, Command};
 
-use adapter::{primitives::AdapterTypes, Adapter, Dummy, Ethereum};
+use adapter::{prelude::*, Adapter, Dummy, Ethereum};
 use primitives::{
     config::{configuration, Environment},
     test_util::DUMMY_AUTH,
-    util::logging::new_logger,
-    ValidatorId,
+    util::{logging::new_logger, ApiUrl},
+    ChannelId, Config, ValidatorId,
+};
+use slog::Logger;
+use tokio::runtime::Runtime;
+use validator_worker::{
+    dead_letter::DeadLetterLog,
+    sentry_interface::build_client,
+    verify::{verify_channel, CampaignEvents},
+    SentryApi, Worker,
 };
-use validator_worker::{SentryApi, Worker};
 
 fn main() -> Result<(), Box<dyn Error>> {
     let cli = Command::new("Validator worker")
@@ -27,7 +34,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                 .long("adapter")
                 .short('a')
                 .help("the adapter for authentication and signing")
-                .required(true)
+                .required_unless_present("listDeadLetters")
                 .default_value("ethereum")
                 .possible_values(["ethereum", "dummy"])
                 .takes_value(true),
@@ -36,23 +43,46 @@ fn main() -> Result<(), Box<dyn Error>> {
             Arg::new("keystoreFile")
                 .long("keystoreFile")
                 .short('k')
-                .help("path to the JSON Ethereum Keystore file")
+                .help("path to the JSON Ethereum Keystore file, pass it multiple times to run multiple validator identities")
+                .multiple_occurrences(true)
                 .takes_value(true),
         )
         .arg(
             Arg::new("dummyIdentity")
                 .long("dummyIdentity")
                 .short('i')
-                .help("the identity to use with the dummy adapter")
+                .help("the identity to use with the dummy adapter, pass it multiple times to run multiple validator identities")
+                .multiple_occurrences(true)
                 .takes_value(true),
         )
         .arg(
             Arg::new("sentryUrl")
                 .long("sentryUrl")
                 .short('u')
-                .help("the URL to the sentry used for listing channels")
+                .help("the URL to the sentry used for listing channels, pass one for each validator identity in the same order")
                 .default_value("http://127.0.0.1:8005")
-                .required(true)
+                .multiple_occurrences(true)
+                .required_unless_present("listDeadLetters")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("deadLetterLog")
+                .long("deadLetterLog")
+                .help("path to the dead-letter log file (JSON lines) for validator messages which failed to propagate")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("listDeadLetters")
+                .long("listDeadLetters")
+                .help("prints the given number of most recent dead-letters from the `deadLetterLog` and exit")
+                .requires("deadLetterLog")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("debugChannel")
+                .long("debug-channel")
+                .help("ticks the given channel with debug tracing, pass it multiple times for multiple channels")
+                .multiple_occurrences(true)
                 .takes_value(true),
         )
         .arg(
@@ -62,8 +92,37 @@ fn main() -> Result<(), Box<dyn Error>> {
                 .takes_value(false)
                 .help("runs the validator in single-tick mode and exit"),
         )
+        .subcommand(
+            Command::new("verify")
+                .about("verifies the last approved state of a channel against its accounting and exit, exits with a non-zero code on a mismatch")
+                .arg(
+                    Arg::new("channel")
+                        .long("channel")
+                        .help("the channel to verify")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("events")
+                        .long("events")
+                        .help("path to a JSON file with the events of each campaign of the channel, e.g. `{\"0x...\": [...]}`, to recompute the accounting from")
+                        .takes_value(true),
+                ),
+        )
         .get_matches();
 
+    let dead_letters = cli.value_of("deadLetterLog").map(DeadLetterLog::new);
+
+    if let Some(limit) = cli.value_of("listDeadLetters") {
+        let dead_letters = dead_letters.expect("deadLetterLog is required");
+
+        for dead_letter in dead_letters.recent(limit.parse()?)? {
+            println!("{}", serde_json::to_string(&dead_letter)?);
+        }
+
+        return Ok(());
+    }
+
     let environment: Environment = match std::env::var("ENV") {
         Ok(string) => serde_json::from_value(serde_json::Value::String(string))
             .expect("Valid Environment - development or production"),
@@ -72,64 +131,208 @@ fn main() -> Result<(), Box<dyn Error>> {
     };
 
     let config_file = cli.value_of("config");
-    let config = configuration(environment, config_file).expect("failed to parse configuration");
-    let sentry_url = cli
-        .value_of("sentryUrl")
+    let mut config =
+        configuration(environment, config_file).expect("failed to parse configuration");
+    // the debug channels passed to the CLI are in addition to the ones in the configuration
+    if let Some(debug_channels) = cli.values_of("debugChannel") {
+        let debug_channels = debug_channels
+            .map(|channel| channel.parse::<ChannelId>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        config.worker.debug_channels.extend(debug_channels);
+    }
+    let sentry_urls = cli
+        .values_of("sentryUrl")
         .expect("sentry url missing")
-        .parse()?;
+        .map(|sentry_url| sentry_url.parse::<ApiUrl>())
+        .collect::<Result<Vec<_>, _>>()?;
     let is_single_tick = cli.is_present("singleTick");
+    let verify_channel = cli
+        .subcommand_matches("verify")
+        .map(|verify| -> Result<_, Box<dyn Error>> {
+            let channel = verify
+                .value_of("channel")
+                .expect("channel is required")
+                .parse::<ChannelId>()?;
+            let events = verify
+                .value_of("events")
+                .map(|events_file| -> Result<CampaignEvents, Box<dyn Error>> {
+                    Ok(serde_json::from_slice(&std::fs::read(events_file)?)?)
+                })
+                .transpose()?;
+
+            Ok((channel, events))
+        })
+        .transpose()?;
 
-    let unlocked_adapter = match cli.value_of("adapter").unwrap() {
+    // the guard should live as long as the workers
+    #[cfg(feature = "tracing")]
+    let _tracing_guard = primitives::util::logging::tracing::init(new_logger("validator_worker"))
+        .expect("Failed to install the tracing subscriber");
+
+    match cli.value_of("adapter").unwrap() {
         "ethereum" => {
-            let keystore_file = cli
-                .value_of("keystoreFile")
-                .expect("unable to get keystore file");
-            let keystore_pwd = std::env::var("KEYSTORE_PWD").expect("unable to get keystore pwd");
-            let keystore_options = adapter::ethereum::Options {
-                keystore_file: keystore_file.to_string(),
-                keystore_pwd,
-            };
-
-            let ethereum =
-                Ethereum::init(keystore_options, &config).expect("failed to init Ethereum adapter");
-
-            let adapter = Adapter::new(ethereum)
-                .unlock()
-                .expect("failed to Unlock Ethereum adapter");
-
-            AdapterTypes::ethereum(adapter)
+            let keystore_files = cli
+                .values_of("keystoreFile")
+                .expect("unable to get keystore file")
+                .collect::<Vec<_>>();
+
+            let adapters = keystore_files
+                .iter()
+                .enumerate()
+                .map(|(index, keystore_file)| {
+                    let keystore_pwd = keystore_password(index, keystore_files.len());
+                    let keystore_options = adapter::ethereum::Options {
+                        keystore_file: keystore_file.to_string(),
+                        keystore_pwd,
+                        token_validity: None,
+                    };
+
+                    let ethereum = Ethereum::init(keystore_options, &config)
+                        .expect("failed to init Ethereum adapter");
+
+                    Adapter::new(ethereum)
+                        .unlock()
+                        .expect("failed to Unlock Ethereum adapter")
+                })
+                .collect();
+
+            match verify_channel {
+                Some((channel, events)) => verify(adapters, sentry_urls, config, channel, events),
+                None => run_workers(adapters, sentry_urls, config, dead_letters, is_single_tick),
+            }
         }
         "dummy" => {
-            let dummy_identity = cli
-                .value_of("dummyIdentity")
-                .expect("unable to get dummyIdentity");
-            let options = adapter::dummy::Options {
-                dummy_identity: ValidatorId::try_from(dummy_identity)?,
-                dummy_auth_tokens: DUMMY_AUTH.clone(),
-                dummy_chains: config.chains.values().cloned().collect(),
-            };
-            let adapter = Adapter::with_unlocked(Dummy::init(options));
-
-            AdapterTypes::dummy(adapter)
+            let adapters = cli
+                .values_of("dummyIdentity")
+                .expect("unable to get dummyIdentity")
+                .map(|dummy_identity| {
+                    let options = adapter::dummy::Options {
+                        dummy_identity: ValidatorId::try_from(dummy_identity)?,
+                        dummy_auth_tokens: DUMMY_AUTH.clone(),
+                        dummy_chains: config.chains.values().cloned().collect(),
+                        failure_injection: None,
+                    };
+
+                    Ok(Adapter::with_unlocked(Dummy::init(options)))
+                })
+                .collect::<Result<_, Box<dyn Error>>>()?;
+
+            match verify_channel {
+                Some((channel, events)) => verify(adapters, sentry_urls, config, channel, events),
+                None => run_workers(adapters, sentry_urls, config, dead_letters, is_single_tick),
+            }
         }
         // @TODO exit gracefully
         _ => panic!("We don't have any other adapters implemented yet!"),
-    };
+    }
+}
 
-    let logger = new_logger("validator_worker");
+/// Creates a [`Worker`] for each validator identity (unlocked [`Adapter`]) and it's [`ApiUrl`]
+/// and runs all of them in a single runtime.
+fn run_workers<C: Unlocked + 'static>(
+    adapters: Vec<Adapter<C, UnlockedState>>,
+    sentry_urls: Vec<ApiUrl>,
+    config: Config,
+    dead_letters: Option<DeadLetterLog>,
+    is_single_tick: bool,
+) -> Result<(), Box<dyn Error>> {
+    if adapters.len() != sentry_urls.len() {
+        return Err(format!(
+            "Each validator identity requires a sentryUrl, identities: {}, sentry urls: {}",
+            adapters.len(),
+            sentry_urls.len()
+        )
+        .into());
+    }
 
-    match unlocked_adapter {
-        AdapterTypes::Ethereum(eth_adapter) => {
-            let sentry = SentryApi::new(*eth_adapter, logger.clone(), config, sentry_url)
-                .expect("Should create the SentryApi");
+    let is_multiple = adapters.len() > 1;
+    // all the validator identities share the same HTTP client
+    let client = build_client(&config)?;
 
-            Worker::from_sentry(sentry).run(is_single_tick)
-        }
-        AdapterTypes::Dummy(dummy_adapter) => {
-            let sentry = SentryApi::new(*dummy_adapter, logger.clone(), config, sentry_url)
-                .expect("Should create the SentryApi");
+    let workers = adapters
+        .into_iter()
+        .zip(sentry_urls)
+        .map(|(adapter, sentry_url)| {
+            let logger = worker_logger(is_multiple, adapter.whoami());
 
-            Worker::from_sentry(sentry).run(is_single_tick)
+            SentryApi::with_client(adapter, logger, config.clone(), sentry_url, client.clone())
+                .map(|sentry| match &dead_letters {
+                    Some(dead_letters) => sentry.with_dead_letters(dead_letters.clone()),
+                    None => sentry,
+                })
+                .map(Worker::from_sentry)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Should create the SentryApi");
+
+    Worker::run_all(workers, is_single_tick)
+}
+
+/// Verifies the `channel` with the first validator identity and it's [`ApiUrl`],
+/// printing the found discrepancies.
+///
+/// When the `events` of the Campaigns are passed, the accounting is recomputed from them as well.
+///
+/// The process exits with code `1` on a mismatch.
+fn verify<C: Unlocked + 'static>(
+    adapters: Vec<Adapter<C, UnlockedState>>,
+    sentry_urls: Vec<ApiUrl>,
+    config: Config,
+    channel: ChannelId,
+    events: Option<CampaignEvents>,
+) -> Result<(), Box<dyn Error>> {
+    let (adapter, sentry_url) = adapters
+        .into_iter()
+        .zip(sentry_urls)
+        .next()
+        .ok_or("A validator identity and a sentryUrl are required")?;
+    let logger = worker_logger(false, adapter.whoami());
+    let sentry = SentryApi::new(adapter, logger, config, sentry_url)?;
+
+    let verification =
+        Runtime::new()?.block_on(verify_channel(&sentry, channel, events.as_ref()))?;
+
+    match &verification.state_root {
+        Some(state_root) => println!("Channel {channel} last approved state root: {state_root}"),
+        None => println!("Channel {channel} has no approved state yet"),
+    }
+
+    if verification.is_valid() {
+        println!("No discrepancies found");
+
+        return Ok(());
+    }
+
+    for discrepancy in &verification.discrepancies {
+        println!("Discrepancy: {discrepancy}");
+    }
+
+    std::process::exit(1)
+}
+
+/// When running multiple validator identities the password for each keystore file
+/// is taken from `KEYSTORE_PWD_{N}` (`N` - the position of the keystore file starting from `1`),
+/// with a fallback to `KEYSTORE_PWD` which is used for a single keystore file.
+fn keystore_password(index: usize, keystore_files: usize) -> String {
+    let keystore_pwd = || std::env::var("KEYSTORE_PWD").expect("unable to get keystore pwd");
+
+    if keystore_files > 1 {
+        match std::env::var(format!("KEYSTORE_PWD_{}", index + 1)) {
+            Ok(keystore_pwd) => keystore_pwd,
+            Err(VarError::NotPresent) => keystore_pwd(),
+            Err(err) => panic!("Invalid `KEYSTORE_PWD_{}`: {err}", index + 1),
         }
+    } else {
+        keystore_pwd()
+    }
+}
+
+/// Each validator identity has it's own prefixed logger when running multiple identities.
+fn worker_logger(is_multiple: bool, whoami: ValidatorId) -> Logger {
+    if is_multiple {
+        new_logger(&format!("validator_worker-{whoami}"))
+    } else {
+        new_logger("validator_worker")
     }
 }
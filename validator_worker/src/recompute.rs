@@ -0,0 +1,212 @@
+//! Offline recomputation of the [`Balances`] of a [`Campaign`] from its events,
+//! independent of the `NewState` produced by the Leader, e.g. for audits.
+
+use primitives::{
+    balances::{CheckedState, OverflowError},
+    config::PayoutRounding,
+    sentry::Event,
+    targeting::get_unit_pricing_bounds,
+    Address, Balances, Campaign, Config, UnifiedNum, ValidatorDesc,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Campaign token is not whitelisted in the Config")]
+    TokenNotWhitelisted,
+    #[error("Campaign has no Leader and Follower")]
+    NoValidators,
+    #[error("Event payout overflow")]
+    PayoutOverflow,
+    #[error(transparent)]
+    Balances(#[from] OverflowError),
+}
+
+/// Recomputes the [`Balances`] which the `events` of the `campaign` should have spent,
+/// the same way as Sentry's `spend_for_events` does, but without any side effects.
+///
+/// The [`Event`] publisher earns the minimum price of the AdUnit pricing bounds for the event type
+/// and the Leader & Follower earn their fees from it, credited to their [`ValidatorDesc::fee_recipient`].
+/// The fees are rounded and capped according to the Campaign token's
+/// [`TokenInfo`](primitives::config::TokenInfo) in the `config`.
+///
+/// The targeting rules and the AdSlots minimum prices are not applied,
+/// as they depend on the event request and the Platform.
+pub fn recompute_balances(
+    config: &Config,
+    campaign: &Campaign,
+    events: &[Event],
+) -> Result<Balances<CheckedState>, Error> {
+    let token = config
+        .find_chain_of(campaign.channel.token)
+        .ok_or(Error::TokenNotWhitelisted)?
+        .token;
+    let (leader, follower) = campaign
+        .leader()
+        .zip(campaign.follower())
+        .ok_or(Error::NoValidators)?;
+    let max_validator_fee = token.max_validator_fee_rate();
+
+    let mut balances = Balances::<CheckedState>::new();
+
+    for event in events {
+        let (publisher, payout) = event_payout(config, campaign, event);
+
+        let leader_fee = validator_fee(payout, leader, token.payout_rounding, max_validator_fee)?;
+        let follower_fee =
+            validator_fee(payout, follower, token.payout_rounding, max_validator_fee)?;
+
+        balances.spend(campaign.creator, leader.fee_recipient(), leader_fee)?;
+        balances.spend(campaign.creator, follower.fee_recipient(), follower_fee)?;
+        balances.spend(campaign.creator, publisher, payout)?;
+    }
+
+    Ok(balances)
+}
+
+fn event_payout(config: &Config, campaign: &Campaign, event: &Event) -> (Address, UnifiedNum) {
+    let (publisher, ad_unit) = match event {
+        Event::Impression {
+            publisher, ad_unit, ..
+        }
+        | Event::Click {
+            publisher, ad_unit, ..
+        }
+        | Event::Conversion {
+            publisher, ad_unit, ..
+        }
+        | Event::Custom {
+            publisher, ad_unit, ..
+        } => (*publisher, ad_unit),
+    };
+
+    let event_type = event.event_type();
+    let default_pricing = config
+        .find_event_type(&event_type)
+        .and_then(|event_type| event_type.default_pricing.as_ref());
+
+    let pricing = get_unit_pricing_bounds(campaign, &event_type, ad_unit, default_pricing);
+
+    (publisher, pricing.min)
+}
+
+/// The same fee calculation as Sentry's `calculate_fee`.
+fn validator_fee(
+    payout: UnifiedNum,
+    validator: &ValidatorDesc,
+    rounding: PayoutRounding,
+    max_fee: Option<UnifiedNum>,
+) -> Result<UnifiedNum, Error> {
+    let validator_fee = match max_fee {
+        Some(max_fee) => validator.fee.min(max_fee),
+        None => validator.fee,
+    };
+
+    match rounding {
+        PayoutRounding::Floor => payout.checked_mul_floor(&validator_fee),
+        PayoutRounding::Nearest => payout.checked_mul(&validator_fee),
+    }
+    .ok_or(Error::PayoutOverflow)
+}
+
+#[cfg(test)]
+mod test {
+    use primitives::{
+        config::GANACHE_CONFIG,
+        sentry::{CLICK, IMPRESSION},
+        test_util::{
+            CampaignBuilder, ADVERTISER, DUMMY_CAMPAIGN, DUMMY_IPFS, DUMMY_VALIDATOR_FOLLOWER,
+            DUMMY_VALIDATOR_LEADER, PUBLISHER,
+        },
+        unified_num::FromWhole,
+    };
+
+    use super::*;
+
+    /// The Campaign's pricing & validator fees are the same as the `CAMPAIGN_1` of the `test_harness`.
+    fn campaign_1() -> Campaign {
+        let leader = ValidatorDesc {
+            fee: UnifiedNum::from_whole(0.005),
+            ..DUMMY_VALIDATOR_LEADER.clone()
+        };
+        let follower = ValidatorDesc {
+            fee: UnifiedNum::from_whole(0.004),
+            ..DUMMY_VALIDATOR_FOLLOWER.clone()
+        };
+
+        CampaignBuilder::new(DUMMY_CAMPAIGN.channel)
+            .creator(*ADVERTISER)
+            .budget_whole(150)
+            .validators(leader, follower)
+            .pricing(IMPRESSION, 0.04, 0.05)
+            .pricing(CLICK, 0.06, 0.1)
+            .build()
+    }
+
+    #[test]
+    fn recomputes_the_test_harness_campaign_1_balances() {
+        let campaign = campaign_1();
+        let ad_unit = DUMMY_IPFS[0];
+
+        let events = [
+            Event::Impression {
+                publisher: *PUBLISHER,
+                ad_unit,
+                ad_slot: DUMMY_IPFS[2],
+                referrer: Some("https://adex.network".into()),
+            },
+            Event::Click {
+                publisher: *PUBLISHER,
+                ad_unit,
+                ad_slot: DUMMY_IPFS[2],
+                referrer: Some("https://ambire.com".into()),
+                click_id: None,
+            },
+        ];
+
+        let balances = recompute_balances(&GANACHE_CONFIG, &campaign, &events)
+            .expect("Should recompute the balances");
+
+        let mut expected = Balances::<CheckedState>::new();
+        expected
+            .spend(
+                campaign.creator,
+                DUMMY_VALIDATOR_LEADER.fee_recipient(),
+                UnifiedNum::from_whole(0.0005),
+            )
+            .expect("Should spend for Leader");
+        expected
+            .spend(
+                campaign.creator,
+                DUMMY_VALIDATOR_FOLLOWER.fee_recipient(),
+                UnifiedNum::from_whole(0.0004),
+            )
+            .expect("Should spend for Follower");
+        expected
+            .spend(campaign.creator, *PUBLISHER, UnifiedNum::from_whole(0.1))
+            .expect("Should spend for Publisher");
+
+        assert_eq!(expected, balances);
+        // events payout + leader fees + follower fees
+        assert_eq!(
+            Some(&UnifiedNum::from_whole(0.1009)),
+            balances.spenders.get(&campaign.creator)
+        );
+    }
+
+    #[test]
+    fn recomputing_without_events_or_with_unknown_token() {
+        let campaign = campaign_1();
+
+        let balances = recompute_balances(&GANACHE_CONFIG, &campaign, &[])
+            .expect("Should recompute the balances");
+        assert_eq!(Balances::new(), balances);
+
+        let mut config = GANACHE_CONFIG.clone();
+        config.chains.clear();
+        assert!(matches!(
+            recompute_balances(&config, &campaign, &[]),
+            Err(Error::TokenNotWhitelisted)
+        ));
+    }
+}
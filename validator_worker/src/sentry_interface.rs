@@ -1,34 +1,71 @@
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
-    time::Duration,
+    num::NonZeroU64,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
-use futures::future::{join_all, try_join_all, TryFutureExt};
-use reqwest::{Client, Method};
-use slog::Logger;
+use chrono::{DateTime, Utc};
+use futures::{
+    future::{self, join_all, TryFutureExt},
+    stream, Future, Stream, StreamExt, TryStreamExt,
+};
+use reqwest::{Client, Method, Response, Url};
+use slog::{error, o, warn, Discard, Logger};
 
-use adapter::{prelude::*, Adapter};
+use adapter::{
+    dummy::{Dummy, Options as DummyOptions},
+    prelude::*,
+    Adapter,
+};
 use primitives::{
     balances::{CheckedState, UncheckedState},
     sentry::{
+        campaign_get::{CampaignsBatchRequest, CampaignsBatchResponse},
+        channel_list::{ChannelListQuery, ChannelListResponse},
+        fees_summary::{
+            FeesSummaryQuery, FeesSummaryRequest, FeesSummaryResponse, ValidatorFeesSummary,
+        },
         validator_messages::{
-            MessageTypesFilter, ValidatorMessagesCreateRequest, ValidatorMessagesListResponse,
+            MessageTypesFilter, QuarantineStateRequest, ValidatorMessage,
+            ValidatorMessagesCreateRequest, ValidatorMessagesListQuery,
+            ValidatorMessagesListResponse,
         },
-        AccountingResponse, AllSpendersResponse, LastApprovedResponse, SuccessResponse,
+        whoami::{challenge_message, WhoamiQuery, WhoamiResponse},
+        AccountingResponse, AllSpendersQuery, AllSpendersResponse, LastApprovedResponse,
+        Pagination, SpenderResponse, SuccessResponse,
     },
     spender::Spender,
     util::ApiUrl,
     validator::{MessageType, MessageTypes},
-    Address, ChainId, ChainOf, Channel, ChannelId, Config, ValidatorId,
+    Address, Campaign, CampaignId, ChainId, ChainOf, Channel, ChannelId, Config, ValidatorId,
 };
 use thiserror::Error;
 
+use crate::{
+    dead_letter::{DeadLetter, DeadLetterLog},
+    debug, tick_report,
+};
+
+/// The maximum number of concurrent requests of [`SentryApi::get_last_approved_many()`].
+pub const LAST_APPROVED_CONCURRENCY: usize = 8;
+
+/// The maximum number of pages fetched concurrently by the paginated streams,
+/// e.g. [`SentryApi::channels_stream()`].
+pub const PAGES_CONCURRENCY: usize = 4;
+
 pub type PropagationResult = Result<ValidatorId, (ValidatorId, Error)>;
 pub type ChainsValidators = HashMap<ChainId, Validators>;
 /// Propagate the Validator messages to these `Validator`s
 /// This map contains the Validator Auth token & Url for a specific Chain
 pub type Validators = HashMap<ValidatorId, Validator>;
 pub type AuthToken = String;
+/// The Sentry [`ApiUrl`] of each validator which passed the `GET /v5/whoami` challenge
+/// alongside the time of the verification.
+pub type VerifiedPeers = Arc<Mutex<HashMap<ValidatorId, (ApiUrl, Instant)>>>;
+/// The latest period of each [`Channel`] for which a fees summary exists,
+/// see [`fees_summary::tick`](crate::fees_summary::tick).
+pub type FeesSummaryPeriods = Arc<Mutex<HashMap<ChannelId, DateTime<Utc>>>>;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Validator {
@@ -62,6 +99,19 @@ pub enum Error {
         channel: Vec<ValidatorId>,
         found: HashMap<ValidatorId, Validator>,
     },
+    #[error("The Sentry at {url} responded to the whoami challenge as {responded} instead of {expected}")]
+    PeerMismatch {
+        expected: ValidatorId,
+        responded: ValidatorId,
+        /// Boxed to keep the size of the `Error` small.
+        url: Box<ApiUrl>,
+    },
+    #[error(
+        "The Sentry at {url} failed to prove with a valid signature that it speaks for {validator}"
+    )]
+    PeerNotVerified { validator: ValidatorId, url: ApiUrl },
+    #[error("The Sentry of validator {validator} at {url} is unhealthy")]
+    ValidatorUnhealthy { validator: ValidatorId, url: ApiUrl },
 }
 
 #[derive(Debug)]
@@ -81,6 +131,19 @@ pub struct SentryApi<C: Unlocked, P = ChainsValidators> {
     /// If set with [`Validators`], `propagate_to` should contain the `whoami` [`Validator`] in each Chain!
     /// use [`SentryApi::init`] or [`SentryApi::with_propagate`] instead
     pub propagate_to: P,
+    /// Messages which failed to propagate to a validator are recorded in this log.
+    /// Use [`SentryApi::with_dead_letters`] to set it.
+    pub dead_letters: Option<DeadLetterLog>,
+    /// The other validators' Sentries which passed the `GET /v5/whoami` challenge,
+    /// see [`SentryApi::ensure_verified_peer`].
+    ///
+    /// It's shared between all the clones of the [`SentryApi`].
+    pub verified_peers: VerifiedPeers,
+    /// The periods of the already created fees summaries,
+    /// so that the Sentry is not queried for them on every tick.
+    ///
+    /// It's shared between all the clones of the [`SentryApi`].
+    pub fees_summary_periods: FeesSummaryPeriods,
 }
 
 impl<C: Unlocked, P: Clone> Clone for SentryApi<C, P> {
@@ -93,10 +156,22 @@ impl<C: Unlocked, P: Clone> Clone for SentryApi<C, P> {
             sentry_url: self.sentry_url.clone(),
             whoami: self.whoami.clone(),
             propagate_to: self.propagate_to.clone(),
+            dead_letters: self.dead_letters.clone(),
+            verified_peers: self.verified_peers.clone(),
+            fees_summary_periods: self.fees_summary_periods.clone(),
         }
     }
 }
 
+/// Builds the HTTP [`Client`] used by the [`SentryApi`]
+/// with the configured [`Timeouts.fetch`](primitives::config::Timeouts::fetch).
+pub fn build_client(config: &Config) -> Result<Client, Error> {
+    Client::builder()
+        .timeout(config.worker.timeouts.fetch)
+        .build()
+        .map_err(Error::BuildingClient)
+}
+
 impl<C: Unlocked + 'static> SentryApi<C, ()> {
     /// `sentry_url` is the default URL to which the current _Who am I_ validator should make requests.
     /// It is used to populate the config Chains with Authentication Token & [`ApiUrl`].
@@ -108,11 +183,22 @@ impl<C: Unlocked + 'static> SentryApi<C, ()> {
         config: Config,
         sentry_url: ApiUrl,
     ) -> Result<SentryApi<C, ()>, Error> {
-        let client = Client::builder()
-            .timeout(config.worker.timeouts.fetch)
-            .build()
-            .map_err(Error::BuildingClient)?;
+        let client = build_client(&config)?;
+
+        Self::with_client(adapter, logger, config, sentry_url, client)
+    }
 
+    /// Same as [`SentryApi::new`] but re-uses an already built [`Client`].
+    ///
+    /// Useful when running multiple validator identities in a single process,
+    /// as all of them can share the same HTTP client (and it's connection pool).
+    pub fn with_client(
+        adapter: Adapter<C, UnlockedState>,
+        logger: Logger,
+        config: Config,
+        sentry_url: ApiUrl,
+        client: Client,
+    ) -> Result<SentryApi<C, ()>, Error> {
         let whoami = config
             .chains
             .values()
@@ -141,6 +227,9 @@ impl<C: Unlocked + 'static> SentryApi<C, ()> {
             sentry_url,
             whoami,
             propagate_to: (),
+            dead_letters: None,
+            verified_peers: VerifiedPeers::default(),
+            fees_summary_periods: FeesSummaryPeriods::default(),
         })
     }
 
@@ -189,17 +278,119 @@ impl<C: Unlocked + 'static> SentryApi<C, ()> {
             sentry_url: self.sentry_url,
             whoami: self.whoami,
             propagate_to,
+            dead_letters: self.dead_letters,
+            verified_peers: self.verified_peers,
+            fees_summary_periods: self.fees_summary_periods,
+        })
+    }
+}
+
+impl SentryApi<Dummy, ()> {
+    /// Creates a read-only [`SentryApi`] for querying a Sentry,
+    /// e.g. the accounting or the last approved state of a [`Channel`],
+    /// without an Ethereum keystore.
+    ///
+    /// It uses a [`Dummy::read_only()`] adapter with the zero address as _Who am I_
+    /// which can't sign, so it's intended only for the GET requests.
+    ///
+    /// **NOTE:** The requests which require authentication in Sentry (e.g. propagating
+    /// validator messages or creating a fees summary) will fail!
+    pub fn from_config(config: Config, sentry_url: ApiUrl) -> Result<Self, Error> {
+        let adapter = Adapter::with_unlocked(Dummy::read_only(DummyOptions {
+            dummy_identity: ValidatorId::from(&[0_u8; 20]),
+            dummy_auth_tokens: HashMap::new(),
+            dummy_chains: config.chains.values().cloned().collect(),
+            failure_injection: None,
+        }));
+        let client = build_client(&config)?;
+
+        Ok(SentryApi {
+            adapter,
+            client,
+            logger: Logger::root(Discard, o!()),
+            config,
+            sentry_url,
+            // without authentication tokens
+            whoami: HashMap::new(),
+            propagate_to: (),
+            dead_letters: None,
+            verified_peers: VerifiedPeers::default(),
+            fees_summary_periods: FeesSummaryPeriods::default(),
         })
     }
 }
 
 impl<C: Unlocked + 'static, P> SentryApi<C, P> {
+    /// Records the messages which failed to propagate in the given [`DeadLetterLog`].
+    pub fn with_dead_letters(mut self, dead_letters: DeadLetterLog) -> Self {
+        self.dead_letters = Some(dead_letters);
+        self
+    }
+
+    /// Makes a GET request and retries it using the configured
+    /// [`Config.worker.retry`](primitives::config::RetryPolicy) policy.
+    ///
+    /// Only failed requests and Server errors (`5xx`) are retried,
+    /// after all attempts are exhausted the last error is returned.
+    ///
+    /// **NOTE:** Use only for idempotent requests!
+    async fn get_with_retry(&self, url: Url, auth_token: Option<&str>) -> Result<Response, Error> {
+        let retry_policy = &self.config.worker.retry;
+        let attempts = retry_policy.attempts.get();
+
+        let mut attempt = 1;
+        loop {
+            let mut request = self.client.get(url.clone());
+            if let Some(auth_token) = auth_token {
+                request = request.bearer_auth(auth_token);
+            }
+
+            let result = request.send().await.and_then(|response| {
+                if response.status().is_server_error() {
+                    response.error_for_status()
+                } else {
+                    Ok(response)
+                }
+            });
+
+            match result {
+                Ok(response) => return Ok(response),
+                Err(err) if attempt < attempts => {
+                    let backoff = retry_policy.backoff_for(attempt);
+                    warn!(
+                        &self.logger,
+                        "GET request failed, retrying in {:?} (attempt {}/{})", backoff, attempt, attempts;
+                        "url" => %url, "error" => ?err
+                    );
+
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                Err(err) => return Err(Error::Request(err)),
+            }
+        }
+    }
+
     pub async fn get_latest_msg(
         &self,
         channel: ChannelId,
         from: ValidatorId,
         message_types: &[MessageType],
     ) -> Result<Option<MessageTypes>, Error> {
+        Ok(self
+            .get_latest_validator_msg(channel, from, message_types)
+            .await?
+            .map(|message| message.msg))
+    }
+
+    /// Same as [`SentryApi::get_latest_msg()`] but returns the whole [`ValidatorMessage`],
+    /// including the time it was received.
+    pub async fn get_latest_validator_msg(
+        &self,
+        channel: ChannelId,
+        from: ValidatorId,
+        message_types: &[MessageType],
+    ) -> Result<Option<ValidatorMessage>, Error> {
         let messages_filter = MessageTypesFilter(message_types.to_vec());
         let messages_encoded = urlencoding::Encoded(messages_filter.to_string());
 
@@ -212,14 +403,76 @@ impl<C: Unlocked + 'static, P> SentryApi<C, P> {
             .expect("Should not error when creating endpoint url");
 
         let response = self
-            .client
-            .get(endpoint)
-            .send()
+            .get_with_retry(endpoint, None)
             .await?
             .json::<ValidatorMessagesListResponse>()
             .await?;
 
-        Ok(response.messages.into_iter().next().map(|m| m.msg))
+        Ok(response.messages.into_iter().next())
+    }
+
+    /// Gets a single page of the validator messages of `from`, ordered oldest-first,
+    /// see [`ValidatorMessagesListQuery`] for the `since` & `cursor` filters.
+    ///
+    /// The page size is Sentry's
+    /// [`Config.limits.msgs_find`](primitives::config::Limits::msgs_find).
+    pub async fn get_msgs_page(
+        &self,
+        channel: ChannelId,
+        from: ValidatorId,
+        message_types: &[MessageType],
+        query: &ValidatorMessagesListQuery,
+    ) -> Result<ValidatorMessagesListResponse, Error> {
+        let messages_filter = MessageTypesFilter(message_types.to_vec());
+        let messages_encoded = urlencoding::Encoded(messages_filter.to_string());
+
+        let endpoint = self
+            .sentry_url
+            .join(&format!(
+                "v5/channel/{}/validator-messages/{}/{}?{}",
+                channel,
+                from,
+                messages_encoded,
+                serde_qs::to_string(query).expect("Should not fail to serialize")
+            ))
+            .expect("Should not error when creating endpoint url");
+
+        self.get_with_retry(endpoint, None)
+            .await?
+            .json()
+            .map_err(Error::Request)
+            .await
+    }
+
+    /// Fetches all the validator messages of `from` received at or after `since`,
+    /// ordered oldest-first, by following the cursor of each page.
+    ///
+    /// Used for catching up with all the messages (e.g. the `NewState`s of the leader)
+    /// missed while the validator was down.
+    pub async fn get_msgs_since(
+        &self,
+        channel: ChannelId,
+        from: ValidatorId,
+        message_types: &[MessageType],
+        since: DateTime<Utc>,
+    ) -> Result<Vec<ValidatorMessage>, Error> {
+        let mut query = ValidatorMessagesListQuery {
+            since: Some(since),
+            ..Default::default()
+        };
+        let mut messages = vec![];
+
+        loop {
+            let page = self
+                .get_msgs_page(channel, from, message_types, &query)
+                .await?;
+            messages.extend(page.messages);
+
+            match page.next_cursor {
+                Some(next_cursor) => query.cursor = Some(next_cursor),
+                None => break Ok(messages),
+            }
+        }
     }
 
     pub async fn get_our_latest_msg(
@@ -236,22 +489,36 @@ impl<C: Unlocked + 'static, P> SentryApi<C, P> {
         &self,
         channel: ChannelId,
     ) -> Result<LastApprovedResponse<UncheckedState>, Error> {
-        self.client
-            .get(
-                self.sentry_url
-                    .join(&format!(
-                        "v5/channel/{}/last-approved?withHeartbeat=true",
-                        channel
-                    ))
-                    .expect("Should not error while creating endpoint"),
-            )
-            .send()
+        let url = self
+            .sentry_url
+            .join(&format!(
+                "v5/channel/{}/last-approved?withHeartbeat=true",
+                channel
+            ))
+            .expect("Should not error while creating endpoint");
+
+        self.get_with_retry(url, None)
             .await?
             .json()
             .await
             .map_err(Error::Request)
     }
 
+    /// Gets the last approved state of multiple Channels, see [`SentryApi::get_last_approved()`].
+    ///
+    /// At most [`LAST_APPROVED_CONCURRENCY`] requests are issued concurrently
+    /// and each Channel has its own result, i.e. a failing Channel does not fail the rest.
+    pub async fn get_last_approved_many(
+        &self,
+        channels: &[ChannelId],
+    ) -> HashMap<ChannelId, Result<LastApprovedResponse<UncheckedState>, Error>> {
+        stream::iter(channels.iter().copied())
+            .map(|channel| async move { (channel, self.get_last_approved(channel).await) })
+            .buffer_unordered(LAST_APPROVED_CONCURRENCY)
+            .collect()
+            .await
+    }
+
     /// page always starts from 0
     pub async fn get_spenders_page(
         &self,
@@ -259,11 +526,17 @@ impl<C: Unlocked + 'static, P> SentryApi<C, P> {
         page: u64,
     ) -> Result<AllSpendersResponse, Error> {
         let channel_id = channel_context.context.id();
+        let query = AllSpendersQuery {
+            page,
+            include_empty: false,
+            limit: self.config.worker.page_size.map(NonZeroU64::get),
+        };
         let url = self
             .sentry_url
             .join(&format!(
-                "v5/channel/{}/spender/all?page={}",
-                channel_id, page
+                "v5/channel/{}/spender/all?{}",
+                channel_id,
+                serde_qs::to_string(&query).expect("Should not fail to serialize")
             ))
             .expect("Should not error when creating endpoint");
 
@@ -274,38 +547,173 @@ impl<C: Unlocked + 'static, P> SentryApi<C, P> {
                 for_chain: channel_context.chain.chain_id,
             })?;
 
-        self.client
-            .get(url)
-            .bearer_auth(&auth_token)
-            .send()
+        self.get_with_retry(url, Some(&auth_token))
             .await?
             .json()
             .map_err(Error::Request)
             .await
     }
 
+    /// Fetches all the [`Spender`]s of the [`Channel`] page by page,
+    /// see [`SentryApi::get_spenders_page`] and [`paginated`].
+    ///
+    /// The page size is the [`Config.worker.page_size`](primitives::config::ValidatorWorkerConfig::page_size),
+    /// capped (and defaulting) to Sentry's
+    /// [`Config.limits.spendable_find`](primitives::config::Limits::spendable_find).
+    pub fn spenders_stream<'a>(
+        &'a self,
+        channel_context: &'a ChainOf<Channel>,
+    ) -> impl Stream<Item = Result<(Address, Spender), Error>> + 'a {
+        paginated(move |page| {
+            self.get_spenders_page(channel_context, page)
+                .map_ok(|response| (response.spenders, response.pagination))
+        })
+    }
+
     pub async fn get_all_spenders(
         &self,
         channel_context: &ChainOf<Channel>,
     ) -> Result<HashMap<Address, Spender>, Error> {
-        let first_page = self.get_spenders_page(channel_context, 0).await?;
+        self.spenders_stream(channel_context).try_collect().await
+    }
 
-        if first_page.pagination.total_pages < 2 {
-            Ok(first_page.spenders)
-        } else {
-            let all: Vec<AllSpendersResponse> = try_join_all(
-                (1..first_page.pagination.total_pages)
-                    .map(|i| self.get_spenders_page(channel_context, i)),
-            )
+    /// page always starts from 0
+    ///
+    /// Returns only the [`Channel`]s of the _Who am I_ validator.
+    pub async fn get_channels_page(&self, page: u64) -> Result<ChannelListResponse, Error> {
+        self.list_channels(ChannelListQuery {
+            page,
+            validator: Some(self.adapter.whoami()),
+            guardian: None,
+            chains: vec![],
+            limit: self.config.worker.page_size.map(NonZeroU64::get),
+        })
+        .await
+    }
+
+    /// page always starts from 0
+    ///
+    /// Returns only the [`Channel`]s of which the _Who am I_ validator is the guardian.
+    pub async fn get_guardian_channels_page(
+        &self,
+        page: u64,
+    ) -> Result<ChannelListResponse, Error> {
+        self.list_channels(ChannelListQuery {
+            page,
+            validator: None,
+            guardian: Some(self.adapter.whoami().to_address()),
+            chains: vec![],
+            limit: self.config.worker.page_size.map(NonZeroU64::get),
+        })
+        .await
+    }
+
+    async fn list_channels(&self, query: ChannelListQuery) -> Result<ChannelListResponse, Error> {
+        let url = self
+            .sentry_url
+            .join(&format!(
+                "v5/channel/list?{}",
+                serde_qs::to_string(&query).expect("Should not fail to serialize")
+            ))
+            .expect("Should not error when creating endpoint");
+
+        self.get_with_retry(url, None)
+            .await?
+            .json()
+            .map_err(Error::Request)
+            .await
+    }
+
+    /// Fetches all the [`Channel`]s of the _Who am I_ validator page by page,
+    /// see [`SentryApi::get_channels_page`] and [`paginated`].
+    ///
+    /// The page size is the [`Config.worker.page_size`](primitives::config::ValidatorWorkerConfig::page_size),
+    /// capped (and defaulting) to Sentry's
+    /// [`Config.limits.channels_find`](primitives::config::Limits::channels_find).
+    pub fn channels_stream(&self) -> impl Stream<Item = Result<Channel, Error>> + '_ {
+        paginated(move |page| {
+            self.get_channels_page(page)
+                .map_ok(|response| (response.channels, response.pagination))
+        })
+    }
+
+    /// Fetches all the [`Channel`]s of which the _Who am I_ validator is the guardian,
+    /// see [`SentryApi::get_guardian_channels_page`] and [`paginated`].
+    pub fn guardian_channels_stream(&self) -> impl Stream<Item = Result<Channel, Error>> + '_ {
+        paginated(move |page| {
+            self.get_guardian_channels_page(page)
+                .map_ok(|response| (response.channels, response.pagination))
+        })
+    }
+
+    /// Get the total deposited and spent amounts of a `spender` in a single call,
+    /// see [`SpenderResponse`].
+    pub async fn get_spender_summary(
+        &self,
+        channel_context: &ChainOf<Channel>,
+        spender: Address,
+    ) -> Result<Spender, Error> {
+        let url = self
+            .sentry_url
+            .join(&format!(
+                "v5/channel/{}/spender/{}",
+                channel_context.context.id(),
+                spender
+            ))
+            .expect("Should not error when creating endpoint");
+
+        let auth_token = self
+            .adapter
+            .get_auth(channel_context.chain.chain_id, self.adapter.whoami())
+            .map_err(|_adapter_err| Error::AuthenticationToken {
+                for_chain: channel_context.chain.chain_id,
+            })?;
+
+        let response = self
+            .get_with_retry(url, Some(&auth_token))
+            .await?
+            .json::<SpenderResponse>()
+            .map_err(Error::Request)
             .await?;
 
-            let result_all: HashMap<Address, Spender> = std::iter::once(first_page)
-                .chain(all.into_iter())
-                .flat_map(|p| p.spenders)
-                .collect();
+        Ok(response.spender)
+    }
 
-            Ok(result_all)
-        }
+    /// Gets the [`Campaign`]s with the given ids in a single call, unknown Campaigns are skipped,
+    /// see [`CampaignsBatchRequest`].
+    ///
+    /// At most Sentry's
+    /// [`Config.limits.campaigns_find`](primitives::config::Limits::campaigns_find)
+    /// Campaigns can be requested at once.
+    pub async fn get_campaigns_batch(
+        &self,
+        campaigns: &[CampaignId],
+    ) -> Result<Vec<Campaign>, Error> {
+        let url = self
+            .sentry_url
+            .join("v5/campaign/batch")
+            .expect("Should not error when creating endpoint");
+
+        let request = CampaignsBatchRequest {
+            campaigns: campaigns.to_vec(),
+        };
+
+        let response = self
+            .client
+            .post(url)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<CampaignsBatchResponse>()
+            .map_err(Error::Request)
+            .await?;
+
+        Ok(response
+            .campaigns
+            .into_iter()
+            .map(|response| response.campaign)
+            .collect())
     }
 
     /// Get the accounting from Sentry
@@ -329,7 +737,7 @@ impl<C: Unlocked + 'static, P> SentryApi<C, P> {
                 for_chain: channel_context.chain.chain_id,
             })?;
 
-        let response = self.client.get(url).bearer_auth(auth_token).send().await?;
+        let response = self.get_with_retry(url, Some(&auth_token)).await?;
 
         assert_eq!(reqwest::StatusCode::OK, response.status());
 
@@ -339,47 +747,275 @@ impl<C: Unlocked + 'static, P> SentryApi<C, P> {
             .await
     }
 
-    /// Fetches all `Campaign`s from the _Who am I_ Sentry.
-    /// It builds the `Channel`s to be processed alongside all the `Validator`s' url & auth token.
-    pub async fn collect_channels(
+    /// Get the _Who am I_ [`ValidatorFeesSummary`]s from Sentry, see [`FeesSummaryQuery`]
+    pub async fn get_fees_summary(
         &self,
-    ) -> Result<(HashSet<ChainOf<Channel>>, ChainsValidators), Error> {
-        let client = reqwest::Client::builder()
-            .timeout(self.config.worker.timeouts.all_campaigns)
-            .build()?;
-
-        let campaigns =
-            campaigns::all_campaigns(client, &self.sentry_url, Some(self.adapter.whoami())).await?;
+        query: &FeesSummaryQuery,
+    ) -> Result<FeesSummaryResponse, Error> {
+        let url = self
+            .sentry_url
+            .join(&format!(
+                "v5/validator/{}/fees-summary?{}",
+                self.adapter.whoami(),
+                serde_qs::to_string(query).expect("Should serialize the fees summary query")
+            ))
+            .expect("Should not error when creating endpoint");
 
-        let (validators, channels) = campaigns.into_iter().fold(
-            (ChainsValidators::new(), HashSet::<ChainOf<Channel>>::new()),
-            |(mut validators, mut channels), campaign| {
-                let channel_context = match self.config.find_chain_of(campaign.channel.token) {
-                    Some(chain_of) => chain_of.with_channel(campaign.channel),
-                    // Skip the current Channel as the Chain/Token is not configured
-                    None => return (validators, channels),
-                };
+        self.get_with_retry(url, None)
+            .await?
+            .json()
+            .map_err(Error::Request)
+            .await
+    }
 
-                // prepare to populate the chain of the Campaign validators
-                let chain_validators = validators
-                    .entry(channel_context.chain.chain_id)
-                    .or_default();
+    /// Creates (or updates) a _Who am I_ [`ValidatorFeesSummary`] in Sentry
+    pub async fn create_fees_summary(
+        &self,
+        channel_context: &ChainOf<Channel>,
+        request: &FeesSummaryRequest,
+    ) -> Result<ValidatorFeesSummary, Error> {
+        let url = self
+            .sentry_url
+            .join(&format!(
+                "v5/validator/{}/fees-summary",
+                self.adapter.whoami()
+            ))
+            .expect("Should not error when creating endpoint");
 
-                for validator_desc in campaign.validators.iter() {
-                    // if Validator is already there, we can just skip it
-                    // remember, the campaigns are ordered by `created DESC`
-                    // so we will always get the latest Validator url first
-                    match chain_validators.entry(validator_desc.id) {
-                        Entry::Occupied(_) => continue,
-                        Entry::Vacant(entry) => {
-                            // try to parse the url of the Validator Desc
-                            let validator_url = validator_desc.url.parse::<ApiUrl>();
-                            // and also try to find the Auth token in the config
+        let auth_token = self
+            .adapter
+            .get_auth(channel_context.chain.chain_id, self.adapter.whoami())
+            .map_err(|_adapter_err| Error::AuthenticationToken {
+                for_chain: channel_context.chain.chain_id,
+            })?;
 
-                            // if there was an error with any of the operations, skip this `ValidatorDesc`
-                            let auth_token = self
-                                .adapter
-                                .get_auth(channel_context.chain.chain_id, validator_desc.id);
+        self.client
+            .post(url)
+            .bearer_auth(&auth_token)
+            .json(request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .map_err(Error::Request)
+            .await
+    }
+
+    /// Quarantines the `NewState` (and its `ApproveState`s) with the given `state_root`
+    /// in our own Sentry after its balances failed to check, see [`QuarantineStateRequest`].
+    pub async fn quarantine_state(
+        &self,
+        channel_context: &ChainOf<Channel>,
+        state_root: &str,
+    ) -> Result<SuccessResponse, Error> {
+        let url = self
+            .sentry_url
+            .join(&format!(
+                "v5/channel/{}/quarantine",
+                channel_context.context.id()
+            ))
+            .expect("Should not error when creating endpoint");
+
+        let auth_token = self
+            .adapter
+            .get_auth(channel_context.chain.chain_id, self.adapter.whoami())
+            .map_err(|_adapter_err| Error::AuthenticationToken {
+                for_chain: channel_context.chain.chain_id,
+            })?;
+
+        let request = QuarantineStateRequest {
+            state_root: state_root.to_string(),
+        };
+
+        self.client
+            .post(url)
+            .bearer_auth(&auth_token)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .map_err(Error::Request)
+            .await
+    }
+
+    /// Checks the health of a (foreign) validator's Sentry using its `/health` route.
+    ///
+    /// The request uses the shorter
+    /// [`Config.worker.timeouts.health_check`](primitives::config::Timeouts::health_check)
+    /// timeout, so an unresponsive validator can be skipped before propagating messages to it.
+    ///
+    /// Returns `false` if the Sentry responds with a non-success status code
+    /// and an [`Error::Request`] if the request itself fails (e.g. timeout).
+    pub async fn check_validator_health(&self, url: &ApiUrl) -> Result<bool, Error> {
+        let endpoint = url
+            .join("health")
+            .expect("Should not error when creating endpoint url");
+
+        let response = self
+            .client
+            .get(endpoint)
+            .timeout(self.config.worker.timeouts.health_check)
+            .send()
+            .await?;
+
+        let is_healthy = response.status().is_success();
+        if !is_healthy {
+            warn!(&self.logger, "Validator is unhealthy"; "url" => %url, "status" => %response.status());
+        }
+
+        Ok(is_healthy)
+    }
+
+    /// Makes sure that the Sentry of another validator is healthy
+    /// using [`SentryApi::check_validator_health`].
+    ///
+    /// Does nothing for the _Who am I_ validator.
+    pub async fn ensure_healthy_peer(
+        &self,
+        validator_id: ValidatorId,
+        validator: &Validator,
+    ) -> Result<(), Error> {
+        if validator_id == self.adapter.whoami() {
+            return Ok(());
+        }
+
+        if self.check_validator_health(&validator.url).await? {
+            Ok(())
+        } else {
+            Err(Error::ValidatorUnhealthy {
+                validator: validator_id,
+                url: validator.url.clone(),
+            })
+        }
+    }
+
+    /// Verifies that the Sentry at `url` speaks for the `validator`.
+    ///
+    /// A random challenge is sent to `GET /v5/whoami` and the returned signature
+    /// of its [`challenge_message()`] is verified against the `validator` using the [`Adapter`].
+    pub async fn verify_peer(&self, validator: ValidatorId, url: &ApiUrl) -> Result<(), Error> {
+        let endpoint = url
+            .join("v5/whoami")
+            .expect("Should not error when creating endpoint url");
+
+        let query = WhoamiQuery {
+            challenge: hex::encode(rand::random::<[u8; 32]>()),
+        };
+
+        let response: WhoamiResponse = self
+            .client
+            .get(endpoint)
+            .timeout(self.config.worker.timeouts.propagation)
+            .query(&query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if response.validator_id != validator {
+            return Err(Error::PeerMismatch {
+                expected: validator,
+                responded: response.validator_id,
+                url: Box::new(url.clone()),
+            });
+        }
+
+        let is_verified = self
+            .adapter
+            .verify(
+                validator,
+                &challenge_message(&query.challenge),
+                &response.signature,
+            )
+            .unwrap_or(false);
+
+        if !is_verified {
+            return Err(Error::PeerNotVerified {
+                validator,
+                url: url.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Makes sure that the Sentry of another validator has been verified using
+    /// [`SentryApi::verify_peer`] in the last
+    /// [`Config.worker.peer_verification_ttl`](primitives::config::ValidatorWorkerConfig::peer_verification_ttl).
+    ///
+    /// Does nothing for the _Who am I_ validator or if the TTL is not configured.
+    pub async fn ensure_verified_peer(
+        &self,
+        validator_id: ValidatorId,
+        validator: &Validator,
+    ) -> Result<(), Error> {
+        let ttl = match self.config.worker.peer_verification_ttl {
+            Some(ttl) if validator_id != self.adapter.whoami() => ttl,
+            _ => return Ok(()),
+        };
+
+        let is_cached = self
+            .verified_peers
+            .lock()
+            .expect("Verified peers mutex poisoned")
+            .get(&validator_id)
+            .is_some_and(|(url, verified_at)| url == &validator.url && verified_at.elapsed() < ttl);
+
+        if !is_cached {
+            self.verify_peer(validator_id, &validator.url).await?;
+
+            self.verified_peers
+                .lock()
+                .expect("Verified peers mutex poisoned")
+                .insert(validator_id, (validator.url.clone(), Instant::now()));
+        }
+
+        Ok(())
+    }
+
+    /// Fetches all `Campaign`s from the _Who am I_ Sentry.
+    /// It builds the `Channel`s to be processed alongside all the `Validator`s' url & auth token.
+    pub async fn collect_channels(
+        &self,
+    ) -> Result<(HashSet<ChainOf<Channel>>, ChainsValidators), Error> {
+        let client = reqwest::Client::builder()
+            .timeout(self.config.worker.timeouts.all_campaigns)
+            .build()?;
+
+        let campaigns =
+            campaigns::all_campaigns(client, &self.sentry_url, Some(self.adapter.whoami())).await?;
+
+        let (validators, channels) = campaigns.into_iter().fold(
+            (ChainsValidators::new(), HashSet::<ChainOf<Channel>>::new()),
+            |(mut validators, mut channels), campaign| {
+                let channel_context = match self.config.find_chain_of(campaign.channel.token) {
+                    Some(chain_of) => chain_of.with_channel(campaign.channel),
+                    // Skip the current Channel as the Chain/Token is not configured
+                    None => return (validators, channels),
+                };
+
+                // prepare to populate the chain of the Campaign validators
+                let chain_validators = validators
+                    .entry(channel_context.chain.chain_id)
+                    .or_default();
+
+                for validator_desc in campaign.validators.iter() {
+                    // if Validator is already there, we can just skip it
+                    // remember, the campaigns are ordered by `created DESC`
+                    // so we will always get the latest Validator url first
+                    match chain_validators.entry(validator_desc.id) {
+                        Entry::Occupied(_) => continue,
+                        Entry::Vacant(entry) => {
+                            // try to parse the url of the Validator Desc
+                            let validator_url = validator_desc.url.parse::<ApiUrl>();
+                            // and also try to find the Auth token in the config
+
+                            // if there was an error with any of the operations, skip this `ValidatorDesc`
+                            let auth_token = self
+                                .adapter
+                                .get_auth(channel_context.chain.chain_id, validator_desc.id);
 
                             // only if `ApiUrl` parsing is `Ok` & Auth Token is found in the `Adapter`
                             if let (Ok(url), Ok(auth_token)) = (validator_url, auth_token) {
@@ -404,6 +1040,67 @@ impl<C: Unlocked + 'static, P> SentryApi<C, P> {
 
         Ok((channels, validators))
     }
+
+    /// Fetches the [`Channel`]s of which the _Who am I_ validator is the guardian
+    /// but neither the leader nor the follower, for the
+    /// [`Config.worker.guardian_monitoring`](primitives::config::ValidatorWorkerConfig::guardian_monitoring).
+    ///
+    /// The Channels with a token which is not configured are skipped.
+    pub async fn collect_guardian_channels(&self) -> Result<HashSet<ChainOf<Channel>>, Error> {
+        let whoami = self.adapter.whoami();
+
+        self.guardian_channels_stream()
+            .try_filter_map(|channel| async move {
+                if channel.find_validator(whoami).is_some() {
+                    return Ok(None);
+                }
+
+                Ok(self
+                    .config
+                    .find_chain_of(channel.token)
+                    .map(|chain_of| chain_of.with_channel(channel)))
+            })
+            .try_collect()
+            .await
+    }
+}
+
+/// Creates a [`Stream`] which fetches the first page (`0`) and then the rest of the pages
+/// (up to [`Pagination::total_pages`]) concurrently, at most [`PAGES_CONCURRENCY`] at a time,
+/// and yields the items of each page in the order of the pages.
+///
+/// The stream ends right after yielding the first error.
+fn paginated<'a, I, T, E, F, Fut>(fetch_page: F) -> impl Stream<Item = Result<T, E>> + 'a
+where
+    I: IntoIterator<Item = T> + 'a,
+    T: 'a,
+    E: 'a,
+    F: Fn(u64) -> Fut + Clone + 'a,
+    Fut: Future<Output = Result<(I, Pagination), E>> + 'a,
+{
+    let first_page = fetch_page(0);
+
+    stream::once(first_page)
+        .map_ok(move |(items, pagination)| {
+            let fetch_page = fetch_page.clone();
+            let next_pages = stream::iter(1..pagination.total_pages)
+                .map(fetch_page)
+                .buffered(PAGES_CONCURRENCY)
+                .map_ok(|(items, _pagination)| items);
+
+            stream::once(future::ready(Ok(items))).chain(next_pages)
+        })
+        .try_flatten()
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten()
+        .scan(false, |failed, result| {
+            if *failed {
+                return future::ready(None);
+            }
+            *failed = result.is_err();
+
+            future::ready(Some(result))
+        })
 }
 
 pub fn assert_result<E>(assert: bool, or_error: E) -> Result<(), E> {
@@ -415,6 +1112,11 @@ pub fn assert_result<E>(assert: bool, or_error: E) -> Result<(), E> {
 }
 
 impl<C: Unlocked + 'static> SentryApi<C> {
+    /// Propagates the `messages` to the Sentries of both Channel validators.
+    ///
+    /// The messages are not propagated to (and are dead lettered for) the other validator
+    /// if its Sentry is unhealthy, see [`SentryApi::ensure_healthy_peer`],
+    /// or could not be verified, see [`SentryApi::ensure_verified_peer`].
     pub async fn propagate(
         &self,
         channel_context: &ChainOf<Channel>,
@@ -451,7 +1153,16 @@ impl<C: Unlocked + 'static> SentryApi<C> {
         }
 
         let propagation_results = join_all(propagate_to_validators.iter().map(
-            |(validator_id, validator)| {
+            |(validator_id, validator)| async move {
+                // skip the unresponsive validators early, using the shorter health check timeout
+                self.ensure_healthy_peer(*validator_id, validator)
+                    .await
+                    .map_err(|err| (*validator_id, err))?;
+
+                self.ensure_verified_peer(*validator_id, validator)
+                    .await
+                    .map_err(|err| (*validator_id, err))?;
+
                 propagate_to::<C>(
                     &self.client,
                     self.config.worker.timeouts.propagation,
@@ -459,12 +1170,45 @@ impl<C: Unlocked + 'static> SentryApi<C> {
                     (*validator_id, validator),
                     messages,
                 )
+                .await
             },
         ))
         .await;
 
+        for (validator_id, err) in propagation_results
+            .iter()
+            .filter_map(|result| result.as_ref().err())
+        {
+            self.dead_letter(channel_context.context.id(), messages, *validator_id, err);
+        }
+
+        debug::propagation(messages, &propagation_results);
+        tick_report::messages(messages);
+
         Ok(propagation_results)
     }
+
+    /// Logs and records (if [`SentryApi::dead_letters`] is set) the messages
+    /// which were not delivered to the validator.
+    fn dead_letter(
+        &self,
+        channel: ChannelId,
+        messages: &[MessageTypes],
+        validator: ValidatorId,
+        err: &Error,
+    ) {
+        for message in messages {
+            let dead_letter = DeadLetter::new(channel, message, validator, err.to_string());
+
+            error!(&self.logger, "Failed to propagate validator message"; "dead_letter" => ?dead_letter);
+
+            if let Some(dead_letters) = &self.dead_letters {
+                if let Err(record_err) = dead_letters.record(&dead_letter) {
+                    error!(&self.logger, "Failed to record dead-letter"; "error" => ?record_err, "path" => ?dead_letters.path());
+                }
+            }
+        }
+    }
 }
 
 async fn propagate_to<C: Unlocked>(
@@ -499,7 +1243,7 @@ async fn propagate_to<C: Unlocked>(
 }
 
 pub mod channels {
-    use futures::{future::try_join_all, TryFutureExt};
+    use futures::{TryFutureExt, TryStreamExt};
     use primitives::{
         sentry::channel_list::{ChannelListQuery, ChannelListResponse},
         util::ApiUrl,
@@ -512,23 +1256,12 @@ pub mod channels {
         sentry_url: &ApiUrl,
         whoami: ValidatorId,
     ) -> Result<Vec<Channel>, reqwest::Error> {
-        let first_page = fetch_page(&client, sentry_url, 0, whoami).await?;
-
-        if first_page.pagination.total_pages < 2 {
-            Ok(first_page.channels)
-        } else {
-            let all: Vec<ChannelListResponse> = try_join_all(
-                (1..first_page.pagination.total_pages)
-                    .map(|i| fetch_page(&client, sentry_url, i, whoami)),
-            )
-            .await?;
-
-            let result_all: Vec<Channel> = std::iter::once(first_page)
-                .chain(all.into_iter())
-                .flat_map(|ch| ch.channels.into_iter())
-                .collect();
-            Ok(result_all)
-        }
+        super::paginated(|page| {
+            fetch_page(&client, sentry_url, page, whoami)
+                .map_ok(|response| (response.channels, response.pagination))
+        })
+        .try_collect()
+        .await
     }
 
     async fn fetch_page(
@@ -540,7 +1273,9 @@ pub mod channels {
         let query = ChannelListQuery {
             page,
             validator: Some(validator),
+            guardian: None,
             chains: vec![],
+            limit: None,
         };
 
         let endpoint = sentry_url
@@ -560,7 +1295,7 @@ pub mod channels {
 
 pub mod campaigns {
     use chrono::Utc;
-    use futures::future::try_join_all;
+    use futures::{TryFutureExt, TryStreamExt};
     use primitives::{
         sentry::campaign_list::{CampaignListQuery, CampaignListResponse, ValidatorParam},
         util::ApiUrl,
@@ -576,23 +1311,12 @@ pub mod campaigns {
         sentry_url: &ApiUrl,
         for_validator: Option<ValidatorId>,
     ) -> Result<Vec<Campaign>, reqwest::Error> {
-        let first_page = fetch_page(&client, sentry_url, 0, for_validator).await?;
-
-        if first_page.pagination.total_pages < 2 {
-            Ok(first_page.campaigns)
-        } else {
-            let all = try_join_all(
-                (1..first_page.pagination.total_pages)
-                    .map(|i| fetch_page(&client, sentry_url, i, for_validator)),
-            )
-            .await?;
-
-            let result_all = std::iter::once(first_page)
-                .chain(all.into_iter())
-                .flat_map(|response| response.campaigns.into_iter())
-                .collect();
-            Ok(result_all)
-        }
+        super::paginated(|page| {
+            fetch_page(&client, sentry_url, page, for_validator)
+                .map_ok(|response| (response.campaigns, response.pagination))
+        })
+        .try_collect()
+        .await
     }
 
     async fn fetch_page(
@@ -626,22 +1350,29 @@ mod test {
         dummy::{Adapter, Dummy, Options},
         ethereum::test_util::GANACHE_INFO_1,
     };
+    use chrono::TimeZone;
     use primitives::{
         campaign::validators::Validators as CampaignValidators,
+        channel::Nonce,
         config::{configuration, Environment, GANACHE_CONFIG},
         sentry::{
-            campaign_list::CampaignListResponse, channel_list::ChannelListResponse, Pagination,
+            campaign_list::CampaignListResponse,
+            channel_list::ChannelListResponse,
+            message::{Message, MessageResponse},
+            validator_messages::MessagesCursor,
+            LastApproved, Pagination,
         },
         test_util::{
             discard_logger, ADVERTISER, ADVERTISER_2, CREATOR, DUMMY_AUTH, DUMMY_CAMPAIGN,
             DUMMY_VALIDATOR_FOLLOWER, DUMMY_VALIDATOR_LEADER, FOLLOWER, GUARDIAN, IDS, LEADER,
             LEADER_2, PUBLISHER, PUBLISHER_2,
         },
+        validator::{ApproveState, Heartbeat, MessageTypes},
         CampaignId, UnifiedNum, ValidatorDesc,
     };
     use std::str::FromStr;
     use wiremock::{
-        matchers::{method, path, query_param},
+        matchers::{body_json, method, path, query_param, query_param_is_missing},
         Mock, MockServer, ResponseTemplate,
     };
 
@@ -683,6 +1414,7 @@ mod test {
             dummy_identity: whoami,
             dummy_auth_tokens: DUMMY_AUTH.clone(),
             dummy_chains: config.chains.values().cloned().collect(),
+            failure_injection: None,
         }));
         let logger = discard_logger();
 
@@ -798,6 +1530,7 @@ mod test {
             dummy_identity: IDS[&LEADER],
             dummy_auth_tokens: DUMMY_AUTH.clone(),
             dummy_chains: config.chains.values().cloned().collect(),
+            failure_injection: None,
         }));
         let logger = discard_logger();
 
@@ -834,6 +1567,938 @@ mod test {
         assert_eq!(res.len(), 0)
     }
 
+    #[tokio::test]
+    async fn test_channels_stream_yields_all_pages() {
+        let server = MockServer::start().await;
+
+        let channel = |nonce: u32| {
+            let mut channel = DUMMY_CAMPAIGN.channel;
+            channel.nonce = Nonce::from(nonce);
+            channel
+        };
+        let pages = [
+            vec![channel(1), channel(2)],
+            vec![channel(3), channel(4)],
+            vec![channel(5)],
+        ];
+
+        for (page, channels) in pages.iter().enumerate() {
+            let response = ChannelListResponse {
+                channels: channels.clone(),
+                pagination: Pagination {
+                    page: page as u64,
+                    total_pages: pages.len() as u64,
+                },
+            };
+
+            Mock::given(method("GET"))
+                .and(path("/v5/channel/list"))
+                .and(query_param("page", page.to_string()))
+                .and(query_param("limit", "2"))
+                .and(query_param("validator", IDS[&LEADER].to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(&response))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let mut config = GANACHE_CONFIG.clone();
+        config.worker.page_size = NonZeroU64::new(2);
+        let sentry = setup_dummy_sentry(IDS[&LEADER], config, sentry_url);
+
+        let channels = sentry
+            .channels_stream()
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Should get all channels");
+
+        let expected = pages.concat();
+        assert_eq!(
+            expected, channels,
+            "All channels should be yielded exactly once"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collect_guardian_channels() {
+        let server = MockServer::start().await;
+
+        // only the guardian
+        let guardian_channel = DUMMY_CAMPAIGN.channel;
+        // the guardian is also the follower
+        let follower_channel = Channel {
+            follower: IDS[&GUARDIAN],
+            nonce: Nonce::from(2_u32),
+            ..DUMMY_CAMPAIGN.channel
+        };
+        // the token is not in the config
+        let unknown_token_channel = Channel {
+            token: *PUBLISHER,
+            nonce: Nonce::from(3_u32),
+            ..DUMMY_CAMPAIGN.channel
+        };
+
+        let response = ChannelListResponse {
+            channels: vec![guardian_channel, follower_channel, unknown_token_channel],
+            pagination: Pagination {
+                page: 0,
+                total_pages: 1,
+            },
+        };
+        Mock::given(method("GET"))
+            .and(path("/v5/channel/list"))
+            .and(query_param("guardian", GUARDIAN.to_string()))
+            .and(query_param_is_missing("validator"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&response))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = setup_dummy_sentry(IDS[&GUARDIAN], GANACHE_CONFIG.clone(), sentry_url);
+
+        let channels = sentry
+            .collect_guardian_channels()
+            .await
+            .expect("Should collect the guardian channels");
+
+        let expected = GANACHE_CONFIG
+            .find_chain_of(guardian_channel.token)
+            .expect("Should find channel token in config")
+            .with_channel(guardian_channel);
+        assert_eq!(vec![expected], channels.into_iter().collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_spenders_stream_surfaces_error_mid_stream() {
+        let server = MockServer::start().await;
+        let spender = Spender {
+            total_deposited: UnifiedNum::from(100_000_000),
+            total_spent: None,
+        };
+
+        let first_page_response = AllSpendersResponse {
+            spenders: vec![(*ADVERTISER, spender.clone())].into_iter().collect(),
+            pagination: Pagination {
+                page: 0,
+                total_pages: 3,
+            },
+        };
+        let endpoint = format!("/v5/channel/{}/spender/all", DUMMY_CAMPAIGN.channel.id());
+
+        Mock::given(method("GET"))
+            .and(path(&endpoint))
+            .and(query_param("page", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&first_page_response))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // not a valid `AllSpendersResponse`
+        Mock::given(method("GET"))
+            .and(path(&endpoint))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(400).set_body_string("Bad request"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // the page might be fetched concurrently with the failing one,
+        // but the stream should end after the error
+        Mock::given(method("GET"))
+            .and(path(&endpoint))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&first_page_response))
+            .expect(0..=1)
+            .mount(&server)
+            .await;
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let config = GANACHE_CONFIG.clone();
+        let channel_context = config
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Should find Dummy campaign token in config")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+        let sentry = setup_dummy_sentry(IDS[&LEADER], config, sentry_url);
+
+        let results = sentry
+            .spenders_stream(&channel_context)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(2, results.len());
+        assert_eq!(
+            (*ADVERTISER, spender),
+            *results[0].as_ref().expect("First page should be Ok")
+        );
+        assert!(matches!(results[1], Err(Error::Request(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_spender_summary() {
+        let server = MockServer::start().await;
+        let test_spender = Spender {
+            total_deposited: UnifiedNum::from(100_000_000),
+            total_spent: Some(UnifiedNum::from(35_000_000)),
+        };
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/v5/channel/{}/spender/{}",
+                DUMMY_CAMPAIGN.channel.id(),
+                *ADVERTISER
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&SpenderResponse {
+                spender: test_spender.clone(),
+                deposit_breakdown: None,
+            }))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = setup_dummy_sentry(IDS[&LEADER], GANACHE_CONFIG.clone(), sentry_url);
+
+        let channel_context = GANACHE_CONFIG
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Should find Dummy campaign token in config")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        let spender = sentry
+            .get_spender_summary(&channel_context, *ADVERTISER)
+            .await
+            .expect("Should get spender summary");
+
+        assert_eq!(test_spender, spender);
+    }
+
+    #[tokio::test]
+    async fn test_quarantine_state() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "/v5/channel/{}/quarantine",
+                DUMMY_CAMPAIGN.channel.id()
+            )))
+            .and(body_json(QuarantineStateRequest {
+                state_root: "bad-state-root".to_string(),
+            }))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(&SuccessResponse { success: true }),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = setup_dummy_sentry(IDS[&FOLLOWER], GANACHE_CONFIG.clone(), sentry_url);
+
+        let channel_context = GANACHE_CONFIG
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Should find Dummy campaign token in config")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        let response = sentry
+            .quarantine_state(&channel_context, "bad-state-root")
+            .await
+            .expect("Should quarantine the state");
+
+        assert!(response.success);
+    }
+
+    #[tokio::test]
+    async fn test_get_msgs_since_follows_the_cursor() {
+        let server = MockServer::start().await;
+        let since = Utc.timestamp_millis(1_660_056_328_160);
+        let heartbeat_msg = |minutes| ValidatorMessage {
+            from: DUMMY_CAMPAIGN.channel.leader,
+            received: since + chrono::Duration::minutes(minutes),
+            msg: MessageTypes::Heartbeat(Heartbeat {
+                signature: String::new(),
+                state_root: String::new(),
+                timestamp: since + chrono::Duration::minutes(minutes),
+            }),
+        };
+        let next_cursor = MessagesCursor {
+            received: since + chrono::Duration::minutes(1),
+            id: 2,
+        };
+        let endpoint = format!(
+            "/v5/channel/{}/validator-messages/{}/Heartbeat",
+            DUMMY_CAMPAIGN.channel.id(),
+            DUMMY_CAMPAIGN.channel.leader,
+        );
+
+        Mock::given(method("GET"))
+            .and(path(&endpoint))
+            .and(query_param("since", since.timestamp_millis().to_string()))
+            .and(query_param_is_missing("cursor"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                &ValidatorMessagesListResponse {
+                    messages: vec![heartbeat_msg(0), heartbeat_msg(1)],
+                    next_cursor: Some(next_cursor),
+                },
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(&endpoint))
+            .and(query_param("cursor", next_cursor.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                &ValidatorMessagesListResponse {
+                    messages: vec![heartbeat_msg(2)],
+                    next_cursor: None,
+                },
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = setup_dummy_sentry(IDS[&LEADER], GANACHE_CONFIG.clone(), sentry_url);
+
+        let all_messages = sentry
+            .get_msgs_since(
+                DUMMY_CAMPAIGN.channel.id(),
+                DUMMY_CAMPAIGN.channel.leader,
+                &[MessageType::Heartbeat],
+                since,
+            )
+            .await
+            .expect("Should get all messages");
+
+        assert_eq!(
+            vec![
+                since,
+                since + chrono::Duration::minutes(1),
+                since + chrono::Duration::minutes(2)
+            ],
+            all_messages
+                .into_iter()
+                .map(|message| message.received)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_only_sentry_from_config() {
+        let server = MockServer::start().await;
+        let channel_id = DUMMY_CAMPAIGN.channel.id();
+
+        let expected_response = LastApprovedResponse::<UncheckedState> {
+            last_approved: None,
+            heartbeats: Some(vec![]),
+        };
+
+        Mock::given(method("GET"))
+            .and(path(format!("/v5/channel/{}/last-approved", channel_id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&expected_response))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = SentryApi::from_config(GANACHE_CONFIG.clone(), sentry_url)
+            .expect("Should build read-only sentry");
+
+        let response = sentry
+            .get_last_approved(channel_id)
+            .await
+            .expect("Should get last approved");
+
+        assert_eq!(expected_response, response);
+        assert!(
+            sentry.adapter.sign("state_root").is_err(),
+            "Read-only sentry should not sign"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_last_approved_many() {
+        let server = MockServer::start().await;
+        let channel = |nonce: u32| Channel {
+            nonce: Nonce::from(nonce),
+            ..DUMMY_CAMPAIGN.channel
+        };
+        let (brand_new, approved, failing) = (channel(1).id(), channel(2).id(), channel(3).id());
+
+        let brand_new_response = LastApprovedResponse::<UncheckedState> {
+            last_approved: None,
+            heartbeats: Some(vec![]),
+        };
+        let approved_response = LastApprovedResponse::<UncheckedState> {
+            last_approved: Some(LastApproved {
+                new_state: None,
+                approve_state: Some(MessageResponse {
+                    from: IDS[&FOLLOWER],
+                    received: Utc.ymd(2022, 6, 1).and_hms(12, 0, 0),
+                    msg: Message::new(ApproveState {
+                        state_root: "state_root".to_string(),
+                        signature: "signature".to_string(),
+                        is_healthy: true,
+                    }),
+                }),
+            }),
+            heartbeats: Some(vec![]),
+        };
+
+        for (channel_id, response) in [
+            (brand_new, &brand_new_response),
+            (approved, &approved_response),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("/v5/channel/{}/last-approved", channel_id)))
+                .and(query_param("withHeartbeat", "true"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(response))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        Mock::given(method("GET"))
+            .and(path(format!("/v5/channel/{}/last-approved", failing)))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = setup_dummy_sentry(IDS[&LEADER], GANACHE_CONFIG.clone(), sentry_url);
+
+        let mut results = sentry
+            .get_last_approved_many(&[brand_new, approved, failing])
+            .await;
+
+        assert_eq!(3, results.len());
+        assert_eq!(
+            brand_new_response,
+            results
+                .remove(&brand_new)
+                .expect("Should have a result")
+                .expect("Should get last approved")
+        );
+        assert_eq!(
+            approved_response,
+            results
+                .remove(&approved)
+                .expect("Should have a result")
+                .expect("Should get last approved")
+        );
+        assert!(matches!(
+            results.remove(&failing),
+            Some(Err(Error::Request(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_retries_on_server_error() {
+        let server = MockServer::start().await;
+        let channel_id = DUMMY_CAMPAIGN.channel.id();
+        let last_approved_path = format!("/v5/channel/{}/last-approved", channel_id);
+
+        let expected_response = LastApprovedResponse::<UncheckedState> {
+            last_approved: None,
+            heartbeats: Some(vec![]),
+        };
+
+        // the first request fails
+        Mock::given(method("GET"))
+            .and(path(&last_approved_path))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // and the next one succeeds
+        Mock::given(method("GET"))
+            .and(path(&last_approved_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&expected_response))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = setup_dummy_sentry(IDS[&LEADER], GANACHE_CONFIG.clone(), sentry_url);
+
+        let response = sentry
+            .get_last_approved(channel_id)
+            .await
+            .expect("Should succeed after retrying");
+
+        assert_eq!(expected_response, response);
+    }
+
+    #[tokio::test]
+    async fn test_check_validator_health() {
+        let server = MockServer::start().await;
+        let healthy_url = ApiUrl::from_str(&format!("{}/healthy", server.uri())).unwrap();
+        let unhealthy_url = ApiUrl::from_str(&format!("{}/unhealthy", server.uri())).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/healthy/health"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(&SuccessResponse { success: true }),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/unhealthy/health"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = setup_dummy_sentry(IDS[&LEADER], GANACHE_CONFIG.clone(), sentry_url);
+
+        assert!(sentry
+            .check_validator_health(&healthy_url)
+            .await
+            .expect("Should check health"));
+        assert!(!sentry
+            .check_validator_health(&unhealthy_url)
+            .await
+            .expect("Should check health"));
+    }
+
+    #[tokio::test]
+    async fn test_check_validator_health_timeout() {
+        let server = MockServer::start().await;
+
+        let mut config = GANACHE_CONFIG.clone();
+        config.worker.timeouts.health_check = Duration::from_millis(50);
+
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+            .mount(&server)
+            .await;
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = setup_dummy_sentry(IDS[&LEADER], config, sentry_url.clone());
+
+        let err = sentry
+            .check_validator_health(&sentry_url)
+            .await
+            .expect_err("Should time out");
+
+        assert!(matches!(err, Error::Request(err) if err.is_timeout()));
+    }
+
+    #[tokio::test]
+    async fn test_get_retries_exhausted() {
+        let server = MockServer::start().await;
+        let channel_id = DUMMY_CAMPAIGN.channel.id();
+
+        let mut config = GANACHE_CONFIG.clone();
+        config.worker.retry.attempts = 2.try_into().unwrap();
+
+        Mock::given(method("GET"))
+            .and(path(format!("/v5/channel/{}/last-approved", channel_id)))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = setup_dummy_sentry(IDS[&LEADER], config, sentry_url);
+
+        let err = sentry
+            .get_last_approved(channel_id)
+            .await
+            .expect_err("Should fail after all attempts");
+
+        match err {
+            Error::Request(err) => {
+                assert_eq!(Some(reqwest::StatusCode::SERVICE_UNAVAILABLE), err.status())
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_propagation_is_dead_lettered() {
+        use crate::dead_letter::DeadLetterLog;
+        use primitives::validator::Heartbeat;
+
+        let server = MockServer::start().await;
+        let channel_context = GANACHE_CONFIG
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Should find Dummy campaign token in config")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        // the Follower Sentry always fails
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "/follower/v5/channel/{}/validator-messages",
+                DUMMY_CAMPAIGN.channel.id()
+            )))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "/leader/v5/channel/{}/validator-messages",
+                DUMMY_CAMPAIGN.channel.id()
+            )))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(&SuccessResponse { success: true }),
+            )
+            .mount(&server)
+            .await;
+
+        let validator = |prefix: &str, validator: &Address| Validator {
+            url: ApiUrl::from_str(&format!("{}/{}", server.uri(), prefix)).expect("Should parse"),
+            token: DUMMY_AUTH[validator].clone(),
+        };
+        let propagate_to = [(
+            channel_context.chain.chain_id,
+            [
+                (IDS[&LEADER], validator("leader", &LEADER)),
+                (IDS[&FOLLOWER], validator("follower", &FOLLOWER)),
+            ]
+            .into_iter()
+            .collect(),
+        )]
+        .into_iter()
+        .collect();
+
+        let dir = tempfile::tempdir().expect("Should create temp dir");
+        let dead_letters = DeadLetterLog::new(dir.path().join("dead-letters.jsonl"));
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = setup_dummy_sentry(IDS[&LEADER], GANACHE_CONFIG.clone(), sentry_url)
+            .with_dead_letters(dead_letters.clone())
+            .with_propagate(propagate_to)
+            .expect("Should set propagation validators");
+
+        let heartbeat = MessageTypes::Heartbeat(Heartbeat::new(
+            "signature".to_string(),
+            "state root".to_string(),
+        ));
+
+        let results = sentry
+            .propagate(&channel_context, &[heartbeat])
+            .await
+            .expect("Should propagate");
+
+        assert_eq!(1, results.iter().filter(|result| result.is_err()).count());
+
+        let recorded = dead_letters.recent(10).expect("Should list dead-letters");
+        assert_eq!(1, recorded.len());
+
+        let dead_letter = &recorded[0];
+        assert_eq!(DUMMY_CAMPAIGN.channel.id(), dead_letter.channel);
+        assert_eq!(MessageType::Heartbeat, dead_letter.message_type);
+        assert_eq!("state root", dead_letter.state_root);
+        assert_eq!(IDS[&FOLLOWER], dead_letter.validator);
+    }
+
+    /// Responds to `GET /v5/whoami` as `validator_id`,
+    /// signing the challenge with the [`Dummy`] adapter of `signer`.
+    struct WhoamiResponder {
+        validator_id: ValidatorId,
+        signer: Adapter<UnlockedState>,
+    }
+
+    impl WhoamiResponder {
+        fn new(validator_id: ValidatorId, signer: ValidatorId) -> Self {
+            let signer = Adapter::with_unlocked(Dummy::init(Options {
+                dummy_identity: signer,
+                dummy_auth_tokens: DUMMY_AUTH.clone(),
+                dummy_chains: GANACHE_CONFIG.chains.values().cloned().collect(),
+                failure_injection: None,
+            }));
+
+            Self {
+                validator_id,
+                signer,
+            }
+        }
+    }
+
+    impl wiremock::Respond for WhoamiResponder {
+        fn respond(&self, request: &wiremock::Request) -> ResponseTemplate {
+            let challenge = request
+                .url
+                .query_pairs()
+                .find_map(|(key, value)| (key == "challenge").then(|| value.into_owned()))
+                .expect("Should have a challenge");
+
+            let response = WhoamiResponse {
+                validator_id: self.validator_id,
+                signature: self
+                    .signer
+                    .sign(&challenge_message(&challenge))
+                    .expect("Should sign"),
+            };
+
+            ResponseTemplate::new(200).set_body_json(&response)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_peer() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/follower/v5/whoami"))
+            .respond_with(WhoamiResponder::new(IDS[&FOLLOWER], IDS[&FOLLOWER]))
+            .mount(&server)
+            .await;
+
+        // claims to be the Follower, but the challenge is signed by another validator
+        Mock::given(method("GET"))
+            .and(path("/impostor/v5/whoami"))
+            .respond_with(WhoamiResponder::new(IDS[&FOLLOWER], IDS[&LEADER_2]))
+            .mount(&server)
+            .await;
+
+        let url = |prefix: &str| {
+            ApiUrl::from_str(&format!("{}/{}", server.uri(), prefix)).expect("Should parse")
+        };
+        let sentry = setup_dummy_sentry(IDS[&LEADER], GANACHE_CONFIG.clone(), url(""));
+
+        sentry
+            .verify_peer(IDS[&FOLLOWER], &url("follower"))
+            .await
+            .expect("Should verify the Follower Sentry");
+
+        let impostor = sentry
+            .verify_peer(IDS[&FOLLOWER], &url("impostor"))
+            .await
+            .expect_err("Should not verify the impostor");
+        assert!(
+            matches!(impostor, Error::PeerNotVerified { validator, .. } if validator == IDS[&FOLLOWER])
+        );
+
+        let mismatch = sentry
+            .verify_peer(IDS[&LEADER_2], &url("follower"))
+            .await
+            .expect_err("Should not verify a Sentry of another validator");
+        assert!(matches!(
+            mismatch,
+            Error::PeerMismatch { expected, responded, .. } if expected == IDS[&LEADER_2] && responded == IDS[&FOLLOWER]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_propagate_only_to_verified_peers() {
+        let server = MockServer::start().await;
+        let channel_context = GANACHE_CONFIG
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Should find Dummy campaign token in config")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        let mut config = GANACHE_CONFIG.clone();
+        config.worker.peer_verification_ttl = Some(Duration::from_secs(60));
+
+        // verified only once, the second propagation uses the cached verification
+        Mock::given(method("GET"))
+            .and(path("/follower/v5/whoami"))
+            .respond_with(WhoamiResponder::new(IDS[&FOLLOWER], IDS[&FOLLOWER]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/impostor/v5/whoami"))
+            .respond_with(WhoamiResponder::new(IDS[&FOLLOWER], IDS[&LEADER_2]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // the _Who am I_ Sentry is never challenged
+        Mock::given(method("GET"))
+            .and(path("/leader/v5/whoami"))
+            .respond_with(WhoamiResponder::new(IDS[&LEADER], IDS[&LEADER]))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        for prefix in ["follower", "impostor"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/{prefix}/health")))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(&server)
+                .await;
+        }
+
+        for (prefix, expected) in [("leader", 3), ("follower", 2), ("impostor", 0)] {
+            Mock::given(method("POST"))
+                .and(path(format!(
+                    "/{}/v5/channel/{}/validator-messages",
+                    prefix,
+                    DUMMY_CAMPAIGN.channel.id()
+                )))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(&SuccessResponse { success: true }),
+                )
+                .expect(expected)
+                .mount(&server)
+                .await;
+        }
+
+        let validator = |prefix: &str, validator: &Address| Validator {
+            url: ApiUrl::from_str(&format!("{}/{}", server.uri(), prefix)).expect("Should parse"),
+            token: DUMMY_AUTH[validator].clone(),
+        };
+        let propagate_to = |follower_prefix: &str| {
+            [(
+                channel_context.chain.chain_id,
+                [
+                    (IDS[&LEADER], validator("leader", &LEADER)),
+                    (IDS[&FOLLOWER], validator(follower_prefix, &FOLLOWER)),
+                ]
+                .into_iter()
+                .collect(),
+            )]
+            .into_iter()
+            .collect()
+        };
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = setup_dummy_sentry(IDS[&LEADER], config, sentry_url);
+
+        let heartbeat = MessageTypes::Heartbeat(Heartbeat::new(
+            "signature".to_string(),
+            "state root".to_string(),
+        ));
+
+        for _ in 0..2 {
+            let results = sentry
+                .clone()
+                .with_propagate(propagate_to("follower"))
+                .expect("Should set propagation validators")
+                .propagate(&channel_context, std::slice::from_ref(&heartbeat))
+                .await
+                .expect("Should propagate");
+
+            assert!(results.iter().all(|result| result.is_ok()));
+        }
+
+        // a different url for the Follower should be verified again
+        let results = sentry
+            .with_propagate(propagate_to("impostor"))
+            .expect("Should set propagation validators")
+            .propagate(&channel_context, &[heartbeat])
+            .await
+            .expect("Should propagate");
+
+        assert!(results.iter().any(|result| matches!(
+            result,
+            Err((validator, Error::PeerNotVerified { .. })) if *validator == IDS[&FOLLOWER]
+        )));
+        assert!(results
+            .iter()
+            .any(|result| matches!(result, Ok(validator) if *validator == IDS[&LEADER])));
+    }
+
+    #[tokio::test]
+    async fn test_propagate_skips_unhealthy_peers() {
+        let server = MockServer::start().await;
+        let channel_context = GANACHE_CONFIG
+            .find_chain_of(DUMMY_CAMPAIGN.channel.token)
+            .expect("Should find Dummy campaign token in config")
+            .with_channel(DUMMY_CAMPAIGN.channel);
+
+        // the health of the _Who am I_ Sentry is never checked
+        Mock::given(method("GET"))
+            .and(path("/leader/health"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/follower/health"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        for (prefix, expected) in [("leader", 1), ("follower", 0)] {
+            Mock::given(method("POST"))
+                .and(path(format!(
+                    "/{}/v5/channel/{}/validator-messages",
+                    prefix,
+                    DUMMY_CAMPAIGN.channel.id()
+                )))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(&SuccessResponse { success: true }),
+                )
+                .expect(expected)
+                .mount(&server)
+                .await;
+        }
+
+        let validator = |prefix: &str, validator: &Address| Validator {
+            url: ApiUrl::from_str(&format!("{}/{}", server.uri(), prefix)).expect("Should parse"),
+            token: DUMMY_AUTH[validator].clone(),
+        };
+        let propagate_to = [(
+            channel_context.chain.chain_id,
+            [
+                (IDS[&LEADER], validator("leader", &LEADER)),
+                (IDS[&FOLLOWER], validator("follower", &FOLLOWER)),
+            ]
+            .into_iter()
+            .collect(),
+        )]
+        .into_iter()
+        .collect();
+
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let heartbeat = MessageTypes::Heartbeat(Heartbeat::new(
+            "signature".to_string(),
+            "state root".to_string(),
+        ));
+
+        let results = setup_dummy_sentry(IDS[&LEADER], GANACHE_CONFIG.clone(), sentry_url)
+            .with_propagate(propagate_to)
+            .expect("Should set propagation validators")
+            .propagate(&channel_context, &[heartbeat])
+            .await
+            .expect("Should propagate");
+
+        assert!(results.iter().any(|result| matches!(
+            result,
+            Err((validator, Error::ValidatorUnhealthy { .. })) if *validator == IDS[&FOLLOWER]
+        )));
+        assert!(results
+            .iter()
+            .any(|result| matches!(result, Ok(validator) if *validator == IDS[&LEADER])));
+    }
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let retry_policy = primitives::config::RetryPolicy {
+            attempts: 4.try_into().unwrap(),
+            backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(1_000),
+        };
+
+        assert_eq!(Duration::from_millis(100), retry_policy.backoff_for(1));
+        assert_eq!(Duration::from_millis(200), retry_policy.backoff_for(2));
+        assert_eq!(Duration::from_millis(400), retry_policy.backoff_for(3));
+        // capped at the max backoff
+        assert_eq!(Duration::from_millis(1_000), retry_policy.backoff_for(5));
+        // the multiplier overflows
+        assert_eq!(
+            Duration::from_millis(1_000),
+            retry_policy.backoff_for(u8::MAX)
+        );
+    }
+
     #[tokio::test]
     async fn test_collecting_and_channels_and_campaigns() {
         let server = MockServer::start().await;
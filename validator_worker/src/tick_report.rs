@@ -0,0 +1,214 @@
+//! Machine-readable reports of the [`Worker`](crate::Worker) ticks.
+//!
+//! When a [`TickReporter`] is set using
+//! [`Worker::with_tick_report_path`](crate::Worker::with_tick_report_path),
+//! a [`TickReport`] with the processed Channels, the produced validator messages
+//! and the errors is written as a JSON file for every tick.
+//! This allows full-stack tests (e.g. in CI) to assert on what the worker did in a given tick.
+//!
+//! The messages are recorded in the scope of each Channel tick, outside of it recording is a no-op.
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use primitives::{validator::MessageTypes, ChannelId, ValidatorId};
+
+use crate::debug::MessageDump;
+
+tokio::task_local! {
+    static TICK_MESSAGES: Mutex<Vec<MessageDump>>;
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Tick report file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Tick report serialization: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// The report of a single [`Worker`](crate::Worker) tick of all Channels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TickReport {
+    /// The validator which ran the tick.
+    pub validator: ValidatorId,
+    /// The number of the tick, starting from `1`.
+    pub tick: u64,
+    pub started: DateTime<Utc>,
+    pub finished: DateTime<Utc>,
+    pub channels: Vec<ChannelReport>,
+    /// The error which prevented ticking the Channels, if any.
+    pub error: Option<String>,
+}
+
+impl TickReport {
+    /// Returns the report of the given Channel, if it was processed in this tick.
+    pub fn channel(&self, channel: ChannelId) -> Option<&ChannelReport> {
+        self.channels
+            .iter()
+            .find(|report| report.channel == channel)
+    }
+}
+
+/// The messages produced for a single Channel in the tick.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelReport {
+    pub channel: ChannelId,
+    /// The propagated messages, in the order they were produced.
+    pub messages: Vec<MessageDump>,
+    /// The error of the Channel tick, if it failed.
+    pub error: Option<String>,
+    /// The warnings of the read-only monitoring of a guardian Channel,
+    /// see [`guardian`](crate::guardian).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Writes a [`TickReport`] file for every tick in the given directory.
+///
+/// Clones share the tick counter.
+#[derive(Debug, Clone)]
+pub struct TickReporter {
+    dir: PathBuf,
+    ticks: Arc<AtomicU64>,
+}
+
+impl TickReporter {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            ticks: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the number of the next tick, starting from `1`.
+    pub fn next_tick(&self) -> u64 {
+        self.ticks.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// The path of the [`TickReport`] file: `{validator}-{tick}.json`
+    pub fn report_path(&self, validator: ValidatorId, tick: u64) -> PathBuf {
+        self.dir.join(format!("{}-{}.json", validator, tick))
+    }
+
+    /// Writes the [`TickReport`] and returns the path of the file.
+    ///
+    /// The report is first written to a temporary file which is then renamed,
+    /// so readers never observe a partially written report.
+    pub fn write(&self, report: &TickReport) -> Result<PathBuf, Error> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        let path = self.report_path(report.validator, report.tick);
+        let tmp_path = path.with_extension("json.tmp");
+
+        std::fs::write(&tmp_path, serde_json::to_vec_pretty(report)?)?;
+        std::fs::rename(&tmp_path, &path)?;
+
+        Ok(path)
+    }
+
+    /// Reads the [`TickReport`] of the given validator and tick.
+    pub fn read(&self, validator: ValidatorId, tick: u64) -> Result<TickReport, Error> {
+        let report = std::fs::read(self.report_path(validator, tick))?;
+
+        Ok(serde_json::from_slice(&report)?)
+    }
+}
+
+/// Runs the Channel tick in a scope which records the propagated messages
+/// and returns them alongside the result of the tick.
+pub async fn scope<F: Future>(tick: F) -> (F::Output, Vec<MessageDump>) {
+    TICK_MESSAGES
+        .scope(Mutex::new(vec![]), async {
+            let output = tick.await;
+            let messages = TICK_MESSAGES.with(|messages| {
+                std::mem::take(&mut *messages.lock().expect("Should lock the tick messages"))
+            });
+
+            (output, messages)
+        })
+        .await
+}
+
+/// Records the propagated messages in the current Channel tick scope, if any.
+pub fn messages(messages: &[MessageTypes]) {
+    let _ = TICK_MESSAGES.try_with(|recorded| {
+        recorded
+            .lock()
+            .expect("Should lock the tick messages")
+            .extend(messages.iter().map(MessageDump::from))
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use primitives::{
+        test_util::{DUMMY_CAMPAIGN, IDS, LEADER},
+        validator::Heartbeat,
+    };
+
+    #[tokio::test]
+    async fn records_messages_in_scope_and_writes_report() {
+        let heartbeat = MessageTypes::Heartbeat(Heartbeat::new(
+            "signature".to_string(),
+            "state root".to_string(),
+        ));
+
+        // outside of a scope recording is a no-op
+        messages(std::slice::from_ref(&heartbeat));
+
+        let ((), recorded) = scope(async { messages(std::slice::from_ref(&heartbeat)) }).await;
+        assert_eq!(1, recorded.len());
+        assert_eq!("state root", recorded[0].state_root);
+
+        let dir = tempfile::tempdir().expect("Should create temp dir");
+        let reporter = TickReporter::new(dir.path().join("reports"));
+        let tick = reporter.next_tick();
+        assert_eq!(1, tick);
+        assert_eq!(2, reporter.clone().next_tick(), "Clones share the counter");
+
+        let report = TickReport {
+            validator: IDS[&LEADER],
+            tick,
+            started: Utc::now(),
+            finished: Utc::now(),
+            channels: vec![ChannelReport {
+                channel: DUMMY_CAMPAIGN.channel.id(),
+                messages: recorded,
+                error: None,
+                warnings: vec![],
+            }],
+            error: None,
+        };
+
+        let path = reporter.write(&report).expect("Should write report");
+        assert_eq!(reporter.report_path(IDS[&LEADER], 1), path);
+        assert!(
+            !path.with_extension("json.tmp").exists(),
+            "Temporary file should be renamed"
+        );
+
+        let read = reporter.read(IDS[&LEADER], 1).expect("Should read report");
+        let channel_report = read
+            .channel(DUMMY_CAMPAIGN.channel.id())
+            .expect("Should have the Channel report");
+        assert_eq!(1, channel_report.messages.len());
+        assert_eq!("state root", channel_report.messages[0].state_root);
+    }
+}
@@ -0,0 +1,579 @@
+//! One-shot verification of the state of a [`Channel`], see the `verify` subcommand of the worker.
+//!
+//! The last approved [`NewState`](primitives::validator::NewState) of the Channel is checked against:
+//!
+//! - its own balances, by recomputing the state root;
+//! - the [`ApproveState`](primitives::validator::ApproveState) of the Follower;
+//! - the current accounting of the Channel, as the approved balances can never exceed it.
+//!
+//! Sentry doesn't expose the raw events of a Channel, so when the events of its Campaigns
+//! are provided (see [`CampaignEvents`]) the expected accounting is recomputed from them with
+//! [`recompute_balances`] and checked against the current accounting of the Channel.
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+};
+
+use futures::{StreamExt, TryStreamExt};
+
+use adapter::prelude::*;
+use primitives::{
+    balances::CheckedState,
+    sentry::{AccountingResponse, Event},
+    Address, Balances, CampaignId, ChainOf, Channel, ChannelId, UnifiedNum,
+};
+use thiserror::Error;
+
+use crate::{
+    recompute::{recompute_balances, Error as RecomputeError},
+    sentry_interface::{Error as SentryApiError, SentryApi},
+    GetStateRoot, GetStateRootError,
+};
+
+/// The events of each Campaign of the Channel, from which the expected accounting is recomputed,
+/// e.g. loaded from the JSON file passed to `verify --events`.
+pub type CampaignEvents = HashMap<CampaignId, Vec<Event>>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Channel {0} was not found in Sentry")]
+    ChannelNotFound(ChannelId),
+    #[error("Channel token is not whitelisted")]
+    ChannelTokenNotWhitelisted,
+    #[error("Campaign {0} was not found in the Channel")]
+    CampaignNotFound(CampaignId),
+    #[error("Recomputing the balances: {0}")]
+    Recompute(#[from] RecomputeError),
+    #[error(transparent)]
+    SentryApi(#[from] SentryApiError),
+    #[error(transparent)]
+    StateRoot(#[from] GetStateRootError),
+}
+
+/// The side of the [`Balances`] of an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Earner,
+    Spender,
+}
+
+/// A mismatch found by [`verify_channel`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discrepancy {
+    /// The approved `NewState` balances are invalid, i.e. the earners and spenders sums differ.
+    InvalidBalances(String),
+    /// The `NewState` state root differs from the one recomputed from its balances.
+    StateRoot { expected: String, actual: String },
+    /// The Follower approved a different state root than the `NewState` one.
+    ApprovedStateRoot { expected: String, actual: String },
+    /// The approved balance of the address exceeds its accounting.
+    Balance {
+        side: Side,
+        address: Address,
+        approved: UnifiedNum,
+        accounting: UnifiedNum,
+    },
+    /// The accounting balance of the address differs from the one recomputed from the events.
+    Recomputed {
+        side: Side,
+        address: Address,
+        recomputed: UnifiedNum,
+        accounting: UnifiedNum,
+    },
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Discrepancy::InvalidBalances(error) => {
+                write!(f, "Invalid approved NewState balances: {error}")
+            }
+            Discrepancy::StateRoot { expected, actual } => write!(
+                f,
+                "NewState state root: {actual}, recomputed from its balances: {expected}"
+            ),
+            Discrepancy::ApprovedStateRoot { expected, actual } => write!(
+                f,
+                "ApproveState state root: {actual}, NewState state root: {expected}"
+            ),
+            Discrepancy::Balance {
+                side,
+                address,
+                approved,
+                accounting,
+            } => write!(
+                f,
+                "{side:?} {address} approved balance: {approved} exceeds its accounting: {accounting}"
+            ),
+            Discrepancy::Recomputed {
+                side,
+                address,
+                recomputed,
+                accounting,
+            } => write!(
+                f,
+                "{side:?} {address} accounting balance: {accounting}, recomputed from the events: {recomputed}"
+            ),
+        }
+    }
+}
+
+/// The verified state of a [`Channel`].
+#[derive(Debug, Clone)]
+pub struct Verification {
+    pub channel: ChannelId,
+    /// The state root of the last approved `NewState`, if any.
+    pub state_root: Option<String>,
+    pub accounting: AccountingResponse<CheckedState>,
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+impl Verification {
+    pub fn is_valid(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
+/// Fetches the last approved state and the accounting of the [`Channel`]
+/// and returns the [`Discrepancy`]s between them.
+///
+/// When the `events` are provided, the accounting is also checked against
+/// the balances recomputed from them, see [`recompute_channel`].
+pub async fn verify_channel<C: Unlocked + 'static, P>(
+    sentry: &SentryApi<C, P>,
+    channel: ChannelId,
+    events: Option<&CampaignEvents>,
+) -> Result<Verification, Error> {
+    let channel_context = find_channel(sentry, channel).await?;
+    let token_precision = channel_context.token.precision.get();
+
+    let last_approved = sentry.get_last_approved(channel).await?.last_approved;
+    let accounting = sentry.get_accounting(&channel_context).await?;
+
+    let mut discrepancies = vec![];
+
+    let new_state = last_approved
+        .as_ref()
+        .and_then(|last_approved| last_approved.new_state.as_ref());
+    let state_root = new_state.map(|new_state| new_state.msg.state_root.clone());
+
+    if let Some(new_state) = new_state {
+        match new_state.msg.balances.clone().check() {
+            Ok(approved) => {
+                let expected = approved.encode(channel, token_precision)?;
+                if expected != new_state.msg.state_root {
+                    discrepancies.push(Discrepancy::StateRoot {
+                        expected,
+                        actual: new_state.msg.state_root.clone(),
+                    });
+                }
+
+                discrepancies.extend(balance_discrepancies(&approved, &accounting.balances));
+            }
+            Err(error) => discrepancies.push(Discrepancy::InvalidBalances(error.to_string())),
+        }
+
+        let approve_state = last_approved
+            .as_ref()
+            .and_then(|last_approved| last_approved.approve_state.as_ref());
+        if let Some(approve_state) = approve_state {
+            if approve_state.msg.state_root != new_state.msg.state_root {
+                discrepancies.push(Discrepancy::ApprovedStateRoot {
+                    expected: new_state.msg.state_root.clone(),
+                    actual: approve_state.msg.state_root.clone(),
+                });
+            }
+        }
+    }
+
+    if let Some(events) = events {
+        let recomputed = recompute_channel(sentry, channel, events).await?;
+
+        discrepancies.extend(recomputed_discrepancies(&recomputed, &accounting.balances));
+    }
+
+    Ok(Verification {
+        channel,
+        state_root,
+        accounting,
+        discrepancies,
+    })
+}
+
+async fn find_channel<C: Unlocked + 'static, P>(
+    sentry: &SentryApi<C, P>,
+    channel: ChannelId,
+) -> Result<ChainOf<Channel>, Error> {
+    let found = sentry
+        .channels_stream()
+        .try_filter(|found| futures::future::ready(found.id() == channel))
+        .boxed_local()
+        .try_next()
+        .await?
+        .ok_or(Error::ChannelNotFound(channel))?;
+
+    sentry
+        .config
+        .find_chain_of(found.token)
+        .map(|chain_of| chain_of.with_channel(found))
+        .ok_or(Error::ChannelTokenNotWhitelisted)
+}
+
+/// Recomputes the [`Balances`] of the [`Channel`] from the `events` of its Campaigns
+/// with [`recompute_balances`].
+///
+/// Every Campaign of the `events` should be part of the Channel.
+pub async fn recompute_channel<C: Unlocked + 'static, P>(
+    sentry: &SentryApi<C, P>,
+    channel: ChannelId,
+    events: &CampaignEvents,
+) -> Result<Balances<CheckedState>, Error> {
+    let campaign_ids = events.keys().copied().collect::<Vec<_>>();
+    let campaigns = sentry
+        .get_campaigns_batch(&campaign_ids)
+        .await?
+        .into_iter()
+        .filter(|campaign| campaign.channel.id() == channel)
+        .map(|campaign| (campaign.id, campaign))
+        .collect::<HashMap<_, _>>();
+
+    let mut balances = Balances::<CheckedState>::new();
+    for (campaign_id, events) in events {
+        let campaign = campaigns
+            .get(campaign_id)
+            .ok_or(Error::CampaignNotFound(*campaign_id))?;
+
+        // all the payouts of a Campaign are spent by its creator
+        let recomputed = recompute_balances(&sentry.config, campaign, events)?;
+        for (earner, amount) in recomputed.earners.iter() {
+            balances
+                .spend(campaign.creator, *earner, *amount)
+                .map_err(RecomputeError::from)?;
+        }
+    }
+
+    Ok(balances)
+}
+
+pub(crate) fn balance_discrepancies(
+    approved: &Balances<CheckedState>,
+    accounting: &Balances<CheckedState>,
+) -> Vec<Discrepancy> {
+    let earners = approved
+        .earners
+        .iter()
+        .map(|(address, amount)| (Side::Earner, address, amount, &accounting.earners));
+    let spenders = approved
+        .spenders
+        .iter()
+        .map(|(address, amount)| (Side::Spender, address, amount, &accounting.spenders));
+
+    earners
+        .chain(spenders)
+        .filter_map(|(side, address, approved, accounting)| {
+            let accounting = accounting.get(address).copied().unwrap_or_default();
+
+            (*approved > accounting).then_some(Discrepancy::Balance {
+                side,
+                address: *address,
+                approved: *approved,
+                accounting,
+            })
+        })
+        .collect()
+}
+
+fn recomputed_discrepancies(
+    recomputed: &Balances<CheckedState>,
+    accounting: &Balances<CheckedState>,
+) -> Vec<Discrepancy> {
+    [
+        (Side::Earner, &recomputed.earners, &accounting.earners),
+        (Side::Spender, &recomputed.spenders, &accounting.spenders),
+    ]
+    .into_iter()
+    .flat_map(|(side, recomputed, accounting)| {
+        let addresses = recomputed
+            .iter()
+            .chain(accounting.iter())
+            .map(|(address, _)| *address)
+            .collect::<BTreeSet<_>>();
+
+        addresses.into_iter().filter_map(move |address| {
+            let recomputed = recomputed.get(&address).copied().unwrap_or_default();
+            let accounting = accounting.get(&address).copied().unwrap_or_default();
+
+            (recomputed != accounting).then_some(Discrepancy::Recomputed {
+                side,
+                address,
+                recomputed,
+                accounting,
+            })
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use chrono::Utc;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use adapter::dummy::{Adapter, Dummy, Options};
+    use primitives::{
+        balances::UncheckedState,
+        config::GANACHE_CONFIG,
+        sentry::{
+            campaign_get::{CampaignResponse, CampaignsBatchResponse},
+            channel_list::ChannelListResponse,
+            message::{Message, MessageResponse},
+            LastApproved, LastApprovedResponse, Pagination,
+        },
+        test_util::{
+            discard_logger, CREATOR, DUMMY_AUTH, DUMMY_CAMPAIGN, DUMMY_IPFS, FOLLOWER, IDS, LEADER,
+            PUBLISHER,
+        },
+        util::ApiUrl,
+        validator::{ApproveState, NewState},
+    };
+
+    use super::*;
+
+    fn balances(amount: u64) -> Balances<CheckedState> {
+        let mut balances = Balances::new();
+        balances
+            .spend(*CREATOR, *PUBLISHER, UnifiedNum::from_u64(amount))
+            .expect("Should spend");
+
+        balances
+    }
+
+    async fn setup_sentry(
+        approved: Balances<CheckedState>,
+        accounting: Balances<CheckedState>,
+    ) -> (MockServer, SentryApi<Dummy, ()>, String) {
+        let server = MockServer::start().await;
+        let channel = DUMMY_CAMPAIGN.channel;
+        let precision = GANACHE_CONFIG
+            .find_chain_of(channel.token)
+            .expect("Should find the token")
+            .token
+            .precision
+            .get();
+        let state_root = approved
+            .encode(channel.id(), precision)
+            .expect("Should encode the state root");
+
+        let channels = ChannelListResponse {
+            channels: vec![channel],
+            pagination: Pagination {
+                total_pages: 1,
+                page: 0,
+            },
+        };
+        Mock::given(method("GET"))
+            .and(path("/v5/channel/list"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&channels))
+            .mount(&server)
+            .await;
+
+        let last_approved = LastApprovedResponse::<UncheckedState> {
+            last_approved: Some(LastApproved {
+                new_state: Some(MessageResponse {
+                    from: IDS[&LEADER],
+                    received: Utc::now(),
+                    msg: Message::new(NewState {
+                        state_root: state_root.clone(),
+                        signature: "signature".to_string(),
+                        balances: approved.into_unchecked(),
+                    }),
+                }),
+                approve_state: Some(MessageResponse {
+                    from: IDS[&FOLLOWER],
+                    received: Utc::now(),
+                    msg: Message::new(ApproveState {
+                        state_root: state_root.clone(),
+                        signature: "signature".to_string(),
+                        is_healthy: true,
+                    }),
+                }),
+            }),
+            heartbeats: None,
+        };
+        Mock::given(method("GET"))
+            .and(path(format!("/v5/channel/{}/last-approved", channel.id())))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&last_approved))
+            .mount(&server)
+            .await;
+
+        let accounting = AccountingResponse {
+            balances: accounting,
+            frozen: false,
+        };
+        Mock::given(method("GET"))
+            .and(path(format!("/v5/channel/{}/accounting", channel.id())))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&accounting))
+            .mount(&server)
+            .await;
+
+        let campaigns = CampaignsBatchResponse {
+            campaigns: vec![CampaignResponse {
+                campaign: DUMMY_CAMPAIGN.clone(),
+                utilization: None,
+            }],
+        };
+        Mock::given(method("POST"))
+            .and(path("/v5/campaign/batch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&campaigns))
+            .mount(&server)
+            .await;
+
+        let adapter = Adapter::with_unlocked(Dummy::init(Options {
+            dummy_identity: IDS[&LEADER],
+            dummy_auth_tokens: DUMMY_AUTH.clone(),
+            dummy_chains: GANACHE_CONFIG.chains.values().cloned().collect(),
+            failure_injection: None,
+        }));
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = SentryApi::new(
+            adapter,
+            discard_logger(),
+            GANACHE_CONFIG.clone(),
+            sentry_url,
+        )
+        .expect("Should build sentry");
+
+        (server, sentry, state_root)
+    }
+
+    #[tokio::test]
+    async fn verifies_the_approved_state_against_the_accounting() {
+        let channel = DUMMY_CAMPAIGN.channel.id();
+
+        // the accounting has grown since the approved state
+        {
+            let (_server, sentry, state_root) = setup_sentry(balances(100), balances(150)).await;
+
+            let verification = verify_channel(&sentry, channel, None)
+                .await
+                .expect("Should verify the Channel");
+
+            assert!(verification.is_valid(), "{:?}", verification.discrepancies);
+            assert_eq!(Some(state_root), verification.state_root);
+        }
+
+        // the approved state exceeds the accounting
+        {
+            let (_server, sentry, _state_root) = setup_sentry(balances(100), balances(50)).await;
+
+            let verification = verify_channel(&sentry, channel, None)
+                .await
+                .expect("Should verify the Channel");
+
+            assert_eq!(
+                vec![
+                    Discrepancy::Balance {
+                        side: Side::Earner,
+                        address: *PUBLISHER,
+                        approved: UnifiedNum::from_u64(100),
+                        accounting: UnifiedNum::from_u64(50),
+                    },
+                    Discrepancy::Balance {
+                        side: Side::Spender,
+                        address: *CREATOR,
+                        approved: UnifiedNum::from_u64(100),
+                        accounting: UnifiedNum::from_u64(50),
+                    },
+                ],
+                verification.discrepancies
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn verifies_the_accounting_against_the_recomputed_events() {
+        let channel = DUMMY_CAMPAIGN.channel.id();
+        let impression = Event::Impression {
+            publisher: *PUBLISHER,
+            ad_unit: DUMMY_IPFS[0],
+            ad_slot: DUMMY_IPFS[2],
+            referrer: None,
+        };
+        let events = CampaignEvents::from([(DUMMY_CAMPAIGN.id, vec![impression.clone(); 3])]);
+        let recomputed = recompute_balances(
+            &GANACHE_CONFIG,
+            &DUMMY_CAMPAIGN,
+            &events[&DUMMY_CAMPAIGN.id],
+        )
+        .expect("Should recompute the balances");
+
+        // the accounting matches the events
+        {
+            let (_server, sentry, _state_root) =
+                setup_sentry(balances(1), recomputed.clone()).await;
+
+            let verification = verify_channel(&sentry, channel, Some(&events))
+                .await
+                .expect("Should verify the Channel");
+
+            assert!(verification.is_valid(), "{:?}", verification.discrepancies);
+        }
+
+        // the accounting is missing the payout of an event
+        {
+            let (_server, sentry, _state_root) = setup_sentry(balances(1), recomputed).await;
+            let events = CampaignEvents::from([(DUMMY_CAMPAIGN.id, vec![impression; 4])]);
+
+            let verification = verify_channel(&sentry, channel, Some(&events))
+                .await
+                .expect("Should verify the Channel");
+
+            assert!(
+                verification
+                    .discrepancies
+                    .iter()
+                    .any(|discrepancy| matches!(
+                        discrepancy,
+                        Discrepancy::Recomputed { side: Side::Earner, address, .. } if *address == *PUBLISHER
+                    )),
+                "{:?}",
+                verification.discrepancies
+            );
+        }
+
+        // the Campaign is not part of the Channel
+        {
+            let (_server, sentry, _state_root) = setup_sentry(balances(1), Balances::new()).await;
+            let unknown_campaign = CampaignId::new();
+            let events = CampaignEvents::from([(unknown_campaign, vec![])]);
+
+            let result = verify_channel(&sentry, channel, Some(&events)).await;
+
+            assert!(
+                matches!(result, Err(Error::CampaignNotFound(id)) if id == unknown_campaign),
+                "{result:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn channel_not_found() {
+        let (_server, sentry, _state_root) = setup_sentry(balances(100), balances(100)).await;
+        let channel = primitives::Channel {
+            nonce: 1_u64.into(),
+            ..DUMMY_CAMPAIGN.channel
+        };
+
+        let result = verify_channel(&sentry, channel.id(), None).await;
+
+        assert!(
+            matches!(result, Err(Error::ChannelNotFound(id)) if id == channel.id()),
+            "{result:?}"
+        );
+    }
+}
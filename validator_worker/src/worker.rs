@@ -1,14 +1,21 @@
-use crate::{channel::channel_tick, SentryApi};
+use crate::{
+    channel::channel_tick,
+    guardian,
+    tick_report::{self, ChannelReport, TickReport, TickReporter},
+    SentryApi,
+};
 use adapter::{prelude::*, Adapter};
+use chrono::Utc;
 use primitives::Config;
 use slog::{error, info, Logger};
-use std::error::Error;
+use std::{error::Error, path::PathBuf};
 
 use futures::{
     future::{join, join_all},
     TryFutureExt,
 };
 use tokio::{runtime::Runtime, time::sleep};
+use tracing::{info_span, Instrument};
 
 #[derive(Debug, Clone)]
 pub struct Worker<C: Unlocked> {
@@ -19,6 +26,9 @@ pub struct Worker<C: Unlocked> {
     /// The unlocked Adapter
     pub adapter: Adapter<C, UnlockedState>,
     pub logger: Logger,
+    /// When set, a [`TickReport`] is written for every tick.
+    /// Use [`Worker::with_tick_report_path`] to set it.
+    pub tick_report: Option<TickReporter>,
 }
 
 impl<C: Unlocked + 'static> Worker<C> {
@@ -29,24 +39,46 @@ impl<C: Unlocked + 'static> Worker<C> {
             adapter: sentry.adapter.clone(),
             logger: sentry.logger.clone(),
             sentry,
+            tick_report: None,
         }
     }
 
+    /// Writes a [`TickReport`] JSON file for every tick in the given directory.
+    pub fn with_tick_report_path(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.tick_report = Some(TickReporter::new(dir));
+        self
+    }
+
     /// Runs the validator in a single tick or it runs infinitely.
     /// Uses [`tokio::runtime::Runtime`]
     pub fn run(self, is_single_tick: bool) -> Result<(), Box<dyn Error>> {
+        Self::run_all(vec![self], is_single_tick)
+    }
+
+    /// Runs multiple validators (e.g. with different identities) in a single tick or infinitely.
+    ///
+    /// All [`Worker`]s are ran concurrently in the same [`tokio::runtime::Runtime`].
+    pub fn run_all(workers: Vec<Self>, is_single_tick: bool) -> Result<(), Box<dyn Error>> {
         // Create the runtime
         let rt = Runtime::new()?;
 
-        if is_single_tick {
-            rt.block_on(self.all_channels_tick());
-        } else {
-            rt.block_on(self.infinite());
-        }
+        rt.block_on(Self::run_concurrently(&workers, is_single_tick));
 
         Ok(())
     }
 
+    /// Runs the passed [`Worker`]s concurrently either for a single tick or infinitely.
+    pub async fn run_concurrently(workers: &[Self], is_single_tick: bool) {
+        join_all(workers.iter().map(|worker| async move {
+            if is_single_tick {
+                worker.all_channels_tick().await
+            } else {
+                worker.infinite().await
+            }
+        }))
+        .await;
+    }
+
     pub async fn infinite(&self) {
         loop {
             let wait_time_future = sleep(self.config.worker.wait_time);
@@ -56,13 +88,40 @@ impl<C: Unlocked + 'static> Worker<C> {
     }
 
     pub async fn all_channels_tick(&self) {
+        let started = Utc::now();
+        let (channels, error) = self.tick_channels().await;
+
+        if let Some(reporter) = &self.tick_report {
+            let report = TickReport {
+                validator: self.adapter.whoami(),
+                tick: reporter.next_tick(),
+                started,
+                finished: Utc::now(),
+                channels,
+                error,
+            };
+
+            match reporter.write(&report) {
+                Ok(path) => {
+                    info!(&self.logger, "Tick report written"; "tick" => report.tick, "path" => %path.display())
+                }
+                Err(err) => {
+                    error!(&self.logger, "Failed to write tick report: {err}"; "tick" => report.tick, "main" => "all_channels_tick")
+                }
+            }
+        }
+    }
+
+    /// Ticks all Channels and returns their reports
+    /// or the error which prevented ticking them.
+    async fn tick_channels(&self) -> (Vec<ChannelReport>, Option<String>) {
         let logger = &self.logger;
 
         let (channels_context, validators) = match self.sentry.collect_channels().await {
             Ok(res) => res,
             Err(err) => {
                 error!(logger, "Error collecting all channels for tick"; "collect_channels" => ?err, "main" => "all_channels_tick");
-                return;
+                return (vec![], Some(err.to_string()));
             }
         };
         let channels_size = channels_context.len();
@@ -71,26 +130,232 @@ impl<C: Unlocked + 'static> Worker<C> {
             Ok(sentry) => sentry,
             Err(err) => {
                 error!(logger, "Failed to set propagation validators: {err}"; "err" => ?err, "main" => "all_channels_tick");
-                return;
+                return (vec![], Some(err.to_string()));
             }
         };
 
         let tick_results = join_all(channels_context.into_iter().map(|channel_context| {
             let channel = channel_context.context;
 
-            channel_tick(&sentry_with_propagate, &self.config, channel_context)
-                .map_err(move |err| (channel, err))
+            tick_report::scope(
+                channel_tick(&sentry_with_propagate, &self.config, channel_context)
+                    .map_err(move |err| (channel, err)),
+            )
         }))
         .await;
 
-        for (channel, channel_err) in tick_results.into_iter().filter_map(Result::err) {
-            error!(logger, "Error processing Channel"; "channel" => ?channel, "error" => ?channel_err, "main" => "all_channels_tick");
-        }
+        let channel_reports = tick_results
+            .into_iter()
+            .map(|(result, messages)| match result {
+                Ok((channel, _status)) => ChannelReport {
+                    channel,
+                    messages,
+                    error: None,
+                    warnings: vec![],
+                },
+                Err((channel, channel_err)) => {
+                    error!(logger, "Error processing Channel"; "channel" => ?channel, "error" => ?channel_err, "main" => "all_channels_tick");
+
+                    ChannelReport {
+                        channel: channel.id(),
+                        messages,
+                        error: Some(channel_err.to_string()),
+                        warnings: vec![],
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
 
         info!(logger, "Processed {} channels", channels_size);
 
-        if channels_size >= self.config.worker.max_channels as usize {
+        let guardian_reports = self.monitor_guardian_channels().await;
+
+        // the monitored guardian Channels count toward the limit as well
+        if channels_size + guardian_reports.len() >= self.config.worker.max_channels as usize {
             error!(logger, "WARNING: channel limit cfg.MAX_CHANNELS={} reached", &self.config.worker.max_channels; "main" => "all_channels_tick");
         }
+
+        (
+            channel_reports
+                .into_iter()
+                .chain(guardian_reports)
+                .collect(),
+            None,
+        )
+    }
+
+    /// Monitors the Channels of which the validator is only the guardian,
+    /// when [`Config.worker.guardian_monitoring`](primitives::config::ValidatorWorkerConfig::guardian_monitoring) is set.
+    ///
+    /// No validator messages are signed or propagated for these Channels.
+    async fn monitor_guardian_channels(&self) -> Vec<ChannelReport> {
+        let logger = &self.logger;
+        let monitoring = match &self.config.worker.guardian_monitoring {
+            Some(monitoring) => monitoring,
+            None => return vec![],
+        };
+
+        let channels_context = match self.sentry.collect_guardian_channels().await {
+            Ok(channels) => channels,
+            Err(err) => {
+                error!(logger, "Error collecting the guardian channels"; "collect_guardian_channels" => ?err, "main" => "all_channels_tick");
+                return vec![];
+            }
+        };
+
+        let reports = join_all(channels_context.iter().map(|channel_context| async move {
+            let channel = channel_context.context.id();
+
+            let span = info_span!("guardian_tick", channel = %channel);
+
+            match guardian::tick(&self.sentry, monitoring, channel_context)
+                .instrument(span)
+                .await
+            {
+                Ok(status) => ChannelReport {
+                    channel,
+                    messages: vec![],
+                    error: None,
+                    warnings: status.warnings.iter().map(ToString::to_string).collect(),
+                },
+                Err(err) => {
+                    error!(logger, "Error monitoring guardian Channel"; "channel" => %channel, "error" => ?err, "main" => "all_channels_tick");
+
+                    ChannelReport {
+                        channel,
+                        messages: vec![],
+                        error: Some(err.to_string()),
+                        warnings: vec![],
+                    }
+                }
+            }
+        }))
+        .await;
+
+        info!(logger, "Monitored {} guardian channels", reports.len());
+
+        reports
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{str::FromStr, time::Duration};
+
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use adapter::dummy::{Dummy, Options};
+    use primitives::{
+        balances::{CheckedState, UncheckedState},
+        config::{GuardianMonitoring, GANACHE_CONFIG},
+        sentry::{
+            channel_list::ChannelListResponse, AccountingResponse, LastApprovedResponse, Pagination,
+        },
+        test_util::{discard_logger, DUMMY_AUTH, DUMMY_CAMPAIGN, FOLLOWER, GUARDIAN, IDS, LEADER},
+        util::ApiUrl,
+        Balances,
+    };
+
+    use crate::guardian::Warning;
+
+    use super::*;
+
+    fn guardian_worker(config: Config, server: &MockServer) -> Worker<Dummy> {
+        let adapter = Adapter::with_unlocked(Dummy::init(Options {
+            dummy_identity: IDS[&GUARDIAN],
+            dummy_auth_tokens: DUMMY_AUTH.clone(),
+            dummy_chains: config.chains.values().cloned().collect(),
+            failure_injection: None,
+        }));
+        let sentry_url = ApiUrl::from_str(&server.uri()).expect("Should parse");
+        let sentry = SentryApi::new(adapter, discard_logger(), config, sentry_url)
+            .expect("Should build sentry");
+
+        Worker::from_sentry(sentry)
+    }
+
+    #[tokio::test]
+    async fn monitors_the_guardian_channels() {
+        let server = MockServer::start().await;
+        let channel = DUMMY_CAMPAIGN.channel;
+
+        let channels = ChannelListResponse {
+            channels: vec![channel],
+            pagination: Pagination {
+                page: 0,
+                total_pages: 1,
+            },
+        };
+        Mock::given(method("GET"))
+            .and(path("/v5/channel/list"))
+            .and(query_param("guardian", GUARDIAN.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&channels))
+            .mount(&server)
+            .await;
+
+        let last_approved = LastApprovedResponse::<UncheckedState> {
+            last_approved: None,
+            heartbeats: None,
+        };
+        Mock::given(method("GET"))
+            .and(path(format!("/v5/channel/{}/last-approved", channel.id())))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&last_approved))
+            .mount(&server)
+            .await;
+
+        let accounting = AccountingResponse {
+            balances: Balances::<CheckedState>::new(),
+            frozen: false,
+        };
+        Mock::given(method("GET"))
+            .and(path(format!("/v5/channel/{}/accounting", channel.id())))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&accounting))
+            .mount(&server)
+            .await;
+
+        // without the monitoring configured the guardian Channels are not even fetched
+        {
+            let mut config = GANACHE_CONFIG.clone();
+            config.worker.guardian_monitoring = None;
+            let worker = guardian_worker(config, &server);
+
+            assert!(worker.monitor_guardian_channels().await.is_empty());
+            assert!(server
+                .received_requests()
+                .await
+                .expect("Should record the requests")
+                .is_empty());
+        }
+
+        let mut config = GANACHE_CONFIG.clone();
+        config.worker.guardian_monitoring = Some(GuardianMonitoring {
+            heartbeat_stale_after: Duration::from_secs(5 * 60),
+            approve_state_stale_after: Duration::from_secs(60 * 60),
+        });
+        let worker = guardian_worker(config, &server);
+
+        let reports = worker.monitor_guardian_channels().await;
+
+        assert_eq!(1, reports.len());
+        let report = &reports[0];
+        assert_eq!(channel.id(), report.channel);
+        assert!(report.messages.is_empty(), "No messages should be created");
+        assert_eq!(None, report.error);
+        assert_eq!(
+            vec![
+                Warning::NoHeartbeat {
+                    validator: IDS[&LEADER],
+                }
+                .to_string(),
+                Warning::NoHeartbeat {
+                    validator: IDS[&FOLLOWER],
+                }
+                .to_string(),
+            ],
+            report.warnings
+        );
     }
 }
@@ -0,0 +1,147 @@
+//! Runs the `verify` subcommand of the worker binary against a mocked Sentry.
+use std::process::{Command, Output};
+
+use chrono::Utc;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use primitives::{
+    balances::{CheckedState, UncheckedState},
+    config::GANACHE_CONFIG,
+    sentry::{
+        channel_list::ChannelListResponse,
+        message::{Message, MessageResponse},
+        AccountingResponse, LastApproved, LastApprovedResponse, Pagination,
+    },
+    test_util::{CREATOR, DUMMY_CAMPAIGN, FOLLOWER, IDS, LEADER, PUBLISHER},
+    validator::{ApproveState, NewState},
+    Balances, UnifiedNum,
+};
+use validator_worker::GetStateRoot;
+
+fn balances(amount: u64) -> Balances<CheckedState> {
+    let mut balances = Balances::new();
+    balances
+        .spend(*CREATOR, *PUBLISHER, UnifiedNum::from_u64(amount))
+        .expect("Should spend");
+
+    balances
+}
+
+async fn mock_sentry(
+    approved: Balances<CheckedState>,
+    accounting: Balances<CheckedState>,
+) -> MockServer {
+    let server = MockServer::start().await;
+    let channel = DUMMY_CAMPAIGN.channel;
+    let precision = GANACHE_CONFIG
+        .find_chain_of(channel.token)
+        .expect("Should find the token")
+        .token
+        .precision
+        .get();
+    let state_root = approved
+        .encode(channel.id(), precision)
+        .expect("Should encode the state root");
+
+    let channels = ChannelListResponse {
+        channels: vec![channel],
+        pagination: Pagination {
+            total_pages: 1,
+            page: 0,
+        },
+    };
+    Mock::given(method("GET"))
+        .and(path("/v5/channel/list"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&channels))
+        .mount(&server)
+        .await;
+
+    let last_approved = LastApprovedResponse::<UncheckedState> {
+        last_approved: Some(LastApproved {
+            new_state: Some(MessageResponse {
+                from: IDS[&LEADER],
+                received: Utc::now(),
+                msg: Message::new(NewState {
+                    state_root: state_root.clone(),
+                    signature: "signature".to_string(),
+                    balances: approved.into_unchecked(),
+                }),
+            }),
+            approve_state: Some(MessageResponse {
+                from: IDS[&FOLLOWER],
+                received: Utc::now(),
+                msg: Message::new(ApproveState {
+                    state_root,
+                    signature: "signature".to_string(),
+                    is_healthy: true,
+                }),
+            }),
+        }),
+        heartbeats: None,
+    };
+    Mock::given(method("GET"))
+        .and(path(format!("/v5/channel/{}/last-approved", channel.id())))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&last_approved))
+        .mount(&server)
+        .await;
+
+    let accounting = AccountingResponse {
+        balances: accounting,
+        frozen: false,
+    };
+    Mock::given(method("GET"))
+        .and(path(format!("/v5/channel/{}/accounting", channel.id())))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&accounting))
+        .mount(&server)
+        .await;
+
+    server
+}
+
+/// Runs `validator_worker --adapter dummy ... verify --channel ...` with the Ganache config.
+async fn run_verify(server: &MockServer) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_validator_worker"));
+    command
+        .env_remove("ENV")
+        .args(["--adapter", "dummy"])
+        .args(["--dummyIdentity", &IDS[&LEADER].to_string()])
+        .args(["--sentryUrl", &server.uri()])
+        .args([
+            "verify",
+            "--channel",
+            &DUMMY_CAMPAIGN.channel.id().to_string(),
+        ]);
+
+    tokio::task::spawn_blocking(move || command.output())
+        .await
+        .expect("Should join the verify command")
+        .expect("Should run the verify command")
+}
+
+#[tokio::test]
+async fn verify_exits_with_non_zero_code_on_mismatch() {
+    // the accounting has grown since the approved state
+    {
+        let server = mock_sentry(balances(100), balances(150)).await;
+
+        let output = run_verify(&server).await;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        assert_eq!(Some(0), output.status.code(), "{stdout}");
+        assert!(stdout.contains("No discrepancies found"), "{stdout}");
+    }
+
+    // the approved state exceeds the accounting
+    {
+        let server = mock_sentry(balances(100), balances(50)).await;
+
+        let output = run_verify(&server).await;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        assert_eq!(Some(1), output.status.code(), "{stdout}");
+        assert!(stdout.contains("Discrepancy: "), "{stdout}");
+    }
+}